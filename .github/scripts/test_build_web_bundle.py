@@ -68,6 +68,14 @@ class BuildWebBundleTest(unittest.TestCase):
 
         self.assertEqual(tags, {"v0.6.0", "v0.6.0-dev.2"})
 
+    def test_release_notes_keep_the_body_and_drop_blank_ones(self) -> None:
+        self.assertEqual(
+            build_web_bundle.release_notes({"body": "\n- Fix PD retry\n"}),
+            "- Fix PD retry",
+        )
+        self.assertIsNone(build_web_bundle.release_notes({"body": "  "}))
+        self.assertIsNone(build_web_bundle.release_notes({}))
+
     def test_output_paths_stay_relative_to_web_base(self) -> None:
         self.assertEqual(
             build_web_bundle.output_path_for("v0.6.0-dev.2", "isolapurr-usb-hub.full.bin"),
@@ -0,0 +1,52 @@
+//! Device-level response fields.
+
+use core::fmt::{Result, Write};
+
+use crate::json::{WriteJson, write_field};
+
+/// Feature flags published as `capabilities` by `info`, `ports.get` and the HTTP
+/// equivalents. Flags missing from older firmware decode as `false`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Capabilities {
+    pub identify: bool,
+    pub sound: bool,
+    pub display: bool,
+    pub display_screenshot: bool,
+    pub buttons: bool,
+    pub scpi: bool,
+    pub jsonl_tcp: bool,
+    pub i2c_diagnostics: bool,
+    pub register_debug: bool,
+    pub crash_report: bool,
+    pub remote_logs: bool,
+    pub port_power_policy: bool,
+    pub port_data_mode: bool,
+    pub charge_termination: bool,
+    pub sessions: bool,
+    pub fast_charge_probe: bool,
+}
+
+impl WriteJson for Capabilities {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        out.write_char('{')?;
+        write_field(out, true, "identify", &self.identify)?;
+        write_field(out, false, "sound", &self.sound)?;
+        write_field(out, false, "display", &self.display)?;
+        write_field(out, false, "display_screenshot", &self.display_screenshot)?;
+        write_field(out, false, "buttons", &self.buttons)?;
+        write_field(out, false, "scpi", &self.scpi)?;
+        write_field(out, false, "jsonl_tcp", &self.jsonl_tcp)?;
+        write_field(out, false, "i2c_diagnostics", &self.i2c_diagnostics)?;
+        write_field(out, false, "register_debug", &self.register_debug)?;
+        write_field(out, false, "crash_report", &self.crash_report)?;
+        write_field(out, false, "remote_logs", &self.remote_logs)?;
+        write_field(out, false, "port_power_policy", &self.port_power_policy)?;
+        write_field(out, false, "port_data_mode", &self.port_data_mode)?;
+        write_field(out, false, "charge_termination", &self.charge_termination)?;
+        write_field(out, false, "sessions", &self.sessions)?;
+        write_field(out, false, "fast_charge_probe", &self.fast_charge_probe)?;
+        out.write_char('}')
+    }
+}
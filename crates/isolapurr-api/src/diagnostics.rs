@@ -0,0 +1,64 @@
+//! Diagnostics vocabulary: I2C bus health for `i2c.*` and
+//! `/api/v1/diagnostics/i2c`, register debug targets for `debug.*`, reset
+//! causes for `diagnostics.crash`, and log levels for `logs.*`.
+
+use crate::wire_enum;
+
+wire_enum! {
+    /// The two I2C controllers: I2C0 to the SW2303 and I2C1 to the INA226s, TMP112,
+    /// TPS55288 and EEPROM U21.
+    pub enum I2cBusId {
+        Pd => "pd",
+        System => "system",
+    }
+}
+
+wire_enum! {
+    /// Outcome of probing one allowlisted address during `i2c.scan`.
+    pub enum I2cProbeResult {
+        Ack => "ack",
+        Nack => "nack",
+        Timeout => "timeout",
+        ArbitrationLost => "arbitration_lost",
+        Error => "error",
+        /// The bus was not probed, e.g. the PD bus while USB-C power is off.
+        Skipped => "skipped",
+    }
+}
+
+wire_enum! {
+    /// Chips reachable through `debug.reg_*`; nothing else on either bus is.
+    pub enum RegTarget {
+        Sw2303 => "sw2303",
+        Tps55288 => "tps55288",
+    }
+}
+
+wire_enum! {
+    /// Why the hub last restarted, reported by `info` and `diagnostics.crash`.
+    pub enum ResetReason {
+        PowerOn => "power_on",
+        /// `reboot`, `/api/v1/reboot` or a settings action that restarts.
+        Requested => "requested",
+        Panic => "panic",
+        Watchdog => "watchdog",
+        Brownout => "brownout",
+        /// A software reset without a recorded request.
+        Software => "software",
+        DeepSleep => "deep_sleep",
+        /// Reset by the USB Serial/JTAG peripheral, e.g. by a flasher.
+        Usb => "usb",
+        Unknown => "unknown",
+    }
+}
+
+wire_enum! {
+    /// Severity of a mirrored firmware log record, most severe first. The
+    /// `logs.*` level keeps records at or above it.
+    pub enum LogLevel {
+        Error => "error",
+        Warn => "warn",
+        Info => "info",
+        Debug => "debug",
+    }
+}
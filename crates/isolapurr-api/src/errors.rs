@@ -0,0 +1,77 @@
+//! Error codes carried in `error.code` by the HTTP API and the JSONL consoles.
+
+pub const BAD_REQUEST: &str = "bad_request";
+pub const BUSY: &str = "busy";
+pub const NOT_FOUND: &str = "not_found";
+pub const INVALID_PORT: &str = "invalid_port";
+pub const UNKNOWN_METHOD: &str = "unknown_method";
+pub const FRAME_TOO_LARGE: &str = "frame_too_large";
+pub const EEPROM_FAILED: &str = "eeprom_failed";
+pub const PROVISIONING_FAILED: &str = "provisioning_failed";
+pub const RUNTIME_APPLY_FAILED: &str = "runtime_apply_failed";
+pub const DATASET_MISSING: &str = "dataset_missing";
+pub const DISPLAY_UNAVAILABLE: &str = "display_unavailable";
+/// A manual `i2c.recover` ran but the bus did not come back idle.
+pub const I2C_RECOVERY_FAILED: &str = "i2c_recovery_failed";
+/// `debug.reg_*` was called without a confirmed `debug.unlock`.
+pub const DEBUG_LOCKED: &str = "debug_locked";
+/// A raw register transaction failed on the bus.
+pub const REGISTER_ACCESS_FAILED: &str = "register_access_failed";
+/// The request is refused on network transports (e.g. Wi-Fi changes); use USB.
+pub const UNSAFE_TRANSPORT: &str = "unsafe_transport";
+/// TCP console only.
+pub const UNAUTHORIZED: &str = "unauthorized";
+/// TCP console only: the method is restricted to USB.
+pub const USB_ONLY: &str = "usb_only";
+/// TCP console only: the console is disabled until the token is reset over USB.
+pub const UNAVAILABLE: &str = "unavailable";
+
+pub const ALL: &[&str] = &[
+    BAD_REQUEST,
+    BUSY,
+    NOT_FOUND,
+    INVALID_PORT,
+    UNKNOWN_METHOD,
+    FRAME_TOO_LARGE,
+    EEPROM_FAILED,
+    PROVISIONING_FAILED,
+    RUNTIME_APPLY_FAILED,
+    DATASET_MISSING,
+    DISPLAY_UNAVAILABLE,
+    I2C_RECOVERY_FAILED,
+    DEBUG_LOCKED,
+    REGISTER_ACCESS_FAILED,
+    UNSAFE_TRANSPORT,
+    UNAUTHORIZED,
+    USB_ONLY,
+    UNAVAILABLE,
+];
+
+// Host-only codes: devd and the desktop agent answer with these, the firmware
+// never does.
+/// The request's `Origin` is not allowed to use the local agent.
+pub const FORBIDDEN: &str = "forbidden";
+/// The request conflicts with host-side state (e.g. a pairing in progress).
+pub const CONFLICT: &str = "conflict";
+pub const INTERNAL_ERROR: &str = "internal_error";
+/// A host-side dependency such as mDNS discovery failed; retry later.
+pub const TEMPORARILY_UNAVAILABLE: &str = "temporarily_unavailable";
+
+pub const HOST_ONLY: &[&str] = &[FORBIDDEN, CONFLICT, INTERNAL_ERROR, TEMPORARILY_UNAVAILABLE];
+
+/// `{"error":{...}}` body of a failed HTTP response. [`HOST_ONLY`] codes travel
+/// in the same shape.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ErrorEnvelope<'a> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub error: ErrorInfo<'a>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ErrorInfo<'a> {
+    pub code: &'a str,
+    pub message: &'a str,
+    pub retryable: bool,
+}
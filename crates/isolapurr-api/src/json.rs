@@ -0,0 +1,67 @@
+//! Minimal JSON encoding for targets without serde.
+
+use core::fmt::{Result, Write};
+
+/// Writes `self` as compact JSON. Implemented for the response types the firmware
+/// sends, mirroring their serde shape field for field.
+pub trait WriteJson {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result;
+}
+
+impl WriteJson for bool {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        out.write_str(if *self { "true" } else { "false" })
+    }
+}
+
+impl WriteJson for u8 {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        write!(out, "{}", self)
+    }
+}
+
+impl WriteJson for u16 {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        write!(out, "{}", self)
+    }
+}
+
+impl WriteJson for u32 {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        write!(out, "{}", self)
+    }
+}
+
+impl WriteJson for u64 {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        write!(out, "{}", self)
+    }
+}
+
+impl<T: WriteJson> WriteJson for Option<T> {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        match self {
+            Some(value) => value.write_json(out),
+            None => out.write_str("null"),
+        }
+    }
+}
+
+/// Writes `"key":value`, preceded by a comma unless it is the first field.
+pub fn write_field<W: Write, T: WriteJson + ?Sized>(
+    out: &mut W,
+    first: bool,
+    key: &str,
+    value: &T,
+) -> Result {
+    if !first {
+        out.write_char(',')?;
+    }
+    write!(out, "\"{}\":", key)?;
+    value.write_json(out)
+}
+
+/// Writes a string known not to need escaping, such as a wire enum name.
+pub fn write_str_value<W: Write>(out: &mut W, value: &str) -> Result {
+    write!(out, "\"{}\"", value)
+}
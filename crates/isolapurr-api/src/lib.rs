@@ -0,0 +1,66 @@
+//! Wire contract shared by the firmware, `isolapurr-devd`, the `isolapurr` CLI and the
+//! desktop app.
+//!
+//! Every type here is `no_std`. The firmware encodes responses through [`WriteJson`];
+//! host tools enable the `serde` feature and decode the same shapes with serde. The
+//! conformance tests check that both encodings agree.
+
+#![no_std]
+
+pub mod device;
+pub mod diagnostics;
+pub mod errors;
+pub mod json;
+pub mod methods;
+pub mod ports;
+pub mod power;
+pub mod sessions;
+
+pub use json::WriteJson;
+
+/// Defines a fieldless enum whose variants travel as fixed strings, keeping
+/// `as_str`, `parse` and the serde names generated from one list.
+macro_rules! wire_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident => $wire:literal),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub enum $name {
+            $(
+                $(#[$variant_meta])*
+                #[cfg_attr(feature = "serde", serde(rename = $wire))]
+                $variant,
+            )+
+        }
+
+        impl $name {
+            pub const ALL: &'static [Self] = &[$(Self::$variant),+];
+
+            pub const fn as_str(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $wire,)+
+                }
+            }
+
+            pub fn parse(value: &str) -> Option<Self> {
+                match value {
+                    $($wire => Some(Self::$variant),)+
+                    _ => None,
+                }
+            }
+        }
+
+        impl $crate::json::WriteJson for $name {
+            fn write_json<W: core::fmt::Write>(&self, out: &mut W) -> core::fmt::Result {
+                $crate::json::write_str_value(out, self.as_str())
+            }
+        }
+    };
+}
+
+pub(crate) use wire_enum;
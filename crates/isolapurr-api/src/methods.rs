@@ -0,0 +1,143 @@
+//! JSONL method names, shared by the USB Serial/JTAG and TCP consoles and their clients.
+
+pub const INFO: &str = "info";
+pub const IDENTIFY: &str = "identify";
+pub const REBOOT: &str = "reboot";
+/// TCP console only; see `docs/specs/j7t4k-jsonl-tcp/SPEC.md`.
+pub const AUTH: &str = "auth";
+
+pub const PORTS_GET: &str = "ports.get";
+pub const PORT_POWER_SET: &str = "port.power_set";
+pub const PORT_REPLUG: &str = "port.replug";
+pub const PORT_DATA_SET: &str = "port.data_set";
+pub const HUB_ROUTE_SET: &str = "hub.route_set";
+pub const PORTS_POWER_POLICY_GET: &str = "ports.power_policy_get";
+pub const PORTS_POWER_POLICY_SET: &str = "ports.power_policy_set";
+pub const PORTS_CHARGE_TERMINATION_GET: &str = "ports.charge_termination_get";
+pub const PORTS_CHARGE_TERMINATION_SET: &str = "ports.charge_termination_set";
+pub const SESSIONS_LIST: &str = "sessions.list";
+
+pub const WIFI_GET: &str = "wifi.get";
+pub const WIFI_SET: &str = "wifi.set";
+pub const WIFI_CLEAR: &str = "wifi.clear";
+
+pub const PD_DIAGNOSTICS: &str = "pd.diagnostics";
+pub const PD_DIAGNOSTICS_GET: &str = "pd.diagnostics_get";
+
+pub const POWER_CONFIG_GET: &str = "power.config_get";
+pub const POWER_CONFIG_SET: &str = "power.config_set";
+pub const POWER_CONFIG_DEFAULTS: &str = "power.config_defaults";
+pub const POWER_RUNTIME_SET: &str = "power.runtime_set";
+pub const POWER_LOCK: &str = "power.lock";
+pub const POWER_IDLE_BIAS_GET: &str = "power.idle_bias_get";
+pub const POWER_IDLE_BIAS_SET: &str = "power.idle_bias_set";
+pub const POWER_IDLE_BIAS_RUN: &str = "power.idle_bias_run";
+pub const POWER_IDLE_BIAS_CLEAR: &str = "power.idle_bias_clear";
+pub const POWER_FAST_CHARGE_PROBE_GET: &str = "power.fast_charge_probe_get";
+pub const POWER_FAST_CHARGE_PROBE_RUN: &str = "power.fast_charge_probe_run";
+pub const POWER_FAST_CHARGE_PROBE_CANCEL: &str = "power.fast_charge_probe_cancel";
+
+pub const SETTINGS_RESET: &str = "settings.reset";
+
+pub const SOUND_GET: &str = "sound.get";
+pub const SOUND_SET: &str = "sound.set";
+pub const SOUND_DEFAULTS: &str = "sound.defaults";
+pub const SOUND_PATTERN_SET: &str = "sound.pattern_set";
+pub const SOUND_PATTERN_CLEAR: &str = "sound.pattern_clear";
+
+pub const DISPLAY_GET: &str = "display.get";
+pub const DISPLAY_SET: &str = "display.set";
+pub const DISPLAY_DEFAULTS: &str = "display.defaults";
+pub const DISPLAY_SCREENSHOT: &str = "display.screenshot";
+
+pub const BUTTONS_GET: &str = "buttons.get";
+pub const BUTTONS_SET: &str = "buttons.set";
+pub const BUTTONS_DEFAULTS: &str = "buttons.defaults";
+
+pub const JSONL_TCP_GET: &str = "jsonl_tcp.get";
+pub const JSONL_TCP_SET: &str = "jsonl_tcp.set";
+pub const JSONL_TCP_CLEAR: &str = "jsonl_tcp.clear";
+
+pub const I2C_DIAGNOSTICS_GET: &str = "i2c.diagnostics_get";
+pub const I2C_SCAN: &str = "i2c.scan";
+pub const I2C_RECOVER: &str = "i2c.recover";
+
+pub const DIAGNOSTICS_CRASH: &str = "diagnostics.crash";
+pub const DIAGNOSTICS_CRASH_ACK: &str = "diagnostics.crash_ack";
+
+pub const LOGS_TAIL: &str = "logs.tail";
+pub const LOGS_CONFIG_GET: &str = "logs.config_get";
+pub const LOGS_CONFIG_SET: &str = "logs.config_set";
+
+/// USB only, refused over TCP; see `docs/specs/h2r8v-register-debug/SPEC.md`.
+pub const DEBUG_STATUS: &str = "debug.status";
+pub const DEBUG_UNLOCK: &str = "debug.unlock";
+pub const DEBUG_EXIT: &str = "debug.exit";
+pub const DEBUG_REG_READ: &str = "debug.reg_read";
+pub const DEBUG_REG_WRITE: &str = "debug.reg_write";
+pub const DEBUG_REG_DUMP: &str = "debug.reg_dump";
+
+/// Every method the firmware dispatches.
+pub const ALL: &[&str] = &[
+    INFO,
+    IDENTIFY,
+    REBOOT,
+    AUTH,
+    PORTS_GET,
+    PORT_POWER_SET,
+    PORT_REPLUG,
+    PORT_DATA_SET,
+    HUB_ROUTE_SET,
+    PORTS_POWER_POLICY_GET,
+    PORTS_POWER_POLICY_SET,
+    PORTS_CHARGE_TERMINATION_GET,
+    PORTS_CHARGE_TERMINATION_SET,
+    SESSIONS_LIST,
+    WIFI_GET,
+    WIFI_SET,
+    WIFI_CLEAR,
+    PD_DIAGNOSTICS,
+    PD_DIAGNOSTICS_GET,
+    POWER_CONFIG_GET,
+    POWER_CONFIG_SET,
+    POWER_CONFIG_DEFAULTS,
+    POWER_RUNTIME_SET,
+    POWER_LOCK,
+    POWER_IDLE_BIAS_GET,
+    POWER_IDLE_BIAS_SET,
+    POWER_IDLE_BIAS_RUN,
+    POWER_IDLE_BIAS_CLEAR,
+    POWER_FAST_CHARGE_PROBE_GET,
+    POWER_FAST_CHARGE_PROBE_RUN,
+    POWER_FAST_CHARGE_PROBE_CANCEL,
+    SETTINGS_RESET,
+    SOUND_GET,
+    SOUND_SET,
+    SOUND_DEFAULTS,
+    SOUND_PATTERN_SET,
+    SOUND_PATTERN_CLEAR,
+    DISPLAY_GET,
+    DISPLAY_SET,
+    DISPLAY_DEFAULTS,
+    DISPLAY_SCREENSHOT,
+    BUTTONS_GET,
+    BUTTONS_SET,
+    BUTTONS_DEFAULTS,
+    JSONL_TCP_GET,
+    JSONL_TCP_SET,
+    JSONL_TCP_CLEAR,
+    I2C_DIAGNOSTICS_GET,
+    I2C_SCAN,
+    I2C_RECOVER,
+    DIAGNOSTICS_CRASH,
+    DIAGNOSTICS_CRASH_ACK,
+    LOGS_TAIL,
+    LOGS_CONFIG_GET,
+    LOGS_CONFIG_SET,
+    DEBUG_STATUS,
+    DEBUG_UNLOCK,
+    DEBUG_EXIT,
+    DEBUG_REG_READ,
+    DEBUG_REG_WRITE,
+    DEBUG_REG_DUMP,
+];
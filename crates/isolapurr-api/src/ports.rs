@@ -0,0 +1,349 @@
+//! Hub and per-port status, as returned by `ports.get` and `GET /api/v1/ports`.
+
+use core::fmt::{Result, Write};
+
+use crate::device::Capabilities;
+use crate::json::{WriteJson, write_field, write_str_value};
+use crate::wire_enum;
+
+wire_enum! {
+    pub enum PortId {
+        PortA => "port_a",
+        PortC => "port_c",
+    }
+}
+
+impl PortId {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::PortA => "USB-A",
+            Self::PortC => "USB-C",
+        }
+    }
+}
+
+wire_enum! {
+    pub enum TelemetryStatus {
+        Ok => "ok",
+        NotInserted => "not_inserted",
+        Error => "error",
+        Overrange => "overrange",
+    }
+}
+
+wire_enum! {
+    /// Which side the USB-C data lines are routed to.
+    pub enum UsbCDownstreamRoute {
+        Mcu => "mcu",
+        UsbC => "usb_c",
+    }
+}
+
+wire_enum! {
+    /// Power state a port comes up in after a reset or power loss.
+    pub enum PortPowerPolicy {
+        On => "on",
+        Off => "off",
+        /// Whatever the port was last switched to before the reset.
+        Last => "last",
+    }
+}
+
+wire_enum! {
+    /// Whether a port's D+/D- lines reach the hub. Charge-only ports supply
+    /// power but never enumerate.
+    #[derive(Default)]
+    pub enum PortDataMode {
+        #[default]
+        Connected => "connected",
+        ChargeOnly => "charge_only",
+    }
+}
+
+wire_enum! {
+    /// What a port does once its charge-termination detector fires.
+    #[derive(Default)]
+    pub enum ChargeTerminationAction {
+        /// Toast and prompt tone only; the port stays powered.
+        #[default]
+        Notify => "notify",
+        PowerOff => "power_off",
+        /// Renegotiate down to 5 V. USB-A is always 5 V, so it only notifies.
+        Drop5v => "drop_5v",
+    }
+}
+
+/// A charge session that ended because the load current stayed below the
+/// termination threshold.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChargeCompletion {
+    pub completed_uptime_ms: u64,
+    /// Time from the start of charging until the detector fired.
+    pub charge_ms: u64,
+    pub energy_mwh: u32,
+    pub action: ChargeTerminationAction,
+}
+
+impl WriteJson for ChargeCompletion {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        out.write_char('{')?;
+        write_field(out, true, "completed_uptime_ms", &self.completed_uptime_ms)?;
+        write_field(out, false, "charge_ms", &self.charge_ms)?;
+        write_field(out, false, "energy_mwh", &self.energy_mwh)?;
+        write_field(out, false, "action", &self.action)?;
+        out.write_char('}')
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PortTelemetry {
+    pub status: TelemetryStatus,
+    pub voltage_mv: Option<u32>,
+    pub current_ma: Option<u32>,
+    pub power_mw: Option<u32>,
+    pub sample_uptime_ms: u64,
+}
+
+impl PortTelemetry {
+    pub const fn unknown() -> Self {
+        Self {
+            status: TelemetryStatus::Error,
+            voltage_mv: None,
+            current_ma: None,
+            power_mw: None,
+            sample_uptime_ms: 0,
+        }
+    }
+}
+
+impl WriteJson for PortTelemetry {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        out.write_char('{')?;
+        write_field(out, true, "status", &self.status)?;
+        write_field(out, false, "voltage_mv", &self.voltage_mv)?;
+        write_field(out, false, "current_ma", &self.current_ma)?;
+        write_field(out, false, "power_mw", &self.power_mw)?;
+        write_field(out, false, "sample_uptime_ms", &self.sample_uptime_ms)?;
+        out.write_char('}')
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PortState {
+    pub power_enabled: bool,
+    pub data_connected: bool,
+    pub replugging: bool,
+    pub busy: bool,
+    /// Persisted data mode; absent from firmware without charge-only support.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub data_mode: PortDataMode,
+}
+
+impl WriteJson for PortState {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        out.write_char('{')?;
+        write_field(out, true, "power_enabled", &self.power_enabled)?;
+        write_field(out, false, "data_connected", &self.data_connected)?;
+        write_field(out, false, "replugging", &self.replugging)?;
+        write_field(out, false, "busy", &self.busy)?;
+        write_field(out, false, "data_mode", &self.data_mode)?;
+        out.write_char('}')
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PortCapabilities {
+    pub data_replug: bool,
+    pub power_set: bool,
+}
+
+impl WriteJson for PortCapabilities {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        out.write_char('{')?;
+        write_field(out, true, "data_replug", &self.data_replug)?;
+        write_field(out, false, "power_set", &self.power_set)?;
+        out.write_char('}')
+    }
+}
+
+/// Latest readings and switch state of one port. `telemetry` is idle-bias
+/// corrected; `telemetry_raw` is the INA226 reading it was derived from.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PortSnapshot {
+    pub telemetry: PortTelemetry,
+    pub telemetry_raw: Option<PortTelemetry>,
+    pub state: PortState,
+    /// Most recent charge-termination event; kept after the load is removed
+    /// and cleared once a new charge starts.
+    pub charge_complete: Option<ChargeCompletion>,
+}
+
+impl PortSnapshot {
+    pub const fn unknown() -> Self {
+        Self {
+            telemetry: PortTelemetry::unknown(),
+            telemetry_raw: None,
+            state: PortState {
+                power_enabled: false,
+                data_connected: false,
+                replugging: false,
+                busy: false,
+                data_mode: PortDataMode::Connected,
+            },
+            charge_complete: None,
+        }
+    }
+}
+
+/// One entry of `ports`. The `label` field is derived from `port_id`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct Port {
+    #[cfg_attr(feature = "serde", serde(rename = "portId"))]
+    pub port_id: PortId,
+    pub telemetry: PortTelemetry,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub telemetry_raw: Option<PortTelemetry>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub state: PortState,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub capabilities: PortCapabilities,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub charge_complete: Option<ChargeCompletion>,
+}
+
+impl Port {
+    pub const fn new(
+        port_id: PortId,
+        snapshot: &PortSnapshot,
+        capabilities: PortCapabilities,
+    ) -> Self {
+        Self {
+            port_id,
+            telemetry: snapshot.telemetry,
+            telemetry_raw: snapshot.telemetry_raw,
+            state: snapshot.state,
+            capabilities,
+            charge_complete: snapshot.charge_complete,
+        }
+    }
+}
+
+impl WriteJson for Port {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        out.write_char('{')?;
+        write_field(out, true, "portId", &self.port_id)?;
+        out.write_str(",\"label\":")?;
+        write_str_value(out, self.port_id.label())?;
+        write_field(out, false, "telemetry", &self.telemetry)?;
+        write_field(out, false, "telemetry_raw", &self.telemetry_raw)?;
+        write_field(out, false, "state", &self.state)?;
+        write_field(out, false, "capabilities", &self.capabilities)?;
+        write_field(out, false, "charge_complete", &self.charge_complete)?;
+        out.write_char('}')
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Port {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut port = serializer.serialize_struct("Port", 7)?;
+        port.serialize_field("portId", &self.port_id)?;
+        port.serialize_field("label", self.port_id.label())?;
+        port.serialize_field("telemetry", &self.telemetry)?;
+        port.serialize_field("telemetry_raw", &self.telemetry_raw)?;
+        port.serialize_field("state", &self.state)?;
+        port.serialize_field("capabilities", &self.capabilities)?;
+        port.serialize_field("charge_complete", &self.charge_complete)?;
+        port.end()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HubStatus {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub upstream_connected: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub isolated_usb_fault: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub isolated_downstream_connected: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub isolated_usb_ready: bool,
+    pub usb_c_downstream_route: UsbCDownstreamRoute,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub usb_c_downstream_persisted: bool,
+}
+
+impl HubStatus {
+    pub const fn unknown() -> Self {
+        Self {
+            upstream_connected: false,
+            isolated_usb_fault: false,
+            isolated_downstream_connected: false,
+            isolated_usb_ready: false,
+            usb_c_downstream_route: UsbCDownstreamRoute::Mcu,
+            usb_c_downstream_persisted: false,
+        }
+    }
+}
+
+impl WriteJson for HubStatus {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        out.write_char('{')?;
+        write_field(out, true, "upstream_connected", &self.upstream_connected)?;
+        write_field(out, false, "isolated_usb_fault", &self.isolated_usb_fault)?;
+        write_field(
+            out,
+            false,
+            "isolated_downstream_connected",
+            &self.isolated_downstream_connected,
+        )?;
+        write_field(out, false, "isolated_usb_ready", &self.isolated_usb_ready)?;
+        write_field(
+            out,
+            false,
+            "usb_c_downstream_route",
+            &self.usb_c_downstream_route,
+        )?;
+        write_field(
+            out,
+            false,
+            "usb_c_downstream_persisted",
+            &self.usb_c_downstream_persisted,
+        )?;
+        out.write_char('}')
+    }
+}
+
+/// Body of `GET /api/v1/ports` and the `result` of `ports.get`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PortsResponse {
+    pub hub: HubStatus,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub capabilities: Capabilities,
+    pub ports: [Port; 2],
+}
+
+impl WriteJson for PortsResponse {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        out.write_char('{')?;
+        write_field(out, true, "hub", &self.hub)?;
+        write_field(out, false, "capabilities", &self.capabilities)?;
+        out.write_str(",\"ports\":[")?;
+        self.ports[0].write_json(out)?;
+        out.write_char(',')?;
+        self.ports[1].write_json(out)?;
+        out.write_str("]}")
+    }
+}
@@ -0,0 +1,516 @@
+//! Power configuration vocabulary used by `power.config_*` and `/api/v1/power/*`.
+
+use core::fmt::{Result, Write};
+
+use crate::json::{WriteJson, write_field};
+use crate::wire_enum;
+
+wire_enum! {
+    pub enum PowerHardwareKind {
+        Sw2303 => "sw2303",
+    }
+}
+
+wire_enum! {
+    pub enum TpsMode {
+        AutoFollow => "auto_follow",
+        Manual => "manual",
+    }
+}
+
+wire_enum! {
+    pub enum LightLoadMode {
+        Pfm => "pfm",
+        Fpwm => "fpwm",
+    }
+}
+
+wire_enum! {
+    pub enum Sw2303LineCompensation {
+        Off => "off",
+        Ohm0 => "0mohm",
+        MilliOhm50 => "50mohm",
+        MilliOhm100 => "100mohm",
+        MilliOhm150 => "150mohm",
+    }
+}
+
+impl Sw2303LineCompensation {
+    pub const fn display_label(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Ohm0 => "0",
+            Self::MilliOhm50 => "50mΩ",
+            Self::MilliOhm100 => "100mΩ",
+            Self::MilliOhm150 => "150mΩ",
+        }
+    }
+}
+
+wire_enum! {
+    pub enum ManualUsbCPathMode {
+        Default => "default",
+        Disconnect => "disconnect",
+        Force => "force",
+    }
+}
+
+wire_enum! {
+    /// Reported as `manual.path_policy`.
+    pub enum Sw2303PathControl {
+        Auto => "auto",
+        ForceClose => "force_close",
+        ForceOpen => "force_open",
+    }
+}
+
+wire_enum! {
+    /// Charging protocol the SW2303 negotiated on USB-C, reported as
+    /// `active_protocol`.
+    pub enum ActiveProtocol {
+        Pd => "pd",
+        Pps => "pps",
+        Qc20 => "qc20",
+        Qc30 => "qc30",
+        Fcp => "fcp",
+        Afc => "afc",
+        Scp => "scp",
+        Pe20 => "pe20",
+        Bc12 => "bc12",
+        Sfcp => "sfcp",
+    }
+}
+
+/// A set of [`ActiveProtocol`]s, encoded as a JSON array in [`ActiveProtocol::ALL`]
+/// order.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ActiveProtocolSet(u16);
+
+impl ActiveProtocolSet {
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    const fn bit(protocol: ActiveProtocol) -> u16 {
+        1 << protocol as u16
+    }
+
+    pub const fn with(self, protocol: ActiveProtocol) -> Self {
+        Self(self.0 | Self::bit(protocol))
+    }
+
+    pub fn insert(&mut self, protocol: ActiveProtocol) {
+        *self = self.with(protocol);
+    }
+
+    pub const fn contains(self, protocol: ActiveProtocol) -> bool {
+        self.0 & Self::bit(protocol) != 0
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn iter(self) -> impl Iterator<Item = ActiveProtocol> {
+        ActiveProtocol::ALL
+            .iter()
+            .copied()
+            .filter(move |protocol| self.contains(*protocol))
+    }
+}
+
+impl FromIterator<ActiveProtocol> for ActiveProtocolSet {
+    fn from_iter<I: IntoIterator<Item = ActiveProtocol>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::empty(), Self::with)
+    }
+}
+
+impl WriteJson for ActiveProtocolSet {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        out.write_char('[')?;
+        for (index, protocol) in self.iter().enumerate() {
+            if index > 0 {
+                out.write_char(',')?;
+            }
+            protocol.write_json(out)?;
+        }
+        out.write_char(']')
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ActiveProtocolSet {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ActiveProtocolSet {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> core::result::Result<Self, D::Error> {
+        struct SetVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for SetVisitor {
+            type Value = ActiveProtocolSet;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> Result {
+                formatter.write_str("an array of protocol names")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> core::result::Result<Self::Value, A::Error> {
+                let mut set = ActiveProtocolSet::empty();
+                while let Some(protocol) = seq.next_element::<ActiveProtocol>()? {
+                    set.insert(protocol);
+                }
+                Ok(set)
+            }
+        }
+
+        deserializer.deserialize_seq(SetVisitor)
+    }
+}
+
+wire_enum! {
+    /// Reported as `capability.profile`.
+    pub enum PowerCapabilityProfile {
+        Full => "full",
+    }
+}
+
+/// PD fixed voltages offered above 5 V, encoded as a JSON array of millivolts in
+/// ascending order.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PdFixedVoltageSet(u8);
+
+impl PdFixedVoltageSet {
+    pub const ALL_MV: [u16; 4] = [9_000, 12_000, 15_000, 20_000];
+
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    fn bit(mv: u16) -> Option<u8> {
+        Self::ALL_MV
+            .iter()
+            .position(|candidate| *candidate == mv)
+            .map(|index| 1 << index)
+    }
+
+    /// `None` when `mv` is not one of [`Self::ALL_MV`].
+    pub fn with(self, mv: u16) -> Option<Self> {
+        Self::bit(mv).map(|bit| Self(self.0 | bit))
+    }
+
+    pub fn contains(self, mv: u16) -> bool {
+        Self::bit(mv).is_some_and(|bit| self.0 & bit != 0)
+    }
+
+    pub fn iter(self) -> impl Iterator<Item = u16> {
+        Self::ALL_MV
+            .iter()
+            .copied()
+            .filter(move |mv| self.contains(*mv))
+    }
+}
+
+impl WriteJson for PdFixedVoltageSet {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        out.write_char('[')?;
+        for (index, mv) in self.iter().enumerate() {
+            if index > 0 {
+                out.write_char(',')?;
+            }
+            mv.write_json(out)?;
+        }
+        out.write_char(']')
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for PdFixedVoltageSet {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PdFixedVoltageSet {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> core::result::Result<Self, D::Error> {
+        struct SetVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for SetVisitor {
+            type Value = PdFixedVoltageSet;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> Result {
+                formatter.write_str("an array of 9000, 12000, 15000 or 20000")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> core::result::Result<Self::Value, A::Error> {
+                let mut set = PdFixedVoltageSet::empty();
+                while let Some(mv) = seq.next_element::<u16>()? {
+                    set = set.with(mv).ok_or_else(|| {
+                        serde::de::Error::invalid_value(
+                            serde::de::Unexpected::Unsigned(u64::from(mv)),
+                            &self,
+                        )
+                    })?;
+                }
+                Ok(set)
+            }
+        }
+
+        deserializer.deserialize_seq(SetVisitor)
+    }
+}
+
+/// `capability.protocols`: which fast-charge families the SW2303 offers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PowerProtocols {
+    pub pd: bool,
+    pub qc20: bool,
+    pub qc30: bool,
+    pub fcp: bool,
+    pub afc: bool,
+    pub scp: bool,
+    pub pe20: bool,
+    pub bc12: bool,
+    pub sfcp: bool,
+}
+
+impl WriteJson for PowerProtocols {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        out.write_char('{')?;
+        write_field(out, true, "pd", &self.pd)?;
+        write_field(out, false, "qc20", &self.qc20)?;
+        write_field(out, false, "qc30", &self.qc30)?;
+        write_field(out, false, "fcp", &self.fcp)?;
+        write_field(out, false, "afc", &self.afc)?;
+        write_field(out, false, "scp", &self.scp)?;
+        write_field(out, false, "pe20", &self.pe20)?;
+        write_field(out, false, "bc12", &self.bc12)?;
+        write_field(out, false, "sfcp", &self.sfcp)?;
+        out.write_char('}')
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PowerPdCapability {
+    pub pps: bool,
+    pub fixed_voltages_mv: PdFixedVoltageSet,
+}
+
+impl WriteJson for PowerPdCapability {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        out.write_char('{')?;
+        write_field(out, true, "pps", &self.pps)?;
+        write_field(out, false, "fixed_voltages_mv", &self.fixed_voltages_mv)?;
+        out.write_char('}')
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PowerCurrentLimits {
+    pub pps3_limit_ma: u16,
+    pub pd_pps_5a: bool,
+    pub type_c_broadcast_ma: u16,
+    pub scp_limit_ma: u16,
+    pub fcp_afc_sfcp_limit_ma: u16,
+}
+
+impl WriteJson for PowerCurrentLimits {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        out.write_char('{')?;
+        write_field(out, true, "pps3_limit_ma", &self.pps3_limit_ma)?;
+        write_field(out, false, "pd_pps_5a", &self.pd_pps_5a)?;
+        write_field(out, false, "type_c_broadcast_ma", &self.type_c_broadcast_ma)?;
+        write_field(out, false, "scp_limit_ma", &self.scp_limit_ma)?;
+        write_field(
+            out,
+            false,
+            "fcp_afc_sfcp_limit_ma",
+            &self.fcp_afc_sfcp_limit_ma,
+        )?;
+        out.write_char('}')
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PowerFastCharge {
+    pub qc20_20v_enabled: bool,
+    pub qc30_20v_enabled: bool,
+    pub pe20_20v_enabled: bool,
+    pub non_pd_12v_enabled: bool,
+}
+
+impl WriteJson for PowerFastCharge {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        out.write_char('{')?;
+        write_field(out, true, "qc20_20v_enabled", &self.qc20_20v_enabled)?;
+        write_field(out, false, "qc30_20v_enabled", &self.qc30_20v_enabled)?;
+        write_field(out, false, "pe20_20v_enabled", &self.pe20_20v_enabled)?;
+        write_field(out, false, "non_pd_12v_enabled", &self.non_pd_12v_enabled)?;
+        out.write_char('}')
+    }
+}
+
+/// The `capability` object. `current` and `fast_charge` are absent from older
+/// firmware and from requests that leave them at their defaults.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PowerCapability {
+    pub profile: PowerCapabilityProfile,
+    pub power_watts: u8,
+    pub protocols: PowerProtocols,
+    pub pd: PowerPdCapability,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub current: Option<PowerCurrentLimits>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub fast_charge: Option<PowerFastCharge>,
+}
+
+impl WriteJson for PowerCapability {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        out.write_char('{')?;
+        write_field(out, true, "profile", &self.profile)?;
+        write_field(out, false, "power_watts", &self.power_watts)?;
+        write_field(out, false, "protocols", &self.protocols)?;
+        write_field(out, false, "pd", &self.pd)?;
+        if let Some(current) = &self.current {
+            write_field(out, false, "current", current)?;
+        }
+        if let Some(fast_charge) = &self.fast_charge {
+            write_field(out, false, "fast_charge", fast_charge)?;
+        }
+        out.write_char('}')
+    }
+}
+
+/// The stored fields of `manual`; `path_policy` is runtime state.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManualPowerSettings {
+    pub voltage_mv: u16,
+    pub current_limit_ma: u16,
+    pub usb_c_path_mode: ManualUsbCPathMode,
+}
+
+impl WriteJson for ManualPowerSettings {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        out.write_char('{')?;
+        write_field(out, true, "voltage_mv", &self.voltage_mv)?;
+        write_field(out, false, "current_limit_ma", &self.current_limit_ma)?;
+        write_field(out, false, "usb_c_path_mode", &self.usb_c_path_mode)?;
+        out.write_char('}')
+    }
+}
+
+/// The settings `power.config_set` stores, as sent in its `config` and read back
+/// from `power.config_get`. Runtime state in the response (`persisted`, `runtime`,
+/// `lock`, `manual.path_policy`) and newer fields are ignored when decoding.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PowerSettings {
+    pub hardware: PowerHardwareKind,
+    pub tps_mode: TpsMode,
+    pub light_load_mode: LightLoadMode,
+    pub capability: PowerCapability,
+    pub manual: ManualPowerSettings,
+}
+
+impl WriteJson for PowerSettings {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        out.write_char('{')?;
+        write_field(out, true, "hardware", &self.hardware)?;
+        write_field(out, false, "tps_mode", &self.tps_mode)?;
+        write_field(out, false, "light_load_mode", &self.light_load_mode)?;
+        write_field(out, false, "capability", &self.capability)?;
+        write_field(out, false, "manual", &self.manual)?;
+        out.write_char('}')
+    }
+}
+
+wire_enum! {
+    /// Progress of the fast-charge protocol probe, reported as `state`.
+    pub enum FastChargeProbeState {
+        Idle => "idle",
+        Running => "running",
+        Complete => "complete",
+        Aborted => "aborted",
+    }
+}
+
+wire_enum! {
+    /// Why a fast-charge probe did not start or stopped early, reported as `error`.
+    pub enum FastChargeProbeError {
+        PortOff => "port_off",
+        ManualMode => "manual_mode",
+        ControllerNotReady => "controller_not_ready",
+        Detached => "detached",
+        Cancelled => "cancelled",
+    }
+}
+
+impl FastChargeProbeError {
+    pub const fn message(self) -> &'static str {
+        match self {
+            Self::PortOff => "USB-C power is off",
+            Self::ManualMode => "The fast-charge probe needs TPS auto_follow mode",
+            Self::ControllerNotReady => "USB-C controller is not ready for the fast-charge probe",
+            Self::Detached => "The USB-C sink detached during the fast-charge probe",
+            Self::Cancelled => "The fast-charge probe was cancelled",
+        }
+    }
+}
+
+/// What the sink negotiated while the probe offered a single protocol family.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FastChargeProbeResult {
+    /// The family that was offered.
+    pub family: ActiveProtocol,
+    /// `negotiated` matches `family`.
+    pub supported: bool,
+    /// The protocol at the highest-power sample of the step, `None` without a
+    /// contract.
+    pub negotiated: Option<ActiveProtocol>,
+    pub voltage_mv: Option<u32>,
+    pub current_ma: Option<u32>,
+}
+
+impl WriteJson for FastChargeProbeResult {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        out.write_char('{')?;
+        write_field(out, true, "family", &self.family)?;
+        write_field(out, false, "supported", &self.supported)?;
+        write_field(out, false, "negotiated", &self.negotiated)?;
+        write_field(out, false, "voltage_mv", &self.voltage_mv)?;
+        write_field(out, false, "current_ma", &self.current_ma)?;
+        out.write_char('}')
+    }
+}
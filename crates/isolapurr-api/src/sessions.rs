@@ -0,0 +1,43 @@
+//! Charging sessions listed by `sessions.list` and `/api/v1/sessions`.
+
+use core::fmt::{Result, Write};
+
+use crate::json::{WriteJson, write_field};
+use crate::ports::PortId;
+use crate::power::ActiveProtocolSet;
+
+/// One load on one port, from attach to detach. Times are device uptime.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChargeSession {
+    /// Starts at 1 every boot.
+    pub id: u32,
+    pub port: PortId,
+    pub start_uptime_ms: u64,
+    /// `None` while the load is still attached.
+    pub end_uptime_ms: Option<u64>,
+    pub energy_mwh: u32,
+    pub peak_power_mw: u32,
+    /// Energy divided by the session duration.
+    pub avg_power_mw: u32,
+    pub max_voltage_mv: u32,
+    /// Every protocol the SW2303 negotiated during the session; always empty on
+    /// USB-A.
+    pub protocols: ActiveProtocolSet,
+}
+
+impl WriteJson for ChargeSession {
+    fn write_json<W: Write>(&self, out: &mut W) -> Result {
+        out.write_char('{')?;
+        write_field(out, true, "id", &self.id)?;
+        write_field(out, false, "port", &self.port)?;
+        write_field(out, false, "start_uptime_ms", &self.start_uptime_ms)?;
+        write_field(out, false, "end_uptime_ms", &self.end_uptime_ms)?;
+        write_field(out, false, "energy_mwh", &self.energy_mwh)?;
+        write_field(out, false, "peak_power_mw", &self.peak_power_mw)?;
+        write_field(out, false, "avg_power_mw", &self.avg_power_mw)?;
+        write_field(out, false, "max_voltage_mv", &self.max_voltage_mv)?;
+        write_field(out, false, "protocols", &self.protocols)?;
+        out.write_char('}')
+    }
+}
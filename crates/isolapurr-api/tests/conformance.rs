@@ -0,0 +1,377 @@
+//! Checks that the firmware's `WriteJson` encoding and the host's serde derives
+//! describe the same wire shapes.
+
+use isolapurr_api::device::Capabilities;
+use isolapurr_api::diagnostics::{I2cBusId, I2cProbeResult, LogLevel, RegTarget, ResetReason};
+use isolapurr_api::ports::{
+    ChargeCompletion, ChargeTerminationAction, HubStatus, Port, PortCapabilities, PortDataMode,
+    PortId, PortPowerPolicy, PortSnapshot, PortState, PortTelemetry, PortsResponse,
+    TelemetryStatus, UsbCDownstreamRoute,
+};
+use isolapurr_api::power::{
+    ActiveProtocol, ActiveProtocolSet, FastChargeProbeError, FastChargeProbeResult,
+    FastChargeProbeState, LightLoadMode, ManualPowerSettings, ManualUsbCPathMode,
+    PdFixedVoltageSet, PowerCapability, PowerCapabilityProfile, PowerCurrentLimits,
+    PowerFastCharge, PowerHardwareKind, PowerPdCapability, PowerProtocols, PowerSettings,
+    Sw2303LineCompensation, Sw2303PathControl, TpsMode,
+};
+use isolapurr_api::sessions::ChargeSession;
+use isolapurr_api::{WriteJson, errors, methods};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+fn written<T: WriteJson>(value: &T) -> String {
+    let mut out = String::new();
+    value.write_json(&mut out).expect("write to String");
+    out
+}
+
+/// The firmware text must parse back to the same value and match serde's own
+/// encoding field for field.
+fn assert_round_trip<T>(value: &T)
+where
+    T: WriteJson + Serialize + DeserializeOwned + PartialEq + core::fmt::Debug,
+{
+    let text = written(value);
+    let decoded: T = serde_json::from_str(&text).expect("firmware JSON decodes");
+    assert_eq!(&decoded, value, "{text}");
+    let firmware: Value = serde_json::from_str(&text).expect("valid JSON");
+    assert_eq!(
+        firmware,
+        serde_json::to_value(value).expect("serde encodes")
+    );
+}
+
+macro_rules! assert_wire_enum {
+    ($($ty:ty),+) => {$(
+        for variant in <$ty>::ALL {
+            assert_eq!(serde_json::to_value(variant).unwrap(), json!(variant.as_str()));
+            assert_eq!(<$ty>::parse(variant.as_str()), Some(*variant));
+            assert_round_trip(variant);
+        }
+        assert_eq!(<$ty>::parse("not-a-variant"), None);
+    )+};
+}
+
+fn sample_port(port_id: PortId) -> Port {
+    let snapshot = PortSnapshot {
+        telemetry: PortTelemetry {
+            status: TelemetryStatus::Ok,
+            voltage_mv: Some(5_012),
+            current_ma: Some(1_480),
+            power_mw: Some(7_417),
+            sample_uptime_ms: 123_456,
+        },
+        telemetry_raw: Some(PortTelemetry {
+            status: TelemetryStatus::Ok,
+            voltage_mv: Some(5_012),
+            current_ma: Some(1_492),
+            power_mw: None,
+            sample_uptime_ms: 123_456,
+        }),
+        state: PortState {
+            power_enabled: true,
+            data_connected: true,
+            replugging: false,
+            busy: false,
+            data_mode: PortDataMode::ChargeOnly,
+        },
+        charge_complete: Some(ChargeCompletion {
+            completed_uptime_ms: 7_200_000,
+            charge_ms: 5_400_000,
+            energy_mwh: 11_250,
+            action: ChargeTerminationAction::PowerOff,
+        }),
+    };
+    Port::new(
+        port_id,
+        &snapshot,
+        PortCapabilities {
+            data_replug: true,
+            power_set: true,
+        },
+    )
+}
+
+#[test]
+fn wire_enums_match_serde_names() {
+    assert_wire_enum!(
+        PortId,
+        TelemetryStatus,
+        UsbCDownstreamRoute,
+        PowerHardwareKind,
+        TpsMode,
+        LightLoadMode,
+        Sw2303LineCompensation,
+        ManualUsbCPathMode,
+        Sw2303PathControl,
+        I2cBusId,
+        I2cProbeResult,
+        RegTarget,
+        ResetReason,
+        LogLevel,
+        PortPowerPolicy,
+        PortDataMode,
+        ChargeTerminationAction,
+        ActiveProtocol,
+        FastChargeProbeState,
+        FastChargeProbeError,
+        PowerCapabilityProfile
+    );
+}
+
+#[test]
+fn ports_response_round_trips() {
+    let response = PortsResponse {
+        hub: HubStatus {
+            upstream_connected: true,
+            isolated_usb_fault: false,
+            isolated_downstream_connected: true,
+            isolated_usb_ready: true,
+            usb_c_downstream_route: UsbCDownstreamRoute::UsbC,
+            usb_c_downstream_persisted: true,
+        },
+        capabilities: Capabilities {
+            identify: true,
+            sound: true,
+            display: false,
+            display_screenshot: true,
+            buttons: true,
+            scpi: false,
+            jsonl_tcp: true,
+            i2c_diagnostics: true,
+            register_debug: true,
+            crash_report: true,
+            remote_logs: true,
+            port_power_policy: true,
+            port_data_mode: true,
+            charge_termination: true,
+            sessions: true,
+            fast_charge_probe: true,
+        },
+        ports: [
+            sample_port(PortId::PortA),
+            Port::new(
+                PortId::PortC,
+                &PortSnapshot::unknown(),
+                PortCapabilities::default(),
+            ),
+        ],
+    };
+    assert_round_trip(&response);
+    assert_round_trip(&response.hub);
+    assert_round_trip(&response.capabilities);
+    assert_round_trip(&response.ports[0]);
+    assert_round_trip(&response.ports[0].telemetry);
+    assert_round_trip(&response.ports[0].charge_complete.unwrap());
+
+    let value: Value = serde_json::from_str(&written(&response)).unwrap();
+    assert_eq!(value["ports"][0]["label"], "USB-A");
+    assert_eq!(value["ports"][1]["telemetry_raw"], Value::Null);
+    assert_eq!(value["ports"][1]["charge_complete"], Value::Null);
+}
+
+#[test]
+fn charge_sessions_round_trip() {
+    let open = ChargeSession {
+        id: 3,
+        port: PortId::PortC,
+        start_uptime_ms: 60_000,
+        end_uptime_ms: None,
+        energy_mwh: 0,
+        peak_power_mw: 18_200,
+        avg_power_mw: 0,
+        max_voltage_mv: 9_012,
+        protocols: [ActiveProtocol::Pps, ActiveProtocol::Pd]
+            .into_iter()
+            .collect(),
+    };
+    assert_round_trip(&open);
+    let closed = ChargeSession {
+        id: 1,
+        port: PortId::PortA,
+        end_uptime_ms: Some(3_660_000),
+        protocols: ActiveProtocolSet::empty(),
+        ..open
+    };
+    assert_round_trip(&closed);
+
+    let value: Value = serde_json::from_str(&written(&open)).unwrap();
+    assert_eq!(value["protocols"], json!(["pd", "pps"]));
+    assert_eq!(value["end_uptime_ms"], Value::Null);
+    assert_eq!(
+        serde_json::from_value::<ActiveProtocolSet>(json!(["scp", "scp", "bc12"])).unwrap(),
+        ActiveProtocolSet::empty()
+            .with(ActiveProtocol::Bc12)
+            .with(ActiveProtocol::Scp)
+    );
+}
+
+#[test]
+fn fast_charge_probe_results_round_trip() {
+    let supported = FastChargeProbeResult {
+        family: ActiveProtocol::Qc30,
+        supported: true,
+        negotiated: Some(ActiveProtocol::Qc30),
+        voltage_mv: Some(8_960),
+        current_ma: Some(1_720),
+    };
+    assert_round_trip(&supported);
+    let fallback = FastChargeProbeResult {
+        family: ActiveProtocol::Scp,
+        supported: false,
+        negotiated: Some(ActiveProtocol::Bc12),
+        voltage_mv: Some(5_040),
+        current_ma: Some(1_050),
+    };
+    assert_round_trip(&fallback);
+    let no_contract = FastChargeProbeResult {
+        family: ActiveProtocol::Pd,
+        supported: false,
+        negotiated: None,
+        voltage_mv: None,
+        current_ma: None,
+    };
+    assert_round_trip(&no_contract);
+
+    let value: Value = serde_json::from_str(&written(&fallback)).unwrap();
+    assert_eq!(value["family"], "scp");
+    assert_eq!(value["negotiated"], "bc12");
+    assert_eq!(
+        serde_json::from_str::<Value>(&written(&no_contract)).unwrap()["negotiated"],
+        Value::Null
+    );
+}
+
+#[test]
+fn power_settings_round_trip_and_ignore_runtime_fields() {
+    let fixed_voltages_mv = [9_000, 20_000]
+        .into_iter()
+        .try_fold(PdFixedVoltageSet::empty(), PdFixedVoltageSet::with)
+        .unwrap();
+    let mut settings = PowerSettings {
+        hardware: PowerHardwareKind::Sw2303,
+        tps_mode: TpsMode::Manual,
+        light_load_mode: LightLoadMode::Fpwm,
+        capability: PowerCapability {
+            profile: PowerCapabilityProfile::Full,
+            power_watts: 65,
+            protocols: PowerProtocols {
+                pd: true,
+                qc20: true,
+                qc30: false,
+                fcp: true,
+                afc: true,
+                scp: false,
+                pe20: true,
+                bc12: true,
+                sfcp: false,
+            },
+            pd: PowerPdCapability {
+                pps: true,
+                fixed_voltages_mv,
+            },
+            current: Some(PowerCurrentLimits {
+                pps3_limit_ma: 3_000,
+                pd_pps_5a: false,
+                type_c_broadcast_ma: 1_500,
+                scp_limit_ma: 5_000,
+                fcp_afc_sfcp_limit_ma: 3_250,
+            }),
+            fast_charge: Some(PowerFastCharge {
+                qc20_20v_enabled: true,
+                qc30_20v_enabled: false,
+                pe20_20v_enabled: true,
+                non_pd_12v_enabled: true,
+            }),
+        },
+        manual: ManualPowerSettings {
+            voltage_mv: 12_000,
+            current_limit_ma: 2_000,
+            usb_c_path_mode: ManualUsbCPathMode::Force,
+        },
+    };
+    assert_round_trip(&settings);
+    assert_eq!(
+        serde_json::to_value(settings).unwrap()["capability"]["pd"]["fixed_voltages_mv"],
+        json!([9000, 20000])
+    );
+
+    let mut observed = serde_json::to_value(settings).unwrap();
+    observed["persisted"] = json!(true);
+    observed["sw2303_line_compensation"] = json!("50mohm");
+    observed["runtime"] = json!({"output_enabled": true, "discharge_enabled": false});
+    observed["manual"]["path_policy"] = json!("force_close");
+    observed["lock"] = json!({"owner": 7, "expires_at_ms": 1234});
+    assert_eq!(
+        serde_json::from_value::<PowerSettings>(observed).unwrap(),
+        settings
+    );
+
+    settings.capability.current = None;
+    settings.capability.fast_charge = None;
+    assert_round_trip(&settings);
+    assert!(
+        serde_json::from_value::<PdFixedVoltageSet>(json!([9000, 5000])).is_err(),
+        "5 V is always offered and not a fixed-voltage option"
+    );
+}
+
+#[test]
+fn older_firmware_shapes_still_decode() {
+    // USB `ports.get` before port capabilities were added, and `info` before
+    // `scpi`/`jsonl_tcp` existed.
+    let port: Port = serde_json::from_value(json!({
+        "portId": "port_c",
+        "label": "USB-C",
+        "telemetry": {
+            "status": "not_inserted",
+            "voltage_mv": null,
+            "current_ma": null,
+            "power_mw": null,
+            "sample_uptime_ms": 10
+        }
+    }))
+    .unwrap();
+    assert_eq!(port.port_id, PortId::PortC);
+    assert_eq!(port.telemetry_raw, None);
+    assert_eq!(port.capabilities, PortCapabilities::default());
+    assert_eq!(port.charge_complete, None);
+
+    let capabilities: Capabilities =
+        serde_json::from_value(json!({"identify": true, "sound": true})).unwrap();
+    assert!(capabilities.identify && !capabilities.jsonl_tcp);
+
+    let hub: HubStatus = serde_json::from_value(json!({"usb_c_downstream_route": "mcu"})).unwrap();
+    assert_eq!(hub, HubStatus::unknown());
+
+    // Port state before charge-only mode existed.
+    let state: PortState = serde_json::from_value(json!({
+        "power_enabled": true,
+        "data_connected": true,
+        "replugging": false,
+        "busy": false
+    }))
+    .unwrap();
+    assert_eq!(state.data_mode, PortDataMode::Connected);
+}
+
+#[test]
+fn method_names_and_error_codes_are_unique() {
+    for list in [methods::ALL, errors::ALL, errors::HOST_ONLY] {
+        for (index, name) in list.iter().enumerate() {
+            assert!(!list[..index].contains(name), "duplicate {name}");
+            assert!(
+                name.bytes().all(|b| b.is_ascii_lowercase()
+                    || b.is_ascii_digit()
+                    || b == b'_'
+                    || b == b'.'),
+                "{name}"
+            );
+        }
+    }
+    for code in errors::HOST_ONLY {
+        assert!(!errors::ALL.contains(code), "{code} is also a device code");
+    }
+}
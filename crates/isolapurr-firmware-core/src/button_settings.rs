@@ -0,0 +1,305 @@
+/// Gap after a short release during which a second short press counts as a double press.
+pub const BUTTON_DOUBLE_PRESS_WINDOW_MS: u64 = 350;
+
+/// USB-C power caps stepped through by [`ButtonAction::CyclePreset`], highest first.
+pub const BUTTON_PRESET_POWER_STEPS: [u8; 5] = [100, 65, 45, 30, 20];
+
+pub const BUTTON_GESTURE_COUNT: usize = 8;
+
+/// A press pattern the button handler recognises. Left is the USB-A button,
+/// right the USB-C button; combo gestures press both together.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ButtonGesture {
+    LeftShort,
+    LeftLong,
+    LeftDouble,
+    RightShort,
+    RightLong,
+    RightDouble,
+    ComboShort,
+    ComboLong,
+}
+
+impl ButtonGesture {
+    pub const ALL: [ButtonGesture; BUTTON_GESTURE_COUNT] = [
+        ButtonGesture::LeftShort,
+        ButtonGesture::LeftLong,
+        ButtonGesture::LeftDouble,
+        ButtonGesture::RightShort,
+        ButtonGesture::RightLong,
+        ButtonGesture::RightDouble,
+        ButtonGesture::ComboShort,
+        ButtonGesture::ComboLong,
+    ];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ButtonGesture::LeftShort => "left_short",
+            ButtonGesture::LeftLong => "left_long",
+            ButtonGesture::LeftDouble => "left_double",
+            ButtonGesture::RightShort => "right_short",
+            ButtonGesture::RightLong => "right_long",
+            ButtonGesture::RightDouble => "right_double",
+            ButtonGesture::ComboShort => "combo_short",
+            ButtonGesture::ComboLong => "combo_long",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|gesture| gesture.as_str() == value)
+    }
+
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    pub const fn is_combo(self) -> bool {
+        matches!(self, ButtonGesture::ComboShort | ButtonGesture::ComboLong)
+    }
+}
+
+/// What a gesture does. Port actions apply to the port of the pressed button.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ButtonAction {
+    None,
+    /// Turn the port off, or back on if it is off.
+    PowerToggle,
+    /// Briefly disconnect the data lines; powers the port on if it is off.
+    DataReplug,
+    /// Step the USB-C power cap through [`BUTTON_PRESET_POWER_STEPS`].
+    CyclePreset,
+    Identify,
+    /// Ignore every gesture until a long combo unlocks the buttons.
+    Lock,
+    SettingsMenu,
+}
+
+impl ButtonAction {
+    pub const ALL: [ButtonAction; 7] = [
+        ButtonAction::None,
+        ButtonAction::PowerToggle,
+        ButtonAction::DataReplug,
+        ButtonAction::CyclePreset,
+        ButtonAction::Identify,
+        ButtonAction::Lock,
+        ButtonAction::SettingsMenu,
+    ];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ButtonAction::None => "none",
+            ButtonAction::PowerToggle => "power_toggle",
+            ButtonAction::DataReplug => "data_replug",
+            ButtonAction::CyclePreset => "cycle_preset",
+            ButtonAction::Identify => "identify",
+            ButtonAction::Lock => "lock",
+            ButtonAction::SettingsMenu => "settings_menu",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.as_str() == value)
+    }
+
+    pub const fn code(self) -> u8 {
+        self as u8
+    }
+
+    pub fn from_code(code: u8) -> Option<Self> {
+        Self::ALL.get(code as usize).copied()
+    }
+
+    pub const fn targets_port(self) -> bool {
+        matches!(self, ButtonAction::PowerToggle | ButtonAction::DataReplug)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ButtonSettingsError {
+    /// Combo gestures press both buttons, so they have no port to act on.
+    PortActionOnCombo,
+}
+
+/// Persisted gesture-to-action table plus the lock flag.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ButtonSettings {
+    actions: [ButtonAction; BUTTON_GESTURE_COUNT],
+    pub locked: bool,
+}
+
+impl ButtonSettings {
+    /// The historical fixed behavior: short press replugs data, long press
+    /// toggles power, a long combo opens the settings menu.
+    pub const fn defaults() -> Self {
+        Self {
+            actions: [
+                ButtonAction::DataReplug,
+                ButtonAction::PowerToggle,
+                ButtonAction::None,
+                ButtonAction::DataReplug,
+                ButtonAction::PowerToggle,
+                ButtonAction::None,
+                ButtonAction::None,
+                ButtonAction::SettingsMenu,
+            ],
+            locked: false,
+        }
+    }
+
+    pub const fn action(&self, gesture: ButtonGesture) -> ButtonAction {
+        self.actions[gesture.index()]
+    }
+
+    pub fn set_action(&mut self, gesture: ButtonGesture, action: ButtonAction) {
+        self.actions[gesture.index()] = action;
+    }
+
+    /// Whether short presses on this button must wait out the double-press window.
+    pub const fn waits_for_double(&self, double: ButtonGesture) -> bool {
+        !matches!(self.actions[double.index()], ButtonAction::None)
+    }
+
+    pub fn validated(self) -> Result<Self, ButtonSettingsError> {
+        let combo_port_action = ButtonGesture::ALL
+            .into_iter()
+            .any(|gesture| gesture.is_combo() && self.action(gesture).targets_port());
+        if combo_port_action {
+            return Err(ButtonSettingsError::PortActionOnCombo);
+        }
+        Ok(self)
+    }
+}
+
+/// Next power cap after `current_watts`, wrapping back to the highest step.
+pub fn next_preset_power_watts(current_watts: u8) -> u8 {
+    BUTTON_PRESET_POWER_STEPS
+        .iter()
+        .copied()
+        .find(|step| *step < current_watts)
+        .unwrap_or(BUTTON_PRESET_POWER_STEPS[0])
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ShortPress {
+    Single,
+    Double,
+}
+
+/// Holds back a short press for [`BUTTON_DOUBLE_PRESS_WINDOW_MS`] so a second
+/// one can turn it into a double press.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DoublePressTracker {
+    single_deadline_ms: Option<u64>,
+}
+
+impl DoublePressTracker {
+    pub const fn new() -> Self {
+        Self {
+            single_deadline_ms: None,
+        }
+    }
+
+    /// Records a valid short release. `None` means the press is held back.
+    pub fn short_release(&mut self, now_ms: u64, waits_for_double: bool) -> Option<ShortPress> {
+        if !waits_for_double {
+            self.single_deadline_ms = None;
+            return Some(ShortPress::Single);
+        }
+        match self.single_deadline_ms.take() {
+            Some(deadline) if now_ms <= deadline => Some(ShortPress::Double),
+            _ => {
+                self.single_deadline_ms = Some(now_ms + BUTTON_DOUBLE_PRESS_WINDOW_MS);
+                None
+            }
+        }
+    }
+
+    /// Releases a held-back single press once its window has passed while the
+    /// button is up.
+    pub fn poll(&mut self, now_ms: u64, pressed: bool) -> Option<ShortPress> {
+        match self.single_deadline_ms {
+            Some(deadline) if now_ms > deadline && !pressed => {
+                self.single_deadline_ms = None;
+                Some(ShortPress::Single)
+            }
+            _ => None,
+        }
+    }
+
+    /// Drops a held-back press, e.g. when the buttons lock or a combo starts.
+    pub fn cancel(&mut self) {
+        self.single_deadline_ms = None;
+    }
+
+    pub const fn pending(&self) -> bool {
+        self.single_deadline_ms.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_match_the_fixed_button_behavior_and_reject_port_combos() {
+        let defaults = ButtonSettings::defaults();
+        assert_eq!(
+            defaults.action(ButtonGesture::LeftShort),
+            ButtonAction::DataReplug
+        );
+        assert_eq!(
+            defaults.action(ButtonGesture::RightLong),
+            ButtonAction::PowerToggle
+        );
+        assert_eq!(
+            defaults.action(ButtonGesture::ComboLong),
+            ButtonAction::SettingsMenu
+        );
+        assert!(!defaults.waits_for_double(ButtonGesture::LeftDouble));
+        assert_eq!(defaults.validated(), Ok(defaults));
+
+        let mut combo = defaults;
+        combo.set_action(ButtonGesture::ComboShort, ButtonAction::PowerToggle);
+        assert_eq!(
+            combo.validated(),
+            Err(ButtonSettingsError::PortActionOnCombo)
+        );
+
+        for gesture in ButtonGesture::ALL {
+            assert_eq!(ButtonGesture::parse(gesture.as_str()), Some(gesture));
+        }
+        for action in ButtonAction::ALL {
+            assert_eq!(ButtonAction::parse(action.as_str()), Some(action));
+            assert_eq!(ButtonAction::from_code(action.code()), Some(action));
+        }
+        assert_eq!(ButtonAction::from_code(7), None);
+    }
+
+    #[test]
+    fn preset_steps_wrap_to_the_highest_cap() {
+        assert_eq!(next_preset_power_watts(100), 65);
+        assert_eq!(next_preset_power_watts(50), 45);
+        assert_eq!(next_preset_power_watts(20), 100);
+    }
+
+    #[test]
+    fn double_press_tracker_holds_back_singles_only_when_needed() {
+        let mut tracker = DoublePressTracker::new();
+        assert_eq!(tracker.short_release(0, false), Some(ShortPress::Single));
+
+        assert_eq!(tracker.short_release(1_000, true), None);
+        assert!(tracker.pending());
+        assert_eq!(tracker.poll(1_200, false), None);
+        assert_eq!(tracker.short_release(1_300, true), Some(ShortPress::Double));
+        assert!(!tracker.pending());
+
+        assert_eq!(tracker.short_release(2_000, true), None);
+        assert_eq!(tracker.poll(2_400, true), None);
+        assert_eq!(tracker.poll(2_400, false), Some(ShortPress::Single));
+        assert_eq!(tracker.poll(2_500, false), None);
+    }
+}
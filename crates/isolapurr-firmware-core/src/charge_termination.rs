@@ -0,0 +1,377 @@
+//! Per-port end-of-charge detection (see
+//! `docs/specs/t4k9c-charge-termination/SPEC.md`).
+
+use isolapurr_api::ports::{ChargeCompletion, ChargeTerminationAction, PortId};
+
+use crate::power_config::TpsMode;
+
+pub const CHARGE_TERMINATION_THRESHOLD_MIN_MA: u16 = 10;
+pub const CHARGE_TERMINATION_THRESHOLD_MAX_MA: u16 = 3_000;
+pub const CHARGE_TERMINATION_HOLD_MIN_S: u16 = 10;
+pub const CHARGE_TERMINATION_HOLD_MAX_S: u16 = 3_600;
+pub const CHARGE_TERMINATION_MIN_CHARGE_MAX_S: u16 = 43_200;
+
+const MW_MS_PER_MWH: u64 = 3_600_000;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ChargeTerminationError {
+    ThresholdOutOfRange,
+    HoldOutOfRange,
+    MinChargeOutOfRange,
+}
+
+/// Detector settings for one port.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ChargeTerminationConfig {
+    pub enabled: bool,
+    pub action: ChargeTerminationAction,
+    /// Charging counts as finished below this current.
+    pub threshold_ma: u16,
+    /// How long the current must stay below the threshold.
+    pub hold_s: u16,
+    /// Charging time before the detector may fire, so the dip while a phone
+    /// negotiates or a battery pack wakes up is not mistaken for a full charge.
+    pub min_charge_s: u16,
+}
+
+impl ChargeTerminationConfig {
+    pub const fn defaults() -> Self {
+        Self {
+            enabled: false,
+            action: ChargeTerminationAction::Notify,
+            threshold_ma: 100,
+            hold_s: 300,
+            min_charge_s: 1_800,
+        }
+    }
+
+    pub fn validated(self) -> Result<Self, ChargeTerminationError> {
+        if !(CHARGE_TERMINATION_THRESHOLD_MIN_MA..=CHARGE_TERMINATION_THRESHOLD_MAX_MA)
+            .contains(&self.threshold_ma)
+        {
+            return Err(ChargeTerminationError::ThresholdOutOfRange);
+        }
+        if !(CHARGE_TERMINATION_HOLD_MIN_S..=CHARGE_TERMINATION_HOLD_MAX_S).contains(&self.hold_s) {
+            return Err(ChargeTerminationError::HoldOutOfRange);
+        }
+        if self.min_charge_s > CHARGE_TERMINATION_MIN_CHARGE_MAX_S {
+            return Err(ChargeTerminationError::MinChargeOutOfRange);
+        }
+        Ok(self)
+    }
+}
+
+/// Persisted in EEPROM U21 (`ports.charge_termination_set`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ChargeTerminationSettings {
+    pub usb_a: ChargeTerminationConfig,
+    pub usb_c: ChargeTerminationConfig,
+}
+
+impl ChargeTerminationSettings {
+    pub const fn defaults() -> Self {
+        Self {
+            usb_a: ChargeTerminationConfig::defaults(),
+            usb_c: ChargeTerminationConfig::defaults(),
+        }
+    }
+
+    pub const fn port(&self, port: PortId) -> ChargeTerminationConfig {
+        match port {
+            PortId::PortA => self.usb_a,
+            PortId::PortC => self.usb_c,
+        }
+    }
+
+    pub const fn with_port(self, port: PortId, config: ChargeTerminationConfig) -> Self {
+        match port {
+            PortId::PortA => Self {
+                usb_a: config,
+                ..self
+            },
+            PortId::PortC => Self {
+                usb_c: config,
+                ..self
+            },
+        }
+    }
+
+    pub fn validated(self) -> Result<Self, ChargeTerminationError> {
+        self.usb_a.validated()?;
+        self.usb_c.validated()?;
+        Ok(self)
+    }
+}
+
+/// The action a port actually takes. USB-A has a fixed 5 V rail, so
+/// [`ChargeTerminationAction::Drop5v`] only notifies there.
+pub const fn applied_action(
+    port: PortId,
+    action: ChargeTerminationAction,
+) -> ChargeTerminationAction {
+    match (port, action) {
+        (PortId::PortA, ChargeTerminationAction::Drop5v) => ChargeTerminationAction::Notify,
+        _ => action,
+    }
+}
+
+/// `drop_5v` narrows the SW2303 offers, which only move the USB-C output while
+/// the TPS follows the PD contract. A manual setpoint keeps its voltage, so the
+/// action is reported as not applied there.
+pub const fn drop_5v_applies(tps_mode: TpsMode) -> bool {
+    matches!(tps_mode, TpsMode::AutoFollow)
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Phase {
+    /// No load, or a load that has not drawn the threshold current yet.
+    Idle,
+    Charging {
+        started_ms: u64,
+        below_since_ms: Option<u64>,
+    },
+    /// Fired; waits for the current to rise again before re-arming.
+    Complete,
+}
+
+/// Tracks one port's charge session from telemetry samples.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ChargeTerminationDetector {
+    port: PortId,
+    phase: Phase,
+    last_sample_ms: Option<u64>,
+    energy_mw_ms: u64,
+    last_completion: Option<ChargeCompletion>,
+}
+
+impl ChargeTerminationDetector {
+    pub const fn new(port: PortId) -> Self {
+        Self {
+            port,
+            phase: Phase::Idle,
+            last_sample_ms: None,
+            energy_mw_ms: 0,
+            last_completion: None,
+        }
+    }
+
+    /// Completed session reported in the port snapshot. Kept after the port is
+    /// switched off and replaced once a new charge starts.
+    pub const fn last_completion(&self) -> Option<ChargeCompletion> {
+        self.last_completion
+    }
+
+    pub const fn charging(&self) -> bool {
+        matches!(self.phase, Phase::Charging { .. })
+    }
+
+    /// Feeds one telemetry sample. Returns the completion on the sample that
+    /// ends the session; the caller then applies `completion.action`.
+    pub fn update(
+        &mut self,
+        config: &ChargeTerminationConfig,
+        now_ms: u64,
+        attached: bool,
+        current_ma: Option<u32>,
+        power_mw: Option<u32>,
+    ) -> Option<ChargeCompletion> {
+        if !config.enabled || !attached {
+            self.phase = Phase::Idle;
+            self.last_sample_ms = None;
+            return None;
+        }
+        let current_ma = current_ma?;
+        let previous_ms = self.last_sample_ms.replace(now_ms);
+        let above = current_ma >= u32::from(config.threshold_ma);
+
+        match self.phase {
+            Phase::Idle | Phase::Complete => {
+                if above {
+                    self.phase = Phase::Charging {
+                        started_ms: now_ms,
+                        below_since_ms: None,
+                    };
+                    self.energy_mw_ms = 0;
+                    self.last_completion = None;
+                }
+                None
+            }
+            Phase::Charging {
+                started_ms,
+                below_since_ms,
+            } => {
+                if let (Some(previous_ms), Some(power_mw)) = (previous_ms, power_mw) {
+                    self.energy_mw_ms = self.energy_mw_ms.saturating_add(
+                        u64::from(power_mw).saturating_mul(now_ms.saturating_sub(previous_ms)),
+                    );
+                }
+                if above {
+                    self.phase = Phase::Charging {
+                        started_ms,
+                        below_since_ms: None,
+                    };
+                    return None;
+                }
+                let below_since_ms = below_since_ms.unwrap_or(now_ms);
+                let held =
+                    now_ms.saturating_sub(below_since_ms) >= u64::from(config.hold_s) * 1_000;
+                let charged =
+                    now_ms.saturating_sub(started_ms) >= u64::from(config.min_charge_s) * 1_000;
+                if !(held && charged) {
+                    self.phase = Phase::Charging {
+                        started_ms,
+                        below_since_ms: Some(below_since_ms),
+                    };
+                    return None;
+                }
+                let completion = ChargeCompletion {
+                    completed_uptime_ms: now_ms,
+                    charge_ms: now_ms.saturating_sub(started_ms),
+                    energy_mwh: (self.energy_mw_ms / MW_MS_PER_MWH).min(u64::from(u32::MAX)) as u32,
+                    action: applied_action(self.port, config.action),
+                };
+                self.phase = Phase::Complete;
+                self.last_completion = Some(completion);
+                Some(completion)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ChargeTerminationConfig, ChargeTerminationDetector, ChargeTerminationError,
+        ChargeTerminationSettings, drop_5v_applies,
+    };
+    use crate::power_config::TpsMode;
+    use isolapurr_api::ports::{ChargeTerminationAction, PortId};
+
+    fn config() -> ChargeTerminationConfig {
+        ChargeTerminationConfig {
+            enabled: true,
+            action: ChargeTerminationAction::Drop5v,
+            threshold_ma: 100,
+            hold_s: 60,
+            min_charge_s: 600,
+        }
+    }
+
+    /// Feeds one sample per second from `from_s` up to (excluding) `to_s`.
+    fn run(
+        detector: &mut ChargeTerminationDetector,
+        config: &ChargeTerminationConfig,
+        from_s: u64,
+        to_s: u64,
+        current_ma: u32,
+    ) -> Option<(u64, isolapurr_api::ports::ChargeCompletion)> {
+        (from_s..to_s).find_map(|second| {
+            detector
+                .update(
+                    config,
+                    second * 1_000,
+                    true,
+                    Some(current_ma),
+                    Some(current_ma * 5),
+                )
+                .map(|completion| (second, completion))
+        })
+    }
+
+    #[test]
+    fn fires_after_hold_once_min_charge_has_elapsed() {
+        let config = config();
+        let mut detector = ChargeTerminationDetector::new(PortId::PortC);
+        assert_eq!(run(&mut detector, &config, 0, 720, 1_000), None);
+        assert!(detector.charging());
+
+        let (second, completion) = run(&mut detector, &config, 720, 900, 50).unwrap();
+        assert_eq!(second, 780);
+        assert_eq!(completion.charge_ms, 780_000);
+        assert_eq!(completion.action, ChargeTerminationAction::Drop5v);
+        // 719 s at 5 W plus 61 s at 0.25 W, rounded down.
+        assert_eq!(completion.energy_mwh, 1_002);
+        assert_eq!(detector.last_completion(), Some(completion));
+
+        // Stays complete while the current is low, re-arms when it rises.
+        assert_eq!(run(&mut detector, &config, 900, 2_000, 50), None);
+        assert_eq!(detector.last_completion(), Some(completion));
+        assert_eq!(run(&mut detector, &config, 2_000, 2_001, 500), None);
+        assert!(detector.charging());
+        assert_eq!(detector.last_completion(), None);
+    }
+
+    #[test]
+    fn early_dips_and_short_lulls_do_not_fire() {
+        let config = config();
+        let mut detector = ChargeTerminationDetector::new(PortId::PortA);
+        assert_eq!(run(&mut detector, &config, 0, 10, 1_000), None);
+        // Below threshold for longer than the hold, but before min charge.
+        assert_eq!(run(&mut detector, &config, 10, 300, 50), None);
+        assert_eq!(run(&mut detector, &config, 300, 620, 1_000), None);
+        // A lull shorter than the hold, then charging resumes.
+        assert_eq!(run(&mut detector, &config, 620, 650, 50), None);
+        assert_eq!(run(&mut detector, &config, 650, 651, 1_000), None);
+        let (second, completion) = run(&mut detector, &config, 651, 800, 50).unwrap();
+        assert_eq!(second, 711);
+        // USB-A cannot drop to 5 V, so it only notifies.
+        assert_eq!(completion.action, ChargeTerminationAction::Notify);
+    }
+
+    #[test]
+    fn detach_or_disable_resets_the_session() {
+        let mut config = config();
+        let mut detector = ChargeTerminationDetector::new(PortId::PortC);
+        assert_eq!(run(&mut detector, &config, 0, 700, 1_000), None);
+        assert_eq!(detector.update(&config, 700_000, false, None, None), None);
+        assert!(!detector.charging());
+        // Low current after re-attach is not the end of a charge.
+        assert_eq!(run(&mut detector, &config, 701, 2_000, 50), None);
+
+        assert_eq!(run(&mut detector, &config, 2_000, 2_700, 1_000), None);
+        config.enabled = false;
+        assert_eq!(run(&mut detector, &config, 2_700, 4_000, 50), None);
+        assert!(!detector.charging());
+    }
+
+    #[test]
+    fn settings_reject_out_of_range_values() {
+        let settings = ChargeTerminationSettings::defaults().with_port(PortId::PortC, config());
+        assert_eq!(settings.port(PortId::PortC), config());
+        assert_eq!(settings.validated(), Ok(settings));
+        assert_eq!(
+            ChargeTerminationConfig {
+                threshold_ma: 5,
+                ..config()
+            }
+            .validated(),
+            Err(ChargeTerminationError::ThresholdOutOfRange)
+        );
+        assert_eq!(
+            ChargeTerminationConfig {
+                hold_s: 0,
+                ..config()
+            }
+            .validated(),
+            Err(ChargeTerminationError::HoldOutOfRange)
+        );
+        assert_eq!(
+            ChargeTerminationSettings::defaults()
+                .with_port(
+                    PortId::PortA,
+                    ChargeTerminationConfig {
+                        min_charge_s: 50_000,
+                        ..config()
+                    }
+                )
+                .validated(),
+            Err(ChargeTerminationError::MinChargeOutOfRange)
+        );
+    }
+
+    #[test]
+    fn drop_5v_needs_auto_follow() {
+        assert!(drop_5v_applies(TpsMode::AutoFollow));
+        assert!(!drop_5v_applies(TpsMode::Manual));
+    }
+}
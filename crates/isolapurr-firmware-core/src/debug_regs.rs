@@ -0,0 +1,281 @@
+//! Unlock handshake, register maps and write log for raw SW2303/TPS55288
+//! register access over USB (see `docs/specs/h2r8v-register-debug/SPEC.md`).
+
+use isolapurr_api::diagnostics::RegTarget;
+
+/// How long a `debug.unlock` challenge can be confirmed.
+pub const DEBUG_CHALLENGE_TTL_MS: u64 = 30_000;
+/// An unlocked session with no `debug.*` traffic for this long ends on its
+/// own, and the saved power config is re-applied.
+pub const DEBUG_IDLE_TIMEOUT_MS: u64 = 120_000;
+pub const DEBUG_WRITE_LOG_LEN: usize = 16;
+/// Longest dump, the whole SW2303 register space.
+pub const DEBUG_MAX_DUMP_LEN: usize = 256;
+
+/// Registers of each chip; SW2303 exposes a full 8-bit space, TPS55288 only
+/// `REF_LSB` (0x00) through `STATUS` (0x07).
+pub const fn register_count(target: RegTarget) -> u16 {
+    match target {
+        RegTarget::Sw2303 => 256,
+        RegTarget::Tps55288 => 8,
+    }
+}
+
+pub const fn register_in_map(target: RegTarget, reg: u8) -> bool {
+    (reg as u16) < register_count(target)
+}
+
+/// Resolves `debug.reg_dump` bounds; both default to the whole map.
+pub fn dump_range(target: RegTarget, start: Option<u8>, count: Option<u16>) -> Option<(u8, u16)> {
+    let start = start.unwrap_or(0);
+    if !register_in_map(target, start) {
+        return None;
+    }
+    let available = register_count(target) - start as u16;
+    let count = count.unwrap_or(available);
+    (count > 0 && count <= available).then_some((start, count))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegWrite {
+    pub uptime_ms: u64,
+    pub target: RegTarget,
+    pub reg: u8,
+    pub value: u8,
+    /// Register value before the write; `None` when the read failed.
+    pub previous: Option<u8>,
+    /// Register value read back after the write; `None` when it failed.
+    pub readback: Option<u8>,
+    pub ok: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SessionState {
+    Locked,
+    Challenge { code: u32, expires_at_ms: u64 },
+    Unlocked { last_activity_ms: u64 },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnlockStep {
+    /// Send the code back with `debug.unlock` to finish unlocking.
+    Challenge(u32),
+    AlreadyUnlocked,
+}
+
+/// The debug session: locked by default, unlocked by confirming a one-time
+/// challenge, and back to locked on `debug.exit`, idle timeout or reboot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DebugSession {
+    state: SessionState,
+    writes: [Option<RegWrite>; DEBUG_WRITE_LOG_LEN],
+    next_write: usize,
+    total_writes: u32,
+}
+
+impl DebugSession {
+    pub const fn new() -> Self {
+        Self {
+            state: SessionState::Locked,
+            writes: [None; DEBUG_WRITE_LOG_LEN],
+            next_write: 0,
+            total_writes: 0,
+        }
+    }
+
+    /// Issues a six-hex-digit challenge from `nonce`. A new challenge replaces
+    /// any earlier one.
+    pub fn begin_unlock(&mut self, now_ms: u64, nonce: u32) -> UnlockStep {
+        if self.is_unlocked() {
+            self.touch(now_ms);
+            return UnlockStep::AlreadyUnlocked;
+        }
+        let code = nonce & 0x00FF_FFFF;
+        self.state = SessionState::Challenge {
+            code,
+            expires_at_ms: now_ms.saturating_add(DEBUG_CHALLENGE_TTL_MS),
+        };
+        UnlockStep::Challenge(code)
+    }
+
+    /// Confirms the pending challenge. Each challenge gets one attempt; a
+    /// wrong or late code leaves the session locked.
+    pub fn confirm_unlock(&mut self, now_ms: u64, code: &str) -> bool {
+        let SessionState::Challenge {
+            code: expected,
+            expires_at_ms,
+        } = self.state
+        else {
+            return self.is_unlocked();
+        };
+        let matches = code.len() == 6
+            && u32::from_str_radix(code, 16).is_ok_and(|code| code == expected)
+            && now_ms < expires_at_ms;
+        self.state = if matches {
+            SessionState::Unlocked {
+                last_activity_ms: now_ms,
+            }
+        } else {
+            SessionState::Locked
+        };
+        matches
+    }
+
+    pub const fn is_unlocked(&self) -> bool {
+        matches!(self.state, SessionState::Unlocked { .. })
+    }
+
+    pub fn touch(&mut self, now_ms: u64) {
+        if let SessionState::Unlocked { last_activity_ms } = &mut self.state {
+            *last_activity_ms = now_ms;
+        }
+    }
+
+    pub fn idle_remaining_ms(&self, now_ms: u64) -> Option<u64> {
+        match self.state {
+            SessionState::Unlocked { last_activity_ms } => {
+                Some(DEBUG_IDLE_TIMEOUT_MS.saturating_sub(now_ms.saturating_sub(last_activity_ms)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Locks an unlocked session that sat idle too long. Returns true when the
+    /// caller must restore the power config.
+    pub fn expire(&mut self, now_ms: u64) -> bool {
+        if self.idle_remaining_ms(now_ms) == Some(0) {
+            return self.lock();
+        }
+        if let SessionState::Challenge { expires_at_ms, .. } = self.state
+            && now_ms >= expires_at_ms
+        {
+            self.state = SessionState::Locked;
+        }
+        false
+    }
+
+    /// Returns true when the session was unlocked, i.e. registers may have
+    /// been touched and the power config must be restored.
+    pub fn lock(&mut self) -> bool {
+        let was_unlocked = self.is_unlocked();
+        self.state = SessionState::Locked;
+        was_unlocked
+    }
+
+    pub fn record_write(&mut self, write: RegWrite) {
+        self.writes[self.next_write] = Some(write);
+        self.next_write = (self.next_write + 1) % DEBUG_WRITE_LOG_LEN;
+        self.total_writes = self.total_writes.saturating_add(1);
+    }
+
+    /// The most recent writes, oldest first. The log survives `lock` so it can
+    /// be read after the session ends.
+    pub fn writes(&self) -> impl Iterator<Item = RegWrite> + '_ {
+        (0..DEBUG_WRITE_LOG_LEN)
+            .filter_map(move |offset| self.writes[(self.next_write + offset) % DEBUG_WRITE_LOG_LEN])
+    }
+
+    pub const fn total_writes(&self) -> u32 {
+        self.total_writes
+    }
+}
+
+impl Default for DebugSession {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DEBUG_CHALLENGE_TTL_MS, DEBUG_IDLE_TIMEOUT_MS, DEBUG_WRITE_LOG_LEN, DebugSession, RegWrite,
+        UnlockStep, dump_range, register_in_map,
+    };
+    use isolapurr_api::diagnostics::RegTarget;
+
+    fn unlocked_at(now_ms: u64) -> DebugSession {
+        let mut session = DebugSession::new();
+        assert_eq!(
+            session.begin_unlock(now_ms, 0xAB12_34CD),
+            UnlockStep::Challenge(0x12_34CD)
+        );
+        assert!(session.confirm_unlock(now_ms + 10, "1234cd"));
+        session
+    }
+
+    #[test]
+    fn unlock_needs_the_issued_code_once() {
+        let session = unlocked_at(1_000);
+        assert!(session.is_unlocked());
+
+        let mut session = DebugSession::new();
+        session.begin_unlock(1_000, 0x12_34CD);
+        assert!(!session.confirm_unlock(1_010, "1234CE"));
+        assert!(!session.confirm_unlock(1_020, "1234CD"));
+        assert!(!session.is_unlocked());
+
+        session.begin_unlock(2_000, 0x12_34CD);
+        assert!(!session.confirm_unlock(2_000 + DEBUG_CHALLENGE_TTL_MS, "1234CD"));
+
+        let mut session = DebugSession::new();
+        assert!(!session.confirm_unlock(0, "000000"));
+    }
+
+    #[test]
+    fn idle_sessions_expire_and_ask_for_a_restore() {
+        let mut session = unlocked_at(0);
+        session.touch(50_000);
+        assert!(!session.expire(50_000 + DEBUG_IDLE_TIMEOUT_MS - 1));
+        assert_eq!(
+            session.idle_remaining_ms(50_000 + DEBUG_IDLE_TIMEOUT_MS - 1),
+            Some(1)
+        );
+        assert!(session.expire(50_000 + DEBUG_IDLE_TIMEOUT_MS));
+        assert!(!session.is_unlocked());
+        assert!(!session.lock());
+    }
+
+    #[test]
+    fn write_log_keeps_the_latest_entries_oldest_first() {
+        let mut session = unlocked_at(0);
+        for reg in 0..(DEBUG_WRITE_LOG_LEN as u8 + 2) {
+            session.record_write(RegWrite {
+                uptime_ms: reg as u64,
+                target: RegTarget::Sw2303,
+                reg,
+                value: 0x5A,
+                previous: Some(0),
+                readback: Some(0x5A),
+                ok: true,
+            });
+        }
+        assert!(session.lock());
+
+        let regs: [u8; DEBUG_WRITE_LOG_LEN] =
+            core::array::from_fn(|index| session.writes().nth(index).unwrap().reg);
+        assert_eq!(regs[0], 2);
+        assert_eq!(regs[DEBUG_WRITE_LOG_LEN - 1], DEBUG_WRITE_LOG_LEN as u8 + 1);
+        assert_eq!(session.total_writes(), DEBUG_WRITE_LOG_LEN as u32 + 2);
+    }
+
+    #[test]
+    fn register_maps_bound_reads_and_dumps() {
+        assert!(register_in_map(RegTarget::Sw2303, 0xFF));
+        assert!(register_in_map(RegTarget::Tps55288, 0x07));
+        assert!(!register_in_map(RegTarget::Tps55288, 0x08));
+
+        assert_eq!(dump_range(RegTarget::Tps55288, None, None), Some((0, 8)));
+        assert_eq!(
+            dump_range(RegTarget::Sw2303, Some(0x10), None),
+            Some((0x10, 240))
+        );
+        assert_eq!(
+            dump_range(RegTarget::Tps55288, Some(6), Some(2)),
+            Some((6, 2))
+        );
+        assert_eq!(dump_range(RegTarget::Tps55288, Some(6), Some(3)), None);
+        assert_eq!(dump_range(RegTarget::Tps55288, Some(8), None), None);
+        assert_eq!(dump_range(RegTarget::Sw2303, None, Some(0)), None);
+    }
+}
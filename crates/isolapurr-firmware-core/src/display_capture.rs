@@ -0,0 +1,148 @@
+//! Encoders for exporting the RGB565 framebuffer as a screenshot.
+
+use core::fmt::{self, Write};
+
+/// File header (14) + BITMAPINFOHEADER (40) + RGB565 channel masks (12).
+pub const BMP_RGB565_HEADER_LEN: usize = 66;
+
+const BMP_PIXEL_OFFSET: u32 = BMP_RGB565_HEADER_LEN as u32;
+const BI_BITFIELDS: u32 = 3;
+
+/// Bytes per BMP row; rows are padded to a multiple of four bytes.
+pub const fn bmp_rgb565_row_stride(width: u16) -> usize {
+    (width as usize * 2 + 3) & !3
+}
+
+/// Total BMP file size for a `width` x `height` RGB565 image.
+pub const fn bmp_rgb565_file_len(width: u16, height: u16) -> usize {
+    BMP_RGB565_HEADER_LEN + bmp_rgb565_row_stride(width) * height as usize
+}
+
+/// Builds a top-down 16-bit BI_BITFIELDS header, so framebuffer rows can be
+/// streamed in order as little-endian pixels without conversion.
+pub fn bmp_rgb565_header(width: u16, height: u16) -> [u8; BMP_RGB565_HEADER_LEN] {
+    let mut header = [0u8; BMP_RGB565_HEADER_LEN];
+    let image_len = (bmp_rgb565_row_stride(width) * height as usize) as u32;
+    let file_len = BMP_PIXEL_OFFSET + image_len;
+
+    header[0..2].copy_from_slice(b"BM");
+    header[2..6].copy_from_slice(&file_len.to_le_bytes());
+    header[10..14].copy_from_slice(&BMP_PIXEL_OFFSET.to_le_bytes());
+
+    header[14..18].copy_from_slice(&40u32.to_le_bytes());
+    header[18..22].copy_from_slice(&(width as i32).to_le_bytes());
+    // Negative height marks the rows as top-down.
+    header[22..26].copy_from_slice(&(-(height as i32)).to_le_bytes());
+    header[26..28].copy_from_slice(&1u16.to_le_bytes());
+    header[28..30].copy_from_slice(&16u16.to_le_bytes());
+    header[30..34].copy_from_slice(&BI_BITFIELDS.to_le_bytes());
+    header[34..38].copy_from_slice(&image_len.to_le_bytes());
+    // 2835 px/m = 72 dpi.
+    header[38..42].copy_from_slice(&2835u32.to_le_bytes());
+    header[42..46].copy_from_slice(&2835u32.to_le_bytes());
+
+    header[54..58].copy_from_slice(&0xF800u32.to_le_bytes());
+    header[58..62].copy_from_slice(&0x07E0u32.to_le_bytes());
+    header[62..66].copy_from_slice(&0x001Fu32.to_le_bytes());
+    header
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Streaming standard (padded) base64 encoder, so a whole frame never has to
+/// be staged as bytes before it is written out.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Base64Encoder {
+    pending: [u8; 3],
+    pending_len: u8,
+}
+
+impl Base64Encoder {
+    pub const fn new() -> Self {
+        Self {
+            pending: [0; 3],
+            pending_len: 0,
+        }
+    }
+
+    pub fn push<W: Write>(&mut self, bytes: &[u8], out: &mut W) -> fmt::Result {
+        for &byte in bytes {
+            self.pending[self.pending_len as usize] = byte;
+            self.pending_len += 1;
+            if self.pending_len == 3 {
+                write_base64_group(self.pending, 3, out)?;
+                self.pending_len = 0;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn finish<W: Write>(self, out: &mut W) -> fmt::Result {
+        if self.pending_len == 0 {
+            return Ok(());
+        }
+        let mut group = [0u8; 3];
+        group[..self.pending_len as usize]
+            .copy_from_slice(&self.pending[..self.pending_len as usize]);
+        write_base64_group(group, self.pending_len as usize, out)
+    }
+}
+
+fn write_base64_group<W: Write>(group: [u8; 3], len: usize, out: &mut W) -> fmt::Result {
+    let value = (group[0] as u32) << 16 | (group[1] as u32) << 8 | group[2] as u32;
+    for index in 0..4 {
+        if index > len {
+            out.write_char('=')?;
+        } else {
+            let sextet = (value >> (18 - index * 6)) & 0x3F;
+            out.write_char(BASE64_ALPHABET[sextet as usize] as char)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BMP_RGB565_HEADER_LEN, Base64Encoder, bmp_rgb565_file_len, bmp_rgb565_header,
+        bmp_rgb565_row_stride,
+    };
+
+    fn encode_chunks(chunks: &[&[u8]]) -> heapless::String<16> {
+        let mut out = heapless::String::new();
+        let mut encoder = Base64Encoder::new();
+        for chunk in chunks {
+            encoder.push(chunk, &mut out).unwrap();
+        }
+        encoder.finish(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn base64_matches_rfc4648_vectors_across_chunk_boundaries() {
+        assert_eq!(encode_chunks(&[b""]), "");
+        assert_eq!(encode_chunks(&[b"f"]), "Zg==");
+        assert_eq!(encode_chunks(&[b"fo"]), "Zm8=");
+        assert_eq!(encode_chunks(&[b"foo"]), "Zm9v");
+        assert_eq!(encode_chunks(&[b"f", b"oob", b"a"]), "Zm9vYmE=");
+        assert_eq!(encode_chunks(&[b"fo", b"oba", b"r"]), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn bmp_header_describes_a_top_down_rgb565_image() {
+        let header = bmp_rgb565_header(320, 172);
+        let u32_at = |at: usize| u32::from_le_bytes(header[at..at + 4].try_into().unwrap());
+        let i32_at = |at: usize| i32::from_le_bytes(header[at..at + 4].try_into().unwrap());
+
+        assert_eq!(&header[0..2], b"BM");
+        assert_eq!(u32_at(2) as usize, bmp_rgb565_file_len(320, 172));
+        assert_eq!(u32_at(10) as usize, BMP_RGB565_HEADER_LEN);
+        assert_eq!(i32_at(18), 320);
+        assert_eq!(i32_at(22), -172);
+        assert_eq!(u32_at(30), 3);
+        assert_eq!(u32_at(54), 0xF800);
+        assert_eq!(bmp_rgb565_row_stride(320), 640);
+        assert_eq!(bmp_rgb565_row_stride(3), 8);
+    }
+}
@@ -1,22 +1,21 @@
-use super::*;
+use super::dashboard_font;
+use super::palette::UiPalette;
+use super::surface::{FrameSurface, blend565, measure_text_aa, rgb565_raw};
+use super::{OkValueError, format_ok_value_6};
+use crate::display_ui::{
+    NormalUiField, NormalUiPort, NormalUiSnapshot, USB_C_DISPLAY_TEXT_CAPACITY,
+    format_port_badge_text, format_port_mode_text,
+};
 
+/// Light-theme dashboard background, logged at boot for panel color checks.
 pub const DASHBOARD_BG_RGB8: (u8, u8, u8) = (0xFF, 0xFF, 0xFF);
 
-const DASHBOARD_BG_RAW: u16 = rgb565_raw(
-    DASHBOARD_BG_RGB8.0,
-    DASHBOARD_BG_RGB8.1,
-    DASHBOARD_BG_RGB8.2,
-);
 const WHITE_RAW: u16 = rgb565_raw(0xFF, 0xFF, 0xFF);
-const AQUA_RAW: u16 = rgb565_raw(0x4B, 0xA6, 0xC3);
-const BERRY_RAW: u16 = rgb565_raw(0xB9, 0x49, 0x5A);
-const INK_RAW: u16 = rgb565_raw(0x21, 0x44, 0x57);
-const INK_SOFT_RAW: u16 = rgb565_raw(0x6E, 0x84, 0x91);
-const BORDER_RAW: u16 = rgb565_raw(0xD6, 0xE5, 0xED);
 
 const CARD_RADIUS: u16 = 14;
 const CHIP_PAD_X: i32 = 8;
 const CHIP_PAD_Y: i32 = 5;
+const CHARGE_ONLY_CHIP: &str = "CHG";
 
 struct PortTheme {
     card_fill: u16,
@@ -29,34 +28,55 @@ struct PortTheme {
     main_text: u16,
     secondary_text: u16,
     power_text: u16,
+    not_present_text: u16,
+    error_text: u16,
+    over_text: u16,
 }
 
 impl PortTheme {
-    fn new(accent: u16) -> Self {
+    fn new(palette: &UiPalette, accent: u16) -> Self {
         Self {
-            card_fill: blend565(WHITE_RAW, accent, 10),
-            title_fill: blend565(WHITE_RAW, accent, 74),
-            title_border: blend565(WHITE_RAW, accent, 132),
-            title_text: blend565(accent, INK_RAW, 24),
-            meta_fill: blend565(accent, INK_RAW, 64),
+            card_fill: blend565(palette.card_base, accent, 10),
+            title_fill: blend565(palette.card_base, accent, 74),
+            title_border: blend565(palette.card_base, accent, 132),
+            title_text: blend565(accent, palette.ink, 24),
+            meta_fill: blend565(accent, palette.meta_shade, 64),
             meta_text: WHITE_RAW,
-            divider: BORDER_RAW,
-            main_text: INK_RAW,
-            secondary_text: INK_SOFT_RAW,
+            divider: palette.divider,
+            main_text: palette.ink,
+            secondary_text: palette.muted,
             power_text: accent,
+            not_present_text: palette.status_not_present,
+            error_text: palette.status_error,
+            over_text: palette.status_over,
         }
     }
 }
 
-pub(super) fn render_dashboard_base(surface: &mut FrameSurface<'_>) {
-    surface.fill(DASHBOARD_BG_RAW);
-    draw_dashboard_port_base(surface, 6, 6, 150, 160, PortTheme::new(AQUA_RAW));
-    draw_dashboard_port_base(surface, 164, 6, 150, 160, PortTheme::new(BERRY_RAW));
+pub fn render_dashboard_base(surface: &mut FrameSurface<'_>, palette: &UiPalette) {
+    surface.fill(palette.bg);
+    draw_dashboard_port_base(
+        surface,
+        6,
+        6,
+        150,
+        160,
+        PortTheme::new(palette, palette.port_a_accent),
+    );
+    draw_dashboard_port_base(
+        surface,
+        164,
+        6,
+        150,
+        160,
+        PortTheme::new(palette, palette.port_c_accent),
+    );
 }
 
-pub(super) fn render_dashboard_dynamic(
+pub fn render_dashboard_dynamic(
     surface: &mut FrameSurface<'_>,
     snapshot: &NormalUiSnapshot,
+    palette: &UiPalette,
 ) {
     draw_dashboard_port_dynamic(
         surface,
@@ -65,7 +85,7 @@ pub(super) fn render_dashboard_dynamic(
         150,
         160,
         snapshot.usb_a,
-        PortTheme::new(AQUA_RAW),
+        PortTheme::new(palette, palette.port_a_accent),
     );
     draw_dashboard_port_dynamic(
         surface,
@@ -74,7 +94,7 @@ pub(super) fn render_dashboard_dynamic(
         150,
         160,
         snapshot.usb_c,
-        PortTheme::new(BERRY_RAW),
+        PortTheme::new(palette, palette.port_c_accent),
     );
 }
 
@@ -130,8 +150,9 @@ fn draw_dashboard_port_dynamic(
     let value_font = &dashboard_font::LARGE;
     let secondary_font = &dashboard_font::MEDIUM;
 
+    let title_x = x as i32 + 10;
     surface.draw_chip(
-        x as i32 + 10,
+        title_x,
         y as i32 + 10,
         chip_font,
         0,
@@ -166,12 +187,40 @@ fn draw_dashboard_port_dynamic(
         theme.meta_text,
     );
 
+    // Charge-only chip sits between the title and the badge; a long USB-C
+    // mode title wins the space.
+    if port.charge_only {
+        let title_end = title_x + measure_text_aa(chip_font, 0, title) + CHIP_PAD_X * 2;
+        let chg_pad_x = CHIP_PAD_X / 2;
+        let chg_w = measure_text_aa(chip_font, 0, CHARGE_ONLY_CHIP) + chg_pad_x * 2;
+        let chg_x = badge_x - chg_w - 3;
+        if chg_x >= title_end + 3 {
+            surface.draw_chip(
+                chg_x,
+                badge_y,
+                chip_font,
+                0,
+                chg_pad_x,
+                CHIP_PAD_Y,
+                CHARGE_ONLY_CHIP,
+                theme.card_fill,
+                theme.title_text,
+                theme.title_border,
+            );
+        }
+    }
+
     let (voltage, voltage_color) =
-        format_dashboard_value(port.present, port.voltage_uv, b'V', theme.main_text);
-    let (current, current_color) =
-        format_dashboard_value(port.present, port.current_ua, b'A', theme.secondary_text);
+        format_dashboard_value(port.present, port.voltage_uv, b'V', theme.main_text, &theme);
+    let (current, current_color) = format_dashboard_value(
+        port.present,
+        port.current_ua,
+        b'A',
+        theme.secondary_text,
+        &theme,
+    );
     let (power, power_color) =
-        format_dashboard_value(port.present, port.power_uw, b'W', theme.power_text);
+        format_dashboard_value(port.present, port.power_uw, b'W', theme.power_text, &theme);
 
     let voltage_text = core::str::from_utf8(&voltage).unwrap_or("ERR");
     let current_text = core::str::from_utf8(&current).unwrap_or("ERR");
@@ -208,19 +257,17 @@ fn format_dashboard_value(
     value: NormalUiField,
     unit: u8,
     ok_color: u16,
+    theme: &PortTheme,
 ) -> ([u8; 6], u16) {
     if !present {
-        return (
-            [b'-', b'-', b'.', b'-', b'-', unit],
-            UI_STATUS_NOT_PRESENT_RAW,
-        );
+        return ([b'-', b'-', b'.', b'-', b'-', unit], theme.not_present_text);
     }
 
     match value {
-        NormalUiField::Err => (*b"ERROR ", UI_STATUS_ERROR_RAW),
+        NormalUiField::Err => (*b"ERROR ", theme.error_text),
         NormalUiField::Ok(micros) => match format_ok_value_6(micros, unit) {
             Ok(text) => (text, ok_color),
-            Err(OkValueError::Over) => (*b"OVER  ", UI_STATUS_OVER_RAW),
+            Err(OkValueError::Over) => (*b"OVER  ", theme.over_text),
         },
     }
 }
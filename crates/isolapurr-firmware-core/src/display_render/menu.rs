@@ -0,0 +1,224 @@
+use super::dashboard_font;
+use super::palette::UiPalette;
+use super::surface::{FrameSurface, blend565};
+
+const IDENTIFY_BORDER_THICKNESS: i32 = 12;
+
+pub fn render_settings_menu(
+    surface: &mut FrameSurface<'_>,
+    palette: &UiPalette,
+    selected_index: usize,
+) {
+    surface.fill(palette.menu_bg);
+
+    surface.fill_round_rect(8, 8, 304, 156, 14, palette.menu_border);
+    surface.fill_round_rect(10, 10, 300, 152, 13, palette.menu_panel);
+
+    surface.draw_text_aa(22, 22, &dashboard_font::SMALL, 1, "SETTINGS", palette.muted);
+    let selected_label = match selected_index {
+        0 => "USB-C MODE",
+        1 => "PRESET",
+        2 => "ADVANCED",
+        3 => "WIFI",
+        4 => "DISPLAY",
+        5 => "PORT POWER",
+        _ => "ABOUT",
+    };
+    surface.draw_text_aa(
+        22,
+        44,
+        &dashboard_font::MEDIUM,
+        0,
+        selected_label,
+        palette.ink,
+    );
+
+    let labels = ["MODE", "PRE", "ADV", "WIFI", "DISP", "PORT", "INFO"];
+    let x0 = 15;
+    let y0 = 88;
+    let segment_w = 38;
+    let segment_h = 56;
+    let gap = 4;
+    for (index, label) in labels.iter().enumerate() {
+        let x = x0 + index as i32 * (segment_w + gap);
+        let selected = index == selected_index;
+        let fill = if selected {
+            palette.menu_accent
+        } else {
+            palette.menu_accent_soft
+        };
+        let border = if selected {
+            palette.menu_accent
+        } else {
+            palette.menu_border
+        };
+        let icon = if selected {
+            palette.menu_panel
+        } else {
+            palette.ink
+        };
+
+        surface.fill_round_rect(x, y0, segment_w, segment_h, 12, border);
+        surface.fill_round_rect(x + 2, y0 + 2, segment_w - 4, segment_h - 4, 10, fill);
+        surface.draw_text_centered_aa(
+            x,
+            y0,
+            segment_w,
+            segment_h,
+            &dashboard_font::SMALL,
+            0,
+            label,
+            icon,
+        );
+    }
+}
+
+pub fn render_message_card(
+    surface: &mut FrameSurface<'_>,
+    palette: &UiPalette,
+    title: &str,
+    primary: &str,
+    secondary: &str,
+    tertiary: &str,
+    accent_raw: u16,
+) {
+    surface.fill(palette.menu_bg);
+    surface.fill_round_rect(8, 8, 304, 156, 14, palette.menu_border);
+    surface.fill_round_rect(10, 10, 300, 152, 13, palette.menu_panel);
+
+    surface.draw_chip(
+        22,
+        20,
+        &dashboard_font::SMALL,
+        0,
+        8,
+        4,
+        title,
+        blend565(palette.menu_panel, accent_raw, 34),
+        accent_raw,
+        blend565(palette.menu_panel, accent_raw, 92),
+    );
+    surface.draw_text_aa(24, 60, &dashboard_font::MEDIUM, 0, primary, palette.ink);
+    if !secondary.is_empty() {
+        surface.draw_text_aa(24, 92, &dashboard_font::SMALL, 0, secondary, palette.muted);
+    }
+    if !tertiary.is_empty() {
+        surface.draw_chip(
+            24,
+            122,
+            &dashboard_font::SMALL,
+            0,
+            8,
+            4,
+            tertiary,
+            blend565(palette.menu_panel, accent_raw, 24),
+            accent_raw,
+            blend565(palette.menu_panel, accent_raw, 76),
+        );
+    }
+}
+
+pub fn render_identify(
+    surface: &mut FrameSurface<'_>,
+    palette: &UiPalette,
+    device_id: &str,
+    ipv4: &str,
+    hostname: &str,
+    border_phase_on: bool,
+) {
+    let border = if border_phase_on {
+        palette.signal
+    } else {
+        palette.menu_bg
+    };
+
+    surface.fill(palette.menu_bg);
+    // Use a strong, full-perimeter pulse: the inactive phase deliberately
+    // clears into the display background so it remains visible at a glance.
+    surface.fill_rect(0, 0, 320, IDENTIFY_BORDER_THICKNESS, border);
+    surface.fill_rect(
+        0,
+        172 - IDENTIFY_BORDER_THICKNESS,
+        320,
+        IDENTIFY_BORDER_THICKNESS,
+        border,
+    );
+    surface.fill_rect(0, 0, IDENTIFY_BORDER_THICKNESS, 172, border);
+    surface.fill_rect(
+        320 - IDENTIFY_BORDER_THICKNESS,
+        0,
+        IDENTIFY_BORDER_THICKNESS,
+        172,
+        border,
+    );
+    surface.fill_round_rect(18, 18, 284, 136, 12, palette.menu_border);
+    surface.fill_round_rect(20, 20, 280, 132, 10, palette.menu_panel);
+    surface.draw_chip(
+        32,
+        30,
+        &dashboard_font::SMALL,
+        0,
+        8,
+        4,
+        "IDENTIFY",
+        blend565(palette.menu_panel, palette.menu_accent, 34),
+        palette.menu_accent,
+        blend565(palette.menu_panel, palette.menu_accent, 92),
+    );
+    surface.draw_text_aa(32, 66, &dashboard_font::MEDIUM, 0, device_id, palette.ink);
+    surface.draw_text_aa(32, 96, &dashboard_font::SMALL, 0, ipv4, palette.muted);
+    surface.draw_text_aa(32, 122, &dashboard_font::SMALL, 0, hostname, palette.muted);
+}
+
+pub fn trim_ascii_line<const N: usize>(line: &[u8; N]) -> &str {
+    let mut end = N;
+    while end > 0 && line[end - 1] == b' ' {
+        end -= 1;
+    }
+    core::str::from_utf8(&line[..end]).unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display_render::FRAME_PIXELS;
+
+    #[test]
+    fn identify_uses_a_high_contrast_pulse_border() {
+        let mut active = [0_u16; FRAME_PIXELS];
+        {
+            let mut surface = FrameSurface::new(&mut active);
+            render_identify(&mut surface, &UiPalette::LIGHT, "ID", "IP", "HOST", true);
+        }
+        let mut inactive = [0_u16; FRAME_PIXELS];
+        {
+            let mut surface = FrameSurface::new(&mut inactive);
+            render_identify(&mut surface, &UiPalette::LIGHT, "ID", "IP", "HOST", false);
+        }
+
+        assert_eq!(active[0], UiPalette::LIGHT.signal);
+        assert_eq!(inactive[0], UiPalette::LIGHT.menu_bg);
+        // First pixel diagonally inside the border; (0, thickness) is still
+        // on the left edge of the pulse.
+        assert_eq!(
+            active[IDENTIFY_BORDER_THICKNESS as usize * (320 + 1)],
+            UiPalette::LIGHT.menu_bg
+        );
+    }
+
+    #[test]
+    fn dark_identify_keeps_the_pulse_distinct_from_the_background() {
+        let mut active = [0_u16; FRAME_PIXELS];
+        {
+            let mut surface = FrameSurface::new(&mut active);
+            render_identify(&mut surface, &UiPalette::DARK, "ID", "IP", "HOST", true);
+        }
+
+        assert_eq!(active[0], UiPalette::DARK.signal);
+        assert_ne!(UiPalette::DARK.signal, UiPalette::DARK.menu_bg);
+        assert_eq!(
+            active[IDENTIFY_BORDER_THICKNESS as usize * (320 + 1)],
+            UiPalette::DARK.menu_bg
+        );
+    }
+}
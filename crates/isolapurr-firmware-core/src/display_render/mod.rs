@@ -0,0 +1,113 @@
+//! Pure framebuffer renderers for the GC9307 screens.
+//!
+//! Everything here draws into a `DISPLAY_WIDTH` x `DISPLAY_HEIGHT` RGB565
+//! slice, so the firmware and the host snapshot tests share one code path.
+
+#![allow(clippy::identity_op)]
+
+pub mod dashboard;
+pub mod dashboard_font;
+pub mod font6x8;
+pub mod menu;
+pub mod palette;
+pub mod surface;
+pub mod toast;
+
+pub use dashboard::{DASHBOARD_BG_RGB8, render_dashboard_base, render_dashboard_dynamic};
+pub use menu::{render_identify, render_message_card, render_settings_menu, trim_ascii_line};
+pub use palette::UiPalette;
+pub use surface::{FrameSurface, blend565, measure_text_aa, rgb565_raw};
+pub use toast::{render_toast, render_toast_compact};
+
+pub const DISPLAY_WIDTH: u16 = 320;
+pub const DISPLAY_HEIGHT: u16 = 172;
+pub const FRAME_PIXELS: usize = DISPLAY_WIDTH as usize * DISPLAY_HEIGHT as usize;
+
+pub const TILE_W: u16 = 24;
+pub const TILE_H: u16 = 48;
+const TILES_X: u16 = 13;
+
+pub const X_OFFSET: u16 = (DISPLAY_WIDTH - TILE_W * TILES_X) / 2;
+pub const Y_OFFSET: u16 = (DISPLAY_HEIGHT - TILE_H * 3) / 2;
+
+// Smaller font + spacing: render 6x8 glyph centered into a 24x48 tile.
+const GLYPH_SX: u16 = 3;
+const GLYPH_SY: u16 = 4;
+
+// Compact toast: 3 rows × 20 columns (small font so IPv4 fits in one line).
+const TOAST_COMPACT_TILE_W: u16 = 16;
+const TOAST_COMPACT_TILE_H: u16 = 32;
+const TOAST_COMPACT_TILES_X: u16 = 20;
+const TOAST_COMPACT_X_OFFSET: u16 =
+    (DISPLAY_WIDTH - TOAST_COMPACT_TILE_W * TOAST_COMPACT_TILES_X) / 2;
+const TOAST_COMPACT_Y_OFFSET: u16 = (DISPLAY_HEIGHT - TOAST_COMPACT_TILE_H * 3) / 2;
+
+const TOAST_COMPACT_GLYPH_SX: u16 = 2;
+const TOAST_COMPACT_GLYPH_SY: u16 = 3;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum OkValueError {
+    Over,
+}
+
+fn format_ok_value_6(micros: u32, unit: u8) -> Result<[u8; 6], OkValueError> {
+    let milli = (micros + 500) / 1_000;
+    if milli < 10_000 {
+        let int = milli / 1_000;
+        let frac = milli % 1_000;
+        return Ok([
+            b'0' + int as u8,
+            b'.',
+            b'0' + (frac / 100) as u8,
+            b'0' + ((frac / 10) % 10) as u8,
+            b'0' + (frac % 10) as u8,
+            unit,
+        ]);
+    }
+
+    let centi = (micros + 5_000) / 10_000;
+    if centi < 10_000 {
+        let int = centi / 100;
+        let frac = centi % 100;
+        return Ok([
+            b'0' + (int / 10) as u8,
+            b'0' + (int % 10) as u8,
+            b'.',
+            b'0' + (frac / 10) as u8,
+            b'0' + (frac % 10) as u8,
+            unit,
+        ]);
+    }
+
+    let deci = (micros + 50_000) / 100_000;
+    if deci < 10_000 {
+        let int = deci / 10;
+        let frac = deci % 10;
+        return Ok([
+            b'0' + (int / 100) as u8,
+            b'0' + ((int / 10) % 10) as u8,
+            b'0' + (int % 10) as u8,
+            b'.',
+            b'0' + frac as u8,
+            unit,
+        ]);
+    }
+
+    Err(OkValueError::Over)
+}
+
+/// Renders one 6x8 glyph into a 1bpp `TILE_W` x `TILE_H` bitmap.
+pub fn render_char_6x8_scaled(ch: u8, out: &mut [u8; 144]) {
+    font6x8::render_char_6x8_scaled_custom(ch, out, TILE_W, TILE_H, GLYPH_SX, GLYPH_SY);
+}
+
+fn render_char_6x8_scaled_custom(
+    ch: u8,
+    out: &mut [u8],
+    tile_w: u16,
+    tile_h: u16,
+    glyph_sx: u16,
+    glyph_sy: u16,
+) {
+    font6x8::render_char_6x8_scaled_custom(ch, out, tile_w, tile_h, glyph_sx, glyph_sy);
+}
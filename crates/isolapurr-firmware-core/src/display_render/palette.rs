@@ -0,0 +1,94 @@
+use super::dashboard::DASHBOARD_BG_RGB8;
+use super::surface::{blend565, rgb565_raw};
+use crate::display_settings::DisplayTheme;
+
+const WHITE_RAW: u16 = rgb565_raw(0xFF, 0xFF, 0xFF);
+
+/// Colors shared by the dashboard, menu, and toast renderers for one theme.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct UiPalette {
+    pub bg: u16,
+    pub card_base: u16,
+    pub port_a_accent: u16,
+    pub port_c_accent: u16,
+    pub meta_shade: u16,
+    pub ink: u16,
+    pub muted: u16,
+    pub divider: u16,
+    pub menu_bg: u16,
+    pub menu_panel: u16,
+    pub menu_border: u16,
+    pub menu_accent: u16,
+    pub menu_accent_soft: u16,
+    pub signal: u16,
+    pub status_not_present: u16,
+    pub status_error: u16,
+    pub status_over: u16,
+    /// How far caller-provided toast/card colors are pulled toward white.
+    accent_lift: u8,
+}
+
+impl UiPalette {
+    pub const LIGHT: Self = Self {
+        bg: rgb565_raw(
+            DASHBOARD_BG_RGB8.0,
+            DASHBOARD_BG_RGB8.1,
+            DASHBOARD_BG_RGB8.2,
+        ),
+        card_base: WHITE_RAW,
+        port_a_accent: rgb565_raw(0x4B, 0xA6, 0xC3),
+        port_c_accent: rgb565_raw(0xB9, 0x49, 0x5A),
+        meta_shade: rgb565_raw(0x21, 0x44, 0x57),
+        ink: rgb565_raw(0x21, 0x44, 0x57),
+        muted: rgb565_raw(0x6E, 0x84, 0x91),
+        divider: rgb565_raw(0xD6, 0xE5, 0xED),
+        menu_bg: rgb565_raw(0xF5, 0xF8, 0xFA),
+        menu_panel: rgb565_raw(0xFB, 0xFD, 0xFE),
+        menu_border: rgb565_raw(0xD7, 0xE3, 0xEA),
+        menu_accent: rgb565_raw(0x4B, 0x63, 0xC7),
+        menu_accent_soft: rgb565_raw(0xE7, 0xEA, 0xFB),
+        signal: rgb565_raw(0xB9, 0x49, 0x5A),
+        status_not_present: 0x4AAC,
+        status_error: 0x98C3,
+        status_over: 0xC201,
+        accent_lift: 0,
+    };
+
+    pub const DARK: Self = Self {
+        bg: rgb565_raw(0x10, 0x17, 0x1C),
+        card_base: rgb565_raw(0x1B, 0x26, 0x2E),
+        port_a_accent: rgb565_raw(0x5C, 0xC0, 0xDE),
+        port_c_accent: rgb565_raw(0xE0, 0x6A, 0x7E),
+        meta_shade: rgb565_raw(0x10, 0x17, 0x1C),
+        ink: rgb565_raw(0xE6, 0xEE, 0xF2),
+        muted: rgb565_raw(0x9A, 0xAE, 0xBA),
+        divider: rgb565_raw(0x33, 0x44, 0x50),
+        menu_bg: rgb565_raw(0x10, 0x17, 0x1C),
+        menu_panel: rgb565_raw(0x1B, 0x26, 0x2E),
+        menu_border: rgb565_raw(0x33, 0x44, 0x50),
+        menu_accent: rgb565_raw(0x7B, 0x8F, 0xE8),
+        menu_accent_soft: rgb565_raw(0x26, 0x30, 0x4A),
+        signal: rgb565_raw(0xE0, 0x5A, 0x6E),
+        status_not_present: rgb565_raw(0x7A, 0x8C, 0x98),
+        status_error: rgb565_raw(0xF0, 0x6A, 0x6A),
+        status_over: rgb565_raw(0xF5, 0x9E, 0x3B),
+        accent_lift: 96,
+    };
+
+    pub const fn for_theme(theme: DisplayTheme) -> Self {
+        match theme {
+            DisplayTheme::Light => Self::LIGHT,
+            DisplayTheme::Dark => Self::DARK,
+        }
+    }
+
+    /// Toast and card accents are tuned for the light background; keep them
+    /// readable on the dark one without every caller knowing the theme.
+    pub fn accent(&self, raw: u16) -> u16 {
+        if self.accent_lift == 0 {
+            raw
+        } else {
+            blend565(raw, WHITE_RAW, self.accent_lift)
+        }
+    }
+}
@@ -5,21 +5,21 @@ use super::{
     render_char_6x8_scaled_custom,
 };
 
-pub(crate) struct FrameSurface<'a> {
+pub struct FrameSurface<'a> {
     pixels: &'a mut [u16],
 }
 
 impl<'a> FrameSurface<'a> {
-    pub(super) fn new(pixels: &'a mut [u16]) -> Self {
+    pub fn new(pixels: &'a mut [u16]) -> Self {
         debug_assert_eq!(pixels.len(), FRAME_PIXELS);
         Self { pixels }
     }
 
-    pub(super) fn fill(&mut self, color: u16) {
+    pub fn fill(&mut self, color: u16) {
         self.pixels.fill(color);
     }
 
-    pub(super) fn fill_rect(&mut self, x: i32, y: i32, w: i32, h: i32, color: u16) {
+    pub fn fill_rect(&mut self, x: i32, y: i32, w: i32, h: i32, color: u16) {
         if w <= 0 || h <= 0 {
             return;
         }
@@ -31,15 +31,7 @@ impl<'a> FrameSurface<'a> {
         }
     }
 
-    pub(super) fn fill_round_rect(
-        &mut self,
-        x: i32,
-        y: i32,
-        w: i32,
-        h: i32,
-        radius: i32,
-        color: u16,
-    ) {
+    pub fn fill_round_rect(&mut self, x: i32, y: i32, w: i32, h: i32, radius: i32, color: u16) {
         if w <= 0 || h <= 0 {
             return;
         }
@@ -52,7 +44,7 @@ impl<'a> FrameSurface<'a> {
         }
     }
 
-    pub(super) fn draw_text_aa(
+    pub fn draw_text_aa(
         &mut self,
         x: i32,
         y: i32,
@@ -69,7 +61,8 @@ impl<'a> FrameSurface<'a> {
         }
     }
 
-    pub(super) fn draw_text_centered_aa(
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_text_centered_aa(
         &mut self,
         x: i32,
         y: i32,
@@ -93,7 +86,7 @@ impl<'a> FrameSurface<'a> {
     }
 
     #[allow(clippy::too_many_arguments)]
-    pub(super) fn draw_chip(
+    pub fn draw_chip(
         &mut self,
         x: i32,
         y: i32,
@@ -134,6 +127,7 @@ impl<'a> FrameSurface<'a> {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn write_bitmap_area(
         &mut self,
         x: i32,
@@ -155,7 +149,7 @@ impl<'a> FrameSurface<'a> {
         }
     }
 
-    pub(super) fn draw_tile_colored_with_bg(
+    pub fn draw_tile_colored_with_bg(
         &mut self,
         tile_x: u16,
         tile_y: u16,
@@ -171,7 +165,7 @@ impl<'a> FrameSurface<'a> {
         self.write_bitmap_area(x as i32, y as i32, TILE_W, TILE_H, &data, fg_raw, bg_raw);
     }
 
-    pub(super) fn draw_compact_tile_colored(
+    pub fn draw_compact_tile_colored(
         &mut self,
         tile_x: u16,
         tile_y: u16,
@@ -218,7 +212,7 @@ impl<'a> FrameSurface<'a> {
     }
 }
 
-pub(crate) const fn rgb565_raw(r: u8, g: u8, b: u8) -> u16 {
+pub const fn rgb565_raw(r: u8, g: u8, b: u8) -> u16 {
     (((r as u16) & 0xF8) << 8) | (((g as u16) & 0xFC) << 3) | ((b as u16) >> 3)
 }
 
@@ -233,7 +227,7 @@ const fn expand_565(c: u16) -> (u8, u8, u8) {
     )
 }
 
-pub(crate) fn blend565(base: u16, over: u16, alpha: u8) -> u16 {
+pub fn blend565(base: u16, over: u16, alpha: u8) -> u16 {
     let (br, bg, bb) = expand_565(base);
     let (or, og, ob) = expand_565(over);
     let a = alpha as u32;
@@ -266,11 +260,7 @@ fn point_in_round_rect(px: i32, py: i32, w: i32, h: i32, r: i32) -> bool {
     dx * dx + dy * dy <= rr
 }
 
-pub(crate) fn measure_text_aa(
-    font: &'static dashboard_font::AaFont,
-    spacing: i32,
-    text: &str,
-) -> i32 {
+pub fn measure_text_aa(font: &'static dashboard_font::AaFont, spacing: i32, text: &str) -> i32 {
     let mut width = 0;
     for (idx, ch) in text.bytes().enumerate() {
         let glyph = dashboard_font::lookup_glyph(font, ch);
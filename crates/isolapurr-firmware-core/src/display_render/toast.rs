@@ -0,0 +1,36 @@
+use super::palette::UiPalette;
+use super::surface::FrameSurface;
+
+/// 3×13 character toast on the large 24×48 tile grid.
+pub fn render_toast(
+    surface: &mut FrameSurface<'_>,
+    palette: &UiPalette,
+    lines: &[[u8; 13]; 3],
+    fg_raw: u16,
+) {
+    let bg_raw = palette.bg;
+    let fg_raw = palette.accent(fg_raw);
+    surface.fill(bg_raw);
+    for (tile_y, row) in lines.iter().enumerate() {
+        for (tile_x, &ch) in row.iter().enumerate() {
+            surface.draw_tile_colored_with_bg(tile_x as u16, tile_y as u16, ch, fg_raw, bg_raw);
+        }
+    }
+}
+
+/// 3×20 character toast on the compact 16×32 tile grid, for longer strings.
+pub fn render_toast_compact(
+    surface: &mut FrameSurface<'_>,
+    palette: &UiPalette,
+    lines: &[[u8; 20]; 3],
+    fg_raw: u16,
+) {
+    let bg_raw = palette.bg;
+    let fg_raw = palette.accent(fg_raw);
+    surface.fill(bg_raw);
+    for (tile_y, row) in lines.iter().enumerate() {
+        for (tile_x, &ch) in row.iter().enumerate() {
+            surface.draw_compact_tile_colored(tile_x as u16, tile_y as u16, ch, fg_raw, bg_raw);
+        }
+    }
+}
@@ -0,0 +1,290 @@
+pub const DISPLAY_BRIGHTNESS_MIN_PCT: u8 = 5;
+pub const DISPLAY_BRIGHTNESS_MAX_PCT: u8 = 100;
+/// Backlight level used while dimmed; a lower saved brightness is kept as is.
+pub const DISPLAY_DIM_BRIGHTNESS_PCT: u8 = 10;
+pub const DISPLAY_IDLE_MIN_S: u16 = 10;
+pub const DISPLAY_IDLE_MAX_S: u16 = 7_200;
+
+/// Brightness steps offered by the on-device menu, brightest first.
+pub const DISPLAY_MENU_BRIGHTNESS_STEPS: [u8; 5] = [100, 75, 50, 25, 10];
+
+/// `(dim_after_s, off_after_s)` presets offered by the on-device menu.
+pub const DISPLAY_MENU_SLEEP_PRESETS: [(u16, u16); 4] =
+    [(0, 0), (60, 300), (300, 900), (900, 1_800)];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DisplayTheme {
+    Light,
+    Dark,
+}
+
+impl DisplayTheme {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            DisplayTheme::Light => "light",
+            DisplayTheme::Dark => "dark",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "light" => Some(DisplayTheme::Light),
+            "dark" => Some(DisplayTheme::Dark),
+            _ => None,
+        }
+    }
+
+    pub const fn toggled(self) -> Self {
+        match self {
+            DisplayTheme::Light => DisplayTheme::Dark,
+            DisplayTheme::Dark => DisplayTheme::Light,
+        }
+    }
+}
+
+/// Panel rotation; `Flipped` is 180° for upside-down mounting.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DisplayRotation {
+    Normal,
+    Flipped,
+}
+
+impl DisplayRotation {
+    pub const fn degrees(self) -> u16 {
+        match self {
+            DisplayRotation::Normal => 0,
+            DisplayRotation::Flipped => 180,
+        }
+    }
+
+    pub const fn from_degrees(degrees: u16) -> Option<Self> {
+        match degrees {
+            0 => Some(DisplayRotation::Normal),
+            180 => Some(DisplayRotation::Flipped),
+            _ => None,
+        }
+    }
+
+    pub const fn toggled(self) -> Self {
+        match self {
+            DisplayRotation::Normal => DisplayRotation::Flipped,
+            DisplayRotation::Flipped => DisplayRotation::Normal,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DisplaySettingsError {
+    BrightnessOutOfRange,
+    IdleTimeoutOutOfRange,
+    OffBeforeDim,
+}
+
+/// Persisted display preferences. A zero idle timeout disables that stage.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DisplaySettings {
+    pub brightness_pct: u8,
+    pub dim_after_s: u16,
+    pub off_after_s: u16,
+    pub rotation: DisplayRotation,
+    pub theme: DisplayTheme,
+}
+
+impl DisplaySettings {
+    pub const fn defaults() -> Self {
+        Self {
+            brightness_pct: DISPLAY_BRIGHTNESS_MAX_PCT,
+            dim_after_s: 0,
+            off_after_s: 0,
+            rotation: DisplayRotation::Normal,
+            theme: DisplayTheme::Light,
+        }
+    }
+
+    pub fn validated(self) -> Result<Self, DisplaySettingsError> {
+        if !(DISPLAY_BRIGHTNESS_MIN_PCT..=DISPLAY_BRIGHTNESS_MAX_PCT).contains(&self.brightness_pct)
+        {
+            return Err(DisplaySettingsError::BrightnessOutOfRange);
+        }
+        let timeout_ok = |seconds: u16| {
+            seconds == 0 || (DISPLAY_IDLE_MIN_S..=DISPLAY_IDLE_MAX_S).contains(&seconds)
+        };
+        if !timeout_ok(self.dim_after_s) || !timeout_ok(self.off_after_s) {
+            return Err(DisplaySettingsError::IdleTimeoutOutOfRange);
+        }
+        if self.dim_after_s != 0 && self.off_after_s != 0 && self.off_after_s <= self.dim_after_s {
+            return Err(DisplaySettingsError::OffBeforeDim);
+        }
+        Ok(self)
+    }
+
+    pub const fn backlight_pct(self, level: BacklightLevel) -> u8 {
+        match level {
+            BacklightLevel::Full => self.brightness_pct,
+            BacklightLevel::Dim => {
+                if self.brightness_pct < DISPLAY_DIM_BRIGHTNESS_PCT {
+                    self.brightness_pct
+                } else {
+                    DISPLAY_DIM_BRIGHTNESS_PCT
+                }
+            }
+            BacklightLevel::Off => 0,
+        }
+    }
+
+    /// Next menu brightness step after the current value, wrapping to the brightest.
+    pub fn next_menu_brightness(self) -> u8 {
+        DISPLAY_MENU_BRIGHTNESS_STEPS
+            .iter()
+            .copied()
+            .find(|step| *step < self.brightness_pct)
+            .unwrap_or(DISPLAY_MENU_BRIGHTNESS_STEPS[0])
+    }
+
+    /// Next menu sleep preset; custom API timeouts restart the cycle at "never".
+    pub fn next_menu_sleep(self) -> (u16, u16) {
+        let current = (self.dim_after_s, self.off_after_s);
+        let index = DISPLAY_MENU_SLEEP_PRESETS
+            .iter()
+            .position(|preset| *preset == current)
+            .map_or(0, |index| (index + 1) % DISPLAY_MENU_SLEEP_PRESETS.len());
+        DISPLAY_MENU_SLEEP_PRESETS[index]
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BacklightLevel {
+    Full,
+    Dim,
+    Off,
+}
+
+impl BacklightLevel {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            BacklightLevel::Full => "on",
+            BacklightLevel::Dim => "dim",
+            BacklightLevel::Off => "off",
+        }
+    }
+}
+
+/// Tracks user/port activity and resolves the dim-then-off backlight stage.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DisplayIdleTimer {
+    last_activity_ms: u64,
+}
+
+impl DisplayIdleTimer {
+    pub const fn new(now_ms: u64) -> Self {
+        Self {
+            last_activity_ms: now_ms,
+        }
+    }
+
+    pub fn note_activity(&mut self, now_ms: u64) {
+        self.last_activity_ms = self.last_activity_ms.max(now_ms);
+    }
+
+    pub fn level(self, settings: DisplaySettings, now_ms: u64) -> BacklightLevel {
+        let idle_ms = now_ms.saturating_sub(self.last_activity_ms);
+        let reached = |seconds: u16| seconds != 0 && idle_ms >= u64::from(seconds) * 1_000;
+        if reached(settings.off_after_s) {
+            BacklightLevel::Off
+        } else if reached(settings.dim_after_s) {
+            BacklightLevel::Dim
+        } else {
+            BacklightLevel::Full
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validation_rejects_out_of_range_values() {
+        let defaults = DisplaySettings::defaults();
+        assert_eq!(defaults.validated(), Ok(defaults));
+
+        let dim = DisplaySettings {
+            brightness_pct: 4,
+            ..defaults
+        };
+        assert_eq!(
+            dim.validated(),
+            Err(DisplaySettingsError::BrightnessOutOfRange)
+        );
+
+        let short = DisplaySettings {
+            dim_after_s: 5,
+            ..defaults
+        };
+        assert_eq!(
+            short.validated(),
+            Err(DisplaySettingsError::IdleTimeoutOutOfRange)
+        );
+
+        let inverted = DisplaySettings {
+            dim_after_s: 300,
+            off_after_s: 60,
+            ..defaults
+        };
+        assert_eq!(
+            inverted.validated(),
+            Err(DisplaySettingsError::OffBeforeDim)
+        );
+
+        let off_only = DisplaySettings {
+            off_after_s: 60,
+            ..defaults
+        };
+        assert_eq!(off_only.validated(), Ok(off_only));
+    }
+
+    #[test]
+    fn idle_timer_dims_then_turns_off_and_wakes_on_activity() {
+        let settings = DisplaySettings {
+            brightness_pct: 80,
+            dim_after_s: 60,
+            off_after_s: 300,
+            ..DisplaySettings::defaults()
+        };
+        let mut timer = DisplayIdleTimer::new(1_000);
+
+        assert_eq!(timer.level(settings, 60_999), BacklightLevel::Full);
+        assert_eq!(timer.level(settings, 61_000), BacklightLevel::Dim);
+        assert_eq!(timer.level(settings, 301_000), BacklightLevel::Off);
+        assert_eq!(settings.backlight_pct(BacklightLevel::Dim), 10);
+        assert_eq!(settings.backlight_pct(BacklightLevel::Off), 0);
+
+        timer.note_activity(400_000);
+        assert_eq!(timer.level(settings, 400_500), BacklightLevel::Full);
+        assert_eq!(settings.backlight_pct(BacklightLevel::Full), 80);
+
+        let never = DisplaySettings::defaults();
+        assert_eq!(timer.level(never, u64::MAX), BacklightLevel::Full);
+    }
+
+    #[test]
+    fn menu_cycles_wrap_and_recover_from_custom_values() {
+        let mut settings = DisplaySettings::defaults();
+        let mut seen = [0u8; 5];
+        for slot in &mut seen {
+            settings.brightness_pct = settings.next_menu_brightness();
+            *slot = settings.brightness_pct;
+        }
+        assert_eq!(seen, [75, 50, 25, 10, 100]);
+
+        settings.brightness_pct = 60;
+        assert_eq!(settings.next_menu_brightness(), 50);
+
+        assert_eq!(settings.next_menu_sleep(), (60, 300));
+        settings.dim_after_s = 900;
+        settings.off_after_s = 1_800;
+        assert_eq!(settings.next_menu_sleep(), (0, 0));
+        settings.dim_after_s = 42;
+        assert_eq!(settings.next_menu_sleep(), (0, 0));
+    }
+}
@@ -48,6 +48,8 @@ pub struct NormalUiPort {
     pub current_ua: NormalUiField,
     /// Power in uW.
     pub power_uw: NormalUiField,
+    /// Data switch held open by the persistent charge-only mode.
+    pub charge_only: bool,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -0,0 +1,327 @@
+//! Guided USB-C fast-charge probe behind `power.fast_charge_probe_*` and
+//! `/api/v1/power/fast-charge-probe` (see
+//! `docs/specs/f7p3q-fast-charge-probe/SPEC.md`).
+//!
+//! The probe only tracks which family is on offer and what the sink did with
+//! it; the PD loop narrows the effective profile with
+//! [`UsbCCapabilityConfig::only_protocol`](crate::power_config::UsbCCapabilityConfig::only_protocol)
+//! and re-contracts whenever [`FastChargeProbe::family`] changes.
+
+use isolapurr_api::power::{
+    ActiveProtocol, FastChargeProbeError, FastChargeProbeResult, FastChargeProbeState,
+};
+
+/// One step per protocol family, in [`ActiveProtocol::ALL`] order.
+pub const FAST_CHARGE_PROBE_STEPS: usize = ActiveProtocol::ALL.len();
+/// Ignored at the start of each step: the profile write, the CC un-drive, the
+/// sink's re-attach and its protocol handshake.
+pub const FAST_CHARGE_PROBE_SETTLE_MS: u64 = 6_000;
+/// Samples after the settle window that make up one family's result.
+pub const FAST_CHARGE_PROBE_SAMPLE_MS: u64 = 4_000;
+pub const FAST_CHARGE_PROBE_STEP_MS: u64 =
+    FAST_CHARGE_PROBE_SETTLE_MS + FAST_CHARGE_PROBE_SAMPLE_MS;
+
+const EMPTY_RESULT: FastChargeProbeResult = FastChargeProbeResult {
+    family: ActiveProtocol::Pd,
+    supported: false,
+    negotiated: None,
+    voltage_mv: None,
+    current_ma: None,
+};
+
+/// One USB-C telemetry sample.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct FastChargeProbeSample {
+    /// `usb_c_present`: the sink is attached or drawing current.
+    pub attached: bool,
+    pub protocol: Option<ActiveProtocol>,
+    pub voltage_mv: Option<u32>,
+    pub current_ma: Option<u32>,
+}
+
+impl FastChargeProbeSample {
+    fn power_uw(&self) -> u64 {
+        u64::from(self.voltage_mv.unwrap_or(0)) * u64::from(self.current_ma.unwrap_or(0))
+    }
+}
+
+/// The probe as reported by `power.fast_charge_probe_get`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FastChargeProbeReport {
+    pub state: FastChargeProbeState,
+    /// The family on offer while running.
+    pub family: Option<ActiveProtocol>,
+    pub error: Option<FastChargeProbeError>,
+    pub started_uptime_ms: Option<u64>,
+    results: [FastChargeProbeResult; FAST_CHARGE_PROBE_STEPS],
+    completed: u8,
+}
+
+impl FastChargeProbeReport {
+    pub const fn idle() -> Self {
+        Self {
+            state: FastChargeProbeState::Idle,
+            family: None,
+            error: None,
+            started_uptime_ms: None,
+            results: [EMPTY_RESULT; FAST_CHARGE_PROBE_STEPS],
+            completed: 0,
+        }
+    }
+
+    /// A run the API accepted that the PD loop has not started yet.
+    pub const fn queued() -> Self {
+        Self {
+            state: FastChargeProbeState::Running,
+            ..Self::idle()
+        }
+    }
+
+    /// The families finished so far, in probe order.
+    pub fn results(&self) -> &[FastChargeProbeResult] {
+        &self.results[..usize::from(self.completed)]
+    }
+
+    pub fn supported_count(&self) -> usize {
+        self.results()
+            .iter()
+            .filter(|result| result.supported)
+            .count()
+    }
+}
+
+/// Steps through every protocol family, keeping the highest-power attached
+/// sample of each step's sample window.
+pub struct FastChargeProbe {
+    report: FastChargeProbeReport,
+    step_started_ms: u64,
+    best: Option<FastChargeProbeSample>,
+}
+
+impl FastChargeProbe {
+    pub const fn new() -> Self {
+        Self {
+            report: FastChargeProbeReport::idle(),
+            step_started_ms: 0,
+            best: None,
+        }
+    }
+
+    pub const fn report(&self) -> FastChargeProbeReport {
+        self.report
+    }
+
+    /// The family the source profile must be narrowed to, `None` when idle.
+    pub const fn family(&self) -> Option<ActiveProtocol> {
+        self.report.family
+    }
+
+    /// Starts a new probe, dropping the previous report. Returns `false` while
+    /// one is already running.
+    pub fn start(&mut self, now_ms: u64) -> bool {
+        if self.report.state == FastChargeProbeState::Running {
+            return false;
+        }
+        self.report = FastChargeProbeReport {
+            state: FastChargeProbeState::Running,
+            family: Some(ActiveProtocol::ALL[0]),
+            started_uptime_ms: Some(now_ms),
+            ..FastChargeProbeReport::idle()
+        };
+        self.step_started_ms = now_ms;
+        self.best = None;
+        true
+    }
+
+    /// Records a probe that was refused before it started.
+    pub fn refuse(&mut self, now_ms: u64, error: FastChargeProbeError) {
+        self.report = FastChargeProbeReport {
+            state: FastChargeProbeState::Aborted,
+            error: Some(error),
+            started_uptime_ms: Some(now_ms),
+            ..FastChargeProbeReport::idle()
+        };
+    }
+
+    /// Stops a running probe, keeping the families finished so far. Returns
+    /// `false` when none was running.
+    pub fn abort(&mut self, error: FastChargeProbeError) -> bool {
+        if self.report.state != FastChargeProbeState::Running {
+            return false;
+        }
+        self.report.state = FastChargeProbeState::Aborted;
+        self.report.family = None;
+        self.report.error = Some(error);
+        true
+    }
+
+    /// Feeds one telemetry sample. Returns `true` when a step finished or the
+    /// probe ended.
+    pub fn update(&mut self, now_ms: u64, sample: &FastChargeProbeSample) -> bool {
+        let Some(family) = self.report.family else {
+            return false;
+        };
+        let elapsed_ms = now_ms.saturating_sub(self.step_started_ms);
+        if elapsed_ms < FAST_CHARGE_PROBE_SETTLE_MS {
+            return false;
+        }
+        if sample.attached
+            && self
+                .best
+                .is_none_or(|best| sample.power_uw() >= best.power_uw())
+        {
+            self.best = Some(*sample);
+        }
+        if elapsed_ms < FAST_CHARGE_PROBE_STEP_MS {
+            return false;
+        }
+
+        let Some(best) = self.best.take() else {
+            return self.abort(FastChargeProbeError::Detached);
+        };
+        let index = usize::from(self.report.completed);
+        self.report.results[index] = FastChargeProbeResult {
+            family,
+            supported: best.protocol == Some(family),
+            negotiated: best.protocol,
+            voltage_mv: best.voltage_mv,
+            current_ma: best.current_ma,
+        };
+        self.report.completed += 1;
+        self.report.family = ActiveProtocol::ALL.get(index + 1).copied();
+        if self.report.family.is_none() {
+            self.report.state = FastChargeProbeState::Complete;
+        }
+        self.step_started_ms = now_ms;
+        true
+    }
+}
+
+impl Default for FastChargeProbe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        FAST_CHARGE_PROBE_SETTLE_MS, FAST_CHARGE_PROBE_STEP_MS, FAST_CHARGE_PROBE_STEPS,
+        FastChargeProbe, FastChargeProbeSample,
+    };
+    use isolapurr_api::power::{ActiveProtocol, FastChargeProbeError, FastChargeProbeState};
+
+    fn sample(protocol: ActiveProtocol, voltage_mv: u32, current_ma: u32) -> FastChargeProbeSample {
+        FastChargeProbeSample {
+            attached: true,
+            protocol: Some(protocol),
+            voltage_mv: Some(voltage_mv),
+            current_ma: Some(current_ma),
+        }
+    }
+
+    /// Runs one step, feeding `during` through the sample window.
+    fn run_step(probe: &mut FastChargeProbe, start_ms: u64, during: FastChargeProbeSample) -> u64 {
+        let mut now_ms = start_ms;
+        let mut finished = false;
+        while !finished {
+            now_ms += 500;
+            let current = if now_ms - start_ms < FAST_CHARGE_PROBE_SETTLE_MS {
+                FastChargeProbeSample::default()
+            } else {
+                during
+            };
+            finished = probe.update(now_ms, &current);
+        }
+        assert_eq!(now_ms - start_ms, FAST_CHARGE_PROBE_STEP_MS);
+        now_ms
+    }
+
+    #[test]
+    fn probe_steps_through_every_family() {
+        let mut probe = FastChargeProbe::new();
+        assert_eq!(probe.family(), None);
+        assert!(probe.start(1_000));
+        assert!(!probe.start(1_500));
+        assert_eq!(probe.family(), Some(ActiveProtocol::Pd));
+
+        let mut now_ms = 1_000;
+        for family in ActiveProtocol::ALL {
+            assert_eq!(probe.family(), Some(*family));
+            // The phone speaks PD, QC3.0 and BC1.2; everything else falls back.
+            let during = match family {
+                ActiveProtocol::Pd => sample(ActiveProtocol::Pd, 9_020, 2_010),
+                ActiveProtocol::Qc30 => sample(ActiveProtocol::Qc30, 8_940, 1_650),
+                ActiveProtocol::Pps => sample(ActiveProtocol::Pd, 9_010, 1_990),
+                _ => sample(ActiveProtocol::Bc12, 5_050, 1_000),
+            };
+            now_ms = run_step(&mut probe, now_ms, during);
+        }
+
+        let report = probe.report();
+        assert_eq!(report.state, FastChargeProbeState::Complete);
+        assert_eq!(report.family, None);
+        assert_eq!(report.results().len(), FAST_CHARGE_PROBE_STEPS);
+        assert_eq!(report.supported_count(), 3);
+        let pps = report.results()[1];
+        assert_eq!(pps.family, ActiveProtocol::Pps);
+        assert!(!pps.supported);
+        assert_eq!(pps.negotiated, Some(ActiveProtocol::Pd));
+        let qc30 = report.results()[3];
+        assert!(qc30.supported);
+        assert_eq!(
+            (qc30.voltage_mv, qc30.current_ma),
+            (Some(8_940), Some(1_650))
+        );
+        assert!(report.results()[8].supported);
+    }
+
+    #[test]
+    fn step_keeps_the_highest_power_sample() {
+        let mut probe = FastChargeProbe::new();
+        probe.start(0);
+        probe.update(
+            FAST_CHARGE_PROBE_SETTLE_MS,
+            &sample(ActiveProtocol::Bc12, 5_000, 900),
+        );
+        probe.update(
+            FAST_CHARGE_PROBE_SETTLE_MS + 1_000,
+            &sample(ActiveProtocol::Pd, 20_000, 3_000),
+        );
+        assert!(probe.update(
+            FAST_CHARGE_PROBE_STEP_MS,
+            &sample(ActiveProtocol::Pd, 20_000, 1_200),
+        ));
+        let result = probe.report().results()[0];
+        assert_eq!(result.negotiated, Some(ActiveProtocol::Pd));
+        assert_eq!(result.voltage_mv, Some(20_000));
+        assert_eq!(result.current_ma, Some(3_000));
+        assert_eq!(probe.family(), Some(ActiveProtocol::Pps));
+    }
+
+    #[test]
+    fn detach_and_cancel_abort_with_partial_results() {
+        let mut probe = FastChargeProbe::new();
+        probe.start(0);
+        let now_ms = run_step(&mut probe, 0, sample(ActiveProtocol::Pd, 9_000, 2_000));
+        assert!(probe.update(
+            now_ms + FAST_CHARGE_PROBE_STEP_MS,
+            &FastChargeProbeSample::default()
+        ));
+        let report = probe.report();
+        assert_eq!(report.state, FastChargeProbeState::Aborted);
+        assert_eq!(report.error, Some(FastChargeProbeError::Detached));
+        assert_eq!(report.results().len(), 1);
+        assert_eq!(probe.family(), None);
+        assert!(!probe.abort(FastChargeProbeError::Cancelled));
+
+        assert!(probe.start(100_000));
+        assert_eq!(probe.report().results().len(), 0);
+        assert!(probe.abort(FastChargeProbeError::Cancelled));
+        assert_eq!(probe.report().error, Some(FastChargeProbeError::Cancelled));
+
+        probe.refuse(200_000, FastChargeProbeError::PortOff);
+        assert_eq!(probe.report().state, FastChargeProbeState::Aborted);
+        assert_eq!(probe.family(), None);
+    }
+}
@@ -0,0 +1,190 @@
+//! Per-bus I2C health counters kept by the allowlist wrappers and reported at
+//! `/api/v1/diagnostics/i2c` (see `docs/specs/q4n7w-i2c-diagnostics/SPEC.md`).
+
+use isolapurr_api::diagnostics::I2cProbeResult;
+
+/// How a failed transaction is counted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum I2cErrorClass {
+    Nack,
+    Timeout,
+    Arbitration,
+    Other,
+}
+
+/// Maps one probe transaction to the result reported by `i2c.scan`.
+pub const fn probe_result(result: Result<(), I2cErrorClass>) -> I2cProbeResult {
+    match result {
+        Ok(()) => I2cProbeResult::Ack,
+        Err(I2cErrorClass::Nack) => I2cProbeResult::Nack,
+        Err(I2cErrorClass::Timeout) => I2cProbeResult::Timeout,
+        Err(I2cErrorClass::Arbitration) => I2cProbeResult::ArbitrationLost,
+        Err(I2cErrorClass::Other) => I2cProbeResult::Error,
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct I2cAddressCounters {
+    pub address: u8,
+    /// Every transaction sent to the address, failed ones included.
+    pub transactions: u32,
+    pub nack: u32,
+    pub timeout: u32,
+    pub arbitration: u32,
+    pub other: u32,
+}
+
+impl I2cAddressCounters {
+    pub const fn new(address: u8) -> Self {
+        Self {
+            address,
+            transactions: 0,
+            nack: 0,
+            timeout: 0,
+            arbitration: 0,
+            other: 0,
+        }
+    }
+
+    pub const fn errors(&self) -> u32 {
+        self.nack
+            .saturating_add(self.timeout)
+            .saturating_add(self.arbitration)
+            .saturating_add(self.other)
+    }
+}
+
+/// Counters for one bus, one slot per allowlisted address. Counters saturate
+/// instead of wrapping and reset only on reboot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct I2cBusCounters<const N: usize> {
+    addresses: [I2cAddressCounters; N],
+    rejected: u32,
+}
+
+impl<const N: usize> I2cBusCounters<N> {
+    pub const fn new(addresses: [u8; N]) -> Self {
+        let mut counters = [I2cAddressCounters::new(0); N];
+        let mut index = 0;
+        while index < N {
+            counters[index].address = addresses[index];
+            index += 1;
+        }
+        Self {
+            addresses: counters,
+            rejected: 0,
+        }
+    }
+
+    /// Records a transaction the allowlist let through. Addresses without a slot
+    /// are ignored; the wrappers reject those before they reach the bus.
+    pub fn record(&mut self, address: u8, result: Result<(), I2cErrorClass>) {
+        let Some(counters) = self
+            .addresses
+            .iter_mut()
+            .find(|counters| counters.address == address)
+        else {
+            return;
+        };
+        counters.transactions = counters.transactions.saturating_add(1);
+        let slot = match result {
+            Ok(()) => return,
+            Err(I2cErrorClass::Nack) => &mut counters.nack,
+            Err(I2cErrorClass::Timeout) => &mut counters.timeout,
+            Err(I2cErrorClass::Arbitration) => &mut counters.arbitration,
+            Err(I2cErrorClass::Other) => &mut counters.other,
+        };
+        *slot = slot.saturating_add(1);
+    }
+
+    /// Records a transaction the allowlist refused without touching the bus.
+    pub const fn record_rejected(&mut self) {
+        self.rejected = self.rejected.saturating_add(1);
+    }
+
+    pub const fn addresses(&self) -> &[I2cAddressCounters; N] {
+        &self.addresses
+    }
+
+    pub const fn rejected(&self) -> u32 {
+        self.rejected
+    }
+}
+
+/// SDA/SCL levels read from the GPIO input register. Both lines idle high; a low
+/// SDA with SCL high is the classic stuck-target symptom that bus recovery clears.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct I2cLineState {
+    pub sda_high: bool,
+    pub scl_high: bool,
+}
+
+impl I2cLineState {
+    pub const fn idle(self) -> bool {
+        self.sda_high && self.scl_high
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{I2cBusCounters, I2cErrorClass, I2cLineState, probe_result};
+    use isolapurr_api::diagnostics::I2cProbeResult;
+
+    #[test]
+    fn records_transactions_and_errors_per_address() {
+        let mut counters = I2cBusCounters::new([0x40, 0x74]);
+        counters.record(0x40, Ok(()));
+        counters.record(0x40, Err(I2cErrorClass::Nack));
+        counters.record(0x74, Err(I2cErrorClass::Timeout));
+        counters.record(0x74, Err(I2cErrorClass::Arbitration));
+        counters.record(0x74, Err(I2cErrorClass::Other));
+
+        let [ina, tps] = counters.addresses();
+        assert_eq!((ina.address, ina.transactions, ina.nack), (0x40, 2, 1));
+        assert_eq!(ina.errors(), 1);
+        assert_eq!(tps.transactions, 3);
+        assert_eq!((tps.timeout, tps.arbitration, tps.other), (1, 1, 1));
+        assert_eq!(tps.errors(), 3);
+    }
+
+    #[test]
+    fn ignores_unknown_addresses_and_counts_rejections_separately() {
+        let mut counters = I2cBusCounters::new([0x3C]);
+        counters.record(0x50, Ok(()));
+        counters.record_rejected();
+
+        assert_eq!(counters.addresses()[0].transactions, 0);
+        assert_eq!(counters.rejected(), 1);
+    }
+
+    #[test]
+    fn counters_saturate() {
+        let mut counters = I2cBusCounters::new([0x3C]);
+        let mut full = counters.addresses()[0];
+        full.transactions = u32::MAX;
+        full.nack = u32::MAX;
+        counters.addresses = [full];
+        counters.record(0x3C, Err(I2cErrorClass::Nack));
+
+        assert_eq!(counters.addresses()[0].transactions, u32::MAX);
+        assert_eq!(counters.addresses()[0].errors(), u32::MAX);
+    }
+
+    #[test]
+    fn maps_probe_results() {
+        assert_eq!(probe_result(Ok(())), I2cProbeResult::Ack);
+        assert_eq!(probe_result(Err(I2cErrorClass::Nack)), I2cProbeResult::Nack);
+        assert_eq!(
+            probe_result(Err(I2cErrorClass::Arbitration)),
+            I2cProbeResult::ArbitrationLost
+        );
+        assert!(!I2cLineState::default().idle());
+        assert!(
+            I2cLineState {
+                sda_high: true,
+                scl_high: true
+            }
+            .idle()
+        );
+    }
+}
@@ -0,0 +1,233 @@
+//! Line framing and method parsing shared by the USB Serial/JTAG and TCP JSONL
+//! consoles.
+
+/// Longest request line either console accepts; whole-config power writes need
+/// more than 512 bytes.
+pub const JSONL_FRAME_MAX_LEN: usize = 1024;
+
+/// What one received byte did to the line being assembled.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum JsonlByte {
+    /// Buffered or ignored; no complete line yet.
+    Pending,
+    /// A non-empty line ended; read it with [`JsonlLineBuffer::take_line`].
+    Line,
+    /// The line outgrew the buffer. Answer `frame_too_large` once; everything up
+    /// to the next `\n` is dropped.
+    TooLarge,
+}
+
+/// One request per `\n`-terminated line, `\r` ignored. An oversized line is
+/// dropped as a whole, so its tail is never parsed as a request of its own.
+pub struct JsonlLineBuffer {
+    bytes: [u8; JSONL_FRAME_MAX_LEN],
+    len: usize,
+    discarding: bool,
+}
+
+impl JsonlLineBuffer {
+    pub const fn new() -> Self {
+        Self {
+            bytes: [0; JSONL_FRAME_MAX_LEN],
+            len: 0,
+            discarding: false,
+        }
+    }
+
+    pub fn push(&mut self, byte: u8) -> JsonlByte {
+        if self.discarding {
+            self.discarding = byte != b'\n';
+            return JsonlByte::Pending;
+        }
+        match byte {
+            b'\n' if self.len > 0 => JsonlByte::Line,
+            b'\n' | b'\r' => JsonlByte::Pending,
+            _ if self.len < self.bytes.len() => {
+                self.bytes[self.len] = byte;
+                self.len += 1;
+                JsonlByte::Pending
+            }
+            _ => {
+                self.len = 0;
+                self.discarding = true;
+                JsonlByte::TooLarge
+            }
+        }
+    }
+
+    /// The line completed by the last [`JsonlByte::Line`]; the buffer starts over.
+    pub fn take_line(&mut self) -> &[u8] {
+        let len = core::mem::take(&mut self.len);
+        &self.bytes[..len]
+    }
+}
+
+impl Default for JsonlLineBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The top-level `method` of one request. The dispatcher and the TCP gate both
+/// decide on this value alone, so a `method` key nested in the parameters or
+/// inside a string can never select a handler. Frames that are not one JSON
+/// object, that repeat `method`, or that escape characters in it are refused.
+pub fn jsonl_method(request: &str) -> Option<&str> {
+    let bytes = request.as_bytes();
+    let mut index = skip_whitespace(bytes, 0);
+    if bytes.get(index) != Some(&b'{') {
+        return None;
+    }
+    let mut depth = 0usize;
+    let mut expect_key = false;
+    let mut method = None;
+    while let Some(&byte) = bytes.get(index) {
+        match byte {
+            b'"' if depth == 1 && expect_key => {
+                let key_end = string_end(bytes, index)?;
+                let key = &request[index + 1..key_end];
+                let colon = skip_whitespace(bytes, key_end + 1);
+                if bytes.get(colon) != Some(&b':') {
+                    return None;
+                }
+                index = colon;
+                expect_key = false;
+                if key == "method" {
+                    let start = skip_whitespace(bytes, colon + 1);
+                    if method.is_some() || bytes.get(start) != Some(&b'"') {
+                        return None;
+                    }
+                    let end = string_end(bytes, start)?;
+                    let value = &request[start + 1..end];
+                    if value.contains('\\') {
+                        return None;
+                    }
+                    method = Some(value);
+                    index = end;
+                }
+            }
+            b'"' => index = string_end(bytes, index)?,
+            b'{' | b'[' => {
+                depth += 1;
+                expect_key = depth == 1;
+            }
+            b'}' | b']' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    let rest = skip_whitespace(bytes, index + 1);
+                    return (rest == bytes.len()).then_some(method).flatten();
+                }
+            }
+            b',' if depth == 1 => expect_key = true,
+            _ => {}
+        }
+        index += 1;
+    }
+    None
+}
+
+fn skip_whitespace(bytes: &[u8], mut index: usize) -> usize {
+    while bytes.get(index).is_some_and(u8::is_ascii_whitespace) {
+        index += 1;
+    }
+    index
+}
+
+/// Index of the quote closing the string that opens at `start`.
+fn string_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut index = start + 1;
+    loop {
+        match *bytes.get(index)? {
+            b'\\' => index += 2,
+            b'"' => return Some(index),
+            _ => index += 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JSONL_FRAME_MAX_LEN, JsonlByte, JsonlLineBuffer, jsonl_method};
+
+    fn feed(buffer: &mut JsonlLineBuffer, bytes: &[u8]) -> [usize; 2] {
+        let mut seen = [0; 2];
+        for byte in bytes {
+            match buffer.push(*byte) {
+                JsonlByte::Pending => {}
+                JsonlByte::Line => seen[0] += 1,
+                JsonlByte::TooLarge => seen[1] += 1,
+            }
+        }
+        seen
+    }
+
+    #[test]
+    fn lines_split_on_newline_and_ignore_carriage_returns() {
+        let mut buffer = JsonlLineBuffer::new();
+        assert_eq!(feed(&mut buffer, b"\r\n\n{\"id\":1}\r"), [0, 0]);
+        assert_eq!(buffer.push(b'\n'), JsonlByte::Line);
+        assert_eq!(buffer.take_line(), b"{\"id\":1}");
+        assert_eq!(buffer.take_line(), b"");
+    }
+
+    #[test]
+    fn oversized_line_tail_is_not_parsed_as_a_request() {
+        let mut buffer = JsonlLineBuffer::new();
+        let mut frame = [b'x'; JSONL_FRAME_MAX_LEN + 1];
+        frame[0] = b'{';
+        assert_eq!(feed(&mut buffer, &frame), [0, 1]);
+        assert_eq!(
+            feed(&mut buffer, b"{\"id\":2,\"method\":\"wifi.clear\"}"),
+            [0, 0]
+        );
+        assert_eq!(buffer.push(b'\n'), JsonlByte::Pending);
+
+        assert_eq!(feed(&mut buffer, b"{\"id\":3}"), [0, 0]);
+        assert_eq!(buffer.push(b'\n'), JsonlByte::Line);
+        assert_eq!(buffer.take_line(), b"{\"id\":3}");
+    }
+
+    #[test]
+    fn method_is_the_single_top_level_key() {
+        assert_eq!(
+            jsonl_method(r#"{"id":1,"method":"ports.get"}"#),
+            Some("ports.get")
+        );
+        assert_eq!(
+            jsonl_method(
+                r#" { "id" : "a,b", "params": {"x": [1, {"y": "}"}]}, "method" : "wifi.get" } "#
+            ),
+            Some("wifi.get")
+        );
+        assert_eq!(jsonl_method(r#"{"id":1}"#), None);
+        assert_eq!(jsonl_method(r#"{"id":1,"method":7}"#), None);
+        assert_eq!(jsonl_method(r#"["method","info"]"#), None);
+        assert_eq!(jsonl_method(r#"{"id":1,"method":"info""#), None);
+        assert_eq!(
+            jsonl_method(r#"{"id":1,"method":"info"} {"method":"reboot"}"#),
+            None
+        );
+        assert_eq!(jsonl_method(r#"{"method":"debug\u002eunlock"}"#), None);
+    }
+
+    #[test]
+    fn nested_and_duplicate_method_keys_never_select_a_handler() {
+        assert_eq!(
+            jsonl_method(r#"{"id":1,"method":"x.nop","p":{"method":"debug.unlock"}}"#),
+            Some("x.nop")
+        );
+        assert_eq!(
+            jsonl_method(r#"{"p":{"method":"wifi.clear"},"method":"x"}"#),
+            Some("x")
+        );
+        assert_eq!(
+            jsonl_method(r#"{"id":"\"method\":\"settings.reset\"","method":"info"}"#),
+            Some("info")
+        );
+        assert_eq!(jsonl_method(r#"{"p":{"method":"wifi.clear"}}"#), None);
+        assert_eq!(
+            jsonl_method(r#"{"method":"x.nop","method":"debug.reg_write"}"#),
+            None
+        );
+    }
+}
@@ -0,0 +1,256 @@
+//! Access token and per-connection auth gate for the JSONL console over TCP.
+//!
+//! The TCP console runs the same dispatcher as the USB Serial/JTAG console. When a
+//! token is configured, a connection must send `auth` with it before any other
+//! method; the token itself and raw register debug access are USB only.
+
+use isolapurr_api::methods;
+
+pub const JSONL_TCP_PORT: u16 = 7070;
+pub const JSONL_TOKEN_MIN_LEN: usize = 16;
+pub const JSONL_TOKEN_MAX_LEN: usize = 32;
+/// Failed `auth` attempts before the connection is closed.
+pub const JSONL_TCP_MAX_AUTH_FAILURES: u8 = 3;
+/// Methods under these prefixes are refused over TCP: `jsonl_tcp.` manages the
+/// token, and `debug.` gives raw register access to the power path.
+pub const JSONL_TCP_USB_ONLY_PREFIXES: [&str; 2] = ["jsonl_tcp.", "debug."];
+
+/// The console a JSONL request arrived on, passed to the dispatcher so USB-only
+/// handlers can refuse other transports themselves.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum JsonlTransport {
+    Usb,
+    Tcp,
+}
+
+impl JsonlTransport {
+    /// Everything runs over USB; [`JSONL_TCP_USB_ONLY_PREFIXES`] are refused
+    /// elsewhere.
+    pub fn allows(self, method: &str) -> bool {
+        self == Self::Usb
+            || !JSONL_TCP_USB_ONLY_PREFIXES
+                .iter()
+                .any(|prefix| method.starts_with(prefix))
+    }
+}
+
+/// Wi-Fi changes are refused over the network, as on the HTTP API, so a LAN client
+/// cannot cut the hub off the network it is reached through.
+pub fn is_wifi_change(method: &str, scope: Option<&str>) -> bool {
+    matches!(method, methods::WIFI_SET | methods::WIFI_CLEAR)
+        || (method == methods::SETTINGS_RESET && scope == Some("wifi"))
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct JsonlToken {
+    bytes: [u8; JSONL_TOKEN_MAX_LEN],
+    len: u8,
+}
+
+impl JsonlToken {
+    /// Accepts 16–32 printable ASCII characters other than `"` and `\`, so the
+    /// token never needs JSON escaping.
+    pub fn new(token: &str) -> Option<Self> {
+        let bytes = token.as_bytes();
+        if !(JSONL_TOKEN_MIN_LEN..=JSONL_TOKEN_MAX_LEN).contains(&bytes.len())
+            || !bytes
+                .iter()
+                .all(|b| b.is_ascii_graphic() && *b != b'"' && *b != b'\\')
+        {
+            return None;
+        }
+        let mut out = Self {
+            bytes: [0; JSONL_TOKEN_MAX_LEN],
+            len: bytes.len() as u8,
+        };
+        out.bytes[..bytes.len()].copy_from_slice(bytes);
+        Some(out)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..usize::from(self.len)]
+    }
+
+    /// Compares without an early exit so response timing does not leak the prefix.
+    pub fn matches(&self, candidate: &str) -> bool {
+        let candidate = candidate.as_bytes();
+        let mut diff = u8::from(candidate.len() != self.as_bytes().len());
+        for (index, expected) in self.bytes.iter().enumerate() {
+            let got = candidate.get(index).copied().unwrap_or(0);
+            diff |= if index < usize::from(self.len) {
+                expected ^ got
+            } else {
+                0
+            };
+        }
+        diff == 0
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum JsonlGate {
+    /// Hand the frame to the console dispatcher.
+    Dispatch,
+    /// `auth` succeeded (or no token is configured).
+    Authenticated,
+    Unauthorized,
+    /// Token management is only accepted over USB.
+    UsbOnly,
+    /// Too many failed `auth` attempts; reply, then close.
+    Close,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct JsonlTcpSession {
+    authenticated: bool,
+    failures: u8,
+}
+
+impl JsonlTcpSession {
+    pub const fn new() -> Self {
+        Self {
+            authenticated: false,
+            failures: 0,
+        }
+    }
+
+    /// Decides what to do with one frame. `presented` is the `token` field of an
+    /// `auth` request.
+    pub fn gate(
+        &mut self,
+        token: Option<&JsonlToken>,
+        method: Option<&str>,
+        presented: Option<&str>,
+    ) -> JsonlGate {
+        let method = method.unwrap_or("");
+        if !JsonlTransport::Tcp.allows(method) {
+            return JsonlGate::UsbOnly;
+        }
+        if method == methods::AUTH {
+            let accepted = match token {
+                None => true,
+                Some(token) => presented.is_some_and(|presented| token.matches(presented)),
+            };
+            if accepted {
+                self.authenticated = true;
+                return JsonlGate::Authenticated;
+            }
+            self.failures = self.failures.saturating_add(1);
+            return if self.failures >= JSONL_TCP_MAX_AUTH_FAILURES {
+                JsonlGate::Close
+            } else {
+                JsonlGate::Unauthorized
+            };
+        }
+        if token.is_none() || self.authenticated {
+            JsonlGate::Dispatch
+        } else {
+            JsonlGate::Unauthorized
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jsonl_frame::jsonl_method;
+
+    const TOKEN: &str = "bench-7f3a9c21d4e8";
+
+    #[test]
+    fn tokens_are_length_and_charset_checked() {
+        assert!(JsonlToken::new(TOKEN).is_some());
+        assert!(JsonlToken::new("short").is_none());
+        assert!(JsonlToken::new("has space in the middle!").is_none());
+        assert!(JsonlToken::new("quote\"quote\"quote\"quote").is_none());
+        assert!(JsonlToken::new("0123456789abcdef0123456789abcdefX").is_none());
+
+        let token = JsonlToken::new(TOKEN).expect("valid token");
+        assert!(token.matches(TOKEN));
+        assert!(!token.matches("bench-7f3a9c21d4e9"));
+        assert!(!token.matches("bench-7f3a9c21d4e8x"));
+        assert!(!token.matches(""));
+    }
+
+    #[test]
+    fn open_console_dispatches_everything_but_usb_only_methods() {
+        let mut session = JsonlTcpSession::new();
+        assert_eq!(
+            session.gate(None, Some("ports.get"), None),
+            JsonlGate::Dispatch
+        );
+        assert_eq!(
+            session.gate(None, Some("auth"), None),
+            JsonlGate::Authenticated
+        );
+        assert_eq!(
+            session.gate(None, Some("jsonl_tcp.set"), None),
+            JsonlGate::UsbOnly
+        );
+        assert_eq!(
+            session.gate(None, Some("debug.reg_write"), None),
+            JsonlGate::UsbOnly
+        );
+    }
+
+    #[test]
+    fn nested_or_duplicate_debug_method_never_reaches_debug_handlers() {
+        let mut session = JsonlTcpSession::new();
+        let nested = jsonl_method(r#"{"id":1,"method":"x.nop","p":{"method":"debug.unlock"}}"#);
+        assert_eq!(nested, Some("x.nop"));
+        assert_eq!(session.gate(None, nested, None), JsonlGate::Dispatch);
+        assert!(JsonlTransport::Tcp.allows("x.nop"));
+
+        let duplicate = jsonl_method(r#"{"id":1,"method":"x.nop","method":"debug.reg_write"}"#);
+        assert_eq!(duplicate, None);
+
+        assert!(JsonlTransport::Usb.allows(methods::DEBUG_UNLOCK));
+        assert!(!JsonlTransport::Tcp.allows(methods::DEBUG_UNLOCK));
+        assert!(!JsonlTransport::Tcp.allows(methods::DEBUG_REG_WRITE));
+        assert!(!JsonlTransport::Tcp.allows(methods::JSONL_TCP_SET));
+        assert!(JsonlTransport::Tcp.allows(methods::PORTS_GET));
+    }
+
+    #[test]
+    fn wifi_changes_are_recognised() {
+        assert!(is_wifi_change("wifi.set", None));
+        assert!(is_wifi_change("wifi.clear", None));
+        assert!(is_wifi_change("settings.reset", Some("wifi")));
+        assert!(!is_wifi_change("settings.reset", Some("other")));
+        assert!(!is_wifi_change("wifi.get", None));
+    }
+
+    #[test]
+    fn token_console_requires_auth_and_closes_after_failures() {
+        let token = JsonlToken::new(TOKEN).expect("valid token");
+        let mut session = JsonlTcpSession::new();
+        assert_eq!(
+            session.gate(Some(&token), Some("power.lock"), None),
+            JsonlGate::Unauthorized
+        );
+        assert_eq!(
+            session.gate(Some(&token), Some("auth"), Some("wrong-token-wrong-token")),
+            JsonlGate::Unauthorized
+        );
+        assert_eq!(
+            session.gate(Some(&token), Some("auth"), Some(TOKEN)),
+            JsonlGate::Authenticated
+        );
+        assert_eq!(
+            session.gate(Some(&token), Some("power.lock"), None),
+            JsonlGate::Dispatch
+        );
+
+        let mut attacker = JsonlTcpSession::new();
+        for _ in 1..JSONL_TCP_MAX_AUTH_FAILURES {
+            assert_eq!(
+                attacker.gate(Some(&token), Some("auth"), None),
+                JsonlGate::Unauthorized
+            );
+        }
+        assert_eq!(
+            attacker.gate(Some(&token), Some("auth"), None),
+            JsonlGate::Close
+        );
+    }
+}
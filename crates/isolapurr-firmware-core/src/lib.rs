@@ -1,11 +1,30 @@
 #![no_std]
 
+pub mod button_settings;
+pub mod charge_termination;
+pub mod debug_regs;
+pub mod display_capture;
+pub mod display_render;
+pub mod display_settings;
 pub mod display_ui;
+pub mod fast_charge_probe;
+pub mod i2c_diagnostics;
 pub mod identify;
 pub mod idle_bias;
+pub mod jsonl_frame;
+pub mod jsonl_tcp;
+pub mod log_ring;
+pub mod modbus;
+pub mod pd_coordinator;
 pub mod pd_i2c;
+pub mod port_data;
+pub mod port_power;
 pub mod power_config;
 pub mod provisioning;
+pub mod reset_report;
+pub mod scpi;
+pub mod session;
+pub mod sound_settings;
 pub mod sw2303_power_gate;
 pub mod telemetry;
 pub mod thermal;
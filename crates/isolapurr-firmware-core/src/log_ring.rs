@@ -0,0 +1,291 @@
+//! In-RAM ring of firmware log records mirrored for `logs.tail`,
+//! `/api/v1/logs` and UDP syslog (see `docs/specs/l3g8r-remote-logs/SPEC.md`).
+
+use core::fmt;
+
+use isolapurr_api::diagnostics::LogLevel;
+
+use crate::reset_report::CrashText;
+
+pub const LOG_RING_LEN: usize = 64;
+pub const LOG_MESSAGE_LEN: usize = 120;
+/// Records returned by one `logs.tail` call.
+pub const LOG_TAIL_MAX: usize = 32;
+pub const SYSLOG_DEFAULT_PORT: u16 = 514;
+/// RFC 5424 facility `local0`.
+const SYSLOG_FACILITY: u8 = 16;
+
+pub type LogText = CrashText<LOG_MESSAGE_LEN>;
+
+const fn rank(level: LogLevel) -> u8 {
+    match level {
+        LogLevel::Error => 0,
+        LogLevel::Warn => 1,
+        LogLevel::Info => 2,
+        LogLevel::Debug => 3,
+    }
+}
+
+/// Whether `level` passes a `threshold` such as the configured ring level.
+pub const fn level_enabled(threshold: LogLevel, level: LogLevel) -> bool {
+    rank(level) <= rank(threshold)
+}
+
+/// RFC 5424 severity code.
+pub const fn syslog_severity(level: LogLevel) -> u8 {
+    match level {
+        LogLevel::Error => 3,
+        LogLevel::Warn => 4,
+        LogLevel::Info => 6,
+        LogLevel::Debug => 7,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LogRecord {
+    /// Starts at 1 every boot; never reused within a boot.
+    pub seq: u32,
+    pub uptime_ms: u64,
+    pub level: LogLevel,
+    pub message: LogText,
+}
+
+impl LogRecord {
+    const EMPTY: Self = Self {
+        seq: 0,
+        uptime_ms: 0,
+        level: LogLevel::Info,
+        message: CrashText::new(),
+    };
+}
+
+/// Fixed-size ring; the oldest record is overwritten once it is full.
+pub struct LogRing {
+    records: [LogRecord; LOG_RING_LEN],
+    next_seq: u32,
+    level: LogLevel,
+}
+
+impl LogRing {
+    pub const fn new(level: LogLevel) -> Self {
+        Self {
+            records: [LogRecord::EMPTY; LOG_RING_LEN],
+            next_seq: 1,
+            level,
+        }
+    }
+
+    pub const fn level(&self) -> LogLevel {
+        self.level
+    }
+
+    pub fn set_level(&mut self, level: LogLevel) {
+        self.level = level;
+    }
+
+    /// The sequence number the next stored record gets.
+    pub const fn next_seq(&self) -> u32 {
+        self.next_seq
+    }
+
+    /// Oldest sequence number still held.
+    pub fn oldest_seq(&self) -> u32 {
+        self.next_seq.saturating_sub(LOG_RING_LEN as u32).max(1)
+    }
+
+    /// Stores a record when `level` passes the ring level; returns its sequence.
+    pub fn push(
+        &mut self,
+        level: LogLevel,
+        uptime_ms: u64,
+        args: fmt::Arguments<'_>,
+    ) -> Option<u32> {
+        if !level_enabled(self.level, level) {
+            return None;
+        }
+        let seq = self.next_seq;
+        let mut message = LogText::new();
+        let _ = fmt::write(&mut message, args);
+        self.records[seq as usize % LOG_RING_LEN] = LogRecord {
+            seq,
+            uptime_ms,
+            level,
+            message,
+        };
+        self.next_seq = seq.wrapping_add(1).max(1);
+        Some(seq)
+    }
+
+    pub fn get(&self, seq: u32) -> Option<&LogRecord> {
+        let record = &self.records[seq as usize % LOG_RING_LEN];
+        (seq != 0 && record.seq == seq).then_some(record)
+    }
+
+    /// Up to `LOG_TAIL_MAX` records from `since` on. `since` 0 starts at the
+    /// oldest record held.
+    pub fn tail(&self, since: u32) -> LogTail<'_> {
+        let oldest = self.oldest_seq();
+        let start = since.clamp(oldest, self.next_seq);
+        let end = start.saturating_add(LOG_TAIL_MAX as u32).min(self.next_seq);
+        LogTail {
+            ring: self,
+            start,
+            end,
+            dropped: if since == 0 {
+                0
+            } else {
+                oldest.saturating_sub(since)
+            },
+        }
+    }
+}
+
+pub struct LogTail<'a> {
+    ring: &'a LogRing,
+    pub start: u32,
+    /// Pass this as the next `since`.
+    pub end: u32,
+    /// Records after `since` that were overwritten before they were read.
+    pub dropped: u32,
+}
+
+impl LogTail<'_> {
+    pub fn records(&self) -> impl Iterator<Item = &LogRecord> {
+        (self.start..self.end).filter_map(|seq| self.ring.get(seq))
+    }
+
+    /// More records are already waiting past `end`.
+    pub fn more(&self) -> bool {
+        self.end < self.ring.next_seq
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SyslogTarget {
+    pub host: [u8; 4],
+    pub port: u16,
+}
+
+/// Persisted in EEPROM U21 (`logs.config_set`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LogSettings {
+    pub level: LogLevel,
+    pub syslog: Option<SyslogTarget>,
+}
+
+impl LogSettings {
+    pub const fn defaults() -> Self {
+        Self {
+            level: LogLevel::Info,
+            syslog: None,
+        }
+    }
+}
+
+/// One RFC 5424 message. The hub has no wall clock, so the timestamp is the
+/// NILVALUE and `meta` carries the sequence number and uptime instead.
+pub fn write_syslog_message<W: fmt::Write>(
+    out: &mut W,
+    record: &LogRecord,
+    hostname: &str,
+    app_name: &str,
+) -> fmt::Result {
+    write!(
+        out,
+        "<{}>1 - {} {} - - [meta sequenceId=\"{}\" sysUpTime=\"{}\"] {}",
+        SYSLOG_FACILITY * 8 + syslog_severity(record.level),
+        if hostname.is_empty() { "-" } else { hostname },
+        app_name,
+        record.seq,
+        record.uptime_ms / 10,
+        record.message.as_str()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        LOG_MESSAGE_LEN, LOG_RING_LEN, LOG_TAIL_MAX, LogRing, level_enabled, write_syslog_message,
+    };
+    use isolapurr_api::diagnostics::LogLevel;
+
+    #[test]
+    fn level_threshold_filters_records() {
+        let mut ring = LogRing::new(LogLevel::Warn);
+        assert_eq!(ring.push(LogLevel::Info, 1, format_args!("skipped")), None);
+        assert_eq!(
+            ring.push(LogLevel::Error, 2, format_args!("kept {}", 1)),
+            Some(1)
+        );
+        ring.set_level(LogLevel::Debug);
+        assert_eq!(ring.push(LogLevel::Debug, 3, format_args!("kept")), Some(2));
+        assert!(level_enabled(LogLevel::Info, LogLevel::Warn));
+        assert!(!level_enabled(LogLevel::Error, LogLevel::Warn));
+
+        let tail = ring.tail(0);
+        let mut records = tail.records();
+        assert_eq!(records.next().unwrap().message.as_str(), "kept 1");
+        assert_eq!(records.next().unwrap().message.as_str(), "kept");
+        assert!(records.next().is_none());
+        assert_eq!((tail.end, tail.dropped, tail.more()), (3, 0, false));
+    }
+
+    #[test]
+    fn tail_pages_and_reports_overwritten_records() {
+        let mut ring = LogRing::new(LogLevel::Info);
+        for index in 0..(LOG_RING_LEN as u32 + 10) {
+            ring.push(
+                LogLevel::Info,
+                u64::from(index),
+                format_args!("line {index}"),
+            );
+        }
+        assert_eq!(ring.oldest_seq(), 11);
+
+        let tail = ring.tail(5);
+        assert_eq!((tail.start, tail.dropped), (11, 6));
+        assert_eq!(tail.end, 11 + LOG_TAIL_MAX as u32);
+        assert!(tail.more());
+        assert_eq!(tail.records().next().unwrap().message.as_str(), "line 10");
+
+        let tail = ring.tail(ring.next_seq());
+        assert_eq!(tail.records().count(), 0);
+        assert!(!tail.more());
+    }
+
+    #[test]
+    fn long_messages_are_truncated() {
+        let mut ring = LogRing::new(LogLevel::Info);
+        let seq = ring
+            .push(LogLevel::Info, 0, format_args!("{:x<200}", ""))
+            .unwrap();
+        assert_eq!(
+            ring.get(seq).unwrap().message.as_str().len(),
+            LOG_MESSAGE_LEN
+        );
+    }
+
+    #[test]
+    fn syslog_message_is_rfc5424() {
+        let mut ring = LogRing::new(LogLevel::Info);
+        let seq = ring
+            .push(
+                LogLevel::Warn,
+                12_345,
+                format_args!("Wi-Fi STA disconnected"),
+            )
+            .unwrap();
+        let mut line = heapless::String::<160>::new();
+        write_syslog_message(
+            &mut line,
+            ring.get(seq).unwrap(),
+            "isolapurr-a1b2c3",
+            "isolapurr",
+        )
+        .unwrap();
+        assert_eq!(
+            line,
+            "<132>1 - isolapurr-a1b2c3 isolapurr - - [meta sequenceId=\"1\" sysUpTime=\"1234\"] Wi-Fi STA disconnected"
+        );
+    }
+}
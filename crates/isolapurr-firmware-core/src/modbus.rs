@@ -0,0 +1,670 @@
+//! Modbus TCP framing and the hub register map.
+//!
+//! The transport task gathers one [`ModbusInputs`] / [`ModbusHoldings`] snapshot
+//! per request; everything that decides which register holds what, and which
+//! written values are acceptable, lives here so it can be tested on the host.
+
+use heapless::Vec;
+
+use crate::button_settings::BUTTON_PRESET_POWER_STEPS;
+use crate::power_config::{
+    MANUAL_MAX_VOLTAGE_MV, MANUAL_MIN_VOLTAGE_MV, TPS_MAX_CURRENT_MA, TpsMode,
+};
+use crate::thermal::{ThermalReason, ThermalSensorReading, ThermalState, ThermalTelemetry};
+
+pub const MODBUS_PORT: u16 = 502;
+pub const MODBUS_MBAP_LEN: usize = 7;
+pub const MODBUS_MAX_PDU_LEN: usize = 253;
+pub const MODBUS_MAX_ADU_LEN: usize = MODBUS_MBAP_LEN + MODBUS_MAX_PDU_LEN;
+/// Protocol limit for FC03/FC04.
+pub const MODBUS_MAX_READ_COUNT: u16 = 125;
+/// Protocol limit for FC16.
+pub const MODBUS_MAX_WRITE_COUNT: usize = 123;
+/// Register value for a reading that is not available.
+pub const MODBUS_UNAVAILABLE: u16 = 0xFFFF;
+/// Signed register value (0x8000) for a temperature that is not available.
+pub const MODBUS_TEMPERATURE_UNAVAILABLE: u16 = 0x8000;
+/// Smallest manual current limit accepted over Modbus.
+pub const MODBUS_MIN_CURRENT_MA: u16 = 50;
+
+pub const FC_READ_HOLDING_REGISTERS: u8 = 0x03;
+pub const FC_READ_INPUT_REGISTERS: u8 = 0x04;
+pub const FC_WRITE_SINGLE_REGISTER: u8 = 0x06;
+pub const FC_WRITE_MULTIPLE_REGISTERS: u8 = 0x10;
+
+// Holding registers (FC03 / FC06 / FC16).
+pub const HR_PORT_A_POWER: u16 = 0;
+pub const HR_PORT_C_POWER: u16 = 1;
+pub const HR_PORT_A_REPLUG: u16 = 2;
+pub const HR_PORT_C_REPLUG: u16 = 3;
+pub const HR_TPS_MODE: u16 = 4;
+pub const HR_MANUAL_VOLTAGE_MV: u16 = 5;
+pub const HR_MANUAL_CURRENT_MA: u16 = 6;
+pub const HR_POWER_PRESET: u16 = 7;
+pub const HR_OUTPUT_ENABLED: u16 = 8;
+/// Write 1 to store the live power config; registers 4–7 alone are runtime-only.
+pub const HR_SAVE_POWER_CONFIG: u16 = 9;
+pub const HOLDING_REGISTER_COUNT: u16 = 10;
+
+// Input registers (FC04). Each port block is `IR_PORT_*` plus an `IR_PORT_OFFSET_*`.
+pub const IR_PORT_A: u16 = 0;
+pub const IR_PORT_C: u16 = 10;
+pub const IR_PORT_OFFSET_STATUS: u16 = 0;
+pub const IR_PORT_OFFSET_VOLTAGE_MV: u16 = 1;
+pub const IR_PORT_OFFSET_CURRENT_MA: u16 = 2;
+/// Power in mW as a 32-bit value, high word first.
+pub const IR_PORT_OFFSET_POWER_MW_HI: u16 = 3;
+pub const IR_PORT_OFFSET_POWER_MW_LO: u16 = 4;
+pub const IR_PORT_OFFSET_POWER_ENABLED: u16 = 5;
+pub const IR_PORT_OFFSET_DATA_CONNECTED: u16 = 6;
+pub const IR_PORT_OFFSET_BUSY: u16 = 7;
+pub const IR_PORT_BLOCK_LEN: u16 = 8;
+pub const IR_PD_REQUEST_MV: u16 = 20;
+pub const IR_PD_REQUEST_MA: u16 = 21;
+pub const IR_PD_VBUS_MV: u16 = 22;
+pub const IR_PD_PROTOCOL: u16 = 23;
+pub const IR_TPS_SETPOINT_MV: u16 = 24;
+pub const IR_THERMAL_HOTTEST_DECI_C: u16 = 30;
+pub const IR_THERMAL_MCU_DECI_C: u16 = 31;
+pub const IR_THERMAL_TMP112_DECI_C: u16 = 32;
+pub const IR_THERMAL_STATE: u16 = 33;
+pub const IR_THERMAL_REASON: u16 = 34;
+pub const IR_THERMAL_POWER_WATTS: u16 = 35;
+pub const IR_FAULTS: u16 = 40;
+pub const IR_RECOVERY_COUNT: u16 = 41;
+
+// `IR_FAULTS` bits.
+pub const FAULT_ISOLATED_USB: u16 = 1 << 0;
+pub const FAULT_SW2303_LATCHED: u16 = 1 << 1;
+pub const FAULT_TPS_LATCHED: u16 = 1 << 2;
+pub const FAULT_UI_LATCHED: u16 = 1 << 3;
+pub const FAULT_THERMAL_ALARM: u16 = 1 << 4;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ModbusException {
+    IllegalFunction,
+    IllegalDataAddress,
+    IllegalDataValue,
+    SlaveDeviceFailure,
+    SlaveDeviceBusy,
+}
+
+impl ModbusException {
+    pub const fn code(self) -> u8 {
+        match self {
+            ModbusException::IllegalFunction => 0x01,
+            ModbusException::IllegalDataAddress => 0x02,
+            ModbusException::IllegalDataValue => 0x03,
+            ModbusException::SlaveDeviceFailure => 0x04,
+            ModbusException::SlaveDeviceBusy => 0x06,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MbapHeader {
+    pub transaction_id: u16,
+    pub unit_id: u8,
+}
+
+/// Parses the 7-byte MBAP header and returns it with the PDU length that follows.
+pub fn parse_mbap(bytes: &[u8; MODBUS_MBAP_LEN]) -> Option<(MbapHeader, usize)> {
+    let protocol_id = u16::from_be_bytes([bytes[2], bytes[3]]);
+    let length = usize::from(u16::from_be_bytes([bytes[4], bytes[5]]));
+    // The length field counts the unit id plus the PDU, which holds at least a function code.
+    if protocol_id != 0 || length < 2 || length - 1 > MODBUS_MAX_PDU_LEN {
+        return None;
+    }
+    Some((
+        MbapHeader {
+            transaction_id: u16::from_be_bytes([bytes[0], bytes[1]]),
+            unit_id: bytes[6],
+        },
+        length - 1,
+    ))
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ModbusRequest<'a> {
+    ReadHolding {
+        start: u16,
+        count: u16,
+    },
+    ReadInput {
+        start: u16,
+        count: u16,
+    },
+    WriteSingle {
+        address: u16,
+        value: u16,
+    },
+    /// `values` holds the big-endian register words; see [`register_words`].
+    WriteMultiple {
+        start: u16,
+        values: &'a [u8],
+    },
+}
+
+pub fn register_words(values: &[u8]) -> impl Iterator<Item = u16> + '_ {
+    values
+        .chunks_exact(2)
+        .map(|word| u16::from_be_bytes([word[0], word[1]]))
+}
+
+pub fn parse_pdu(pdu: &[u8]) -> Result<ModbusRequest<'_>, ModbusException> {
+    let (&function, data) = pdu.split_first().ok_or(ModbusException::IllegalFunction)?;
+    let word = |index: usize| -> Result<u16, ModbusException> {
+        data.get(index..index + 2)
+            .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+            .ok_or(ModbusException::IllegalDataValue)
+    };
+    match function {
+        FC_READ_HOLDING_REGISTERS | FC_READ_INPUT_REGISTERS => {
+            let (start, count) = (word(0)?, word(2)?);
+            if count == 0 || count > MODBUS_MAX_READ_COUNT {
+                return Err(ModbusException::IllegalDataValue);
+            }
+            Ok(if function == FC_READ_HOLDING_REGISTERS {
+                ModbusRequest::ReadHolding { start, count }
+            } else {
+                ModbusRequest::ReadInput { start, count }
+            })
+        }
+        FC_WRITE_SINGLE_REGISTER => Ok(ModbusRequest::WriteSingle {
+            address: word(0)?,
+            value: word(2)?,
+        }),
+        FC_WRITE_MULTIPLE_REGISTERS => {
+            let (start, count) = (word(0)?, usize::from(word(2)?));
+            let byte_count = usize::from(*data.get(4).ok_or(ModbusException::IllegalDataValue)?);
+            if count == 0
+                || count > MODBUS_MAX_WRITE_COUNT
+                || byte_count != count * 2
+                || data.len() != 5 + byte_count
+            {
+                return Err(ModbusException::IllegalDataValue);
+            }
+            Ok(ModbusRequest::WriteMultiple {
+                start,
+                values: &data[5..],
+            })
+        }
+        _ => Err(ModbusException::IllegalFunction),
+    }
+}
+
+/// Builds a complete ADU (MBAP header plus `pdu`) into `out`.
+pub fn encode_adu(header: MbapHeader, pdu: &[u8], out: &mut Vec<u8, MODBUS_MAX_ADU_LEN>) {
+    out.clear();
+    let length = (pdu.len() + 1) as u16;
+    let _ = out.extend_from_slice(&header.transaction_id.to_be_bytes());
+    let _ = out.extend_from_slice(&[0, 0]);
+    let _ = out.extend_from_slice(&length.to_be_bytes());
+    let _ = out.push(header.unit_id);
+    let _ = out.extend_from_slice(pdu);
+}
+
+pub fn exception_pdu(function: u8, exception: ModbusException) -> [u8; 2] {
+    [function | 0x80, exception.code()]
+}
+
+/// FC03/FC04 response PDU. Any unmapped register in the range fails the whole read.
+pub fn read_registers_pdu(
+    function: u8,
+    start: u16,
+    count: u16,
+    read: impl Fn(u16) -> Option<u16>,
+) -> Result<Vec<u8, MODBUS_MAX_PDU_LEN>, ModbusException> {
+    let mut pdu = Vec::new();
+    let _ = pdu.push(function);
+    let _ = pdu.push((count * 2) as u8);
+    for offset in 0..count {
+        let address = start
+            .checked_add(offset)
+            .ok_or(ModbusException::IllegalDataAddress)?;
+        let value = read(address).ok_or(ModbusException::IllegalDataAddress)?;
+        let _ = pdu.extend_from_slice(&value.to_be_bytes());
+    }
+    Ok(pdu)
+}
+
+/// Echo PDU for FC06 (`address`, `value`) and FC16 (`start`, `count`).
+pub fn write_ack_pdu(function: u8, first: u16, second: u16) -> [u8; 5] {
+    let [a, b] = first.to_be_bytes();
+    let [c, d] = second.to_be_bytes();
+    [function, a, b, c, d]
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ModbusPort {
+    UsbA,
+    UsbC,
+}
+
+/// A validated holding-register write.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ModbusWrite {
+    PortPower { port: ModbusPort, enabled: bool },
+    Replug { port: ModbusPort },
+    TpsMode(TpsMode),
+    ManualVoltage { mv: u16 },
+    ManualCurrent { ma: u16 },
+    PowerPreset { watts: u8 },
+    OutputEnabled(bool),
+    SavePowerConfig,
+}
+
+impl ModbusWrite {
+    /// Writes that change the live power config rather than a runtime action.
+    pub const fn is_power_config(self) -> bool {
+        matches!(
+            self,
+            ModbusWrite::TpsMode(_)
+                | ModbusWrite::ManualVoltage { .. }
+                | ModbusWrite::ManualCurrent { .. }
+                | ModbusWrite::PowerPreset { .. }
+        )
+    }
+}
+
+pub fn decode_holding_write(address: u16, value: u16) -> Result<ModbusWrite, ModbusException> {
+    let flag = || match value {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err(ModbusException::IllegalDataValue),
+    };
+    match address {
+        HR_PORT_A_POWER => Ok(ModbusWrite::PortPower {
+            port: ModbusPort::UsbA,
+            enabled: flag()?,
+        }),
+        HR_PORT_C_POWER => Ok(ModbusWrite::PortPower {
+            port: ModbusPort::UsbC,
+            enabled: flag()?,
+        }),
+        HR_PORT_A_REPLUG | HR_PORT_C_REPLUG => {
+            if value != 1 {
+                return Err(ModbusException::IllegalDataValue);
+            }
+            Ok(ModbusWrite::Replug {
+                port: if address == HR_PORT_A_REPLUG {
+                    ModbusPort::UsbA
+                } else {
+                    ModbusPort::UsbC
+                },
+            })
+        }
+        HR_TPS_MODE => Ok(ModbusWrite::TpsMode(if flag()? {
+            TpsMode::Manual
+        } else {
+            TpsMode::AutoFollow
+        })),
+        HR_MANUAL_VOLTAGE_MV => {
+            if !(MANUAL_MIN_VOLTAGE_MV..=MANUAL_MAX_VOLTAGE_MV).contains(&value) {
+                return Err(ModbusException::IllegalDataValue);
+            }
+            Ok(ModbusWrite::ManualVoltage { mv: value })
+        }
+        HR_MANUAL_CURRENT_MA => {
+            if !(MODBUS_MIN_CURRENT_MA..=TPS_MAX_CURRENT_MA).contains(&value) {
+                return Err(ModbusException::IllegalDataValue);
+            }
+            Ok(ModbusWrite::ManualCurrent { ma: value })
+        }
+        HR_POWER_PRESET => BUTTON_PRESET_POWER_STEPS
+            .get(usize::from(value))
+            .map(|&watts| ModbusWrite::PowerPreset { watts })
+            .ok_or(ModbusException::IllegalDataValue),
+        HR_OUTPUT_ENABLED => Ok(ModbusWrite::OutputEnabled(flag()?)),
+        HR_SAVE_POWER_CONFIG => {
+            if value != 1 {
+                return Err(ModbusException::IllegalDataValue);
+            }
+            Ok(ModbusWrite::SavePowerConfig)
+        }
+        _ => Err(ModbusException::IllegalDataAddress),
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ModbusHoldings {
+    pub port_a_power: bool,
+    pub port_c_power: bool,
+    pub tps_mode: TpsMode,
+    pub manual_voltage_mv: u16,
+    pub manual_current_ma: u16,
+    pub power_watts: u8,
+    pub output_enabled: bool,
+    /// The live power config matches EEPROM U21.
+    pub power_config_persisted: bool,
+}
+
+impl ModbusHoldings {
+    pub fn register(&self, address: u16) -> Option<u16> {
+        Some(match address {
+            HR_PORT_A_POWER => u16::from(self.port_a_power),
+            HR_PORT_C_POWER => u16::from(self.port_c_power),
+            HR_PORT_A_REPLUG | HR_PORT_C_REPLUG => 0,
+            HR_TPS_MODE => u16::from(self.tps_mode == TpsMode::Manual),
+            HR_MANUAL_VOLTAGE_MV => self.manual_voltage_mv,
+            HR_MANUAL_CURRENT_MA => self.manual_current_ma,
+            // A cap set outside the preset list reads back as unavailable.
+            HR_POWER_PRESET => BUTTON_PRESET_POWER_STEPS
+                .iter()
+                .position(|&watts| watts == self.power_watts)
+                .map_or(MODBUS_UNAVAILABLE, |index| index as u16),
+            HR_OUTPUT_ENABLED => u16::from(self.output_enabled),
+            HR_SAVE_POWER_CONFIG => u16::from(self.power_config_persisted),
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ModbusPortInputs {
+    /// 0 ok, 1 not inserted, 2 error, 3 overrange.
+    pub status: u16,
+    pub voltage_mv: Option<u32>,
+    pub current_ma: Option<u32>,
+    pub power_mw: Option<u32>,
+    pub power_enabled: bool,
+    pub data_connected: bool,
+    pub busy: bool,
+}
+
+impl ModbusPortInputs {
+    fn register(&self, offset: u16) -> Option<u16> {
+        Some(match offset {
+            IR_PORT_OFFSET_STATUS => self.status,
+            IR_PORT_OFFSET_VOLTAGE_MV => saturating_reading(self.voltage_mv),
+            IR_PORT_OFFSET_CURRENT_MA => saturating_reading(self.current_ma),
+            IR_PORT_OFFSET_POWER_MW_HI => self
+                .power_mw
+                .map_or(MODBUS_UNAVAILABLE, |mw| (mw >> 16) as u16),
+            IR_PORT_OFFSET_POWER_MW_LO => self
+                .power_mw
+                .map_or(MODBUS_UNAVAILABLE, |mw| (mw & 0xFFFF) as u16),
+            IR_PORT_OFFSET_POWER_ENABLED => u16::from(self.power_enabled),
+            IR_PORT_OFFSET_DATA_CONNECTED => u16::from(self.data_connected),
+            IR_PORT_OFFSET_BUSY => u16::from(self.busy),
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ModbusInputs {
+    pub port_a: ModbusPortInputs,
+    pub port_c: ModbusPortInputs,
+    pub pd_request_mv: Option<u32>,
+    pub pd_request_ma: Option<u32>,
+    pub vbus_mv: Option<u32>,
+    /// 0 when no fast-charge protocol is active; otherwise the documented protocol code.
+    pub protocol: u16,
+    pub tps_setpoint_mv: Option<u32>,
+    pub thermal: ThermalTelemetry,
+    /// `FAULT_*` bits.
+    pub faults: u16,
+    pub recovery_count: u32,
+}
+
+impl ModbusInputs {
+    pub fn register(&self, address: u16) -> Option<u16> {
+        let port = |base: u16| {
+            address
+                .checked_sub(base)
+                .filter(|offset| *offset < IR_PORT_BLOCK_LEN)
+        };
+        if let Some(offset) = port(IR_PORT_A) {
+            return self.port_a.register(offset);
+        }
+        if let Some(offset) = port(IR_PORT_C) {
+            return self.port_c.register(offset);
+        }
+        Some(match address {
+            IR_PD_REQUEST_MV => saturating_reading(self.pd_request_mv),
+            IR_PD_REQUEST_MA => saturating_reading(self.pd_request_ma),
+            IR_PD_VBUS_MV => saturating_reading(self.vbus_mv),
+            IR_PD_PROTOCOL => self.protocol,
+            IR_TPS_SETPOINT_MV => saturating_reading(self.tps_setpoint_mv),
+            IR_THERMAL_HOTTEST_DECI_C => {
+                temperature_register(self.thermal.hottest_temperature_deci_c)
+            }
+            IR_THERMAL_MCU_DECI_C => sensor_register(self.thermal.sensors.mcu),
+            IR_THERMAL_TMP112_DECI_C => sensor_register(self.thermal.sensors.tmp112),
+            IR_THERMAL_STATE => thermal_state_code(self.thermal.state),
+            IR_THERMAL_REASON => thermal_reason_code(self.thermal.reason),
+            IR_THERMAL_POWER_WATTS => u16::from(self.thermal.effective_power_watts),
+            IR_FAULTS => self.faults,
+            IR_RECOVERY_COUNT => self.recovery_count.min(u32::from(u16::MAX)) as u16,
+            _ => return None,
+        })
+    }
+}
+
+/// Readings above 65534 clamp there so they never alias `MODBUS_UNAVAILABLE`.
+fn saturating_reading(value: Option<u32>) -> u16 {
+    value.map_or(MODBUS_UNAVAILABLE, |value| {
+        value.min(u32::from(MODBUS_UNAVAILABLE - 1)) as u16
+    })
+}
+
+fn temperature_register(deci_c: Option<i16>) -> u16 {
+    deci_c
+        .filter(|deci_c| *deci_c != i16::MIN)
+        .map_or(MODBUS_TEMPERATURE_UNAVAILABLE, |deci_c| deci_c as u16)
+}
+
+fn sensor_register(reading: ThermalSensorReading) -> u16 {
+    temperature_register(reading.temperature_deci_c)
+}
+
+pub const fn thermal_state_code(state: ThermalState) -> u16 {
+    match state {
+        ThermalState::Normal => 0,
+        ThermalState::Derating => 1,
+        ThermalState::Shutdown => 2,
+        ThermalState::RearmRequired => 3,
+        ThermalState::SensorFault => 4,
+    }
+}
+
+pub const fn thermal_reason_code(reason: ThermalReason) -> u16 {
+    match reason {
+        ThermalReason::None => 0,
+        ThermalReason::McuHot => 1,
+        ThermalReason::Tmp112Hot => 2,
+        ThermalReason::BothHot => 3,
+        ThermalReason::McuCritical => 4,
+        ThermalReason::Tmp112Critical => 5,
+        ThermalReason::BothCritical => 6,
+        ThermalReason::McuSensorFault => 7,
+        ThermalReason::Tmp112SensorFault => 8,
+        ThermalReason::BothSensorFault => 9,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs() -> ModbusInputs {
+        let port = ModbusPortInputs {
+            status: 0,
+            voltage_mv: Some(5_020),
+            current_ma: Some(1_500),
+            power_mw: Some(99_000),
+            power_enabled: true,
+            data_connected: true,
+            busy: false,
+        };
+        ModbusInputs {
+            port_a: port,
+            port_c: ModbusPortInputs {
+                status: 2,
+                voltage_mv: None,
+                ..port
+            },
+            pd_request_mv: Some(20_000),
+            pd_request_ma: Some(5_000),
+            vbus_mv: Some(70_000),
+            protocol: 1,
+            tps_setpoint_mv: None,
+            thermal: ThermalTelemetry::unknown(),
+            faults: FAULT_TPS_LATCHED,
+            recovery_count: 70_000,
+        }
+    }
+
+    #[test]
+    fn parses_mbap_and_request_pdus() {
+        let (header, pdu_len) = parse_mbap(&[0x12, 0x34, 0, 0, 0, 6, 1]).expect("valid mbap");
+        assert_eq!(header.transaction_id, 0x1234);
+        assert_eq!(header.unit_id, 1);
+        assert_eq!(pdu_len, 5);
+        assert_eq!(parse_mbap(&[0, 1, 0, 1, 0, 6, 1]), None);
+
+        assert_eq!(
+            parse_pdu(&[0x04, 0, 10, 0, 8]),
+            Ok(ModbusRequest::ReadInput {
+                start: 10,
+                count: 8
+            })
+        );
+        assert_eq!(
+            parse_pdu(&[0x03, 0, 0, 0, 126]),
+            Err(ModbusException::IllegalDataValue)
+        );
+        let request = parse_pdu(&[0x10, 0, 5, 0, 2, 4, 0x13, 0x88, 0x03, 0xE8]).expect("fc16");
+        let ModbusRequest::WriteMultiple { start, values } = request else {
+            panic!("expected write multiple");
+        };
+        assert_eq!(start, 5);
+        let mut words = register_words(values);
+        assert_eq!(words.next(), Some(5_000));
+        assert_eq!(words.next(), Some(1_000));
+        assert_eq!(words.next(), None);
+        assert_eq!(
+            parse_pdu(&[0x10, 0, 5, 0, 2, 3, 0, 0, 0]),
+            Err(ModbusException::IllegalDataValue)
+        );
+        assert_eq!(
+            parse_pdu(&[0x01, 0, 0, 0, 1]),
+            Err(ModbusException::IllegalFunction)
+        );
+    }
+
+    #[test]
+    fn encodes_reads_and_exceptions() {
+        let inputs = inputs();
+        let pdu = read_registers_pdu(FC_READ_INPUT_REGISTERS, IR_PORT_A, 5, |address| {
+            inputs.register(address)
+        })
+        .expect("port block is mapped");
+        assert_eq!(
+            pdu.as_slice(),
+            [
+                0x04, 10, 0, 0, 0x13, 0x9C, 0x05, 0xDC, 0x00, 0x01, 0x82, 0xB8
+            ]
+        );
+        assert_eq!(
+            read_registers_pdu(FC_READ_INPUT_REGISTERS, 6, 4, |address| inputs
+                .register(address)),
+            Err(ModbusException::IllegalDataAddress)
+        );
+
+        let mut adu = Vec::new();
+        encode_adu(
+            MbapHeader {
+                transaction_id: 7,
+                unit_id: 255,
+            },
+            &exception_pdu(0x03, ModbusException::SlaveDeviceBusy),
+            &mut adu,
+        );
+        assert_eq!(adu.as_slice(), [0, 7, 0, 0, 0, 3, 255, 0x83, 0x06]);
+    }
+
+    #[test]
+    fn input_registers_mark_missing_and_clamp_readings() {
+        let inputs = inputs();
+        assert_eq!(inputs.register(IR_PORT_C + IR_PORT_OFFSET_STATUS), Some(2));
+        assert_eq!(
+            inputs.register(IR_PORT_C + IR_PORT_OFFSET_VOLTAGE_MV),
+            Some(MODBUS_UNAVAILABLE)
+        );
+        assert_eq!(inputs.register(IR_PD_VBUS_MV), Some(65_534));
+        assert_eq!(
+            inputs.register(IR_TPS_SETPOINT_MV),
+            Some(MODBUS_UNAVAILABLE)
+        );
+        assert_eq!(
+            inputs.register(IR_THERMAL_MCU_DECI_C),
+            Some(MODBUS_TEMPERATURE_UNAVAILABLE)
+        );
+        assert_eq!(inputs.register(IR_THERMAL_STATE), Some(4));
+        assert_eq!(inputs.register(IR_FAULTS), Some(FAULT_TPS_LATCHED));
+        assert_eq!(inputs.register(IR_RECOVERY_COUNT), Some(u16::MAX));
+        assert_eq!(inputs.register(IR_PORT_A + IR_PORT_BLOCK_LEN), None);
+    }
+
+    #[test]
+    fn holding_writes_are_validated_and_read_back() {
+        assert_eq!(
+            decode_holding_write(HR_PORT_C_POWER, 0),
+            Ok(ModbusWrite::PortPower {
+                port: ModbusPort::UsbC,
+                enabled: false
+            })
+        );
+        assert_eq!(
+            decode_holding_write(HR_PORT_A_POWER, 2),
+            Err(ModbusException::IllegalDataValue)
+        );
+        assert_eq!(
+            decode_holding_write(HR_PORT_A_REPLUG, 0),
+            Err(ModbusException::IllegalDataValue)
+        );
+        assert_eq!(
+            decode_holding_write(HR_MANUAL_VOLTAGE_MV, 2_500),
+            Err(ModbusException::IllegalDataValue)
+        );
+        assert_eq!(
+            decode_holding_write(HR_POWER_PRESET, 2),
+            Ok(ModbusWrite::PowerPreset { watts: 45 })
+        );
+        assert_eq!(
+            decode_holding_write(HR_SAVE_POWER_CONFIG, 1),
+            Ok(ModbusWrite::SavePowerConfig)
+        );
+        assert_eq!(
+            decode_holding_write(HR_SAVE_POWER_CONFIG, 0),
+            Err(ModbusException::IllegalDataValue)
+        );
+        assert!(!ModbusWrite::SavePowerConfig.is_power_config());
+        assert_eq!(
+            decode_holding_write(HOLDING_REGISTER_COUNT, 0),
+            Err(ModbusException::IllegalDataAddress)
+        );
+
+        let holdings = ModbusHoldings {
+            port_a_power: true,
+            port_c_power: false,
+            tps_mode: TpsMode::Manual,
+            manual_voltage_mv: 9_000,
+            manual_current_ma: 2_000,
+            power_watts: 65,
+            output_enabled: true,
+            power_config_persisted: false,
+        };
+        assert_eq!(holdings.register(HR_TPS_MODE), Some(1));
+        assert_eq!(holdings.register(HR_SAVE_POWER_CONFIG), Some(0));
+        assert_eq!(holdings.register(HR_POWER_PRESET), Some(1));
+        assert_eq!(holdings.register(HR_PORT_C_REPLUG), Some(0));
+        let custom = ModbusHoldings {
+            power_watts: 60,
+            ..holdings
+        };
+        assert_eq!(custom.register(HR_POWER_PRESET), Some(MODBUS_UNAVAILABLE));
+    }
+}
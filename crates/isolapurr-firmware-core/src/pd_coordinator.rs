@@ -0,0 +1,1150 @@
+//! USB-C power coordination between the SW2303 PD controller and the TPS55288
+//! converter that powers it.
+//!
+//! The firmware main loop calls [`PdCoordinator::tick`] once per pass. Each tick
+//! follows the [`Sw2303PowerGate`] sequence, polls the SW2303 for the sink
+//! request, programs the TPS55288 and, after repeated TPS write failures,
+//! recovers both buses by cycling `CE_TPS`. Hardware access goes through
+//! [`Sw2303Ops`] and [`Tps55288Ops`] so the same state machine runs against
+//! simulated devices in the host tests.
+
+use crate::pd_i2c::{PowerRequest, PowerSetpoint};
+use crate::power_config::{
+    LightLoadMode, PowerConfig, Sw2303CapabilityReadback, Sw2303LineCompensation,
+    Sw2303PathControl, TpsCdcRise, TpsMode, quantize_manual_voltage_mv,
+    resolve_manual_path_control,
+};
+use crate::sw2303_power_gate::Sw2303PowerGate;
+use crate::thermal::{clamp_manual_current_limit_ma, current_limit_ma_for_power_watts};
+
+const CE_RECOVERY_CYCLES: u32 = 3;
+const CE_RECOVERY_POLLS: u64 = 100;
+
+/// Delays, retry counts and thresholds used by [`PdCoordinator`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PdTimings {
+    /// TPS output stays off this long before SW2303 may be powered again.
+    pub off_hold_ms: u64,
+    /// SW2303 power-on reset time after the TPS boot setpoint.
+    pub por_release_ms: u64,
+    pub poll_ms: u64,
+    /// Loop delay after a TPS write error and the SW2303 read backoff once latched.
+    pub error_retry_ms: u64,
+    pub read_retries: u8,
+    pub read_retry_delay_ms: u64,
+    pub stable_reads_before_tps: u16,
+    pub stable_reads_before_profile: u16,
+    /// Backoff between failed profile applies outside a pending recontract.
+    pub profile_retry_ms: u64,
+    pub cc_un_driving_ms: u64,
+    /// Consecutive TPS write errors that trigger a `CE_TPS` recovery.
+    pub recovery_error_limit: u8,
+    pub recovery_min_interval_ms: u64,
+    pub recovery_tps_retries: u8,
+    pub discharge_settle_ms: u64,
+    pub release_settle_ms: u64,
+    pub ce_hold_ms: u64,
+    pub ce_poll_ms: u64,
+    pub tps_retry_delay_ms: u64,
+}
+
+/// SW2303 access on the dedicated PD I2C bus.
+#[allow(async_fn_in_trait)]
+pub trait Sw2303Ops {
+    type Error: core::fmt::Debug;
+
+    async fn read_power_request(&mut self) -> Result<PowerRequest, Self::Error>;
+    /// Writes the source profile and returns the capabilities read back.
+    async fn apply_profile(
+        &mut self,
+        config: &PowerConfig,
+    ) -> Result<Sw2303CapabilityReadback, Self::Error>;
+    async fn trigger_cc_un_driving(&mut self) -> Result<(), Self::Error>;
+    async fn set_path_control(&mut self, control: Sw2303PathControl) -> Result<(), Self::Error>;
+    async fn apply_line_compensation(
+        &mut self,
+        compensation: Sw2303LineCompensation,
+    ) -> Result<(), Self::Error>;
+    /// Drives SDA/SCL low so an unpowered SW2303 is not fed through its pull-ups.
+    fn park_bus(&mut self);
+    /// Detaches SDA/SCL from the I2C peripheral and lets them float to the pull-ups.
+    fn release_bus(&mut self);
+    fn bus_lines_high(&self) -> bool;
+    /// Hands the lines back to the I2C peripheral with its runtime configuration.
+    fn restore_bus(&mut self);
+}
+
+/// TPS55288 access, including the cached "last applied" state that lets
+/// repeated writes no-op.
+#[allow(async_fn_in_trait)]
+pub trait Tps55288Ops {
+    type Error: core::fmt::Debug;
+
+    /// Programs `setpoint`; a no-op when it equals [`Self::applied_setpoint`].
+    async fn apply_setpoint(&mut self, setpoint: PowerSetpoint) -> Result<(), Self::Error>;
+    async fn apply_light_load_mode(&mut self, mode: LightLoadMode) -> Result<(), Self::Error>;
+    async fn apply_cable_compensation(&mut self, rise: TpsCdcRise) -> Result<(), Self::Error>;
+    async fn stop_output_and_enable_discharge(&mut self) -> Result<(), Self::Error>;
+    /// Drives `CE_TPS`; a disabled converter resets to its power-on registers.
+    fn set_chip_enabled(&mut self, enabled: bool);
+    fn applied_setpoint(&self) -> Option<PowerSetpoint>;
+    fn forget_setpoint(&mut self);
+    fn forget_light_load_mode(&mut self);
+    fn setpoint_for_request(&self, request: PowerRequest) -> PowerSetpoint;
+    fn quantize_current_limit_ma(&self, ma: u16) -> u16;
+}
+
+/// Monotonic time and delays for [`PdCoordinator`].
+#[allow(async_fn_in_trait)]
+pub trait PdClock {
+    fn now_ms(&self) -> u64;
+    async fn delay_ms(&mut self, ms: u64);
+}
+
+/// Per-tick inputs owned by the rest of the firmware.
+#[derive(Clone, Copy, Debug)]
+pub struct PdTickInput<'a> {
+    pub usb_c_power_on: bool,
+    pub runtime_output_enabled: bool,
+    pub runtime_discharge_enabled: bool,
+    /// A runtime output/discharge change is waiting for [`PdTickOutcome::runtime_result`].
+    pub runtime_result_inflight: bool,
+    pub power_config: &'a PowerConfig,
+    /// `power_config` with the thermally derated power budget applied.
+    pub effective_power_config: &'a PowerConfig,
+    pub thermal_power_watts: u8,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PdTickOutcome {
+    pub request: Option<PowerRequest>,
+    /// The TPS setpoint this tick aimed for.
+    pub setpoint: PowerSetpoint,
+    /// Setpoint used when USB-C power is switched off outside the coordinator.
+    pub off_setpoint: PowerSetpoint,
+    pub loop_delay_ms: u64,
+    /// Resolution of an in-flight runtime output/discharge change.
+    pub runtime_result: Option<bool>,
+    /// The runtime output/discharge state may now be reported as applied.
+    pub runtime_applied: bool,
+    /// The rest of the main loop pass should be skipped.
+    pub restart: bool,
+    /// A `CE_TPS` recovery completed and reset the coordinator.
+    pub recovered: bool,
+}
+
+/// Things worth logging, reported through the `observe` callback of [`PdCoordinator::tick`].
+#[derive(Debug)]
+pub enum PdEvent<'a, SE, TE> {
+    BusParked,
+    BusReleasedBeforeBoot,
+    PorElapsed,
+    ReadRecovered {
+        retries: u8,
+    },
+    StableReads {
+        count: u16,
+        request: PowerRequest,
+    },
+    /// First SW2303 read failure since the last good read.
+    ReadError(&'a SE),
+    ProfileApplied {
+        recontract: bool,
+        readback_matches: bool,
+    },
+    ProfileError(&'a SE),
+    Recontracted,
+    RecontractError(&'a SE),
+    RequestChanged(PowerRequest),
+    FastProtocol(bool),
+    PathControl(Sw2303PathControl),
+    PathControlError {
+        control: Sw2303PathControl,
+        error: &'a SE,
+    },
+    LineCompensationError {
+        compensation: Sw2303LineCompensation,
+        error: &'a SE,
+    },
+    CableCompensationError(&'a TE),
+    LightLoadError(&'a TE),
+    TpsApplyError(&'a TE),
+    TpsOffApplied,
+    TpsBootApplied,
+    RecoveryStarted {
+        count: u32,
+        sw2303_errors: u8,
+        tps_errors: u8,
+        sw2303_i2c_allowed: bool,
+        stable_reads: u16,
+    },
+    RecoveryDischarged,
+    RecoveryDischargeError(&'a TE),
+    RecoveryBusReleased {
+        lines_high: bool,
+        cycles: u32,
+        recovered_after_ms: u64,
+    },
+    /// Output is requested off, so the recovery leaves the converter in reset.
+    RecoveryLeftOff,
+    RecoveryBootApplied {
+        attempt: u8,
+        attempts: u8,
+    },
+    RecoveryBootError {
+        attempt: u8,
+        attempts: u8,
+        error: &'a TE,
+    },
+    RecoveryPorElapsed {
+        lines_high: bool,
+    },
+    RecoveryComplete {
+        count: u32,
+        sw2303_i2c_allowed: bool,
+    },
+    RecoveryFailed {
+        count: u32,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PdCoordinator {
+    timings: PdTimings,
+    boot_setpoint: PowerSetpoint,
+    gate: Sw2303PowerGate,
+    i2c_parked: bool,
+    i2c_allowed: bool,
+    sw2303_error_latched: bool,
+    tps_error_latched: bool,
+    profile_applied: bool,
+    readback: Sw2303CapabilityReadback,
+    recontract_pending: bool,
+    last_profile_attempt_ms: Option<u64>,
+    last_read_attempt_ms: Option<u64>,
+    stable_reads: u16,
+    sw2303_consecutive_errors: u8,
+    tps_consecutive_errors: u8,
+    last_recovery_ms: Option<u64>,
+    recovery_failed: bool,
+    recovery_requested: bool,
+    recovery_count: u32,
+    tps_5v_since_ms: Option<u64>,
+    last_valid_request: Option<PowerRequest>,
+    last_request: Option<PowerRequest>,
+    last_fast_protocol: Option<bool>,
+    last_path_control: Option<Sw2303PathControl>,
+    last_line_compensation: Option<Sw2303LineCompensation>,
+    last_tps_cdc_rise: Option<TpsCdcRise>,
+}
+
+impl PdCoordinator {
+    pub const fn new(timings: PdTimings, boot_setpoint: PowerSetpoint) -> Self {
+        Self {
+            timings,
+            boot_setpoint,
+            gate: Sw2303PowerGate::new(timings.off_hold_ms, timings.por_release_ms),
+            i2c_parked: false,
+            i2c_allowed: false,
+            sw2303_error_latched: false,
+            tps_error_latched: false,
+            profile_applied: false,
+            readback: Sw2303CapabilityReadback::unavailable(),
+            recontract_pending: false,
+            last_profile_attempt_ms: None,
+            last_read_attempt_ms: None,
+            stable_reads: 0,
+            sw2303_consecutive_errors: 0,
+            tps_consecutive_errors: 0,
+            last_recovery_ms: None,
+            recovery_failed: false,
+            recovery_requested: false,
+            recovery_count: 0,
+            tps_5v_since_ms: None,
+            last_valid_request: None,
+            last_request: None,
+            last_fast_protocol: None,
+            last_path_control: None,
+            last_line_compensation: None,
+            last_tps_cdc_rise: None,
+        }
+    }
+
+    pub const fn gate(&self) -> &Sw2303PowerGate {
+        &self.gate
+    }
+
+    pub const fn sw2303_i2c_allowed(&self) -> bool {
+        self.i2c_allowed
+    }
+
+    pub const fn sw2303_error_latched(&self) -> bool {
+        self.sw2303_error_latched
+    }
+
+    pub const fn tps_error_latched(&self) -> bool {
+        self.tps_error_latched
+    }
+
+    pub const fn profile_applied(&self) -> bool {
+        self.profile_applied
+    }
+
+    pub const fn readback(&self) -> Sw2303CapabilityReadback {
+        self.readback
+    }
+
+    pub const fn recontract_pending(&self) -> bool {
+        self.recontract_pending
+    }
+
+    pub const fn stable_reads(&self) -> u16 {
+        self.stable_reads
+    }
+
+    pub const fn last_valid_request(&self) -> Option<PowerRequest> {
+        self.last_valid_request
+    }
+
+    pub const fn path_control(&self) -> Option<Sw2303PathControl> {
+        self.last_path_control
+    }
+
+    pub const fn recovery_count(&self) -> u32 {
+        self.recovery_count
+    }
+
+    /// Asks the next tick to run the `CE_TPS` recovery without waiting for TPS
+    /// errors, e.g. to free a PD bus found stuck by `i2c.scan`. Refused while
+    /// the previous recovery is within `recovery_min_interval_ms`; a request
+    /// made while USB-C power is off is dropped by the next tick.
+    pub fn request_recovery(&mut self, now_ms: u64) -> bool {
+        if !self.recovery_interval_elapsed(now_ms) {
+            return false;
+        }
+        self.recovery_requested = true;
+        true
+    }
+
+    /// When the TPS last started delivering a powered setpoint, if it still is.
+    pub const fn tps_5v_since_ms(&self) -> Option<u64> {
+        self.tps_5v_since_ms
+    }
+
+    /// The boot sequence applied the TPS boot setpoint at `now_ms`.
+    pub fn mark_boot_applied(&mut self, now_ms: u64) {
+        self.tps_error_latched = false;
+        self.tps_5v_since_ms = Some(now_ms);
+        self.gate.mark_tps_boot_applied(now_ms);
+    }
+
+    /// The boot sequence waited out the SW2303 power-on reset.
+    pub fn finish_boot_por(&mut self, now_ms: u64) {
+        self.gate.advance(now_ms);
+        self.i2c_allowed = true;
+    }
+
+    pub fn latch_tps_error(&mut self) {
+        self.tps_error_latched = true;
+    }
+
+    /// A TPS output-off write outside [`Self::tick`] (port power-off) succeeded.
+    pub fn record_tps_off_applied(&mut self) {
+        self.tps_error_latched = false;
+        self.tps_consecutive_errors = 0;
+        self.tps_5v_since_ms = None;
+    }
+
+    /// A TPS output-off write outside [`Self::tick`] failed. Returns `true`
+    /// when this newly latched the TPS error.
+    pub fn record_tps_off_failed(&mut self) -> bool {
+        let newly_latched = !self.tps_error_latched;
+        self.tps_error_latched = true;
+        self.tps_consecutive_errors = self.tps_consecutive_errors.saturating_add(1);
+        newly_latched
+    }
+
+    /// The TPS reported a short, over-current or over-voltage fault.
+    pub fn clear_tps_5v_since(&mut self) {
+        self.tps_5v_since_ms = None;
+    }
+
+    pub fn set_tps_5v_since(&mut self, since_ms: Option<u64>) {
+        self.tps_5v_since_ms = since_ms;
+    }
+
+    pub fn set_path_control_applied(&mut self, control: Option<Sw2303PathControl>) {
+        self.last_path_control = control;
+    }
+
+    /// USB-C power was switched back on; negotiate from scratch without a
+    /// `CE_TPS` hard cycle.
+    pub fn restart_contract(&mut self) {
+        self.tps_5v_since_ms = None;
+        self.i2c_allowed = false;
+        self.profile_applied = false;
+        self.readback = Sw2303CapabilityReadback::unavailable();
+        self.recontract_pending = false;
+        self.stable_reads = 0;
+        self.sw2303_consecutive_errors = 0;
+        self.tps_consecutive_errors = 0;
+        self.recovery_failed = false;
+        self.last_read_attempt_ms = None;
+        self.last_profile_attempt_ms = None;
+        self.last_valid_request = None;
+        self.last_request = None;
+        self.last_fast_protocol = None;
+    }
+
+    /// The runtime output was switched on or off; restart the contract and
+    /// forget every cached SW2303/TPS setting and error latch.
+    pub fn restart_runtime_output(&mut self) {
+        self.restart_contract();
+        self.last_path_control = None;
+        self.last_line_compensation = None;
+        self.last_tps_cdc_rise = None;
+        self.sw2303_error_latched = false;
+        self.tps_error_latched = false;
+    }
+
+    /// A new power config was stored. `recontract` asks the sink to
+    /// renegotiate once the new profile reads back.
+    pub fn reload_power_config(&mut self, recontract: bool) {
+        self.refresh_profile(recontract);
+        self.last_path_control = None;
+        self.last_line_compensation = None;
+        self.last_tps_cdc_rise = None;
+        self.stable_reads = 0;
+        self.last_valid_request = None;
+        self.last_request = None;
+        self.last_fast_protocol = None;
+    }
+
+    /// The effective source profile changed (thermal derating); re-apply it.
+    pub fn refresh_profile(&mut self, recontract: bool) {
+        self.profile_applied = false;
+        self.readback = Sw2303CapabilityReadback::unavailable();
+        self.recontract_pending = recontract;
+        self.last_profile_attempt_ms = None;
+    }
+
+    /// Restores the negotiated contract after the idle-bias sweep borrowed the
+    /// controllers, and forces the path control and TPS setpoint to be rewritten.
+    pub fn restore_contract(&mut self, previous: &Self) {
+        self.last_valid_request = previous.last_valid_request;
+        self.last_request = previous.last_request;
+        self.last_fast_protocol = previous.last_fast_protocol;
+        self.stable_reads = previous.stable_reads;
+        self.profile_applied = previous.profile_applied;
+        self.readback = previous.readback;
+        self.recontract_pending = previous.recontract_pending;
+        self.last_path_control = None;
+        self.last_read_attempt_ms = None;
+    }
+
+    pub async fn tick<S, T, C>(
+        &mut self,
+        input: &PdTickInput<'_>,
+        sw: &mut S,
+        tps: &mut T,
+        clock: &mut C,
+        mut observe: impl FnMut(PdEvent<'_, S::Error, T::Error>),
+    ) -> PdTickOutcome
+    where
+        S: Sw2303Ops,
+        T: Tps55288Ops,
+        C: PdClock,
+    {
+        let timings = self.timings;
+        let boot_sp = self.boot_setpoint;
+        let power_on = input.usb_c_power_on;
+        let config = input.power_config;
+        let manual = config.tps_mode == TpsMode::Manual;
+        let off_setpoint = PowerSetpoint {
+            output_enabled: false,
+            discharge_enabled: input.runtime_discharge_enabled,
+            ..boot_sp
+        };
+        let mut outcome = PdTickOutcome {
+            request: None,
+            setpoint: off_setpoint,
+            off_setpoint,
+            loop_delay_ms: timings.poll_ms,
+            runtime_result: None,
+            runtime_applied: false,
+            restart: false,
+            recovered: false,
+        };
+
+        self.advance_gate(input, sw, clock, &mut observe).await;
+
+        let (mut request, mut setpoint, mut requested_i_lim_ma) = self
+            .read_target(power_on, off_setpoint, sw, tps, clock, &mut observe)
+            .await;
+
+        let profile_apply_failed = self
+            .apply_profile(
+                input,
+                sw,
+                tps,
+                clock,
+                &mut observe,
+                &mut request,
+                &mut setpoint,
+            )
+            .await;
+
+        if input.runtime_result_inflight
+            && input.runtime_output_enabled
+            && self.gate.on_transition_complete()
+            && (profile_apply_failed || self.sw2303_error_latched)
+        {
+            // A failed profile or post-POR read cannot become a source-ready
+            // runtime-on result. Resolve the waiting request now; the normal
+            // retry path may try the controller again without leaving the
+            // caller blocked.
+            outcome.runtime_result = Some(false);
+        }
+
+        // A runtime on request must keep the owner-facing control busy until
+        // the restarted controller has accepted its source profile. POR alone
+        // only means I2C may be used; it does not mean USB-C output is ready.
+        let runtime_on_ready = input.runtime_output_enabled
+            && self.gate.on_transition_complete()
+            && self.profile_applied
+            && !self.sw2303_error_latched
+            && !self.tps_error_latched;
+
+        if let Some(request) = request {
+            if request_changed(self.last_request, request) {
+                observe(PdEvent::RequestChanged(request));
+                self.last_request = Some(request);
+            }
+            if self.last_fast_protocol != Some(request.fast_protocol) {
+                observe(PdEvent::FastProtocol(request.fast_protocol));
+                self.last_fast_protocol = Some(request.fast_protocol);
+            }
+        } else {
+            self.last_request = None;
+            self.last_fast_protocol = None;
+        }
+
+        if power_on && manual {
+            let manual_vout_mv = quantize_manual_voltage_mv(config.manual.voltage_mv);
+            requested_i_lim_ma = config.manual.current_limit_ma;
+            setpoint = PowerSetpoint {
+                v_out_mv: manual_vout_mv,
+                i_lim_ma: config.manual.current_limit_ma,
+                ..setpoint
+            };
+            let explicit_request_mv = request
+                .filter(|request| {
+                    request.status_valid
+                        && (request.negotiated_protocol.is_some()
+                            || request.fast_protocol
+                            || request.fast_voltage)
+                })
+                .map(|request| request.v_req_mv);
+            let path_control = resolve_manual_path_control(
+                config.manual.usb_c_path_mode,
+                manual_vout_mv,
+                explicit_request_mv,
+            );
+            self.apply_path_control(sw, path_control, &mut observe)
+                .await;
+            self.apply_line_compensation(sw, Sw2303LineCompensation::Off, &mut observe)
+                .await;
+        } else if power_on {
+            self.apply_path_control(sw, Sw2303PathControl::Auto, &mut observe)
+                .await;
+        }
+
+        let thermal_current_limit_ma =
+            current_limit_ma_for_power_watts(setpoint.v_out_mv, input.thermal_power_watts);
+        requested_i_lim_ma = requested_i_lim_ma.min(thermal_current_limit_ma);
+        setpoint.i_lim_ma = if manual {
+            clamp_manual_current_limit_ma(
+                setpoint.v_out_mv,
+                requested_i_lim_ma,
+                input.thermal_power_watts,
+            )
+        } else {
+            tps.quantize_current_limit_ma(requested_i_lim_ma)
+        };
+
+        let target_tps_cdc_rise = if manual {
+            config.manual.tps_cdc_rise
+        } else {
+            TpsCdcRise::V0
+        };
+        if self.last_tps_cdc_rise != Some(target_tps_cdc_rise) {
+            match tps.apply_cable_compensation(target_tps_cdc_rise).await {
+                Ok(()) => self.last_tps_cdc_rise = Some(target_tps_cdc_rise),
+                Err(err) => {
+                    if !self.tps_error_latched {
+                        observe(PdEvent::CableCompensationError(&err));
+                    }
+                    self.tps_error_latched = true;
+                }
+            }
+        }
+
+        if !manual {
+            self.apply_line_compensation(sw, config.sw2303_line_compensation, &mut observe)
+                .await;
+        }
+
+        match tps.apply_light_load_mode(config.light_load_mode).await {
+            Ok(()) => {
+                self.apply_tps(
+                    input,
+                    tps,
+                    clock,
+                    &mut observe,
+                    &mut setpoint,
+                    &mut outcome,
+                    runtime_on_ready,
+                )
+                .await;
+            }
+            Err(err) => {
+                if !self.tps_error_latched {
+                    observe(PdEvent::LightLoadError(&err));
+                    self.tps_error_latched = true;
+                }
+                self.tps_consecutive_errors = self.tps_consecutive_errors.saturating_add(1);
+                tps.forget_light_load_mode();
+                tps.forget_setpoint();
+                if input.runtime_result_inflight {
+                    outcome.runtime_result = Some(false);
+                }
+                // Skip the TPS apply but still reach the recovery check below, so a
+                // stuck TPS bus is recovered instead of failing here forever.
+                outcome.restart = true;
+            }
+        }
+
+        outcome.request = request;
+        outcome.setpoint = setpoint;
+
+        if self
+            .recover_if_due(power_on, sw, tps, clock, &mut observe)
+            .await
+        {
+            outcome.restart = true;
+            outcome.recovered = true;
+        }
+        outcome
+    }
+
+    async fn advance_gate<S: Sw2303Ops, TE, C: PdClock>(
+        &mut self,
+        input: &PdTickInput<'_>,
+        sw: &mut S,
+        clock: &mut C,
+        observe: &mut impl FnMut(PdEvent<'_, S::Error, TE>),
+    ) {
+        self.gate
+            .set_output_requested(input.usb_c_power_on && input.runtime_output_enabled);
+        let gate_was_ready = self.gate.allows_sw2303_i2c();
+        self.gate.advance(clock.now_ms());
+        let gate_became_ready = !gate_was_ready && self.gate.allows_sw2303_i2c();
+
+        if self.gate.should_park_i2c() && !self.i2c_parked {
+            sw.park_bus();
+            self.i2c_allowed = false;
+            self.i2c_parked = true;
+            observe(PdEvent::BusParked);
+        }
+
+        if self.gate.should_release_i2c() && self.i2c_parked {
+            sw.release_bus();
+            clock.delay_ms(self.timings.release_settle_ms).await;
+            self.gate.mark_pre_boot_i2c_released();
+            self.i2c_allowed = false;
+            self.i2c_parked = false;
+            sw.restore_bus();
+            observe(PdEvent::BusReleasedBeforeBoot);
+        }
+
+        if gate_became_ready && !self.i2c_parked {
+            self.i2c_allowed = true;
+            self.sw2303_error_latched = false;
+            observe(PdEvent::PorElapsed);
+        }
+    }
+
+    /// Picks this tick's request and TPS target, reading the SW2303 when the
+    /// gate and the error backoff allow it.
+    #[allow(clippy::too_many_arguments)]
+    async fn read_target<S: Sw2303Ops, T: Tps55288Ops, C: PdClock>(
+        &mut self,
+        power_on: bool,
+        off_setpoint: PowerSetpoint,
+        sw: &mut S,
+        tps: &T,
+        clock: &mut C,
+        observe: &mut impl FnMut(PdEvent<'_, S::Error, T::Error>),
+    ) -> (Option<PowerRequest>, PowerSetpoint, u16) {
+        let timings = self.timings;
+        let boot_sp = self.boot_setpoint;
+        // After a failed recovery the SW2303 contract is gone; keep the output
+        // off until a recovery brings the boot supply back.
+        if self.gate.requires_tps_off() || !power_on || self.recovery_failed {
+            return (None, off_setpoint, 0);
+        }
+        if self.gate.requires_boot_setpoint() {
+            return (None, boot_sp, boot_sp.i_lim_ma);
+        }
+
+        let now_ms = clock.now_ms();
+        let allow_probe = self.gate.allows_sw2303_i2c()
+            && (!self.sw2303_error_latched
+                || self.last_read_attempt_ms.is_none_or(|attempt| {
+                    now_ms.saturating_sub(attempt) >= timings.error_retry_ms
+                }));
+        if !(self.i2c_allowed && allow_probe) {
+            return self.last_valid_target(tps);
+        }
+
+        self.last_read_attempt_ms = Some(now_ms);
+        let mut retry = 0;
+        let result = loop {
+            match sw.read_power_request().await {
+                Ok(request) => break Ok(request),
+                Err(_) if retry < timings.read_retries => {
+                    retry += 1;
+                    clock.delay_ms(timings.read_retry_delay_ms).await;
+                }
+                Err(err) => break Err(err),
+            }
+        };
+        sw.restore_bus();
+
+        match result {
+            Ok(request) => {
+                if retry > 0 {
+                    observe(PdEvent::ReadRecovered { retries: retry });
+                }
+                self.sw2303_error_latched = false;
+                self.sw2303_consecutive_errors = 0;
+                self.last_valid_request = Some(request);
+                self.stable_reads = self.stable_reads.saturating_add(1);
+                if matches!(self.stable_reads, 1 | 10 | 50 | 100)
+                    || self.stable_reads.is_multiple_of(500)
+                {
+                    observe(PdEvent::StableReads {
+                        count: self.stable_reads,
+                        request,
+                    });
+                }
+                if self.stable_reads >= timings.stable_reads_before_tps {
+                    (
+                        Some(request),
+                        tps.setpoint_for_request(request),
+                        request.i_req_ma,
+                    )
+                } else {
+                    (Some(request), boot_sp, boot_sp.i_lim_ma)
+                }
+            }
+            Err(err) => {
+                if !self.sw2303_error_latched {
+                    observe(PdEvent::ReadError(&err));
+                    self.sw2303_error_latched = true;
+                }
+                self.sw2303_consecutive_errors = self.sw2303_consecutive_errors.saturating_add(1);
+                self.last_valid_target(tps)
+            }
+        }
+    }
+
+    /// Keeps driving the last good request, or the boot setpoint before one exists.
+    fn last_valid_target<T: Tps55288Ops>(
+        &self,
+        tps: &T,
+    ) -> (Option<PowerRequest>, PowerSetpoint, u16) {
+        match self.last_valid_request {
+            Some(request) => (
+                Some(request),
+                tps.setpoint_for_request(request),
+                request.i_req_ma,
+            ),
+            None => (None, self.boot_setpoint, self.boot_setpoint.i_lim_ma),
+        }
+    }
+
+    /// Boot profile apply waits for stable target reads; runtime config changes
+    /// apply immediately once SW2303 I2C is usable so source caps do not lag
+    /// EEPROM. Returns `true` when an apply was attempted and failed.
+    #[allow(clippy::too_many_arguments)]
+    async fn apply_profile<S: Sw2303Ops, T: Tps55288Ops, C: PdClock>(
+        &mut self,
+        input: &PdTickInput<'_>,
+        sw: &mut S,
+        tps: &mut T,
+        clock: &mut C,
+        observe: &mut impl FnMut(PdEvent<'_, S::Error, T::Error>),
+        request: &mut Option<PowerRequest>,
+        setpoint: &mut PowerSetpoint,
+    ) -> bool {
+        let timings = self.timings;
+        if self.profile_applied
+            || !self.i2c_allowed
+            || !(self.recontract_pending
+                || (request.is_some() && self.stable_reads >= timings.stable_reads_before_profile))
+        {
+            return false;
+        }
+        let now_ms = clock.now_ms();
+        let allow_retry = self
+            .last_profile_attempt_ms
+            .is_none_or(|attempt| now_ms.saturating_sub(attempt) >= timings.profile_retry_ms)
+            || self.recontract_pending;
+        if !allow_retry {
+            return false;
+        }
+
+        let recontract = self.recontract_pending;
+        self.last_profile_attempt_ms = Some(now_ms);
+        let readback = match sw.apply_profile(input.effective_power_config).await {
+            Ok(readback) => readback,
+            Err(err) => {
+                self.readback = Sw2303CapabilityReadback::unavailable();
+                observe(PdEvent::ProfileError(&err));
+                return true;
+            }
+        };
+        self.readback = readback;
+        let readback_matches = readback.matches_config(input.effective_power_config);
+        self.profile_applied = readback_matches;
+        observe(PdEvent::ProfileApplied {
+            recontract,
+            readback_matches,
+        });
+        if !readback_matches {
+            return true;
+        }
+        if recontract {
+            match sw.trigger_cc_un_driving().await {
+                Ok(()) => {
+                    clock.delay_ms(timings.cc_un_driving_ms).await;
+                    observe(PdEvent::Recontracted);
+                }
+                Err(err) => observe(PdEvent::RecontractError(&err)),
+            }
+            *request = None;
+            *setpoint = self.boot_setpoint;
+            self.last_valid_request = None;
+            self.last_request = None;
+            self.last_fast_protocol = None;
+            self.stable_reads = 0;
+            self.recontract_pending = false;
+            tps.forget_light_load_mode();
+        }
+        false
+    }
+
+    async fn apply_path_control<S: Sw2303Ops, TE>(
+        &mut self,
+        sw: &mut S,
+        control: Sw2303PathControl,
+        observe: &mut impl FnMut(PdEvent<'_, S::Error, TE>),
+    ) {
+        if !self.i2c_allowed || self.last_path_control == Some(control) {
+            return;
+        }
+        match sw.set_path_control(control).await {
+            Ok(()) => {
+                self.last_path_control = Some(control);
+                observe(PdEvent::PathControl(control));
+            }
+            Err(error) => observe(PdEvent::PathControlError {
+                control,
+                error: &error,
+            }),
+        }
+    }
+
+    async fn apply_line_compensation<S: Sw2303Ops, TE>(
+        &mut self,
+        sw: &mut S,
+        compensation: Sw2303LineCompensation,
+        observe: &mut impl FnMut(PdEvent<'_, S::Error, TE>),
+    ) {
+        if !self.i2c_allowed || self.last_line_compensation == Some(compensation) {
+            return;
+        }
+        match sw.apply_line_compensation(compensation).await {
+            Ok(()) => self.last_line_compensation = Some(compensation),
+            Err(error) => observe(PdEvent::LineCompensationError {
+                compensation,
+                error: &error,
+            }),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn apply_tps<T: Tps55288Ops, C: PdClock, SE>(
+        &mut self,
+        input: &PdTickInput<'_>,
+        tps: &mut T,
+        clock: &mut C,
+        observe: &mut impl FnMut(PdEvent<'_, SE, T::Error>),
+        setpoint: &mut PowerSetpoint,
+        outcome: &mut PdTickOutcome,
+        runtime_on_ready: bool,
+    ) {
+        let power_on = input.usb_c_power_on;
+        if self.gate.requires_tps_off() {
+            setpoint.output_enabled = false;
+            setpoint.discharge_enabled =
+                self.gate.requires_active_discharge() || input.runtime_discharge_enabled;
+        } else if power_on {
+            setpoint.output_enabled = true;
+            setpoint.discharge_enabled = false;
+        }
+
+        let waiting_for_off_apply = self.gate.waiting_for_tps_off_apply();
+        let waiting_for_boot_apply = self.gate.waiting_for_tps_boot_apply();
+        let apply_needed = tps.applied_setpoint() != Some(*setpoint)
+            || waiting_for_off_apply
+            || waiting_for_boot_apply;
+        let voltage_update_needed = setpoint.v_out_mv > self.boot_setpoint.v_out_mv;
+        let manual_tps_active = power_on && input.power_config.tps_mode == TpsMode::Manual;
+        let runtime_settled = !input.runtime_output_enabled || runtime_on_ready;
+        if (!manual_tps_active
+            && power_on
+            && !input.runtime_result_inflight
+            && input.runtime_output_enabled
+            && self.stable_reads < self.timings.stable_reads_before_tps
+            && !waiting_for_boot_apply)
+            || (!voltage_update_needed && !apply_needed)
+        {
+            if input.runtime_result_inflight && !apply_needed && runtime_settled {
+                outcome.runtime_applied = true;
+                outcome.runtime_result = Some(true);
+            }
+            return;
+        }
+
+        if let Err(err) = tps.apply_setpoint(*setpoint).await {
+            if !self.tps_error_latched {
+                observe(PdEvent::TpsApplyError(&err));
+                self.tps_error_latched = true;
+            }
+            self.tps_consecutive_errors = self.tps_consecutive_errors.saturating_add(1);
+            tps.forget_setpoint();
+            tps.forget_light_load_mode();
+            if input.runtime_result_inflight {
+                outcome.runtime_result = Some(false);
+            }
+            outcome.loop_delay_ms = self.timings.error_retry_ms;
+            return;
+        }
+
+        self.tps_error_latched = false;
+        self.tps_consecutive_errors = 0;
+        let now_ms = clock.now_ms();
+        if waiting_for_off_apply {
+            self.gate.mark_tps_off_applied(now_ms);
+            self.tps_5v_since_ms = None;
+            self.i2c_allowed = false;
+            observe(PdEvent::TpsOffApplied);
+        } else if waiting_for_boot_apply {
+            self.gate.mark_tps_boot_applied(now_ms);
+            self.tps_5v_since_ms = Some(now_ms);
+            observe(PdEvent::TpsBootApplied);
+        } else if setpoint.output_enabled {
+            self.tps_5v_since_ms.get_or_insert(now_ms);
+        }
+        if !input.runtime_output_enabled || self.gate.on_transition_complete() {
+            outcome.runtime_applied = true;
+        }
+        if input.runtime_result_inflight && runtime_settled {
+            outcome.runtime_result = Some(true);
+        }
+    }
+
+    fn recovery_interval_elapsed(&self, now_ms: u64) -> bool {
+        self.last_recovery_ms
+            .is_none_or(|last| now_ms.saturating_sub(last) >= self.timings.recovery_min_interval_ms)
+    }
+
+    /// Cycles `CE_TPS` after repeated TPS write failures or a manual request,
+    /// which also power cycles the SW2303 and frees a bus it holds low. Returns
+    /// `true` when the coordinator was reset and the main loop should start over.
+    async fn recover_if_due<S: Sw2303Ops, T: Tps55288Ops, C: PdClock>(
+        &mut self,
+        power_on: bool,
+        sw: &mut S,
+        tps: &mut T,
+        clock: &mut C,
+        observe: &mut impl FnMut(PdEvent<'_, S::Error, T::Error>),
+    ) -> bool {
+        let timings = self.timings;
+        let now_ms = clock.now_ms();
+        self.recovery_requested &= power_on;
+        let recovery_due = power_on
+            && (self.recovery_failed
+                || self.recovery_requested
+                || self.tps_consecutive_errors >= timings.recovery_error_limit);
+        if !(recovery_due && self.recovery_interval_elapsed(now_ms)) {
+            return false;
+        }
+
+        self.recovery_requested = false;
+        self.recovery_count = self.recovery_count.saturating_add(1);
+        self.last_recovery_ms = Some(now_ms);
+        observe(PdEvent::RecoveryStarted {
+            count: self.recovery_count,
+            sw2303_errors: self.sw2303_consecutive_errors,
+            tps_errors: self.tps_consecutive_errors,
+            sw2303_i2c_allowed: self.i2c_allowed,
+            stable_reads: self.stable_reads,
+        });
+
+        match tps.stop_output_and_enable_discharge().await {
+            Ok(()) => observe(PdEvent::RecoveryDischarged),
+            Err(err) => {
+                observe(PdEvent::RecoveryDischargeError(&err));
+                tps.forget_setpoint();
+                tps.forget_light_load_mode();
+            }
+        }
+        clock.delay_ms(timings.discharge_settle_ms).await;
+
+        sw.release_bus();
+        let mut recovered_after_ms = 0;
+        let mut cycles = 0;
+        for cycle in 1..=CE_RECOVERY_CYCLES {
+            cycles = cycle;
+            self.pulse_ce(tps, clock).await;
+            for step in 1..=CE_RECOVERY_POLLS {
+                clock.delay_ms(timings.ce_poll_ms).await;
+                if sw.bus_lines_high() {
+                    recovered_after_ms = timings.ce_hold_ms + step * timings.ce_poll_ms;
+                    break;
+                }
+            }
+            if sw.bus_lines_high() {
+                break;
+            }
+        }
+        observe(PdEvent::RecoveryBusReleased {
+            lines_high: sw.bus_lines_high(),
+            cycles,
+            recovered_after_ms,
+        });
+        sw.restore_bus();
+
+        let (boot_ready, sw2303_allowed) = if self.gate.requires_tps_off() {
+            // Re-applying the boot setpoint would switch the output back on
+            // while it is meant to be off; the converter is already in reset.
+            observe(PdEvent::RecoveryLeftOff);
+            (true, false)
+        } else {
+            self.recover_boot_supply(sw, tps, clock, observe).await
+        };
+
+        self.i2c_allowed = sw2303_allowed;
+        self.recovery_failed = !boot_ready;
+        if !boot_ready {
+            observe(PdEvent::RecoveryFailed {
+                count: self.recovery_count,
+            });
+            return false;
+        }
+
+        self.sw2303_consecutive_errors = 0;
+        self.tps_consecutive_errors = 0;
+        self.sw2303_error_latched = false;
+        self.tps_error_latched = false;
+        self.stable_reads = 0;
+        self.profile_applied = false;
+        self.readback = Sw2303CapabilityReadback::unavailable();
+        self.recontract_pending = false;
+        self.last_read_attempt_ms = None;
+        self.last_profile_attempt_ms = None;
+        self.last_valid_request = None;
+        self.last_request = None;
+        self.last_fast_protocol = None;
+        self.last_line_compensation = None;
+        self.last_tps_cdc_rise = None;
+        observe(PdEvent::RecoveryComplete {
+            count: self.recovery_count,
+            sw2303_i2c_allowed: self.i2c_allowed,
+        });
+        clock.delay_ms(timings.poll_ms).await;
+        true
+    }
+
+    /// Re-applies the TPS boot setpoint after a `CE_TPS` recovery and waits out
+    /// the SW2303 POR. Returns whether the TPS accepted it and whether the PD
+    /// bus lines came back high.
+    async fn recover_boot_supply<S: Sw2303Ops, T: Tps55288Ops, C: PdClock>(
+        &mut self,
+        sw: &mut S,
+        tps: &mut T,
+        clock: &mut C,
+        observe: &mut impl FnMut(PdEvent<'_, S::Error, T::Error>),
+    ) -> (bool, bool) {
+        let timings = self.timings;
+        let attempts = timings.recovery_tps_retries;
+        for attempt in 1..=attempts {
+            match tps.apply_setpoint(self.boot_setpoint).await {
+                Ok(()) => {
+                    sw.release_bus();
+                    self.tps_5v_since_ms = Some(clock.now_ms());
+                    observe(PdEvent::RecoveryBootApplied { attempt, attempts });
+                    clock.delay_ms(timings.por_release_ms).await;
+                    clock.delay_ms(timings.release_settle_ms).await;
+                    let lines_high = sw.bus_lines_high();
+                    observe(PdEvent::RecoveryPorElapsed { lines_high });
+                    sw.restore_bus();
+                    tps.forget_light_load_mode();
+                    return (true, lines_high);
+                }
+                Err(error) => {
+                    observe(PdEvent::RecoveryBootError {
+                        attempt,
+                        attempts,
+                        error: &error,
+                    });
+                    if attempt < attempts {
+                        sw.release_bus();
+                        self.pulse_ce(tps, clock).await;
+                        clock.delay_ms(timings.tps_retry_delay_ms).await;
+                        sw.restore_bus();
+                    }
+                    tps.forget_setpoint();
+                }
+            }
+        }
+        (false, false)
+    }
+
+    async fn pulse_ce<T: Tps55288Ops, C: PdClock>(&self, tps: &mut T, clock: &mut C) {
+        tps.set_chip_enabled(false);
+        clock.delay_ms(self.timings.ce_hold_ms).await;
+        tps.set_chip_enabled(true);
+        // The converter restarted with its power-on registers.
+        tps.forget_setpoint();
+        tps.forget_light_load_mode();
+    }
+}
+
+fn request_changed(last: Option<PowerRequest>, next: PowerRequest) -> bool {
+    match last {
+        Some(last) => {
+            last.fast_protocol != next.fast_protocol
+                || last.fast_voltage != next.fast_voltage
+                || last.negotiated_protocol != next.negotiated_protocol
+                || last.cc_attached != next.cc_attached
+                || last.status_valid != next.status_valid
+                || last.v_req_mv != next.v_req_mv
+                || last.i_req_ma != next.i_req_ma
+        }
+        None => true,
+    }
+}
@@ -0,0 +1,66 @@
+//! Persistent per-port data mode (see `docs/specs/c8d3m-charge-only-ports/SPEC.md`).
+
+use isolapurr_api::ports::{PortDataMode, PortId, UsbCDownstreamRoute};
+
+/// Persisted in EEPROM U21 (`port.data_set`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PortDataSettings {
+    pub usb_a: PortDataMode,
+    pub usb_c: PortDataMode,
+}
+
+impl PortDataSettings {
+    pub const fn defaults() -> Self {
+        Self {
+            usb_a: PortDataMode::Connected,
+            usb_c: PortDataMode::Connected,
+        }
+    }
+
+    pub const fn mode(&self, port: PortId) -> PortDataMode {
+        match port {
+            PortId::PortA => self.usb_a,
+            PortId::PortC => self.usb_c,
+        }
+    }
+
+    pub const fn with_mode(self, port: PortId, mode: PortDataMode) -> Self {
+        match port {
+            PortId::PortA => Self {
+                usb_a: mode,
+                ..self
+            },
+            PortId::PortC => Self {
+                usb_c: mode,
+                ..self
+            },
+        }
+    }
+
+    pub const fn usb_a_charge_only(&self) -> bool {
+        matches!(self.usb_a, PortDataMode::ChargeOnly)
+    }
+
+    /// USB-C shares the hub data channel with the MCU console. The route
+    /// already keeps the port off the bus while it points at the MCU, and
+    /// opening the switch there would only drop the console.
+    pub const fn usb_c_charge_only(&self, route: UsbCDownstreamRoute) -> bool {
+        matches!(self.usb_c, PortDataMode::ChargeOnly) && matches!(route, UsbCDownstreamRoute::UsbC)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PortDataSettings;
+    use isolapurr_api::ports::{PortDataMode, PortId, UsbCDownstreamRoute};
+
+    #[test]
+    fn usb_c_charge_only_keeps_the_mcu_console() {
+        let settings =
+            PortDataSettings::defaults().with_mode(PortId::PortC, PortDataMode::ChargeOnly);
+        assert_eq!(settings.mode(PortId::PortA), PortDataMode::Connected);
+        assert!(!settings.usb_a_charge_only());
+        assert!(settings.usb_c_charge_only(UsbCDownstreamRoute::UsbC));
+        assert!(!settings.usb_c_charge_only(UsbCDownstreamRoute::Mcu));
+    }
+}
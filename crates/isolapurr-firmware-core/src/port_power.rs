@@ -0,0 +1,191 @@
+//! Per-port power-on policy applied at boot, and write coalescing for the
+//! remembered last state (see `docs/specs/p6w2n-port-power-policy/SPEC.md`).
+
+use isolapurr_api::ports::PortPowerPolicy;
+
+/// A changed last state is written once it has been stable this long, so a
+/// burst of toggles costs one EEPROM write.
+pub const PORT_POWER_LAST_STATE_SETTLE_MS: u64 = 5_000;
+
+/// Persisted in EEPROM U21 (`ports.power_policy_set`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PortPowerSettings {
+    pub usb_a: PortPowerPolicy,
+    pub usb_c: PortPowerPolicy,
+    /// Last USB-A / USB-C power state, used by [`PortPowerPolicy::Last`].
+    pub last_usb_a_on: bool,
+    pub last_usb_c_on: bool,
+}
+
+impl PortPowerSettings {
+    /// Both ports come up powered, as before the policy existed.
+    pub const fn defaults() -> Self {
+        Self {
+            usb_a: PortPowerPolicy::On,
+            usb_c: PortPowerPolicy::On,
+            last_usb_a_on: true,
+            last_usb_c_on: true,
+        }
+    }
+
+    pub const fn usb_a_on_at_boot(&self) -> bool {
+        power_on_at_boot(self.usb_a, self.last_usb_a_on)
+    }
+
+    pub const fn usb_c_on_at_boot(&self) -> bool {
+        power_on_at_boot(self.usb_c, self.last_usb_c_on)
+    }
+
+    /// Whether any port restores its last state, i.e. whether power changes
+    /// need to be written back at all.
+    pub const fn tracks_last_state(&self) -> bool {
+        matches!(self.usb_a, PortPowerPolicy::Last) || matches!(self.usb_c, PortPowerPolicy::Last)
+    }
+
+    pub const fn with_last_state(self, usb_a_on: bool, usb_c_on: bool) -> Self {
+        Self {
+            last_usb_a_on: usb_a_on,
+            last_usb_c_on: usb_c_on,
+            ..self
+        }
+    }
+}
+
+pub const fn power_on_at_boot(policy: PortPowerPolicy, last_on: bool) -> bool {
+    match policy {
+        PortPowerPolicy::On => true,
+        PortPowerPolicy::Off => false,
+        PortPowerPolicy::Last => last_on,
+    }
+}
+
+/// Delays last-state writes until the port power has settled.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LastStateCoalescer {
+    pending: Option<((bool, bool), u64)>,
+}
+
+impl LastStateCoalescer {
+    pub const fn new() -> Self {
+        Self { pending: None }
+    }
+
+    /// Feeds the current `(usb_a_on, usb_c_on)` state. Returns the settings to
+    /// store once a state that differs from `stored` has held for
+    /// [`PORT_POWER_LAST_STATE_SETTLE_MS`]; nothing is due while no port uses
+    /// [`PortPowerPolicy::Last`].
+    pub fn poll(
+        &mut self,
+        stored: &PortPowerSettings,
+        current: (bool, bool),
+        now_ms: u64,
+    ) -> Option<PortPowerSettings> {
+        if !stored.tracks_last_state() || (stored.last_usb_a_on, stored.last_usb_c_on) == current {
+            self.pending = None;
+            return None;
+        }
+        match self.pending {
+            Some((state, since_ms)) if state == current => {
+                if now_ms.saturating_sub(since_ms) < PORT_POWER_LAST_STATE_SETTLE_MS {
+                    return None;
+                }
+                // Restart the wait so a failed write is retried after another
+                // settle period rather than on every tick.
+                self.pending = Some((current, now_ms));
+                Some(stored.with_last_state(current.0, current.1))
+            }
+            _ => {
+                self.pending = Some((current, now_ms));
+                None
+            }
+        }
+    }
+}
+
+impl Default for LastStateCoalescer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        LastStateCoalescer, PORT_POWER_LAST_STATE_SETTLE_MS, PortPowerSettings, power_on_at_boot,
+    };
+    use isolapurr_api::ports::PortPowerPolicy;
+
+    #[test]
+    fn boot_state_follows_the_policy() {
+        assert!(power_on_at_boot(PortPowerPolicy::On, false));
+        assert!(!power_on_at_boot(PortPowerPolicy::Off, true));
+        assert!(power_on_at_boot(PortPowerPolicy::Last, true));
+        assert!(!power_on_at_boot(PortPowerPolicy::Last, false));
+
+        let settings = PortPowerSettings {
+            usb_a: PortPowerPolicy::Off,
+            usb_c: PortPowerPolicy::Last,
+            ..PortPowerSettings::defaults()
+        }
+        .with_last_state(true, false);
+        assert!(!settings.usb_a_on_at_boot());
+        assert!(!settings.usb_c_on_at_boot());
+    }
+
+    #[test]
+    fn last_state_is_written_once_after_it_settles() {
+        let stored = PortPowerSettings {
+            usb_a: PortPowerPolicy::Last,
+            ..PortPowerSettings::defaults()
+        };
+        let mut coalescer = LastStateCoalescer::new();
+        assert_eq!(coalescer.poll(&stored, (true, true), 0), None);
+
+        // Toggles restart the settle window.
+        assert_eq!(coalescer.poll(&stored, (false, true), 100), None);
+        assert_eq!(coalescer.poll(&stored, (true, true), 200), None);
+        assert_eq!(coalescer.poll(&stored, (false, true), 300), None);
+        assert_eq!(
+            coalescer.poll(
+                &stored,
+                (false, true),
+                300 + PORT_POWER_LAST_STATE_SETTLE_MS - 1
+            ),
+            None
+        );
+        let due = coalescer
+            .poll(
+                &stored,
+                (false, true),
+                300 + PORT_POWER_LAST_STATE_SETTLE_MS,
+            )
+            .unwrap();
+        assert_eq!((due.last_usb_a_on, due.last_usb_c_on), (false, true));
+
+        // Not stored yet (write failed): retried after another settle period.
+        let retry_at = 300 + 2 * PORT_POWER_LAST_STATE_SETTLE_MS;
+        assert_eq!(coalescer.poll(&stored, (false, true), retry_at - 1), None);
+        assert!(coalescer.poll(&stored, (false, true), retry_at).is_some());
+
+        // Once stored, nothing is due.
+        assert_eq!(coalescer.poll(&due, (false, true), retry_at + 1), None);
+    }
+
+    #[test]
+    fn fixed_policies_never_write() {
+        let stored = PortPowerSettings {
+            usb_a: PortPowerPolicy::Off,
+            ..PortPowerSettings::defaults()
+        };
+        let mut coalescer = LastStateCoalescer::new();
+        assert_eq!(coalescer.poll(&stored, (false, false), 0), None);
+        assert_eq!(
+            coalescer.poll(
+                &stored,
+                (false, false),
+                10 * PORT_POWER_LAST_STATE_SETTLE_MS
+            ),
+            None
+        );
+    }
+}
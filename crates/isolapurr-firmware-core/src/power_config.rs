@@ -1,45 +1,7 @@
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub enum PowerHardwareKind {
-    Sw2303,
-}
-
-impl PowerHardwareKind {
-    pub const fn as_str(self) -> &'static str {
-        match self {
-            Self::Sw2303 => "sw2303",
-        }
-    }
-}
-
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub enum TpsMode {
-    AutoFollow,
-    Manual,
-}
-
-impl TpsMode {
-    pub const fn as_str(self) -> &'static str {
-        match self {
-            Self::AutoFollow => "auto_follow",
-            Self::Manual => "manual",
-        }
-    }
-}
-
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub enum LightLoadMode {
-    Pfm,
-    Fpwm,
-}
-
-impl LightLoadMode {
-    pub const fn as_str(self) -> &'static str {
-        match self {
-            Self::Pfm => "pfm",
-            Self::Fpwm => "fpwm",
-        }
-    }
-}
+pub use isolapurr_api::power::{
+    ActiveProtocol, LightLoadMode, ManualUsbCPathMode, PowerHardwareKind, Sw2303LineCompensation,
+    Sw2303PathControl, TpsMode,
+};
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum TpsCdcRise {
@@ -82,71 +44,6 @@ impl TpsCdcRise {
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub enum Sw2303LineCompensation {
-    Off,
-    Ohm0,
-    MilliOhm50,
-    MilliOhm100,
-    MilliOhm150,
-}
-
-impl Sw2303LineCompensation {
-    pub const fn as_str(self) -> &'static str {
-        match self {
-            Self::Off => "off",
-            Self::Ohm0 => "0mohm",
-            Self::MilliOhm50 => "50mohm",
-            Self::MilliOhm100 => "100mohm",
-            Self::MilliOhm150 => "150mohm",
-        }
-    }
-
-    pub const fn display_label(self) -> &'static str {
-        match self {
-            Self::Off => "Off",
-            Self::Ohm0 => "0",
-            Self::MilliOhm50 => "50mΩ",
-            Self::MilliOhm100 => "100mΩ",
-            Self::MilliOhm150 => "150mΩ",
-        }
-    }
-}
-
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub enum ManualUsbCPathMode {
-    Default,
-    Disconnect,
-    Force,
-}
-
-impl ManualUsbCPathMode {
-    pub const fn as_str(self) -> &'static str {
-        match self {
-            Self::Default => "default",
-            Self::Disconnect => "disconnect",
-            Self::Force => "force",
-        }
-    }
-}
-
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub enum Sw2303PathControl {
-    Auto,
-    ForceClose,
-    ForceOpen,
-}
-
-impl Sw2303PathControl {
-    pub const fn as_str(self) -> &'static str {
-        match self {
-            Self::Auto => "auto",
-            Self::ForceClose => "force_close",
-            Self::ForceOpen => "force_open",
-        }
-    }
-}
-
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct UsbCCapabilityConfig {
     pub power_watts: u8,
@@ -230,6 +127,87 @@ impl UsbCCapabilityConfig {
             fast_charge: UsbCFastChargeConfig::defaults(),
         }
     }
+
+    /// The same profile with every source offer above 5 V withdrawn: PD keeps
+    /// its 5 V PDO, BC1.2 stays, and the proprietary fast-charge protocols,
+    /// PPS and the higher fixed PDOs are off.
+    pub const fn five_volt_only(self) -> Self {
+        Self {
+            qc20_enabled: false,
+            qc30_enabled: false,
+            fcp_enabled: false,
+            afc_enabled: false,
+            scp_enabled: false,
+            pe20_enabled: false,
+            sfcp_enabled: false,
+            pps_enabled: false,
+            fixed_9v: false,
+            fixed_12v: false,
+            fixed_15v: false,
+            fixed_20v: false,
+            ..self
+        }
+    }
+
+    /// The same profile offering one protocol family, as the fast-charge probe
+    /// steps through them. PD keeps the configured fixed PDOs and PPS adds the
+    /// PD contract it rides on; BC1.2 stays on for every family as the 5 V
+    /// baseline the proprietary handshakes start from.
+    pub const fn only_protocol(self, family: ActiveProtocol) -> Self {
+        let only = Self {
+            pd_enabled: false,
+            qc20_enabled: false,
+            qc30_enabled: false,
+            fcp_enabled: false,
+            afc_enabled: false,
+            scp_enabled: false,
+            pe20_enabled: false,
+            bc12_enabled: true,
+            sfcp_enabled: false,
+            pps_enabled: false,
+            ..self
+        };
+        match family {
+            ActiveProtocol::Pd => Self {
+                pd_enabled: true,
+                ..only
+            },
+            ActiveProtocol::Pps => Self {
+                pd_enabled: true,
+                pps_enabled: true,
+                ..only
+            },
+            ActiveProtocol::Qc20 => Self {
+                qc20_enabled: true,
+                ..only
+            },
+            ActiveProtocol::Qc30 => Self {
+                qc30_enabled: true,
+                ..only
+            },
+            ActiveProtocol::Fcp => Self {
+                fcp_enabled: true,
+                ..only
+            },
+            ActiveProtocol::Afc => Self {
+                afc_enabled: true,
+                ..only
+            },
+            ActiveProtocol::Scp => Self {
+                scp_enabled: true,
+                ..only
+            },
+            ActiveProtocol::Pe20 => Self {
+                pe20_enabled: true,
+                ..only
+            },
+            ActiveProtocol::Bc12 => only,
+            ActiveProtocol::Sfcp => Self {
+                sfcp_enabled: true,
+                ..only
+            },
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -489,6 +467,34 @@ mod tests {
         assert_eq!(cfg.manual.current_limit_ma, 4_750);
     }
 
+    #[test]
+    fn five_volt_only_keeps_pd_and_bc12() {
+        let capability = UsbCCapabilityConfig::full_100w().five_volt_only();
+        assert!(capability.pd_enabled && capability.bc12_enabled);
+        assert!(!capability.pps_enabled && !capability.fixed_9v && !capability.fixed_20v);
+        assert!(!capability.qc30_enabled && !capability.scp_enabled);
+        assert_eq!(capability.power_watts, DEFAULT_POWER_WATTS);
+    }
+
+    #[test]
+    fn only_protocol_offers_one_family_over_bc12() {
+        let full = UsbCCapabilityConfig::full_100w();
+        let qc30 = full.only_protocol(ActiveProtocol::Qc30);
+        assert!(qc30.qc30_enabled && qc30.bc12_enabled);
+        assert!(!qc30.pd_enabled && !qc30.qc20_enabled && !qc30.scp_enabled);
+        assert!(qc30.fixed_20v && qc30.fast_charge.qc30_20v_enabled);
+
+        let pps = full.only_protocol(ActiveProtocol::Pps);
+        assert!(pps.pd_enabled && pps.pps_enabled && !pps.afc_enabled);
+        let pd = full.only_protocol(ActiveProtocol::Pd);
+        assert!(pd.pd_enabled && !pd.pps_enabled && pd.fixed_9v);
+
+        let bc12 = full.only_protocol(ActiveProtocol::Bc12);
+        assert!(bc12.bc12_enabled && !bc12.pd_enabled && !bc12.sfcp_enabled);
+        assert_eq!(bc12.power_watts, full.power_watts);
+        assert_eq!(bc12.current, full.current);
+    }
+
     #[test]
     fn defaults_to_pfm_light_load_mode() {
         assert_eq!(PowerConfig::defaults().light_load_mode, LightLoadMode::Pfm);
@@ -1,12 +1,26 @@
+use isolapurr_api::diagnostics::LogLevel;
+use isolapurr_api::ports::{ChargeTerminationAction, PortDataMode, PortPowerPolicy};
+
+use crate::button_settings::{ButtonAction, ButtonGesture, ButtonSettings};
+use crate::charge_termination::{ChargeTerminationConfig, ChargeTerminationSettings};
+use crate::display_settings::{DisplayRotation, DisplaySettings, DisplayTheme};
 use crate::idle_bias::{
     IDLE_BIAS_MAX_VOLTAGE_MV, IDLE_BIAS_MIN_VOLTAGE_MV, IDLE_BIAS_POINT_COUNT, IDLE_BIAS_STEP_MV,
     IdleBiasCalibration, IdleBiasMetadata,
 };
+use crate::jsonl_tcp::{JSONL_TOKEN_MAX_LEN, JsonlToken};
+use crate::log_ring::{LogSettings, SyslogTarget};
+use crate::port_data::PortDataSettings;
+use crate::port_power::PortPowerSettings;
 use crate::power_config::{
     DEFAULT_SW2303_LINE_COMPENSATION, LightLoadMode, ManualTpsConfig, ManualUsbCPathMode,
     PowerConfig, PowerHardwareKind, Sw2303LineCompensation, TpsCdcRise, TpsMode,
     UsbCCapabilityConfig, UsbCCurrentLimitConfig, UsbCFastChargeConfig,
 };
+use crate::sound_settings::{
+    CUSTOM_TONE_MAX_STEPS, CustomSoundPattern, CustomToneStep, QuietHours, SoundClassFlags,
+    SoundSettings, SoundSlot,
+};
 
 const IDLE_BIAS_FIXED_METADATA: IdleBiasMetadata = IdleBiasMetadata::fixed();
 
@@ -16,6 +30,51 @@ pub const POWER_SETTINGS_VERSION: u8 = 3;
 pub const IDLE_BIAS_RECORD_LEN: usize = 96;
 pub const IDLE_BIAS_MAGIC: &[u8; 8] = b"IPIBIAS\0";
 pub const IDLE_BIAS_VERSION: u8 = 1;
+pub const SOUND_SETTINGS_RECORD_LEN: usize = 32;
+pub const SOUND_SETTINGS_MAGIC: &[u8; 8] = b"IPSND01\0";
+pub const SOUND_SETTINGS_VERSION: u8 = 1;
+pub const CUSTOM_TONE_RECORD_LEN: usize = 48;
+pub const CUSTOM_TONE_MAGIC: &[u8; 8] = b"IPTONE1\0";
+pub const CUSTOM_TONE_VERSION: u8 = 1;
+pub const DISPLAY_SETTINGS_RECORD_LEN: usize = 32;
+pub const DISPLAY_SETTINGS_MAGIC: &[u8; 8] = b"IPDSP01\0";
+pub const DISPLAY_SETTINGS_VERSION: u8 = 1;
+pub const BUTTON_SETTINGS_RECORD_LEN: usize = 32;
+pub const BUTTON_SETTINGS_MAGIC: &[u8; 8] = b"IPBTN01\0";
+pub const BUTTON_SETTINGS_VERSION: u8 = 1;
+pub const JSONL_TOKEN_RECORD_LEN: usize = 48;
+pub const JSONL_TOKEN_MAGIC: &[u8; 8] = b"IPJTK01\0";
+pub const JSONL_TOKEN_VERSION: u8 = 1;
+pub const LOG_SETTINGS_RECORD_LEN: usize = 32;
+pub const LOG_SETTINGS_MAGIC: &[u8; 8] = b"IPLOG01\0";
+pub const LOG_SETTINGS_VERSION: u8 = 1;
+pub const PORT_POWER_RECORD_LEN: usize = 16;
+pub const PORT_POWER_MAGIC: &[u8; 8] = b"IPPORT1\0";
+pub const PORT_POWER_VERSION: u8 = 1;
+pub const PORT_DATA_RECORD_LEN: usize = 16;
+pub const PORT_DATA_MAGIC: &[u8; 8] = b"IPDATA1\0";
+pub const PORT_DATA_VERSION: u8 = 1;
+pub const CHARGE_TERMINATION_RECORD_LEN: usize = 32;
+pub const CHARGE_TERMINATION_MAGIC: &[u8; 8] = b"IPCHGT1\0";
+pub const CHARGE_TERMINATION_VERSION: u8 = 1;
+
+const SOUND_FLAG_MUTED: u8 = 1 << 0;
+const SOUND_FLAG_BOOT: u8 = 1 << 1;
+const SOUND_FLAG_MENU: u8 = 1 << 2;
+const SOUND_FLAG_ACTION: u8 = 1 << 3;
+const SOUND_FLAG_WARNING: u8 = 1 << 4;
+const SOUND_FLAG_QUIET_HOURS: u8 = 1 << 5;
+const CUSTOM_TONE_STEPS_OFFSET: usize = 11;
+const DISPLAY_FLAG_FLIPPED: u8 = 1 << 0;
+const DISPLAY_FLAG_DARK: u8 = 1 << 1;
+const BUTTON_FLAG_LOCKED: u8 = 1 << 0;
+const BUTTON_ACTIONS_OFFSET: usize = 10;
+const JSONL_TOKEN_OFFSET: usize = 10;
+const LOG_FLAG_SYSLOG: u8 = 1 << 0;
+const PORT_POWER_FLAG_LAST_USB_A_ON: u8 = 1 << 0;
+const PORT_POWER_FLAG_LAST_USB_C_ON: u8 = 1 << 1;
+const CHARGE_TERMINATION_USB_A_OFFSET: usize = 9;
+const CHARGE_TERMINATION_USB_C_OFFSET: usize = 17;
 
 pub fn checksum(bytes: &[u8]) -> u32 {
     let mut h = 0x811c_9dc5u32;
@@ -342,6 +401,283 @@ pub fn decode_idle_bias_calibration(
     ))
 }
 
+pub fn encode_sound_settings(
+    record: &mut [u8; SOUND_SETTINGS_RECORD_LEN],
+    settings: SoundSettings,
+) {
+    let mut flags = 0u8;
+    for (enabled, bit) in [
+        (settings.muted, SOUND_FLAG_MUTED),
+        (settings.classes.boot, SOUND_FLAG_BOOT),
+        (settings.classes.menu, SOUND_FLAG_MENU),
+        (settings.classes.action, SOUND_FLAG_ACTION),
+        (settings.classes.warning, SOUND_FLAG_WARNING),
+        (settings.quiet_hours.enabled, SOUND_FLAG_QUIET_HOURS),
+    ] {
+        if enabled {
+            flags |= bit;
+        }
+    }
+    record[9] = flags;
+    record[10] = settings.volume_pct;
+    record[11..13].copy_from_slice(&settings.quiet_hours.start_minute.to_le_bytes());
+    record[13..15].copy_from_slice(&settings.quiet_hours.end_minute.to_le_bytes());
+}
+
+pub fn decode_sound_settings(record: &[u8; SOUND_SETTINGS_RECORD_LEN]) -> Option<SoundSettings> {
+    let flags = record[9];
+    SoundSettings {
+        muted: flags & SOUND_FLAG_MUTED != 0,
+        classes: SoundClassFlags {
+            boot: flags & SOUND_FLAG_BOOT != 0,
+            menu: flags & SOUND_FLAG_MENU != 0,
+            action: flags & SOUND_FLAG_ACTION != 0,
+            warning: flags & SOUND_FLAG_WARNING != 0,
+        },
+        quiet_hours: QuietHours {
+            enabled: flags & SOUND_FLAG_QUIET_HOURS != 0,
+            start_minute: u16::from_le_bytes([record[11], record[12]]),
+            end_minute: u16::from_le_bytes([record[13], record[14]]),
+        },
+        volume_pct: record[10],
+    }
+    .validated()
+    .ok()
+}
+
+pub fn encode_custom_tone(
+    record: &mut [u8; CUSTOM_TONE_RECORD_LEN],
+    slot: SoundSlot,
+    pattern: &CustomSoundPattern,
+) {
+    record[9] = slot.index() as u8;
+    record[10] = pattern.steps().len() as u8;
+    for (index, step) in pattern.steps().iter().enumerate() {
+        let start = CUSTOM_TONE_STEPS_OFFSET + (index * 4);
+        record[start..start + 2].copy_from_slice(&step.freq_hz.to_le_bytes());
+        record[start + 2..start + 4].copy_from_slice(&step.duration_ms.to_le_bytes());
+    }
+}
+
+pub fn decode_custom_tone(
+    record: &[u8; CUSTOM_TONE_RECORD_LEN],
+    slot: SoundSlot,
+) -> Option<CustomSoundPattern> {
+    if usize::from(record[9]) != slot.index() {
+        return None;
+    }
+    let len = usize::from(record[10]);
+    if len > CUSTOM_TONE_MAX_STEPS {
+        return None;
+    }
+    let mut steps = [CustomToneStep::default(); CUSTOM_TONE_MAX_STEPS];
+    for (index, step) in steps[..len].iter_mut().enumerate() {
+        let start = CUSTOM_TONE_STEPS_OFFSET + (index * 4);
+        *step = CustomToneStep {
+            freq_hz: u16::from_le_bytes([record[start], record[start + 1]]),
+            duration_ms: u16::from_le_bytes([record[start + 2], record[start + 3]]),
+        };
+    }
+    CustomSoundPattern::from_steps(&steps[..len])
+}
+
+pub fn encode_display_settings(
+    record: &mut [u8; DISPLAY_SETTINGS_RECORD_LEN],
+    settings: DisplaySettings,
+) {
+    let mut flags = 0u8;
+    if settings.rotation == DisplayRotation::Flipped {
+        flags |= DISPLAY_FLAG_FLIPPED;
+    }
+    if settings.theme == DisplayTheme::Dark {
+        flags |= DISPLAY_FLAG_DARK;
+    }
+    record[9] = flags;
+    record[10] = settings.brightness_pct;
+    record[11..13].copy_from_slice(&settings.dim_after_s.to_le_bytes());
+    record[13..15].copy_from_slice(&settings.off_after_s.to_le_bytes());
+}
+
+pub fn decode_display_settings(
+    record: &[u8; DISPLAY_SETTINGS_RECORD_LEN],
+) -> Option<DisplaySettings> {
+    let flags = record[9];
+    DisplaySettings {
+        brightness_pct: record[10],
+        dim_after_s: u16::from_le_bytes([record[11], record[12]]),
+        off_after_s: u16::from_le_bytes([record[13], record[14]]),
+        rotation: if flags & DISPLAY_FLAG_FLIPPED != 0 {
+            DisplayRotation::Flipped
+        } else {
+            DisplayRotation::Normal
+        },
+        theme: if flags & DISPLAY_FLAG_DARK != 0 {
+            DisplayTheme::Dark
+        } else {
+            DisplayTheme::Light
+        },
+    }
+    .validated()
+    .ok()
+}
+
+pub fn encode_button_settings(
+    record: &mut [u8; BUTTON_SETTINGS_RECORD_LEN],
+    settings: ButtonSettings,
+) {
+    record[9] = if settings.locked {
+        BUTTON_FLAG_LOCKED
+    } else {
+        0
+    };
+    for gesture in ButtonGesture::ALL {
+        record[BUTTON_ACTIONS_OFFSET + gesture.index()] = settings.action(gesture).code();
+    }
+}
+
+pub fn decode_button_settings(record: &[u8; BUTTON_SETTINGS_RECORD_LEN]) -> Option<ButtonSettings> {
+    let mut settings = ButtonSettings::defaults();
+    settings.locked = record[9] & BUTTON_FLAG_LOCKED != 0;
+    for gesture in ButtonGesture::ALL {
+        let code = record[BUTTON_ACTIONS_OFFSET + gesture.index()];
+        settings.set_action(gesture, ButtonAction::from_code(code)?);
+    }
+    settings.validated().ok()
+}
+
+pub fn encode_jsonl_token(record: &mut [u8; JSONL_TOKEN_RECORD_LEN], token: &JsonlToken) {
+    let bytes = token.as_bytes();
+    record[9] = bytes.len() as u8;
+    record[JSONL_TOKEN_OFFSET..JSONL_TOKEN_OFFSET + bytes.len()].copy_from_slice(bytes);
+}
+
+pub fn decode_jsonl_token(record: &[u8; JSONL_TOKEN_RECORD_LEN]) -> Option<JsonlToken> {
+    let len = usize::from(record[9]);
+    if len > JSONL_TOKEN_MAX_LEN {
+        return None;
+    }
+    let token = core::str::from_utf8(&record[JSONL_TOKEN_OFFSET..JSONL_TOKEN_OFFSET + len]).ok()?;
+    JsonlToken::new(token)
+}
+
+pub fn encode_log_settings(record: &mut [u8; LOG_SETTINGS_RECORD_LEN], settings: LogSettings) {
+    record[9] = settings.level as u8;
+    if let Some(target) = settings.syslog {
+        record[10] = LOG_FLAG_SYSLOG;
+        record[11..15].copy_from_slice(&target.host);
+        record[15..17].copy_from_slice(&target.port.to_le_bytes());
+    }
+}
+
+pub fn decode_log_settings(record: &[u8; LOG_SETTINGS_RECORD_LEN]) -> Option<LogSettings> {
+    let level = *LogLevel::ALL.get(usize::from(record[9]))?;
+    let syslog = match record[10] {
+        0 => None,
+        LOG_FLAG_SYSLOG => {
+            let port = u16::from_le_bytes([record[15], record[16]]);
+            if port == 0 {
+                return None;
+            }
+            Some(SyslogTarget {
+                host: [record[11], record[12], record[13], record[14]],
+                port,
+            })
+        }
+        _ => return None,
+    };
+    Some(LogSettings { level, syslog })
+}
+
+pub fn encode_port_power_settings(
+    record: &mut [u8; PORT_POWER_RECORD_LEN],
+    settings: PortPowerSettings,
+) {
+    record[9] = settings.usb_a as u8;
+    record[10] = settings.usb_c as u8;
+    let mut flags = 0;
+    if settings.last_usb_a_on {
+        flags |= PORT_POWER_FLAG_LAST_USB_A_ON;
+    }
+    if settings.last_usb_c_on {
+        flags |= PORT_POWER_FLAG_LAST_USB_C_ON;
+    }
+    record[11] = flags;
+}
+
+pub fn encode_port_data_settings(
+    record: &mut [u8; PORT_DATA_RECORD_LEN],
+    settings: PortDataSettings,
+) {
+    record[9] = settings.usb_a as u8;
+    record[10] = settings.usb_c as u8;
+}
+
+pub fn decode_port_data_settings(record: &[u8; PORT_DATA_RECORD_LEN]) -> Option<PortDataSettings> {
+    Some(PortDataSettings {
+        usb_a: *PortDataMode::ALL.get(usize::from(record[9]))?,
+        usb_c: *PortDataMode::ALL.get(usize::from(record[10]))?,
+    })
+}
+
+pub fn decode_port_power_settings(
+    record: &[u8; PORT_POWER_RECORD_LEN],
+) -> Option<PortPowerSettings> {
+    let flags = record[11];
+    if flags & !(PORT_POWER_FLAG_LAST_USB_A_ON | PORT_POWER_FLAG_LAST_USB_C_ON) != 0 {
+        return None;
+    }
+    Some(PortPowerSettings {
+        usb_a: *PortPowerPolicy::ALL.get(usize::from(record[9]))?,
+        usb_c: *PortPowerPolicy::ALL.get(usize::from(record[10]))?,
+        last_usb_a_on: flags & PORT_POWER_FLAG_LAST_USB_A_ON != 0,
+        last_usb_c_on: flags & PORT_POWER_FLAG_LAST_USB_C_ON != 0,
+    })
+}
+
+/// Each port takes 8 bytes: enabled, action, then threshold, hold and
+/// minimum charge time as little-endian `u16`s.
+pub fn encode_charge_termination_settings(
+    record: &mut [u8; CHARGE_TERMINATION_RECORD_LEN],
+    settings: ChargeTerminationSettings,
+) {
+    for (offset, config) in [
+        (CHARGE_TERMINATION_USB_A_OFFSET, settings.usb_a),
+        (CHARGE_TERMINATION_USB_C_OFFSET, settings.usb_c),
+    ] {
+        record[offset] = config.enabled as u8;
+        record[offset + 1] = config.action as u8;
+        record[offset + 2..offset + 4].copy_from_slice(&config.threshold_ma.to_le_bytes());
+        record[offset + 4..offset + 6].copy_from_slice(&config.hold_s.to_le_bytes());
+        record[offset + 6..offset + 8].copy_from_slice(&config.min_charge_s.to_le_bytes());
+    }
+}
+
+pub fn decode_charge_termination_settings(
+    record: &[u8; CHARGE_TERMINATION_RECORD_LEN],
+) -> Option<ChargeTerminationSettings> {
+    let config = |offset: usize| {
+        let enabled = match record[offset] {
+            0 => false,
+            1 => true,
+            _ => return None,
+        };
+        let u16_at = |at: usize| u16::from_le_bytes([record[at], record[at + 1]]);
+        ChargeTerminationConfig {
+            enabled,
+            action: *ChargeTerminationAction::ALL.get(usize::from(record[offset + 1]))?,
+            threshold_ma: u16_at(offset + 2),
+            hold_s: u16_at(offset + 4),
+            min_charge_s: u16_at(offset + 6),
+        }
+        .validated()
+        .ok()
+    };
+    Some(ChargeTerminationSettings {
+        usb_a: config(CHARGE_TERMINATION_USB_A_OFFSET)?,
+        usb_c: config(CHARGE_TERMINATION_USB_C_OFFSET)?,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -526,4 +862,199 @@ mod tests {
 
         assert_ne!(record[IDLE_BIAS_MAGIC.len()], IDLE_BIAS_VERSION);
     }
+
+    #[test]
+    fn sound_settings_record_round_trips() {
+        let settings = SoundSettings {
+            muted: true,
+            classes: SoundClassFlags {
+                boot: false,
+                menu: true,
+                action: false,
+                warning: true,
+            },
+            quiet_hours: QuietHours {
+                enabled: true,
+                start_minute: 21 * 60 + 30,
+                end_minute: 6 * 60,
+            },
+            volume_pct: 35,
+        };
+        let mut record = [0u8; SOUND_SETTINGS_RECORD_LEN];
+        record[..SOUND_SETTINGS_MAGIC.len()].copy_from_slice(SOUND_SETTINGS_MAGIC);
+        record[SOUND_SETTINGS_MAGIC.len()] = SOUND_SETTINGS_VERSION;
+        encode_sound_settings(&mut record, settings);
+        write_record_checksum(&mut record);
+
+        let mut validated = record;
+        assert!(record_checksum_matches(&mut validated));
+        assert_eq!(decode_sound_settings(&record), Some(settings));
+
+        record[10] = 150;
+        assert!(decode_sound_settings(&record).is_none());
+    }
+
+    #[test]
+    fn custom_tone_record_round_trips_and_checks_slot() {
+        let pattern = CustomSoundPattern::parse("2700:40,0:60,3200:40").expect("pattern");
+        let mut record = [0u8; CUSTOM_TONE_RECORD_LEN];
+        record[..CUSTOM_TONE_MAGIC.len()].copy_from_slice(CUSTOM_TONE_MAGIC);
+        record[CUSTOM_TONE_MAGIC.len()] = CUSTOM_TONE_VERSION;
+        encode_custom_tone(&mut record, SoundSlot::MenuConfirm, &pattern);
+        write_record_checksum(&mut record);
+
+        let mut validated = record;
+        assert!(record_checksum_matches(&mut validated));
+        assert_eq!(
+            decode_custom_tone(&record, SoundSlot::MenuConfirm),
+            Some(pattern)
+        );
+        assert!(decode_custom_tone(&record, SoundSlot::ActionOk).is_none());
+    }
+
+    #[test]
+    fn display_settings_record_round_trips() {
+        let settings = DisplaySettings {
+            brightness_pct: 45,
+            dim_after_s: 120,
+            off_after_s: 600,
+            rotation: DisplayRotation::Flipped,
+            theme: DisplayTheme::Dark,
+        };
+        let mut record = [0u8; DISPLAY_SETTINGS_RECORD_LEN];
+        record[..DISPLAY_SETTINGS_MAGIC.len()].copy_from_slice(DISPLAY_SETTINGS_MAGIC);
+        record[DISPLAY_SETTINGS_MAGIC.len()] = DISPLAY_SETTINGS_VERSION;
+        encode_display_settings(&mut record, settings);
+        write_record_checksum(&mut record);
+
+        let mut validated = record;
+        assert!(record_checksum_matches(&mut validated));
+        assert_eq!(decode_display_settings(&record), Some(settings));
+
+        record[13..15].copy_from_slice(&60u16.to_le_bytes());
+        assert!(decode_display_settings(&record).is_none());
+    }
+
+    #[test]
+    fn button_settings_record_round_trips() {
+        let mut settings = ButtonSettings::defaults();
+        settings.set_action(ButtonGesture::RightDouble, ButtonAction::CyclePreset);
+        settings.set_action(ButtonGesture::ComboShort, ButtonAction::Lock);
+        settings.locked = true;
+        let mut record = [0u8; BUTTON_SETTINGS_RECORD_LEN];
+        record[..BUTTON_SETTINGS_MAGIC.len()].copy_from_slice(BUTTON_SETTINGS_MAGIC);
+        record[BUTTON_SETTINGS_MAGIC.len()] = BUTTON_SETTINGS_VERSION;
+        encode_button_settings(&mut record, settings);
+        write_record_checksum(&mut record);
+
+        let mut validated = record;
+        assert!(record_checksum_matches(&mut validated));
+        assert_eq!(decode_button_settings(&record), Some(settings));
+
+        record[BUTTON_ACTIONS_OFFSET + ButtonGesture::ComboLong.index()] =
+            ButtonAction::DataReplug.code();
+        assert!(decode_button_settings(&record).is_none());
+        record[BUTTON_ACTIONS_OFFSET + ButtonGesture::ComboLong.index()] = 0xFF;
+        assert!(decode_button_settings(&record).is_none());
+    }
+
+    #[test]
+    fn jsonl_token_record_round_trips() {
+        let token = JsonlToken::new("bench-7f3a9c21d4e8").expect("valid token");
+        let mut record = [0u8; JSONL_TOKEN_RECORD_LEN];
+        record[..JSONL_TOKEN_MAGIC.len()].copy_from_slice(JSONL_TOKEN_MAGIC);
+        record[JSONL_TOKEN_MAGIC.len()] = JSONL_TOKEN_VERSION;
+        encode_jsonl_token(&mut record, &token);
+        write_record_checksum(&mut record);
+
+        let mut validated = record;
+        assert!(record_checksum_matches(&mut validated));
+        assert_eq!(decode_jsonl_token(&record), Some(token));
+
+        record[9] = 40;
+        assert!(decode_jsonl_token(&record).is_none());
+        record[9] = 4;
+        assert!(decode_jsonl_token(&record).is_none());
+    }
+
+    #[test]
+    fn log_settings_record_round_trips() {
+        let settings = LogSettings {
+            level: LogLevel::Warn,
+            syslog: Some(SyslogTarget {
+                host: [192, 168, 1, 20],
+                port: 5514,
+            }),
+        };
+        let mut record = [0u8; LOG_SETTINGS_RECORD_LEN];
+        encode_log_settings(&mut record, settings);
+        assert_eq!(decode_log_settings(&record), Some(settings));
+
+        let mut record = [0u8; LOG_SETTINGS_RECORD_LEN];
+        encode_log_settings(&mut record, LogSettings::defaults());
+        assert_eq!(decode_log_settings(&record), Some(LogSettings::defaults()));
+
+        record[9] = 9;
+        assert!(decode_log_settings(&record).is_none());
+        record[9] = 0;
+        record[10] = LOG_FLAG_SYSLOG;
+        assert!(decode_log_settings(&record).is_none());
+    }
+
+    #[test]
+    fn port_power_settings_round_trip() {
+        let settings = PortPowerSettings {
+            usb_a: PortPowerPolicy::Last,
+            usb_c: PortPowerPolicy::Off,
+            last_usb_a_on: false,
+            last_usb_c_on: true,
+        };
+        let mut record = [0u8; PORT_POWER_RECORD_LEN];
+        encode_port_power_settings(&mut record, settings);
+        assert_eq!(decode_port_power_settings(&record), Some(settings));
+
+        record[10] = 3;
+        assert!(decode_port_power_settings(&record).is_none());
+        record[10] = 0;
+        record[11] = 0x04;
+        assert!(decode_port_power_settings(&record).is_none());
+    }
+
+    #[test]
+    fn port_data_settings_round_trip() {
+        let settings = PortDataSettings {
+            usb_a: PortDataMode::ChargeOnly,
+            usb_c: PortDataMode::Connected,
+        };
+        let mut record = [0u8; PORT_DATA_RECORD_LEN];
+        encode_port_data_settings(&mut record, settings);
+        assert_eq!(decode_port_data_settings(&record), Some(settings));
+
+        record[9] = 2;
+        assert!(decode_port_data_settings(&record).is_none());
+    }
+
+    #[test]
+    fn charge_termination_settings_round_trip() {
+        let settings = ChargeTerminationSettings {
+            usb_a: ChargeTerminationConfig::defaults(),
+            usb_c: ChargeTerminationConfig {
+                enabled: true,
+                action: ChargeTerminationAction::Drop5v,
+                threshold_ma: 150,
+                hold_s: 600,
+                min_charge_s: 3_600,
+            },
+        };
+        let mut record = [0u8; CHARGE_TERMINATION_RECORD_LEN];
+        encode_charge_termination_settings(&mut record, settings);
+        assert_eq!(decode_charge_termination_settings(&record), Some(settings));
+
+        record[CHARGE_TERMINATION_USB_C_OFFSET + 1] = 3;
+        assert!(decode_charge_termination_settings(&record).is_none());
+        record[CHARGE_TERMINATION_USB_C_OFFSET + 1] = 0;
+        record[CHARGE_TERMINATION_USB_A_OFFSET + 2] = 0;
+        record[CHARGE_TERMINATION_USB_A_OFFSET + 3] = 0;
+        assert!(decode_charge_termination_settings(&record).is_none());
+    }
 }
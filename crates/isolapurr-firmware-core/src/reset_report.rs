@@ -0,0 +1,489 @@
+//! Reset cause classification and the crash record kept in RTC fast memory
+//! across resets (see `docs/specs/c5v2p-crash-report/SPEC.md`).
+
+use core::fmt;
+
+use isolapurr_api::diagnostics::ResetReason;
+
+use crate::provisioning::{record_checksum_matches, write_record_checksum};
+
+pub const CRASH_MESSAGE_LEN: usize = 96;
+pub const CRASH_FILE_LEN: usize = 48;
+pub const CRASH_BACKTRACE_LEN: usize = 12;
+pub const RESET_RECORD_LEN: usize = 256;
+
+const RESET_RECORD_MAGIC: [u8; 4] = *b"IPRR";
+const RESET_RECORD_VERSION: u8 = 1;
+
+/// Maps an ESP32-S3 ROM reset reason (`RESET_REASON` in esp-idf `rtc.h`).
+pub const fn classify_hw_reset(code: u8) -> ResetReason {
+    match code {
+        0x01 => ResetReason::PowerOn,
+        // CORE_SW / CPU0_SW
+        0x03 | 0x0C => ResetReason::Software,
+        0x05 => ResetReason::DeepSleep,
+        // MWDT0/1 and RTC WDT at core, CPU and system level, and the super WDT.
+        0x07 | 0x08 | 0x09 | 0x0B | 0x0D | 0x10 | 0x11 | 0x12 => ResetReason::Watchdog,
+        // SYS_BROWN_OUT and CORE_PWR_GLITCH
+        0x0F | 0x17 => ResetReason::Brownout,
+        // CORE_USB_UART / CORE_USB_JTAG
+        0x15 | 0x16 => ResetReason::Usb,
+        _ => ResetReason::Unknown,
+    }
+}
+
+/// Resets that leave a crash report until it is acknowledged.
+pub const fn is_crash(reason: ResetReason) -> bool {
+    matches!(
+        reason,
+        ResetReason::Panic | ResetReason::Watchdog | ResetReason::Brownout
+    )
+}
+
+/// Fixed-capacity UTF-8 text for the panic handler, which cannot allocate.
+/// Writes past the capacity are dropped on a char boundary.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CrashText<const N: usize> {
+    bytes: [u8; N],
+    len: u8,
+}
+
+impl<const N: usize> CrashText<N> {
+    pub const fn new() -> Self {
+        Self {
+            bytes: [0; N],
+            len: 0,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..self.len as usize]).unwrap_or("")
+    }
+
+    pub fn push_str(&mut self, text: &str) {
+        let len = self.len as usize;
+        let mut take = text.len().min(N - len);
+        while !text.is_char_boundary(take) {
+            take -= 1;
+        }
+        self.bytes[len..len + take].copy_from_slice(&text.as_bytes()[..take]);
+        self.len = (len + take) as u8;
+    }
+
+    fn encode(&self, out: &mut [u8]) {
+        out[0] = self.len;
+        out[1..1 + N].copy_from_slice(&self.bytes);
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        let mut text = Self::new();
+        text.len = bytes[0];
+        if text.len as usize > N {
+            return None;
+        }
+        text.bytes.copy_from_slice(&bytes[1..1 + N]);
+        core::str::from_utf8(&text.bytes[..text.len as usize]).ok()?;
+        Some(text)
+    }
+}
+
+impl<const N: usize> Default for CrashText<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> fmt::Write for CrashText<N> {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.push_str(text);
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PanicLocation {
+    pub file: CrashText<CRASH_FILE_LEN>,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl PanicLocation {
+    /// Keeps the tail of long paths (registry sources), where the file name is.
+    pub fn new(file: &str, line: u32, column: u32) -> Self {
+        let mut start = file.len().saturating_sub(CRASH_FILE_LEN);
+        while !file.is_char_boundary(start) {
+            start += 1;
+        }
+        let mut text = CrashText::new();
+        text.push_str(&file[start..]);
+        Self {
+            file: text,
+            line,
+            column,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CrashReport {
+    pub reason: ResetReason,
+    /// ROM reset reason of the boot that followed the crash.
+    pub hw_code: u8,
+    /// Uptime when the panic fired; 0 for watchdog and brown-out resets.
+    pub uptime_ms: u64,
+    /// Panic message; empty for other crashes.
+    pub message: CrashText<CRASH_MESSAGE_LEN>,
+    pub location: Option<PanicLocation>,
+    backtrace: [u32; CRASH_BACKTRACE_LEN],
+    backtrace_len: u8,
+}
+
+impl CrashReport {
+    pub const fn new(reason: ResetReason, hw_code: u8, uptime_ms: u64) -> Self {
+        Self {
+            reason,
+            hw_code,
+            uptime_ms,
+            message: CrashText::new(),
+            location: None,
+            backtrace: [0; CRASH_BACKTRACE_LEN],
+            backtrace_len: 0,
+        }
+    }
+
+    /// Return addresses, innermost first.
+    pub fn backtrace(&self) -> &[u32] {
+        &self.backtrace[..self.backtrace_len as usize]
+    }
+
+    /// Returns false once the backtrace is full.
+    pub fn push_frame(&mut self, address: u32) -> bool {
+        let Some(slot) = self.backtrace.get_mut(self.backtrace_len as usize) else {
+            return false;
+        };
+        *slot = address;
+        self.backtrace_len += 1;
+        true
+    }
+}
+
+/// Set right before a reset the firmware chose, and consumed on the next boot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PendingReset {
+    None,
+    Requested,
+    Panic,
+}
+
+/// What survives in RTC fast memory: the pending marker and the latest
+/// unacknowledged crash.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResetRecord {
+    pending: PendingReset,
+    pub crash: Option<CrashReport>,
+    /// Crashes since the last acknowledge; only the latest is kept.
+    pub unacknowledged: u16,
+}
+
+impl ResetRecord {
+    pub const fn new() -> Self {
+        Self {
+            pending: PendingReset::None,
+            crash: None,
+            unacknowledged: 0,
+        }
+    }
+
+    /// The coming software reset was asked for (`reboot`, settings reset).
+    pub fn mark_requested(&mut self) {
+        if self.pending == PendingReset::None {
+            self.pending = PendingReset::Requested;
+        }
+    }
+
+    /// Called from the panic handler; replaces any older crash.
+    pub fn record_panic(&mut self, crash: CrashReport) {
+        self.pending = PendingReset::Panic;
+        self.crash = Some(crash);
+    }
+
+    pub fn acknowledge(&mut self) {
+        self.crash = None;
+        self.unacknowledged = 0;
+    }
+
+    /// Resolves why this boot happened from the pending marker and the ROM
+    /// reset reason, and keeps a crash report when it was one.
+    pub fn on_boot(&mut self, hw_code: u8) -> ResetReason {
+        let hw = classify_hw_reset(hw_code);
+        let reason = match (self.pending, hw) {
+            (PendingReset::Panic, _) => ResetReason::Panic,
+            (PendingReset::Requested, ResetReason::Software) => ResetReason::Requested,
+            _ => hw,
+        };
+        self.pending = PendingReset::None;
+        match reason {
+            ResetReason::Panic => match &mut self.crash {
+                Some(crash) => crash.hw_code = hw_code,
+                None => self.crash = Some(CrashReport::new(reason, hw_code, 0)),
+            },
+            reason if is_crash(reason) => {
+                self.crash = Some(CrashReport::new(reason, hw_code, 0));
+            }
+            _ => return reason,
+        }
+        self.unacknowledged = self.unacknowledged.saturating_add(1);
+        reason
+    }
+}
+
+impl Default for ResetRecord {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// This boot's reset cause plus the outstanding crash, as served by `info` and
+/// `diagnostics.crash`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResetReport {
+    pub reason: ResetReason,
+    pub hw_code: u8,
+    pub crash: Option<CrashReport>,
+    pub unacknowledged: u16,
+}
+
+impl ResetReport {
+    pub const fn unknown() -> Self {
+        Self {
+            reason: ResetReason::Unknown,
+            hw_code: 0,
+            crash: None,
+            unacknowledged: 0,
+        }
+    }
+
+    pub const fn from_boot(reason: ResetReason, hw_code: u8, record: &ResetRecord) -> Self {
+        Self {
+            reason,
+            hw_code,
+            crash: record.crash,
+            unacknowledged: record.unacknowledged,
+        }
+    }
+}
+
+const CRASH_OFFSET: usize = 12;
+const MESSAGE_OFFSET: usize = CRASH_OFFSET + 11;
+const LOCATION_OFFSET: usize = MESSAGE_OFFSET + 1 + CRASH_MESSAGE_LEN;
+const BACKTRACE_OFFSET: usize = LOCATION_OFFSET + 1 + 1 + CRASH_FILE_LEN + 8;
+const _: () = assert!(BACKTRACE_OFFSET + 1 + 4 * CRASH_BACKTRACE_LEN <= RESET_RECORD_LEN - 4);
+
+fn reason_index(reason: ResetReason) -> u8 {
+    ResetReason::ALL
+        .iter()
+        .position(|candidate| *candidate == reason)
+        .unwrap_or(0) as u8
+}
+
+fn u32_at(record: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        record[offset],
+        record[offset + 1],
+        record[offset + 2],
+        record[offset + 3],
+    ])
+}
+
+pub fn encode_reset_record(record: &mut [u8; RESET_RECORD_LEN], value: &ResetRecord) {
+    record.fill(0);
+    record[..4].copy_from_slice(&RESET_RECORD_MAGIC);
+    record[4] = RESET_RECORD_VERSION;
+    record[5] = match value.pending {
+        PendingReset::None => 0,
+        PendingReset::Requested => 1,
+        PendingReset::Panic => 2,
+    };
+    record[6..8].copy_from_slice(&value.unacknowledged.to_le_bytes());
+    if let Some(crash) = &value.crash {
+        record[8] = 1;
+        record[CRASH_OFFSET] = reason_index(crash.reason);
+        record[CRASH_OFFSET + 1] = crash.hw_code;
+        record[CRASH_OFFSET + 2..CRASH_OFFSET + 10].copy_from_slice(&crash.uptime_ms.to_le_bytes());
+        crash.message.encode(&mut record[MESSAGE_OFFSET..]);
+        if let Some(location) = &crash.location {
+            record[LOCATION_OFFSET] = 1;
+            location.file.encode(&mut record[LOCATION_OFFSET + 1..]);
+            let numbers = LOCATION_OFFSET + 2 + CRASH_FILE_LEN;
+            record[numbers..numbers + 4].copy_from_slice(&location.line.to_le_bytes());
+            record[numbers + 4..numbers + 8].copy_from_slice(&location.column.to_le_bytes());
+        }
+        record[BACKTRACE_OFFSET] = crash.backtrace_len;
+        for (index, address) in crash.backtrace().iter().enumerate() {
+            let offset = BACKTRACE_OFFSET + 1 + index * 4;
+            record[offset..offset + 4].copy_from_slice(&address.to_le_bytes());
+        }
+    }
+    write_record_checksum(record);
+}
+
+/// `None` for anything but an intact record, e.g. the random contents RTC
+/// fast memory holds after power-on.
+pub fn decode_reset_record(record: &[u8; RESET_RECORD_LEN]) -> Option<ResetRecord> {
+    let mut scratch = *record;
+    if record[..4] != RESET_RECORD_MAGIC
+        || record[4] != RESET_RECORD_VERSION
+        || !record_checksum_matches(&mut scratch)
+    {
+        return None;
+    }
+    let pending = match record[5] {
+        0 => PendingReset::None,
+        1 => PendingReset::Requested,
+        2 => PendingReset::Panic,
+        _ => return None,
+    };
+    let crash = match record[8] {
+        0 => None,
+        1 => {
+            let reason = *ResetReason::ALL.get(record[CRASH_OFFSET] as usize)?;
+            let mut uptime = [0u8; 8];
+            uptime.copy_from_slice(&record[CRASH_OFFSET + 2..CRASH_OFFSET + 10]);
+            let mut crash =
+                CrashReport::new(reason, record[CRASH_OFFSET + 1], u64::from_le_bytes(uptime));
+            crash.message = CrashText::decode(&record[MESSAGE_OFFSET..])?;
+            if record[LOCATION_OFFSET] == 1 {
+                let numbers = LOCATION_OFFSET + 2 + CRASH_FILE_LEN;
+                crash.location = Some(PanicLocation {
+                    file: CrashText::decode(&record[LOCATION_OFFSET + 1..])?,
+                    line: u32_at(record, numbers),
+                    column: u32_at(record, numbers + 4),
+                });
+            }
+            let frames = record[BACKTRACE_OFFSET] as usize;
+            if frames > CRASH_BACKTRACE_LEN {
+                return None;
+            }
+            for index in 0..frames {
+                crash.push_frame(u32_at(record, BACKTRACE_OFFSET + 1 + index * 4));
+            }
+            Some(crash)
+        }
+        _ => return None,
+    };
+    Some(ResetRecord {
+        pending,
+        crash,
+        unacknowledged: u16::from_le_bytes([record[6], record[7]]),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CRASH_BACKTRACE_LEN, CRASH_FILE_LEN, CrashReport, CrashText, PanicLocation,
+        RESET_RECORD_LEN, ResetRecord, classify_hw_reset, decode_reset_record, encode_reset_record,
+    };
+    use core::fmt::Write as _;
+    use isolapurr_api::diagnostics::ResetReason;
+
+    fn panic_report() -> CrashReport {
+        let mut crash = CrashReport::new(ResetReason::Panic, 0, 12_345);
+        let _ = write!(crash.message, "index out of bounds: {} >= {}", 9, 8);
+        crash.location = Some(PanicLocation::new(
+            "src/bin/firmware_main/main_loop_pd.inc",
+            42,
+            7,
+        ));
+        crash.push_frame(0x4200_1234);
+        crash.push_frame(0x4200_5678);
+        crash
+    }
+
+    #[test]
+    fn rom_reset_codes_map_to_reasons() {
+        assert_eq!(classify_hw_reset(0x01), ResetReason::PowerOn);
+        assert_eq!(classify_hw_reset(0x0C), ResetReason::Software);
+        assert_eq!(classify_hw_reset(0x07), ResetReason::Watchdog);
+        assert_eq!(classify_hw_reset(0x12), ResetReason::Watchdog);
+        assert_eq!(classify_hw_reset(0x0F), ResetReason::Brownout);
+        assert_eq!(classify_hw_reset(0x15), ResetReason::Usb);
+        assert_eq!(classify_hw_reset(0x00), ResetReason::Unknown);
+    }
+
+    #[test]
+    fn panic_survives_the_reset_until_acknowledged() {
+        let mut record = ResetRecord::new();
+        record.record_panic(panic_report());
+        let mut bytes = [0u8; RESET_RECORD_LEN];
+        encode_reset_record(&mut bytes, &record);
+
+        let mut record = decode_reset_record(&bytes).expect("record should decode");
+        assert_eq!(record.on_boot(0x0C), ResetReason::Panic);
+        let crash = record.crash.expect("panic should be kept");
+        assert_eq!(crash.hw_code, 0x0C);
+        assert_eq!(crash.message.as_str(), "index out of bounds: 9 >= 8");
+        assert_eq!(crash.location.unwrap().line, 42);
+        assert_eq!(crash.backtrace(), &[0x4200_1234, 0x4200_5678]);
+        assert_eq!(record.unacknowledged, 1);
+
+        // A requested reboot keeps the unacknowledged crash.
+        record.mark_requested();
+        encode_reset_record(&mut bytes, &record);
+        let mut record = decode_reset_record(&bytes).unwrap();
+        assert_eq!(record.on_boot(0x0C), ResetReason::Requested);
+        assert_eq!(record.crash, Some(crash));
+
+        record.acknowledge();
+        assert_eq!(record.on_boot(0x03), ResetReason::Software);
+        assert_eq!((record.crash, record.unacknowledged), (None, 0));
+    }
+
+    #[test]
+    fn watchdog_and_brownout_replace_the_crash_and_count() {
+        let mut record = ResetRecord::new();
+        record.record_panic(panic_report());
+        record.on_boot(0x0C);
+        assert_eq!(record.on_boot(0x07), ResetReason::Watchdog);
+        let crash = record.crash.unwrap();
+        assert_eq!((crash.reason, crash.hw_code), (ResetReason::Watchdog, 0x07));
+        assert!(crash.backtrace().is_empty());
+        assert_eq!(record.unacknowledged, 2);
+
+        // A requested marker does not hide a brown-out.
+        record.mark_requested();
+        assert_eq!(record.on_boot(0x0F), ResetReason::Brownout);
+        assert_eq!(record.unacknowledged, 3);
+    }
+
+    #[test]
+    fn garbage_and_tampered_records_are_rejected() {
+        assert_eq!(decode_reset_record(&[0xA5; RESET_RECORD_LEN]), None);
+
+        let mut bytes = [0u8; RESET_RECORD_LEN];
+        encode_reset_record(&mut bytes, &ResetRecord::new());
+        assert_eq!(decode_reset_record(&bytes), Some(ResetRecord::new()));
+        bytes[6] ^= 1;
+        assert_eq!(decode_reset_record(&bytes), None);
+    }
+
+    #[test]
+    fn text_and_backtrace_truncate_safely() {
+        let mut text = CrashText::<4>::new();
+        text.push_str("ab");
+        text.push_str("é€");
+        assert_eq!(text.as_str(), "abé");
+
+        let path = "/home/ci/.cargo/registry/src/index.crates.io-1949cf8c6b5b557f/esp-hal-1.0.0/src/i2c/master/mod.rs";
+        let location = PanicLocation::new(path, 1, 2);
+        assert_eq!(location.file.as_str().len(), CRASH_FILE_LEN);
+        assert!(location.file.as_str().ends_with("src/i2c/master/mod.rs"));
+
+        let mut crash = CrashReport::new(ResetReason::Panic, 0, 0);
+        for address in 0..CRASH_BACKTRACE_LEN as u32 {
+            assert!(crash.push_frame(address));
+        }
+        assert!(!crash.push_frame(0xFFFF_FFFF));
+        assert_eq!(crash.backtrace().len(), CRASH_BACKTRACE_LEN);
+    }
+}
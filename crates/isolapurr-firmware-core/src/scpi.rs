@@ -0,0 +1,555 @@
+//! SCPI command parser for the raw-socket bench-supply interface.
+//!
+//! Only the small power-supply subset is understood. Headers accept the short
+//! or long mnemonic in any case, optional nodes may be omitted, and each
+//! `;`-separated command is parsed from the root.
+
+use core::fmt::Write;
+
+use heapless::Deque;
+
+use crate::power_config::{
+    MANUAL_DEFAULT_CURRENT_MA, MANUAL_DEFAULT_VOLTAGE_MV, MANUAL_MAX_VOLTAGE_MV,
+    MANUAL_MIN_VOLTAGE_MV, TPS_MAX_CURRENT_MA,
+};
+
+pub const SCPI_PORT: u16 = 5025;
+pub const SCPI_MAX_LINE_LEN: usize = 256;
+pub const SCPI_ERROR_QUEUE_LEN: usize = 8;
+/// Smallest non-zero current limit the TPS55288 can be programmed with.
+pub const SCPI_MIN_CURRENT_MA: u16 = 50;
+
+/// A numeric parameter, in millivolts or milliamps.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ScpiLevel {
+    Milli(u32),
+    Min,
+    Max,
+    Default,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ScpiCommand {
+    /// `*IDN?`
+    Identify,
+    /// `*RST`
+    Reset,
+    /// `*CLS`
+    ClearStatus,
+    /// `*OPC?`
+    OperationComplete,
+    /// `*SAV [0]`: store the live setpoint; there is a single register.
+    Save,
+    /// `[SOUR:]VOLT[:LEV][:IMM][:AMPL] <level>`
+    SetVoltage(ScpiLevel),
+    QueryVoltage,
+    /// `[SOUR:]CURR[:LEV][:IMM][:AMPL] <level>`
+    SetCurrent(ScpiLevel),
+    QueryCurrent,
+    /// `OUTP[:STAT] ON|OFF|1|0`
+    SetOutput(bool),
+    QueryOutput,
+    /// `MEAS[:SCAL]:VOLT[:DC]?`
+    MeasureVoltage,
+    MeasureCurrent,
+    MeasurePower,
+    /// `SYST:ERR[:NEXT]?`
+    SystemError,
+}
+
+impl ScpiCommand {
+    pub const fn is_query(self) -> bool {
+        matches!(
+            self,
+            ScpiCommand::Identify
+                | ScpiCommand::OperationComplete
+                | ScpiCommand::QueryVoltage
+                | ScpiCommand::QueryCurrent
+                | ScpiCommand::QueryOutput
+                | ScpiCommand::MeasureVoltage
+                | ScpiCommand::MeasureCurrent
+                | ScpiCommand::MeasurePower
+                | ScpiCommand::SystemError
+        )
+    }
+}
+
+/// Standard SCPI error codes reported through `SYST:ERR?`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ScpiError {
+    DataTypeError,
+    ParameterNotAllowed,
+    MissingParameter,
+    UndefinedHeader,
+    ExecutionError,
+    SettingsConflict,
+    DataOutOfRange,
+    DataStale,
+    QueueOverflow,
+    InputBufferOverrun,
+}
+
+impl ScpiError {
+    pub const fn code(self) -> i16 {
+        match self {
+            ScpiError::DataTypeError => -104,
+            ScpiError::ParameterNotAllowed => -108,
+            ScpiError::MissingParameter => -109,
+            ScpiError::UndefinedHeader => -113,
+            ScpiError::ExecutionError => -200,
+            ScpiError::SettingsConflict => -221,
+            ScpiError::DataOutOfRange => -222,
+            ScpiError::DataStale => -230,
+            ScpiError::QueueOverflow => -350,
+            ScpiError::InputBufferOverrun => -363,
+        }
+    }
+
+    pub const fn message(self) -> &'static str {
+        match self {
+            ScpiError::DataTypeError => "Data type error",
+            ScpiError::ParameterNotAllowed => "Parameter not allowed",
+            ScpiError::MissingParameter => "Missing parameter",
+            ScpiError::UndefinedHeader => "Undefined header",
+            ScpiError::ExecutionError => "Execution error",
+            ScpiError::SettingsConflict => "Settings conflict",
+            ScpiError::DataOutOfRange => "Data out of range",
+            ScpiError::DataStale => "Data stale",
+            ScpiError::QueueOverflow => "Queue overflow",
+            ScpiError::InputBufferOverrun => "Input buffer overrun",
+        }
+    }
+}
+
+/// Per-connection error queue. When full, the newest entry becomes
+/// `-350 Queue overflow` as IEEE 488.2 requires.
+#[derive(Debug, Default)]
+pub struct ScpiErrorQueue {
+    entries: Deque<ScpiError, SCPI_ERROR_QUEUE_LEN>,
+}
+
+impl ScpiErrorQueue {
+    pub const fn new() -> Self {
+        Self {
+            entries: Deque::new(),
+        }
+    }
+
+    pub fn push(&mut self, error: ScpiError) {
+        if self.entries.is_full() {
+            let _ = self.entries.pop_back();
+            let _ = self.entries.push_back(ScpiError::QueueOverflow);
+        } else {
+            let _ = self.entries.push_back(error);
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<ScpiError> {
+        self.entries.pop_front()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Writes the next `SYST:ERR?` response, e.g. `-113,"Undefined header"`.
+    pub fn write_next<W: Write>(&mut self, out: &mut W) -> core::fmt::Result {
+        match self.pop() {
+            Some(error) => write!(out, "{},\"{}\"", error.code(), error.message()),
+            None => out.write_str("0,\"No error\""),
+        }
+    }
+}
+
+/// Splits a program message into its `;`-separated commands.
+pub fn split_program(line: &str) -> impl Iterator<Item = &str> {
+    line.split(';')
+        .map(str::trim)
+        .filter(|command| !command.is_empty())
+}
+
+pub fn parse_command(text: &str) -> Result<ScpiCommand, ScpiError> {
+    let text = text.trim();
+    let (header, params) = match text.find(|c: char| c.is_ascii_whitespace()) {
+        Some(split) => (&text[..split], text[split..].trim()),
+        None => (text, ""),
+    };
+    let (header, query) = match header.strip_suffix('?') {
+        Some(header) => (header, true),
+        None => (header, false),
+    };
+
+    let command = if let Some(common) = header.strip_prefix('*') {
+        match query {
+            true if common.eq_ignore_ascii_case("IDN") => ScpiCommand::Identify,
+            false if common.eq_ignore_ascii_case("RST") => ScpiCommand::Reset,
+            false if common.eq_ignore_ascii_case("CLS") => ScpiCommand::ClearStatus,
+            true if common.eq_ignore_ascii_case("OPC") => ScpiCommand::OperationComplete,
+            false if common.eq_ignore_ascii_case("SAV") => match params {
+                "" | "0" => ScpiCommand::Save,
+                _ => return Err(ScpiError::DataOutOfRange),
+            },
+            _ => return Err(ScpiError::UndefinedHeader),
+        }
+    } else {
+        parse_subsystem(header.strip_prefix(':').unwrap_or(header), query, params)?
+    };
+
+    let takes_param = matches!(
+        command,
+        ScpiCommand::Save
+            | ScpiCommand::SetVoltage(_)
+            | ScpiCommand::SetCurrent(_)
+            | ScpiCommand::SetOutput(_)
+    );
+    if !takes_param && !params.is_empty() {
+        return Err(ScpiError::ParameterNotAllowed);
+    }
+    Ok(command)
+}
+
+fn parse_subsystem(header: &str, query: bool, params: &str) -> Result<ScpiCommand, ScpiError> {
+    let mut nodes: heapless::Vec<&str, 6> = heapless::Vec::new();
+    for node in header.split(':') {
+        nodes.push(node).map_err(|_| ScpiError::UndefinedHeader)?;
+    }
+    let mut nodes = nodes.as_slice();
+    if nodes
+        .first()
+        .is_some_and(|node| mnemonic(node, "SOUR", "SOURCE"))
+    {
+        nodes = &nodes[1..];
+    }
+    let Some((first, rest)) = nodes.split_first() else {
+        return Err(ScpiError::UndefinedHeader);
+    };
+
+    if mnemonic(first, "VOLT", "VOLTAGE") || mnemonic(first, "CURR", "CURRENT") {
+        if !optional_nodes(
+            rest,
+            &[
+                ("LEV", "LEVEL"),
+                ("IMM", "IMMEDIATE"),
+                ("AMPL", "AMPLITUDE"),
+            ],
+        ) {
+            return Err(ScpiError::UndefinedHeader);
+        }
+        let voltage = mnemonic(first, "VOLT", "VOLTAGE");
+        return match (voltage, query) {
+            (true, true) => Ok(ScpiCommand::QueryVoltage),
+            (false, true) => Ok(ScpiCommand::QueryCurrent),
+            (true, false) => Ok(ScpiCommand::SetVoltage(parse_level(params, b'V')?)),
+            (false, false) => Ok(ScpiCommand::SetCurrent(parse_level(params, b'A')?)),
+        };
+    }
+
+    if mnemonic(first, "OUTP", "OUTPUT") {
+        if !optional_nodes(rest, &[("STAT", "STATE")]) {
+            return Err(ScpiError::UndefinedHeader);
+        }
+        return if query {
+            Ok(ScpiCommand::QueryOutput)
+        } else {
+            Ok(ScpiCommand::SetOutput(parse_bool(params)?))
+        };
+    }
+
+    if mnemonic(first, "MEAS", "MEASURE") && query {
+        let rest = match rest.split_first() {
+            Some((node, tail)) if mnemonic(node, "SCAL", "SCALAR") => tail,
+            _ => rest,
+        };
+        let Some((quantity, tail)) = rest.split_first() else {
+            return Err(ScpiError::UndefinedHeader);
+        };
+        if !optional_nodes(tail, &[("DC", "DC")]) {
+            return Err(ScpiError::UndefinedHeader);
+        }
+        if mnemonic(quantity, "VOLT", "VOLTAGE") {
+            return Ok(ScpiCommand::MeasureVoltage);
+        }
+        if mnemonic(quantity, "CURR", "CURRENT") {
+            return Ok(ScpiCommand::MeasureCurrent);
+        }
+        if mnemonic(quantity, "POW", "POWER") {
+            return Ok(ScpiCommand::MeasurePower);
+        }
+        return Err(ScpiError::UndefinedHeader);
+    }
+
+    if mnemonic(first, "SYST", "SYSTEM")
+        && query
+        && rest.split_first().is_some_and(|(node, tail)| {
+            mnemonic(node, "ERR", "ERROR") && optional_nodes(tail, &[("NEXT", "NEXT")])
+        })
+    {
+        return Ok(ScpiCommand::SystemError);
+    }
+
+    Err(ScpiError::UndefinedHeader)
+}
+
+/// SCPI allows exactly the short or the long form of a mnemonic.
+fn mnemonic(node: &str, short: &str, long: &str) -> bool {
+    node.eq_ignore_ascii_case(short) || node.eq_ignore_ascii_case(long)
+}
+
+/// Every remaining node must be one of `optional`, in order.
+fn optional_nodes(nodes: &[&str], optional: &[(&str, &str)]) -> bool {
+    let mut allowed = optional.iter();
+    nodes.iter().all(|node| {
+        allowed
+            .by_ref()
+            .any(|(short, long)| mnemonic(node, short, long))
+    })
+}
+
+fn parse_bool(params: &str) -> Result<bool, ScpiError> {
+    if params.is_empty() {
+        return Err(ScpiError::MissingParameter);
+    }
+    if params.eq_ignore_ascii_case("ON") || params == "1" {
+        Ok(true)
+    } else if params.eq_ignore_ascii_case("OFF") || params == "0" {
+        Ok(false)
+    } else {
+        Err(ScpiError::DataTypeError)
+    }
+}
+
+/// Parses `<NRf>[<unit>]`, `MIN`, `MAX` or `DEF` into milli-units of `unit`.
+fn parse_level(params: &str, unit: u8) -> Result<ScpiLevel, ScpiError> {
+    if params.is_empty() {
+        return Err(ScpiError::MissingParameter);
+    }
+    if mnemonic(params, "MIN", "MINIMUM") {
+        return Ok(ScpiLevel::Min);
+    }
+    if mnemonic(params, "MAX", "MAXIMUM") {
+        return Ok(ScpiLevel::Max);
+    }
+    if mnemonic(params, "DEF", "DEFAULT") {
+        return Ok(ScpiLevel::Default);
+    }
+
+    let bytes = params.as_bytes();
+    let number_end = bytes
+        .iter()
+        .position(|b| !(b.is_ascii_digit() || matches!(b, b'.' | b'+' | b'-' | b'e' | b'E')))
+        .unwrap_or(bytes.len());
+    let (number, suffix) = params.split_at(number_end);
+    let suffix = suffix.trim();
+    let suffix_exp = if suffix.is_empty() || suffix.as_bytes().eq_ignore_ascii_case(&[unit]) {
+        0
+    } else if suffix.len() == 2
+        && suffix.as_bytes()[0].eq_ignore_ascii_case(&b'M')
+        && suffix.as_bytes()[1].eq_ignore_ascii_case(&unit)
+    {
+        -3
+    } else {
+        return Err(ScpiError::DataTypeError);
+    };
+
+    let milli = parse_decimal_scaled(number, 3 + suffix_exp).ok_or(ScpiError::DataTypeError)?;
+    if milli < 0 {
+        return Err(ScpiError::DataOutOfRange);
+    }
+    u32::try_from(milli)
+        .map(ScpiLevel::Milli)
+        .map_err(|_| ScpiError::DataOutOfRange)
+}
+
+/// Parses `[+|-]digits[.digits][E[+|-]digits]` and returns it times `10^scale`,
+/// rounded half away from zero.
+fn parse_decimal_scaled(text: &str, scale: i32) -> Option<i64> {
+    let (mantissa, exponent) = match text.find(['e', 'E']) {
+        Some(split) => (&text[..split], text[split + 1..].parse::<i32>().ok()?),
+        None => (text, 0),
+    };
+    let (negative, mantissa) = match mantissa.as_bytes().first()? {
+        b'-' => (true, &mantissa[1..]),
+        b'+' => (false, &mantissa[1..]),
+        _ => (false, mantissa),
+    };
+    let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if int_part.is_empty() && frac_part.is_empty() {
+        return None;
+    }
+    let mut digits: i64 = 0;
+    let mut significant = 0;
+    for b in int_part.bytes().chain(frac_part.bytes()) {
+        if !b.is_ascii_digit() {
+            return None;
+        }
+        significant += 1;
+        if significant > 15 {
+            return None;
+        }
+        digits = digits * 10 + i64::from(b - b'0');
+    }
+
+    let power = scale + exponent - frac_part.len() as i32;
+    let value = if power >= 0 {
+        if power > 12 {
+            return None;
+        }
+        digits.checked_mul(10_i64.pow(power as u32))?
+    } else if power < -15 {
+        0
+    } else {
+        let divisor = 10_i64.pow((-power) as u32);
+        (digits + divisor / 2) / divisor
+    };
+    Some(if negative { -value } else { value })
+}
+
+/// Resolves a `VOLT` level against the manual TPS output range.
+pub fn resolve_voltage_mv(level: ScpiLevel) -> Result<u16, ScpiError> {
+    match level {
+        ScpiLevel::Min => Ok(MANUAL_MIN_VOLTAGE_MV),
+        ScpiLevel::Max => Ok(MANUAL_MAX_VOLTAGE_MV),
+        ScpiLevel::Default => Ok(MANUAL_DEFAULT_VOLTAGE_MV),
+        ScpiLevel::Milli(mv) => u16::try_from(mv)
+            .ok()
+            .filter(|mv| (MANUAL_MIN_VOLTAGE_MV..=MANUAL_MAX_VOLTAGE_MV).contains(mv))
+            .ok_or(ScpiError::DataOutOfRange),
+    }
+}
+
+/// Resolves a `CURR` level against the TPS current-limit range.
+pub fn resolve_current_ma(level: ScpiLevel) -> Result<u16, ScpiError> {
+    match level {
+        ScpiLevel::Min => Ok(SCPI_MIN_CURRENT_MA),
+        ScpiLevel::Max => Ok(TPS_MAX_CURRENT_MA),
+        ScpiLevel::Default => Ok(MANUAL_DEFAULT_CURRENT_MA),
+        ScpiLevel::Milli(ma) => u16::try_from(ma)
+            .ok()
+            .filter(|ma| (SCPI_MIN_CURRENT_MA..=TPS_MAX_CURRENT_MA).contains(ma))
+            .ok_or(ScpiError::DataOutOfRange),
+    }
+}
+
+/// Writes a milli-unit reading as an NR2 value with three decimals.
+pub fn write_milli<W: Write>(out: &mut W, milli: u32) -> core::fmt::Result {
+    write!(out, "{}.{:03}", milli / 1_000, milli % 1_000)
+}
+
+/// SCPI "not a number", returned when a reading is unavailable.
+pub const SCPI_NAN: &str = "9.91E+37";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_common_and_short_long_headers() {
+        assert_eq!(parse_command("*idn?"), Ok(ScpiCommand::Identify));
+        assert_eq!(parse_command("*RST"), Ok(ScpiCommand::Reset));
+        assert_eq!(parse_command("*RST?"), Err(ScpiError::UndefinedHeader));
+        assert_eq!(parse_command("*sav"), Ok(ScpiCommand::Save));
+        assert_eq!(parse_command("*SAV 0"), Ok(ScpiCommand::Save));
+        assert_eq!(
+            parse_command("SOURce:VOLTage:LEVel:IMMediate 12.5"),
+            Ok(ScpiCommand::SetVoltage(ScpiLevel::Milli(12_500)))
+        );
+        assert_eq!(
+            parse_command(":volt 5000mV"),
+            Ok(ScpiCommand::SetVoltage(ScpiLevel::Milli(5_000)))
+        );
+        assert_eq!(
+            parse_command("CURR 0.5A"),
+            Ok(ScpiCommand::SetCurrent(ScpiLevel::Milli(500)))
+        );
+        assert_eq!(
+            parse_command("CURR 1.5E3 MA"),
+            Ok(ScpiCommand::SetCurrent(ScpiLevel::Milli(1_500)))
+        );
+        assert_eq!(
+            parse_command("CURR MAX"),
+            Ok(ScpiCommand::SetCurrent(ScpiLevel::Max))
+        );
+        assert_eq!(parse_command("VOLT?"), Ok(ScpiCommand::QueryVoltage));
+        assert_eq!(
+            parse_command("OUTP:STAT ON"),
+            Ok(ScpiCommand::SetOutput(true))
+        );
+        assert_eq!(parse_command("output 0"), Ok(ScpiCommand::SetOutput(false)));
+        assert_eq!(parse_command("OUTP?"), Ok(ScpiCommand::QueryOutput));
+        assert_eq!(parse_command("MEAS:VOLT?"), Ok(ScpiCommand::MeasureVoltage));
+        assert_eq!(
+            parse_command("MEASure:SCALar:CURRent:DC?"),
+            Ok(ScpiCommand::MeasureCurrent)
+        );
+        assert_eq!(parse_command("MEAS:POW?"), Ok(ScpiCommand::MeasurePower));
+        assert_eq!(
+            parse_command("SYST:ERR:NEXT?"),
+            Ok(ScpiCommand::SystemError)
+        );
+    }
+
+    #[test]
+    fn rejects_bad_headers_and_parameters() {
+        assert_eq!(parse_command("VOLTA 5"), Err(ScpiError::UndefinedHeader));
+        assert_eq!(
+            parse_command("VOLT:IMM:LEV 5"),
+            Err(ScpiError::UndefinedHeader)
+        );
+        assert_eq!(parse_command("MEAS:VOLT"), Err(ScpiError::UndefinedHeader));
+        assert_eq!(parse_command("VOLT"), Err(ScpiError::MissingParameter));
+        assert_eq!(parse_command("VOLT 5A"), Err(ScpiError::DataTypeError));
+        assert_eq!(parse_command("VOLT -1"), Err(ScpiError::DataOutOfRange));
+        assert_eq!(parse_command("OUTP MAYBE"), Err(ScpiError::DataTypeError));
+        assert_eq!(parse_command("*SAV 1"), Err(ScpiError::DataOutOfRange));
+        assert_eq!(
+            parse_command("*IDN? 1"),
+            Err(ScpiError::ParameterNotAllowed)
+        );
+    }
+
+    #[test]
+    fn resolves_levels_against_the_manual_tps_range() {
+        assert_eq!(resolve_voltage_mv(ScpiLevel::Milli(3_300)), Ok(3_300));
+        assert_eq!(resolve_voltage_mv(ScpiLevel::Max), Ok(21_000));
+        assert_eq!(
+            resolve_voltage_mv(ScpiLevel::Milli(24_000)),
+            Err(ScpiError::DataOutOfRange)
+        );
+        assert_eq!(resolve_current_ma(ScpiLevel::Min), Ok(50));
+        assert_eq!(
+            resolve_current_ma(ScpiLevel::Milli(0)),
+            Err(ScpiError::DataOutOfRange)
+        );
+    }
+
+    #[test]
+    fn error_queue_reports_oldest_first_and_overflows() {
+        let mut queue = ScpiErrorQueue::new();
+        let mut out = heapless::String::<64>::new();
+        queue.write_next(&mut out).unwrap();
+        assert_eq!(out.as_str(), "0,\"No error\"");
+
+        for _ in 0..SCPI_ERROR_QUEUE_LEN + 2 {
+            queue.push(ScpiError::UndefinedHeader);
+        }
+        out.clear();
+        queue.write_next(&mut out).unwrap();
+        assert_eq!(out.as_str(), "-113,\"Undefined header\"");
+        let mut last = None;
+        while let Some(error) = queue.pop() {
+            last = Some(error);
+        }
+        assert_eq!(last, Some(ScpiError::QueueOverflow));
+    }
+
+    #[test]
+    fn splits_programs_and_formats_readings() {
+        let commands: heapless::Vec<&str, 4> =
+            split_program("VOLT 5; OUTP ON;;MEAS:VOLT?").collect();
+        assert_eq!(commands.as_slice(), ["VOLT 5", "OUTP ON", "MEAS:VOLT?"]);
+
+        let mut out = heapless::String::<16>::new();
+        write_milli(&mut out, 5_042).unwrap();
+        assert_eq!(out.as_str(), "5.042");
+    }
+}
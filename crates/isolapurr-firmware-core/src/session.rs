@@ -0,0 +1,312 @@
+//! Per-port charging sessions listed by `sessions.list` and `/api/v1/sessions`
+//! (see `docs/specs/s5r2h-charge-sessions/SPEC.md`).
+
+use heapless::Vec;
+use isolapurr_api::ports::PortId;
+use isolapurr_api::power::{ActiveProtocol, ActiveProtocolSet};
+use isolapurr_api::sessions::ChargeSession;
+
+/// Finished sessions kept in RAM; the oldest is dropped once it is full.
+pub const SESSION_LOG_LEN: usize = 16;
+/// Finished sessions plus one open session per port.
+pub const SESSION_LIST_MAX: usize = SESSION_LOG_LEN + 2;
+/// Current that counts as a load when the port reports no attach of its own.
+pub const SESSION_LOAD_THRESHOLD_MA: u32 = 50;
+/// A session survives shorter gaps in the load, such as a phone renegotiating
+/// or pausing its charger.
+pub const SESSION_DETACH_GRACE_MS: u64 = 5_000;
+
+const MW_MS_PER_MWH: u64 = 3_600_000;
+
+const EMPTY_SESSION: ChargeSession = ChargeSession {
+    id: 0,
+    port: PortId::PortA,
+    start_uptime_ms: 0,
+    end_uptime_ms: None,
+    energy_mwh: 0,
+    peak_power_mw: 0,
+    avg_power_mw: 0,
+    max_voltage_mv: 0,
+    protocols: ActiveProtocolSet::empty(),
+};
+
+/// One telemetry sample of one port.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SessionSample {
+    /// The port's own attach signal: USB-C passes `usb_c_present`, which covers
+    /// `cc_attached` and an active protocol. USB-A has none and passes `false`.
+    pub attached: bool,
+    pub voltage_mv: Option<u32>,
+    pub current_ma: Option<u32>,
+    pub power_mw: Option<u32>,
+    pub protocol: Option<ActiveProtocol>,
+}
+
+impl SessionSample {
+    pub fn loaded(&self) -> bool {
+        self.attached
+            || self
+                .current_ma
+                .is_some_and(|current_ma| current_ma >= SESSION_LOAD_THRESHOLD_MA)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct OpenSession {
+    session: ChargeSession,
+    last_sample_ms: u64,
+    last_loaded_ms: u64,
+    energy_mw_ms: u64,
+}
+
+impl OpenSession {
+    fn record(&mut self, now_ms: u64, sample: &SessionSample) {
+        let session = &mut self.session;
+        if let Some(power_mw) = sample.power_mw {
+            self.energy_mw_ms = self.energy_mw_ms.saturating_add(
+                u64::from(power_mw).saturating_mul(now_ms.saturating_sub(self.last_sample_ms)),
+            );
+            session.peak_power_mw = session.peak_power_mw.max(power_mw);
+        }
+        if let Some(voltage_mv) = sample.voltage_mv {
+            session.max_voltage_mv = session.max_voltage_mv.max(voltage_mv);
+        }
+        if let Some(protocol) = sample.protocol {
+            session.protocols.insert(protocol);
+        }
+        self.last_sample_ms = now_ms;
+        if sample.loaded() {
+            self.last_loaded_ms = now_ms;
+        }
+        session.energy_mwh = (self.energy_mw_ms / MW_MS_PER_MWH).min(u64::from(u32::MAX)) as u32;
+        let duration_ms = self.last_loaded_ms.saturating_sub(session.start_uptime_ms);
+        session.avg_power_mw = self
+            .energy_mw_ms
+            .checked_div(duration_ms)
+            .map_or(0, |avg_mw| avg_mw.min(u64::from(u32::MAX)) as u32);
+    }
+}
+
+/// Opens a session when a load appears on a port and closes it once the load
+/// has been gone for [`SESSION_DETACH_GRACE_MS`].
+pub struct SessionRecorder {
+    open: [Option<OpenSession>; 2],
+    closed: [ChargeSession; SESSION_LOG_LEN],
+    closed_len: usize,
+    next_closed: usize,
+    next_id: u32,
+}
+
+impl SessionRecorder {
+    pub const fn new() -> Self {
+        Self {
+            open: [None; 2],
+            closed: [EMPTY_SESSION; SESSION_LOG_LEN],
+            closed_len: 0,
+            next_closed: 0,
+            next_id: 1,
+        }
+    }
+
+    const fn slot(port: PortId) -> usize {
+        match port {
+            PortId::PortA => 0,
+            PortId::PortC => 1,
+        }
+    }
+
+    /// The session currently open on `port`, with totals up to its last sample.
+    pub fn open(&self, port: PortId) -> Option<ChargeSession> {
+        self.open[Self::slot(port)].map(|open| open.session)
+    }
+
+    /// Feeds one telemetry sample. Returns the session it closed, if any.
+    pub fn update(
+        &mut self,
+        port: PortId,
+        now_ms: u64,
+        sample: &SessionSample,
+    ) -> Option<ChargeSession> {
+        let slot = Self::slot(port);
+        let Some(open) = self.open[slot].as_mut() else {
+            if sample.loaded() {
+                let mut open = OpenSession {
+                    session: ChargeSession {
+                        id: self.next_id,
+                        port,
+                        start_uptime_ms: now_ms,
+                        ..EMPTY_SESSION
+                    },
+                    last_sample_ms: now_ms,
+                    last_loaded_ms: now_ms,
+                    energy_mw_ms: 0,
+                };
+                open.record(now_ms, sample);
+                self.open[slot] = Some(open);
+                self.next_id = self.next_id.wrapping_add(1).max(1);
+            }
+            return None;
+        };
+
+        open.record(now_ms, sample);
+        if now_ms.saturating_sub(open.last_loaded_ms) < SESSION_DETACH_GRACE_MS {
+            return None;
+        }
+        let mut session = open.session;
+        session.end_uptime_ms = Some(open.last_loaded_ms);
+        self.open[slot] = None;
+        self.closed[self.next_closed] = session;
+        self.next_closed = (self.next_closed + 1) % SESSION_LOG_LEN;
+        self.closed_len = (self.closed_len + 1).min(SESSION_LOG_LEN);
+        Some(session)
+    }
+
+    /// Every held session, finished and open, ordered by id.
+    pub fn list(&self) -> Vec<ChargeSession, SESSION_LIST_MAX> {
+        let oldest = (self.next_closed + SESSION_LOG_LEN - self.closed_len) % SESSION_LOG_LEN;
+        let closed =
+            (0..self.closed_len).map(|index| self.closed[(oldest + index) % SESSION_LOG_LEN]);
+        let open = self.open.iter().flatten().map(|open| open.session);
+        let mut list: Vec<ChargeSession, SESSION_LIST_MAX> = closed.chain(open).collect();
+        list.sort_unstable_by_key(|session| session.id);
+        list
+    }
+}
+
+impl Default for SessionRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SESSION_DETACH_GRACE_MS, SESSION_LOG_LEN, SessionRecorder, SessionSample};
+    use isolapurr_api::ports::PortId;
+    use isolapurr_api::power::ActiveProtocol;
+
+    fn load(power_mw: u32, voltage_mv: u32) -> SessionSample {
+        SessionSample {
+            attached: false,
+            voltage_mv: Some(voltage_mv),
+            current_ma: Some(power_mw * 1_000 / voltage_mv),
+            power_mw: Some(power_mw),
+            protocol: None,
+        }
+    }
+
+    fn idle() -> SessionSample {
+        SessionSample {
+            attached: false,
+            voltage_mv: Some(5_000),
+            current_ma: Some(0),
+            power_mw: Some(0),
+            protocol: None,
+        }
+    }
+
+    #[test]
+    fn session_records_totals_and_closes_after_grace() {
+        let mut recorder = SessionRecorder::new();
+        assert_eq!(recorder.update(PortId::PortA, 0, &idle()), None);
+        assert_eq!(recorder.open(PortId::PortA), None);
+
+        // One hour at 10 W with a 20 W peak.
+        for second in 1..=3_600u64 {
+            let power_mw = if second == 600 { 20_000 } else { 10_000 };
+            assert_eq!(
+                recorder.update(PortId::PortA, second * 1_000, &load(power_mw, 5_100)),
+                None
+            );
+        }
+        let open = recorder.open(PortId::PortA).expect("session open");
+        assert_eq!(open.id, 1);
+        assert_eq!(open.start_uptime_ms, 1_000);
+        assert_eq!(open.end_uptime_ms, None);
+        assert_eq!(open.peak_power_mw, 20_000);
+
+        // A dip shorter than the grace period keeps the session.
+        assert_eq!(recorder.update(PortId::PortA, 3_601_000, &idle()), None);
+        assert_eq!(
+            recorder.update(PortId::PortA, 3_602_000, &load(10_000, 5_100)),
+            None
+        );
+
+        let mut closed = None;
+        for second in 3_603..3_603 + SESSION_DETACH_GRACE_MS / 1_000 + 1 {
+            closed = closed.or(recorder.update(PortId::PortA, second * 1_000, &idle()));
+        }
+        let closed = closed.expect("session closed after the grace period");
+        assert_eq!(closed.end_uptime_ms, Some(3_602_000));
+        // 3600 s at 10 W, counting the 20 W peak and the dip: 36_010 J.
+        assert_eq!(closed.energy_mwh, 10_002);
+        assert_eq!(closed.avg_power_mw, 10_000);
+        assert_eq!(closed.max_voltage_mv, 5_100);
+        assert!(closed.protocols.is_empty());
+        assert_eq!(recorder.open(PortId::PortA), None);
+        assert_eq!(recorder.list().as_slice(), &[closed]);
+    }
+
+    #[test]
+    fn usb_c_attach_opens_a_session_and_collects_protocols() {
+        let mut recorder = SessionRecorder::new();
+        let attached = SessionSample {
+            attached: true,
+            voltage_mv: Some(5_000),
+            current_ma: Some(0),
+            power_mw: Some(0),
+            protocol: Some(ActiveProtocol::Bc12),
+        };
+        recorder.update(PortId::PortC, 1_000, &attached);
+        recorder.update(
+            PortId::PortC,
+            2_000,
+            &SessionSample {
+                protocol: Some(ActiveProtocol::Pd),
+                voltage_mv: Some(9_050),
+                ..attached
+            },
+        );
+        let open = recorder
+            .open(PortId::PortC)
+            .expect("attach opens a session");
+        assert_eq!(open.port, PortId::PortC);
+        assert!(open.protocols.contains(ActiveProtocol::Bc12));
+        assert!(open.protocols.contains(ActiveProtocol::Pd));
+        assert!(!open.protocols.contains(ActiveProtocol::Pps));
+        assert_eq!(open.max_voltage_mv, 9_050);
+
+        let detached = SessionSample {
+            attached: false,
+            protocol: None,
+            ..attached
+        };
+        assert_eq!(recorder.update(PortId::PortC, 6_999, &detached), None);
+        let closed = recorder
+            .update(PortId::PortC, 7_000, &detached)
+            .expect("detach closes the session");
+        assert_eq!(closed.end_uptime_ms, Some(2_000));
+    }
+
+    #[test]
+    fn list_keeps_the_newest_sessions_in_id_order() {
+        let mut recorder = SessionRecorder::new();
+        // USB-C stays open while USB-A cycles through more sessions than fit.
+        recorder.update(PortId::PortC, 0, &load(5_000, 5_000));
+        let mut now_ms = 0;
+        for _ in 0..SESSION_LOG_LEN + 3 {
+            now_ms += 1_000;
+            recorder.update(PortId::PortA, now_ms, &load(5_000, 5_000));
+            now_ms += SESSION_DETACH_GRACE_MS;
+            assert!(recorder.update(PortId::PortA, now_ms, &idle()).is_some());
+        }
+        let list = recorder.list();
+        let ids: heapless::Vec<u32, 32> = list.iter().map(|session| session.id).collect();
+        assert_eq!(ids.len(), SESSION_LOG_LEN + 1);
+        assert_eq!(ids[0], 1);
+        assert_eq!(list[0].end_uptime_ms, None);
+        assert_eq!(ids[1], 5);
+        assert_eq!(*ids.last().unwrap(), SESSION_LOG_LEN as u32 + 4);
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
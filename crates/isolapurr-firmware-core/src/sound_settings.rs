@@ -0,0 +1,494 @@
+pub const SOUND_VOLUME_MAX_PCT: u8 = 100;
+pub const MINUTES_PER_DAY: u16 = 24 * 60;
+pub const CUSTOM_TONE_MAX_STEPS: usize = 8;
+pub const CUSTOM_TONE_MIN_FREQ_HZ: u16 = 200;
+pub const CUSTOM_TONE_MAX_FREQ_HZ: u16 = 8_000;
+pub const CUSTOM_TONE_MIN_STEP_MS: u16 = 10;
+pub const CUSTOM_TONE_MAX_STEP_MS: u16 = 2_000;
+pub const SOUND_SLOT_COUNT: usize = 10;
+
+/// Event classes that can be enabled or muted independently.
+///
+/// `Safety` is never muted, never scaled, and ignores quiet hours.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SoundClass {
+    Boot,
+    Menu,
+    Action,
+    Warning,
+    Safety,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SoundClassFlags {
+    pub boot: bool,
+    pub menu: bool,
+    pub action: bool,
+    pub warning: bool,
+}
+
+impl SoundClassFlags {
+    pub const fn all() -> Self {
+        Self {
+            boot: true,
+            menu: true,
+            action: true,
+            warning: true,
+        }
+    }
+
+    pub const fn enabled(self, class: SoundClass) -> bool {
+        match class {
+            SoundClass::Boot => self.boot,
+            SoundClass::Menu => self.menu,
+            SoundClass::Action => self.action,
+            SoundClass::Warning => self.warning,
+            SoundClass::Safety => true,
+        }
+    }
+}
+
+/// Daily quiet window in local minutes of day; `start == end` is an empty window.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct QuietHours {
+    pub enabled: bool,
+    pub start_minute: u16,
+    pub end_minute: u16,
+}
+
+impl QuietHours {
+    pub const fn disabled() -> Self {
+        Self {
+            enabled: false,
+            start_minute: 22 * 60,
+            end_minute: 7 * 60,
+        }
+    }
+
+    pub const fn contains(self, minute_of_day: u16) -> bool {
+        if !self.enabled || self.start_minute == self.end_minute {
+            return false;
+        }
+        if self.start_minute < self.end_minute {
+            minute_of_day >= self.start_minute && minute_of_day < self.end_minute
+        } else {
+            minute_of_day >= self.start_minute || minute_of_day < self.end_minute
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SoundSettingsError {
+    VolumeOutOfRange,
+    QuietMinuteOutOfRange,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SoundSettings {
+    pub muted: bool,
+    pub classes: SoundClassFlags,
+    pub quiet_hours: QuietHours,
+    pub volume_pct: u8,
+}
+
+impl SoundSettings {
+    pub const fn defaults() -> Self {
+        Self {
+            muted: false,
+            classes: SoundClassFlags::all(),
+            quiet_hours: QuietHours::disabled(),
+            volume_pct: SOUND_VOLUME_MAX_PCT,
+        }
+    }
+
+    pub fn validated(self) -> Result<Self, SoundSettingsError> {
+        if self.volume_pct > SOUND_VOLUME_MAX_PCT {
+            return Err(SoundSettingsError::VolumeOutOfRange);
+        }
+        if self.quiet_hours.start_minute >= MINUTES_PER_DAY
+            || self.quiet_hours.end_minute >= MINUTES_PER_DAY
+        {
+            return Err(SoundSettingsError::QuietMinuteOutOfRange);
+        }
+        Ok(self)
+    }
+
+    /// Whether a sound of `class` may start; quiet hours apply only once the
+    /// local minute of day is known.
+    pub fn allows(self, class: SoundClass, minute_of_day: Option<u16>) -> bool {
+        if class == SoundClass::Safety {
+            return true;
+        }
+        if self.muted || self.volume_pct == 0 || !self.classes.enabled(class) {
+            return false;
+        }
+        !minute_of_day.is_some_and(|minute| self.quiet_hours.contains(minute))
+    }
+
+    /// Maps a pattern duty through the volume setting. Non-zero inputs never
+    /// scale below 1% so a low volume stays audible instead of going silent.
+    pub fn scaled_duty_pct(self, class: SoundClass, duty_pct: u8) -> u8 {
+        if class == SoundClass::Safety || duty_pct == 0 {
+            return duty_pct;
+        }
+        if self.volume_pct == 0 {
+            return 0;
+        }
+        let scaled = (u16::from(duty_pct) * u16::from(self.volume_pct)
+            + u16::from(SOUND_VOLUME_MAX_PCT / 2))
+            / u16::from(SOUND_VOLUME_MAX_PCT);
+        scaled.clamp(1, u16::from(duty_pct)) as u8
+    }
+}
+
+/// User-replaceable pattern slots; each maps to one built-in prompt sound.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SoundSlot {
+    BootOk,
+    BootWarn,
+    BootFail,
+    Warning,
+    Error,
+    ActionOk,
+    ActionFail,
+    MenuNavigate,
+    MenuConfirm,
+    Identify,
+}
+
+impl SoundSlot {
+    pub const ALL: [SoundSlot; SOUND_SLOT_COUNT] = [
+        SoundSlot::BootOk,
+        SoundSlot::BootWarn,
+        SoundSlot::BootFail,
+        SoundSlot::Warning,
+        SoundSlot::Error,
+        SoundSlot::ActionOk,
+        SoundSlot::ActionFail,
+        SoundSlot::MenuNavigate,
+        SoundSlot::MenuConfirm,
+        SoundSlot::Identify,
+    ];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            SoundSlot::BootOk => "boot_ok",
+            SoundSlot::BootWarn => "boot_warn",
+            SoundSlot::BootFail => "boot_fail",
+            SoundSlot::Warning => "warning",
+            SoundSlot::Error => "error",
+            SoundSlot::ActionOk => "action_ok",
+            SoundSlot::ActionFail => "action_fail",
+            SoundSlot::MenuNavigate => "menu_navigate",
+            SoundSlot::MenuConfirm => "menu_confirm",
+            SoundSlot::Identify => "identify",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|slot| slot.as_str() == value)
+    }
+
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    pub const fn class(self) -> SoundClass {
+        match self {
+            SoundSlot::BootOk | SoundSlot::BootWarn | SoundSlot::BootFail => SoundClass::Boot,
+            SoundSlot::Warning | SoundSlot::Error => SoundClass::Warning,
+            SoundSlot::ActionOk | SoundSlot::ActionFail | SoundSlot::Identify => SoundClass::Action,
+            SoundSlot::MenuNavigate | SoundSlot::MenuConfirm => SoundClass::Menu,
+        }
+    }
+}
+
+/// One custom step; `freq_hz == 0` is a silence step.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CustomToneStep {
+    pub freq_hz: u16,
+    pub duration_ms: u16,
+}
+
+impl CustomToneStep {
+    pub const fn valid(self) -> bool {
+        (self.freq_hz == 0
+            || (self.freq_hz >= CUSTOM_TONE_MIN_FREQ_HZ && self.freq_hz <= CUSTOM_TONE_MAX_FREQ_HZ))
+            && self.duration_ms >= CUSTOM_TONE_MIN_STEP_MS
+            && self.duration_ms <= CUSTOM_TONE_MAX_STEP_MS
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CustomSoundPattern {
+    steps: [CustomToneStep; CUSTOM_TONE_MAX_STEPS],
+    len: u8,
+}
+
+impl CustomSoundPattern {
+    pub fn from_steps(steps: &[CustomToneStep]) -> Option<Self> {
+        if steps.is_empty() || steps.len() > CUSTOM_TONE_MAX_STEPS {
+            return None;
+        }
+        if !steps.iter().all(|step| step.valid()) {
+            return None;
+        }
+        let mut pattern = Self::default();
+        pattern.steps[..steps.len()].copy_from_slice(steps);
+        pattern.len = steps.len() as u8;
+        Some(pattern)
+    }
+
+    pub fn steps(&self) -> &[CustomToneStep] {
+        &self.steps[..usize::from(self.len)]
+    }
+
+    /// Parses the wire form `freq:ms,freq:ms,...` (for example `2700:40,0:60,3200:40`).
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut steps = [CustomToneStep::default(); CUSTOM_TONE_MAX_STEPS];
+        let mut len = 0usize;
+        for item in text.split(',') {
+            let item = item.trim();
+            if item.is_empty() {
+                return None;
+            }
+            if len == CUSTOM_TONE_MAX_STEPS {
+                return None;
+            }
+            let (freq, duration) = item.split_once(':')?;
+            steps[len] = CustomToneStep {
+                freq_hz: freq.trim().parse().ok()?,
+                duration_ms: duration.trim().parse().ok()?,
+            };
+            len += 1;
+        }
+        Self::from_steps(&steps[..len])
+    }
+
+    /// Writes the same `freq:ms,...` form accepted by [`Self::parse`].
+    pub fn write_to<W: core::fmt::Write>(&self, out: &mut W) -> core::fmt::Result {
+        for (index, step) in self.steps().iter().enumerate() {
+            if index > 0 {
+                out.write_char(',')?;
+            }
+            write!(out, "{}:{}", step.freq_hz, step.duration_ms)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a local `HH:MM` time into a minute of day.
+pub fn parse_minute_of_day(text: &str) -> Option<u16> {
+    let (hours, minutes) = text.split_once(':')?;
+    if hours.len() != 2 || minutes.len() != 2 {
+        return None;
+    }
+    let hours: u16 = hours.parse().ok()?;
+    let minutes: u16 = minutes.parse().ok()?;
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+pub fn write_minute_of_day<W: core::fmt::Write>(
+    out: &mut W,
+    minute_of_day: u16,
+) -> core::fmt::Result {
+    write!(out, "{:02}:{:02}", minute_of_day / 60, minute_of_day % 60)
+}
+
+/// Local wall-clock anchor supplied by a host, since the board has no RTC.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LocalClockAnchor {
+    pub minute_of_day: u16,
+    pub uptime_ms: u64,
+}
+
+impl LocalClockAnchor {
+    pub fn new(minute_of_day: u16, uptime_ms: u64) -> Option<Self> {
+        (minute_of_day < MINUTES_PER_DAY).then_some(Self {
+            minute_of_day,
+            uptime_ms,
+        })
+    }
+
+    pub fn minute_of_day_at(self, uptime_ms: u64) -> u16 {
+        let elapsed_minutes = uptime_ms.saturating_sub(self.uptime_ms) / 60_000;
+        ((u64::from(self.minute_of_day) + elapsed_minutes) % u64::from(MINUTES_PER_DAY)) as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quiet_hours_wrap_across_midnight() {
+        let quiet = QuietHours {
+            enabled: true,
+            start_minute: 22 * 60,
+            end_minute: 7 * 60,
+        };
+        assert!(quiet.contains(23 * 60));
+        assert!(quiet.contains(0));
+        assert!(quiet.contains(7 * 60 - 1));
+        assert!(!quiet.contains(7 * 60));
+        assert!(!quiet.contains(12 * 60));
+    }
+
+    #[test]
+    fn quiet_hours_same_day_window_and_disabled_states() {
+        let quiet = QuietHours {
+            enabled: true,
+            start_minute: 12 * 60,
+            end_minute: 13 * 60,
+        };
+        assert!(quiet.contains(12 * 60 + 30));
+        assert!(!quiet.contains(11 * 60));
+        assert!(
+            !QuietHours {
+                end_minute: 12 * 60,
+                ..quiet
+            }
+            .contains(12 * 60)
+        );
+        assert!(
+            !QuietHours {
+                enabled: false,
+                ..quiet
+            }
+            .contains(12 * 60 + 30)
+        );
+    }
+
+    #[test]
+    fn safety_is_never_muted_or_scaled() {
+        let settings = SoundSettings {
+            muted: true,
+            volume_pct: 0,
+            ..SoundSettings::defaults()
+        };
+        assert!(settings.allows(SoundClass::Safety, Some(0)));
+        assert_eq!(settings.scaled_duty_pct(SoundClass::Safety, 6), 6);
+        assert!(!settings.allows(SoundClass::Action, None));
+    }
+
+    #[test]
+    fn class_flags_and_quiet_hours_gate_non_safety_sounds() {
+        let settings = SoundSettings {
+            classes: SoundClassFlags {
+                menu: false,
+                ..SoundClassFlags::all()
+            },
+            quiet_hours: QuietHours {
+                enabled: true,
+                start_minute: 22 * 60,
+                end_minute: 7 * 60,
+            },
+            ..SoundSettings::defaults()
+        };
+        assert!(!settings.allows(SoundClass::Menu, None));
+        assert!(settings.allows(SoundClass::Boot, None));
+        assert!(settings.allows(SoundClass::Boot, Some(12 * 60)));
+        assert!(!settings.allows(SoundClass::Boot, Some(23 * 60)));
+        assert!(settings.allows(SoundClass::Safety, Some(23 * 60)));
+    }
+
+    #[test]
+    fn volume_scales_duty_without_rounding_to_silence() {
+        let half = SoundSettings {
+            volume_pct: 50,
+            ..SoundSettings::defaults()
+        };
+        assert_eq!(half.scaled_duty_pct(SoundClass::Action, 12), 6);
+        assert_eq!(
+            SoundSettings {
+                volume_pct: 1,
+                ..half
+            }
+            .scaled_duty_pct(SoundClass::Action, 6),
+            1
+        );
+        assert_eq!(
+            SoundSettings::defaults().scaled_duty_pct(SoundClass::Boot, 18),
+            18
+        );
+    }
+
+    #[test]
+    fn settings_validation_rejects_out_of_range_values() {
+        let mut settings = SoundSettings::defaults();
+        settings.volume_pct = 101;
+        assert_eq!(
+            settings.validated(),
+            Err(SoundSettingsError::VolumeOutOfRange)
+        );
+        settings.volume_pct = 40;
+        settings.quiet_hours.end_minute = MINUTES_PER_DAY;
+        assert_eq!(
+            settings.validated(),
+            Err(SoundSettingsError::QuietMinuteOutOfRange)
+        );
+    }
+
+    #[test]
+    fn slot_names_round_trip() {
+        for (index, slot) in SoundSlot::ALL.into_iter().enumerate() {
+            assert_eq!(slot.index(), index);
+            assert_eq!(SoundSlot::parse(slot.as_str()), Some(slot));
+        }
+        assert_eq!(SoundSlot::parse("safety"), None);
+    }
+
+    #[test]
+    fn custom_pattern_parses_and_formats_wire_form() {
+        let pattern = CustomSoundPattern::parse("2700:40, 0:60,3200:40").expect("pattern");
+        assert_eq!(pattern.steps().len(), 3);
+        assert_eq!(
+            pattern.steps()[1],
+            CustomToneStep {
+                freq_hz: 0,
+                duration_ms: 60
+            }
+        );
+
+        let mut out = heapless::String::<64>::new();
+        pattern.write_to(&mut out).expect("format");
+        assert_eq!(out.as_str(), "2700:40,0:60,3200:40");
+    }
+
+    #[test]
+    fn custom_pattern_rejects_invalid_steps() {
+        assert!(CustomSoundPattern::parse("").is_none());
+        assert!(CustomSoundPattern::parse("2700").is_none());
+        assert!(CustomSoundPattern::parse("100:40").is_none());
+        assert!(CustomSoundPattern::parse("2700:5").is_none());
+        assert!(CustomSoundPattern::parse("2700:40,").is_none());
+        assert!(
+            CustomSoundPattern::parse(
+                "1000:10,1000:10,1000:10,1000:10,1000:10,1000:10,1000:10,1000:10,1000:10"
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn minute_of_day_parses_and_formats_hh_mm() {
+        assert_eq!(parse_minute_of_day("07:05"), Some(425));
+        assert_eq!(parse_minute_of_day("23:59"), Some(MINUTES_PER_DAY - 1));
+        assert_eq!(parse_minute_of_day("24:00"), None);
+        assert_eq!(parse_minute_of_day("7:05"), None);
+        assert_eq!(parse_minute_of_day("07:60"), None);
+
+        let mut out = heapless::String::<8>::new();
+        write_minute_of_day(&mut out, 425).expect("format");
+        assert_eq!(out.as_str(), "07:05");
+    }
+
+    #[test]
+    fn clock_anchor_advances_and_wraps() {
+        let anchor = LocalClockAnchor::new(23 * 60 + 59, 10_000).expect("anchor");
+        assert_eq!(anchor.minute_of_day_at(10_000), 23 * 60 + 59);
+        assert_eq!(anchor.minute_of_day_at(70_000), 0);
+        assert_eq!(anchor.minute_of_day_at(5_000), 23 * 60 + 59);
+        assert!(LocalClockAnchor::new(MINUTES_PER_DAY, 0).is_none());
+    }
+}
@@ -21,6 +21,13 @@ impl<T> Field<T> {
         matches!(self, Self::Err)
     }
 
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Self::Ok(v) => Some(v),
+            Self::Err => None,
+        }
+    }
+
     pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Field<U> {
         match self {
             Self::Ok(v) => Field::Ok(f(v)),
@@ -0,0 +1,262 @@
+//! Golden snapshots of every on-device screen, rendered with the same code
+//! the firmware runs. Regenerate after an intended visual change with:
+//!
+//! `UPDATE_GOLDENS=1 cargo test -p isolapurr-firmware-core --test display_snapshots`
+
+mod support;
+
+use isolapurr_firmware_core::display_render::{
+    DISPLAY_HEIGHT, DISPLAY_WIDTH, FRAME_PIXELS, FrameSurface, UiPalette, render_dashboard_base,
+    render_dashboard_dynamic, render_identify, render_message_card, render_settings_menu,
+    render_toast, render_toast_compact, trim_ascii_line,
+};
+use isolapurr_firmware_core::display_ui::{
+    NormalUiField, NormalUiPort, NormalUiPortBadge, NormalUiPortMode, NormalUiSnapshot,
+};
+use support::assert_display_golden;
+
+// Toast/card colors used by the firmware (`ui_runtime.inc`).
+const TOAST_OK_RAW: u16 = 0x1407;
+const TOAST_INFO_RAW: u16 = 0x1A7B;
+const TOAST_WARN_RAW: u16 = 0xC201;
+const TOAST_ERR_RAW: u16 = 0x98C3;
+
+const SETTINGS_PAGES: usize = 7;
+
+fn snapshot(name: &str, draw: impl FnOnce(&mut FrameSurface<'_>)) {
+    let mut frame = vec![0u16; FRAME_PIXELS];
+    draw(&mut FrameSurface::new(&mut frame));
+    assert_display_golden(name, DISPLAY_WIDTH, DISPLAY_HEIGHT, &frame);
+}
+
+fn port(
+    mode: NormalUiPortMode,
+    badge: NormalUiPortBadge,
+    voltage_uv: NormalUiField,
+    current_ua: NormalUiField,
+    power_uw: NormalUiField,
+) -> NormalUiPort {
+    NormalUiPort {
+        present: true,
+        mode,
+        badge,
+        voltage_uv,
+        current_ua,
+        power_uw,
+        charge_only: false,
+    }
+}
+
+fn absent(mode: NormalUiPortMode) -> NormalUiPort {
+    NormalUiPort {
+        present: false,
+        mode,
+        badge: NormalUiPortBadge::Off,
+        voltage_uv: NormalUiField::ok(0),
+        current_ua: NormalUiField::ok(0),
+        power_uw: NormalUiField::ok(0),
+        charge_only: false,
+    }
+}
+
+fn dashboard_fixtures() -> [(&'static str, NormalUiSnapshot); 6] {
+    let usb_a_charging = port(
+        NormalUiPortMode::UsbA,
+        NormalUiPortBadge::On,
+        NormalUiField::ok(5_124_000),
+        NormalUiField::ok(1_873_000),
+        NormalUiField::ok(9_597_000),
+    );
+    [
+        (
+            "pd_charging",
+            NormalUiSnapshot {
+                usb_a: usb_a_charging,
+                usb_c: port(
+                    NormalUiPortMode::Pd,
+                    NormalUiPortBadge::VoltageMv(20_000),
+                    NormalUiField::ok(19_982_000),
+                    NormalUiField::ok(3_215_000),
+                    NormalUiField::ok(64_242_000),
+                ),
+            },
+        ),
+        (
+            "pps_focus",
+            NormalUiSnapshot {
+                usb_a: usb_a_charging,
+                usb_c: port(
+                    NormalUiPortMode::Pps,
+                    NormalUiPortBadge::Focus,
+                    NormalUiField::ok(8_840_000),
+                    NormalUiField::ok(2_010_000),
+                    NormalUiField::ok(17_768_000),
+                ),
+            },
+        ),
+        (
+            "manual_dc",
+            NormalUiSnapshot {
+                usb_a: usb_a_charging,
+                usb_c: port(
+                    NormalUiPortMode::ManualVoltageMv(12_000),
+                    NormalUiPortBadge::VoltageMv(12_000),
+                    NormalUiField::ok(12_004_000),
+                    NormalUiField::ok(0),
+                    NormalUiField::ok(0),
+                ),
+            },
+        ),
+        (
+            "charge_only",
+            NormalUiSnapshot {
+                usb_a: NormalUiPort {
+                    charge_only: true,
+                    ..port(
+                        NormalUiPortMode::UsbA,
+                        NormalUiPortBadge::VoltageMv(5_000),
+                        NormalUiField::ok(5_098_000),
+                        NormalUiField::ok(912_000),
+                        NormalUiField::ok(4_649_000),
+                    )
+                },
+                usb_c: NormalUiPort {
+                    charge_only: true,
+                    ..port(
+                        NormalUiPortMode::Pd,
+                        NormalUiPortBadge::VoltageMv(9_000),
+                        NormalUiField::ok(9_012_000),
+                        NormalUiField::ok(2_104_000),
+                        NormalUiField::ok(18_961_000),
+                    )
+                },
+            },
+        ),
+        (
+            "idle",
+            NormalUiSnapshot {
+                usb_a: absent(NormalUiPortMode::UsbA),
+                usb_c: absent(NormalUiPortMode::Off),
+            },
+        ),
+        (
+            "faults",
+            NormalUiSnapshot {
+                usb_a: port(
+                    NormalUiPortMode::UsbA,
+                    NormalUiPortBadge::Unknown,
+                    NormalUiField::err(),
+                    NormalUiField::err(),
+                    NormalUiField::err(),
+                ),
+                usb_c: port(
+                    NormalUiPortMode::Dc,
+                    NormalUiPortBadge::On,
+                    NormalUiField::ok(28_100_000),
+                    NormalUiField::ok(5_000_000),
+                    NormalUiField::ok(1_000_000_000),
+                ),
+            },
+        ),
+    ]
+}
+
+fn palettes() -> [(&'static str, UiPalette); 2] {
+    [("light", UiPalette::LIGHT), ("dark", UiPalette::DARK)]
+}
+
+#[test]
+fn dashboard_states_match_goldens() {
+    for (theme, palette) in palettes() {
+        for (state, fixture) in dashboard_fixtures() {
+            snapshot(&format!("dashboard_{state}_{theme}"), |surface| {
+                render_dashboard_base(surface, &palette);
+                render_dashboard_dynamic(surface, &fixture, &palette);
+            });
+        }
+    }
+}
+
+#[test]
+fn settings_menu_pages_match_goldens() {
+    for (theme, palette) in palettes() {
+        for page in 0..SETTINGS_PAGES {
+            snapshot(&format!("settings_menu_{page}_{theme}"), |surface| {
+                render_settings_menu(surface, &palette, page);
+            });
+        }
+    }
+}
+
+#[test]
+fn toasts_match_goldens() {
+    let toast = [*b"USB-C PWRON  ", *b"DONE         ", *b"             "];
+    let busy = [*b"USB-A BUSY   ", *b"REJECT       ", *b"             "];
+    let compact = [
+        *b"WIFI CONNECTED      ",
+        *b"192.168.100.200     ",
+        *b"ISOLAPURR-A1B2C3    ",
+    ];
+    for (theme, palette) in palettes() {
+        snapshot(&format!("toast_ok_{theme}"), |surface| {
+            render_toast(surface, &palette, &toast, TOAST_OK_RAW);
+        });
+        snapshot(&format!("toast_warn_{theme}"), |surface| {
+            render_toast(surface, &palette, &busy, TOAST_WARN_RAW);
+        });
+        snapshot(&format!("toast_compact_{theme}"), |surface| {
+            render_toast_compact(surface, &palette, &compact, TOAST_INFO_RAW);
+        });
+    }
+}
+
+#[test]
+fn identify_border_phases_match_goldens() {
+    for (theme, palette) in palettes() {
+        for (phase, on) in [("on", true), ("off", false)] {
+            snapshot(&format!("identify_{phase}_{theme}"), |surface| {
+                render_identify(
+                    surface,
+                    &palette,
+                    "A1B2C3",
+                    "192.168.100.200",
+                    "ISOLAPURR-A1B2C3",
+                    on,
+                );
+            });
+        }
+    }
+}
+
+#[test]
+fn message_cards_match_goldens() {
+    let lines = [
+        *b"ROUTE MCU           ",
+        *b"MODE USB-C HOST     ",
+        *b"PRESS AGAIN TO SET  ",
+    ];
+    for (theme, palette) in palettes() {
+        snapshot(&format!("message_card_{theme}"), |surface| {
+            render_message_card(
+                surface,
+                &palette,
+                "RESET",
+                "SETTINGS CLEARED",
+                "REBOOTING",
+                "",
+                palette.accent(TOAST_ERR_RAW),
+            );
+        });
+        snapshot(&format!("lines_card_{theme}"), |surface| {
+            render_message_card(
+                surface,
+                &palette,
+                "USB-C ROUTE",
+                trim_ascii_line(&lines[0]),
+                trim_ascii_line(&lines[1]),
+                trim_ascii_line(&lines[2]),
+                palette.accent(TOAST_INFO_RAW),
+            );
+        });
+    }
+}
@@ -0,0 +1,727 @@
+//! Simulation tests for the USB-C PD coordinator. The SW2303 and TPS55288 are
+//! modelled on one shared bench: the SW2303 is powered from the TPS output,
+//! `CE_TPS` resets the converter, and faults (NACKs, a held-low PD bus,
+//! brown-outs, sink renegotiation) are injected between ticks.
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+use isolapurr_firmware_core::pd_coordinator::{
+    PdClock, PdCoordinator, PdEvent, PdTickInput, PdTickOutcome, PdTimings, Sw2303Ops, Tps55288Ops,
+};
+use isolapurr_firmware_core::pd_i2c::{PowerRequest, PowerSetpoint};
+use isolapurr_firmware_core::power_config::{
+    LightLoadMode, PowerConfig, Sw2303CapabilityReadback, Sw2303LineCompensation,
+    Sw2303PathControl, TpsCdcRise,
+};
+use isolapurr_firmware_core::sw2303_power_gate::Sw2303PowerGatePhase;
+
+// Same values as `ui_runtime.inc`.
+const TIMINGS: PdTimings = PdTimings {
+    off_hold_ms: 110,
+    por_release_ms: 100,
+    poll_ms: 20,
+    error_retry_ms: 100,
+    read_retries: 20,
+    read_retry_delay_ms: 5,
+    stable_reads_before_tps: 1,
+    stable_reads_before_profile: 50,
+    profile_retry_ms: 60_000,
+    cc_un_driving_ms: 1_200,
+    recovery_error_limit: 3,
+    recovery_min_interval_ms: 1_000,
+    recovery_tps_retries: 4,
+    discharge_settle_ms: 50,
+    release_settle_ms: 10,
+    ce_hold_ms: 5,
+    ce_poll_ms: 5,
+    tps_retry_delay_ms: 10,
+};
+
+const BOOT_SETPOINT: PowerSetpoint = PowerSetpoint {
+    output_enabled: true,
+    discharge_enabled: false,
+    v_out_mv: 5_000,
+    i_lim_ma: 6_350,
+};
+
+#[derive(Debug, Eq, PartialEq)]
+struct Nack;
+
+#[derive(Default)]
+struct Bench {
+    now_ms: u64,
+    // TPS55288.
+    chip_enabled: bool,
+    output: bool,
+    vout_mv: u16,
+    discharge: bool,
+    applied: Option<PowerSetpoint>,
+    light_load: Option<LightLoadMode>,
+    tps_nacks: u32,
+    tps_stuck: bool,
+    ce_pulses: u32,
+    // SW2303.
+    powered_since_ms: Option<u64>,
+    sink_request_mv: u16,
+    sink_request_ma: u16,
+    sw_nacks: u32,
+    holds_sda_low: bool,
+    profile_writes: u32,
+    cc_un_driving: u32,
+    bus_parked: bool,
+    bus_detached: bool,
+    violations: Vec<String>,
+}
+
+impl Bench {
+    fn tps_transfer(&mut self) -> Result<(), Nack> {
+        if !self.chip_enabled || self.tps_stuck {
+            return Err(Nack);
+        }
+        if self.tps_nacks > 0 {
+            self.tps_nacks -= 1;
+            return Err(Nack);
+        }
+        Ok(())
+    }
+
+    fn set_output(&mut self, enabled: bool) {
+        if enabled && !self.output {
+            self.powered_since_ms = Some(self.now_ms);
+        }
+        if !enabled {
+            self.powered_since_ms = None;
+            // An unpowered SW2303 lets go of the bus and forgets its profile.
+            self.holds_sda_low = false;
+        }
+        self.output = enabled;
+    }
+
+    fn sw_transfer(&mut self, what: &str) -> Result<(), Nack> {
+        if self.bus_parked || self.bus_detached {
+            self.violations.push(format!(
+                "{what} at {}ms with the PD bus parked",
+                self.now_ms
+            ));
+            return Err(Nack);
+        }
+        let Some(powered_since_ms) = self.powered_since_ms else {
+            return Err(Nack);
+        };
+        if self.now_ms < powered_since_ms + TIMINGS.por_release_ms {
+            self.violations
+                .push(format!("{what} at {}ms inside the SW2303 POR", self.now_ms));
+        }
+        if self.holds_sda_low {
+            return Err(Nack);
+        }
+        if self.sw_nacks > 0 {
+            self.sw_nacks -= 1;
+            return Err(Nack);
+        }
+        Ok(())
+    }
+
+    /// Converter input sags: the TPS55288 drops its output and NACKs until
+    /// the supply returns.
+    fn brown_out(&mut self) {
+        self.set_output(false);
+        self.tps_stuck = true;
+    }
+}
+
+#[derive(Clone)]
+struct Sim(Rc<RefCell<Bench>>);
+
+impl Sim {
+    fn new() -> Self {
+        Self(Rc::new(RefCell::new(Bench {
+            chip_enabled: true,
+            sink_request_mv: 5_000,
+            sink_request_ma: 3_000,
+            ..Bench::default()
+        })))
+    }
+
+    fn bench(&self) -> std::cell::RefMut<'_, Bench> {
+        self.0.borrow_mut()
+    }
+}
+
+impl Sw2303Ops for Sim {
+    type Error = Nack;
+
+    async fn read_power_request(&mut self) -> Result<PowerRequest, Nack> {
+        let mut bench = self.bench();
+        bench.sw_transfer("read")?;
+        Ok(PowerRequest {
+            fast_protocol: bench.sink_request_mv > 5_000,
+            fast_voltage: bench.sink_request_mv > 5_000,
+            negotiated_protocol: Some(sw2303::ProtocolType::PD),
+            cc_attached: true,
+            status_valid: true,
+            v_req_mv: bench.sink_request_mv,
+            i_req_ma: bench.sink_request_ma,
+            vbus_mv: bench.output.then_some(u32::from(bench.vout_mv)),
+        })
+    }
+
+    async fn apply_profile(
+        &mut self,
+        config: &PowerConfig,
+    ) -> Result<Sw2303CapabilityReadback, Nack> {
+        let mut bench = self.bench();
+        bench.sw_transfer("profile")?;
+        bench.profile_writes += 1;
+        Ok(readback_for(config))
+    }
+
+    async fn trigger_cc_un_driving(&mut self) -> Result<(), Nack> {
+        let mut bench = self.bench();
+        bench.sw_transfer("cc un-driving")?;
+        bench.cc_un_driving += 1;
+        // The sink re-attaches and starts over from a 5V contract.
+        bench.sink_request_mv = 5_000;
+        Ok(())
+    }
+
+    async fn set_path_control(&mut self, _control: Sw2303PathControl) -> Result<(), Nack> {
+        self.bench().sw_transfer("path control")
+    }
+
+    async fn apply_line_compensation(
+        &mut self,
+        _compensation: Sw2303LineCompensation,
+    ) -> Result<(), Nack> {
+        self.bench().sw_transfer("line compensation")
+    }
+
+    fn park_bus(&mut self) {
+        self.bench().bus_parked = true;
+    }
+
+    fn release_bus(&mut self) {
+        let mut bench = self.bench();
+        bench.bus_parked = false;
+        bench.bus_detached = true;
+    }
+
+    fn bus_lines_high(&self) -> bool {
+        let bench = self.0.borrow();
+        !bench.bus_parked && !bench.holds_sda_low
+    }
+
+    fn restore_bus(&mut self) {
+        self.bench().bus_detached = false;
+    }
+}
+
+impl Tps55288Ops for Sim {
+    type Error = Nack;
+
+    async fn apply_setpoint(&mut self, setpoint: PowerSetpoint) -> Result<(), Nack> {
+        let mut bench = self.bench();
+        if bench.applied == Some(setpoint) {
+            return Ok(());
+        }
+        bench.tps_transfer()?;
+        bench.vout_mv = setpoint.v_out_mv;
+        bench.discharge = setpoint.discharge_enabled;
+        bench.set_output(setpoint.output_enabled);
+        bench.applied = Some(setpoint);
+        Ok(())
+    }
+
+    async fn apply_light_load_mode(&mut self, mode: LightLoadMode) -> Result<(), Nack> {
+        let mut bench = self.bench();
+        if bench.light_load == Some(mode) {
+            return Ok(());
+        }
+        bench.tps_transfer()?;
+        bench.light_load = Some(mode);
+        Ok(())
+    }
+
+    async fn apply_cable_compensation(&mut self, _rise: TpsCdcRise) -> Result<(), Nack> {
+        self.bench().tps_transfer()
+    }
+
+    async fn stop_output_and_enable_discharge(&mut self) -> Result<(), Nack> {
+        let mut bench = self.bench();
+        bench.tps_transfer()?;
+        bench.set_output(false);
+        bench.discharge = true;
+        Ok(())
+    }
+
+    fn set_chip_enabled(&mut self, enabled: bool) {
+        let mut bench = self.bench();
+        if !enabled {
+            bench.ce_pulses += 1;
+            bench.set_output(false);
+            bench.vout_mv = 0;
+            bench.discharge = false;
+        }
+        bench.chip_enabled = enabled;
+    }
+
+    fn applied_setpoint(&self) -> Option<PowerSetpoint> {
+        self.0.borrow().applied
+    }
+
+    fn forget_setpoint(&mut self) {
+        self.bench().applied = None;
+    }
+
+    fn forget_light_load_mode(&mut self) {
+        self.bench().light_load = None;
+    }
+
+    fn setpoint_for_request(&self, request: PowerRequest) -> PowerSetpoint {
+        PowerSetpoint {
+            output_enabled: true,
+            discharge_enabled: false,
+            v_out_mv: request.v_req_mv / 20 * 20,
+            i_lim_ma: self.quantize_current_limit_ma(request.i_req_ma),
+        }
+    }
+
+    fn quantize_current_limit_ma(&self, ma: u16) -> u16 {
+        ma.saturating_sub(50).min(6_350) / 50 * 50
+    }
+}
+
+impl PdClock for Sim {
+    fn now_ms(&self) -> u64 {
+        self.0.borrow().now_ms
+    }
+
+    async fn delay_ms(&mut self, ms: u64) {
+        self.bench().now_ms += ms;
+    }
+}
+
+fn readback_for(config: &PowerConfig) -> Sw2303CapabilityReadback {
+    let cap = config.capability;
+    Sw2303CapabilityReadback {
+        available: true,
+        power_watts: Some(cap.power_watts),
+        pd_enabled: Some(cap.pd_enabled),
+        qc20_enabled: Some(cap.qc20_enabled),
+        qc30_enabled: Some(cap.qc30_enabled),
+        fcp_enabled: Some(cap.fcp_enabled),
+        afc_enabled: Some(cap.afc_enabled),
+        scp_enabled: Some(cap.scp_enabled),
+        pe20_enabled: Some(cap.pe20_enabled),
+        bc12_enabled: Some(cap.bc12_enabled),
+        sfcp_enabled: Some(cap.sfcp_enabled),
+        pps_enabled: Some(cap.pps_enabled),
+        fixed_9v: Some(cap.fixed_9v),
+        fixed_12v: Some(cap.fixed_12v),
+        fixed_15v: Some(cap.fixed_15v),
+        fixed_20v: Some(cap.fixed_20v),
+        pps3_limit_ma: Some(cap.current.pps3_limit_ma),
+        pd_pps_5a: Some(cap.current.pd_pps_5a),
+        type_c_broadcast_ma: Some(cap.current.type_c_broadcast_ma),
+        scp_limit_ma: Some(cap.current.scp_limit_ma),
+        fcp_afc_sfcp_limit_ma: Some(cap.current.fcp_afc_sfcp_limit_ma),
+        qc20_20v_enabled: Some(cap.fast_charge.qc20_20v_enabled),
+        qc30_20v_enabled: Some(cap.fast_charge.qc30_20v_enabled),
+        pe20_20v_enabled: Some(cap.fast_charge.pe20_20v_enabled),
+        non_pd_12v_enabled: Some(cap.fast_charge.non_pd_12v_enabled),
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+/// Owner-facing state the firmware feeds into every tick.
+struct Controls {
+    usb_c_power_on: bool,
+    runtime_output_enabled: bool,
+    config: PowerConfig,
+}
+
+struct Harness {
+    sim: Sim,
+    pd: PdCoordinator,
+    controls: Controls,
+    events: Vec<String>,
+}
+
+impl Harness {
+    /// Mirrors the firmware boot sequence: boot setpoint, then the SW2303 POR.
+    fn booted() -> Self {
+        let mut sim = Sim::new();
+        let mut pd = PdCoordinator::new(TIMINGS, BOOT_SETPOINT);
+        block_on(Tps55288Ops::apply_setpoint(&mut sim, BOOT_SETPOINT)).expect("boot setpoint");
+        pd.mark_boot_applied(sim.now_ms());
+        block_on(sim.delay_ms(TIMINGS.por_release_ms));
+        pd.finish_boot_por(sim.now_ms());
+        Self {
+            sim,
+            pd,
+            controls: Controls {
+                usb_c_power_on: true,
+                runtime_output_enabled: true,
+                config: PowerConfig::defaults(),
+            },
+            events: Vec::new(),
+        }
+    }
+
+    fn tick(&mut self) -> PdTickOutcome {
+        let input = PdTickInput {
+            usb_c_power_on: self.controls.usb_c_power_on,
+            runtime_output_enabled: self.controls.runtime_output_enabled,
+            runtime_discharge_enabled: false,
+            runtime_result_inflight: false,
+            power_config: &self.controls.config,
+            effective_power_config: &self.controls.config,
+            thermal_power_watts: self.controls.config.capability.power_watts,
+        };
+        let mut sw = self.sim.clone();
+        let mut tps = self.sim.clone();
+        let mut clock = self.sim.clone();
+        let events = &mut self.events;
+        let outcome = block_on(
+            self.pd
+                .tick(&input, &mut sw, &mut tps, &mut clock, |event| {
+                    events.push(event_name(&event))
+                }),
+        );
+        self.sim.bench().now_ms += outcome.loop_delay_ms;
+        self.assert_safe();
+        outcome
+    }
+
+    fn run(&mut self, ticks: usize) {
+        for _ in 0..ticks {
+            self.tick();
+        }
+    }
+
+    /// Invariants that must hold after every tick, whatever was injected.
+    fn assert_safe(&self) {
+        let bench = self.sim.0.borrow();
+        assert!(
+            bench.violations.is_empty(),
+            "SW2303 accessed unsafely: {:?}",
+            bench.violations
+        );
+        let phase = self.pd.gate().phase();
+        if matches!(
+            phase,
+            Sw2303PowerGatePhase::HoldingOff { .. }
+                | Sw2303PowerGatePhase::Off
+                | Sw2303PowerGatePhase::WaitingForPreBootI2cRelease
+                | Sw2303PowerGatePhase::WaitingForBootApply
+        ) {
+            assert!(
+                !bench.output,
+                "TPS output enabled at {}ms during gate phase {phase:?}",
+                bench.now_ms
+            );
+        }
+        if bench.output && !bench.chip_enabled {
+            panic!("TPS output reported on while CE_TPS holds it in reset");
+        }
+    }
+
+    fn output(&self) -> (bool, u16) {
+        let bench = self.sim.0.borrow();
+        (bench.output, bench.vout_mv)
+    }
+
+    fn negotiate(&mut self, request_mv: u16) {
+        self.sim.bench().sink_request_mv = request_mv;
+        self.run(60);
+    }
+}
+
+fn event_name<SE, TE>(event: &PdEvent<'_, SE, TE>) -> String {
+    let name = match event {
+        PdEvent::ReadError(_) => "read_error",
+        PdEvent::ProfileApplied { .. } => "profile_applied",
+        PdEvent::Recontracted => "recontracted",
+        PdEvent::TpsApplyError(_) => "tps_apply_error",
+        PdEvent::LightLoadError(_) => "light_load_error",
+        PdEvent::TpsOffApplied => "tps_off",
+        PdEvent::TpsBootApplied => "tps_boot",
+        PdEvent::RecoveryStarted { .. } => "recovery_started",
+        PdEvent::RecoveryLeftOff => "recovery_left_off",
+        PdEvent::RecoveryComplete { .. } => "recovery_complete",
+        PdEvent::RecoveryFailed { .. } => "recovery_failed",
+        PdEvent::BusParked => "bus_parked",
+        PdEvent::PorElapsed => "por_elapsed",
+        _ => "other",
+    };
+    name.to_string()
+}
+
+#[test]
+fn follows_the_sink_request_and_applies_the_profile_after_stable_reads() {
+    let mut harness = Harness::booted();
+    assert_eq!(harness.output(), (true, 5_000));
+
+    harness.negotiate(20_000);
+    assert_eq!(harness.output(), (true, 20_000));
+    assert!(harness.pd.profile_applied());
+    assert_eq!(harness.sim.0.borrow().profile_writes, 1);
+    assert_eq!(harness.pd.path_control(), Some(Sw2303PathControl::Auto));
+
+    harness.negotiate(9_000);
+    assert_eq!(harness.output(), (true, 9_000));
+}
+
+#[test]
+fn sw2303_nacks_keep_the_last_valid_target_until_reads_recover() {
+    let mut harness = Harness::booted();
+    harness.negotiate(15_000);
+
+    // More NACKs than one tick's read retries.
+    harness.sim.bench().sw_nacks = u32::from(TIMINGS.read_retries) + 5;
+    harness.tick();
+    assert!(harness.pd.sw2303_error_latched());
+    assert_eq!(harness.output(), (true, 15_000));
+
+    harness.run(10);
+    assert!(!harness.pd.sw2303_error_latched());
+    assert_eq!(harness.output(), (true, 15_000));
+    assert_eq!(
+        harness
+            .events
+            .iter()
+            .filter(|event| *event == "read_error")
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn power_off_parks_the_bus_and_power_on_waits_for_the_sw2303_por() {
+    let mut harness = Harness::booted();
+    harness.negotiate(20_000);
+
+    harness.controls.usb_c_power_on = false;
+    harness.tick();
+    assert!(!harness.output().0);
+    assert!(harness.sim.0.borrow().bus_parked);
+    assert!(harness.pd.gate().off_transition_complete());
+    harness.run(20);
+
+    harness.pd.restart_contract();
+    harness.sim.forget_setpoint();
+    harness.controls.usb_c_power_on = true;
+    harness.tick();
+    // The boot 5V comes back before any SW2303 transaction.
+    assert_eq!(harness.output(), (true, 5_000));
+    assert!(!harness.pd.sw2303_i2c_allowed());
+
+    harness.negotiate(20_000);
+    assert!(harness.pd.sw2303_i2c_allowed());
+    assert_eq!(harness.output(), (true, 20_000));
+}
+
+#[test]
+fn tps_nacks_recover_by_cycling_ce_and_restoring_the_boot_supply() {
+    let mut harness = Harness::booted();
+    harness.negotiate(20_000);
+
+    harness.sim.bench().tps_nacks = 5;
+    harness.sim.bench().sink_request_mv = 9_000;
+    let mut recovered = false;
+    for _ in 0..20 {
+        if harness.tick().recovered {
+            recovered = true;
+            break;
+        }
+    }
+    assert!(recovered, "events: {:?}", harness.events);
+    assert!(harness.sim.0.borrow().ce_pulses >= 1);
+    assert_eq!(harness.pd.recovery_count(), 1);
+    assert!(!harness.pd.tps_error_latched());
+    assert_eq!(harness.output(), (true, 5_000));
+
+    harness.run(5);
+    assert_eq!(harness.output(), (true, 9_000));
+}
+
+#[test]
+fn a_stuck_tps_bus_during_power_off_never_re_enables_the_output() {
+    let mut harness = Harness::booted();
+    harness.negotiate(20_000);
+
+    // Thermal shutdown asks for the runtime output off just as the TPS bus
+    // stops answering.
+    harness.sim.bench().tps_stuck = true;
+    harness.controls.runtime_output_enabled = false;
+    for _ in 0..40 {
+        harness.tick();
+    }
+    assert!(harness.events.contains(&"recovery_left_off".to_string()));
+    assert!(!harness.events.contains(&"tps_boot".to_string()));
+    // CE_TPS reset is what turned the output off; nothing switched it back on.
+    assert!(harness.sim.0.borrow().ce_pulses >= 1);
+    assert!(!harness.output().0);
+
+    harness.sim.bench().tps_stuck = false;
+    harness.run(5);
+    assert!(!harness.output().0);
+    assert!(harness.pd.gate().off_transition_complete());
+    assert!(harness.sim.0.borrow().discharge);
+}
+
+#[test]
+fn a_stuck_tps_bus_still_reaches_the_ce_recovery() {
+    let mut harness = Harness::booted();
+    harness.negotiate(12_000);
+
+    harness.sim.bench().tps_stuck = true;
+    harness.sim.bench().light_load = None;
+    harness.sim.bench().sink_request_mv = 9_000;
+    harness.run(10);
+    assert!(harness.events.contains(&"light_load_error".to_string()));
+    assert!(harness.events.contains(&"recovery_started".to_string()));
+    assert!(harness.events.contains(&"recovery_failed".to_string()));
+    assert!(!harness.output().0);
+
+    // The bus answers again, but the contract died with the CE pulse: nothing
+    // may drive the old target until a recovery has restarted from 5V.
+    harness.sim.bench().tps_stuck = false;
+    harness.sim.bench().now_ms += TIMINGS.recovery_min_interval_ms;
+    harness.tick();
+    assert!(harness.events.contains(&"recovery_complete".to_string()));
+    assert_eq!(harness.output(), (true, 5_000));
+    harness.run(10);
+    assert_eq!(harness.output(), (true, 9_000));
+}
+
+#[test]
+fn a_held_low_pd_bus_is_freed_by_the_ce_recovery() {
+    let mut harness = Harness::booted();
+    harness.negotiate(20_000);
+
+    harness.sim.bench().holds_sda_low = true;
+    harness.sim.bench().tps_nacks = 3;
+    harness.sim.bench().sink_request_mv = 9_000;
+    harness.pd.set_path_control_applied(None);
+    harness.controls.config.manual.voltage_mv = 9_000;
+    harness.controls.config.tps_mode = isolapurr_firmware_core::power_config::TpsMode::Manual;
+    let mut recovered = false;
+    for _ in 0..20 {
+        if harness.tick().recovered {
+            recovered = true;
+            break;
+        }
+    }
+    assert!(recovered, "events: {:?}", harness.events);
+    // Cycling CE_TPS powered the SW2303 down, which released SDA.
+    assert!(!harness.sim.0.borrow().holds_sda_low);
+    assert!(harness.pd.sw2303_i2c_allowed());
+    assert_eq!(harness.output(), (true, 5_000));
+}
+
+#[test]
+fn a_requested_recovery_cycles_ce_once_and_respects_the_interval() {
+    let mut harness = Harness::booted();
+    harness.negotiate(9_000);
+
+    let now_ms = harness.sim.now_ms();
+    assert!(harness.pd.request_recovery(now_ms));
+    assert!(harness.tick().recovered, "events: {:?}", harness.events);
+    assert_eq!(harness.pd.recovery_count(), 1);
+    assert_eq!(harness.output(), (true, 5_000));
+
+    // The request was consumed and a second one inside the interval is refused.
+    assert!(!harness.pd.request_recovery(harness.sim.now_ms()));
+    harness.run(10);
+    assert_eq!(harness.pd.recovery_count(), 1);
+    assert_eq!(harness.output(), (true, 9_000));
+}
+
+#[test]
+fn a_recovery_requested_while_power_is_off_is_dropped() {
+    let mut harness = Harness::booted();
+    harness.negotiate(9_000);
+    harness.controls.usb_c_power_on = false;
+    harness.run(5);
+
+    assert!(harness.pd.request_recovery(harness.sim.now_ms()));
+    assert!(!harness.tick().recovered);
+    harness.controls.usb_c_power_on = true;
+    harness.pd.restart_contract();
+    harness.run(10);
+    assert_eq!(harness.pd.recovery_count(), 0);
+    assert_eq!(harness.sim.0.borrow().ce_pulses, 0);
+}
+
+#[test]
+fn a_brown_out_stays_off_until_the_port_restarts_from_the_boot_supply() {
+    let mut harness = Harness::booted();
+    harness.negotiate(20_000);
+
+    harness.sim.bench().brown_out();
+    harness.run(20);
+    assert!(!harness.output().0);
+
+    // Supply is back; the sink dropped its contract along with the SW2303.
+    harness.sim.bench().tps_stuck = false;
+    harness.sim.bench().sink_request_mv = 5_000;
+    harness.controls.usb_c_power_on = false;
+    harness.run(20);
+    harness.pd.restart_contract();
+    harness.sim.forget_setpoint();
+    harness.controls.usb_c_power_on = true;
+    harness.tick();
+    assert_eq!(harness.output(), (true, 5_000));
+
+    harness.negotiate(15_000);
+    assert_eq!(harness.output(), (true, 15_000));
+}
+
+#[test]
+fn a_profile_change_recontracts_and_follows_the_renegotiated_request() {
+    let mut harness = Harness::booted();
+    harness.negotiate(20_000);
+    assert_eq!(harness.sim.0.borrow().profile_writes, 1);
+
+    harness.controls.config.capability.power_watts = 45;
+    harness.pd.refresh_profile(true);
+    harness.tick();
+    assert_eq!(harness.sim.0.borrow().profile_writes, 2);
+    assert_eq!(harness.sim.0.borrow().cc_un_driving, 1);
+    assert!(harness.events.contains(&"recontracted".to_string()));
+    assert!(harness.pd.last_valid_request().is_none());
+
+    // The next read sees the sink back at 5V, so 20V is never driven into the
+    // fresh contract.
+    harness.tick();
+    assert_eq!(harness.output(), (true, 5_000));
+    harness.negotiate(15_000);
+    assert_eq!(harness.output(), (true, 15_000));
+}
+
+#[test]
+fn tps_off_failures_outside_the_coordinator_count_towards_recovery() {
+    let mut harness = Harness::booted();
+    assert!(harness.pd.record_tps_off_failed());
+    assert!(!harness.pd.record_tps_off_failed());
+    assert!(harness.pd.tps_error_latched());
+    harness.pd.record_tps_off_applied();
+    assert!(!harness.pd.tps_error_latched());
+    assert_eq!(harness.pd.tps_5v_since_ms(), None);
+    harness.tick();
+    assert_eq!(harness.pd.recovery_count(), 0);
+}
@@ -0,0 +1,36 @@
+pub mod png;
+
+use std::path::PathBuf;
+
+/// Compares an RGB565 frame with `tests/golden/display/<name>.png`.
+///
+/// Set `UPDATE_GOLDENS=1` to rewrite the golden instead. On mismatch the new
+/// render is written next to the test target so it can be inspected.
+pub fn assert_display_golden(name: &str, width: u16, height: u16, frame: &[u16]) {
+    let encoded = png::encode_rgb565(u32::from(width), u32::from(height), frame);
+    let golden = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden/display")
+        .join(format!("{name}.png"));
+
+    if std::env::var_os("UPDATE_GOLDENS").is_some_and(|value| value == "1") {
+        std::fs::create_dir_all(golden.parent().unwrap()).unwrap();
+        std::fs::write(&golden, &encoded).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read(&golden).unwrap_or_else(|err| {
+        panic!(
+            "missing golden {} ({err}); rerun with UPDATE_GOLDENS=1",
+            golden.display()
+        )
+    });
+    if expected != encoded {
+        let actual = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(format!("{name}.actual.png"));
+        std::fs::write(&actual, &encoded).unwrap();
+        panic!(
+            "{name} differs from {}; new render saved to {}. Rerun with UPDATE_GOLDENS=1 if the change is intended",
+            golden.display(),
+            actual.display()
+        );
+    }
+}
@@ -0,0 +1,206 @@
+//! Minimal deterministic PNG writer for RGB565 display snapshots.
+//!
+//! Rows are filtered (None/Sub/Up, whichever leaves the most zero bytes) and
+//! compressed with one fixed-Huffman deflate block that only emits
+//! distance-1 runs. That is enough to keep flat UI screens small, and the
+//! output is byte-stable so goldens can be compared as plain files.
+
+pub fn encode_rgb565(width: u32, height: u32, pixels: &[u16]) -> Vec<u8> {
+    assert_eq!(pixels.len(), width as usize * height as usize);
+
+    let mut filtered = Vec::with_capacity((width as usize * 3 + 1) * height as usize);
+    let mut previous = vec![0u8; width as usize * 3];
+    for row in pixels.chunks_exact(width as usize) {
+        let current: Vec<u8> = row.iter().flat_map(|&pixel| rgb888(pixel)).collect();
+        let none = current.clone();
+        let sub: Vec<u8> = (0..current.len())
+            .map(|i| current[i].wrapping_sub(if i >= 3 { current[i - 3] } else { 0 }))
+            .collect();
+        let up: Vec<u8> = (0..current.len())
+            .map(|i| current[i].wrapping_sub(previous[i]))
+            .collect();
+        let zeros = |bytes: &[u8]| bytes.iter().filter(|&&b| b == 0).count();
+        let (kind, bytes) = [(0u8, none), (1, sub), (2, up)]
+            .into_iter()
+            .max_by_key(|(kind, bytes)| (zeros(bytes), u8::MAX - kind))
+            .unwrap();
+        filtered.push(kind);
+        filtered.extend_from_slice(&bytes);
+        previous = current;
+    }
+
+    let mut zlib = vec![0x78, 0x01];
+    zlib.extend_from_slice(&deflate_runs(&filtered));
+    zlib.extend_from_slice(&adler32(&filtered).to_be_bytes());
+
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    ihdr.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    write_chunk(&mut png, b"IHDR", &ihdr);
+    write_chunk(&mut png, b"IDAT", &zlib);
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn rgb888(pixel: u16) -> [u8; 3] {
+    let r = ((pixel >> 11) & 0x1F) as u8;
+    let g = ((pixel >> 5) & 0x3F) as u8;
+    let b = (pixel & 0x1F) as u8;
+    [
+        (r << 3) | (r >> 2),
+        (g << 2) | (g >> 4),
+        (b << 3) | (b >> 2),
+    ]
+}
+
+/// (base length, extra bits) for length symbols 257..=285.
+const LENGTHS: [(u16, u8); 29] = [
+    (3, 0),
+    (4, 0),
+    (5, 0),
+    (6, 0),
+    (7, 0),
+    (8, 0),
+    (9, 0),
+    (10, 0),
+    (11, 1),
+    (13, 1),
+    (15, 1),
+    (17, 1),
+    (19, 2),
+    (23, 2),
+    (27, 2),
+    (31, 2),
+    (35, 3),
+    (43, 3),
+    (51, 3),
+    (59, 3),
+    (67, 4),
+    (83, 4),
+    (99, 4),
+    (115, 4),
+    (131, 5),
+    (163, 5),
+    (195, 5),
+    (227, 5),
+    (258, 0),
+];
+
+fn deflate_runs(data: &[u8]) -> Vec<u8> {
+    let mut out = BitWriter::default();
+    // BFINAL=1, BTYPE=01 (fixed Huffman).
+    out.bits(1, 1);
+    out.bits(1, 2);
+
+    let mut i = 0;
+    while i < data.len() {
+        let run = if i == 0 {
+            0
+        } else {
+            data[i..]
+                .iter()
+                .take(258)
+                .take_while(|&&b| b == data[i - 1])
+                .count()
+        };
+        if run >= 3 {
+            let (index, (base, extra)) = LENGTHS
+                .iter()
+                .enumerate()
+                .rev()
+                .find(|(_, (base, _))| *base as usize <= run)
+                .unwrap();
+            out.symbol(257 + index as u16);
+            out.bits(u32::from(run as u16 - base), *extra);
+            // Distance code 0 (distance 1) is five zero bits.
+            out.code(0, 5);
+            i += run;
+        } else {
+            out.symbol(u16::from(data[i]));
+            i += 1;
+        }
+    }
+    out.symbol(256);
+    out.finish()
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    acc: u32,
+    len: u8,
+}
+
+impl BitWriter {
+    /// Writes a value least-significant bit first.
+    fn bits(&mut self, value: u32, count: u8) {
+        for bit in 0..count {
+            self.acc |= ((value >> bit) & 1) << self.len;
+            self.len += 1;
+            if self.len == 8 {
+                self.bytes.push(self.acc as u8);
+                self.acc = 0;
+                self.len = 0;
+            }
+        }
+    }
+
+    /// Writes a Huffman code most-significant bit first.
+    fn code(&mut self, code: u16, count: u8) {
+        for bit in (0..count).rev() {
+            self.bits(u32::from((code >> bit) & 1), 1);
+        }
+    }
+
+    fn symbol(&mut self, symbol: u16) {
+        match symbol {
+            0..=143 => self.code(0x30 + symbol, 8),
+            144..=255 => self.code(0x190 + symbol - 144, 9),
+            256..=279 => self.code(symbol - 256, 7),
+            _ => self.code(0xC0 + symbol - 280, 8),
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.len > 0 {
+            self.bytes.push(self.acc as u8);
+        }
+        self.bytes
+    }
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in bytes {
+        a = (a + u32::from(byte)) % 65_521;
+        b = (b + a) % 65_521;
+    }
+    (b << 16) | a
+}
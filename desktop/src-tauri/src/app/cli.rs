@@ -110,18 +110,6 @@ enum FirmwareCmd {
     },
 }
 
-#[derive(Clone, Debug, Serialize)]
-struct ErrorEnvelope {
-    error: ErrorInfo,
-}
-
-#[derive(Clone, Debug, Serialize)]
-struct ErrorInfo {
-    code: &'static str,
-    message: String,
-    retryable: bool,
-}
-
 #[derive(Clone, Debug, Serialize)]
 struct BootstrapResponse {
     token: String,
@@ -52,8 +52,8 @@ async fn api_discovery_refresh(State(state): State<AppState>, headers: HeaderMap
             StatusCode::SERVICE_UNAVAILABLE,
             Json(ErrorEnvelope {
                 error: ErrorInfo {
-                    code: "temporarily_unavailable",
-                    message: err.to_string(),
+                    code: errors::TEMPORARILY_UNAVAILABLE,
+                    message: &err.to_string(),
                     retryable: true,
                 },
             }),
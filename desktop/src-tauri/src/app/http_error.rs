@@ -66,8 +66,8 @@ fn unauthorized(message: &str) -> Response {
         StatusCode::UNAUTHORIZED,
         Json(ErrorEnvelope {
             error: ErrorInfo {
-                code: "unauthorized",
-                message: message.to_string(),
+                code: errors::UNAUTHORIZED,
+                message,
                 retryable: false,
             },
         }),
@@ -80,8 +80,8 @@ fn forbidden(message: &str) -> Response {
         StatusCode::FORBIDDEN,
         Json(ErrorEnvelope {
             error: ErrorInfo {
-                code: "forbidden",
-                message: message.to_string(),
+                code: errors::FORBIDDEN,
+                message,
                 retryable: false,
             },
         }),
@@ -94,8 +94,8 @@ fn bad_request(message: &str) -> Response {
         StatusCode::BAD_REQUEST,
         Json(ErrorEnvelope {
             error: ErrorInfo {
-                code: "bad_request",
-                message: message.to_string(),
+                code: errors::BAD_REQUEST,
+                message,
                 retryable: false,
             },
         }),
@@ -108,8 +108,8 @@ fn conflict(message: &str) -> Response {
         StatusCode::CONFLICT,
         Json(ErrorEnvelope {
             error: ErrorInfo {
-                code: "conflict",
-                message: message.to_string(),
+                code: errors::CONFLICT,
+                message,
                 retryable: false,
             },
         }),
@@ -122,8 +122,8 @@ fn not_found(message: &str) -> Response {
         StatusCode::NOT_FOUND,
         Json(ErrorEnvelope {
             error: ErrorInfo {
-                code: "not_found",
-                message: message.to_string(),
+                code: errors::NOT_FOUND,
+                message,
                 retryable: false,
             },
         }),
@@ -136,8 +136,8 @@ fn internal_error(message: &str) -> Response {
         StatusCode::INTERNAL_SERVER_ERROR,
         Json(ErrorEnvelope {
             error: ErrorInfo {
-                code: "internal_error",
-                message: message.to_string(),
+                code: errors::INTERNAL_ERROR,
+                message,
                 retryable: false,
             },
         }),
@@ -242,7 +242,7 @@ fn identify_port(port_path: &str) -> Result<PortIdentityCache, String> {
         timeout_ms: 2_500,
         request: serde_json::json!({
             "id": 1,
-            "method": "info",
+            "method": methods::INFO,
             "params": {},
         }),
     })?;
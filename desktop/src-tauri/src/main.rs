@@ -25,6 +25,8 @@ use clap::{Parser, Subcommand};
 use default_net::interface::InterfaceType;
 use directories::ProjectDirs;
 use futures::{StreamExt as _, stream};
+use isolapurr_api::errors::{ErrorEnvelope, ErrorInfo};
+use isolapurr_api::{errors, methods};
 use mdns_sd::{ServiceDaemon, ServiceEvent};
 use rust_embed::RustEmbed;
 use serde::{Deserialize, Serialize};
@@ -5,7 +5,7 @@ from pathlib import Path
 from PIL import Image, ImageDraw, ImageFont
 
 ROOT = Path(__file__).resolve().parents[4]
-SRC = ROOT / "src" / "display_ui" / "dashboard_font.rs"
+SRC = ROOT / "crates" / "isolapurr-firmware-core" / "src" / "display_render" / "dashboard_font.rs"
 FONT_PATH = Path("/System/Library/Fonts/Supplemental/Arial Narrow Bold.ttf")
 CHARSET = " 0123456789.-/ABCDEFGHIJKLMNOPQRSTUVWXYZ"
 
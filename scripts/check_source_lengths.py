@@ -49,7 +49,7 @@ EXCLUDED_FILES = {
 }
 
 ALLOWLIST = {
-    "src/display_ui/dashboard_font.rs": "generated dashboard font data",
+    "crates/isolapurr-firmware-core/src/display_render/dashboard_font.rs": "generated dashboard font data",
 }
 
 
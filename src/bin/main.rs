@@ -16,6 +16,17 @@ mod spi_device;
 #[cfg(feature = "net_http")]
 extern crate alloc;
 
+/// Logs through defmt and mirrors the record into the remote log ring
+/// (`logs.tail`, syslog). Takes `core::fmt` arguments rather than defmt ones.
+macro_rules! hub_log {
+    ($level:ident, $($arg:tt)+) => {
+        crate::log_record(
+            isolapurr_api::diagnostics::LogLevel::$level,
+            format_args!($($arg)+),
+        )
+    };
+}
+
 // Optional Wi‑Fi + mDNS + HTTP support; compiled only when `net_http` feature is set.
 #[cfg(feature = "net_http")]
 #[path = "../mdns.rs"]
@@ -30,7 +41,6 @@ const BUILD_GIT_DIRTY: &str = env!("USB_HUB_BUILD_GIT_DIRTY");
 const BUILD_PROFILE: &str = env!("USB_HUB_BUILD_PROFILE");
 
 use core::cell::RefCell;
-#[cfg(feature = "net_http")]
 use core::fmt::Write as _;
 use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use critical_section::Mutex;
@@ -57,21 +67,71 @@ use esp_hal::timer::timg::TimerGroup;
 use esp_hal::usb_serial_jtag::UsbSerialJtag;
 use esp_hal::{dma_buffers, handler, ram};
 #[cfg(feature = "net_http")]
+use isolapurr_api::diagnostics::{I2cBusId, I2cProbeResult, RegTarget};
+use isolapurr_api::diagnostics::{LogLevel, ResetReason};
+#[cfg(feature = "net_http")]
+use isolapurr_api::ports::PortPowerPolicy;
+use isolapurr_api::ports::{ChargeTerminationAction, PortId};
+#[cfg(feature = "net_http")]
+use isolapurr_api::power::{FastChargeProbeError, FastChargeProbeState};
+#[cfg(feature = "net_http")]
+use isolapurr_api::{WriteJson, errors, methods};
+#[cfg(feature = "net_http")]
 use isolapurr_firmware_core::identify::IdentifyState;
-use isolapurr_firmware_core::sw2303_power_gate::Sw2303PowerGate;
+use isolapurr_firmware_core::pd_coordinator::{
+    PdClock, PdCoordinator, PdEvent, PdTickInput, PdTimings, Sw2303Ops, Tps55288Ops,
+};
+#[cfg(feature = "net_http")]
+use isolapurr_usb_hub::button_settings::next_preset_power_watts;
+use isolapurr_usb_hub::button_settings::{
+    BUTTON_DOUBLE_PRESS_WINDOW_MS, ButtonAction, ButtonGesture, ButtonSettings, DoublePressTracker,
+    ShortPress,
+};
+#[cfg(feature = "net_http")]
+use isolapurr_usb_hub::buzzer::BuzzerControl;
 use isolapurr_usb_hub::buzzer::ledc::LedcBuzzer;
+use isolapurr_usb_hub::charge_termination::{
+    ChargeTerminationDetector, ChargeTerminationSettings, drop_5v_applies,
+};
+#[cfg(feature = "net_http")]
+use isolapurr_usb_hub::debug_regs::{
+    DEBUG_CHALLENGE_TTL_MS, DEBUG_IDLE_TIMEOUT_MS, DEBUG_MAX_DUMP_LEN, RegWrite, UnlockStep,
+    dump_range, register_in_map,
+};
+#[cfg(feature = "net_http")]
+use isolapurr_usb_hub::display_settings::DisplayTheme;
+use isolapurr_usb_hub::display_settings::{BacklightLevel, DisplayIdleTimer, DisplaySettings};
 use isolapurr_usb_hub::display_ui::{
-    ActiveLowBacklight, DASHBOARD_BG_RGB8, DisplayUi, EspHalSpinTimer, NormalUiField, NormalUiPort,
+    DASHBOARD_BG_RGB8, DisplayUi, EspHalSpinTimer, LedcBacklight, NormalUiField, NormalUiPort,
     NormalUiPortBadge, NormalUiPortMode, NormalUiSnapshot, UsbCDisplayInput, WORKBUF_SIZE,
     resolve_usb_c_display,
 };
 #[cfg(feature = "net_http")]
+use isolapurr_usb_hub::display_ui::{DisplayFrame, alloc_display_frame};
+#[cfg(feature = "net_http")]
+use isolapurr_usb_hub::fast_charge_probe::{
+    FAST_CHARGE_PROBE_STEPS, FastChargeProbe, FastChargeProbeReport, FastChargeProbeSample,
+};
+#[cfg(feature = "net_http")]
+use isolapurr_usb_hub::i2c_diagnostics::{
+    I2cBusCounters, I2cLineState, classify_i2c_error, probe_result,
+};
+#[cfg(feature = "net_http")]
 use isolapurr_usb_hub::idle_bias::{
     IDLE_BIAS_POINT_COUNT, IDLE_BIAS_SAMPLE_COUNT, IDLE_BIAS_SAMPLE_INTERVAL_MS,
     IDLE_BIAS_SETTLE_WINDOW_MS, IdleBiasCalibration, IdleBiasMetadata, average_current_ma,
     corrected_current_ma, corrected_power_mw, idle_bias_point_voltage_mv,
 };
+#[cfg(feature = "net_http")]
+use isolapurr_usb_hub::jsonl_frame::{JsonlByte, JsonlLineBuffer, jsonl_method};
+#[cfg(feature = "net_http")]
+use isolapurr_usb_hub::jsonl_tcp::{
+    JSONL_TCP_PORT, JSONL_TOKEN_MAX_LEN, JSONL_TOKEN_MIN_LEN, JsonlGate, JsonlTcpSession,
+    JsonlToken, JsonlTransport, is_wifi_change,
+};
 use isolapurr_usb_hub::pd_i2c::I2cAllowlist;
+#[cfg(feature = "net_http")]
+use isolapurr_usb_hub::pd_i2c::PD_I2C_ADDRESSES;
 use isolapurr_usb_hub::pd_i2c::PowerRequest;
 use isolapurr_usb_hub::pd_i2c::PowerSetpoint;
 use isolapurr_usb_hub::pd_i2c::TPS55288_ADDR_7BIT;
@@ -84,8 +144,16 @@ use isolapurr_usb_hub::pd_i2c::tps55288::{
     boot_supply_setpoint, power_request_to_setpoint, quantize_ilim_ma_floor_with_margin,
     stop_output_and_enable_discharge,
 };
+#[cfg(feature = "net_http")]
+use isolapurr_usb_hub::pd_i2c::{SCL_GPIO, SDA_GPIO};
+use isolapurr_usb_hub::pd_i2c::{SCL_SW_GPIO, SDA_SW_GPIO};
+use isolapurr_usb_hub::port_data::PortDataSettings;
+#[cfg(feature = "net_http")]
+use isolapurr_usb_hub::port_power::LastStateCoalescer;
+use isolapurr_usb_hub::port_power::PortPowerSettings;
 use isolapurr_usb_hub::power_config::{
-    MANUAL_DEFAULT_CURRENT_MA, PowerConfig, Sw2303CapabilityReadback, Sw2303PathControl, TpsMode,
+    ActiveProtocol, LightLoadMode, MANUAL_DEFAULT_CURRENT_MA, PowerConfig,
+    Sw2303CapabilityReadback, Sw2303LineCompensation, Sw2303PathControl, TpsCdcRise, TpsMode,
     clamp_manual_current_ma, quantize_manual_voltage_mv, resolve_manual_path_control,
 };
 use isolapurr_usb_hub::prompt_tone::{
@@ -95,14 +163,21 @@ use isolapurr_usb_hub::prompt_tone::{
 #[cfg(feature = "net_http")]
 use isolapurr_usb_hub::provisioning;
 use isolapurr_usb_hub::release_version;
+use isolapurr_usb_hub::reset_report::{
+    CrashReport, PanicLocation, RESET_RECORD_LEN, ResetRecord, ResetReport, decode_reset_record,
+    encode_reset_record, is_crash,
+};
+#[cfg(feature = "net_http")]
+use isolapurr_usb_hub::session::SessionSample;
+#[cfg(feature = "net_http")]
+use isolapurr_usb_hub::sound_settings::{LocalClockAnchor, SoundSettings, SoundSlot};
 use isolapurr_usb_hub::telemetry::{Field, NormalUiTelemetrySampler, TelemetryI2cAllowlist};
 use isolapurr_usb_hub::thermal::{
     THERMAL_SAMPLE_INTERVAL_MS, ThermalController, ThermalState, ThermalTelemetry,
-    clamp_manual_current_limit_ma, current_limit_ma_for_power_watts,
 };
 
 #[cfg(feature = "net_http")]
-use isolapurr_usb_hub::telemetry::PortMetrics;
+use isolapurr_usb_hub::telemetry::{PortMetrics, TELEMETRY_I2C_ADDRESSES, TelemetryI2cCounters};
 use {esp_backtrace as _, esp_println as _};
 
 use mcu_temperature::Esp32S3TemperatureSensor;
@@ -145,6 +220,15 @@ static POWER_RUNTIME_RESULT: Signal<CriticalSectionRawMutex, bool> = Signal::new
 #[cfg(feature = "net_http")]
 static IDLE_BIAS_RESULT: Signal<CriticalSectionRawMutex, bool> = Signal::new();
 
+#[cfg(feature = "net_http")]
+static SOUND_RESULT: Signal<CriticalSectionRawMutex, bool> = Signal::new();
+
+#[cfg(feature = "net_http")]
+static DISPLAY_RESULT: Signal<CriticalSectionRawMutex, bool> = Signal::new();
+
+#[cfg(feature = "net_http")]
+static BUTTONS_RESULT: Signal<CriticalSectionRawMutex, bool> = Signal::new();
+
 #[cfg(feature = "net_http")]
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum SettingsResetResult {
@@ -160,13 +244,67 @@ static SETTINGS_RESET_RESULT: Signal<CriticalSectionRawMutex, SettingsResetResul
 static WIFI_CREDENTIALS_CACHE: Mutex<RefCell<Option<provisioning::WifiCredentials>>> =
     Mutex::new(RefCell::new(None));
 
+#[cfg(feature = "net_http")]
+#[derive(Clone, Copy)]
+enum JsonlTokenCommand {
+    Store(JsonlToken),
+    Clear,
+}
+
+#[cfg(feature = "net_http")]
+static JSONL_TOKEN_PENDING: Mutex<RefCell<Option<JsonlTokenCommand>>> =
+    Mutex::new(RefCell::new(None));
+
+#[cfg(feature = "net_http")]
+static JSONL_TOKEN_RESULT: Signal<CriticalSectionRawMutex, bool> = Signal::new();
+
+#[cfg(feature = "net_http")]
+static JSONL_TOKEN_CACHE: Mutex<RefCell<Option<JsonlToken>>> = Mutex::new(RefCell::new(None));
+
+/// Set when the stored token could not be read at boot; cleared once USB sets or
+/// clears the token.
+#[cfg(feature = "net_http")]
+static JSONL_TCP_DISABLED: AtomicBool = AtomicBool::new(false);
+
 #[cfg(feature = "net_http")]
 static REBOOT_PENDING: AtomicBool = AtomicBool::new(false);
 
+#[cfg(feature = "net_http")]
+static LOGS_RESULT: Signal<CriticalSectionRawMutex, bool> = Signal::new();
+
+#[cfg(feature = "net_http")]
+static PORT_POWER_RESULT: Signal<CriticalSectionRawMutex, bool> = Signal::new();
+
+#[cfg(feature = "net_http")]
+static CHARGE_TERMINATION_RESULT: Signal<CriticalSectionRawMutex, bool> = Signal::new();
+
+fn log_record(level: LogLevel, args: core::fmt::Arguments<'_>) {
+    match level {
+        LogLevel::Error => defmt::error!("{}", defmt::Display2Format(&args)),
+        LogLevel::Warn => defmt::warn!("{}", defmt::Display2Format(&args)),
+        LogLevel::Info => info!("{}", defmt::Display2Format(&args)),
+        LogLevel::Debug => defmt::debug!("{}", defmt::Display2Format(&args)),
+    }
+    #[cfg(feature = "net_http")]
+    net::push_log(level, uptime_ms_from_instant(Instant::now()), args);
+}
+
+include!("firmware_main/display_screenshot.inc");
+
 include!("firmware_main/usb_console.inc");
 
+include!("firmware_main/jsonl_tcp.inc");
+
 include!("firmware_main/ui_runtime.inc");
 
+include!("firmware_main/pd_hardware.inc");
+
+include!("firmware_main/i2c_diagnostics.inc");
+
+include!("firmware_main/register_debug.inc");
+
+include!("firmware_main/crash_report.inc");
+
 #[esp_rtos::main]
 async fn main(_spawner: Spawner) {
     include!("firmware_main/main_runtime.inc")
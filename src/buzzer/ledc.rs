@@ -84,40 +84,55 @@ impl<'d> LedcBuzzer<'d> {
         })
     }
 
+    /// Shared LEDC instance. The buzzer holds timer0/channel0; other low-speed
+    /// PWM users (the display backlight) must use the remaining ones.
+    pub fn ledc(&self) -> &Ledc<'d> {
+        &self._ledc
+    }
+
     fn set_duty_pct(&self, duty_pct: u8) -> Result<(), BuzzerError> {
         if duty_pct > 100 {
             return Err(BuzzerError::InvalidDutyPct(duty_pct));
         }
 
-        let duty_bits = self.duty_resolution as u32;
-        let duty_range = 1u32 << duty_bits;
-        let duty_value = (duty_range * duty_pct as u32) / 100;
-
-        // Mirror the esp-hal LEDC channel driver:
-        // - duty register stores duty << 4
-        // - kick duty_start, then para_up to apply
-        let ledc = esp_hal::peripherals::LEDC::regs();
-        let ch = self.channel as usize;
-        ledc.ch(ch)
-            .duty()
-            .write(|w| unsafe { w.duty().bits(duty_value << 4) });
-
-        ledc.ch(ch).conf1().write(|w| {
-            w.duty_start().set_bit();
-            w.duty_inc().set_bit();
-            unsafe {
-                w.duty_num().bits(0x1);
-                w.duty_cycle().bits(0x1);
-                w.duty_scale().bits(0x0)
-            }
-        });
-
-        ledc.ch(ch).conf0().modify(|_, w| w.para_up().set_bit());
-
+        write_channel_duty_pct(self.channel, self.duty_resolution, duty_pct);
         Ok(())
     }
 }
 
+/// Update a configured low-speed channel's duty without holding its
+/// `Channel` handle (which borrows the timer).
+pub(crate) fn write_channel_duty_pct(
+    channel: channel::Number,
+    duty_resolution: timer::config::Duty,
+    duty_pct: u8,
+) {
+    let duty_bits = duty_resolution as u32;
+    let duty_range = 1u32 << duty_bits;
+    let duty_value = (duty_range * duty_pct.min(100) as u32) / 100;
+
+    // Mirror the esp-hal LEDC channel driver:
+    // - duty register stores duty << 4
+    // - kick duty_start, then para_up to apply
+    let ledc = esp_hal::peripherals::LEDC::regs();
+    let ch = channel as usize;
+    ledc.ch(ch)
+        .duty()
+        .write(|w| unsafe { w.duty().bits(duty_value << 4) });
+
+    ledc.ch(ch).conf1().write(|w| {
+        w.duty_start().set_bit();
+        w.duty_inc().set_bit();
+        unsafe {
+            w.duty_num().bits(0x1);
+            w.duty_cycle().bits(0x1);
+            w.duty_scale().bits(0x0)
+        }
+    });
+
+    ledc.ch(ch).conf0().modify(|_, w| w.para_up().set_bit());
+}
+
 impl BuzzerControl for LedcBuzzer<'_> {
     fn start_tone(&mut self, freq_hz: u32, duty_pct: u8) -> Result<(), BuzzerError> {
         if freq_hz == 0 {
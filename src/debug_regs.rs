@@ -0,0 +1 @@
+pub use isolapurr_firmware_core::debug_regs::*;
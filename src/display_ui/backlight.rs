@@ -0,0 +1,91 @@
+use esp_hal::{
+    gpio::{DriveMode, Level, Output, OutputConfig, OutputPin},
+    ledc::{
+        Ledc, LowSpeed,
+        channel::{self, ChannelIFace},
+        timer::{self, TimerIFace},
+    },
+    time::Rate,
+};
+
+use super::BacklightControl;
+use crate::buzzer::ledc::write_channel_duty_pct;
+
+const BACKLIGHT_PWM_HZ: u32 = 20_000;
+const BACKLIGHT_TIMER: timer::Number = timer::Number::Timer1;
+const BACKLIGHT_CHANNEL: channel::Number = channel::Number::Channel1;
+const BACKLIGHT_DUTY: timer::config::Duty = timer::config::Duty::Duty10Bit;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BacklightPwmError;
+
+/// PWM-dimmed backlight on the active-low BLK gate (GPIO15).
+///
+/// Shares the buzzer's LEDC instance on timer1/channel1. The PWM runs above
+/// the audible range so the P-MOSFET gate does not whine at low duty.
+pub struct LedcBacklight<'d> {
+    _timer: timer::Timer<'d, LowSpeed>,
+    level_pct: u8,
+}
+
+impl<'d> LedcBacklight<'d> {
+    pub fn new(
+        ledc: &Ledc<'d>,
+        blk_pin: impl OutputPin + 'd,
+        level_pct: u8,
+    ) -> Result<Self, BacklightPwmError> {
+        let level_pct = level_pct.min(100);
+        // Start with the gate already at the requested level so the panel is
+        // lit before display init, matching the plain GPIO behavior.
+        let initial = if level_pct == 0 {
+            Level::High
+        } else {
+            Level::Low
+        };
+        let blk_output =
+            Output::new(blk_pin, initial, OutputConfig::default()).into_peripheral_output();
+
+        let mut timer1 = ledc.timer::<LowSpeed>(BACKLIGHT_TIMER);
+        timer1
+            .configure(timer::config::Config {
+                duty: BACKLIGHT_DUTY,
+                clock_source: timer::LSClockSource::APBClk,
+                frequency: Rate::from_hz(BACKLIGHT_PWM_HZ),
+            })
+            .map_err(|_| BacklightPwmError)?;
+
+        let mut channel1 = ledc.channel::<LowSpeed>(BACKLIGHT_CHANNEL, blk_output);
+        channel1
+            .configure(channel::config::Config {
+                timer: &timer1,
+                duty_pct: gate_high_pct(level_pct),
+                drive_mode: DriveMode::PushPull,
+            })
+            .map_err(|_| BacklightPwmError)?;
+
+        Ok(Self {
+            _timer: timer1,
+            level_pct,
+        })
+    }
+}
+
+impl BacklightControl for LedcBacklight<'_> {
+    fn on(&mut self) {
+        self.set_level_pct(self.level_pct);
+    }
+
+    fn set_level_pct(&mut self, pct: u8) {
+        self.level_pct = pct.min(100);
+        write_channel_duty_pct(
+            BACKLIGHT_CHANNEL,
+            BACKLIGHT_DUTY,
+            gate_high_pct(self.level_pct),
+        );
+    }
+}
+
+/// BLK is active-low, so the LEDC high time is the backlight's off time.
+const fn gate_high_pct(level_pct: u8) -> u8 {
+    100 - level_pct
+}
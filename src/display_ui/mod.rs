@@ -1,18 +1,17 @@
 #![allow(clippy::identity_op)]
 
-mod dashboard;
-mod dashboard_font;
-mod font6x8;
-mod menu;
-mod surface;
+mod backlight;
 mod usb_c_display;
 
-pub use dashboard::DASHBOARD_BG_RGB8;
+pub use backlight::{BacklightPwmError, LedcBacklight};
+
+pub use isolapurr_firmware_core::display_render::{
+    DASHBOARD_BG_RGB8, DISPLAY_HEIGHT, DISPLAY_WIDTH,
+};
 pub use isolapurr_firmware_core::display_ui::{
     NormalUiField, NormalUiPort, NormalUiPortBadge, NormalUiPortMode, NormalUiSnapshot,
     normal_ui_usb_c_mode, normal_ui_usb_c_present, normal_ui_usb_c_protocol_active,
 };
-pub(super) use surface::{FrameSurface, blend565, measure_text_aa, rgb565_raw};
 pub use usb_c_display::{
     USB_C_DISPLAY_TEXT_CAPACITY, UsbCDisplayInput, UsbCDisplayState, format_port_badge_text,
     format_port_mode_text, resolve_usb_c_display,
@@ -30,45 +29,30 @@ use embedded_hal_async::spi::SpiDevice;
 use esp_alloc::ExternalMemory;
 use esp_hal::time::{Duration, Instant};
 use gc9307_async::{Config, Error as GcError, GC9307C, Orientation, Timer};
+use isolapurr_firmware_core::display_render::{
+    self as render, FRAME_PIXELS, FrameSurface, TILE_H, TILE_W, UiPalette, X_OFFSET, Y_OFFSET,
+    render_char_6x8_scaled,
+};
 
+use crate::display_settings::{DisplayRotation, DisplayTheme};
 use crate::telemetry::{Field, TelemetrySnapshot};
 
 pub const WORKBUF_SIZE: usize = gc9307_async::BUF_SIZE;
-pub const DISPLAY_WIDTH: u16 = 320;
-pub const DISPLAY_HEIGHT: u16 = 172;
-const FRAME_PIXELS: usize = DISPLAY_WIDTH as usize * DISPLAY_HEIGHT as usize;
-
-const TILE_W: u16 = 24;
-const TILE_H: u16 = 48;
-const TILES_X: u16 = 13;
-
-const X_OFFSET: u16 = (DISPLAY_WIDTH - TILE_W * TILES_X) / 2;
-const Y_OFFSET: u16 = (DISPLAY_HEIGHT - TILE_H * 3) / 2;
-
-// Smaller font + spacing: render 6x8 glyph centered into a 24x48 tile.
-const GLYPH_SX: u16 = 3;
-const GLYPH_SY: u16 = 4;
-
-// Compact toast: 3 rows × 20 columns (small font so IPv4 fits in one line).
-const TOAST_COMPACT_TILE_W: u16 = 16;
-const TOAST_COMPACT_TILE_H: u16 = 32;
-const TOAST_COMPACT_TILES_X: u16 = 20;
-const TOAST_COMPACT_X_OFFSET: u16 =
-    (DISPLAY_WIDTH - TOAST_COMPACT_TILE_W * TOAST_COMPACT_TILES_X) / 2;
-const TOAST_COMPACT_Y_OFFSET: u16 = (DISPLAY_HEIGHT - TOAST_COMPACT_TILE_H * 3) / 2;
-
-const TOAST_COMPACT_GLYPH_SX: u16 = 2;
-const TOAST_COMPACT_GLYPH_SY: u16 = 3;
 
 const FRAME_FG: Rgb565 = Rgb565::BLACK;
 const FRAME_BG: Rgb565 = Rgb565::WHITE;
 const UI_BG_RAW: u16 = 0xFFFF;
-const UI_STATUS_NOT_PRESENT_RAW: u16 = 0x4AAC;
-const UI_STATUS_ERROR_RAW: u16 = 0x98C3;
-const UI_STATUS_OVER_RAW: u16 = 0xC201;
 
 pub trait BacklightControl {
     fn on(&mut self);
+
+    /// Set brightness in percent; `0` turns the backlight off. Switch-only
+    /// backlights treat any non-zero level as fully on.
+    fn set_level_pct(&mut self, pct: u8) {
+        if pct > 0 {
+            self.on();
+        }
+    }
 }
 
 pub struct AlwaysOnBacklight;
@@ -87,6 +71,14 @@ where
     fn on(&mut self) {
         self.0.set_low().ok();
     }
+
+    fn set_level_pct(&mut self, pct: u8) {
+        if pct == 0 {
+            self.0.set_high().ok();
+        } else {
+            self.0.set_low().ok();
+        }
+    }
 }
 
 pub struct EspHalSpinTimer;
@@ -139,6 +131,14 @@ enum FlushStrategy {
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct DisplayUiAllocError;
 
+/// A full RGB565 frame in PSRAM, `DISPLAY_WIDTH` pixels per row, top row first.
+pub type DisplayFrame = Vec<u16, ExternalMemory>;
+
+/// Allocates a frame for [`DisplayUi::copy_front_frame`].
+pub fn alloc_display_frame() -> Result<DisplayFrame, DisplayUiAllocError> {
+    alloc_psram_frame(UI_BG_RAW)
+}
+
 pub struct DisplayUi<'b, SPI, DC, RST, TimerImpl = EspHalSpinTimer, BL = AlwaysOnBacklight>
 where
     SPI: SpiDevice,
@@ -149,6 +149,9 @@ where
 {
     display: GC9307C<'b, SPI, DC, RST, TimerImpl>,
     backlight: BL,
+    backlight_pct: u8,
+    palette: UiPalette,
+    rotation: DisplayRotation,
     active_view: ActiveView,
     cache: FrameCache,
     toast_until: Option<Instant>,
@@ -182,6 +185,9 @@ where
         Ok(Self {
             display: GC9307C::new(config, spi, dc, rst, &mut workbuf[..]),
             backlight,
+            backlight_pct: 100,
+            palette: UiPalette::LIGHT,
+            rotation: DisplayRotation::Normal,
             active_view: ActiveView::TelemetryFrame,
             cache: FrameCache::empty(),
             toast_until: None,
@@ -221,6 +227,56 @@ where
         Ok(())
     }
 
+    /// Switch the color theme; the next frame repaints from scratch.
+    pub fn set_theme(&mut self, theme: DisplayTheme) {
+        let palette = UiPalette::for_theme(theme);
+        if palette != self.palette {
+            self.palette = palette;
+            self.dashboard_base_ready = false;
+            self.front_valid = false;
+        }
+    }
+
+    /// Rotate the panel by 180° and repaint the current frame in the new
+    /// orientation (GRAM contents do not move with MADCTL).
+    pub async fn set_rotation(&mut self, rotation: DisplayRotation) -> Result<(), GcError<E>> {
+        if rotation == self.rotation {
+            return Ok(());
+        }
+        let orientation = match rotation {
+            DisplayRotation::Normal => Orientation::Landscape,
+            DisplayRotation::Flipped => Orientation::LandscapeSwapped,
+        };
+        self.display.set_orientation(orientation).await?;
+        self.rotation = rotation;
+        if self.front_valid {
+            self.display
+                .write_rgb565_rect(0, 0, DISPLAY_WIDTH, DISPLAY_HEIGHT, self.front.as_slice())
+                .await?;
+        }
+        Ok(())
+    }
+
+    pub fn set_backlight_pct(&mut self, pct: u8) {
+        self.backlight_pct = pct.min(100);
+        self.backlight.set_level_pct(self.backlight_pct);
+    }
+
+    pub fn backlight_pct(&self) -> u8 {
+        self.backlight_pct
+    }
+
+    /// Copies the frame currently on the panel. Rotation is applied by the
+    /// panel, so the copy is always upright. Returns `false` before the first
+    /// full frame has been presented.
+    pub fn copy_front_frame(&self, out: &mut [u16]) -> bool {
+        if !self.front_valid || out.len() != FRAME_PIXELS {
+            return false;
+        }
+        out.copy_from_slice(self.front.as_slice());
+        true
+    }
+
     pub fn toast_active(&self, now: Instant) -> bool {
         self.toast_until.is_some_and(|until| now < until)
     }
@@ -241,7 +297,7 @@ where
 
         {
             let mut surface = FrameSurface::new(self.back.as_mut_slice());
-            menu::render_settings_menu(&mut surface, selected_index);
+            render::render_settings_menu(&mut surface, &self.palette, selected_index);
         }
 
         self.present_back(FlushStrategy::Full).await
@@ -261,7 +317,16 @@ where
 
         {
             let mut surface = FrameSurface::new(self.back.as_mut_slice());
-            menu::render_message_card(&mut surface, title, primary, secondary, "", accent_raw);
+            let accent_raw = self.palette.accent(accent_raw);
+            render::render_message_card(
+                &mut surface,
+                &self.palette,
+                title,
+                primary,
+                secondary,
+                "",
+                accent_raw,
+            );
         }
 
         self.present_back(FlushStrategy::Full).await
@@ -280,12 +345,14 @@ where
 
         {
             let mut surface = FrameSurface::new(self.back.as_mut_slice());
-            menu::render_message_card(
+            let accent_raw = self.palette.accent(accent_raw);
+            render::render_message_card(
                 &mut surface,
+                &self.palette,
                 title,
-                menu::trim_ascii_line(&lines[0]),
-                menu::trim_ascii_line(&lines[1]),
-                menu::trim_ascii_line(&lines[2]),
+                render::trim_ascii_line(&lines[0]),
+                render::trim_ascii_line(&lines[1]),
+                render::trim_ascii_line(&lines[2]),
                 accent_raw,
             );
         }
@@ -306,18 +373,7 @@ where
 
         {
             let mut surface = FrameSurface::new(self.back.as_mut_slice());
-            surface.fill(UI_BG_RAW);
-            for (tile_y, row) in lines.iter().enumerate() {
-                for (tile_x, &ch) in row.iter().enumerate() {
-                    surface.draw_tile_colored_with_bg(
-                        tile_x as u16,
-                        tile_y as u16,
-                        ch,
-                        fg_raw,
-                        UI_BG_RAW,
-                    );
-                }
-            }
+            render::render_toast(&mut surface, &self.palette, lines, fg_raw);
         }
 
         self.present_back(FlushStrategy::Full).await
@@ -336,18 +392,7 @@ where
 
         {
             let mut surface = FrameSurface::new(self.back.as_mut_slice());
-            surface.fill(UI_BG_RAW);
-            for (tile_y, row) in lines.iter().enumerate() {
-                for (tile_x, &ch) in row.iter().enumerate() {
-                    surface.draw_compact_tile_colored(
-                        tile_x as u16,
-                        tile_y as u16,
-                        ch,
-                        fg_raw,
-                        UI_BG_RAW,
-                    );
-                }
-            }
+            render::render_toast_compact(&mut surface, &self.palette, lines, fg_raw);
         }
 
         self.present_back(FlushStrategy::Full).await
@@ -399,7 +444,7 @@ where
             .copy_from_slice(self.dashboard_base.as_slice());
         {
             let mut surface = FrameSurface::new(self.back.as_mut_slice());
-            dashboard::render_dashboard_dynamic(&mut surface, snapshot);
+            render::render_dashboard_dynamic(&mut surface, snapshot, &self.palette);
         }
 
         self.toast_until = None;
@@ -417,7 +462,14 @@ where
     ) -> Result<(), GcError<E>> {
         {
             let mut surface = FrameSurface::new(self.back.as_mut_slice());
-            menu::render_identify(&mut surface, device_id, ipv4, hostname, border_phase_on);
+            render::render_identify(
+                &mut surface,
+                &self.palette,
+                device_id,
+                ipv4,
+                hostname,
+                border_phase_on,
+            );
         }
         self.toast_until = None;
         self.present_back(FlushStrategy::Full).await?;
@@ -431,7 +483,7 @@ where
         }
 
         let mut surface = FrameSurface::new(self.dashboard_base.as_mut_slice());
-        dashboard::render_dashboard_base(&mut surface);
+        render::render_dashboard_base(&mut surface, &self.palette);
         self.dashboard_base_ready = true;
     }
 
@@ -587,69 +639,3 @@ fn format_ma_2dp_4(i: Field<u16>) -> [u8; 4] {
         }
     }
 }
-
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-enum OkValueError {
-    Over,
-}
-
-fn format_ok_value_6(micros: u32, unit: u8) -> Result<[u8; 6], OkValueError> {
-    let milli = (micros + 500) / 1_000;
-    if milli < 10_000 {
-        let int = milli / 1_000;
-        let frac = milli % 1_000;
-        return Ok([
-            b'0' + int as u8,
-            b'.',
-            b'0' + (frac / 100) as u8,
-            b'0' + ((frac / 10) % 10) as u8,
-            b'0' + (frac % 10) as u8,
-            unit,
-        ]);
-    }
-
-    let centi = (micros + 5_000) / 10_000;
-    if centi < 10_000 {
-        let int = centi / 100;
-        let frac = centi % 100;
-        return Ok([
-            b'0' + (int / 10) as u8,
-            b'0' + (int % 10) as u8,
-            b'.',
-            b'0' + (frac / 10) as u8,
-            b'0' + (frac % 10) as u8,
-            unit,
-        ]);
-    }
-
-    let deci = (micros + 50_000) / 100_000;
-    if deci < 10_000 {
-        let int = deci / 10;
-        let frac = deci % 10;
-        return Ok([
-            b'0' + (int / 100) as u8,
-            b'0' + ((int / 10) % 10) as u8,
-            b'0' + (int % 10) as u8,
-            b'.',
-            b'0' + frac as u8,
-            unit,
-        ]);
-    }
-
-    Err(OkValueError::Over)
-}
-
-fn render_char_6x8_scaled(ch: u8, out: &mut [u8; 144]) {
-    font6x8::render_char_6x8_scaled_custom(ch, out, TILE_W, TILE_H, GLYPH_SX, GLYPH_SY);
-}
-
-fn render_char_6x8_scaled_custom(
-    ch: u8,
-    out: &mut [u8],
-    tile_w: u16,
-    tile_h: u16,
-    glyph_sx: u16,
-    glyph_sy: u16,
-) {
-    font6x8::render_char_6x8_scaled_custom(ch, out, tile_w, tile_h, glyph_sx, glyph_sy);
-}
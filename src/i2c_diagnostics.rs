@@ -0,0 +1,15 @@
+use embedded_hal::i2c::ErrorKind;
+
+pub use isolapurr_firmware_core::i2c_diagnostics::*;
+
+/// Classifies a bus error for the diagnostics counters. esp-hal reports its
+/// software timeout (and an incomplete command sequence) as `ErrorKind::Other`,
+/// so `Other` is counted as a timeout.
+pub fn classify_i2c_error<E: embedded_hal::i2c::Error>(error: &E) -> I2cErrorClass {
+    match error.kind() {
+        ErrorKind::NoAcknowledge(_) => I2cErrorClass::Nack,
+        ErrorKind::ArbitrationLoss => I2cErrorClass::Arbitration,
+        ErrorKind::Other => I2cErrorClass::Timeout,
+        _ => I2cErrorClass::Other,
+    }
+}
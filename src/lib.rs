@@ -2,14 +2,37 @@
 
 extern crate alloc;
 
+pub mod button_settings;
 pub mod buzzer;
+pub mod charge_termination;
+pub mod debug_regs;
+pub mod display_capture;
+pub mod display_settings;
 pub mod display_ui;
+#[cfg(feature = "net_http")]
+pub mod fast_charge_probe;
+pub mod i2c_diagnostics;
 pub mod idle_bias;
+#[cfg(feature = "net_http")]
+pub mod jsonl_frame;
+#[cfg(feature = "net_http")]
+pub mod jsonl_tcp;
+pub mod log_ring;
+#[cfg(feature = "modbus_tcp")]
+pub mod modbus;
 pub mod pd_i2c;
+pub mod port_data;
+pub mod port_power;
 pub mod power_config;
 pub mod prompt_tone;
 #[cfg(feature = "net_http")]
 pub mod provisioning;
+pub mod reset_report;
+#[cfg(feature = "net_http")]
+pub mod scpi;
+#[cfg(feature = "net_http")]
+pub mod session;
+pub mod sound_settings;
 pub mod telemetry;
 pub mod thermal;
 
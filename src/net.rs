@@ -17,16 +17,42 @@ use esp_radio::{
     wifi::{self, ClientConfig, ModeConfig, WifiController, WifiDevice, WifiEvent},
 };
 use heapless::{String as HString, Vec};
-use isolapurr_usb_hub::display_ui::{NormalUiPortBadge, NormalUiPortMode};
+use isolapurr_api::device::Capabilities;
+use isolapurr_api::diagnostics::{I2cBusId, I2cProbeResult};
+use isolapurr_api::ports::{Port, PortCapabilities, PortsResponse};
+use isolapurr_api::{WriteJson, errors};
+use isolapurr_usb_hub::button_settings::{ButtonAction, ButtonGesture, ButtonSettings};
+use isolapurr_usb_hub::display_capture::{Base64Encoder, bmp_rgb565_file_len, bmp_rgb565_header};
+use isolapurr_usb_hub::display_settings::{
+    BacklightLevel, DisplayRotation, DisplaySettings, DisplayTheme,
+};
+use isolapurr_usb_hub::display_ui::{
+    DISPLAY_HEIGHT, DISPLAY_WIDTH, NormalUiPortBadge, NormalUiPortMode,
+};
+use isolapurr_usb_hub::i2c_diagnostics::{I2cBusCounters, I2cLineState};
 use isolapurr_usb_hub::idle_bias::{IDLE_BIAS_POINT_COUNT, IdleBiasMetadata};
+use isolapurr_usb_hub::pd_i2c::{PD_I2C_ADDRESSES, pd_i2c_device};
 use isolapurr_usb_hub::power_config::{
-    LightLoadMode, ManualTpsConfig, ManualUsbCPathMode, PowerConfig, Sw2303CapabilityReadback,
-    Sw2303LineCompensation, TpsCdcRise, TpsMode, UsbCCapabilityConfig,
+    DEFAULT_SW2303_LINE_COMPENSATION, LightLoadMode, MANUAL_DEFAULT_CURRENT_MA,
+    MANUAL_DEFAULT_VOLTAGE_MV, ManualTpsConfig, ManualUsbCPathMode, PowerConfig, PowerHardwareKind,
+    Sw2303CapabilityReadback, Sw2303LineCompensation, TpsCdcRise, TpsMode, UsbCCapabilityConfig,
+    quantize_manual_current_ma, quantize_manual_voltage_mv,
 };
 use isolapurr_usb_hub::provisioning::{
     DEFAULT_USB_C_DOWNSTREAM_ROUTE, UsbCDownstreamRoute, WifiCredentials,
 };
 use isolapurr_usb_hub::release_version;
+use isolapurr_usb_hub::scpi::{
+    SCPI_MAX_LINE_LEN, SCPI_NAN, SCPI_PORT, ScpiCommand, ScpiError, ScpiErrorQueue, parse_command,
+    resolve_current_ma, resolve_voltage_mv, split_program, write_milli,
+};
+use isolapurr_usb_hub::sound_settings::{
+    CustomSoundPattern, MINUTES_PER_DAY, SOUND_SLOT_COUNT, SoundSettings, SoundSlot,
+    parse_minute_of_day, write_minute_of_day,
+};
+use isolapurr_usb_hub::telemetry::{
+    TELEMETRY_I2C_ADDRESSES, TelemetryI2cCounters, telemetry_i2c_device,
+};
 use isolapurr_usb_hub::thermal::ThermalTelemetry;
 use static_cell::StaticCell;
 
@@ -44,6 +70,7 @@ const WIFI_GATEWAY: Option<&str> = option_env!("USB_HUB_WIFI_GATEWAY");
 const WIFI_DNS: Option<&str> = option_env!("USB_HUB_WIFI_DNS");
 
 pub struct NetHandles {
+    pub stack: Stack<'static>,
     pub device_names: &'static DeviceNames,
     pub wifi_state: &'static WifiStateMutex,
 }
@@ -101,119 +128,46 @@ pub type WifiStateMutex = Mutex<CriticalSectionRawMutex, WifiState>;
 static WIFI_STATE_CELL: StaticCell<WifiStateMutex> = StaticCell::new();
 static DEVICE_NAMES_CELL: StaticCell<DeviceNames> = StaticCell::new();
 static RADIO_CONTROLLER: StaticCell<RadioController<'static>> = StaticCell::new();
-static NET_RESOURCES: StaticCell<StackResources<8>> = StaticCell::new();
+// HTTP x3, SCPI, Modbus, JSONL console x2, mDNS and DHCP, plus one spare.
+static NET_RESOURCES: StaticCell<StackResources<10>> = StaticCell::new();
 static API_STATE_CELL: StaticCell<ApiSharedMutex> = StaticCell::new();
 static WIFI_APPLY_SIGNAL: Signal<CriticalSectionRawMutex, ()> = Signal::new();
 
 // --- HTTP API (Plan #0005) -------------------------------------------------
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum ApiPortId {
-    PortA,
-    PortC,
-}
-
-impl ApiPortId {
-    pub const fn as_str(self) -> &'static str {
-        match self {
-            ApiPortId::PortA => "port_a",
-            ApiPortId::PortC => "port_c",
-        }
-    }
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum ApiTelemetryStatus {
-    Ok,
-    NotInserted,
-    Error,
-    Overrange,
-}
-
-impl ApiTelemetryStatus {
-    pub const fn as_str(self) -> &'static str {
-        match self {
-            ApiTelemetryStatus::Ok => "ok",
-            ApiTelemetryStatus::NotInserted => "not_inserted",
-            ApiTelemetryStatus::Error => "error",
-            ApiTelemetryStatus::Overrange => "overrange",
-        }
-    }
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub struct ApiPortTelemetry {
-    pub status: ApiTelemetryStatus,
-    pub voltage_mv: Option<u32>,
-    pub current_ma: Option<u32>,
-    pub power_mw: Option<u32>,
-    pub sample_uptime_ms: u64,
-}
-
-impl ApiPortTelemetry {
-    pub const fn unknown() -> Self {
-        Self {
-            status: ApiTelemetryStatus::Error,
-            voltage_mv: None,
-            current_ma: None,
-            power_mw: None,
-            sample_uptime_ms: 0,
-        }
-    }
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub struct ApiPortState {
-    pub power_enabled: bool,
-    pub data_connected: bool,
-    pub replugging: bool,
-    pub busy: bool,
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub struct ApiHubSnapshot {
-    pub upstream_connected: bool,
-    pub isolated_usb_fault: bool,
-    pub isolated_downstream_connected: bool,
-    pub isolated_usb_ready: bool,
-    pub usb_c_downstream_route: UsbCDownstreamRoute,
-    pub usb_c_downstream_persisted: bool,
-}
-
-impl ApiHubSnapshot {
-    pub const fn unknown() -> Self {
-        Self {
-            upstream_connected: false,
-            isolated_usb_fault: false,
-            isolated_downstream_connected: false,
-            isolated_usb_ready: false,
-            usb_c_downstream_route: DEFAULT_USB_C_DOWNSTREAM_ROUTE,
-            usb_c_downstream_persisted: false,
-        }
-    }
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub struct ApiPortSnapshot {
-    pub telemetry: ApiPortTelemetry,
-    pub telemetry_raw: Option<ApiPortTelemetry>,
-    pub state: ApiPortState,
-}
+// Wire types shared with host tools; the `Api*` names are kept for the firmware code.
+pub use isolapurr_api::ports::{
+    HubStatus as ApiHubSnapshot, PortDataMode, PortId as ApiPortId,
+    PortSnapshot as ApiPortSnapshot, PortState as ApiPortState, PortTelemetry as ApiPortTelemetry,
+    TelemetryStatus as ApiTelemetryStatus,
+};
+pub use isolapurr_api::power::ActiveProtocol as ApiActiveProtocol;
+
+/// Reported as `capabilities` by `info` and the ports endpoints on every transport.
+pub const DEVICE_CAPABILITIES: Capabilities = Capabilities {
+    identify: true,
+    sound: true,
+    display: true,
+    display_screenshot: true,
+    buttons: true,
+    scpi: true,
+    jsonl_tcp: true,
+    i2c_diagnostics: true,
+    register_debug: true,
+    crash_report: true,
+    remote_logs: true,
+    port_power_policy: true,
+    port_data_mode: true,
+    charge_termination: true,
+    sessions: true,
+    fast_charge_probe: true,
+};
 
-impl ApiPortSnapshot {
-    pub const fn unknown() -> Self {
-        Self {
-            telemetry: ApiPortTelemetry::unknown(),
-            telemetry_raw: None,
-            state: ApiPortState {
-                power_enabled: false,
-                data_connected: false,
-                replugging: false,
-                busy: false,
-            },
-        }
-    }
-}
+/// Both ports support data replug and power switching.
+pub const PORT_CAPABILITIES: PortCapabilities = PortCapabilities {
+    data_replug: true,
+    power_set: true,
+};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct ApiPortsSnapshot {
@@ -295,37 +249,6 @@ impl ApiPdSnapshot {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum ApiActiveProtocol {
-    Pd,
-    Pps,
-    Qc20,
-    Qc30,
-    Fcp,
-    Afc,
-    Scp,
-    Pe20,
-    Bc12,
-    Sfcp,
-}
-
-impl ApiActiveProtocol {
-    pub const fn as_str(self) -> &'static str {
-        match self {
-            Self::Pd => "pd",
-            Self::Pps => "pps",
-            Self::Qc20 => "qc20",
-            Self::Qc30 => "qc30",
-            Self::Fcp => "fcp",
-            Self::Afc => "afc",
-            Self::Scp => "scp",
-            Self::Pe20 => "pe20",
-            Self::Bc12 => "bc12",
-            Self::Sfcp => "sfcp",
-        }
-    }
-}
-
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct ApiPowerLock {
     pub owner: u32,
@@ -363,7 +286,7 @@ impl ApiIdleBiasErrorCode {
             Self::AttachDetected => "attach_detected",
             Self::ControllerNotReady => "controller_not_ready",
             Self::TelemetryUnavailable => "telemetry_unavailable",
-            Self::EepromFailed => "eeprom_failed",
+            Self::EepromFailed => errors::EEPROM_FAILED,
         }
     }
 
@@ -446,15 +369,78 @@ impl ApiPowerSnapshot {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ApiSoundSnapshot {
+    pub settings: SoundSettings,
+    pub persisted: bool,
+    pub custom_patterns: [Option<CustomSoundPattern>; SOUND_SLOT_COUNT],
+    /// A host has supplied the local time, so quiet hours can be evaluated.
+    pub clock_synced: bool,
+    pub quiet_active: bool,
+}
+
+impl ApiSoundSnapshot {
+    pub const fn unknown() -> Self {
+        Self {
+            settings: SoundSettings::defaults(),
+            persisted: false,
+            custom_patterns: [None; SOUND_SLOT_COUNT],
+            clock_synced: false,
+            quiet_active: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ApiDisplaySnapshot {
+    pub settings: DisplaySettings,
+    pub persisted: bool,
+    /// Current idle stage of the backlight (on, dimmed, or off).
+    pub backlight: BacklightLevel,
+}
+
+impl ApiDisplaySnapshot {
+    pub const fn unknown() -> Self {
+        Self {
+            settings: DisplaySettings::defaults(),
+            persisted: false,
+            backlight: BacklightLevel::Full,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ApiButtonsSnapshot {
+    pub settings: ButtonSettings,
+    pub persisted: bool,
+}
+
+impl ApiButtonsSnapshot {
+    pub const fn unknown() -> Self {
+        Self {
+            settings: ButtonSettings::defaults(),
+            persisted: false,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ApiPortAction {
     Replug,
     Power { enabled: bool },
+    Data { mode: PortDataMode },
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ApiPowerConfigCommand {
-    Set { config: PowerConfig },
+    Set {
+        config: PowerConfig,
+    },
+    /// Takes effect like `Set` but leaves EEPROM U21 untouched, so `persisted`
+    /// reads false until the next `Set`.
+    Apply {
+        config: PowerConfig,
+    },
     Defaults,
 }
 
@@ -471,6 +457,34 @@ pub enum ApiIdleBiasCommand {
     Run,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApiSoundCommand {
+    Set {
+        settings: SoundSettings,
+        local_minute: Option<u16>,
+    },
+    SetPattern {
+        slot: SoundSlot,
+        pattern: CustomSoundPattern,
+    },
+    ClearPattern {
+        slot: SoundSlot,
+    },
+    Defaults,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApiDisplayCommand {
+    Set { settings: DisplaySettings },
+    Defaults,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApiButtonsCommand {
+    Set { settings: ButtonSettings },
+    Defaults,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ApiSettingsResetScope {
     Other,
@@ -493,6 +507,15 @@ pub struct ApiPendingActions {
     pub power_runtime: Option<ApiPowerRuntimeCommand>,
     pub idle_bias: Option<ApiIdleBiasCommand>,
     pub settings_reset: Option<ApiSettingsResetScope>,
+    pub sound: Option<ApiSoundCommand>,
+    pub display: Option<ApiDisplayCommand>,
+    pub buttons: Option<ApiButtonsCommand>,
+    pub i2c: Option<ApiI2cCommand>,
+    pub debug: Option<ApiDebugCommand>,
+    pub logs: Option<LogSettings>,
+    pub port_power: Option<PortPowerSettings>,
+    pub charge_termination: Option<ChargeTerminationSettings>,
+    pub fast_charge_probe: Option<ApiFastChargeProbeCommand>,
 }
 
 impl ApiPendingActions {
@@ -505,6 +528,15 @@ impl ApiPendingActions {
             power_runtime: None,
             idle_bias: None,
             settings_reset: None,
+            sound: None,
+            display: None,
+            buttons: None,
+            i2c: None,
+            debug: None,
+            logs: None,
+            port_power: None,
+            charge_termination: None,
+            fast_charge_probe: None,
         }
     }
 }
@@ -516,6 +548,16 @@ pub struct ApiSharedState {
     pub pd: ApiPdSnapshot,
     pub power: ApiPowerSnapshot,
     pub idle_bias: ApiIdleBiasSnapshot,
+    pub sound: ApiSoundSnapshot,
+    pub display: ApiDisplaySnapshot,
+    pub buttons: ApiButtonsSnapshot,
+    pub i2c: ApiI2cSnapshot,
+    pub debug: DebugSession,
+    pub reset: ResetReport,
+    pub logs: ApiLogsSnapshot,
+    pub port_power: ApiPortPowerSnapshot,
+    pub charge_termination: ApiChargeTerminationSnapshot,
+    pub fast_charge_probe: FastChargeProbeReport,
     /// Monotonically increments for each accepted identify request. The runtime
     /// consumes this edge and restarts its fixed-duration local presentation.
     pub identify_sequence: u32,
@@ -540,6 +582,16 @@ impl ApiSharedState {
             pd: ApiPdSnapshot::unknown(),
             power: ApiPowerSnapshot::unknown(),
             idle_bias: ApiIdleBiasSnapshot::unknown(),
+            sound: ApiSoundSnapshot::unknown(),
+            display: ApiDisplaySnapshot::unknown(),
+            buttons: ApiButtonsSnapshot::unknown(),
+            i2c: ApiI2cSnapshot::unknown(),
+            debug: DebugSession::new(),
+            reset: ResetReport::unknown(),
+            logs: ApiLogsSnapshot::unknown(),
+            port_power: ApiPortPowerSnapshot::unknown(),
+            charge_termination: ApiChargeTerminationSnapshot::unknown(),
+            fast_charge_probe: FastChargeProbeReport::idle(),
             identify_sequence: 0,
             identify_requested_at_ms: 0,
             identify_rendered_sequence: 0,
@@ -549,6 +601,23 @@ impl ApiSharedState {
             pending: ApiPendingActions::empty(),
         }
     }
+
+    pub fn port(&self, port_id: ApiPortId) -> Port {
+        let snapshot = match port_id {
+            ApiPortId::PortA => &self.ports.port_a,
+            ApiPortId::PortC => &self.ports.port_c,
+        };
+        Port::new(port_id, snapshot, PORT_CAPABILITIES)
+    }
+
+    /// Body of `GET /api/v1/ports`, also the `result` of JSONL `ports.get`.
+    pub fn ports_response(&self) -> PortsResponse {
+        PortsResponse {
+            hub: self.hub,
+            capabilities: DEVICE_CAPABILITIES,
+            ports: [self.port(ApiPortId::PortA), self.port(ApiPortId::PortC)],
+        }
+    }
 }
 
 pub type ApiSharedMutex = Mutex<CriticalSectionRawMutex, ApiSharedState>;
@@ -610,7 +679,7 @@ pub fn spawn_wifi_mdns_http(
     let rng = Rng::new();
     let seed = (rng.random() as u64) << 32 | rng.random() as u64;
 
-    let resources = NET_RESOURCES.init(StackResources::<8>::new());
+    let resources = NET_RESOURCES.init(StackResources::<10>::new());
     let (stack, runner) = embassy_net::new(wifi_device, net_cfg, resources, seed);
 
     spawner
@@ -633,10 +702,14 @@ pub fn spawn_wifi_mdns_http(
         port: HTTP_PORT,
     };
     spawner.spawn(mdns::mdns_task(stack, mdns_cfg)).ok()?;
+    spawner
+        .spawn(syslog_task(stack, device_names, api_state))
+        .ok()?;
 
     spawner.spawn(net_task(runner)).ok()?;
 
     Some(NetHandles {
+        stack,
         device_names,
         wifi_state,
     })
@@ -805,7 +878,7 @@ async fn wifi_task(
             continue;
         }
 
-        info!("Connecting to Wi-Fi SSID=\"{}\"", ssid.as_str());
+        hub_log!(Info, "Connecting to Wi-Fi SSID=\"{}\"", ssid.as_str());
         match controller.connect_async().await {
             Ok(()) => {
                 info!("Wi-Fi connect_async returned Ok; waiting for IPv4 config");
@@ -816,7 +889,7 @@ async fn wifi_task(
                         break;
                     }
                     if retries >= 30 {
-                        warn!("Wi-Fi DHCP/static config not ready within timeout");
+                        hub_log!(Warn, "Wi-Fi DHCP/static config not ready within timeout");
                         {
                             let mut guard = state.lock().await;
                             guard.state = WifiConnectionState::Error;
@@ -860,7 +933,7 @@ async fn wifi_task(
                 if let Some(cfg) = stack.config_v4() {
                     let ip = cfg.address.address();
                     let gw = cfg.gateway.unwrap_or(Ipv4Address::UNSPECIFIED);
-                    info!("Wi-Fi link up: ip={} gw={}", ip, gw);
+                    hub_log!(Info, "Wi-Fi link up: ip={} gw={}", ip, gw);
                     {
                         let mut guard = state.lock().await;
                         guard.state = WifiConnectionState::Connected;
@@ -879,7 +952,7 @@ async fn wifi_task(
                 .await
                 {
                     Either::First(()) => {
-                        warn!("Wi-Fi STA disconnected; will retry");
+                        hub_log!(Warn, "Wi-Fi STA disconnected; will retry");
                         {
                             let mut guard = state.lock().await;
                             guard.state = WifiConnectionState::Error;
@@ -900,7 +973,7 @@ async fn wifi_task(
                         }
                     }
                     Either::Second(()) => {
-                        info!("Wi-Fi runtime configuration changed; reconnecting");
+                        hub_log!(Info, "Wi-Fi runtime configuration changed; reconnecting");
                         active_credentials = crate::wifi_credentials_cache();
                         if let Err(err) = controller.disconnect_async().await {
                             warn!(
@@ -938,6 +1011,22 @@ include!("net/http.rs");
 
 include!("net/names_config.rs");
 
+include!("net/i2c_state.rs");
+
+include!("net/debug_regs.rs");
+
+include!("net/crash_report.rs");
+
+include!("net/logs.rs");
+
+include!("net/port_power.rs");
+
+include!("net/charge_termination.rs");
+
+include!("net/sessions.rs");
+
+include!("net/fast_charge_probe.rs");
+
 #[cfg(test)]
 mod tests {
     use super::*;
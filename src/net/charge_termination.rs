@@ -0,0 +1,82 @@
+// Per-port charge-termination settings served by `ports.charge_termination_*`
+// and `/api/v1/ports/charge-termination`; persisted in EEPROM U21 by the main
+// loop. Completed sessions are reported in the port snapshot instead.
+
+use isolapurr_api::ports::ChargeTerminationAction;
+use isolapurr_usb_hub::charge_termination::{ChargeTerminationConfig, ChargeTerminationSettings};
+
+pub const CHARGE_TERMINATION_INVALID_MESSAGE: &str = "port must be port_a|port_c; action must be notify|power_off|drop_5v; threshold_ma 10..=3000, hold_s 10..=3600, min_charge_s 0..=43200";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ApiChargeTerminationSnapshot {
+    pub settings: ChargeTerminationSettings,
+    pub persisted: bool,
+}
+
+impl ApiChargeTerminationSnapshot {
+    pub const fn unknown() -> Self {
+        Self {
+            settings: ChargeTerminationSettings::defaults(),
+            persisted: false,
+        }
+    }
+}
+
+fn write_charge_termination_config_json(body: &mut String, config: &ChargeTerminationConfig) {
+    let _ = core::write!(
+        body,
+        "{{\"enabled\":{},\"action\":\"{}\",\"threshold_ma\":{},\"hold_s\":{},\"min_charge_s\":{}}}",
+        config.enabled,
+        config.action.as_str(),
+        config.threshold_ma,
+        config.hold_s,
+        config.min_charge_s
+    );
+}
+
+/// `ports.charge_termination_get` / `ports.charge_termination_set` result.
+pub fn write_charge_termination_json(body: &mut String, snapshot: &ApiChargeTerminationSnapshot) {
+    body.push_str("{\"port_a\":");
+    write_charge_termination_config_json(body, &snapshot.settings.usb_a);
+    body.push_str(",\"port_c\":");
+    write_charge_termination_config_json(body, &snapshot.settings.usb_c);
+    let _ = core::write!(body, ",\"persisted\":{}}}", snapshot.persisted);
+}
+
+/// Updates the port named by `port` on top of `current`; absent keys keep
+/// their value.
+pub fn parse_charge_termination_body(
+    body: &str,
+    current: ChargeTerminationSettings,
+) -> Option<ChargeTerminationSettings> {
+    let port = ApiPortId::parse(extract_body_string(body, "port")?.as_str())?;
+    let mut config = current.port(port);
+    set_bool_if_present(body, "enabled", &mut config.enabled);
+    if json_value_after_key_body(body, "action").is_some() {
+        config.action =
+            ChargeTerminationAction::parse(extract_body_string(body, "action")?.as_str())?;
+    }
+    if json_value_after_key_body(body, "threshold_ma").is_some() {
+        config.threshold_ma = extract_body_u16(body, "threshold_ma")?;
+    }
+    if json_value_after_key_body(body, "hold_s").is_some() {
+        config.hold_s = extract_body_u16(body, "hold_s")?;
+    }
+    if json_value_after_key_body(body, "min_charge_s").is_some() {
+        config.min_charge_s = extract_body_u16(body, "min_charge_s")?;
+    }
+    Some(current.with_port(port, config.validated().ok()?))
+}
+
+pub async fn try_set_charge_termination(
+    api_state: &'static ApiSharedMutex,
+    settings: ChargeTerminationSettings,
+) -> Result<(), ApiActionError> {
+    let mut guard = api_state.lock().await;
+    if guard.pending.charge_termination.is_some() || guard.pending.settings_reset.is_some() {
+        return Err(ApiActionError::Busy);
+    }
+    crate::reset_charge_termination_result();
+    guard.pending.charge_termination = Some(settings);
+    Ok(())
+}
@@ -0,0 +1,81 @@
+// Reset cause and persisted panic report for `info` and `diagnostics.crash`.
+// The firmware resolves `ApiSharedState::reset` once at boot; acknowledging
+// clears both the state and the RTC record.
+
+use isolapurr_usb_hub::reset_report::{CrashReport, ResetReport};
+
+/// The `reset` object of `info`: just enough to notice an outstanding crash.
+pub fn write_reset_summary_json(body: &mut String, reset: &ResetReport) {
+    let _ = core::write!(
+        body,
+        "{{\"reason\":\"{}\",\"hw_code\":{},\"crash_pending\":{},\"unacknowledged\":{}}}",
+        reset.reason.as_str(),
+        reset.hw_code,
+        reset.crash.is_some(),
+        reset.unacknowledged
+    );
+}
+
+/// `diagnostics.crash` result.
+pub fn write_crash_report_json(body: &mut String, reset: &ResetReport) {
+    let _ = core::write!(
+        body,
+        "{{\"reason\":\"{}\",\"hw_code\":{},\"unacknowledged\":{},\"crash\":",
+        reset.reason.as_str(),
+        reset.hw_code,
+        reset.unacknowledged
+    );
+    match &reset.crash {
+        Some(crash) => write_crash_json(body, crash),
+        None => {
+            let _ = body.push_str("null");
+        }
+    }
+    let _ = body.push('}');
+}
+
+fn write_crash_json(body: &mut String, crash: &CrashReport) {
+    let _ = core::write!(
+        body,
+        "{{\"reason\":\"{}\",\"hw_code\":{},\"uptime_ms\":{},\"message\":",
+        crash.reason.as_str(),
+        crash.hw_code,
+        crash.uptime_ms
+    );
+    write_json_string(body, crash.message.as_str());
+    let _ = body.push_str(",\"location\":");
+    match &crash.location {
+        Some(location) => {
+            let _ = body.push_str("{\"file\":");
+            write_json_string(body, location.file.as_str());
+            let _ = core::write!(
+                body,
+                ",\"line\":{},\"column\":{}}}",
+                location.line,
+                location.column
+            );
+        }
+        None => {
+            let _ = body.push_str("null");
+        }
+    }
+    let _ = body.push_str(",\"backtrace\":[");
+    for (index, address) in crash.backtrace().iter().enumerate() {
+        if index > 0 {
+            let _ = body.push(',');
+        }
+        let _ = core::write!(body, "\"{:#010x}\"", address);
+    }
+    let _ = body.push_str("]}");
+}
+
+/// Clears the outstanding crash from the state and the RTC record. Returns how
+/// many crashes were acknowledged.
+pub async fn acknowledge_crash(api_state: &'static ApiSharedMutex) -> u16 {
+    let mut guard = api_state.lock().await;
+    let cleared = guard.reset.unacknowledged;
+    guard.reset.crash = None;
+    guard.reset.unacknowledged = 0;
+    crate::acknowledge_crash_report();
+    cleared
+}
@@ -0,0 +1,132 @@
+// Raw SW2303/TPS55288 register access for the USB-only `debug.*` JSONL
+// methods. The session lives in `ApiSharedState`; reads and writes run in the
+// main loop, which owns both buses.
+
+use isolapurr_api::diagnostics::RegTarget;
+use isolapurr_usb_hub::debug_regs::{DebugSession, RegWrite};
+use isolapurr_usb_hub::pd_i2c::{SW2303_ADDR_7BIT, TPS55288_ADDR_7BIT};
+
+pub const DEBUG_LOCKED_MESSAGE: &str = "register debug is locked; confirm a debug.unlock challenge";
+pub const DEBUG_BAD_CODE_MESSAGE: &str = "unlock code is wrong or expired; request a new challenge";
+pub const DEBUG_BUSY_MESSAGE: &str = "another register or power action is in progress";
+pub const DEBUG_TARGET_INVALID_MESSAGE: &str = "target must be sw2303 or tps55288";
+pub const DEBUG_REG_INVALID_MESSAGE: &str = "register is outside the target's register map";
+pub const DEBUG_VALUE_INVALID_MESSAGE: &str = "value must be 0..=255";
+pub const DEBUG_BUS_UNAVAILABLE_MESSAGE: &str =
+    "SW2303 bus is parked or in POR; retry once USB-C power is up";
+pub const DEBUG_ACCESS_FAILED_MESSAGE: &str = "register transfer failed";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApiDebugCommand {
+    Read {
+        target: RegTarget,
+        start: u8,
+        count: u16,
+    },
+    Write {
+        target: RegTarget,
+        reg: u8,
+        value: u8,
+    },
+    /// Re-apply the saved power config after the session ended.
+    Restore,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApiDebugError {
+    Locked,
+    Busy,
+}
+
+pub const fn reg_target_address(target: RegTarget) -> u8 {
+    match target {
+        RegTarget::Sw2303 => SW2303_ADDR_7BIT,
+        RegTarget::Tps55288 => TPS55288_ADDR_7BIT,
+    }
+}
+
+/// Queues a register read or write for the main loop. Only an unlocked
+/// session may do so, and never next to a reset or an idle-bias run that
+/// reprograms the same chips.
+pub async fn try_debug_command(
+    api_state: &'static ApiSharedMutex,
+    command: ApiDebugCommand,
+) -> Result<(), ApiDebugError> {
+    let mut guard = api_state.lock().await;
+    if !guard.debug.is_unlocked() {
+        return Err(ApiDebugError::Locked);
+    }
+    if guard.pending.debug.is_some()
+        || guard.pending.settings_reset.is_some()
+        || guard.idle_bias.run.state == ApiIdleBiasRunState::Running
+    {
+        return Err(ApiDebugError::Busy);
+    }
+    guard.debug.touch(uptime_ms());
+    crate::reset_debug_reg_result();
+    guard.pending.debug = Some(command);
+    Ok(())
+}
+
+/// Ends the session. Returns whether the saved power config is being
+/// re-applied because the session had been unlocked.
+pub async fn end_debug_session(api_state: &'static ApiSharedMutex) -> Result<bool, ApiDebugError> {
+    let mut guard = api_state.lock().await;
+    if guard.pending.debug.is_some() {
+        return Err(ApiDebugError::Busy);
+    }
+    let restore = guard.debug.lock();
+    if restore {
+        crate::reset_debug_reg_result();
+        guard.pending.debug = Some(ApiDebugCommand::Restore);
+    }
+    Ok(restore)
+}
+
+fn write_reg_write_json(body: &mut String, write: &RegWrite) {
+    let _ = core::write!(
+        body,
+        "{{\"uptime_ms\":{},\"target\":\"{}\",\"reg\":{},\"value\":{},",
+        write.uptime_ms,
+        write.target.as_str(),
+        write.reg,
+        write.value,
+    );
+    for (key, value) in [("previous", write.previous), ("readback", write.readback)] {
+        let _ = match value {
+            Some(value) => core::write!(body, "\"{key}\":{value},"),
+            None => core::write!(body, "\"{key}\":null,"),
+        };
+    }
+    let _ = core::write!(body, "\"ok\":{}}}", write.ok);
+}
+
+/// `result` of `debug.status`.
+pub fn write_debug_status_json(body: &mut String, session: &DebugSession) {
+    let _ = core::write!(
+        body,
+        "{{\"unlocked\":{},\"idle_timeout_ms\":{},\"idle_remaining_ms\":",
+        session.is_unlocked(),
+        isolapurr_usb_hub::debug_regs::DEBUG_IDLE_TIMEOUT_MS,
+    );
+    match session.idle_remaining_ms(uptime_ms()) {
+        Some(remaining) => {
+            let _ = core::write!(body, "{remaining}");
+        }
+        None => {
+            let _ = body.push_str("null");
+        }
+    }
+    let _ = core::write!(
+        body,
+        ",\"total_writes\":{},\"writes\":[",
+        session.total_writes()
+    );
+    for (index, write) in session.writes().enumerate() {
+        if index > 0 {
+            let _ = body.push(',');
+        }
+        write_reg_write_json(body, &write);
+    }
+    let _ = body.push_str("]}");
+}
@@ -0,0 +1,65 @@
+// Guided fast-charge protocol probe served by `power.fast_charge_probe_*` and
+// `/api/v1/power/fast-charge-probe`. The main loop runs it and publishes its
+// report here; the saved power config is never touched.
+
+use isolapurr_api::json::write_field;
+use isolapurr_api::power::FastChargeProbeState;
+use isolapurr_usb_hub::fast_charge_probe::{FAST_CHARGE_PROBE_STEPS, FastChargeProbeReport};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApiFastChargeProbeCommand {
+    Run,
+    Cancel,
+}
+
+/// `power.fast_charge_probe_*` result.
+pub fn write_fast_charge_probe_json(body: &mut String, report: &FastChargeProbeReport) {
+    body.push('{');
+    let _ = write_field(body, true, "state", &report.state);
+    let _ = write_field(body, false, "family", &report.family);
+    let _ = write_field(body, false, "error", &report.error);
+    let _ = write_field(body, false, "started_uptime_ms", &report.started_uptime_ms);
+    let _ = write_field(body, false, "step_count", &(FAST_CHARGE_PROBE_STEPS as u32));
+    body.push_str(",\"results\":[");
+    for (index, result) in report.results().iter().enumerate() {
+        if index > 0 {
+            body.push(',');
+        }
+        let _ = result.write_json(body);
+    }
+    body.push_str("]}");
+}
+
+pub async fn try_fast_charge_probe(
+    api_state: &'static ApiSharedMutex,
+    command: ApiFastChargeProbeCommand,
+    owner: Option<u32>,
+) -> Result<(), ApiActionError> {
+    let mut guard = api_state.lock().await;
+    let now = uptime_ms();
+    if let Some(lock) = guard.power.lock {
+        if lock.expires_at_ms <= now {
+            guard.power.lock = None;
+        } else if owner != Some(lock.owner) {
+            return Err(ApiActionError::Busy);
+        }
+    }
+    if guard.pending.fast_charge_probe.is_some() {
+        return Err(ApiActionError::Busy);
+    }
+    if command == ApiFastChargeProbeCommand::Run
+        && (guard.ports.port_c.state.busy
+            || guard.pending.port_c.is_some()
+            || guard.pending.idle_bias.is_some()
+            || guard.pending.settings_reset.is_some()
+            || guard.idle_bias.run.state == ApiIdleBiasRunState::Running
+            || guard.fast_charge_probe.state == FastChargeProbeState::Running)
+    {
+        return Err(ApiActionError::Busy);
+    }
+    guard.pending.fast_charge_probe = Some(command);
+    if command == ApiFastChargeProbeCommand::Run {
+        guard.fast_charge_probe = FastChargeProbeReport::queued();
+    }
+    Ok(())
+}
@@ -62,6 +62,11 @@ pub fn spawn_http_tasks(
             ))
             .ok()?;
     }
+    spawner
+        .spawn(scpi_task(stack, device_names, api_state))
+        .ok()?;
+    #[cfg(feature = "modbus_tcp")]
+    spawner.spawn(modbus_task(stack, api_state)).ok()?;
     Some(())
 }
 
@@ -255,7 +260,7 @@ async fn handle_api_request(
                             socket,
                             "503 Service Unavailable",
                             allow_origin,
-                            "display_unavailable",
+                            errors::DISPLAY_UNAVAILABLE,
                             "display did not acknowledge the identify frame",
                             true,
                         )
@@ -267,7 +272,7 @@ async fn handle_api_request(
                         socket,
                         "409 Conflict",
                         allow_origin,
-                        "busy",
+                        errors::BUSY,
                         "device is in a safety or reset state",
                         true,
                     )
@@ -307,51 +312,20 @@ async fn handle_api_request(
             }
 
             let _ = core::write!(body, ",\"is_static\":{}", wifi.is_static);
-            let _ = body.push_str("}},\"capabilities\":{\"identify\":true}}");
+            let _ = body.push_str("}},\"reset\":");
+            let reset = { api_state.lock().await.reset };
+            write_reset_summary_json(&mut body, &reset);
+            let _ = body.push_str(",\"capabilities\":");
+            let _ = DEVICE_CAPABILITIES.write_json(&mut body);
+            let _ = body.push('}');
 
             write_json_response(socket, "200 OK", allow_origin, body.as_str()).await?;
             return Ok(());
         }
         ("GET", "/api/v1/ports") => {
-            let state = { *api_state.lock().await };
+            let ports = { api_state.lock().await.ports_response() };
             let mut body = String::new();
-            let _ = body.push_str("{\"hub\":{\"upstream_connected\":");
-            let _ = body.push_str(if state.hub.upstream_connected {
-                "true"
-            } else {
-                "false"
-            });
-            let _ = body.push_str(",\"isolated_usb_fault\":");
-            let _ = body.push_str(if state.hub.isolated_usb_fault {
-                "true"
-            } else {
-                "false"
-            });
-            let _ = body.push_str(",\"isolated_downstream_connected\":");
-            let _ = body.push_str(if state.hub.isolated_downstream_connected {
-                "true"
-            } else {
-                "false"
-            });
-            let _ = body.push_str(",\"isolated_usb_ready\":");
-            let _ = body.push_str(if state.hub.isolated_usb_ready {
-                "true"
-            } else {
-                "false"
-            });
-            let _ = body.push_str(",\"usb_c_downstream_route\":\"");
-            let _ = body.push_str(state.hub.usb_c_downstream_route.as_str());
-            let _ = body.push_str("\",\"usb_c_downstream_persisted\":");
-            let _ = body.push_str(if state.hub.usb_c_downstream_persisted {
-                "true"
-            } else {
-                "false"
-            });
-            let _ = body.push_str("},\"capabilities\":{\"identify\":true},\"ports\":[");
-            write_port_json(&mut body, ApiPortId::PortA, "USB-A", &state.ports.port_a);
-            let _ = body.push(',');
-            write_port_json(&mut body, ApiPortId::PortC, "USB-C", &state.ports.port_c);
-            let _ = body.push_str("]}");
+            let _ = ports.write_json(&mut body);
             write_json_response(socket, "200 OK", allow_origin, body.as_str()).await?;
             return Ok(());
         }
@@ -382,7 +356,7 @@ async fn handle_api_request(
                     socket,
                     "400 Bad Request",
                     allow_origin,
-                    "bad_request",
+                    errors::BAD_REQUEST,
                     "missing or invalid power config",
                     false,
                 )
@@ -404,7 +378,7 @@ async fn handle_api_request(
                             socket,
                             "500 Internal Server Error",
                             allow_origin,
-                            "eeprom_failed",
+                            errors::EEPROM_FAILED,
                             "Power configuration could not be saved to EEPROM U21",
                             true,
                         )
@@ -416,7 +390,7 @@ async fn handle_api_request(
                         socket,
                         "409 Conflict",
                         allow_origin,
-                        "busy",
+                        errors::BUSY,
                         "power configuration is busy or locked",
                         true,
                     )
@@ -431,7 +405,7 @@ async fn handle_api_request(
                     socket,
                     "400 Bad Request",
                     allow_origin,
-                    "bad_request",
+                    errors::BAD_REQUEST,
                     "missing or invalid power runtime command",
                     false,
                 )
@@ -451,7 +425,7 @@ async fn handle_api_request(
                             socket,
                             "500 Internal Server Error",
                             allow_origin,
-                            "runtime_apply_failed",
+                            errors::RUNTIME_APPLY_FAILED,
                             "Power runtime command could not be applied",
                             true,
                         )
@@ -463,7 +437,7 @@ async fn handle_api_request(
                         socket,
                         "409 Conflict",
                         allow_origin,
-                        "busy",
+                        errors::BUSY,
                         "power runtime control is busy or locked",
                         true,
                     )
@@ -478,7 +452,7 @@ async fn handle_api_request(
                     socket,
                     "400 Bad Request",
                     allow_origin,
-                    "bad_request",
+                    errors::BAD_REQUEST,
                     "missing or invalid correction_enabled",
                     false,
                 )
@@ -504,7 +478,7 @@ async fn handle_api_request(
                             socket,
                             "500 Internal Server Error",
                             allow_origin,
-                            "eeprom_failed",
+                            errors::EEPROM_FAILED,
                             "Idle-bias correction could not be saved to EEPROM U21",
                             true,
                         )
@@ -516,7 +490,7 @@ async fn handle_api_request(
                         socket,
                         "409 Conflict",
                         allow_origin,
-                        "busy",
+                        errors::BUSY,
                         "idle-bias settings are busy or locked",
                         true,
                     )
@@ -527,7 +501,7 @@ async fn handle_api_request(
                         socket,
                         "409 Conflict",
                         allow_origin,
-                        "dataset_missing",
+                        errors::DATASET_MISSING,
                         "Run USB-C idle-bias calibration before enabling correction",
                         false,
                     )
@@ -550,7 +524,7 @@ async fn handle_api_request(
                             socket,
                             "500 Internal Server Error",
                             allow_origin,
-                            "eeprom_failed",
+                            errors::EEPROM_FAILED,
                             "Power defaults could not be saved to EEPROM U21",
                             true,
                         )
@@ -562,7 +536,7 @@ async fn handle_api_request(
                         socket,
                         "409 Conflict",
                         allow_origin,
-                        "busy",
+                        errors::BUSY,
                         "power configuration is busy or locked",
                         true,
                     )
@@ -586,7 +560,7 @@ async fn handle_api_request(
                         socket,
                         "409 Conflict",
                         allow_origin,
-                        "busy",
+                        errors::BUSY,
                         "idle-bias calibration is busy or locked",
                         true,
                     )
@@ -609,7 +583,7 @@ async fn handle_api_request(
                             socket,
                             "500 Internal Server Error",
                             allow_origin,
-                            "eeprom_failed",
+                            errors::EEPROM_FAILED,
                             "Idle-bias dataset could not be cleared from EEPROM U21",
                             true,
                         )
@@ -621,7 +595,7 @@ async fn handle_api_request(
                         socket,
                         "409 Conflict",
                         allow_origin,
-                        "busy",
+                        errors::BUSY,
                         "idle-bias settings are busy or locked",
                         true,
                     )
@@ -637,7 +611,7 @@ async fn handle_api_request(
                     socket,
                     "400 Bad Request",
                     allow_origin,
-                    "bad_request",
+                    errors::BAD_REQUEST,
                     "missing owner",
                     false,
                 )
@@ -656,7 +630,7 @@ async fn handle_api_request(
                         socket,
                         "409 Conflict",
                         allow_origin,
-                        "busy",
+                        errors::BUSY,
                         "power configuration lock is owned by another host",
                         true,
                     )
@@ -671,7 +645,7 @@ async fn handle_api_request(
                     socket,
                     "400 Bad Request",
                     allow_origin,
-                    "bad_request",
+                    errors::BAD_REQUEST,
                     "missing owner",
                     false,
                 )
@@ -691,7 +665,7 @@ async fn handle_api_request(
                     socket,
                     "400 Bad Request",
                     allow_origin,
-                    "bad_request",
+                    errors::BAD_REQUEST,
                     "missing or invalid route",
                     false,
                 )
@@ -714,7 +688,7 @@ async fn handle_api_request(
                             socket,
                             "500 Internal Server Error",
                             allow_origin,
-                            "eeprom_failed",
+                            errors::EEPROM_FAILED,
                             "USB-C downstream route could not be saved to EEPROM U21",
                             true,
                         )
@@ -726,7 +700,7 @@ async fn handle_api_request(
                         socket,
                         "409 Conflict",
                         allow_origin,
-                        "busy",
+                        errors::BUSY,
                         "USB-C downstream route switch is busy",
                         true,
                     )
@@ -738,6 +712,16 @@ async fn handle_api_request(
         _ => {}
     }
 
+    // Before the per-port routes, which would read `power-policy` as a port id.
+    if handle_port_power_api_request(socket, method, path, body, allow_origin, api_state).await? {
+        return Ok(());
+    }
+    if handle_charge_termination_api_request(socket, method, path, body, allow_origin, api_state)
+        .await?
+    {
+        return Ok(());
+    }
+
     if let Some(rest) = path.strip_prefix("/api/v1/ports/") {
         let (port_id_s, tail) = rest.split_once('/').unwrap_or((rest, ""));
         let Some(port_id) = parse_port_id(port_id_s) else {
@@ -745,7 +729,7 @@ async fn handle_api_request(
                 socket,
                 "404 Not Found",
                 allow_origin,
-                "invalid_port",
+                errors::INVALID_PORT,
                 "invalid port",
                 false,
             )
@@ -754,22 +738,9 @@ async fn handle_api_request(
         };
 
         if method == "GET" && tail.is_empty() {
-            let ports = { api_state.lock().await.ports };
-            let port = match port_id {
-                ApiPortId::PortA => ports.port_a,
-                ApiPortId::PortC => ports.port_c,
-            };
-
+            let port = { api_state.lock().await.port(port_id) };
             let mut body = String::new();
-            write_port_json(
-                &mut body,
-                port_id,
-                match port_id {
-                    ApiPortId::PortA => "USB-A",
-                    ApiPortId::PortC => "USB-C",
-                },
-                &port,
-            );
+            let _ = port.write_json(&mut body);
             write_json_response(socket, "200 OK", allow_origin, body.as_str()).await?;
             return Ok(());
         }
@@ -791,7 +762,7 @@ async fn handle_api_request(
                         socket,
                         "409 Conflict",
                         allow_origin,
-                        "busy",
+                        errors::BUSY,
                         "port is busy",
                         true,
                     )
@@ -807,7 +778,7 @@ async fn handle_api_request(
                     socket,
                     "400 Bad Request",
                     allow_origin,
-                    "bad_request",
+                    errors::BAD_REQUEST,
                     "missing or invalid enabled",
                     false,
                 )
@@ -832,7 +803,48 @@ async fn handle_api_request(
                         socket,
                         "409 Conflict",
                         allow_origin,
-                        "busy",
+                        errors::BUSY,
+                        "port is busy",
+                        true,
+                    )
+                    .await?;
+                }
+            }
+            return Ok(());
+        }
+
+        if method == "POST" && tail == "data" {
+            let Some(mode) = parse_query_value(query, "mode")
+                .and_then(|mode| PortDataMode::parse(mode.as_str()))
+            else {
+                write_api_error(
+                    socket,
+                    "400 Bad Request",
+                    allow_origin,
+                    errors::BAD_REQUEST,
+                    "mode must be connected or charge_only",
+                    false,
+                )
+                .await?;
+                return Ok(());
+            };
+
+            match try_set_action(api_state, port_id, ApiPortAction::Data { mode }).await {
+                Ok(()) => {
+                    let mut body = String::new();
+                    let _ = core::write!(
+                        body,
+                        "{{\"accepted\":true,\"data_mode\":\"{}\"}}",
+                        mode.as_str()
+                    );
+                    write_json_response(socket, "200 OK", allow_origin, body.as_str()).await?;
+                }
+                Err(ApiActionError::Busy) => {
+                    write_api_error(
+                        socket,
+                        "409 Conflict",
+                        allow_origin,
+                        errors::BUSY,
                         "port is busy",
                         true,
                     )
@@ -886,7 +898,7 @@ async fn handle_api_request(
             socket,
             "403 Forbidden",
             allow_origin,
-            "unsafe_transport",
+            errors::UNSAFE_TRANSPORT,
             "Wi-Fi configuration changes require Web Serial or Local USB",
             false,
         )
@@ -899,7 +911,7 @@ async fn handle_api_request(
             socket,
             "403 Forbidden",
             allow_origin,
-            "unsafe_transport",
+            errors::UNSAFE_TRANSPORT,
             "Wi-Fi configuration changes require Web Serial or Local USB",
             false,
         )
@@ -913,7 +925,7 @@ async fn handle_api_request(
                 socket,
                 "400 Bad Request",
                 allow_origin,
-                "bad_request",
+                errors::BAD_REQUEST,
                 "missing or invalid scope",
                 false,
             )
@@ -925,7 +937,7 @@ async fn handle_api_request(
                 socket,
                 "403 Forbidden",
                 allow_origin,
-                "unsafe_transport",
+                errors::UNSAFE_TRANSPORT,
                 "Wi-Fi settings reset requires Web Serial or Local USB",
                 false,
             )
@@ -950,7 +962,7 @@ async fn handle_api_request(
                             socket,
                             "500 Internal Server Error",
                             allow_origin,
-                            "eeprom_failed",
+                            errors::EEPROM_FAILED,
                             "Non-Wi-Fi settings were partially cleared; refresh settings before retrying",
                             true,
                         )
@@ -961,7 +973,7 @@ async fn handle_api_request(
                         socket,
                         "500 Internal Server Error",
                         allow_origin,
-                        "eeprom_failed",
+                        errors::EEPROM_FAILED,
                         "Non-Wi-Fi settings could not be cleared from EEPROM U21",
                         true,
                     )
@@ -973,7 +985,7 @@ async fn handle_api_request(
                     socket,
                     "409 Conflict",
                     allow_origin,
-                    "busy",
+                    errors::BUSY,
                     "settings reset is busy or locked",
                     true,
                 )
@@ -988,7 +1000,7 @@ async fn handle_api_request(
             socket,
             "403 Forbidden",
             allow_origin,
-            "unsafe_transport",
+            errors::UNSAFE_TRANSPORT,
             "Reboot to apply Wi-Fi changes requires Web Serial or Local USB",
             false,
         )
@@ -996,205 +1008,65 @@ async fn handle_api_request(
         return Ok(());
     }
 
-    write_api_error(
-        socket,
-        "400 Bad Request",
-        allow_origin,
-        "bad_request",
-        "unknown endpoint",
-        false,
-    )
-    .await?;
-    Ok(())
-}
+    if handle_sound_api_request(socket, method, path, body, allow_origin, api_state).await? {
+        return Ok(());
+    }
 
-fn parse_port_id(s: &str) -> Option<ApiPortId> {
-    match s {
-        "port_a" => Some(ApiPortId::PortA),
-        "port_c" => Some(ApiPortId::PortC),
-        _ => None,
+    if handle_display_api_request(socket, method, path, body, allow_origin, api_state).await? {
+        return Ok(());
     }
-}
 
-fn parse_enabled_query(query: &str) -> Option<bool> {
-    // enabled={0|1}
-    for part in query.split('&') {
-        let (k, v) = part.split_once('=')?;
-        if k == "enabled" {
-            return match v {
-                "0" => Some(false),
-                "1" => Some(true),
-                _ => None,
-            };
-        }
+    if handle_buttons_api_request(socket, method, path, body, allow_origin, api_state).await? {
+        return Ok(());
     }
-    None
-}
 
-fn parse_owner_query(query: &str) -> Option<u32> {
-    for part in query.split('&') {
-        let (k, v) = part.split_once('=')?;
-        if k == "owner" {
-            return v.parse::<u32>().ok().filter(|v| *v != 0);
-        }
+    if handle_i2c_api_request(socket, method, path, query, allow_origin, api_state).await? {
+        return Ok(());
     }
-    None
-}
 
-fn parse_usb_c_downstream_route(query: &str) -> Option<UsbCDownstreamRoute> {
-    for part in query.split('&') {
-        let (key, value) = part.split_once('=')?;
-        if key != "route" {
-            continue;
-        }
-        return match value {
-            "mcu" => Some(UsbCDownstreamRoute::Mcu),
-            "usb_c" => Some(UsbCDownstreamRoute::UsbC),
-            _ => None,
-        };
+    if handle_crash_api_request(socket, method, path, allow_origin, api_state).await? {
+        return Ok(());
     }
-    None
-}
 
-fn parse_settings_reset_scope(query: &str) -> Option<&str> {
-    for part in query.split('&') {
-        let Some((key, value)) = part.split_once('=') else {
-            continue;
-        };
-        if key != "scope" {
-            continue;
-        }
-        return match value {
-            "wifi" | "other" => Some(value),
-            _ => None,
-        };
+    if handle_logs_api_request(socket, method, path, query, body, allow_origin, api_state).await? {
+        return Ok(());
     }
-    None
-}
 
-pub fn parse_power_config_body(body: &str) -> Option<PowerConfig> {
-    let hardware = extract_body_string(body, "hardware").unwrap_or_else(|| String::from("sw2303"));
-    if hardware.as_str() != "sw2303" {
-        return None;
+    if handle_sessions_api_request(socket, method, path, allow_origin).await? {
+        return Ok(());
     }
-    let tps_mode = match extract_body_string(body, "tps_mode")?.as_str() {
-        "auto_follow" => TpsMode::AutoFollow,
-        "manual" => TpsMode::Manual,
-        _ => return None,
-    };
-    let light_load_mode = match extract_body_string(body, "light_load_mode")
-        .unwrap_or_else(|| String::from("pfm"))
-        .as_str()
-    {
-        "pfm" => LightLoadMode::Pfm,
-        "fpwm" => LightLoadMode::Fpwm,
-        _ => return None,
-    };
-    let manual_path = match extract_body_string(body, "usb_c_path_mode")
-        .unwrap_or_else(|| String::from("default"))
-        .as_str()
-    {
-        "default" => ManualUsbCPathMode::Default,
-        "disconnect" => ManualUsbCPathMode::Disconnect,
-        "force" => ManualUsbCPathMode::Force,
-        _ => return None,
-    };
-    let tps_cdc_rise = match extract_body_u16(body, "tps_cdc_rise_mv").unwrap_or(0) {
-        0 => TpsCdcRise::V0,
-        100 => TpsCdcRise::V100,
-        200 => TpsCdcRise::V200,
-        300 => TpsCdcRise::V300,
-        400 => TpsCdcRise::V400,
-        500 => TpsCdcRise::V500,
-        600 => TpsCdcRise::V600,
-        700 => TpsCdcRise::V700,
-        _ => return None,
-    };
-    let sw2303_line_compensation = match extract_body_string(body, "sw2303_line_compensation")
-        .unwrap_or_else(|| String::from("50mohm"))
-        .as_str()
+
+    if handle_fast_charge_probe_api_request(socket, method, path, query, allow_origin, api_state)
+        .await?
     {
-        "off" => Sw2303LineCompensation::Off,
-        "0mohm" => Sw2303LineCompensation::Ohm0,
-        "50mohm" => Sw2303LineCompensation::MilliOhm50,
-        "100mohm" => Sw2303LineCompensation::MilliOhm100,
-        "150mohm" => Sw2303LineCompensation::MilliOhm150,
-        _ => return None,
-    };
-    let mut config = PowerConfig::defaults();
-    config.tps_mode = tps_mode;
-    config.light_load_mode = light_load_mode;
-    config.sw2303_line_compensation = sw2303_line_compensation;
-    config.manual = ManualTpsConfig {
-        voltage_mv: extract_body_u16(body, "voltage_mv").unwrap_or(config.manual.voltage_mv),
-        current_limit_ma: extract_body_u16(body, "current_limit_ma")
-            .unwrap_or(config.manual.current_limit_ma),
-        usb_c_path_mode: manual_path,
-        tps_cdc_rise,
-    };
-    if let Some(power_watts) = extract_body_u8(body, "power_watts") {
-        config.capability.power_watts = power_watts;
-    }
-    set_bool_if_present(body, "pd", &mut config.capability.pd_enabled);
-    set_bool_if_present(body, "qc20", &mut config.capability.qc20_enabled);
-    set_bool_if_present(body, "qc30", &mut config.capability.qc30_enabled);
-    set_bool_if_present(body, "fcp", &mut config.capability.fcp_enabled);
-    set_bool_if_present(body, "afc", &mut config.capability.afc_enabled);
-    set_bool_if_present(body, "scp", &mut config.capability.scp_enabled);
-    set_bool_if_present(body, "pe20", &mut config.capability.pe20_enabled);
-    set_bool_if_present(body, "bc12", &mut config.capability.bc12_enabled);
-    set_bool_if_present(body, "sfcp", &mut config.capability.sfcp_enabled);
-    set_bool_if_present(body, "pps", &mut config.capability.pps_enabled);
-    if let Some(pps3_limit_ma) = extract_body_u16(body, "pps3_limit_ma") {
-        config.capability.current.pps3_limit_ma = pps3_limit_ma;
-    }
-    set_bool_if_present(body, "pd_pps_5a", &mut config.capability.current.pd_pps_5a);
-    if let Some(type_c_broadcast_ma) = extract_body_u16(body, "type_c_broadcast_ma") {
-        config.capability.current.type_c_broadcast_ma = type_c_broadcast_ma;
-    }
-    if let Some(scp_limit_ma) = extract_body_u16(body, "scp_limit_ma") {
-        config.capability.current.scp_limit_ma = scp_limit_ma;
-    }
-    if let Some(fcp_afc_sfcp_limit_ma) = extract_body_u16(body, "fcp_afc_sfcp_limit_ma") {
-        config.capability.current.fcp_afc_sfcp_limit_ma = fcp_afc_sfcp_limit_ma;
+        return Ok(());
     }
-    set_bool_if_present(
-        body,
-        "qc20_20v_enabled",
-        &mut config.capability.fast_charge.qc20_20v_enabled,
-    );
-    set_bool_if_present(
-        body,
-        "qc30_20v_enabled",
-        &mut config.capability.fast_charge.qc30_20v_enabled,
-    );
-    set_bool_if_present(
-        body,
-        "pe20_20v_enabled",
-        &mut config.capability.fast_charge.pe20_20v_enabled,
-    );
-    set_bool_if_present(
-        body,
-        "non_pd_12v_enabled",
-        &mut config.capability.fast_charge.non_pd_12v_enabled,
-    );
-    apply_fixed_voltages_if_present(body, &mut config.capability)?;
-    config.validated().ok()
-}
-
-pub fn parse_idle_bias_body(body: &str) -> Option<bool> {
-    extract_body_bool(body, "correction_enabled")
-}
 
-pub fn parse_power_runtime_body(body: &str) -> Option<ApiPowerRuntimeCommand> {
-    let action = extract_body_string(body, "action")?;
-    let enabled = extract_body_bool(body, "enabled")?;
-    match action.as_str() {
-        "output" => Some(ApiPowerRuntimeCommand::SetOutputEnabled { enabled }),
-        "discharge" => Some(ApiPowerRuntimeCommand::SetDischargeEnabled { enabled }),
-        _ => None,
-    }
+    write_api_error(
+        socket,
+        "400 Bad Request",
+        allow_origin,
+        errors::BAD_REQUEST,
+        "unknown endpoint",
+        false,
+    )
+    .await?;
+    Ok(())
 }
 
+include!("http_request_parse.inc");
 include!("http_body_parse.inc");
 include!("http_response.rs");
+include!("http_sound.rs");
+include!("http_display.rs");
+include!("http_buttons.rs");
+include!("http_i2c.rs");
+include!("http_crash.rs");
+include!("http_logs.rs");
+include!("http_port_power.rs");
+include!("http_charge_termination.rs");
+include!("http_sessions.rs");
+include!("http_fast_charge_probe.rs");
+include!("scpi.rs");
+#[cfg(feature = "modbus_tcp")]
+include!("modbus.rs");
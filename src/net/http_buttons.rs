@@ -0,0 +1,122 @@
+async fn handle_buttons_api_request(
+    socket: &mut TcpSocket<'_>,
+    method: &str,
+    path: &str,
+    body: &str,
+    allow_origin: Option<&str>,
+    api_state: &'static ApiSharedMutex,
+) -> Result<bool, embassy_net::tcp::Error> {
+    let command = match (method, path) {
+        ("GET", "/api/v1/buttons") => {
+            let state = { *api_state.lock().await };
+            let mut body = String::new();
+            write_buttons_json(&mut body, &state.buttons);
+            write_json_response(socket, "200 OK", allow_origin, body.as_str()).await?;
+            return Ok(true);
+        }
+        ("PUT", "/api/v1/buttons") => {
+            let current = { api_state.lock().await.buttons.settings };
+            let Some(settings) = parse_button_settings_body(body, current) else {
+                write_api_error(
+                    socket,
+                    "400 Bad Request",
+                    allow_origin,
+                    errors::BAD_REQUEST,
+                    BUTTON_SETTINGS_INVALID_MESSAGE,
+                    false,
+                )
+                .await?;
+                return Ok(true);
+            };
+            ApiButtonsCommand::Set { settings }
+        }
+        ("POST", "/api/v1/buttons/defaults") => ApiButtonsCommand::Defaults,
+        _ => return Ok(false),
+    };
+
+    match try_set_buttons(api_state, command).await {
+        Ok(()) => {
+            if crate::wait_buttons_result().await {
+                let state = { *api_state.lock().await };
+                let mut body = String::new();
+                write_buttons_json(&mut body, &state.buttons);
+                write_json_response(socket, "200 OK", allow_origin, body.as_str()).await?;
+            } else {
+                write_api_error(
+                    socket,
+                    "500 Internal Server Error",
+                    allow_origin,
+                    errors::EEPROM_FAILED,
+                    "Button settings could not be saved to EEPROM U21",
+                    true,
+                )
+                .await?;
+            }
+        }
+        Err(ApiActionError::Busy) => {
+            write_api_error(
+                socket,
+                "409 Conflict",
+                allow_origin,
+                errors::BUSY,
+                "button settings are busy",
+                true,
+            )
+            .await?;
+        }
+    }
+    Ok(true)
+}
+
+pub const BUTTON_SETTINGS_INVALID_MESSAGE: &str = "gesture actions must be none|power_toggle|data_replug|cycle_preset|identify|lock|settings_menu, combo gestures cannot use port actions, locked must be a boolean";
+
+pub async fn try_set_buttons(
+    api_state: &'static ApiSharedMutex,
+    command: ApiButtonsCommand,
+) -> Result<(), ApiActionError> {
+    let mut guard = api_state.lock().await;
+    if guard.pending.buttons.is_some() || guard.pending.settings_reset.is_some() {
+        return Err(ApiActionError::Busy);
+    }
+    crate::reset_buttons_result();
+    guard.pending.buttons = Some(command);
+    Ok(())
+}
+
+/// Applies a partial update on top of `current`; absent keys keep their value.
+pub fn parse_button_settings_body(body: &str, current: ButtonSettings) -> Option<ButtonSettings> {
+    let mut settings = current;
+    for gesture in ButtonGesture::ALL {
+        if json_value_after_key_body(body, gesture.as_str()).is_some() {
+            let action = extract_body_string(body, gesture.as_str())?;
+            settings.set_action(gesture, ButtonAction::parse(action.as_str())?);
+        }
+    }
+    if json_value_after_key_body(body, "locked").is_some() {
+        settings.locked = extract_body_bool(body, "locked")?;
+    }
+    settings.validated().ok()
+}
+
+pub fn write_buttons_json(body: &mut String, buttons: &ApiButtonsSnapshot) {
+    let _ = body.push_str("{\"actions\":{");
+    for (index, gesture) in ButtonGesture::ALL.into_iter().enumerate() {
+        let _ = core::write!(
+            body,
+            "{}\"{}\":\"{}\"",
+            if index == 0 { "" } else { "," },
+            gesture.as_str(),
+            buttons.settings.action(gesture).as_str(),
+        );
+    }
+    let _ = core::write!(
+        body,
+        "}},\"locked\":{},\"persisted\":{}}}",
+        if buttons.settings.locked {
+            "true"
+        } else {
+            "false"
+        },
+        if buttons.persisted { "true" } else { "false" },
+    );
+}
@@ -0,0 +1,68 @@
+async fn handle_charge_termination_api_request(
+    socket: &mut TcpSocket<'_>,
+    method: &str,
+    path: &str,
+    body: &str,
+    allow_origin: Option<&str>,
+    api_state: &'static ApiSharedMutex,
+) -> Result<bool, embassy_net::tcp::Error> {
+    let settings = match (method, path) {
+        ("GET", "/api/v1/ports/charge-termination") => {
+            let state = { *api_state.lock().await };
+            let mut body = String::new();
+            write_charge_termination_json(&mut body, &state.charge_termination);
+            write_json_response(socket, "200 OK", allow_origin, body.as_str()).await?;
+            return Ok(true);
+        }
+        ("PUT", "/api/v1/ports/charge-termination") => {
+            let current = { api_state.lock().await.charge_termination.settings };
+            let Some(settings) = parse_charge_termination_body(body, current) else {
+                write_api_error(
+                    socket,
+                    "400 Bad Request",
+                    allow_origin,
+                    errors::BAD_REQUEST,
+                    CHARGE_TERMINATION_INVALID_MESSAGE,
+                    false,
+                )
+                .await?;
+                return Ok(true);
+            };
+            settings
+        }
+        _ => return Ok(false),
+    };
+
+    match try_set_charge_termination(api_state, settings).await {
+        Ok(()) => {
+            if crate::wait_charge_termination_result().await {
+                let state = { *api_state.lock().await };
+                let mut body = String::new();
+                write_charge_termination_json(&mut body, &state.charge_termination);
+                write_json_response(socket, "200 OK", allow_origin, body.as_str()).await?;
+            } else {
+                write_api_error(
+                    socket,
+                    "500 Internal Server Error",
+                    allow_origin,
+                    errors::EEPROM_FAILED,
+                    "Charge termination settings could not be saved to EEPROM U21",
+                    true,
+                )
+                .await?;
+            }
+        }
+        Err(ApiActionError::Busy) => {
+            write_api_error(
+                socket,
+                "409 Conflict",
+                allow_origin,
+                errors::BUSY,
+                "charge termination settings are busy",
+                true,
+            )
+            .await?;
+        }
+    }
+    Ok(true)
+}
@@ -0,0 +1,22 @@
+async fn handle_crash_api_request(
+    socket: &mut TcpSocket<'_>,
+    method: &str,
+    path: &str,
+    allow_origin: Option<&str>,
+    api_state: &'static ApiSharedMutex,
+) -> Result<bool, embassy_net::tcp::Error> {
+    let mut body = String::new();
+    match (method, path) {
+        ("GET", "/api/v1/diagnostics/crash") => {
+            let reset = { api_state.lock().await.reset };
+            write_crash_report_json(&mut body, &reset);
+        }
+        ("POST", "/api/v1/diagnostics/crash/ack") => {
+            let cleared = acknowledge_crash(api_state).await;
+            let _ = core::write!(body, "{{\"acknowledged\":{}}}", cleared);
+        }
+        _ => return Ok(false),
+    }
+    write_json_response(socket, "200 OK", allow_origin, body.as_str()).await?;
+    Ok(true)
+}
@@ -0,0 +1,206 @@
+async fn handle_display_api_request(
+    socket: &mut TcpSocket<'_>,
+    method: &str,
+    path: &str,
+    body: &str,
+    allow_origin: Option<&str>,
+    api_state: &'static ApiSharedMutex,
+) -> Result<bool, embassy_net::tcp::Error> {
+    let command = match (method, path) {
+        ("GET", "/api/v1/display") => {
+            let state = { *api_state.lock().await };
+            let mut body = String::new();
+            write_display_json(&mut body, &state.display);
+            write_json_response(socket, "200 OK", allow_origin, body.as_str()).await?;
+            return Ok(true);
+        }
+        ("PUT", "/api/v1/display") => {
+            let current = { api_state.lock().await.display.settings };
+            let Some(settings) = parse_display_settings_body(body, current) else {
+                write_api_error(
+                    socket,
+                    "400 Bad Request",
+                    allow_origin,
+                    errors::BAD_REQUEST,
+                    DISPLAY_SETTINGS_INVALID_MESSAGE,
+                    false,
+                )
+                .await?;
+                return Ok(true);
+            };
+            ApiDisplayCommand::Set { settings }
+        }
+        ("POST", "/api/v1/display/defaults") => ApiDisplayCommand::Defaults,
+        ("GET", "/api/v1/display/screenshot") => {
+            match crate::capture_display_screenshot().await {
+                Ok(frame) => write_display_screenshot_bmp(socket, allow_origin, &frame).await?,
+                Err(err) => {
+                    let (status, code, message) = display_screenshot_error(err);
+                    write_api_error(socket, status, allow_origin, code, message, true).await?;
+                }
+            }
+            return Ok(true);
+        }
+        _ => return Ok(false),
+    };
+
+    match try_set_display(api_state, command).await {
+        Ok(()) => {
+            if crate::wait_display_result().await {
+                let state = { *api_state.lock().await };
+                let mut body = String::new();
+                write_display_json(&mut body, &state.display);
+                write_json_response(socket, "200 OK", allow_origin, body.as_str()).await?;
+            } else {
+                write_api_error(
+                    socket,
+                    "500 Internal Server Error",
+                    allow_origin,
+                    errors::EEPROM_FAILED,
+                    "Display settings could not be saved to EEPROM U21",
+                    true,
+                )
+                .await?;
+            }
+        }
+        Err(ApiActionError::Busy) => {
+            write_api_error(
+                socket,
+                "409 Conflict",
+                allow_origin,
+                errors::BUSY,
+                "display settings are busy",
+                true,
+            )
+            .await?;
+        }
+    }
+    Ok(true)
+}
+
+pub const DISPLAY_SETTINGS_INVALID_MESSAGE: &str = "brightness_pct must be 5-100, timeouts 0 or 10-7200s with off_after_s > dim_after_s, rotation 0|180, theme light|dark";
+
+pub async fn try_set_display(
+    api_state: &'static ApiSharedMutex,
+    command: ApiDisplayCommand,
+) -> Result<(), ApiActionError> {
+    let mut guard = api_state.lock().await;
+    if guard.pending.display.is_some() || guard.pending.settings_reset.is_some() {
+        return Err(ApiActionError::Busy);
+    }
+    crate::reset_display_result();
+    guard.pending.display = Some(command);
+    Ok(())
+}
+
+/// Applies a partial update on top of `current`; absent keys keep their value.
+pub fn parse_display_settings_body(
+    body: &str,
+    current: DisplaySettings,
+) -> Option<DisplaySettings> {
+    let mut settings = current;
+    if json_value_after_key_body(body, "brightness_pct").is_some() {
+        settings.brightness_pct = extract_body_u8(body, "brightness_pct")?;
+    }
+    if json_value_after_key_body(body, "dim_after_s").is_some() {
+        settings.dim_after_s = extract_body_u16(body, "dim_after_s")?;
+    }
+    if json_value_after_key_body(body, "off_after_s").is_some() {
+        settings.off_after_s = extract_body_u16(body, "off_after_s")?;
+    }
+    if json_value_after_key_body(body, "rotation").is_some() {
+        settings.rotation = DisplayRotation::from_degrees(extract_body_u16(body, "rotation")?)?;
+    }
+    if json_value_after_key_body(body, "theme").is_some() {
+        settings.theme = DisplayTheme::parse(extract_body_string(body, "theme")?.as_str())?;
+    }
+    settings.validated().ok()
+}
+
+pub fn write_display_json(body: &mut String, display: &ApiDisplaySnapshot) {
+    let settings = display.settings;
+    let _ = core::write!(
+        body,
+        "{{\"brightness_pct\":{},\"dim_after_s\":{},\"off_after_s\":{},\"rotation\":{},\"theme\":\"{}\",\"backlight\":\"{}\",\"persisted\":{}}}",
+        settings.brightness_pct,
+        settings.dim_after_s,
+        settings.off_after_s,
+        settings.rotation.degrees(),
+        settings.theme.as_str(),
+        display.backlight.as_str(),
+        if display.persisted { "true" } else { "false" },
+    );
+}
+
+pub fn display_screenshot_error(
+    err: crate::DisplayScreenshotError,
+) -> (&'static str, &'static str, &'static str) {
+    match err {
+        crate::DisplayScreenshotError::Busy => (
+            "409 Conflict",
+            errors::BUSY,
+            "another screenshot is in progress",
+        ),
+        crate::DisplayScreenshotError::NoMemory => (
+            "503 Service Unavailable",
+            "no_memory",
+            "no PSRAM available for a screenshot frame",
+        ),
+        crate::DisplayScreenshotError::NotReady => (
+            "503 Service Unavailable",
+            "display_not_ready",
+            "the display has not presented a full frame yet",
+        ),
+    }
+}
+
+async fn write_display_screenshot_bmp(
+    socket: &mut TcpSocket<'_>,
+    allow_origin: Option<&str>,
+    frame: &[u16],
+) -> Result<(), embassy_net::tcp::Error> {
+    let mut header = String::new();
+    let _ = header.push_str("HTTP/1.1 200 OK\r\nContent-Type: image/bmp\r\n");
+    let _ = core::write!(
+        header,
+        "Content-Length: {}\r\n",
+        bmp_rgb565_file_len(DISPLAY_WIDTH, DISPLAY_HEIGHT)
+    );
+    let _ = header.push_str("Cache-Control: no-store\r\nConnection: close\r\n");
+    if let Some(origin) = allow_origin {
+        let _ = core::write!(
+            header,
+            "Access-Control-Allow-Origin: {}\r\nVary: Origin\r\n",
+            origin,
+        );
+    }
+    let _ = header.push_str("\r\n");
+    socket_write_all(socket, header.as_bytes()).await?;
+    socket_write_all(socket, &bmp_rgb565_header(DISPLAY_WIDTH, DISPLAY_HEIGHT)).await?;
+
+    // DISPLAY_WIDTH * 2 is already a multiple of four, so BMP rows need no padding.
+    let mut row = [0u8; DISPLAY_WIDTH as usize * 2];
+    for pixels in frame.chunks_exact(DISPLAY_WIDTH as usize) {
+        for (bytes, pixel) in row.chunks_exact_mut(2).zip(pixels) {
+            bytes.copy_from_slice(&pixel.to_le_bytes());
+        }
+        socket_write_all(socket, &row).await?;
+    }
+    Ok(())
+}
+
+/// JSONL form of a screenshot: raw little-endian RGB565 pixels, base64 encoded.
+pub fn write_display_screenshot_json(body: &mut String, frame: &[u16]) {
+    let _ = core::write!(
+        body,
+        "{{\"width\":{},\"height\":{},\"format\":\"rgb565le\",\"data\":\"",
+        DISPLAY_WIDTH,
+        DISPLAY_HEIGHT,
+    );
+    let mut encoder = Base64Encoder::new();
+    for pixel in frame {
+        let _ = encoder.push(&pixel.to_le_bytes(), body);
+    }
+    let _ = encoder.finish(body);
+    let _ = body.push_str("\"}");
+}
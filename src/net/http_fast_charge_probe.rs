@@ -0,0 +1,42 @@
+async fn handle_fast_charge_probe_api_request(
+    socket: &mut TcpSocket<'_>,
+    method: &str,
+    path: &str,
+    query: &str,
+    allow_origin: Option<&str>,
+    api_state: &'static ApiSharedMutex,
+) -> Result<bool, embassy_net::tcp::Error> {
+    let command = match (method, path) {
+        ("GET", "/api/v1/power/fast-charge-probe") => {
+            let state = { *api_state.lock().await };
+            let mut body = String::new();
+            write_fast_charge_probe_json(&mut body, &state.fast_charge_probe);
+            write_json_response(socket, "200 OK", allow_origin, body.as_str()).await?;
+            return Ok(true);
+        }
+        ("POST", "/api/v1/power/fast-charge-probe/run") => ApiFastChargeProbeCommand::Run,
+        ("POST", "/api/v1/power/fast-charge-probe/cancel") => ApiFastChargeProbeCommand::Cancel,
+        _ => return Ok(false),
+    };
+
+    match try_fast_charge_probe(api_state, command, parse_owner_query(query)).await {
+        Ok(()) => {
+            let state = { *api_state.lock().await };
+            let mut body = String::new();
+            write_fast_charge_probe_json(&mut body, &state.fast_charge_probe);
+            write_json_response(socket, "202 Accepted", allow_origin, body.as_str()).await?;
+        }
+        Err(ApiActionError::Busy) => {
+            write_api_error(
+                socket,
+                "409 Conflict",
+                allow_origin,
+                errors::BUSY,
+                "fast-charge probe is busy or locked",
+                true,
+            )
+            .await?;
+        }
+    }
+    Ok(true)
+}
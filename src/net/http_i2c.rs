@@ -0,0 +1,279 @@
+async fn handle_i2c_api_request(
+    socket: &mut TcpSocket<'_>,
+    method: &str,
+    path: &str,
+    query: &str,
+    allow_origin: Option<&str>,
+    api_state: &'static ApiSharedMutex,
+) -> Result<bool, embassy_net::tcp::Error> {
+    let command = match (method, path) {
+        ("GET", "/api/v1/diagnostics/i2c") => {
+            let state = { *api_state.lock().await };
+            let mut body = String::new();
+            write_i2c_diagnostics_json(&mut body, &state.i2c);
+            write_json_response(socket, "200 OK", allow_origin, body.as_str()).await?;
+            return Ok(true);
+        }
+        ("POST", "/api/v1/diagnostics/i2c/scan") => ApiI2cCommand::Scan,
+        ("POST", "/api/v1/diagnostics/i2c/recover") => {
+            let Some(bus) =
+                parse_query_value(query, "bus").and_then(|bus| I2cBusId::parse(bus.as_str()))
+            else {
+                write_api_error(
+                    socket,
+                    "400 Bad Request",
+                    allow_origin,
+                    errors::BAD_REQUEST,
+                    I2C_BUS_INVALID_MESSAGE,
+                    false,
+                )
+                .await?;
+                return Ok(true);
+            };
+            ApiI2cCommand::Recover { bus }
+        }
+        _ => return Ok(false),
+    };
+
+    match try_i2c_command(api_state, command, parse_owner_query(query)).await {
+        Ok(()) => match crate::wait_i2c_result().await {
+            crate::I2cActionResult::Done => {
+                let state = { *api_state.lock().await };
+                let mut body = String::new();
+                write_i2c_diagnostics_json(&mut body, &state.i2c);
+                write_json_response(socket, "200 OK", allow_origin, body.as_str()).await?;
+            }
+            crate::I2cActionResult::RecoveryFailed => {
+                write_api_error(
+                    socket,
+                    "500 Internal Server Error",
+                    allow_origin,
+                    errors::I2C_RECOVERY_FAILED,
+                    I2C_RECOVERY_FAILED_MESSAGE,
+                    true,
+                )
+                .await?;
+            }
+            crate::I2cActionResult::Throttled => {
+                write_api_error(
+                    socket,
+                    "409 Conflict",
+                    allow_origin,
+                    errors::BUSY,
+                    I2C_RECOVERY_THROTTLED_MESSAGE,
+                    true,
+                )
+                .await?;
+            }
+        },
+        Err(ApiI2cActionError::Busy) => {
+            write_api_error(
+                socket,
+                "409 Conflict",
+                allow_origin,
+                errors::BUSY,
+                I2C_BUSY_MESSAGE,
+                true,
+            )
+            .await?;
+        }
+        Err(ApiI2cActionError::UsbCPowerOff) => {
+            write_api_error(
+                socket,
+                "409 Conflict",
+                allow_origin,
+                errors::BUSY,
+                I2C_PD_POWER_OFF_MESSAGE,
+                false,
+            )
+            .await?;
+        }
+    }
+    Ok(true)
+}
+
+pub const I2C_BUS_INVALID_MESSAGE: &str = "bus must be pd|system";
+pub const I2C_BUSY_MESSAGE: &str = "I2C diagnostics are busy or locked";
+pub const I2C_PD_POWER_OFF_MESSAGE: &str =
+    "PD bus recovery needs USB-C power on; the bus is parked while it is off";
+pub const I2C_RECOVERY_THROTTLED_MESSAGE: &str =
+    "PD bus recovery ran less than a second ago; retry shortly";
+pub const I2C_RECOVERY_FAILED_MESSAGE: &str = "I2C bus did not return to idle after recovery";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApiI2cActionError {
+    Busy,
+    UsbCPowerOff,
+}
+
+/// Queues a scan or a recovery for the main loop. A recovery resets the bus
+/// the power path depends on, so it honours the power lock like the other
+/// power actions; a PD recovery also cycles `CE_TPS` and drops USB-C output.
+pub async fn try_i2c_command(
+    api_state: &'static ApiSharedMutex,
+    command: ApiI2cCommand,
+    owner: Option<u32>,
+) -> Result<(), ApiI2cActionError> {
+    let mut guard = api_state.lock().await;
+    if guard.pending.i2c.is_some() || guard.pending.settings_reset.is_some() {
+        return Err(ApiI2cActionError::Busy);
+    }
+    if let ApiI2cCommand::Recover { bus } = command {
+        let now = uptime_ms();
+        if let Some(lock) = guard.power.lock {
+            if lock.expires_at_ms <= now {
+                guard.power.lock = None;
+            } else if owner != Some(lock.owner) {
+                return Err(ApiI2cActionError::Busy);
+            }
+        }
+        if guard.ports.port_c.state.busy
+            || guard.pending.port_c.is_some()
+            || guard.pending.usb_c_downstream_route.is_some()
+            || guard.pending.power_config.is_some()
+            || guard.pending.power_runtime.is_some()
+            || guard.pending.idle_bias.is_some()
+            || guard.idle_bias.run.state == ApiIdleBiasRunState::Running
+        {
+            return Err(ApiI2cActionError::Busy);
+        }
+        if bus == I2cBusId::Pd && !guard.ports.port_c.state.power_enabled {
+            return Err(ApiI2cActionError::UsbCPowerOff);
+        }
+    }
+    crate::reset_i2c_result();
+    guard.pending.i2c = Some(command);
+    Ok(())
+}
+
+fn write_i2c_line_state_json(body: &mut String, lines: I2cLineState) {
+    let _ = core::write!(
+        body,
+        "{{\"sda_high\":{},\"scl_high\":{},\"idle\":{}}}",
+        lines.sda_high,
+        lines.scl_high,
+        lines.idle(),
+    );
+}
+
+fn write_i2c_bus_json<const N: usize>(
+    body: &mut String,
+    bus: I2cBusId,
+    available: bool,
+    recoveries: u32,
+    counters: &I2cBusCounters<N>,
+    device: fn(u8) -> &'static str,
+) {
+    let (sda_gpio, scl_gpio) = crate::i2c_bus_gpios(bus);
+    let _ = core::write!(
+        body,
+        "{{\"bus\":\"{}\",\"sda_gpio\":{},\"scl_gpio\":{},\"available\":{},\"lines\":",
+        bus.as_str(),
+        sda_gpio,
+        scl_gpio,
+        available,
+    );
+    write_i2c_line_state_json(body, crate::sample_i2c_lines(bus));
+    let _ = core::write!(
+        body,
+        ",\"recoveries\":{},\"rejected\":{},\"addresses\":[",
+        recoveries,
+        counters.rejected(),
+    );
+    for (index, address) in counters.addresses().iter().enumerate() {
+        let _ = core::write!(
+            body,
+            "{}{{\"address\":{},\"device\":\"{}\",\"transactions\":{},\"errors\":{},\"nack\":{},\"timeout\":{},\"arbitration\":{},\"other\":{}}}",
+            if index == 0 { "" } else { "," },
+            address.address,
+            device(address.address),
+            address.transactions,
+            address.errors(),
+            address.nack,
+            address.timeout,
+            address.arbitration,
+            address.other,
+        );
+    }
+    let _ = body.push_str("]}");
+}
+
+fn write_i2c_probe_json(
+    body: &mut String,
+    first: &mut bool,
+    bus: I2cBusId,
+    address: u8,
+    device: &str,
+    result: I2cProbeResult,
+) {
+    let _ = core::write!(
+        body,
+        "{}{{\"bus\":\"{}\",\"address\":{},\"device\":\"{}\",\"result\":\"{}\"}}",
+        if *first { "" } else { "," },
+        bus.as_str(),
+        address,
+        device,
+        result.as_str(),
+    );
+    *first = false;
+}
+
+/// Body of `GET /api/v1/diagnostics/i2c`, also the `result` of the JSONL
+/// `i2c.*` methods. Line levels are sampled while the body is written.
+pub fn write_i2c_diagnostics_json(body: &mut String, i2c: &ApiI2cSnapshot) {
+    let _ = body.push_str("{\"buses\":[");
+    write_i2c_bus_json(
+        body,
+        I2cBusId::Pd,
+        i2c.pd_i2c_allowed,
+        i2c.pd_recoveries,
+        &i2c.pd,
+        pd_i2c_device,
+    );
+    let _ = body.push(',');
+    write_i2c_bus_json(
+        body,
+        I2cBusId::System,
+        true,
+        i2c.system_recoveries,
+        &i2c.system,
+        telemetry_i2c_device,
+    );
+    let _ = body.push_str("],\"last_scan\":");
+    match i2c.last_scan {
+        Some(scan) => {
+            let _ = core::write!(body, "{{\"uptime_ms\":{},\"results\":[", scan.uptime_ms);
+            let mut first = true;
+            for (address, result) in PD_I2C_ADDRESSES.into_iter().zip(scan.pd) {
+                let device = pd_i2c_device(address);
+                write_i2c_probe_json(body, &mut first, I2cBusId::Pd, address, device, result);
+            }
+            for (address, result) in TELEMETRY_I2C_ADDRESSES.into_iter().zip(scan.system) {
+                let device = telemetry_i2c_device(address);
+                write_i2c_probe_json(body, &mut first, I2cBusId::System, address, device, result);
+            }
+            let _ = body.push_str("]}");
+        }
+        None => {
+            let _ = body.push_str("null");
+        }
+    }
+    let _ = body.push_str(",\"last_recovery\":");
+    match i2c.last_recovery {
+        Some(recovery) => {
+            let _ = core::write!(
+                body,
+                "{{\"bus\":\"{}\",\"uptime_ms\":{},\"recovered\":{},\"lines\":",
+                recovery.bus.as_str(),
+                recovery.uptime_ms,
+                recovery.recovered,
+            );
+            write_i2c_line_state_json(body, recovery.lines);
+            let _ = body.push('}');
+        }
+        None => {
+            let _ = body.push_str("null");
+        }
+    }
+    let _ = core::write!(body, ",\"sample_uptime_ms\":{}}}", uptime_ms());
+}
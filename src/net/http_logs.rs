@@ -0,0 +1,91 @@
+async fn handle_logs_api_request(
+    socket: &mut TcpSocket<'_>,
+    method: &str,
+    path: &str,
+    query: &str,
+    body: &str,
+    allow_origin: Option<&str>,
+    api_state: &'static ApiSharedMutex,
+) -> Result<bool, embassy_net::tcp::Error> {
+    let settings = match (method, path) {
+        ("GET", "/api/v1/logs") => {
+            let since = match parse_query_value(query, "since") {
+                Some(since) => since.parse::<u32>().ok(),
+                None => Some(0),
+            };
+            let Some(since) = since else {
+                write_api_error(
+                    socket,
+                    "400 Bad Request",
+                    allow_origin,
+                    errors::BAD_REQUEST,
+                    "since must be a log sequence number",
+                    false,
+                )
+                .await?;
+                return Ok(true);
+            };
+            let mut body = String::new();
+            write_logs_tail_json(&mut body, since);
+            write_json_response(socket, "200 OK", allow_origin, body.as_str()).await?;
+            return Ok(true);
+        }
+        ("GET", "/api/v1/logs/config") => {
+            let state = { *api_state.lock().await };
+            let mut body = String::new();
+            write_logs_config_json(&mut body, &state.logs);
+            write_json_response(socket, "200 OK", allow_origin, body.as_str()).await?;
+            return Ok(true);
+        }
+        ("PUT", "/api/v1/logs/config") => {
+            let current = { api_state.lock().await.logs.settings };
+            let Some(settings) = parse_log_settings_body(body, current) else {
+                write_api_error(
+                    socket,
+                    "400 Bad Request",
+                    allow_origin,
+                    errors::BAD_REQUEST,
+                    LOG_SETTINGS_INVALID_MESSAGE,
+                    false,
+                )
+                .await?;
+                return Ok(true);
+            };
+            settings
+        }
+        _ => return Ok(false),
+    };
+
+    match try_set_logs(api_state, settings).await {
+        Ok(()) => {
+            if crate::wait_logs_result().await {
+                let state = { *api_state.lock().await };
+                let mut body = String::new();
+                write_logs_config_json(&mut body, &state.logs);
+                write_json_response(socket, "200 OK", allow_origin, body.as_str()).await?;
+            } else {
+                write_api_error(
+                    socket,
+                    "500 Internal Server Error",
+                    allow_origin,
+                    errors::EEPROM_FAILED,
+                    "Log settings could not be saved to EEPROM U21",
+                    true,
+                )
+                .await?;
+            }
+        }
+        Err(ApiActionError::Busy) => {
+            write_api_error(
+                socket,
+                "409 Conflict",
+                allow_origin,
+                errors::BUSY,
+                "log settings are busy",
+                true,
+            )
+            .await?;
+        }
+    }
+    Ok(true)
+}
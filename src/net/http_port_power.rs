@@ -0,0 +1,68 @@
+async fn handle_port_power_api_request(
+    socket: &mut TcpSocket<'_>,
+    method: &str,
+    path: &str,
+    body: &str,
+    allow_origin: Option<&str>,
+    api_state: &'static ApiSharedMutex,
+) -> Result<bool, embassy_net::tcp::Error> {
+    let settings = match (method, path) {
+        ("GET", "/api/v1/ports/power-policy") => {
+            let state = { *api_state.lock().await };
+            let mut body = String::new();
+            write_port_power_json(&mut body, &state.port_power);
+            write_json_response(socket, "200 OK", allow_origin, body.as_str()).await?;
+            return Ok(true);
+        }
+        ("PUT", "/api/v1/ports/power-policy") => {
+            let current = { api_state.lock().await.port_power.settings };
+            let Some(settings) = parse_port_power_body(body, current) else {
+                write_api_error(
+                    socket,
+                    "400 Bad Request",
+                    allow_origin,
+                    errors::BAD_REQUEST,
+                    PORT_POWER_POLICY_INVALID_MESSAGE,
+                    false,
+                )
+                .await?;
+                return Ok(true);
+            };
+            settings
+        }
+        _ => return Ok(false),
+    };
+
+    match try_set_port_power(api_state, settings).await {
+        Ok(()) => {
+            if crate::wait_port_power_result().await {
+                let state = { *api_state.lock().await };
+                let mut body = String::new();
+                write_port_power_json(&mut body, &state.port_power);
+                write_json_response(socket, "200 OK", allow_origin, body.as_str()).await?;
+            } else {
+                write_api_error(
+                    socket,
+                    "500 Internal Server Error",
+                    allow_origin,
+                    errors::EEPROM_FAILED,
+                    "Port power policy could not be saved to EEPROM U21",
+                    true,
+                )
+                .await?;
+            }
+        }
+        Err(ApiActionError::Busy) => {
+            write_api_error(
+                socket,
+                "409 Conflict",
+                allow_origin,
+                errors::BUSY,
+                "port power policy is busy",
+                true,
+            )
+            .await?;
+        }
+    }
+    Ok(true)
+}
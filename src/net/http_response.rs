@@ -1,58 +1,3 @@
-fn write_port_telemetry_json(body: &mut String, telemetry: &ApiPortTelemetry) {
-    let _ = core::write!(
-        body,
-        "{{\"status\":\"{}\",\"voltage_mv\":",
-        telemetry.status.as_str(),
-    );
-    write_json_u32_or_null(body, telemetry.voltage_mv);
-    let _ = body.push_str(",\"current_ma\":");
-    write_json_u32_or_null(body, telemetry.current_ma);
-    let _ = body.push_str(",\"power_mw\":");
-    write_json_u32_or_null(body, telemetry.power_mw);
-    let _ = core::write!(
-        body,
-        ",\"sample_uptime_ms\":{}}}",
-        telemetry.sample_uptime_ms
-    );
-}
-
-fn write_port_json(body: &mut String, port_id: ApiPortId, label: &str, port: &ApiPortSnapshot) {
-    let _ = core::write!(
-        body,
-        "{{\"portId\":\"{}\",\"label\":\"{}\",\"telemetry\":",
-        port_id.as_str(),
-        label,
-    );
-    write_port_telemetry_json(body, &port.telemetry);
-    let _ = body.push_str(",\"telemetry_raw\":");
-    match port.telemetry_raw {
-        Some(telemetry_raw) => write_port_telemetry_json(body, &telemetry_raw),
-        None => {
-            let _ = body.push_str("null");
-        }
-    }
-    let _ = core::write!(
-        body,
-        ",\"state\":{{\"power_enabled\":{},\"data_connected\":{},\"replugging\":{},\"busy\":{}}},\"capabilities\":{{\"data_replug\":true,\"power_set\":true}}}}",
-        if port.state.power_enabled {
-            "true"
-        } else {
-            "false"
-        },
-        if port.state.data_connected {
-            "true"
-        } else {
-            "false"
-        },
-        if port.state.replugging {
-            "true"
-        } else {
-            "false"
-        },
-        if port.state.busy { "true" } else { "false" },
-    );
-}
-
 pub fn write_pd_diagnostics_json(
     body: &mut String,
     pd: &ApiPdSnapshot,
@@ -113,7 +58,7 @@ pub fn write_pd_diagnostics_json(
     let _ = body.push_str(",\"display\":{");
     write_usb_c_display_json(body, pd);
     let _ = body.push_str("},\"usb_c_actual\":");
-    write_port_telemetry_json(body, &pd.usb_c_actual);
+    let _ = pd.usb_c_actual.write_json(body);
     let _ = body.push_str(",\"tps_setpoint\":{\"output_enabled\":");
     write_json_bool_or_null(body, pd.tps_setpoint_output_enabled);
     let _ = body.push_str(",\"discharge_enabled\":");
@@ -701,6 +646,9 @@ pub async fn try_reset_settings(
         || guard.pending.power_runtime.is_some()
         || guard.pending.idle_bias.is_some()
         || guard.pending.settings_reset.is_some()
+        || guard.pending.sound.is_some()
+        || guard.pending.display.is_some()
+        || guard.pending.buttons.is_some()
         || guard.idle_bias.run.state == ApiIdleBiasRunState::Running
     {
         return Err(ApiActionError::Busy);
@@ -819,6 +767,7 @@ pub async fn try_run_idle_bias(
         || guard.pending.idle_bias.is_some()
         || guard.pending.settings_reset.is_some()
         || guard.idle_bias.run.state == ApiIdleBiasRunState::Running
+        || guard.fast_charge_probe.state == FastChargeProbeState::Running
     {
         return Err(ApiActionError::Busy);
     }
@@ -948,7 +897,7 @@ async fn write_http_response(
     Ok(())
 }
 
-async fn socket_write_all(
+pub(crate) async fn socket_write_all(
     socket: &mut TcpSocket<'_>,
     mut buf: &[u8],
 ) -> Result<(), embassy_net::tcp::Error> {
@@ -0,0 +1,14 @@
+async fn handle_sessions_api_request(
+    socket: &mut TcpSocket<'_>,
+    method: &str,
+    path: &str,
+    allow_origin: Option<&str>,
+) -> Result<bool, embassy_net::tcp::Error> {
+    if (method, path) != ("GET", "/api/v1/sessions") {
+        return Ok(false);
+    }
+    let mut body = String::new();
+    write_sessions_json(&mut body);
+    write_json_response(socket, "200 OK", allow_origin, body.as_str()).await?;
+    Ok(true)
+}
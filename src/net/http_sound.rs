@@ -0,0 +1,201 @@
+async fn handle_sound_api_request(
+    socket: &mut TcpSocket<'_>,
+    method: &str,
+    path: &str,
+    body: &str,
+    allow_origin: Option<&str>,
+    api_state: &'static ApiSharedMutex,
+) -> Result<bool, embassy_net::tcp::Error> {
+    let command = match (method, path) {
+        ("GET", "/api/v1/sound") => {
+            let state = { *api_state.lock().await };
+            let mut body = String::new();
+            write_sound_json(&mut body, &state.sound);
+            write_json_response(socket, "200 OK", allow_origin, body.as_str()).await?;
+            return Ok(true);
+        }
+        ("PUT", "/api/v1/sound") => {
+            let current = { api_state.lock().await.sound.settings };
+            let Some((settings, local_minute)) = parse_sound_settings_body(body, current) else {
+                write_api_error(
+                    socket,
+                    "400 Bad Request",
+                    allow_origin,
+                    errors::BAD_REQUEST,
+                    "invalid sound settings",
+                    false,
+                )
+                .await?;
+                return Ok(true);
+            };
+            ApiSoundCommand::Set {
+                settings,
+                local_minute,
+            }
+        }
+        ("POST", "/api/v1/sound/defaults") => ApiSoundCommand::Defaults,
+        _ => {
+            let Some(rest) = path.strip_prefix("/api/v1/sound/patterns/") else {
+                return Ok(false);
+            };
+            let (slot, clear) = match rest.strip_suffix("/clear") {
+                Some(slot) => (slot, true),
+                None => (rest, false),
+            };
+            let Some(slot) = SoundSlot::parse(slot) else {
+                write_api_error(
+                    socket,
+                    "404 Not Found",
+                    allow_origin,
+                    errors::NOT_FOUND,
+                    "unknown sound slot",
+                    false,
+                )
+                .await?;
+                return Ok(true);
+            };
+            match (method, clear) {
+                ("POST", true) => ApiSoundCommand::ClearPattern { slot },
+                ("PUT", false) => {
+                    let Some(pattern) = parse_sound_pattern_body(body) else {
+                        write_api_error(
+                            socket,
+                            "400 Bad Request",
+                            allow_origin,
+                            errors::BAD_REQUEST,
+                            "steps must be 1-8 freq_hz:duration_ms pairs (0 or 200-8000Hz, 10-2000ms)",
+                            false,
+                        )
+                        .await?;
+                        return Ok(true);
+                    };
+                    ApiSoundCommand::SetPattern { slot, pattern }
+                }
+                _ => return Ok(false),
+            }
+        }
+    };
+
+    match try_set_sound(api_state, command).await {
+        Ok(()) => {
+            if crate::wait_sound_result().await {
+                let state = { *api_state.lock().await };
+                let mut body = String::new();
+                write_sound_json(&mut body, &state.sound);
+                write_json_response(socket, "200 OK", allow_origin, body.as_str()).await?;
+            } else {
+                write_api_error(
+                    socket,
+                    "500 Internal Server Error",
+                    allow_origin,
+                    errors::EEPROM_FAILED,
+                    "Sound settings could not be saved to EEPROM U21",
+                    true,
+                )
+                .await?;
+            }
+        }
+        Err(ApiActionError::Busy) => {
+            write_api_error(
+                socket,
+                "409 Conflict",
+                allow_origin,
+                errors::BUSY,
+                "sound settings are busy",
+                true,
+            )
+            .await?;
+        }
+    }
+    Ok(true)
+}
+
+pub async fn try_set_sound(
+    api_state: &'static ApiSharedMutex,
+    command: ApiSoundCommand,
+) -> Result<(), ApiActionError> {
+    let mut guard = api_state.lock().await;
+    if guard.pending.sound.is_some() || guard.pending.settings_reset.is_some() {
+        return Err(ApiActionError::Busy);
+    }
+    crate::reset_sound_result();
+    guard.pending.sound = Some(command);
+    Ok(())
+}
+
+/// Applies a partial update on top of `current`; `local_minute` (0..1439)
+/// anchors the device clock used to evaluate quiet hours.
+pub fn parse_sound_settings_body(
+    body: &str,
+    current: SoundSettings,
+) -> Option<(SoundSettings, Option<u16>)> {
+    let mut settings = current;
+    set_bool_if_present(body, "muted", &mut settings.muted);
+    set_bool_if_present(body, "boot", &mut settings.classes.boot);
+    set_bool_if_present(body, "menu", &mut settings.classes.menu);
+    set_bool_if_present(body, "action", &mut settings.classes.action);
+    set_bool_if_present(body, "warning", &mut settings.classes.warning);
+    set_bool_if_present(body, "enabled", &mut settings.quiet_hours.enabled);
+    if json_value_after_key_body(body, "volume_pct").is_some() {
+        settings.volume_pct = extract_body_u8(body, "volume_pct")?;
+    }
+    if json_value_after_key_body(body, "start").is_some() {
+        settings.quiet_hours.start_minute =
+            parse_minute_of_day(&extract_body_string(body, "start")?)?;
+    }
+    if json_value_after_key_body(body, "end").is_some() {
+        settings.quiet_hours.end_minute = parse_minute_of_day(&extract_body_string(body, "end")?)?;
+    }
+    let local_minute = match json_value_after_key_body(body, "local_minute") {
+        Some(_) => Some(extract_body_u16(body, "local_minute").filter(|m| *m < MINUTES_PER_DAY)?),
+        None => None,
+    };
+    Some((settings.validated().ok()?, local_minute))
+}
+
+pub fn parse_sound_pattern_body(body: &str) -> Option<CustomSoundPattern> {
+    CustomSoundPattern::parse(extract_body_string(body, "steps")?.as_str())
+}
+
+pub fn write_sound_json(body: &mut String, sound: &ApiSoundSnapshot) {
+    let settings = sound.settings;
+    let flag = |value: bool| if value { "true" } else { "false" };
+    let _ = core::write!(
+        body,
+        "{{\"muted\":{},\"volume_pct\":{},\"classes\":{{\"boot\":{},\"menu\":{},\"action\":{},\"warning\":{},\"safety\":true}},\"quiet_hours\":{{\"enabled\":{},\"start\":\"",
+        flag(settings.muted),
+        settings.volume_pct,
+        flag(settings.classes.boot),
+        flag(settings.classes.menu),
+        flag(settings.classes.action),
+        flag(settings.classes.warning),
+        flag(settings.quiet_hours.enabled),
+    );
+    let _ = write_minute_of_day(body, settings.quiet_hours.start_minute);
+    let _ = body.push_str("\",\"end\":\"");
+    let _ = write_minute_of_day(body, settings.quiet_hours.end_minute);
+    let _ = core::write!(
+        body,
+        "\",\"active\":{}}},\"clock_synced\":{},\"persisted\":{},\"patterns\":{{",
+        flag(sound.quiet_active),
+        flag(sound.clock_synced),
+        flag(sound.persisted),
+    );
+    for (index, slot) in SoundSlot::ALL.iter().enumerate() {
+        if index > 0 {
+            let _ = body.push(',');
+        }
+        let _ = core::write!(body, "\"{}\":", slot.as_str());
+        match &sound.custom_patterns[slot.index()] {
+            Some(pattern) => {
+                let _ = body.push('"');
+                let _ = pattern.write_to(body);
+                let _ = body.push('"');
+            }
+            None => {
+                let _ = body.push_str("null");
+            }
+        }
+    }
+    let _ = body.push_str("}}");
+}
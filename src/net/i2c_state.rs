@@ -0,0 +1,55 @@
+// I2C bus counters, scans and recoveries served by `i2c.*`. The main loop owns
+// both buses and publishes into `ApiSharedState::i2c`.
+
+/// One `i2c.scan` pass over every allowlisted address of both buses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ApiI2cScan {
+    pub uptime_ms: u64,
+    /// Indexed like `PD_I2C_ADDRESSES`.
+    pub pd: [I2cProbeResult; PD_I2C_ADDRESSES.len()],
+    /// Indexed like `TELEMETRY_I2C_ADDRESSES`.
+    pub system: [I2cProbeResult; TELEMETRY_I2C_ADDRESSES.len()],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ApiI2cRecovery {
+    pub bus: I2cBusId,
+    pub uptime_ms: u64,
+    pub recovered: bool,
+    /// Line state right after the recovery.
+    pub lines: I2cLineState,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ApiI2cSnapshot {
+    pub pd: I2cBusCounters<{ PD_I2C_ADDRESSES.len() }>,
+    pub system: TelemetryI2cCounters,
+    /// SW2303 transactions are allowed. False while the PD bus is parked
+    /// (USB-C power off) or the SW2303 is still in POR.
+    pub pd_i2c_allowed: bool,
+    /// `CE_TPS` recoveries, automatic or manual; each one also frees the PD bus.
+    pub pd_recoveries: u32,
+    pub system_recoveries: u32,
+    pub last_scan: Option<ApiI2cScan>,
+    pub last_recovery: Option<ApiI2cRecovery>,
+}
+
+impl ApiI2cSnapshot {
+    pub const fn unknown() -> Self {
+        Self {
+            pd: I2cBusCounters::new(PD_I2C_ADDRESSES),
+            system: I2cBusCounters::new(TELEMETRY_I2C_ADDRESSES),
+            pd_i2c_allowed: false,
+            pd_recoveries: 0,
+            system_recoveries: 0,
+            last_scan: None,
+            last_recovery: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApiI2cCommand {
+    Scan,
+    Recover { bus: I2cBusId },
+}
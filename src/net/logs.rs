@@ -0,0 +1,217 @@
+// Remote log ring served by `logs.tail` and `/api/v1/logs`, plus RFC 5424
+// forwarding to the configured syslog host. `hub_log!` call sites push here;
+// the level and target persist in EEPROM U21 and are applied by the main loop.
+
+use core::cell::RefCell;
+
+use embassy_net::{
+    IpAddress, IpEndpoint,
+    udp::{PacketMetadata, UdpSocket},
+};
+use embassy_sync::blocking_mutex::Mutex as BlockingMutex;
+use isolapurr_api::diagnostics::LogLevel;
+use isolapurr_usb_hub::log_ring::{
+    LOG_RING_LEN, LogRecord, LogRing, LogSettings, SYSLOG_DEFAULT_PORT, SyslogTarget,
+    write_syslog_message,
+};
+
+static LOG_RING: BlockingMutex<CriticalSectionRawMutex, RefCell<LogRing>> =
+    BlockingMutex::new(RefCell::new(LogRing::new(LogLevel::Info)));
+
+/// Wakes the syslog forwarder after a record was stored.
+static LOG_PUSHED: Signal<CriticalSectionRawMutex, ()> = Signal::new();
+
+const SYSLOG_APP_NAME: &str = "isolapurr";
+
+pub const LOG_SETTINGS_INVALID_MESSAGE: &str = "level must be error|warn|info|debug, syslog must be null or {\"host\":\"a.b.c.d\",\"port\":1-65535}";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ApiLogsSnapshot {
+    pub settings: LogSettings,
+    pub persisted: bool,
+}
+
+impl ApiLogsSnapshot {
+    pub const fn unknown() -> Self {
+        Self {
+            settings: LogSettings::defaults(),
+            persisted: false,
+        }
+    }
+}
+
+/// Stores one record when it passes the configured level.
+pub fn push_log(level: LogLevel, uptime_ms: u64, args: core::fmt::Arguments<'_>) {
+    let stored = LOG_RING.lock(|ring| ring.borrow_mut().push(level, uptime_ms, args));
+    if stored.is_some() {
+        LOG_PUSHED.signal(());
+    }
+}
+
+pub fn set_log_level(level: LogLevel) {
+    LOG_RING.lock(|ring| ring.borrow_mut().set_level(level));
+}
+
+/// `logs.tail` result: records from `since` on, at most `LOG_TAIL_MAX`.
+pub fn write_logs_tail_json(body: &mut String, since: u32) {
+    LOG_RING.lock(|ring| {
+        let ring = ring.borrow();
+        let tail = ring.tail(since);
+        let _ = core::write!(
+            body,
+            "{{\"level\":\"{}\",\"oldest\":{},\"next\":{},\"dropped\":{},\"more\":{},\"records\":[",
+            ring.level().as_str(),
+            ring.oldest_seq(),
+            tail.end,
+            tail.dropped,
+            tail.more()
+        );
+        for (index, record) in tail.records().enumerate() {
+            if index > 0 {
+                let _ = body.push(',');
+            }
+            let _ = core::write!(
+                body,
+                "{{\"seq\":{},\"uptime_ms\":{},\"level\":\"{}\",\"message\":",
+                record.seq,
+                record.uptime_ms,
+                record.level.as_str()
+            );
+            write_json_string(body, record.message.as_str());
+            let _ = body.push('}');
+        }
+        let _ = body.push_str("]}");
+    });
+}
+
+/// `logs.config_get` / `logs.config_set` result.
+pub fn write_logs_config_json(body: &mut String, logs: &ApiLogsSnapshot) {
+    let _ = core::write!(
+        body,
+        "{{\"level\":\"{}\",\"ring_len\":{},\"persisted\":{},\"syslog\":",
+        logs.settings.level.as_str(),
+        LOG_RING_LEN,
+        logs.persisted
+    );
+    match logs.settings.syslog {
+        Some(target) => {
+            let [a, b, c, d] = target.host;
+            let _ = core::write!(
+                body,
+                "{{\"host\":\"{}.{}.{}.{}\",\"port\":{}}}}}",
+                a,
+                b,
+                c,
+                d,
+                target.port
+            );
+        }
+        None => {
+            let _ = body.push_str("null}");
+        }
+    }
+}
+
+/// Applies a partial update on top of `current`; absent keys keep their value.
+/// `"syslog":null` stops forwarding.
+pub fn parse_log_settings_body(body: &str, current: LogSettings) -> Option<LogSettings> {
+    let mut settings = current;
+    if json_value_after_key_body(body, "level").is_some() {
+        settings.level = LogLevel::parse(extract_body_string(body, "level")?.as_str())?;
+    }
+    if let Some(syslog) = json_value_after_key_body(body, "syslog") {
+        settings.syslog = if syslog.starts_with("null") {
+            None
+        } else {
+            let host = extract_body_string(body, "host")?;
+            let host = host.parse::<core::net::Ipv4Addr>().ok()?.octets();
+            let port = match json_value_after_key_body(body, "port") {
+                Some(_) => extract_body_u16(body, "port").filter(|port| *port != 0)?,
+                None => SYSLOG_DEFAULT_PORT,
+            };
+            Some(SyslogTarget { host, port })
+        };
+    }
+    Some(settings)
+}
+
+pub async fn try_set_logs(
+    api_state: &'static ApiSharedMutex,
+    settings: LogSettings,
+) -> Result<(), ApiActionError> {
+    let mut guard = api_state.lock().await;
+    if guard.pending.logs.is_some() {
+        return Err(ApiActionError::Busy);
+    }
+    crate::reset_logs_result();
+    guard.pending.logs = Some(settings);
+    Ok(())
+}
+
+fn next_log_record(since: u32) -> Option<LogRecord> {
+    LOG_RING.lock(|ring| ring.borrow().tail(since).records().next().copied())
+}
+
+fn log_ring_next_seq() -> u32 {
+    LOG_RING.lock(|ring| ring.borrow().next_seq())
+}
+
+/// Forwards every stored record to the syslog target once the link is up.
+/// Records older than the ring are lost; nothing here logs through `hub_log!`
+/// so forwarding cannot feed itself.
+#[embassy_executor::task]
+async fn syslog_task(
+    stack: Stack<'static>,
+    device_names: &'static DeviceNames,
+    api_state: &'static ApiSharedMutex,
+) {
+    let mut rx_meta = [PacketMetadata::EMPTY; 1];
+    let mut rx_storage = [0u8; 16];
+    let mut tx_meta = [PacketMetadata::EMPTY; 4];
+    let mut tx_storage = [0u8; 1024];
+    let mut socket = UdpSocket::new(
+        stack,
+        &mut rx_meta,
+        &mut rx_storage,
+        &mut tx_meta,
+        &mut tx_storage,
+    );
+    if socket.bind(0).is_err() {
+        warn!("syslog: UDP bind failed; forwarding disabled");
+        return;
+    }
+
+    let mut next_seq = 0u32;
+    loop {
+        let target = { api_state.lock().await.logs.settings.syslog };
+        let Some(target) = target else {
+            next_seq = log_ring_next_seq();
+            LOG_PUSHED.wait().await;
+            continue;
+        };
+        if !stack.is_config_up() {
+            stack.wait_config_up().await;
+            continue;
+        }
+
+        while let Some(record) = next_log_record(next_seq) {
+            let mut line: HString<256> = HString::new();
+            let _ = write_syslog_message(
+                &mut line,
+                &record,
+                device_names.hostname.as_str(),
+                SYSLOG_APP_NAME,
+            );
+            let [a, b, c, d] = target.host;
+            let endpoint =
+                IpEndpoint::new(IpAddress::Ipv4(Ipv4Address::new(a, b, c, d)), target.port);
+            if socket.send_to(line.as_bytes(), endpoint).await.is_err() {
+                debug!("syslog: send_to failed; retrying with the next record");
+                Timer::after(Duration::from_secs(1)).await;
+                break;
+            }
+            next_seq = record.seq.wrapping_add(1);
+        }
+        LOG_PUSHED.wait().await;
+    }
+}
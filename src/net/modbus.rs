@@ -0,0 +1,295 @@
+use isolapurr_usb_hub::modbus::{
+    FAULT_ISOLATED_USB, FAULT_SW2303_LATCHED, FAULT_THERMAL_ALARM, FAULT_TPS_LATCHED,
+    FAULT_UI_LATCHED, FC_READ_HOLDING_REGISTERS, FC_READ_INPUT_REGISTERS,
+    FC_WRITE_MULTIPLE_REGISTERS, FC_WRITE_SINGLE_REGISTER, MODBUS_MAX_ADU_LEN, MODBUS_MAX_PDU_LEN,
+    MODBUS_MBAP_LEN, MODBUS_PORT, ModbusException, ModbusHoldings, ModbusInputs, ModbusPort,
+    ModbusPortInputs, ModbusRequest, ModbusWrite, decode_holding_write, encode_adu, exception_pdu,
+    parse_mbap, parse_pdu, read_registers_pdu, register_words, write_ack_pdu,
+};
+
+/// PLCs poll continuously; a silent master is assumed gone after this long.
+const MODBUS_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Modbus TCP slave for PLC/SCADA integration. Serves one master at a time; the
+/// register map is documented in `docs/specs/m8d2w-modbus-tcp/SPEC.md`.
+#[embassy_executor::task]
+async fn modbus_task(stack: Stack<'static>, api_state: &'static ApiSharedMutex) {
+    let mut rx_buf = [0u8; 512];
+    let mut tx_buf = [0u8; 512];
+
+    info!("Modbus TCP listener starting (port={})", MODBUS_PORT);
+
+    loop {
+        stack.wait_config_up().await;
+
+        let mut socket = TcpSocket::new(stack, &mut rx_buf, &mut tx_buf);
+        socket.set_timeout(Some(MODBUS_IDLE_TIMEOUT));
+
+        match socket.accept(MODBUS_PORT).await {
+            Ok(()) => {
+                if let Err(err) = handle_modbus_connection(&mut socket, api_state).await {
+                    warn!("Modbus connection handling error: {:?}", err);
+                }
+                socket.close();
+                let _ = socket.flush().await;
+            }
+            Err(err) => {
+                warn!("Modbus accept error: {:?}", err);
+                Timer::after(Duration::from_millis(200)).await;
+            }
+        }
+    }
+}
+
+async fn handle_modbus_connection(
+    socket: &mut TcpSocket<'_>,
+    api_state: &'static ApiSharedMutex,
+) -> Result<(), embassy_net::tcp::Error> {
+    let mut header = [0u8; MODBUS_MBAP_LEN];
+    let mut pdu = [0u8; MODBUS_MAX_PDU_LEN];
+    let mut adu: Vec<u8, MODBUS_MAX_ADU_LEN> = Vec::new();
+
+    loop {
+        if !socket_read_exact(socket, &mut header).await? {
+            return Ok(());
+        }
+        // A bad MBAP header leaves the stream unframed, so drop the connection.
+        let Some((mbap, pdu_len)) = parse_mbap(&header) else {
+            warn!("Modbus: invalid MBAP header; closing connection");
+            return Ok(());
+        };
+        if !socket_read_exact(socket, &mut pdu[..pdu_len]).await? {
+            return Ok(());
+        }
+        let pdu = &pdu[..pdu_len];
+        let function = pdu[0];
+
+        match handle_modbus_pdu(pdu, api_state).await {
+            Ok(response) => encode_adu(mbap, &response, &mut adu),
+            Err(exception) => encode_adu(mbap, &exception_pdu(function, exception), &mut adu),
+        }
+        socket_write_all(socket, &adu).await?;
+        socket.flush().await?;
+    }
+}
+
+/// Fills `buf` completely; returns `false` if the master closed the connection first.
+async fn socket_read_exact(
+    socket: &mut TcpSocket<'_>,
+    buf: &mut [u8],
+) -> Result<bool, embassy_net::tcp::Error> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        let n = socket.read(&mut buf[filled..]).await?;
+        if n == 0 {
+            return Ok(false);
+        }
+        filled += n;
+    }
+    Ok(true)
+}
+
+async fn handle_modbus_pdu(
+    pdu: &[u8],
+    api_state: &'static ApiSharedMutex,
+) -> Result<Vec<u8, MODBUS_MAX_PDU_LEN>, ModbusException> {
+    match parse_pdu(pdu)? {
+        ModbusRequest::ReadHolding { start, count } => {
+            let holdings = modbus_holdings(&*api_state.lock().await);
+            read_registers_pdu(FC_READ_HOLDING_REGISTERS, start, count, |address| {
+                holdings.register(address)
+            })
+        }
+        ModbusRequest::ReadInput { start, count } => {
+            let inputs = modbus_inputs(&*api_state.lock().await);
+            read_registers_pdu(FC_READ_INPUT_REGISTERS, start, count, |address| {
+                inputs.register(address)
+            })
+        }
+        ModbusRequest::WriteSingle { address, value } => {
+            let write = decode_holding_write(address, value)?;
+            apply_modbus_writes(api_state, &[write]).await?;
+            Ok(
+                Vec::from_slice(&write_ack_pdu(FC_WRITE_SINGLE_REGISTER, address, value))
+                    .unwrap_or_default(),
+            )
+        }
+        ModbusRequest::WriteMultiple { start, values } => {
+            // Validate every register before applying any, so a bad value in the
+            // block leaves the device untouched.
+            let mut writes: Vec<ModbusWrite, 16> = Vec::new();
+            let mut count = 0u16;
+            for value in register_words(values) {
+                let address = start
+                    .checked_add(count)
+                    .ok_or(ModbusException::IllegalDataAddress)?;
+                writes
+                    .push(decode_holding_write(address, value)?)
+                    .map_err(|_| ModbusException::IllegalDataAddress)?;
+                count += 1;
+            }
+            apply_modbus_writes(api_state, &writes).await?;
+            Ok(
+                Vec::from_slice(&write_ack_pdu(FC_WRITE_MULTIPLE_REGISTERS, start, count))
+                    .unwrap_or_default(),
+            )
+        }
+    }
+}
+
+/// Power-config registers in one request are folded into a single live config
+/// that stays runtime-only until register 9 stores it to EEPROM U21. A config
+/// that is already live (and, for a save, already stored) is not re-sent, so a
+/// PLC rewriting the same values every scan costs nothing. Port and output
+/// registers go through the same actions as the HTTP API.
+async fn apply_modbus_writes(
+    api_state: &'static ApiSharedMutex,
+    writes: &[ModbusWrite],
+) -> Result<(), ModbusException> {
+    let save = writes.contains(&ModbusWrite::SavePowerConfig);
+    if save || writes.iter().any(|write| write.is_power_config()) {
+        let (current, persisted) = {
+            let state = api_state.lock().await;
+            (state.power.config, state.power.persisted)
+        };
+        let mut config = current;
+        for write in writes {
+            match *write {
+                ModbusWrite::TpsMode(mode) => config.tps_mode = mode,
+                ModbusWrite::ManualVoltage { mv } => {
+                    config.manual.voltage_mv = quantize_manual_voltage_mv(mv)
+                }
+                ModbusWrite::ManualCurrent { ma } => {
+                    config.manual.current_limit_ma = quantize_manual_current_ma(ma)
+                }
+                ModbusWrite::PowerPreset { watts } => config.capability.power_watts = watts,
+                _ => {}
+            }
+        }
+        let config = config
+            .validated()
+            .map_err(|_| ModbusException::IllegalDataValue)?;
+        let command = if save {
+            (config != current || !persisted).then_some(ApiPowerConfigCommand::Set { config })
+        } else {
+            (config != current).then_some(ApiPowerConfigCommand::Apply { config })
+        };
+        if let Some(command) = command {
+            match try_set_power_config(api_state, command, None).await {
+                Ok(()) if crate::wait_power_config_result().await => {}
+                Ok(()) => return Err(ModbusException::SlaveDeviceFailure),
+                Err(ApiActionError::Busy) => return Err(ModbusException::SlaveDeviceBusy),
+            }
+        }
+    }
+
+    for write in writes {
+        match *write {
+            ModbusWrite::PortPower { port, enabled } => {
+                try_set_action(
+                    api_state,
+                    api_port_id(port),
+                    ApiPortAction::Power { enabled },
+                )
+                .await
+                .map_err(|_| ModbusException::SlaveDeviceBusy)?;
+            }
+            ModbusWrite::Replug { port } => {
+                try_set_action(api_state, api_port_id(port), ApiPortAction::Replug)
+                    .await
+                    .map_err(|_| ModbusException::SlaveDeviceBusy)?;
+            }
+            ModbusWrite::OutputEnabled(enabled) => {
+                match try_set_power_runtime(
+                    api_state,
+                    ApiPowerRuntimeCommand::SetOutputEnabled { enabled },
+                    None,
+                )
+                .await
+                {
+                    Ok(()) if crate::wait_power_runtime_result().await => {}
+                    Ok(()) => return Err(ModbusException::SlaveDeviceFailure),
+                    Err(ApiActionError::Busy) => return Err(ModbusException::SlaveDeviceBusy),
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+const fn api_port_id(port: ModbusPort) -> ApiPortId {
+    match port {
+        ModbusPort::UsbA => ApiPortId::PortA,
+        ModbusPort::UsbC => ApiPortId::PortC,
+    }
+}
+
+fn modbus_holdings(state: &ApiSharedState) -> ModbusHoldings {
+    let config = state.power.config;
+    ModbusHoldings {
+        port_a_power: state.ports.port_a.state.power_enabled,
+        port_c_power: state.ports.port_c.state.power_enabled,
+        tps_mode: config.tps_mode,
+        manual_voltage_mv: config.manual.voltage_mv,
+        manual_current_ma: config.manual.current_limit_ma,
+        power_watts: config.capability.power_watts,
+        output_enabled: state.power.runtime_output_enabled,
+        power_config_persisted: state.power.persisted,
+    }
+}
+
+fn modbus_port_inputs(port: &ApiPortSnapshot) -> ModbusPortInputs {
+    ModbusPortInputs {
+        status: match port.telemetry.status {
+            ApiTelemetryStatus::Ok => 0,
+            ApiTelemetryStatus::NotInserted => 1,
+            ApiTelemetryStatus::Error => 2,
+            ApiTelemetryStatus::Overrange => 3,
+        },
+        voltage_mv: port.telemetry.voltage_mv,
+        current_ma: port.telemetry.current_ma,
+        power_mw: port.telemetry.power_mw,
+        power_enabled: port.state.power_enabled,
+        data_connected: port.state.data_connected,
+        busy: port.state.busy,
+    }
+}
+
+fn modbus_inputs(state: &ApiSharedState) -> ModbusInputs {
+    let pd = &state.pd;
+    let faults = [
+        (state.hub.isolated_usb_fault, FAULT_ISOLATED_USB),
+        (pd.sw2303_error_latched, FAULT_SW2303_LATCHED),
+        (pd.tps_error_latched, FAULT_TPS_LATCHED),
+        (state.ui_error_latched, FAULT_UI_LATCHED),
+        (pd.thermal.state.alarm_active(), FAULT_THERMAL_ALARM),
+    ]
+    .into_iter()
+    .filter(|(active, _)| *active)
+    .fold(0, |faults, (_, bit)| faults | bit);
+
+    ModbusInputs {
+        port_a: modbus_port_inputs(&state.ports.port_a),
+        port_c: modbus_port_inputs(&state.ports.port_c),
+        pd_request_mv: pd.sw2303_request_mv,
+        pd_request_ma: pd.sw2303_request_ma,
+        vbus_mv: pd.sw2303_vbus_mv,
+        protocol: pd.active_protocol.map_or(0, |protocol| match protocol {
+            ApiActiveProtocol::Pd => 1,
+            ApiActiveProtocol::Pps => 2,
+            ApiActiveProtocol::Qc20 => 3,
+            ApiActiveProtocol::Qc30 => 4,
+            ApiActiveProtocol::Fcp => 5,
+            ApiActiveProtocol::Afc => 6,
+            ApiActiveProtocol::Scp => 7,
+            ApiActiveProtocol::Pe20 => 8,
+            ApiActiveProtocol::Bc12 => 9,
+            ApiActiveProtocol::Sfcp => 10,
+        }),
+        tps_setpoint_mv: pd.tps_setpoint_mv,
+        thermal: pd.thermal,
+        faults,
+        recovery_count: pd.runtime_recovery_count,
+    }
+}
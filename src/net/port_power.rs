@@ -0,0 +1,68 @@
+// Per-port power-on policy served by `ports.power_policy_*` and
+// `/api/v1/ports/power-policy`; persisted in EEPROM U21 by the main loop.
+
+use isolapurr_api::ports::PortPowerPolicy;
+use isolapurr_usb_hub::port_power::PortPowerSettings;
+
+pub const PORT_POWER_POLICY_INVALID_MESSAGE: &str =
+    "port_a and port_c must be on|off|last; at least one is required";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ApiPortPowerSnapshot {
+    pub settings: PortPowerSettings,
+    pub persisted: bool,
+}
+
+impl ApiPortPowerSnapshot {
+    pub const fn unknown() -> Self {
+        Self {
+            settings: PortPowerSettings::defaults(),
+            persisted: false,
+        }
+    }
+}
+
+/// `ports.power_policy_get` / `ports.power_policy_set` result.
+pub fn write_port_power_json(body: &mut String, port_power: &ApiPortPowerSnapshot) {
+    let settings = &port_power.settings;
+    let _ = core::write!(
+        body,
+        "{{\"port_a\":\"{}\",\"port_c\":\"{}\",\"last_state\":{{\"port_a\":{},\"port_c\":{}}},\"boot_state\":{{\"port_a\":{},\"port_c\":{}}},\"persisted\":{}}}",
+        settings.usb_a.as_str(),
+        settings.usb_c.as_str(),
+        settings.last_usb_a_on,
+        settings.last_usb_c_on,
+        settings.usb_a_on_at_boot(),
+        settings.usb_c_on_at_boot(),
+        port_power.persisted
+    );
+}
+
+/// Applies a partial update on top of `current`; absent ports keep their policy.
+pub fn parse_port_power_body(body: &str, current: PortPowerSettings) -> Option<PortPowerSettings> {
+    let mut settings = current;
+    let mut any = false;
+    for (key, policy) in [
+        ("port_a", &mut settings.usb_a),
+        ("port_c", &mut settings.usb_c),
+    ] {
+        if json_value_after_key_body(body, key).is_some() {
+            *policy = PortPowerPolicy::parse(extract_body_string(body, key)?.as_str())?;
+            any = true;
+        }
+    }
+    any.then_some(settings)
+}
+
+pub async fn try_set_port_power(
+    api_state: &'static ApiSharedMutex,
+    settings: PortPowerSettings,
+) -> Result<(), ApiActionError> {
+    let mut guard = api_state.lock().await;
+    if guard.pending.port_power.is_some() || guard.pending.settings_reset.is_some() {
+        return Err(ApiActionError::Busy);
+    }
+    crate::reset_port_power_result();
+    guard.pending.port_power = Some(settings);
+    Ok(())
+}
@@ -0,0 +1,245 @@
+const SCPI_IDLE_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Raw-socket SCPI server for lab software (LabVIEW, PyVISA `TCPIP::<host>::5025::SOCKET`).
+/// One client at a time; commands drive the manual TPS setpoint and USB-C output.
+/// Setpoints stay runtime-only until `*SAV`, so scripted sweeps never wear EEPROM U21.
+#[embassy_executor::task]
+async fn scpi_task(
+    stack: Stack<'static>,
+    device_names: &'static DeviceNames,
+    api_state: &'static ApiSharedMutex,
+) {
+    let mut rx_buf = [0u8; 512];
+    let mut tx_buf = [0u8; 512];
+
+    info!("SCPI server listener starting (port={})", SCPI_PORT);
+
+    loop {
+        stack.wait_config_up().await;
+
+        let mut socket = TcpSocket::new(stack, &mut rx_buf, &mut tx_buf);
+        // Instrument drivers keep their session open between polls, so the idle
+        // timeout only reclaims the slot from clients that vanished.
+        socket.set_timeout(Some(SCPI_IDLE_TIMEOUT));
+
+        match socket.accept(SCPI_PORT).await {
+            Ok(()) => {
+                if let Err(err) = handle_scpi_connection(&mut socket, device_names, api_state).await
+                {
+                    warn!("SCPI connection handling error: {:?}", err);
+                }
+                socket.close();
+                let _ = socket.flush().await;
+            }
+            Err(err) => {
+                warn!("SCPI accept error: {:?}", err);
+                Timer::after(Duration::from_millis(200)).await;
+            }
+        }
+    }
+}
+
+async fn handle_scpi_connection(
+    socket: &mut TcpSocket<'_>,
+    device_names: &'static DeviceNames,
+    api_state: &'static ApiSharedMutex,
+) -> Result<(), embassy_net::tcp::Error> {
+    let mut errors = ScpiErrorQueue::new();
+    let mut line: Vec<u8, SCPI_MAX_LINE_LEN> = Vec::new();
+    let mut overrun = false;
+    let mut chunk = [0u8; 128];
+
+    loop {
+        let n = socket.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        for &byte in &chunk[..n] {
+            if byte != b'\n' {
+                if byte != b'\r' && line.push(byte).is_err() {
+                    overrun = true;
+                }
+                continue;
+            }
+            if overrun {
+                errors.push(ScpiError::InputBufferOverrun);
+            } else if let Ok(text) = core::str::from_utf8(&line) {
+                let mut response: HString<SCPI_MAX_LINE_LEN> = HString::new();
+                for command in split_program(text) {
+                    run_scpi_command(command, &mut response, &mut errors, device_names, api_state)
+                        .await;
+                }
+                if !response.is_empty() {
+                    socket_write_all(socket, response.as_bytes()).await?;
+                    socket_write_all(socket, b"\n").await?;
+                    socket.flush().await?;
+                }
+            } else {
+                errors.push(ScpiError::DataTypeError);
+            }
+            line.clear();
+            overrun = false;
+        }
+    }
+}
+
+/// Executes one command, appending any query response `;`-separated as IEEE 488.2
+/// requires for program messages with several queries.
+async fn run_scpi_command(
+    text: &str,
+    response: &mut HString<SCPI_MAX_LINE_LEN>,
+    errors: &mut ScpiErrorQueue,
+    device_names: &'static DeviceNames,
+    api_state: &'static ApiSharedMutex,
+) {
+    let command = match parse_command(text) {
+        Ok(command) => command,
+        Err(err) => {
+            errors.push(err);
+            return;
+        }
+    };
+    let mut out: HString<96> = HString::new();
+
+    let result = match command {
+        ScpiCommand::Identify => {
+            let _ = core::write!(
+                out,
+                "IvanLi-CN,Isolapurr USB Hub,{},{}",
+                device_names.device_id.as_str(),
+                release_version()
+            );
+            Ok(())
+        }
+        ScpiCommand::Reset => {
+            let output = scpi_set_output(api_state, false).await;
+            let manual = scpi_update_manual(api_state, |manual| {
+                manual.voltage_mv = MANUAL_DEFAULT_VOLTAGE_MV;
+                manual.current_limit_ma = MANUAL_DEFAULT_CURRENT_MA;
+            })
+            .await;
+            output.and(manual)
+        }
+        ScpiCommand::ClearStatus => {
+            errors.clear();
+            Ok(())
+        }
+        ScpiCommand::OperationComplete => {
+            let _ = out.push('1');
+            Ok(())
+        }
+        ScpiCommand::Save => {
+            let config = { api_state.lock().await.power.config };
+            scpi_set_power_config(api_state, ApiPowerConfigCommand::Set { config }).await
+        }
+        ScpiCommand::SetVoltage(level) => match resolve_voltage_mv(level) {
+            Ok(mv) => {
+                scpi_update_manual(api_state, |manual| {
+                    manual.voltage_mv = quantize_manual_voltage_mv(mv);
+                })
+                .await
+            }
+            Err(err) => Err(err),
+        },
+        ScpiCommand::SetCurrent(level) => match resolve_current_ma(level) {
+            Ok(ma) => {
+                scpi_update_manual(api_state, |manual| {
+                    manual.current_limit_ma = quantize_manual_current_ma(ma);
+                })
+                .await
+            }
+            Err(err) => Err(err),
+        },
+        ScpiCommand::QueryVoltage => {
+            let manual = { api_state.lock().await.power.config.manual };
+            let _ = write_milli(&mut out, u32::from(manual.voltage_mv));
+            Ok(())
+        }
+        ScpiCommand::QueryCurrent => {
+            let manual = { api_state.lock().await.power.config.manual };
+            let _ = write_milli(&mut out, u32::from(manual.current_limit_ma));
+            Ok(())
+        }
+        ScpiCommand::SetOutput(enabled) => scpi_set_output(api_state, enabled).await,
+        ScpiCommand::QueryOutput => {
+            let enabled = { api_state.lock().await.power.runtime_output_enabled };
+            let _ = out.push(if enabled { '1' } else { '0' });
+            Ok(())
+        }
+        ScpiCommand::MeasureVoltage | ScpiCommand::MeasureCurrent | ScpiCommand::MeasurePower => {
+            let telemetry = { api_state.lock().await.ports.port_c.telemetry };
+            let reading = match command {
+                ScpiCommand::MeasureVoltage => telemetry.voltage_mv,
+                ScpiCommand::MeasureCurrent => telemetry.current_ma,
+                _ => telemetry.power_mw,
+            };
+            match reading {
+                Some(milli) if telemetry.status == ApiTelemetryStatus::Ok => {
+                    let _ = write_milli(&mut out, milli);
+                    Ok(())
+                }
+                // Keep the response count aligned with the queries sent.
+                _ => {
+                    let _ = out.push_str(SCPI_NAN);
+                    Err(ScpiError::DataStale)
+                }
+            }
+        }
+        ScpiCommand::SystemError => {
+            let _ = errors.write_next(&mut out);
+            Ok(())
+        }
+    };
+
+    if let Err(err) = result {
+        errors.push(err);
+    }
+    if !out.is_empty() {
+        if !response.is_empty() {
+            let _ = response.push(';');
+        }
+        let _ = response.push_str(out.as_str());
+    }
+}
+
+/// Applies `update` to the manual TPS setpoint and switches the TPS to manual mode
+/// without touching EEPROM; the saved profile returns on reboot unless `*SAV` stores
+/// it. A held power lock rejects it.
+async fn scpi_update_manual(
+    api_state: &'static ApiSharedMutex,
+    update: impl FnOnce(&mut ManualTpsConfig),
+) -> Result<(), ScpiError> {
+    let mut config = { api_state.lock().await.power.config };
+    config.tps_mode = TpsMode::Manual;
+    update(&mut config.manual);
+    let config = config.validated().map_err(|_| ScpiError::ExecutionError)?;
+    scpi_set_power_config(api_state, ApiPowerConfigCommand::Apply { config }).await
+}
+
+async fn scpi_set_power_config(
+    api_state: &'static ApiSharedMutex,
+    command: ApiPowerConfigCommand,
+) -> Result<(), ScpiError> {
+    match try_set_power_config(api_state, command, None).await {
+        Ok(()) if crate::wait_power_config_result().await => Ok(()),
+        Ok(()) => Err(ScpiError::ExecutionError),
+        Err(ApiActionError::Busy) => Err(ScpiError::SettingsConflict),
+    }
+}
+
+async fn scpi_set_output(
+    api_state: &'static ApiSharedMutex,
+    enabled: bool,
+) -> Result<(), ScpiError> {
+    match try_set_power_runtime(
+        api_state,
+        ApiPowerRuntimeCommand::SetOutputEnabled { enabled },
+        None,
+    )
+    .await
+    {
+        Ok(()) if crate::wait_power_runtime_result().await => Ok(()),
+        Ok(()) => Err(ScpiError::ExecutionError),
+        Err(ApiActionError::Busy) => Err(ScpiError::SettingsConflict),
+    }
+}
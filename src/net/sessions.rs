@@ -0,0 +1,30 @@
+// Charging sessions served by `sessions.list` and `/api/v1/sessions`. The main
+// loop feeds every telemetry tick in; the list lives in RAM only.
+
+use isolapurr_api::sessions::ChargeSession;
+use isolapurr_usb_hub::session::{SESSION_LOG_LEN, SessionRecorder, SessionSample};
+
+static SESSION_RECORDER: BlockingMutex<CriticalSectionRawMutex, RefCell<SessionRecorder>> =
+    BlockingMutex::new(RefCell::new(SessionRecorder::new()));
+
+/// Feeds one port sample; returns the session it closed, if any.
+pub fn record_session_sample(
+    port: ApiPortId,
+    now_ms: u64,
+    sample: &SessionSample,
+) -> Option<ChargeSession> {
+    SESSION_RECORDER.lock(|recorder| recorder.borrow_mut().update(port, now_ms, sample))
+}
+
+/// `sessions.list` result: finished sessions and any open ones, oldest first.
+pub fn write_sessions_json(body: &mut String) {
+    let sessions = SESSION_RECORDER.lock(|recorder| recorder.borrow().list());
+    let _ = core::write!(body, "{{\"capacity\":{},\"sessions\":[", SESSION_LOG_LEN);
+    for (index, session) in sessions.iter().enumerate() {
+        if index > 0 {
+            body.push(',');
+        }
+        let _ = session.write_json(body);
+    }
+    body.push_str("]}");
+}
@@ -2,6 +2,18 @@ use embedded_hal::i2c::{ErrorKind, ErrorType};
 use embedded_hal_async::i2c::{I2c, Operation};
 
 use super::SW2303_ADDR_7BIT;
+use crate::i2c_diagnostics::{I2cBusCounters, classify_i2c_error};
+
+/// Every address the PD bus allowlist lets through, in diagnostics order.
+pub const PD_I2C_ADDRESSES: [u8; 1] = [SW2303_ADDR_7BIT];
+
+/// Device label reported next to a PD bus address by the diagnostics API.
+pub const fn pd_i2c_device(address: u8) -> &'static str {
+    match address {
+        SW2303_ADDR_7BIT => "sw2303",
+        _ => "unknown",
+    }
+}
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum PdI2cAddr {
@@ -36,6 +48,7 @@ where
 
 pub struct I2cAllowlist<I2C> {
     inner: I2C,
+    counters: I2cBusCounters<{ PD_I2C_ADDRESSES.len() }>,
 }
 
 fn ensure_allowed_address<E>(address: u8) -> Result<(), I2cAllowlistError<E>> {
@@ -48,13 +61,29 @@ fn ensure_allowed_address<E>(address: u8) -> Result<(), I2cAllowlistError<E>> {
 
 impl<I2C> I2cAllowlist<I2C> {
     pub const fn new(inner: I2C) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            counters: I2cBusCounters::new(PD_I2C_ADDRESSES),
+        }
     }
 
     pub fn into_inner(self) -> I2C {
         self.inner
     }
 
+    /// Rebuilds the wrapped bus (e.g. re-attaching pins after a park) while
+    /// keeping the diagnostics counters.
+    pub fn map_inner<J>(self, f: impl FnOnce(I2C) -> J) -> I2cAllowlist<J> {
+        I2cAllowlist {
+            inner: f(self.inner),
+            counters: self.counters,
+        }
+    }
+
+    pub const fn counters(&self) -> &I2cBusCounters<{ PD_I2C_ADDRESSES.len() }> {
+        &self.counters
+    }
+
     pub fn inner_mut(&mut self) -> &mut I2C {
         &mut self.inner
     }
@@ -78,10 +107,15 @@ where
         address: u8,
         operations: &mut [Operation<'_>],
     ) -> Result<(), Self::Error> {
-        ensure_allowed_address::<I2C::Error>(address)?;
-        self.inner
-            .transaction(address, operations)
-            .await
-            .map_err(I2cAllowlistError::Bus)
+        if let Err(err) = ensure_allowed_address::<I2C::Error>(address) {
+            self.counters.record_rejected();
+            return Err(err);
+        }
+        let result = self.inner.transaction(address, operations).await;
+        self.counters.record(
+            address,
+            result.as_ref().map(|_| ()).map_err(classify_i2c_error),
+        );
+        result.map_err(I2cAllowlistError::Bus)
     }
 }
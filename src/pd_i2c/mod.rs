@@ -3,7 +3,7 @@ pub mod sw2303;
 pub mod tps55288;
 pub mod types;
 
-pub use allowlist::{I2cAllowlist, PdI2cAddr};
+pub use allowlist::{I2cAllowlist, PD_I2C_ADDRESSES, PdI2cAddr, pd_i2c_device};
 pub use types::{PowerRequest, PowerSetpoint};
 
 /// SW2303 7-bit I2C address.
@@ -1,6 +1,9 @@
 use core::time::Duration;
 
 use crate::buzzer::BuzzerControl;
+use crate::sound_settings::{
+    CustomSoundPattern, LocalClockAnchor, SOUND_SLOT_COUNT, SoundClass, SoundSettings, SoundSlot,
+};
 
 use super::{
     DEFAULT_DUTY_PCT, DEFAULT_FREQ_HZ, ErrorKind, SoundEvent, SoundId, SoundPattern, SoundRepeat,
@@ -139,6 +142,10 @@ where
 
     playing: Option<ActivePlayback>,
     last_now: Duration,
+
+    settings: SoundSettings,
+    custom_patterns: [Option<CustomSoundPattern>; SOUND_SLOT_COUNT],
+    local_clock: Option<LocalClockAnchor>,
 }
 
 impl<B> PromptToneManager<B>
@@ -157,9 +164,53 @@ where
             identify_active: false,
             playing: None,
             last_now: Duration::ZERO,
+            settings: SoundSettings::defaults(),
+            custom_patterns: [None; SOUND_SLOT_COUNT],
+            local_clock: None,
         }
     }
 
+    /// Replace the user sound settings; a playing sound that is no longer
+    /// allowed stops immediately (safety alarms are never affected).
+    pub fn set_sound_settings(&mut self, settings: SoundSettings) {
+        self.settings = settings;
+        if self.playing.is_some_and(|p| !self.allows(p.id)) {
+            let _ = self.buzzer.stop();
+            self.playing = None;
+        }
+    }
+
+    pub fn sound_settings(&self) -> SoundSettings {
+        self.settings
+    }
+
+    /// Install (`Some`) or remove (`None`) a user pattern for `slot`.
+    pub fn set_custom_pattern(&mut self, slot: SoundSlot, pattern: Option<CustomSoundPattern>) {
+        self.custom_patterns[slot.index()] = pattern;
+        if self.playing.is_some_and(|p| sound_slot(p.id) == Some(slot)) {
+            let _ = self.buzzer.stop();
+            self.playing = None;
+        }
+    }
+
+    pub fn custom_patterns(&self) -> [Option<CustomSoundPattern>; SOUND_SLOT_COUNT] {
+        self.custom_patterns
+    }
+
+    /// Anchor the local wall clock used by quiet hours (the board has no RTC).
+    pub fn set_local_clock(&mut self, anchor: LocalClockAnchor) {
+        self.local_clock = Some(anchor);
+    }
+
+    pub fn local_clock_synced(&self) -> bool {
+        self.local_clock.is_some()
+    }
+
+    pub fn quiet_hours_active(&self) -> bool {
+        self.local_minute_of_day()
+            .is_some_and(|minute| self.settings.quiet_hours.contains(minute))
+    }
+
     pub fn notify(&mut self, event: SoundEvent) {
         match event {
             SoundEvent::InitWarn(_) => {
@@ -245,6 +296,12 @@ where
             SoundEvent::MenuConfirm => {
                 self.restart_feedback(SoundId::MenuConfirmOnce);
             }
+            SoundEvent::ChargeComplete => {
+                if self.safety_active {
+                    self.pause_safety_for_one_shot();
+                }
+                self.request_one_shot(SoundId::ChargeDoneOnce);
+            }
         }
     }
 
@@ -287,7 +344,7 @@ where
             if !self.safety_suspended {
                 self.start_with_now(SoundId::SafetyAlarm, now);
             }
-        } else if self.identify_active {
+        } else if self.identify_active && self.allows(SoundId::IdentifyLoop) {
             self.start_with_now(SoundId::IdentifyLoop, now);
         }
     }
@@ -307,7 +364,7 @@ where
             return;
         }
 
-        if self.is_one_shot_active_or_pending(id) {
+        if !self.allows(id) || self.is_one_shot_active_or_pending(id) {
             return;
         }
 
@@ -326,6 +383,9 @@ where
     }
 
     fn restart_feedback(&mut self, id: SoundId) {
+        if !self.allows(id) {
+            return;
+        }
         if self.safety_active {
             self.pause_safety_for_one_shot();
         }
@@ -360,6 +420,46 @@ where
         }
     }
 
+    fn allows(&self, id: SoundId) -> bool {
+        self.settings
+            .allows(sound_class(id), self.local_minute_of_day())
+    }
+
+    fn local_minute_of_day(&self) -> Option<u16> {
+        self.local_clock
+            .map(|clock| clock.minute_of_day_at(self.last_now.as_millis() as u64))
+    }
+
+    /// Step count and repeat mode, preferring a user pattern over the built-in one.
+    fn pattern_shape(&self, id: SoundId) -> Option<(usize, SoundRepeat)> {
+        let builtin = pattern_for(id)?;
+        match self.custom_pattern(id) {
+            Some(custom) => Some((custom.steps().len(), builtin.repeat)),
+            None => Some((builtin.steps.len(), builtin.repeat)),
+        }
+    }
+
+    fn step_at(&self, id: SoundId, index: usize) -> Option<SoundStep> {
+        let Some(custom) = self.custom_pattern(id) else {
+            return pattern_for(id)?.steps.get(index).copied();
+        };
+        let step = custom.steps().get(index)?;
+        let duration = Duration::from_millis(u64::from(step.duration_ms));
+        Some(if step.freq_hz == 0 {
+            SoundStep::Silence { duration }
+        } else {
+            SoundStep::Tone {
+                freq_hz: u32::from(step.freq_hz),
+                duty_pct: DEFAULT_DUTY_PCT,
+                duration,
+            }
+        })
+    }
+
+    fn custom_pattern(&self, id: SoundId) -> Option<&CustomSoundPattern> {
+        self.custom_patterns[sound_slot(id)?.index()].as_ref()
+    }
+
     fn is_one_shot_active_or_pending(&self, id: SoundId) -> bool {
         self.playing.is_some_and(|p| p.id == id) || self.queue.contains(id)
     }
@@ -380,19 +480,23 @@ where
             return;
         };
 
-        let Some(pattern) = pattern_for(playing.id) else {
+        let Some((step_count, repeat)) = self.pattern_shape(playing.id) else {
             let _ = self.buzzer.stop();
             self.playing = None;
             return;
         };
-        if pattern.steps.is_empty() {
+        if step_count == 0 {
             let _ = self.buzzer.stop();
             self.playing = None;
             return;
         }
 
         if playing.deadline.is_none() {
-            let step = pattern.steps[playing.step_index];
+            let Some(step) = self.step_at(playing.id, playing.step_index) else {
+                let _ = self.buzzer.stop();
+                self.playing = None;
+                return;
+            };
             playing.deadline = Some(now.saturating_add(step_duration(step)));
         }
 
@@ -403,15 +507,15 @@ where
             }
 
             guard += 1;
-            if guard > pattern.steps.len().saturating_add(2) {
+            if guard > step_count.saturating_add(2) {
                 let _ = self.buzzer.stop();
                 self.playing = None;
                 return;
             }
 
             playing.step_index += 1;
-            if playing.step_index >= pattern.steps.len() {
-                match pattern.repeat {
+            if playing.step_index >= step_count {
+                match repeat {
                     SoundRepeat::Once => {
                         let _ = self.buzzer.stop();
                         self.playing = None;
@@ -436,12 +540,7 @@ where
         let Some(playing) = self.playing else {
             return;
         };
-        let Some(pattern) = pattern_for(playing.id) else {
-            let _ = self.buzzer.stop();
-            self.playing = None;
-            return;
-        };
-        let Some(step) = pattern.steps.get(playing.step_index).copied() else {
+        let Some(step) = self.step_at(playing.id, playing.step_index) else {
             let _ = self.buzzer.stop();
             self.playing = None;
             return;
@@ -451,7 +550,14 @@ where
             SoundStep::Tone {
                 freq_hz, duty_pct, ..
             } => {
-                let _ = self.buzzer.start_tone(freq_hz, duty_pct);
+                let duty_pct = self
+                    .settings
+                    .scaled_duty_pct(sound_class(playing.id), duty_pct);
+                if duty_pct == 0 {
+                    let _ = self.buzzer.stop();
+                } else {
+                    let _ = self.buzzer.start_tone(freq_hz, duty_pct);
+                }
             }
             SoundStep::Silence { .. } => {
                 let _ = self.buzzer.stop();
@@ -463,10 +569,7 @@ where
         let Some(mut playing) = self.playing else {
             return;
         };
-        let Some(pattern) = pattern_for(playing.id) else {
-            return;
-        };
-        let Some(step) = pattern.steps.get(playing.step_index).copied() else {
+        let Some(step) = self.step_at(playing.id, playing.step_index) else {
             return;
         };
 
@@ -495,6 +598,7 @@ fn sound_priority(id: SoundId) -> u8 {
         SoundId::WarningOnce => 50,
 
         SoundId::RecoverOnce => 40,
+        SoundId::ChargeDoneOnce => 40,
         SoundId::ActionOkOnce => 35,
         SoundId::ActionFailOnce => 35,
         SoundId::MenuConfirmOnce => 35,
@@ -508,6 +612,40 @@ fn sound_priority(id: SoundId) -> u8 {
     }
 }
 
+fn sound_class(id: SoundId) -> SoundClass {
+    match id {
+        SoundId::SafetyAlarm | SoundId::OverTemp | SoundId::OverCurrent | SoundId::OverVoltage => {
+            SoundClass::Safety
+        }
+        SoundId::BootOk | SoundId::BootWarn | SoundId::BootFail => SoundClass::Boot,
+        SoundId::WarningOnce | SoundId::ErrorOnce => SoundClass::Warning,
+        SoundId::MenuNavigateOnce | SoundId::MenuConfirmOnce => SoundClass::Menu,
+        SoundId::IdentifyLoop
+        | SoundId::RecoverOnce
+        | SoundId::ActionOkOnce
+        | SoundId::ActionFailOnce
+        | SoundId::ChargeDoneOnce
+        | SoundId::ActionOnce
+        | SoundId::PdOnce => SoundClass::Action,
+    }
+}
+
+fn sound_slot(id: SoundId) -> Option<SoundSlot> {
+    match id {
+        SoundId::BootOk => Some(SoundSlot::BootOk),
+        SoundId::BootWarn => Some(SoundSlot::BootWarn),
+        SoundId::BootFail => Some(SoundSlot::BootFail),
+        SoundId::WarningOnce => Some(SoundSlot::Warning),
+        SoundId::ErrorOnce => Some(SoundSlot::Error),
+        SoundId::ActionOkOnce => Some(SoundSlot::ActionOk),
+        SoundId::ActionFailOnce => Some(SoundSlot::ActionFail),
+        SoundId::MenuNavigateOnce => Some(SoundSlot::MenuNavigate),
+        SoundId::MenuConfirmOnce => Some(SoundSlot::MenuConfirm),
+        SoundId::IdentifyLoop => Some(SoundSlot::Identify),
+        _ => None,
+    }
+}
+
 fn map_error_to_sound(kind: ErrorKind) -> SoundId {
     match kind {
         ErrorKind::Sw2303I2c => SoundId::ErrorOnce,
@@ -761,6 +899,31 @@ const ACTION_FAIL_ONCE_STEPS: &[SoundStep] = &[
     },
 ];
 
+/// Rising three-note chime, distinct from the boot pattern and action clicks.
+const CHARGE_DONE_ONCE_STEPS: &[SoundStep] = &[
+    SoundStep::Tone {
+        freq_hz: DEFAULT_FREQ_HZ,
+        duty_pct: ACTION_DUTY_PCT,
+        duration: Duration::from_millis(90),
+    },
+    SoundStep::Silence {
+        duration: Duration::from_millis(60),
+    },
+    SoundStep::Tone {
+        freq_hz: ACTION_FREQ_HZ,
+        duty_pct: ACTION_DUTY_PCT,
+        duration: Duration::from_millis(90),
+    },
+    SoundStep::Silence {
+        duration: Duration::from_millis(60),
+    },
+    SoundStep::Tone {
+        freq_hz: 3200,
+        duty_pct: ACTION_DUTY_PCT,
+        duration: Duration::from_millis(180),
+    },
+];
+
 const SAFETY_ALARM_STEPS: &[SoundStep] = &[
     SoundStep::Tone {
         freq_hz: DEFAULT_FREQ_HZ,
@@ -781,6 +944,7 @@ pub const PATTERN_ACTION_OK_ONCE: SoundPattern = SoundPattern::once(ACTION_OK_ON
 pub const PATTERN_ACTION_FAIL_ONCE: SoundPattern = SoundPattern::once(ACTION_FAIL_ONCE_STEPS);
 pub const PATTERN_MENU_NAVIGATE_ONCE: SoundPattern = SoundPattern::once(MENU_NAVIGATE_ONCE_STEPS);
 pub const PATTERN_MENU_CONFIRM_ONCE: SoundPattern = SoundPattern::once(MENU_CONFIRM_ONCE_STEPS);
+pub const PATTERN_CHARGE_DONE_ONCE: SoundPattern = SoundPattern::once(CHARGE_DONE_ONCE_STEPS);
 pub const PATTERN_SAFETY_ALARM: SoundPattern = SoundPattern::looped(SAFETY_ALARM_STEPS);
 pub const PATTERN_IDENTIFY_LOOP: SoundPattern = SoundPattern::looped(IDENTIFY_LOOP_STEPS);
 
@@ -795,6 +959,7 @@ fn pattern_for(id: SoundId) -> Option<&'static SoundPattern> {
         SoundId::ActionFailOnce => Some(&PATTERN_ACTION_FAIL_ONCE),
         SoundId::MenuNavigateOnce => Some(&PATTERN_MENU_NAVIGATE_ONCE),
         SoundId::MenuConfirmOnce => Some(&PATTERN_MENU_CONFIRM_ONCE),
+        SoundId::ChargeDoneOnce => Some(&PATTERN_CHARGE_DONE_ONCE),
         SoundId::SafetyAlarm => Some(&PATTERN_SAFETY_ALARM),
         SoundId::IdentifyLoop => Some(&PATTERN_IDENTIFY_LOOP),
         _ => None,
@@ -62,6 +62,8 @@ pub enum SoundId {
     MenuNavigateOnce,
     /// A retriggerable acknowledgement for hardware menu confirmation.
     MenuConfirmOnce,
+    /// A port's charge-termination detector fired.
+    ChargeDoneOnce,
 
     /// Reserved: a generic acknowledgement for user actions.
     ActionOnce,
@@ -108,6 +110,8 @@ pub enum SoundEvent {
     MenuNavigate,
     /// Hardware menu item was opened or confirmed.
     MenuConfirm,
+    /// A port finished charging (charge-termination detector).
+    ChargeComplete,
 }
 
 /// Warning reasons during initialization.
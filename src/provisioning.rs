@@ -4,13 +4,34 @@ use embassy_time::{Duration, Timer};
 use embedded_hal::i2c::{Error, ErrorKind, SevenBitAddress};
 use embedded_hal_async::i2c::{I2c, Operation};
 
+use crate::button_settings::ButtonSettings;
+use crate::charge_termination::ChargeTerminationSettings;
+use crate::display_settings::DisplaySettings;
 use crate::idle_bias::IdleBiasCalibration;
+use crate::jsonl_tcp::JsonlToken;
+use crate::log_ring::LogSettings;
+use crate::port_data::PortDataSettings;
+use crate::port_power::PortPowerSettings;
 use crate::power_config::PowerConfig;
+use crate::sound_settings::{CustomSoundPattern, SoundSettings, SoundSlot};
 use isolapurr_firmware_core::provisioning::{
-    IDLE_BIAS_MAGIC, IDLE_BIAS_RECORD_LEN, IDLE_BIAS_VERSION, POWER_SETTINGS_MAGIC,
-    POWER_SETTINGS_RECORD_LEN, POWER_SETTINGS_VERSION, checksum, decode_idle_bias_calibration,
-    decode_power_config, encode_idle_bias_calibration, encode_power_config,
-    power_settings_version_supported, record_checksum_matches, write_record_checksum,
+    BUTTON_SETTINGS_MAGIC, BUTTON_SETTINGS_RECORD_LEN, BUTTON_SETTINGS_VERSION,
+    CHARGE_TERMINATION_MAGIC, CHARGE_TERMINATION_RECORD_LEN, CHARGE_TERMINATION_VERSION,
+    CUSTOM_TONE_MAGIC, CUSTOM_TONE_RECORD_LEN, CUSTOM_TONE_VERSION, DISPLAY_SETTINGS_MAGIC,
+    DISPLAY_SETTINGS_RECORD_LEN, DISPLAY_SETTINGS_VERSION, IDLE_BIAS_MAGIC, IDLE_BIAS_RECORD_LEN,
+    IDLE_BIAS_VERSION, JSONL_TOKEN_MAGIC, JSONL_TOKEN_RECORD_LEN, JSONL_TOKEN_VERSION,
+    LOG_SETTINGS_MAGIC, LOG_SETTINGS_RECORD_LEN, LOG_SETTINGS_VERSION, PORT_DATA_MAGIC,
+    PORT_DATA_RECORD_LEN, PORT_DATA_VERSION, PORT_POWER_MAGIC, PORT_POWER_RECORD_LEN,
+    PORT_POWER_VERSION, POWER_SETTINGS_MAGIC, POWER_SETTINGS_RECORD_LEN, POWER_SETTINGS_VERSION,
+    SOUND_SETTINGS_MAGIC, SOUND_SETTINGS_RECORD_LEN, SOUND_SETTINGS_VERSION, checksum,
+    decode_button_settings, decode_charge_termination_settings, decode_custom_tone,
+    decode_display_settings, decode_idle_bias_calibration, decode_jsonl_token, decode_log_settings,
+    decode_port_data_settings, decode_port_power_settings, decode_power_config,
+    decode_sound_settings, encode_button_settings, encode_charge_termination_settings,
+    encode_custom_tone, encode_display_settings, encode_idle_bias_calibration, encode_jsonl_token,
+    encode_log_settings, encode_port_data_settings, encode_port_power_settings,
+    encode_power_config, encode_sound_settings, power_settings_version_supported,
+    record_checksum_matches, write_record_checksum,
 };
 
 pub const WIFI_EEPROM_ADDR_7BIT: SevenBitAddress = 0x50;
@@ -32,21 +53,17 @@ const DEVICE_SETTINGS_ROUTE_MCU: u8 = 0;
 const DEVICE_SETTINGS_ROUTE_USB_C: u8 = 1;
 const POWER_SETTINGS_RECORD_OFFSET: u16 = 320;
 const IDLE_BIAS_RECORD_OFFSET: u16 = 416;
-
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub enum UsbCDownstreamRoute {
-    Mcu,
-    UsbC,
-}
-
-impl UsbCDownstreamRoute {
-    pub const fn as_str(self) -> &'static str {
-        match self {
-            Self::Mcu => "mcu",
-            Self::UsbC => "usb_c",
-        }
-    }
-}
+const SOUND_SETTINGS_RECORD_OFFSET: u16 = 512;
+const CUSTOM_TONE_RECORD_OFFSET: u16 = 544;
+const DISPLAY_SETTINGS_RECORD_OFFSET: u16 = 1024;
+const BUTTON_SETTINGS_RECORD_OFFSET: u16 = 1056;
+const JSONL_TOKEN_RECORD_OFFSET: u16 = 1088;
+const LOG_SETTINGS_RECORD_OFFSET: u16 = 1136;
+const PORT_POWER_RECORD_OFFSET: u16 = 1168;
+const PORT_DATA_RECORD_OFFSET: u16 = 1184;
+const CHARGE_TERMINATION_RECORD_OFFSET: u16 = 1200;
+
+pub use isolapurr_api::ports::UsbCDownstreamRoute;
 
 pub const DEFAULT_USB_C_DOWNSTREAM_ROUTE: UsbCDownstreamRoute = UsbCDownstreamRoute::Mcu;
 
@@ -418,6 +435,513 @@ where
     eeprom_write(i2c, IDLE_BIAS_RECORD_OFFSET, &[0u8; IDLE_BIAS_RECORD_LEN]).await
 }
 
+pub async fn load_sound_settings<I2C>(
+    i2c: &mut I2C,
+) -> Result<Option<SoundSettings>, ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    let mut record = [0u8; SOUND_SETTINGS_RECORD_LEN];
+    eeprom_read(i2c, SOUND_SETTINGS_RECORD_OFFSET, &mut record).await?;
+
+    if record.iter().all(|b| *b == 0x00 || *b == 0xff) {
+        return Ok(None);
+    }
+    if &record[..SOUND_SETTINGS_MAGIC.len()] != SOUND_SETTINGS_MAGIC
+        || record[SOUND_SETTINGS_MAGIC.len()] != SOUND_SETTINGS_VERSION
+    {
+        return Err(ProvisioningError::InvalidRecord);
+    }
+
+    if !record_checksum_matches(&mut record) {
+        return Err(ProvisioningError::InvalidRecord);
+    }
+
+    decode_sound_settings(&record)
+        .map(Some)
+        .ok_or(ProvisioningError::InvalidRecord)
+}
+
+pub async fn store_sound_settings<I2C>(
+    i2c: &mut I2C,
+    settings: SoundSettings,
+) -> Result<(), ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    let settings = settings
+        .validated()
+        .map_err(|_| ProvisioningError::InvalidInput)?;
+    let mut record = [0u8; SOUND_SETTINGS_RECORD_LEN];
+    record[..SOUND_SETTINGS_MAGIC.len()].copy_from_slice(SOUND_SETTINGS_MAGIC);
+    record[SOUND_SETTINGS_MAGIC.len()] = SOUND_SETTINGS_VERSION;
+    encode_sound_settings(&mut record, settings);
+
+    write_record_checksum(&mut record);
+    eeprom_write(i2c, SOUND_SETTINGS_RECORD_OFFSET, &record).await
+}
+
+pub async fn clear_sound_settings<I2C>(i2c: &mut I2C) -> Result<(), ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    eeprom_write(
+        i2c,
+        SOUND_SETTINGS_RECORD_OFFSET,
+        &[0u8; SOUND_SETTINGS_RECORD_LEN],
+    )
+    .await
+}
+
+const fn custom_tone_record_offset(slot: SoundSlot) -> u16 {
+    CUSTOM_TONE_RECORD_OFFSET + (slot.index() * CUSTOM_TONE_RECORD_LEN) as u16
+}
+
+pub async fn load_custom_tone<I2C>(
+    i2c: &mut I2C,
+    slot: SoundSlot,
+) -> Result<Option<CustomSoundPattern>, ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    let mut record = [0u8; CUSTOM_TONE_RECORD_LEN];
+    eeprom_read(i2c, custom_tone_record_offset(slot), &mut record).await?;
+
+    if record.iter().all(|b| *b == 0x00 || *b == 0xff) {
+        return Ok(None);
+    }
+    if &record[..CUSTOM_TONE_MAGIC.len()] != CUSTOM_TONE_MAGIC
+        || record[CUSTOM_TONE_MAGIC.len()] != CUSTOM_TONE_VERSION
+    {
+        return Err(ProvisioningError::InvalidRecord);
+    }
+
+    if !record_checksum_matches(&mut record) {
+        return Err(ProvisioningError::InvalidRecord);
+    }
+
+    decode_custom_tone(&record, slot)
+        .map(Some)
+        .ok_or(ProvisioningError::InvalidRecord)
+}
+
+pub async fn store_custom_tone<I2C>(
+    i2c: &mut I2C,
+    slot: SoundSlot,
+    pattern: &CustomSoundPattern,
+) -> Result<(), ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    let mut record = [0u8; CUSTOM_TONE_RECORD_LEN];
+    record[..CUSTOM_TONE_MAGIC.len()].copy_from_slice(CUSTOM_TONE_MAGIC);
+    record[CUSTOM_TONE_MAGIC.len()] = CUSTOM_TONE_VERSION;
+    encode_custom_tone(&mut record, slot, pattern);
+
+    write_record_checksum(&mut record);
+    eeprom_write(i2c, custom_tone_record_offset(slot), &record).await
+}
+
+pub async fn clear_custom_tone<I2C>(
+    i2c: &mut I2C,
+    slot: SoundSlot,
+) -> Result<(), ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    eeprom_write(
+        i2c,
+        custom_tone_record_offset(slot),
+        &[0u8; CUSTOM_TONE_RECORD_LEN],
+    )
+    .await
+}
+
+pub async fn clear_custom_tones<I2C>(i2c: &mut I2C) -> Result<(), ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    for slot in SoundSlot::ALL {
+        clear_custom_tone(i2c, slot).await?;
+    }
+    Ok(())
+}
+
+pub async fn load_display_settings<I2C>(
+    i2c: &mut I2C,
+) -> Result<Option<DisplaySettings>, ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    let mut record = [0u8; DISPLAY_SETTINGS_RECORD_LEN];
+    eeprom_read(i2c, DISPLAY_SETTINGS_RECORD_OFFSET, &mut record).await?;
+
+    if record.iter().all(|b| *b == 0x00 || *b == 0xff) {
+        return Ok(None);
+    }
+    if &record[..DISPLAY_SETTINGS_MAGIC.len()] != DISPLAY_SETTINGS_MAGIC
+        || record[DISPLAY_SETTINGS_MAGIC.len()] != DISPLAY_SETTINGS_VERSION
+    {
+        return Err(ProvisioningError::InvalidRecord);
+    }
+
+    if !record_checksum_matches(&mut record) {
+        return Err(ProvisioningError::InvalidRecord);
+    }
+
+    decode_display_settings(&record)
+        .map(Some)
+        .ok_or(ProvisioningError::InvalidRecord)
+}
+
+pub async fn store_display_settings<I2C>(
+    i2c: &mut I2C,
+    settings: DisplaySettings,
+) -> Result<(), ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    let settings = settings
+        .validated()
+        .map_err(|_| ProvisioningError::InvalidInput)?;
+    let mut record = [0u8; DISPLAY_SETTINGS_RECORD_LEN];
+    record[..DISPLAY_SETTINGS_MAGIC.len()].copy_from_slice(DISPLAY_SETTINGS_MAGIC);
+    record[DISPLAY_SETTINGS_MAGIC.len()] = DISPLAY_SETTINGS_VERSION;
+    encode_display_settings(&mut record, settings);
+
+    write_record_checksum(&mut record);
+    eeprom_write(i2c, DISPLAY_SETTINGS_RECORD_OFFSET, &record).await
+}
+
+pub async fn clear_display_settings<I2C>(i2c: &mut I2C) -> Result<(), ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    eeprom_write(
+        i2c,
+        DISPLAY_SETTINGS_RECORD_OFFSET,
+        &[0u8; DISPLAY_SETTINGS_RECORD_LEN],
+    )
+    .await
+}
+
+pub async fn load_button_settings<I2C>(
+    i2c: &mut I2C,
+) -> Result<Option<ButtonSettings>, ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    let mut record = [0u8; BUTTON_SETTINGS_RECORD_LEN];
+    eeprom_read(i2c, BUTTON_SETTINGS_RECORD_OFFSET, &mut record).await?;
+
+    if record.iter().all(|b| *b == 0x00 || *b == 0xff) {
+        return Ok(None);
+    }
+    if &record[..BUTTON_SETTINGS_MAGIC.len()] != BUTTON_SETTINGS_MAGIC
+        || record[BUTTON_SETTINGS_MAGIC.len()] != BUTTON_SETTINGS_VERSION
+    {
+        return Err(ProvisioningError::InvalidRecord);
+    }
+
+    if !record_checksum_matches(&mut record) {
+        return Err(ProvisioningError::InvalidRecord);
+    }
+
+    decode_button_settings(&record)
+        .map(Some)
+        .ok_or(ProvisioningError::InvalidRecord)
+}
+
+pub async fn store_button_settings<I2C>(
+    i2c: &mut I2C,
+    settings: ButtonSettings,
+) -> Result<(), ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    let settings = settings
+        .validated()
+        .map_err(|_| ProvisioningError::InvalidInput)?;
+    let mut record = [0u8; BUTTON_SETTINGS_RECORD_LEN];
+    record[..BUTTON_SETTINGS_MAGIC.len()].copy_from_slice(BUTTON_SETTINGS_MAGIC);
+    record[BUTTON_SETTINGS_MAGIC.len()] = BUTTON_SETTINGS_VERSION;
+    encode_button_settings(&mut record, settings);
+
+    write_record_checksum(&mut record);
+    eeprom_write(i2c, BUTTON_SETTINGS_RECORD_OFFSET, &record).await
+}
+
+pub async fn clear_button_settings<I2C>(i2c: &mut I2C) -> Result<(), ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    eeprom_write(
+        i2c,
+        BUTTON_SETTINGS_RECORD_OFFSET,
+        &[0u8; BUTTON_SETTINGS_RECORD_LEN],
+    )
+    .await
+}
+
+pub async fn load_jsonl_token<I2C>(
+    i2c: &mut I2C,
+) -> Result<Option<JsonlToken>, ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    let mut record = [0u8; JSONL_TOKEN_RECORD_LEN];
+    eeprom_read(i2c, JSONL_TOKEN_RECORD_OFFSET, &mut record).await?;
+
+    if record.iter().all(|b| *b == 0x00 || *b == 0xff) {
+        return Ok(None);
+    }
+    if &record[..JSONL_TOKEN_MAGIC.len()] != JSONL_TOKEN_MAGIC
+        || record[JSONL_TOKEN_MAGIC.len()] != JSONL_TOKEN_VERSION
+    {
+        return Err(ProvisioningError::InvalidRecord);
+    }
+
+    if !record_checksum_matches(&mut record) {
+        return Err(ProvisioningError::InvalidRecord);
+    }
+
+    decode_jsonl_token(&record)
+        .map(Some)
+        .ok_or(ProvisioningError::InvalidRecord)
+}
+
+pub async fn store_jsonl_token<I2C>(
+    i2c: &mut I2C,
+    token: &JsonlToken,
+) -> Result<(), ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    let mut record = [0u8; JSONL_TOKEN_RECORD_LEN];
+    record[..JSONL_TOKEN_MAGIC.len()].copy_from_slice(JSONL_TOKEN_MAGIC);
+    record[JSONL_TOKEN_MAGIC.len()] = JSONL_TOKEN_VERSION;
+    encode_jsonl_token(&mut record, token);
+
+    write_record_checksum(&mut record);
+    eeprom_write(i2c, JSONL_TOKEN_RECORD_OFFSET, &record).await
+}
+
+pub async fn clear_jsonl_token<I2C>(i2c: &mut I2C) -> Result<(), ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    eeprom_write(
+        i2c,
+        JSONL_TOKEN_RECORD_OFFSET,
+        &[0u8; JSONL_TOKEN_RECORD_LEN],
+    )
+    .await
+}
+
+pub async fn load_log_settings<I2C>(
+    i2c: &mut I2C,
+) -> Result<Option<LogSettings>, ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    let mut record = [0u8; LOG_SETTINGS_RECORD_LEN];
+    eeprom_read(i2c, LOG_SETTINGS_RECORD_OFFSET, &mut record).await?;
+
+    if record.iter().all(|b| *b == 0x00 || *b == 0xff) {
+        return Ok(None);
+    }
+    if &record[..LOG_SETTINGS_MAGIC.len()] != LOG_SETTINGS_MAGIC
+        || record[LOG_SETTINGS_MAGIC.len()] != LOG_SETTINGS_VERSION
+    {
+        return Err(ProvisioningError::InvalidRecord);
+    }
+
+    if !record_checksum_matches(&mut record) {
+        return Err(ProvisioningError::InvalidRecord);
+    }
+
+    decode_log_settings(&record)
+        .map(Some)
+        .ok_or(ProvisioningError::InvalidRecord)
+}
+
+pub async fn store_log_settings<I2C>(
+    i2c: &mut I2C,
+    settings: LogSettings,
+) -> Result<(), ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    let mut record = [0u8; LOG_SETTINGS_RECORD_LEN];
+    record[..LOG_SETTINGS_MAGIC.len()].copy_from_slice(LOG_SETTINGS_MAGIC);
+    record[LOG_SETTINGS_MAGIC.len()] = LOG_SETTINGS_VERSION;
+    encode_log_settings(&mut record, settings);
+
+    write_record_checksum(&mut record);
+    eeprom_write(i2c, LOG_SETTINGS_RECORD_OFFSET, &record).await
+}
+
+pub async fn load_port_power_settings<I2C>(
+    i2c: &mut I2C,
+) -> Result<Option<PortPowerSettings>, ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    let mut record = [0u8; PORT_POWER_RECORD_LEN];
+    eeprom_read(i2c, PORT_POWER_RECORD_OFFSET, &mut record).await?;
+
+    if record.iter().all(|b| *b == 0x00 || *b == 0xff) {
+        return Ok(None);
+    }
+    if &record[..PORT_POWER_MAGIC.len()] != PORT_POWER_MAGIC
+        || record[PORT_POWER_MAGIC.len()] != PORT_POWER_VERSION
+    {
+        return Err(ProvisioningError::InvalidRecord);
+    }
+
+    if !record_checksum_matches(&mut record) {
+        return Err(ProvisioningError::InvalidRecord);
+    }
+
+    decode_port_power_settings(&record)
+        .map(Some)
+        .ok_or(ProvisioningError::InvalidRecord)
+}
+
+pub async fn store_port_power_settings<I2C>(
+    i2c: &mut I2C,
+    settings: PortPowerSettings,
+) -> Result<(), ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    let mut record = [0u8; PORT_POWER_RECORD_LEN];
+    record[..PORT_POWER_MAGIC.len()].copy_from_slice(PORT_POWER_MAGIC);
+    record[PORT_POWER_MAGIC.len()] = PORT_POWER_VERSION;
+    encode_port_power_settings(&mut record, settings);
+
+    write_record_checksum(&mut record);
+    eeprom_write(i2c, PORT_POWER_RECORD_OFFSET, &record).await
+}
+
+pub async fn clear_port_power_settings<I2C>(
+    i2c: &mut I2C,
+) -> Result<(), ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    eeprom_write(i2c, PORT_POWER_RECORD_OFFSET, &[0u8; PORT_POWER_RECORD_LEN]).await
+}
+
+pub async fn load_port_data_settings<I2C>(
+    i2c: &mut I2C,
+) -> Result<Option<PortDataSettings>, ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    let mut record = [0u8; PORT_DATA_RECORD_LEN];
+    eeprom_read(i2c, PORT_DATA_RECORD_OFFSET, &mut record).await?;
+
+    if record.iter().all(|b| *b == 0x00 || *b == 0xff) {
+        return Ok(None);
+    }
+    if &record[..PORT_DATA_MAGIC.len()] != PORT_DATA_MAGIC
+        || record[PORT_DATA_MAGIC.len()] != PORT_DATA_VERSION
+    {
+        return Err(ProvisioningError::InvalidRecord);
+    }
+
+    if !record_checksum_matches(&mut record) {
+        return Err(ProvisioningError::InvalidRecord);
+    }
+
+    decode_port_data_settings(&record)
+        .map(Some)
+        .ok_or(ProvisioningError::InvalidRecord)
+}
+
+pub async fn store_port_data_settings<I2C>(
+    i2c: &mut I2C,
+    settings: PortDataSettings,
+) -> Result<(), ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    let mut record = [0u8; PORT_DATA_RECORD_LEN];
+    record[..PORT_DATA_MAGIC.len()].copy_from_slice(PORT_DATA_MAGIC);
+    record[PORT_DATA_MAGIC.len()] = PORT_DATA_VERSION;
+    encode_port_data_settings(&mut record, settings);
+
+    write_record_checksum(&mut record);
+    eeprom_write(i2c, PORT_DATA_RECORD_OFFSET, &record).await
+}
+
+pub async fn clear_port_data_settings<I2C>(
+    i2c: &mut I2C,
+) -> Result<(), ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    eeprom_write(i2c, PORT_DATA_RECORD_OFFSET, &[0u8; PORT_DATA_RECORD_LEN]).await
+}
+
+pub async fn load_charge_termination_settings<I2C>(
+    i2c: &mut I2C,
+) -> Result<Option<ChargeTerminationSettings>, ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    let mut record = [0u8; CHARGE_TERMINATION_RECORD_LEN];
+    eeprom_read(i2c, CHARGE_TERMINATION_RECORD_OFFSET, &mut record).await?;
+
+    if record.iter().all(|b| *b == 0x00 || *b == 0xff) {
+        return Ok(None);
+    }
+    if &record[..CHARGE_TERMINATION_MAGIC.len()] != CHARGE_TERMINATION_MAGIC
+        || record[CHARGE_TERMINATION_MAGIC.len()] != CHARGE_TERMINATION_VERSION
+    {
+        return Err(ProvisioningError::InvalidRecord);
+    }
+
+    if !record_checksum_matches(&mut record) {
+        return Err(ProvisioningError::InvalidRecord);
+    }
+
+    decode_charge_termination_settings(&record)
+        .map(Some)
+        .ok_or(ProvisioningError::InvalidRecord)
+}
+
+pub async fn store_charge_termination_settings<I2C>(
+    i2c: &mut I2C,
+    settings: ChargeTerminationSettings,
+) -> Result<(), ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    let mut record = [0u8; CHARGE_TERMINATION_RECORD_LEN];
+    record[..CHARGE_TERMINATION_MAGIC.len()].copy_from_slice(CHARGE_TERMINATION_MAGIC);
+    record[CHARGE_TERMINATION_MAGIC.len()] = CHARGE_TERMINATION_VERSION;
+    encode_charge_termination_settings(&mut record, settings);
+
+    write_record_checksum(&mut record);
+    eeprom_write(i2c, CHARGE_TERMINATION_RECORD_OFFSET, &record).await
+}
+
+pub async fn clear_charge_termination_settings<I2C>(
+    i2c: &mut I2C,
+) -> Result<(), ProvisioningError<I2C::Error>>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    eeprom_write(
+        i2c,
+        CHARGE_TERMINATION_RECORD_OFFSET,
+        &[0u8; CHARGE_TERMINATION_RECORD_LEN],
+    )
+    .await
+}
+
 async fn eeprom_read<I2C>(
     i2c: &mut I2C,
     offset: u16,
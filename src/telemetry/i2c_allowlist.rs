@@ -5,10 +5,49 @@ use super::hardware::{
     INA226_U13_ADDR_7BIT, INA226_U13_FALLBACK_ADDR_7BIT, INA226_U17_ADDR_7BIT,
     INA226_U17_FALLBACK_ADDR_7BIT, TMP112_ADDR_7BIT,
 };
+use crate::i2c_diagnostics::{I2cBusCounters, classify_i2c_error};
 use crate::pd_i2c::TPS55288_ADDR_7BIT;
 #[cfg(feature = "net_http")]
 use crate::provisioning::WIFI_EEPROM_ADDR_7BIT;
 
+/// Every address the system bus allowlist lets through, in diagnostics order.
+#[cfg(feature = "net_http")]
+pub const TELEMETRY_I2C_ADDRESSES: [SevenBitAddress; 7] = [
+    INA226_U13_ADDR_7BIT,
+    INA226_U13_FALLBACK_ADDR_7BIT,
+    INA226_U17_ADDR_7BIT,
+    INA226_U17_FALLBACK_ADDR_7BIT,
+    TMP112_ADDR_7BIT,
+    TPS55288_ADDR_7BIT,
+    WIFI_EEPROM_ADDR_7BIT,
+];
+/// Every address the system bus allowlist lets through, in diagnostics order.
+#[cfg(not(feature = "net_http"))]
+pub const TELEMETRY_I2C_ADDRESSES: [SevenBitAddress; 6] = [
+    INA226_U13_ADDR_7BIT,
+    INA226_U13_FALLBACK_ADDR_7BIT,
+    INA226_U17_ADDR_7BIT,
+    INA226_U17_FALLBACK_ADDR_7BIT,
+    TMP112_ADDR_7BIT,
+    TPS55288_ADDR_7BIT,
+];
+
+pub type TelemetryI2cCounters = I2cBusCounters<{ TELEMETRY_I2C_ADDRESSES.len() }>;
+
+/// Device label reported next to a system bus address by the diagnostics API.
+/// Only one address of each INA226 pair answers on a given board.
+pub const fn telemetry_i2c_device(address: SevenBitAddress) -> &'static str {
+    match address {
+        INA226_U13_ADDR_7BIT | INA226_U13_FALLBACK_ADDR_7BIT => "ina226_u13",
+        INA226_U17_ADDR_7BIT | INA226_U17_FALLBACK_ADDR_7BIT => "ina226_u17",
+        TMP112_ADDR_7BIT => "tmp112_u23",
+        TPS55288_ADDR_7BIT => "tps55288",
+        #[cfg(feature = "net_http")]
+        WIFI_EEPROM_ADDR_7BIT => "eeprom_u21",
+        _ => "unknown",
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum TelemetryI2cError<E> {
     AddressNotAllowed(SevenBitAddress),
@@ -34,13 +73,22 @@ impl<E: Error> Error for TelemetryI2cError<E> {
 ///   provisioning calls when `net_http` is enabled.
 /// - Allow direct TMP112 U23 register reads at `0x48`; never scan / never
 ///   touch any other devices on the same bus.
+/// - `i2c.scan` probes exactly [`TELEMETRY_I2C_ADDRESSES`], nothing else.
 pub struct TelemetryI2cAllowlist<I2C> {
     inner: I2C,
+    counters: TelemetryI2cCounters,
 }
 
 impl<I2C> TelemetryI2cAllowlist<I2C> {
     pub const fn new(inner: I2C) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            counters: I2cBusCounters::new(TELEMETRY_I2C_ADDRESSES),
+        }
+    }
+
+    pub const fn counters(&self) -> &TelemetryI2cCounters {
+        &self.counters
     }
 
     pub fn into_inner(self) -> I2C {
@@ -65,31 +113,16 @@ where
         address: SevenBitAddress,
         operations: &mut [Operation<'_>],
     ) -> Result<(), Self::Error> {
-        #[cfg(feature = "net_http")]
-        if address != INA226_U13_ADDR_7BIT
-            && address != INA226_U13_FALLBACK_ADDR_7BIT
-            && address != INA226_U17_ADDR_7BIT
-            && address != INA226_U17_FALLBACK_ADDR_7BIT
-            && address != TMP112_ADDR_7BIT
-            && address != TPS55288_ADDR_7BIT
-            && address != WIFI_EEPROM_ADDR_7BIT
-        {
-            return Err(TelemetryI2cError::AddressNotAllowed(address));
-        }
-        #[cfg(not(feature = "net_http"))]
-        if address != INA226_U13_ADDR_7BIT
-            && address != INA226_U13_FALLBACK_ADDR_7BIT
-            && address != INA226_U17_ADDR_7BIT
-            && address != INA226_U17_FALLBACK_ADDR_7BIT
-            && address != TMP112_ADDR_7BIT
-            && address != TPS55288_ADDR_7BIT
-        {
+        if !TELEMETRY_I2C_ADDRESSES.contains(&address) {
+            self.counters.record_rejected();
             return Err(TelemetryI2cError::AddressNotAllowed(address));
         }
 
-        self.inner
-            .transaction(address, operations)
-            .await
-            .map_err(TelemetryI2cError::Bus)
+        let result = self.inner.transaction(address, operations).await;
+        self.counters.record(
+            address,
+            result.as_ref().map(|_| ()).map_err(classify_i2c_error),
+        );
+        result.map_err(TelemetryI2cError::Bus)
     }
 }
@@ -10,6 +10,9 @@ pub use hardware::{
     TELEMETRY_I2C1_SDA_GPIO, U13_R22_SHUNT_RESISTANCE_UOHMS, U17_I_MAX_MA,
     U17_R29_SHUNT_RESISTANCE_UOHMS,
 };
-pub use i2c_allowlist::{TelemetryI2cAllowlist, TelemetryI2cError};
+pub use i2c_allowlist::{
+    TELEMETRY_I2C_ADDRESSES, TelemetryI2cAllowlist, TelemetryI2cCounters, TelemetryI2cError,
+    telemetry_i2c_device,
+};
 pub use normal_ui::{NormalUiTelemetrySampler, NormalUiTelemetrySnapshot, PortMetrics};
 pub use sampler::TelemetrySampler;
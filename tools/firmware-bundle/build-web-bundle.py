@@ -335,6 +335,13 @@ def current_release_manifest_entry(
     )
 
 
+def release_notes(release: dict[str, Any]) -> str | None:
+    body = release.get("body")
+    if not isinstance(body, str) or not body.strip():
+        return None
+    return body.strip()
+
+
 def current_release_args_present(args: argparse.Namespace) -> bool:
     fields = [
         args.current_release_tag,
@@ -501,6 +508,7 @@ def main() -> int:
                 "version": app_artifact.get("version", tag_name),
                 "publishedAt": release["published_at"],
                 "prerelease": bool(release.get("prerelease")),
+                "notes": release_notes(release),
                 "catalogPath": f"firmware/{catalog_rel_path}",
                 "app": {
                     "artifactId": app_artifact["artifactId"],
@@ -0,0 +1,179 @@
+//! `isolapurr-devd` IPC transport: one JSON line per request over a Unix socket
+//! or a Windows named pipe.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use anyhow::{Context as _, anyhow};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::task::JoinHandle;
+
+use crate::{Call, jsonl_result};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IpcRequest {
+    pub id: String,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IpcResponse {
+    pub id: String,
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Sends one request to devd and returns its `result`. IPC-level failures
+/// (unknown device, expired lease) come back as plain errors.
+pub async fn ipc_call(endpoint: &str, method: &str, params: Value) -> anyhow::Result<Value> {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+    let request = IpcRequest {
+        id: format!(
+            "client-{}-{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ),
+        method: method.to_string(),
+        params,
+    };
+    #[cfg(unix)]
+    {
+        let stream = tokio::net::UnixStream::connect(endpoint)
+            .await
+            .with_context(|| format!("connect IPC socket {endpoint}"))?;
+        send_ipc_request(stream, request).await
+    }
+    #[cfg(windows)]
+    {
+        let stream = tokio::net::windows::named_pipe::ClientOptions::new()
+            .open(endpoint)
+            .with_context(|| format!("connect IPC pipe {endpoint}"))?;
+        send_ipc_request(stream, request).await
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = (endpoint, request);
+        Err(anyhow!("isolapurr IPC is unsupported on this platform"))
+    }
+}
+
+async fn send_ipc_request<S>(mut stream: S, request: IpcRequest) -> anyhow::Result<Value>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut encoded = serde_json::to_vec(&request)?;
+    encoded.push(b'\n');
+    stream.write_all(&encoded).await?;
+    stream.flush().await?;
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).await?;
+    let response: IpcResponse = serde_json::from_str(line.trim()).context("decode IPC response")?;
+    if response.ok {
+        Ok(response.result.unwrap_or_else(|| json!({})))
+    } else {
+        Err(anyhow!(
+            "{}",
+            response
+                .error
+                .unwrap_or_else(|| "IPC request failed".to_string())
+        ))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LeaseGrant {
+    lease_id: String,
+    heartbeat_interval_ms: u64,
+}
+
+pub(crate) struct DevdTransport {
+    endpoint: String,
+    device_id: String,
+    lease_id: String,
+    heartbeat: JoinHandle<()>,
+}
+
+impl DevdTransport {
+    pub(crate) async fn connect(endpoint: String, device_id: String) -> anyhow::Result<Self> {
+        let grant: LeaseGrant = serde_json::from_value(
+            ipc_call(
+                &endpoint,
+                "serial.lease.create",
+                json!({"device_id": device_id}),
+            )
+            .await
+            .with_context(|| format!("lease {device_id} from isolapurr-devd"))?,
+        )
+        .context("decode lease")?;
+        let heartbeat = tokio::spawn(heartbeat_lease(
+            endpoint.clone(),
+            grant.lease_id.clone(),
+            Duration::from_millis(grant.heartbeat_interval_ms.max(100)),
+        ));
+        Ok(Self {
+            endpoint,
+            device_id,
+            lease_id: grant.lease_id,
+            heartbeat,
+        })
+    }
+
+    pub(crate) fn lease_id(&self) -> &str {
+        &self.lease_id
+    }
+
+    pub(crate) async fn call(&self, call: Call) -> anyhow::Result<Value> {
+        let (method, mut params) = match call {
+            Call::Info => ("device.status", json!({})),
+            Call::Ports => ("device.ports.get", json!({})),
+            Call::PortPower { port, enabled } => (
+                "device.port.power",
+                json!({"port": port, "enabled": enabled}),
+            ),
+            Call::PowerConfig => ("device.power.config_get", json!({})),
+            Call::Identify => ("device.identify", json!({})),
+        };
+        params["device_id"] = json!(self.device_id);
+        // devd forwards the device's JSONL envelope unchanged.
+        jsonl_result(ipc_call(&self.endpoint, method, params).await?)
+    }
+
+    pub(crate) async fn release(self) -> anyhow::Result<()> {
+        self.heartbeat.abort();
+        ipc_call(
+            &self.endpoint,
+            "serial.lease.release",
+            json!({"lease_id": self.lease_id}),
+        )
+        .await
+        .map(drop)
+    }
+}
+
+impl Drop for DevdTransport {
+    // Without `release`, devd drops the lease once its TTL passes.
+    fn drop(&mut self) {
+        self.heartbeat.abort();
+    }
+}
+
+async fn heartbeat_lease(endpoint: String, lease_id: String, interval: Duration) {
+    loop {
+        tokio::time::sleep(interval).await;
+        // A missed beat is retried on the next tick; the TTL covers a few.
+        let _ = ipc_call(
+            &endpoint,
+            "serial.lease.heartbeat",
+            json!({"lease_id": lease_id}),
+        )
+        .await;
+    }
+}
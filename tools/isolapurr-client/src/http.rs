@@ -0,0 +1,70 @@
+//! LAN HTTP transport (`/api/v1/*`).
+
+use std::time::Duration;
+
+use anyhow::Context as _;
+use reqwest::{Client, Method, StatusCode, Url};
+use serde_json::Value;
+
+use crate::{Call, device_error};
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub(crate) struct HttpTransport {
+    client: Client,
+    base_url: Url,
+}
+
+impl HttpTransport {
+    pub(crate) fn new(base_url: &str) -> anyhow::Result<Self> {
+        let trimmed = base_url.trim().trim_end_matches('/');
+        let base_url = if trimmed.contains("://") {
+            format!("{trimmed}/")
+        } else {
+            format!("http://{trimmed}/")
+        };
+        Ok(Self {
+            client: Client::builder().timeout(HTTP_TIMEOUT).build()?,
+            base_url: Url::parse(&base_url)
+                .with_context(|| format!("invalid hub URL {base_url}"))?,
+        })
+    }
+
+    pub(crate) async fn call(&self, call: Call) -> anyhow::Result<Value> {
+        let (method, path) = route(call);
+        let url = self.base_url.join(&path)?;
+        let response = self
+            .client
+            .request(method, url.clone())
+            .send()
+            .await
+            .with_context(|| format!("request {url}"))?;
+        let status = response.status();
+        let body = if status == StatusCode::NO_CONTENT {
+            Value::Null
+        } else {
+            response
+                .json::<Value>()
+                .await
+                .with_context(|| format!("decode {url}"))?
+        };
+        if status.is_success() {
+            Ok(body)
+        } else {
+            Err(device_error(&body).context(format!("{url} returned {status}")))
+        }
+    }
+}
+
+fn route(call: Call) -> (Method, String) {
+    match call {
+        Call::Info => (Method::GET, "api/v1/info".to_string()),
+        Call::Ports => (Method::GET, "api/v1/ports".to_string()),
+        Call::PortPower { port, enabled } => (
+            Method::POST,
+            format!("api/v1/ports/{}/power?enabled={enabled}", port.as_str()),
+        ),
+        Call::PowerConfig => (Method::GET, "api/v1/power/config".to_string()),
+        Call::Identify => (Method::POST, "api/v1/identify".to_string()),
+    }
+}
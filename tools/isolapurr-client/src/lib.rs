@@ -0,0 +1,272 @@
+//! Typed async client for IsolaPurr hubs.
+//!
+//! A [`Hub`] talks to one hub over the LAN HTTP API, the JSONL console (on a USB
+//! serial port or TCP port 7070), or a device owned by a local `isolapurr-devd`. Every transport
+//! returns the same `isolapurr-api` types, and failures reported by the firmware
+//! surface as [`DeviceError`] with its `error.code`.
+//!
+//! ```no_run
+//! # async fn demo() -> anyhow::Result<()> {
+//! use isolapurr_client::{Hub, Target};
+//! use isolapurr_client::api::ports::PortId;
+//!
+//! let hub = Hub::connect(Target::http("isolapurr-a1b2c3.local")).await?;
+//! hub.set_port_power(PortId::PortA, false).await?;
+//! println!("{:?}", hub.ports().await?.ports[0].state);
+//! hub.close().await
+//! # }
+//! ```
+
+pub mod devd;
+mod http;
+pub mod power;
+pub mod serial;
+pub mod tcp;
+mod telemetry;
+
+use std::{fmt, time::Duration};
+
+use anyhow::{Context as _, anyhow};
+use isolapurr_api::device::Capabilities;
+use isolapurr_api::methods;
+use isolapurr_api::ports::{Port, PortId, PortsResponse};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+pub use isolapurr_api as api;
+pub use power::PowerConfig;
+pub use telemetry::TelemetryStream;
+
+/// Where a [`Hub`] is reached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    /// LAN HTTP API. A bare host name is treated as `http://<host>`.
+    Http { base_url: String },
+    /// JSONL console on a serial port such as `/dev/ttyACM0` or `COM5`.
+    Serial { port_path: String },
+    /// JSONL console on TCP port 7070 of a Wi-Fi hub. `addr` is a host name or
+    /// IP address with an optional `:port`; `token` is sent with `auth` first.
+    Tcp { addr: String, token: Option<String> },
+    /// A device registered with `isolapurr-devd`, reached over its IPC endpoint.
+    Devd { endpoint: String, device_id: String },
+}
+
+impl Target {
+    pub fn http(base_url: impl Into<String>) -> Self {
+        Self::Http {
+            base_url: base_url.into(),
+        }
+    }
+
+    pub fn serial(port_path: impl Into<String>) -> Self {
+        Self::Serial {
+            port_path: port_path.into(),
+        }
+    }
+
+    pub fn tcp(addr: impl Into<String>, token: Option<String>) -> Self {
+        Self::Tcp {
+            addr: addr.into(),
+            token,
+        }
+    }
+
+    pub fn devd(endpoint: impl Into<String>, device_id: impl Into<String>) -> Self {
+        Self::Devd {
+            endpoint: endpoint.into(),
+            device_id: device_id.into(),
+        }
+    }
+}
+
+/// An `error` object returned by the firmware, e.g. `busy` while a port replugs.
+///
+/// Hub methods return it inside `anyhow::Error`; use `downcast_ref` to branch on
+/// `code`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct DeviceError {
+    pub code: String,
+    pub message: String,
+    #[serde(default)]
+    pub retryable: bool,
+}
+
+impl fmt::Display for DeviceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for DeviceError {}
+
+/// Result of `info` / `GET /api/v1/info`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct Info {
+    #[serde(default)]
+    pub device: DeviceInfo,
+    #[serde(default)]
+    pub capabilities: Capabilities,
+}
+
+/// The `device` object of [`Info`]. Identity fields are missing on USB before
+/// the device names are ready.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct DeviceInfo {
+    pub device_id: Option<String>,
+    pub hostname: Option<String>,
+    pub mac: Option<String>,
+    pub firmware: FirmwareInfo,
+    pub uptime_ms: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct FirmwareInfo {
+    pub name: String,
+    pub version: String,
+}
+
+/// Handle to one hub. Cheap calls share the handle; drop it or call
+/// [`Hub::close`] when done.
+pub struct Hub {
+    transport: Transport,
+}
+
+enum Transport {
+    Http(http::HttpTransport),
+    Serial(serial::SerialTransport),
+    Tcp(tcp::TcpTransport),
+    Devd(devd::DevdTransport),
+}
+
+/// One hub operation. Each transport maps it to an HTTP route, a JSONL method
+/// or a devd IPC method.
+#[derive(Debug, Clone, Copy)]
+enum Call {
+    Info,
+    Ports,
+    PortPower { port: PortId, enabled: bool },
+    PowerConfig,
+    Identify,
+}
+
+impl Call {
+    fn jsonl(self) -> (&'static str, Value) {
+        match self {
+            Self::Info => (methods::INFO, json!({})),
+            Self::Ports => (methods::PORTS_GET, json!({})),
+            Self::PortPower { port, enabled } => (
+                methods::PORT_POWER_SET,
+                json!({"port": port, "enabled": enabled}),
+            ),
+            Self::PowerConfig => (methods::POWER_CONFIG_GET, json!({})),
+            Self::Identify => (methods::IDENTIFY, json!({})),
+        }
+    }
+}
+
+impl Hub {
+    /// Opens a handle. For devd targets this also takes a serial lease that is
+    /// kept alive in the background until the handle is closed or dropped.
+    pub async fn connect(target: Target) -> anyhow::Result<Self> {
+        let transport = match target {
+            Target::Http { base_url } => Transport::Http(http::HttpTransport::new(&base_url)?),
+            Target::Serial { port_path } => {
+                Transport::Serial(serial::SerialTransport::new(port_path))
+            }
+            Target::Tcp { addr, token } => Transport::Tcp(tcp::TcpTransport::new(addr, token)),
+            Target::Devd {
+                endpoint,
+                device_id,
+            } => Transport::Devd(devd::DevdTransport::connect(endpoint, device_id).await?),
+        };
+        Ok(Self { transport })
+    }
+
+    /// The devd lease held by this handle, if any.
+    pub fn lease_id(&self) -> Option<&str> {
+        match &self.transport {
+            Transport::Devd(devd) => Some(devd.lease_id()),
+            Transport::Http(_) | Transport::Serial(_) | Transport::Tcp(_) => None,
+        }
+    }
+
+    pub async fn info(&self) -> anyhow::Result<Info> {
+        self.call(Call::Info).await
+    }
+
+    pub async fn ports(&self) -> anyhow::Result<PortsResponse> {
+        self.call(Call::Ports).await
+    }
+
+    pub async fn port(&self, port: PortId) -> anyhow::Result<Port> {
+        self.ports()
+            .await?
+            .ports
+            .into_iter()
+            .find(|candidate| candidate.port_id == port)
+            .ok_or_else(|| anyhow!("hub did not report {}", port.as_str()))
+    }
+
+    pub async fn set_port_power(&self, port: PortId, enabled: bool) -> anyhow::Result<()> {
+        self.call::<Value>(Call::PortPower { port, enabled })
+            .await
+            .map(drop)
+    }
+
+    pub async fn power_config(&self) -> anyhow::Result<PowerConfig> {
+        self.call(Call::PowerConfig).await
+    }
+
+    /// Shows the identify frame for five seconds. Refused unless the firmware
+    /// advertises `capabilities.identify`.
+    pub async fn identify(&self) -> anyhow::Result<()> {
+        if !self.info().await?.capabilities.identify {
+            return Err(anyhow!(
+                "device does not advertise capabilities.identify=true; refusing identify"
+            ));
+        }
+        self.call::<Value>(Call::Identify).await.map(drop)
+    }
+
+    /// Polls `ports` every `interval`. The first sample is taken immediately.
+    pub fn stream_telemetry(&self, interval: Duration) -> TelemetryStream<'_> {
+        TelemetryStream::new(self, interval)
+    }
+
+    /// Releases the devd lease, if any.
+    pub async fn close(self) -> anyhow::Result<()> {
+        match self.transport {
+            Transport::Devd(devd) => devd.release().await,
+            Transport::Http(_) | Transport::Serial(_) | Transport::Tcp(_) => Ok(()),
+        }
+    }
+
+    async fn call<T: DeserializeOwned>(&self, call: Call) -> anyhow::Result<T> {
+        let value = match &self.transport {
+            Transport::Http(http) => http.call(call).await?,
+            Transport::Serial(serial) => serial.call(call).await?,
+            Transport::Tcp(tcp) => tcp.call(call).await?,
+            Transport::Devd(devd) => devd.call(call).await?,
+        };
+        serde_json::from_value(value).with_context(|| format!("decode {call:?} response"))
+    }
+}
+
+/// Unwraps a JSONL response envelope into its `result`.
+fn jsonl_result(response: Value) -> anyhow::Result<Value> {
+    match response.get("ok").and_then(Value::as_bool) {
+        Some(true) => Ok(response.get("result").cloned().unwrap_or_else(|| json!({}))),
+        Some(false) => Err(device_error(&response)),
+        None => Err(anyhow!("JSONL response has no `ok` field: {response}")),
+    }
+}
+
+fn device_error(body: &Value) -> anyhow::Error {
+    match body.get("error").map(DeviceError::deserialize) {
+        Some(Ok(error)) => error.into(),
+        _ => anyhow!("request failed: {body}"),
+    }
+}
@@ -0,0 +1,128 @@
+//! `power.config_get` / `GET /api/v1/power/config` result.
+//!
+//! Enum-valued fields stay strings so a newer firmware value still decodes;
+//! compare them with the `isolapurr_api::power` enums' `as_str`.
+
+use isolapurr_api::power::{LightLoadMode, Sw2303LineCompensation};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PowerConfig {
+    pub hardware: String,
+    pub persisted: bool,
+    pub tps_mode: String,
+    #[serde(default = "default_light_load_mode")]
+    pub light_load_mode: String,
+    #[serde(default = "default_sw2303_line_compensation")]
+    pub sw2303_line_compensation: String,
+    #[serde(default)]
+    pub runtime: PowerRuntime,
+    pub capability: PowerCapability,
+    pub manual: PowerManual,
+    pub lock: Option<PowerLock>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PowerRuntime {
+    #[serde(default = "default_runtime_output_enabled")]
+    pub output_enabled: bool,
+    #[serde(default)]
+    pub discharge_enabled: bool,
+}
+
+impl Default for PowerRuntime {
+    fn default() -> Self {
+        Self {
+            output_enabled: default_runtime_output_enabled(),
+            discharge_enabled: false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PowerCapability {
+    pub profile: String,
+    pub power_watts: u8,
+    /// Protocol name to enabled flag, e.g. `{"pd": true, "qc20": false}`.
+    pub protocols: Value,
+    pub pd: PowerPd,
+    #[serde(default)]
+    pub current: PowerCurrentProfile,
+    #[serde(default)]
+    pub fast_charge: PowerFastChargeProfile,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PowerPd {
+    pub pps: bool,
+    pub fixed_voltages_mv: Vec<u16>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PowerCurrentProfile {
+    pub pps3_limit_ma: u16,
+    pub pd_pps_5a: bool,
+    pub type_c_broadcast_ma: u16,
+    pub scp_limit_ma: u16,
+    pub fcp_afc_sfcp_limit_ma: u16,
+}
+
+impl Default for PowerCurrentProfile {
+    fn default() -> Self {
+        Self {
+            pps3_limit_ma: 5000,
+            pd_pps_5a: false,
+            type_c_broadcast_ma: 500,
+            scp_limit_ma: 5000,
+            fcp_afc_sfcp_limit_ma: 3250,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PowerFastChargeProfile {
+    pub qc20_20v_enabled: bool,
+    pub qc30_20v_enabled: bool,
+    pub pe20_20v_enabled: bool,
+    pub non_pd_12v_enabled: bool,
+}
+
+impl Default for PowerFastChargeProfile {
+    fn default() -> Self {
+        Self {
+            qc20_20v_enabled: true,
+            qc30_20v_enabled: true,
+            pe20_20v_enabled: true,
+            non_pd_12v_enabled: true,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PowerManual {
+    pub voltage_mv: u16,
+    pub current_limit_ma: u16,
+    pub usb_c_path_mode: String,
+    #[serde(default)]
+    pub tps_cdc_rise_mv: u16,
+    pub path_policy: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PowerLock {
+    pub owner: u32,
+    pub expires_at_ms: u64,
+}
+
+fn default_light_load_mode() -> String {
+    LightLoadMode::Pfm.as_str().to_string()
+}
+
+fn default_sw2303_line_compensation() -> String {
+    Sw2303LineCompensation::MilliOhm50.as_str().to_string()
+}
+
+fn default_runtime_output_enabled() -> bool {
+    true
+}
@@ -0,0 +1,108 @@
+//! JSONL console transport over a USB serial port.
+
+use std::io::{Read as _, Write as _};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context as _, anyhow};
+use isolapurr_api::methods;
+use serde_json::{Value, json};
+use tokio::sync::Mutex;
+
+use crate::{Call, jsonl_result};
+
+pub const SERIAL_BAUD: u32 = 115_200;
+const DEFAULT_TIMEOUT: Duration = Duration::from_millis(1_500);
+// The firmware waits up to 1 s for the identify frame to render before replying.
+const IDENTIFY_TIMEOUT: Duration = Duration::from_millis(3_000);
+
+/// Writes one JSONL request to `port_path` and returns the first response line
+/// with the same `id`, skipping log lines and replies to other requests.
+///
+/// Blocking; run it on a blocking thread from async code.
+pub fn jsonl_roundtrip(
+    port_path: &str,
+    request: &Value,
+    timeout: Duration,
+) -> anyhow::Result<Value> {
+    let mut port = serialport::new(port_path, SERIAL_BAUD)
+        .timeout(Duration::from_millis(50))
+        .open()
+        .with_context(|| format!("open serial port {port_path}"))?;
+    let mut line = serde_json::to_string(request)?;
+    line.push('\n');
+    port.write_all(line.as_bytes()).context("serial write")?;
+    port.flush().context("serial flush")?;
+
+    let expected_id = request.get("id");
+    let deadline = Instant::now() + timeout;
+    let mut raw = Vec::<u8>::new();
+    let mut buf = [0_u8; 256];
+    while Instant::now() < deadline {
+        match port.read(&mut buf) {
+            Ok(0) => {}
+            Ok(n) => {
+                raw.extend_from_slice(&buf[..n]);
+                while let Some(pos) = raw.iter().position(|byte| *byte == b'\n') {
+                    let frame = raw.drain(..=pos).collect::<Vec<_>>();
+                    let text = String::from_utf8_lossy(&frame);
+                    let trimmed = text.trim();
+                    if trimmed.is_empty() {
+                        continue;
+                    }
+                    let Ok(value) = serde_json::from_str::<Value>(trimmed) else {
+                        continue;
+                    };
+                    if expected_id.is_none() || value.get("id") == expected_id {
+                        return Ok(value);
+                    }
+                }
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::TimedOut => {}
+            Err(err) => return Err(err).context("serial read"),
+        }
+    }
+    Err(anyhow!("serial response timed out"))
+}
+
+pub(crate) fn call_timeout(method: &str) -> Duration {
+    if method == methods::IDENTIFY {
+        IDENTIFY_TIMEOUT
+    } else {
+        DEFAULT_TIMEOUT
+    }
+}
+
+pub(crate) struct SerialTransport {
+    port_path: String,
+    next_id: AtomicU64,
+    // One request at a time: a second open of the port would steal the reply.
+    busy: Mutex<()>,
+}
+
+impl SerialTransport {
+    pub(crate) fn new(port_path: String) -> Self {
+        Self {
+            port_path,
+            next_id: AtomicU64::new(1),
+            busy: Mutex::new(()),
+        }
+    }
+
+    pub(crate) async fn call(&self, call: Call) -> anyhow::Result<Value> {
+        let (method, params) = call.jsonl();
+        let timeout = call_timeout(method);
+        let request = json!({
+            "id": self.next_id.fetch_add(1, Ordering::Relaxed),
+            "method": method,
+            "params": params,
+        });
+        let _busy = self.busy.lock().await;
+        let port_path = self.port_path.clone();
+        let response =
+            tokio::task::spawn_blocking(move || jsonl_roundtrip(&port_path, &request, timeout))
+                .await
+                .context("serial worker join")??;
+        jsonl_result(response)
+    }
+}
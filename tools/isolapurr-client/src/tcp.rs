@@ -0,0 +1,123 @@
+//! JSONL console transport over TCP (port 7070 on Wi-Fi hubs).
+
+use std::net::Ipv6Addr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use anyhow::{Context as _, anyhow};
+use isolapurr_api::methods;
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufReadExt as _, AsyncWriteExt as _, BufReader};
+use tokio::net::TcpStream;
+
+use crate::{Call, device_error, jsonl_result, serial};
+
+pub const JSONL_TCP_PORT: u16 = 7070;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+// Never sent as a request id, so the auth reply cannot match a request.
+const AUTH_ID: u64 = 0;
+
+/// `host:port` for `addr`; a bare host name or IP address gets port 7070.
+pub fn socket_addr(addr: &str) -> String {
+    let addr = addr.trim();
+    if addr.parse::<Ipv6Addr>().is_ok() {
+        return format!("[{addr}]:{JSONL_TCP_PORT}");
+    }
+    let has_port = addr
+        .rsplit_once(':')
+        .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok());
+    if has_port {
+        addr.to_string()
+    } else {
+        format!("{addr}:{JSONL_TCP_PORT}")
+    }
+}
+
+/// Opens a connection to `addr`, sends `auth` first when a `token` is given,
+/// then writes `request` and returns the response line with the same `id`.
+///
+/// A refused `auth` surfaces as a [`crate::DeviceError`] (`unauthorized`).
+pub async fn jsonl_roundtrip(
+    addr: &str,
+    token: Option<&str>,
+    request: &Value,
+    timeout: Duration,
+) -> anyhow::Result<Value> {
+    let addr = socket_addr(addr);
+    let stream = tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect(&addr))
+        .await
+        .map_err(|_| anyhow!("connect {addr} timed out"))?
+        .with_context(|| format!("connect {addr}"))?;
+    let mut stream = BufReader::new(stream);
+    tokio::time::timeout(timeout, async {
+        if let Some(token) = token {
+            let auth = json!({"id": AUTH_ID, "method": methods::AUTH, "token": token});
+            let response = exchange(&mut stream, &auth).await?;
+            if response.get("ok").and_then(Value::as_bool) != Some(true) {
+                return Err(device_error(&response).context(format!("{addr} refused auth")));
+            }
+        }
+        exchange(&mut stream, request).await
+    })
+    .await
+    .map_err(|_| anyhow!("{addr} response timed out"))?
+}
+
+async fn exchange(stream: &mut BufReader<TcpStream>, request: &Value) -> anyhow::Result<Value> {
+    let mut line = serde_json::to_string(request)?;
+    line.push('\n');
+    stream
+        .get_mut()
+        .write_all(line.as_bytes())
+        .await
+        .context("tcp write")?;
+    let expected_id = request.get("id");
+    let mut frame = String::new();
+    loop {
+        frame.clear();
+        if stream.read_line(&mut frame).await.context("tcp read")? == 0 {
+            return Err(anyhow!("hub closed the JSONL connection"));
+        }
+        let Ok(value) = serde_json::from_str::<Value>(frame.trim()) else {
+            continue;
+        };
+        if expected_id.is_none() || value.get("id") == expected_id {
+            return Ok(value);
+        }
+    }
+}
+
+pub(crate) struct TcpTransport {
+    addr: String,
+    token: Option<String>,
+    next_id: AtomicU64,
+}
+
+impl TcpTransport {
+    pub(crate) fn new(addr: String, token: Option<String>) -> Self {
+        Self {
+            addr,
+            token,
+            next_id: AtomicU64::new(AUTH_ID + 1),
+        }
+    }
+
+    /// One connection per call, like the serial transport: the firmware serves
+    /// two clients and drops idle ones, so nothing is kept open between calls.
+    pub(crate) async fn call(&self, call: Call) -> anyhow::Result<Value> {
+        let (method, params) = call.jsonl();
+        let request = json!({
+            "id": self.next_id.fetch_add(1, Ordering::Relaxed),
+            "method": method,
+            "params": params,
+        });
+        let response = jsonl_roundtrip(
+            &self.addr,
+            self.token.as_deref(),
+            &request,
+            serial::call_timeout(method),
+        )
+        .await?;
+        jsonl_result(response)
+    }
+}
@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+use isolapurr_api::ports::PortsResponse;
+use tokio::time::{Interval, MissedTickBehavior};
+
+use crate::Hub;
+
+/// Periodic `ports` samples from [`Hub::stream_telemetry`].
+///
+/// ```no_run
+/// # async fn demo(hub: &isolapurr_client::Hub) -> anyhow::Result<()> {
+/// let mut telemetry = hub.stream_telemetry(std::time::Duration::from_millis(500));
+/// loop {
+///     let sample = telemetry.next().await?;
+///     println!("{:?}", sample.ports[1].telemetry.power_mw);
+/// }
+/// # }
+/// ```
+pub struct TelemetryStream<'a> {
+    hub: &'a Hub,
+    interval: Interval,
+}
+
+impl<'a> TelemetryStream<'a> {
+    pub(crate) fn new(hub: &'a Hub, period: Duration) -> Self {
+        let mut interval = tokio::time::interval(period);
+        // A slow transport should not cause a burst of catch-up polls.
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        Self { hub, interval }
+    }
+
+    /// Waits for the next tick and polls the hub. After a failed poll the
+    /// stream can keep going.
+    pub async fn next(&mut self) -> anyhow::Result<PortsResponse> {
+        self.interval.tick().await;
+        self.hub.ports().await
+    }
+}
@@ -0,0 +1,258 @@
+//! Drives `Hub` against in-process fakes of the firmware HTTP API, the TCP JSONL
+//! console and devd IPC.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use isolapurr_client::api::device::Capabilities;
+use isolapurr_client::api::ports::{
+    HubStatus, Port, PortCapabilities, PortId, PortSnapshot, PortsResponse,
+};
+use isolapurr_client::{DeviceError, Hub, Target};
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+fn sample_ports() -> PortsResponse {
+    PortsResponse {
+        hub: HubStatus::unknown(),
+        capabilities: Capabilities::default(),
+        ports: [PortId::PortA, PortId::PortC].map(|port_id| {
+            Port::new(
+                port_id,
+                &PortSnapshot::unknown(),
+                PortCapabilities {
+                    data_replug: true,
+                    power_set: true,
+                },
+            )
+        }),
+    }
+}
+
+/// Serves canned HTTP responses keyed by request line and records every
+/// request line it sees.
+async fn fake_http_hub(
+    routes: Vec<(&'static str, &'static str, Value)>,
+) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_by_server = seen.clone();
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0_u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]).to_string();
+            let request_line = request.lines().next().unwrap_or_default();
+            let request_line = request_line.trim_end_matches(" HTTP/1.1").to_string();
+            seen_by_server.lock().unwrap().push(request_line.clone());
+            let (status, body) = routes
+                .iter()
+                .find(|(line, _, _)| *line == request_line)
+                .map(|(_, status, body)| (*status, body.to_string()))
+                .unwrap_or(("404 Not Found", "{}".to_string()));
+            let response = format!(
+                "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+    (base_url, seen)
+}
+
+#[tokio::test]
+async fn http_hub_decodes_ports_and_surfaces_device_errors() {
+    let (base_url, seen) = fake_http_hub(vec![
+        (
+            "GET /api/v1/ports",
+            "200 OK",
+            serde_json::to_value(sample_ports()).unwrap(),
+        ),
+        (
+            "POST /api/v1/ports/port_c/power?enabled=false",
+            "409 Conflict",
+            json!({"error": {"code": "busy", "message": "port is busy", "retryable": true}}),
+        ),
+    ])
+    .await;
+    let hub = Hub::connect(Target::http(base_url)).await.unwrap();
+
+    assert_eq!(hub.ports().await.unwrap(), sample_ports());
+    assert_eq!(
+        hub.port(PortId::PortC).await.unwrap().port_id,
+        PortId::PortC
+    );
+
+    let err = hub.set_port_power(PortId::PortC, false).await.unwrap_err();
+    let device_error = err
+        .downcast_ref::<DeviceError>()
+        .expect("typed device error");
+    assert_eq!(device_error.code, "busy");
+    assert!(device_error.retryable);
+
+    let mut telemetry = hub.stream_telemetry(Duration::from_millis(10));
+    telemetry.next().await.unwrap();
+    telemetry.next().await.unwrap();
+    assert_eq!(
+        seen.lock()
+            .unwrap()
+            .iter()
+            .filter(|line| *line == "GET /api/v1/ports")
+            .count(),
+        4
+    );
+}
+
+#[tokio::test]
+async fn identify_requires_the_capability_flag() {
+    let (base_url, seen) = fake_http_hub(vec![(
+        "GET /api/v1/info",
+        "200 OK",
+        json!({"device": {"firmware": {"name": "isolapurr-usb-hub", "version": "0.1.0"}}, "capabilities": {"identify": false}}),
+    )])
+    .await;
+    let hub = Hub::connect(Target::http(base_url)).await.unwrap();
+
+    let err = hub.identify().await.unwrap_err();
+    assert!(err.to_string().contains("capabilities.identify"));
+    assert!(
+        !seen
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|line| line.contains("identify"))
+    );
+}
+
+/// Serves a token-gated JSONL console that answers `ports.get` once the
+/// connection has sent `auth` with `token`.
+async fn fake_jsonl_tcp_hub(token: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            tokio::spawn(async move {
+                let (read, mut write) = tokio::io::split(stream);
+                let mut lines = BufReader::new(read).lines();
+                let mut authenticated = false;
+                while let Some(line) = lines.next_line().await.unwrap() {
+                    let request: Value = serde_json::from_str(&line).unwrap();
+                    let response = match request["method"].as_str().unwrap() {
+                        "auth" if request["token"] == token => {
+                            authenticated = true;
+                            json!({"ok": true, "result": {"authenticated": true, "token_required": true}})
+                        }
+                        _ if !authenticated => json!({
+                            "ok": false,
+                            "error": {"code": "unauthorized", "message": "send auth first", "retryable": false}
+                        }),
+                        "ports.get" => json!({"ok": true, "result": sample_ports()}),
+                        _ => json!({"ok": true, "result": {}}),
+                    };
+                    let mut response = response;
+                    response["id"] = request["id"].clone();
+                    let mut encoded = serde_json::to_vec(&response).unwrap();
+                    encoded.push(b'\n');
+                    write.write_all(&encoded).await.unwrap();
+                }
+            });
+        }
+    });
+    addr
+}
+
+#[tokio::test]
+async fn tcp_hub_authenticates_before_each_request() {
+    let addr = fake_jsonl_tcp_hub("0123456789abcdef").await;
+
+    let hub = Hub::connect(Target::tcp(&addr, Some("0123456789abcdef".to_string())))
+        .await
+        .unwrap();
+    assert_eq!(hub.lease_id(), None);
+    assert_eq!(hub.ports().await.unwrap(), sample_ports());
+    assert_eq!(hub.ports().await.unwrap(), sample_ports());
+
+    for token in [None, Some("wrong-token-0000".to_string())] {
+        let hub = Hub::connect(Target::tcp(&addr, token)).await.unwrap();
+        let err = hub.ports().await.unwrap_err();
+        let device_error = err
+            .downcast_ref::<DeviceError>()
+            .expect("typed device error");
+        assert_eq!(device_error.code, "unauthorized");
+    }
+}
+
+#[test]
+fn tcp_addresses_default_to_the_console_port() {
+    use isolapurr_client::tcp::socket_addr;
+
+    assert_eq!(socket_addr("hub.local"), "hub.local:7070");
+    assert_eq!(socket_addr("192.168.1.20:7071"), "192.168.1.20:7071");
+    assert_eq!(socket_addr("fe80::1"), "[fe80::1]:7070");
+    assert_eq!(socket_addr("[fe80::1]:7070"), "[fe80::1]:7070");
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn devd_hub_holds_a_lease_and_unwraps_jsonl_envelopes() {
+    use tokio::net::UnixListener;
+
+    let dir = std::env::temp_dir().join(format!("isolapurr-client-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let endpoint = dir.join("devd.sock");
+    let _ = std::fs::remove_file(&endpoint);
+    let listener = UnixListener::bind(&endpoint).unwrap();
+    let calls = Arc::new(Mutex::new(Vec::<Value>::new()));
+    let calls_by_server = calls.clone();
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (read, mut write) = tokio::io::split(stream);
+            let mut line = String::new();
+            BufReader::new(read).read_line(&mut line).await.unwrap();
+            let request: Value = serde_json::from_str(&line).unwrap();
+            calls_by_server.lock().unwrap().push(request.clone());
+            let result = match request["method"].as_str().unwrap() {
+                "serial.lease.create" => json!({
+                    "lease_id": "lease-1",
+                    "device_id": "hub-1",
+                    "heartbeat_interval_ms": 100,
+                    "lease_ttl_ms": 400,
+                }),
+                "device.ports.get" => json!({"id": "x", "ok": true, "result": sample_ports()}),
+                _ => json!({"ok": true}),
+            };
+            let response = json!({"id": request["id"], "ok": true, "result": result});
+            let mut encoded = serde_json::to_vec(&response).unwrap();
+            encoded.push(b'\n');
+            write.write_all(&encoded).await.unwrap();
+        }
+    });
+
+    let hub = Hub::connect(Target::devd(endpoint.to_str().unwrap(), "hub-1"))
+        .await
+        .unwrap();
+    assert_eq!(hub.lease_id(), Some("lease-1"));
+    assert_eq!(hub.ports().await.unwrap(), sample_ports());
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    hub.close().await.unwrap();
+
+    let calls = calls.lock().unwrap();
+    let methods = calls
+        .iter()
+        .map(|call| call["method"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(methods.first(), Some(&"serial.lease.create"));
+    assert_eq!(methods.last(), Some(&"serial.lease.release"));
+    assert!(methods.contains(&"serial.lease.heartbeat"));
+    let ports_call = calls
+        .iter()
+        .find(|call| call["method"] == "device.ports.get")
+        .unwrap();
+    assert_eq!(ports_call["params"]["device_id"], "hub-1");
+    let _ = std::fs::remove_dir_all(dir);
+}
@@ -1,7 +1,7 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use isolapurr_host::{
     DEFAULT_BIND, DEFAULT_IPC_IDLE_TIMEOUT_SECS, DevdConfig, IpcConfig, default_ipc_endpoint,
-    serve_http_bridge, serve_ipc,
+    default_replay_ipc_endpoint, default_trace_dir, serve_http_bridge, serve_ipc, serve_replay_ipc,
 };
 use std::{net::SocketAddr, path::PathBuf, time::Duration};
 
@@ -23,6 +23,8 @@ enum Command {
         endpoint: String,
         #[arg(long, default_value_t = DEFAULT_IPC_IDLE_TIMEOUT_SECS)]
         idle_timeout_secs: u64,
+        #[command(flatten)]
+        trace: TraceArgs,
     },
     BridgeHttp {
         #[arg(long, default_value = DEFAULT_BIND)]
@@ -31,7 +33,42 @@ enum Command {
         web_root: Option<PathBuf>,
         #[arg(long)]
         allow_dev_cors: bool,
+        #[command(flatten)]
+        trace: TraceArgs,
     },
+    #[command(about = "Serve recorded trace responses over IPC as fake devices")]
+    Replay {
+        #[arg(required = true, help = "Trace files from `isolapurr trace export`")]
+        traces: Vec<PathBuf>,
+        #[arg(long, default_value_t = default_replay_ipc_endpoint())]
+        endpoint: String,
+        #[arg(long, default_value_t = 0, help = "0 keeps serving until interrupted")]
+        idle_timeout_secs: u64,
+    },
+}
+
+#[derive(Debug, Args)]
+struct TraceArgs {
+    #[arg(
+        long,
+        help = "Directory for rotating device trace files [default: user data dir]"
+    )]
+    trace_dir: Option<PathBuf>,
+    #[arg(long, conflicts_with = "trace_dir")]
+    no_trace: bool,
+}
+
+impl TraceArgs {
+    fn resolve(self) -> Option<PathBuf> {
+        if self.no_trace {
+            return None;
+        }
+        self.trace_dir.or_else(|| default_trace_dir().ok())
+    }
+}
+
+fn idle_timeout(secs: u64) -> Option<Duration> {
+    (secs > 0).then(|| Duration::from_secs(secs))
 }
 
 #[tokio::main]
@@ -45,16 +82,40 @@ async fn main() -> anyhow::Result<()> {
         Command::Serve {
             endpoint,
             idle_timeout_secs,
+            trace,
         } => {
-            let idle_timeout =
-                (idle_timeout_secs > 0).then(|| Duration::from_secs(idle_timeout_secs));
-            serve_ipc(IpcConfig::new(endpoint).with_idle_timeout(idle_timeout)).await?
+            serve_ipc(
+                IpcConfig::new(endpoint)
+                    .with_idle_timeout(idle_timeout(idle_timeout_secs))
+                    .with_trace_dir(trace.resolve()),
+            )
+            .await?
         }
         Command::BridgeHttp {
             bind,
             web_root,
             allow_dev_cors,
-        } => serve_http_bridge(DevdConfig::new(bind, web_root, allow_dev_cors)).await?,
+            trace,
+        } => {
+            serve_http_bridge(
+                DevdConfig::new(bind, web_root, allow_dev_cors).with_trace_dir(trace.resolve()),
+            )
+            .await?
+        }
+        Command::Replay {
+            traces,
+            endpoint,
+            idle_timeout_secs,
+        } => {
+            eprintln!(
+                "replaying on {endpoint}; point the CLI at it with `isolapurr --ipc {endpoint} --no-auto-start ...`"
+            );
+            serve_replay_ipc(
+                IpcConfig::new(endpoint).with_idle_timeout(idle_timeout(idle_timeout_secs)),
+                &traces,
+            )
+            .await?
+        }
     }
     Ok(())
 }
@@ -2,10 +2,24 @@ use anyhow::{Context as _, anyhow};
 use clap::{ArgAction, Parser, Subcommand, ValueEnum};
 use crossterm::terminal;
 use dialoguer::{MultiSelect, Select};
+use isolapurr_api::diagnostics::LogLevel;
+use isolapurr_api::ports::{
+    ChargeTerminationAction, Port, PortDataMode, PortId, PortPowerPolicy, PortTelemetry,
+    TelemetryStatus,
+};
+use isolapurr_api::power::{
+    ActiveProtocol, FastChargeProbeError, FastChargeProbeResult, LightLoadMode, ManualUsbCPathMode,
+    Sw2303LineCompensation, TpsMode,
+};
+use isolapurr_api::sessions::ChargeSession;
+use isolapurr_client::power::{
+    PowerCapability, PowerConfig, PowerCurrentProfile, PowerFastChargeProfile, PowerManual, PowerPd,
+};
 use isolapurr_host::{
     DeviceIdentity, DeviceProfile, DeviceProfileTransports, DeviceRecord, FirmwareCatalog,
-    SavedHardwareInput, api_url, default_ipc_endpoint, ipc_call, read_hardware_registry,
-    redact_sensitive, registry_path, save_hardware, validate_identify_capability,
+    SavedHardwareInput, TraceRecord, api_url, compare_firmware_versions, default_ipc_endpoint,
+    default_trace_dir, ipc_call, read_hardware_registry, read_trace_records, redact_sensitive,
+    registry_path, save_hardware, trace_files, validate_identify_capability,
 };
 use mdns_sd::{ServiceDaemon, ServiceEvent};
 use ratatui::{
@@ -33,6 +47,24 @@ include!("isolapurr/format.rs");
 include!("isolapurr/power_support.rs");
 include!("isolapurr/source_capability_tui.rs");
 include!("isolapurr/power_runtime.rs");
+include!("isolapurr/sound.rs");
+include!("isolapurr/display.rs");
+include!("isolapurr/display_screenshot.rs");
+include!("isolapurr/buttons.rs");
+include!("isolapurr/diagnostics_i2c.rs");
+include!("isolapurr/diagnostics_reg.rs");
+include!("isolapurr/diagnostics_crash.rs");
 include!("isolapurr/platform.rs");
+include!("isolapurr/charge_termination.rs");
 include!("isolapurr/discover.rs");
+include!("isolapurr/watch.rs");
+include!("isolapurr/test_plan.rs");
+include!("isolapurr/test_runner.rs");
+include!("isolapurr/log_format.rs");
+include!("isolapurr/log.rs");
+include!("isolapurr/logs.rs");
+include!("isolapurr/sessions.rs");
+include!("isolapurr/fast_charge_probe.rs");
+include!("isolapurr/trace.rs");
+include!("isolapurr/firmware.rs");
 include!("isolapurr/tests.rs");
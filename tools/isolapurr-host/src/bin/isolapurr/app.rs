@@ -110,8 +110,27 @@ async fn main() -> anyhow::Result<()> {
                     request_selected(&client, &devd, selector, Method::GET, "/diagnostics", None)
                         .await?
                 }
+                DiagnosticsCommand::I2c { command } => {
+                    handle_diagnostics_i2c(&client, &devd, command, !cli.json).await?
+                }
+                DiagnosticsCommand::Reg { command } => {
+                    handle_diagnostics_reg(&client, &devd, command, !cli.json).await?
+                }
+                DiagnosticsCommand::Crash(args) => {
+                    handle_diagnostics_crash(&client, &devd, args).await?
+                }
             },
             Command::Power { command } => handle_power(&client, &devd, command, !cli.json).await?,
+            Command::Sound { command } => handle_sound(&client, &devd, command).await?,
+            Command::Display { command } => handle_display(&client, &devd, command).await?,
+            Command::Buttons { command } => handle_buttons(&client, &devd, command).await?,
+            Command::Watch(args) => handle_watch(&client, &devd, args).await?,
+            Command::Test { command } => handle_test(&client, &devd, command).await?,
+            Command::Log(args) => handle_log(&client, &devd, args).await?,
+            Command::Logs(args) => handle_logs(&client, &devd, args, !cli.json).await?,
+            Command::Sessions(args) => handle_sessions(&client, &devd, args).await?,
+            Command::Trace { command } => handle_trace(command)?,
+            Command::Firmware { command } => handle_firmware(&client, &devd, command).await?,
         })
     }
     .await;
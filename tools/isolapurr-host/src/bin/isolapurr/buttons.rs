@@ -0,0 +1,143 @@
+const BUTTON_GESTURES: [&str; 8] = [
+    "left_short",
+    "left_long",
+    "left_double",
+    "right_short",
+    "right_long",
+    "right_double",
+    "combo_short",
+    "combo_long",
+];
+
+const BUTTON_ACTIONS: [&str; 7] = [
+    "none",
+    "power_toggle",
+    "data_replug",
+    "cycle_preset",
+    "identify",
+    "lock",
+    "settings_menu",
+];
+
+#[derive(Debug, Subcommand)]
+enum ButtonsCommand {
+    #[command(about = "Show the gesture-to-action table and lock state")]
+    Show(ApiSelectorArgs),
+    #[command(
+        about = "Map gestures to actions; omitted gestures keep their current action",
+        after_help = "Left is the USB-A button, right the USB-C button. Port actions act on the\npressed button's port, so combo gestures cannot use power_toggle or data_replug.\nMapping a double press delays single presses on that button by 350 ms."
+    )]
+    Set(ButtonsSetArgs),
+    #[command(about = "Restore the default button mapping and unlock")]
+    Defaults(ApiSelectorArgs),
+    #[command(about = "Ignore button presses until a long combo press or `buttons unlock`")]
+    Lock(ApiSelectorArgs),
+    #[command(about = "Accept button presses again")]
+    Unlock(ApiSelectorArgs),
+}
+
+#[derive(Debug, Default, clap::Args)]
+struct ButtonsSetArgs {
+    #[command(flatten)]
+    selector: ApiSelectorArgs,
+    #[arg(long = "left-short", value_name = "ACTION", value_parser = BUTTON_ACTIONS)]
+    left_short: Option<String>,
+    #[arg(long = "left-long", value_name = "ACTION", value_parser = BUTTON_ACTIONS)]
+    left_long: Option<String>,
+    #[arg(long = "left-double", value_name = "ACTION", value_parser = BUTTON_ACTIONS)]
+    left_double: Option<String>,
+    #[arg(long = "right-short", value_name = "ACTION", value_parser = BUTTON_ACTIONS)]
+    right_short: Option<String>,
+    #[arg(long = "right-long", value_name = "ACTION", value_parser = BUTTON_ACTIONS)]
+    right_long: Option<String>,
+    #[arg(long = "right-double", value_name = "ACTION", value_parser = BUTTON_ACTIONS)]
+    right_double: Option<String>,
+    #[arg(long = "combo-short", value_name = "ACTION", value_parser = BUTTON_ACTIONS)]
+    combo_short: Option<String>,
+    #[arg(long = "combo-long", value_name = "ACTION", value_parser = BUTTON_ACTIONS)]
+    combo_long: Option<String>,
+}
+
+async fn handle_buttons(
+    client: &Client,
+    devd: &DevdClient,
+    command: ButtonsCommand,
+) -> anyhow::Result<Value> {
+    let (selector, method, suffix, body) = match command {
+        ButtonsCommand::Show(selector) => (selector, Method::GET, "/buttons", None),
+        ButtonsCommand::Set(args) => {
+            let body = buttons_set_body(&args)?;
+            (args.selector, Method::PUT, "/buttons", Some(body))
+        }
+        ButtonsCommand::Defaults(selector) => (selector, Method::POST, "/buttons/defaults", None),
+        ButtonsCommand::Lock(selector) => (
+            selector,
+            Method::PUT,
+            "/buttons",
+            Some(json!({"locked": true})),
+        ),
+        ButtonsCommand::Unlock(selector) => (
+            selector,
+            Method::PUT,
+            "/buttons",
+            Some(json!({"locked": false})),
+        ),
+    };
+    let value = request_selected(client, devd, selector, method, suffix, body).await?;
+    unwrap_device_success_result(value)
+}
+
+fn buttons_set_body(args: &ButtonsSetArgs) -> anyhow::Result<Value> {
+    let mut actions = serde_json::Map::new();
+    for (gesture, action) in BUTTON_GESTURES.into_iter().zip([
+        &args.left_short,
+        &args.left_long,
+        &args.left_double,
+        &args.right_short,
+        &args.right_long,
+        &args.right_double,
+        &args.combo_short,
+        &args.combo_long,
+    ]) {
+        let Some(action) = action.as_deref() else {
+            continue;
+        };
+        if gesture.starts_with("combo_") && matches!(action, "power_toggle" | "data_replug") {
+            return Err(anyhow!(
+                "{gesture} presses both buttons and cannot use the port action {action}"
+            ));
+        }
+        actions.insert(gesture.to_string(), json!(action));
+    }
+    if actions.is_empty() {
+        return Err(anyhow!("buttons set needs at least one gesture to change"));
+    }
+    Ok(json!({ "actions": actions }))
+}
+
+fn format_buttons_output(output: &Value) -> String {
+    let mut lines = vec![format!(
+        "Buttons: {}",
+        if output.get("locked").and_then(Value::as_bool) == Some(true) {
+            "locked (hold both buttons to unlock)"
+        } else {
+            "unlocked"
+        }
+    )];
+    for gesture in BUTTON_GESTURES {
+        let action = output
+            .pointer(&format!("/actions/{gesture}"))
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        lines.push(format!("  {gesture:<13} {action}"));
+    }
+    lines.push(format!(
+        "Saved: {}",
+        if output.get("persisted").and_then(Value::as_bool) == Some(true) {
+            "yes"
+        } else {
+            "no (defaults)"
+        }
+    ));
+    format!("{}\n", lines.join("\n"))
+}
@@ -0,0 +1,94 @@
+#[derive(Debug, clap::Args)]
+struct ChargeTerminationArgs {
+    /// Port to change (`port_a` or `port_c`); required with any setting below.
+    #[arg(long)]
+    port: Option<String>,
+    #[arg(long, value_parser = clap::value_parser!(bool), action = ArgAction::Set)]
+    enabled: Option<bool>,
+    #[arg(long, value_enum)]
+    action: Option<ChargeTerminationActionArg>,
+    /// Charge counts as finished once current stays below this (10..=3000 mA).
+    #[arg(long)]
+    threshold_ma: Option<u16>,
+    /// How long current must stay below the threshold (10..=3600 s).
+    #[arg(long)]
+    hold_s: Option<u16>,
+    /// Minimum charge time before termination may trigger (0..=43200 s).
+    #[arg(long)]
+    min_charge_s: Option<u16>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ChargeTerminationActionArg {
+    Notify,
+    #[value(alias = "power_off")]
+    PowerOff,
+    #[value(name = "drop-5v", alias = "drop_5v")]
+    Drop5v,
+}
+
+impl ChargeTerminationActionArg {
+    const fn as_wire(self) -> &'static str {
+        match self {
+            Self::Notify => ChargeTerminationAction::Notify.as_str(),
+            Self::PowerOff => ChargeTerminationAction::PowerOff.as_str(),
+            Self::Drop5v => ChargeTerminationAction::Drop5v.as_str(),
+        }
+    }
+}
+
+/// `None` reads the settings; any change needs `--port` and becomes a PUT.
+fn charge_termination_body(args: &ChargeTerminationArgs) -> anyhow::Result<Option<Value>> {
+    let mut body = serde_json::Map::new();
+    if let Some(enabled) = args.enabled {
+        body.insert("enabled".to_string(), json!(enabled));
+    }
+    if let Some(action) = args.action {
+        body.insert("action".to_string(), json!(action.as_wire()));
+    }
+    for (key, value) in [
+        ("threshold_ma", args.threshold_ma),
+        ("hold_s", args.hold_s),
+        ("min_charge_s", args.min_charge_s),
+    ] {
+        if let Some(value) = value {
+            body.insert(key.to_string(), json!(value));
+        }
+    }
+    if body.is_empty() {
+        return Ok(None);
+    }
+    let port = args
+        .port
+        .as_deref()
+        .ok_or_else(|| anyhow!("--port is required when changing charge termination"))?;
+    body.insert("port".to_string(), json!(port));
+    Ok(Some(Value::Object(body)))
+}
+
+fn format_charge_termination_output(output: &Value) -> String {
+    let mut text = String::new();
+    for (label, key) in [("USB-A", "port_a"), ("USB-C", "port_c")] {
+        let config = output.get(key).unwrap_or(&Value::Null);
+        let field = |name: &str| config.get(name).and_then(Value::as_u64).unwrap_or(0);
+        if config.get("enabled").and_then(Value::as_bool) == Some(true) {
+            text.push_str(&format!(
+                "{label}: {} below {} mA for {} s, after {} s of charging\n",
+                config.get("action").and_then(Value::as_str).unwrap_or("?"),
+                field("threshold_ma"),
+                field("hold_s"),
+                field("min_charge_s"),
+            ));
+        } else {
+            text.push_str(&format!("{label}: disabled\n"));
+        }
+    }
+    text.push_str(&format!(
+        "Persisted: {}\n",
+        output
+            .get("persisted")
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    ));
+    text
+}
@@ -59,9 +59,182 @@ enum Command {
         #[command(subcommand)]
         command: PowerCommand,
     },
+    #[command(about = "Mute, schedule, or customize device prompt tones")]
+    Sound {
+        #[command(subcommand)]
+        command: SoundCommand,
+    },
+    #[command(about = "Adjust screen brightness, idle sleep, rotation, and theme")]
+    Display {
+        #[command(subcommand)]
+        command: DisplayCommand,
+    },
+    #[command(about = "Map button gestures to actions, or lock the buttons")]
+    Buttons {
+        #[command(subcommand)]
+        command: ButtonsCommand,
+    },
+    #[command(
+        about = "Full-screen live dashboard for one or more hubs",
+        after_help = "Keys: Tab/Shift-Tab switch hub, Up/Down pick a port, p toggles its power,\nr replugs it, i identifies the hub, -/+ step the power preset, q quits."
+    )]
+    Watch(WatchArgs),
+    #[command(about = "Run declarative DUT test plans against a hub")]
+    Test {
+        #[command(subcommand)]
+        command: TestCommand,
+    },
+    #[command(
+        about = "Record port telemetry, PD, and thermal state to CSV or JSON Lines",
+        after_help = "Durations take a unit: 100ms, 1.5s, 10m, 2h. Ctrl-C stops cleanly.\nTransport loss is retried every second; a USB hub that re-enumerates is found again through devd."
+    )]
+    Log(LogArgs),
+    #[command(
+        about = "Read the firmware log ring over USB or LAN, or set its level and syslog target",
+        after_help = "--follow polls every --interval and prints records as they arrive; Ctrl-C stops.\nWith --json each record is printed as one JSON line."
+    )]
+    Logs(LogsArgs),
+    #[command(
+        about = "List the charging sessions the hub recorded since boot",
+        after_help = "A session opens when a load appears on a port and closes 5 s after it is gone."
+    )]
+    Sessions(SessionsArgs),
+    #[command(
+        about = "Export devd device traces for bug reports",
+        after_help = "Replay an exported trace without hardware:\n  isolapurr-devd replay trace.jsonl\n  isolapurr --ipc <replay endpoint> --no-auto-start ports --device-id <id>"
+    )]
+    Trace {
+        #[command(subcommand)]
+        command: TraceCommand,
+    },
+    #[command(
+        about = "Check devices for firmware updates, or update one over Local USB",
+        after_help = "--catalog takes the release manifest URL or a local path to releases-manifest.json."
+    )]
+    Firmware {
+        #[command(subcommand)]
+        command: FirmwareCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum FirmwareCommand {
+    #[command(about = "Compare every known device with the newest release on a channel")]
+    Check(FirmwareCheckArgs),
+    #[command(about = "Flash a catalog release; refuses downgrades unless --force")]
+    Update(FirmwareUpdateArgs),
 }
 
 #[derive(Debug, clap::Args, Clone)]
+struct FirmwareSourceArgs {
+    #[arg(long, default_value = DEFAULT_FIRMWARE_CATALOG)]
+    catalog: String,
+    #[arg(long, value_enum, default_value_t = FirmwareChannel::Stable)]
+    channel: FirmwareChannel,
+}
+
+#[derive(Debug, clap::Args)]
+struct FirmwareCheckArgs {
+    #[command(flatten)]
+    source: FirmwareSourceArgs,
+    #[command(flatten)]
+    selector: ApiSelectorArgs,
+}
+
+#[derive(Debug, clap::Args)]
+struct FirmwareUpdateArgs {
+    #[command(flatten)]
+    source: FirmwareSourceArgs,
+    #[command(flatten)]
+    selector: UsbSelectorArgs,
+    #[arg(long, help = "Release version or tag [default: newest on the channel]")]
+    version: Option<String>,
+    #[arg(long, help = "Allow reinstalling or downgrading")]
+    force: bool,
+    #[arg(long)]
+    real: bool,
+    #[arg(long, help = "Download cache [default: user cache dir]")]
+    cache_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+enum TraceCommand {
+    #[command(about = "Collect recorded request/response pairs into one JSON Lines file")]
+    Export(TraceExportArgs),
+}
+
+#[derive(Debug, clap::Args)]
+struct TraceExportArgs {
+    #[arg(long)]
+    out: PathBuf,
+    #[arg(
+        long = "device-id",
+        help = "Only this devd device id or serial port path"
+    )]
+    device_id: Option<String>,
+    #[arg(long, value_parser = parse_log_duration, help = "Only the last 30m, 2h, ...")]
+    since: Option<Duration>,
+    #[arg(long, help = "devd trace directory [default: user data dir]")]
+    trace_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Args)]
+struct LogArgs {
+    #[command(flatten)]
+    selector: ApiSelectorArgs,
+    #[arg(long, value_parser = parse_log_duration, default_value = "1s")]
+    interval: Duration,
+    #[arg(long, help = "Output file; .jsonl or .ndjson selects JSON Lines")]
+    out: PathBuf,
+    #[arg(long, value_enum)]
+    format: Option<LogFormatArg>,
+    #[arg(long, help = "Start a new file after this many MiB")]
+    rotate_mb: Option<u64>,
+    #[arg(long, value_parser = parse_log_duration, help = "Start a new file after this long")]
+    rotate_every: Option<Duration>,
+    #[arg(long, value_parser = parse_log_duration, help = "Stop after this long")]
+    duration: Option<Duration>,
+    #[arg(long, help = "Stop once --energy-port has delivered this many mWh")]
+    energy_mwh: Option<f64>,
+    #[arg(long, value_parser = parse_log_port, default_value = "port_c")]
+    energy_port: PortId,
+    #[arg(
+        long,
+        value_parser = parse_log_trigger,
+        help = "Stop when <port>.<voltage_mv|current_ma|power_mw><op><value> holds, e.g. port_c.current_ma<50"
+    )]
+    stop_when: Option<LogTrigger>,
+    #[arg(
+        long,
+        value_parser = parse_log_duration,
+        default_value = "0s",
+        requires = "stop_when",
+        help = "How long --stop-when must hold"
+    )]
+    stop_hold: Duration,
+}
+
+#[derive(Debug, Subcommand)]
+enum TestCommand {
+    #[command(
+        about = "Run a TOML test plan and report each case",
+        after_help = "Progress goes to stderr. The command fails when any case fails, after writing\nthe --junit and --report files."
+    )]
+    Run(TestRunArgs),
+}
+
+#[derive(Debug, clap::Args)]
+struct TestRunArgs {
+    plan: PathBuf,
+    #[command(flatten)]
+    selector: ApiSelectorArgs,
+    #[arg(long, help = "Write a JUnit XML report to this path")]
+    junit: Option<PathBuf>,
+    #[arg(long, help = "Write a JSON report to this path")]
+    report: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Args, Clone, Default)]
 struct ApiSelectorArgs {
     #[arg(long = "device-id", conflicts_with = "url")]
     device_id: Option<String>,
@@ -79,6 +252,32 @@ impl ApiSelectorArgs {
     }
 }
 
+#[derive(Debug, clap::Args, Clone, Default)]
+struct WatchArgs {
+    #[arg(long = "device-id", help = "Hub to watch; repeat to tile several hubs")]
+    device_ids: Vec<String>,
+    #[arg(long = "url", help = "HTTP base URL of a hub; may be repeated")]
+    urls: Vec<String>,
+    #[arg(long, default_value_t = 1000, value_parser = clap::value_parser!(u64).range(200..=60_000))]
+    interval_ms: u64,
+}
+
+impl WatchArgs {
+    fn selectors(&self) -> Vec<ApiSelectorArgs> {
+        self.device_ids
+            .iter()
+            .map(|device_id| ApiSelectorArgs {
+                device_id: Some(device_id.clone()),
+                url: None,
+            })
+            .chain(self.urls.iter().map(|url| ApiSelectorArgs {
+                device_id: None,
+                url: Some(url.clone()),
+            }))
+            .collect()
+    }
+}
+
 #[derive(Debug, clap::Args, Clone, Default)]
 struct PowerSelectorArgs {
     #[arg(long = "device-id")]
@@ -188,6 +387,55 @@ enum PortsCommand {
         #[arg(long)]
         route: String,
     },
+    #[command(about = "Show or set the power state each port comes up in after a reset")]
+    PowerPolicy {
+        #[arg(long, alias = "port-a", value_enum)]
+        usb_a: Option<PortPowerPolicyArg>,
+        #[arg(long, alias = "port-c", value_enum)]
+        usb_c: Option<PortPowerPolicyArg>,
+    },
+    #[command(about = "Keep a port's USB data connected or make it charge-only (persisted)")]
+    Data {
+        #[arg(long)]
+        port: String,
+        #[arg(long, value_enum)]
+        mode: PortDataModeArg,
+    },
+    #[command(about = "Show or set when a finished charge ends on a port (persisted)")]
+    ChargeTermination(ChargeTerminationArgs),
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum PortPowerPolicyArg {
+    On,
+    Off,
+    Last,
+}
+
+impl PortPowerPolicyArg {
+    const fn as_config_value(self) -> &'static str {
+        match self {
+            Self::On => PortPowerPolicy::On.as_str(),
+            Self::Off => PortPowerPolicy::Off.as_str(),
+            Self::Last => PortPowerPolicy::Last.as_str(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum PortDataModeArg {
+    Connected,
+    #[value(alias = "charge_only")]
+    ChargeOnly,
+}
+
+impl PortDataModeArg {
+    const fn as_wire(self) -> &'static str {
+        match self {
+            Self::Connected => PortDataMode::Connected.as_str(),
+            Self::ChargeOnly => PortDataMode::ChargeOnly.as_str(),
+        }
+    }
 }
 
 #[derive(Debug, clap::Args)]
@@ -213,6 +461,20 @@ struct FlashArgs {
 #[derive(Debug, Subcommand)]
 enum DiagnosticsCommand {
     Export(ApiSelectorArgs),
+    #[command(about = "Inspect I2C bus health, scan allowlisted addresses or recover a bus")]
+    I2c {
+        #[command(subcommand)]
+        command: I2cCommand,
+    },
+    #[command(about = "Read or write SW2303/TPS55288 registers over Local USB after an unlock")]
+    Reg {
+        #[command(subcommand)]
+        command: RegCommand,
+    },
+    #[command(
+        about = "Show the last reset reason and persisted panic report, symbolised against the local ELF"
+    )]
+    Crash(CrashArgs),
 }
 
 #[derive(Debug, Subcommand)]
@@ -232,6 +494,11 @@ enum PowerCommand {
         #[command(subcommand)]
         command: IdleBiasCommand,
     },
+    #[command(
+        name = "fast-charge-probe",
+        about = "Offer one fast-charge protocol at a time and report what the USB-C sink negotiates"
+    )]
+    FastChargeProbe(FastChargeProbeArgs),
     #[command(about = "Restore the default USB-C source capability profile")]
     Defaults {
         #[command(flatten)]
@@ -411,8 +678,8 @@ enum LightLoadModeArg {
 impl LightLoadModeArg {
     const fn as_config_value(self) -> &'static str {
         match self {
-            Self::Pfm => "pfm",
-            Self::Fpwm => "fpwm",
+            Self::Pfm => LightLoadMode::Pfm.as_str(),
+            Self::Fpwm => LightLoadMode::Fpwm.as_str(),
         }
     }
 }
@@ -426,8 +693,8 @@ enum TpsModeArg {
 impl TpsModeArg {
     const fn as_config_value(self) -> &'static str {
         match self {
-            Self::AutoFollow => "auto_follow",
-            Self::Manual => "manual",
+            Self::AutoFollow => TpsMode::AutoFollow.as_str(),
+            Self::Manual => TpsMode::Manual.as_str(),
         }
     }
 }
@@ -448,11 +715,11 @@ enum Sw2303LineCompArg {
 impl Sw2303LineCompArg {
     const fn as_config_value(self) -> &'static str {
         match self {
-            Self::Off => "off",
-            Self::Zero => "0mohm",
-            Self::Fifty => "50mohm",
-            Self::OneHundred => "100mohm",
-            Self::OneHundredFifty => "150mohm",
+            Self::Off => Sw2303LineCompensation::Off.as_str(),
+            Self::Zero => Sw2303LineCompensation::Ohm0.as_str(),
+            Self::Fifty => Sw2303LineCompensation::MilliOhm50.as_str(),
+            Self::OneHundred => Sw2303LineCompensation::MilliOhm100.as_str(),
+            Self::OneHundredFifty => Sw2303LineCompensation::MilliOhm150.as_str(),
         }
     }
 }
@@ -503,90 +770,6 @@ struct SourceCapabilitySetArgs {
     non_pd_12v_enabled: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
-struct CliPowerConfig {
-    hardware: String,
-    persisted: bool,
-    tps_mode: String,
-    #[serde(default = "default_light_load_mode")]
-    light_load_mode: String,
-    #[serde(default = "default_sw2303_line_compensation")]
-    sw2303_line_compensation: String,
-    #[serde(default)]
-    runtime: CliPowerRuntime,
-    capability: CliPowerCapability,
-    manual: CliPowerManual,
-    lock: Option<CliPowerLock>,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
-struct CliPowerRuntime {
-    #[serde(default = "default_runtime_output_enabled")]
-    output_enabled: bool,
-    #[serde(default)]
-    discharge_enabled: bool,
-}
-
-impl Default for CliPowerRuntime {
-    fn default() -> Self {
-        Self {
-            output_enabled: default_runtime_output_enabled(),
-            discharge_enabled: false,
-        }
-    }
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
-struct CliPowerCapability {
-    profile: String,
-    power_watts: u8,
-    protocols: Value,
-    pd: CliPowerPd,
-    #[serde(default)]
-    current: CliPowerCurrentProfile,
-    #[serde(default)]
-    fast_charge: CliPowerFastChargeProfile,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
-struct CliPowerPd {
-    pps: bool,
-    fixed_voltages_mv: Vec<u16>,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
-struct CliPowerCurrentProfile {
-    pps3_limit_ma: u16,
-    pd_pps_5a: bool,
-    type_c_broadcast_ma: u16,
-    scp_limit_ma: u16,
-    fcp_afc_sfcp_limit_ma: u16,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
-struct CliPowerFastChargeProfile {
-    qc20_20v_enabled: bool,
-    qc30_20v_enabled: bool,
-    pe20_20v_enabled: bool,
-    non_pd_12v_enabled: bool,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
-struct CliPowerManual {
-    voltage_mv: u16,
-    current_limit_ma: u16,
-    usb_c_path_mode: String,
-    #[serde(default)]
-    tps_cdc_rise_mv: u16,
-    path_policy: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
-struct CliPowerLock {
-    owner: u32,
-    expires_at_ms: u64,
-}
-
 #[derive(Debug, Serialize, Deserialize, Clone)]
 struct CliPowerDiagnostics {
     usb_c_power_enabled: bool,
@@ -603,7 +786,7 @@ struct CliPowerDiagnostics {
     #[serde(default)]
     display: Option<CliUsbCDisplay>,
     #[serde(default)]
-    usb_c_actual: Option<CliPortTelemetry>,
+    usb_c_actual: Option<PortTelemetry>,
     #[serde(default)]
     active_protocol: Option<String>,
     tps_setpoint: CliPowerSetpoint,
@@ -688,26 +871,7 @@ struct CliIdleBiasError {
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 struct CliPortsResponse {
-    ports: Vec<CliPort>,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-struct CliPort {
-    #[serde(rename = "portId")]
-    port_id: String,
-    label: String,
-    telemetry: CliPortTelemetry,
-    #[serde(default)]
-    telemetry_raw: Option<CliPortTelemetry>,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-struct CliPortTelemetry {
-    status: String,
-    voltage_mv: Option<u32>,
-    current_ma: Option<u32>,
-    power_mw: Option<u32>,
-    sample_uptime_ms: u64,
+    ports: Vec<Port>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -761,29 +925,6 @@ struct CliPowerFastChargeReadback {
     non_pd_12v_enabled: Option<bool>,
 }
 
-impl Default for CliPowerCurrentProfile {
-    fn default() -> Self {
-        Self {
-            pps3_limit_ma: 5000,
-            pd_pps_5a: false,
-            type_c_broadcast_ma: 500,
-            scp_limit_ma: 5000,
-            fcp_afc_sfcp_limit_ma: 3250,
-        }
-    }
-}
-
-impl Default for CliPowerFastChargeProfile {
-    fn default() -> Self {
-        Self {
-            qc20_20v_enabled: true,
-            qc30_20v_enabled: true,
-            pe20_20v_enabled: true,
-            non_pd_12v_enabled: true,
-        }
-    }
-}
-
 impl Default for CliPowerCurrentReadback {
     fn default() -> Self {
         Self {
@@ -923,18 +1064,6 @@ impl<'de> Deserialize<'de> for CliPowerSetpoint {
 const MANUAL_OUTPUT_DEFAULT_VOLTAGE_MV: u16 = 5_000;
 const MANUAL_OUTPUT_DEFAULT_CURRENT_MA: u16 = 1_000;
 
-fn default_light_load_mode() -> String {
-    "pfm".to_string()
-}
-
-fn default_sw2303_line_compensation() -> String {
-    "50mohm".to_string()
-}
-
-fn default_runtime_output_enabled() -> bool {
-    true
-}
-
 fn parse_tps_cdc_rise_mv(raw: &str) -> Result<u16, String> {
     match raw {
         "0" | "100" | "200" | "300" | "400" | "500" | "600" | "700" => raw
@@ -998,9 +1127,9 @@ impl PowerConfigSetArgs {
 impl OutputUsbCPathArg {
     fn as_config_value(self) -> &'static str {
         match self {
-            Self::Automatic => "default",
-            Self::Disconnected => "disconnect",
-            Self::ForcedOn => "force",
+            Self::Automatic => ManualUsbCPathMode::Default.as_str(),
+            Self::Disconnected => ManualUsbCPathMode::Disconnect.as_str(),
+            Self::ForcedOn => ManualUsbCPathMode::Force.as_str(),
         }
     }
 }
@@ -0,0 +1,200 @@
+/// Where `cargo build --release` (and mcu-agentd) leave the firmware ELF.
+const DEFAULT_FIRMWARE_ELF: &str = "target/xtensa-esp32s3-none-elf/release/isolapurr-usb-hub";
+
+#[derive(Debug, clap::Args)]
+struct CrashArgs {
+    #[command(flatten)]
+    selector: ApiSelectorArgs,
+    /// Firmware ELF used to symbolise the backtrace; defaults to the local release build.
+    #[arg(long)]
+    elf: Option<PathBuf>,
+    #[arg(long, default_value = "xtensa-esp32s3-elf-addr2line")]
+    addr2line: String,
+    /// Clear the stored crash report after showing it.
+    #[arg(long)]
+    ack: bool,
+}
+
+async fn handle_diagnostics_crash(
+    client: &Client,
+    devd: &DevdClient,
+    args: CrashArgs,
+) -> anyhow::Result<Value> {
+    let value = request_selected(
+        client,
+        devd,
+        args.selector.clone(),
+        Method::GET,
+        "/diagnostics/crash",
+        None,
+    )
+    .await?;
+    let mut output = unwrap_device_success_result(value)?;
+
+    let addresses: Vec<String> = output
+        .pointer("/crash/backtrace")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|address| address.as_str().map(str::to_string))
+        .collect();
+    let elf = args.elf.or_else(|| {
+        let path = PathBuf::from(DEFAULT_FIRMWARE_ELF);
+        path.is_file().then_some(path)
+    });
+    if let (Some(elf), false) = (elf, addresses.is_empty()) {
+        output["symbols"] = match symbolize_backtrace(&args.addr2line, &elf, &addresses) {
+            Ok(symbols) => Value::Array(symbols),
+            Err(err) => {
+                eprintln!("warning: backtrace not symbolised: {err:#}");
+                Value::Null
+            }
+        };
+    }
+
+    if args.ack {
+        let value = request_selected(
+            client,
+            devd,
+            args.selector,
+            Method::POST,
+            "/diagnostics/crash/ack",
+            None,
+        )
+        .await?;
+        let ack = unwrap_device_success_result(value)?;
+        output["acknowledged"] = ack.get("acknowledged").cloned().unwrap_or(json!(0));
+    }
+    Ok(output)
+}
+
+fn symbolize_backtrace(
+    addr2line: &str,
+    elf: &std::path::Path,
+    addresses: &[String],
+) -> anyhow::Result<Vec<Value>> {
+    let output = ProcessCommand::new(addr2line)
+        .arg("-pfiaC")
+        .arg("-e")
+        .arg(elf)
+        .args(addresses)
+        .stdin(Stdio::null())
+        .output()
+        .with_context(|| format!("run {addr2line}"))?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "{addr2line} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(parse_addr2line_output(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+/// Parses `addr2line -pfiaC` output: one `0xADDR: function at file:line` line
+/// per address, followed by ` (inlined by) ...` lines for inlined callers.
+fn parse_addr2line_output(stdout: &str) -> Vec<Value> {
+    let mut symbols: Vec<Value> = Vec::new();
+    for line in stdout.lines() {
+        let line = line.trim();
+        if let Some(inlined) = line.strip_prefix("(inlined by) ") {
+            if let Some(Value::Array(callers)) = symbols
+                .last_mut()
+                .and_then(|symbol| symbol.get_mut("inlined_by"))
+            {
+                callers.push(json!(inlined));
+            }
+        } else if let Some((address, symbol)) = line.split_once(": ") {
+            // Unknown addresses print as `?? ??:0`, without the ` at `.
+            let (function, location) =
+                match symbol.split_once(" at ").or_else(|| symbol.split_once(' ')) {
+                    Some((function, location)) => (function, Some(location)),
+                    None => (symbol, None),
+                };
+            symbols.push(json!({
+                "address": address,
+                "function": function,
+                "location": location.filter(|location| !location.starts_with("??")),
+                "inlined_by": [],
+            }));
+        }
+    }
+    symbols
+}
+
+fn format_crash_output(output: &Value) -> String {
+    let mut lines = Vec::new();
+    let unacknowledged = output
+        .get("unacknowledged")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    lines.push(format!(
+        "Last reset: {} (hw code 0x{:02X}), {unacknowledged} unacknowledged crash(es)",
+        output.get("reason").and_then(Value::as_str).unwrap_or("?"),
+        output.get("hw_code").and_then(Value::as_u64).unwrap_or(0),
+    ));
+    match output.get("crash").filter(|crash| !crash.is_null()) {
+        None => lines.push("No crash report stored".to_string()),
+        Some(crash) => {
+            lines.push(format!(
+                "Crash: {} after {:.1} s uptime",
+                crash.get("reason").and_then(Value::as_str).unwrap_or("?"),
+                crash.get("uptime_ms").and_then(Value::as_u64).unwrap_or(0) as f64 / 1000.0,
+            ));
+            if let Some(message) = crash
+                .get("message")
+                .and_then(Value::as_str)
+                .filter(|message| !message.is_empty())
+            {
+                lines.push(format!("  message: {message}"));
+            }
+            if let Some(location) = crash.get("location").filter(|location| !location.is_null()) {
+                lines.push(format!(
+                    "  at {}:{}:{}",
+                    location.get("file").and_then(Value::as_str).unwrap_or("?"),
+                    location.get("line").and_then(Value::as_u64).unwrap_or(0),
+                    location.get("column").and_then(Value::as_u64).unwrap_or(0),
+                ));
+            }
+            let symbols = output.get("symbols").and_then(Value::as_array);
+            let backtrace = crash.get("backtrace").and_then(Value::as_array);
+            if backtrace.is_some_and(|backtrace| !backtrace.is_empty()) {
+                lines.push("Backtrace:".to_string());
+            }
+            for (index, address) in backtrace.into_iter().flatten().enumerate() {
+                let address = address.as_str().unwrap_or("?");
+                match symbols.and_then(|symbols| symbols.get(index)) {
+                    Some(symbol) => {
+                        lines.push(format!(
+                            "  {address} {}{}",
+                            symbol
+                                .get("function")
+                                .and_then(Value::as_str)
+                                .unwrap_or("??"),
+                            symbol
+                                .get("location")
+                                .and_then(Value::as_str)
+                                .map(|location| format!(" at {location}"))
+                                .unwrap_or_default(),
+                        ));
+                        for caller in symbol
+                            .get("inlined_by")
+                            .and_then(Value::as_array)
+                            .into_iter()
+                            .flatten()
+                            .filter_map(Value::as_str)
+                        {
+                            lines.push(format!("      inlined by {caller}"));
+                        }
+                    }
+                    None => lines.push(format!("  {address}")),
+                }
+            }
+        }
+    }
+    if let Some(acknowledged) = output.get("acknowledged").and_then(Value::as_u64) {
+        lines.push(format!("Acknowledged {acknowledged} crash report(s)"));
+    }
+    format!("{}\n", lines.join("\n"))
+}
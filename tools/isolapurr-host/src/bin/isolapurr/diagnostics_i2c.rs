@@ -0,0 +1,171 @@
+#[derive(Debug, Subcommand)]
+enum I2cCommand {
+    #[command(about = "Show per-address I2C counters, line levels and the last scan or recovery")]
+    Show(ApiSelectorArgs),
+    #[command(about = "Probe every allowlisted address with a one-byte read")]
+    Scan(ApiSelectorArgs),
+    #[command(
+        about = "Reset one I2C bus controller and check that the lines return to idle",
+        after_help = "Recovering the pd bus cycles CE_TPS: USB-C output drops briefly and the SW2303\nrenegotiates with the attached sink. It needs USB-C power on and is limited to\none run per second."
+    )]
+    Recover {
+        #[command(flatten)]
+        selector: ApiSelectorArgs,
+        #[arg(long, value_parser = ["pd", "system"])]
+        bus: String,
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+async fn handle_diagnostics_i2c(
+    client: &Client,
+    devd: &DevdClient,
+    command: I2cCommand,
+    allow_interactive: bool,
+) -> anyhow::Result<Value> {
+    let (selector, method, suffix) = match command {
+        I2cCommand::Show(selector) => (selector, Method::GET, "/diagnostics/i2c".to_string()),
+        I2cCommand::Scan(selector) => (selector, Method::POST, "/diagnostics/i2c/scan".to_string()),
+        I2cCommand::Recover { selector, bus, yes } => {
+            if !allow_interactive && !yes {
+                return Err(anyhow!("i2c recovery requires --yes when --json is set"));
+            }
+            if allow_interactive && !yes {
+                confirm_i2c_recover(&bus)?;
+            }
+            let owner = next_power_owner();
+            (
+                selector,
+                Method::POST,
+                format!("/diagnostics/i2c/recover?bus={bus}&owner={owner}"),
+            )
+        }
+    };
+    let value = request_selected(client, devd, selector, method, &suffix, None).await?;
+    unwrap_device_success_result(value)
+}
+
+fn confirm_i2c_recover(bus: &str) -> anyhow::Result<()> {
+    use std::io::IsTerminal;
+
+    if !std::io::stdin().is_terminal() {
+        return Err(anyhow!(
+            "i2c recovery requires an interactive terminal or --yes"
+        ));
+    }
+    eprintln!("Recover the {bus} I2C bus on the selected IsolaPurr hub.");
+    if bus == "pd" {
+        eprintln!("This cycles CE_TPS: USB-C output drops and the attached sink renegotiates.");
+    } else {
+        eprintln!("Telemetry, EEPROM and TPS55288 access pause while the controller resets.");
+    }
+    eprintln!("Type 'recover {bus}' to continue:");
+    let mut line = String::new();
+    std::io::stdin().read_line(&mut line)?;
+    if line.trim() != format!("recover {bus}") {
+        return Err(UserCancelled.into());
+    }
+    Ok(())
+}
+
+fn format_i2c_lines(lines: Option<&Value>) -> String {
+    let level = |key: &str| match lines
+        .and_then(|lines| lines.get(key))
+        .and_then(Value::as_bool)
+    {
+        Some(true) => "high",
+        Some(false) => "LOW",
+        None => "?",
+    };
+    format!("SDA {} SCL {}", level("sda_high"), level("scl_high"))
+}
+
+fn format_i2c_diagnostics_output(output: &Value) -> String {
+    let mut lines = Vec::new();
+    for bus in output
+        .get("buses")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let u64_field = |key: &str| bus.get(key).and_then(Value::as_u64).unwrap_or(0);
+        lines.push(format!(
+            "I2C {} (GPIO{} SDA / GPIO{} SCL): {}{}, recoveries {}, rejected {}",
+            bus.get("bus").and_then(Value::as_str).unwrap_or("?"),
+            u64_field("sda_gpio"),
+            u64_field("scl_gpio"),
+            format_i2c_lines(bus.get("lines")),
+            if bus.get("available").and_then(Value::as_bool) == Some(false) {
+                " (parked)"
+            } else {
+                ""
+            },
+            u64_field("recoveries"),
+            u64_field("rejected"),
+        ));
+        for address in bus
+            .get("addresses")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let count = |key: &str| address.get(key).and_then(Value::as_u64).unwrap_or(0);
+            lines.push(format!(
+                "  0x{:02X} {:<12} tx {:>8}  err {:>5} (nack {}, timeout {}, arb {}, other {})",
+                count("address"),
+                address.get("device").and_then(Value::as_str).unwrap_or("?"),
+                count("transactions"),
+                count("errors"),
+                count("nack"),
+                count("timeout"),
+                count("arbitration"),
+                count("other"),
+            ));
+        }
+    }
+    match output.get("last_scan").filter(|scan| !scan.is_null()) {
+        Some(scan) => {
+            lines.push(format!(
+                "Last scan at {} ms:",
+                scan.get("uptime_ms").and_then(Value::as_u64).unwrap_or(0)
+            ));
+            for result in scan
+                .get("results")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                lines.push(format!(
+                    "  {:<6} 0x{:02X} {:<12} {}",
+                    result.get("bus").and_then(Value::as_str).unwrap_or("?"),
+                    result.get("address").and_then(Value::as_u64).unwrap_or(0),
+                    result.get("device").and_then(Value::as_str).unwrap_or("?"),
+                    result.get("result").and_then(Value::as_str).unwrap_or("?"),
+                ));
+            }
+        }
+        None => lines.push("Last scan: none".to_string()),
+    }
+    match output
+        .get("last_recovery")
+        .filter(|recovery| !recovery.is_null())
+    {
+        Some(recovery) => lines.push(format!(
+            "Last recovery: {} bus at {} ms, {} ({})",
+            recovery.get("bus").and_then(Value::as_str).unwrap_or("?"),
+            recovery
+                .get("uptime_ms")
+                .and_then(Value::as_u64)
+                .unwrap_or(0),
+            if recovery.get("recovered").and_then(Value::as_bool) == Some(true) {
+                "recovered"
+            } else {
+                "FAILED"
+            },
+            format_i2c_lines(recovery.get("lines")),
+        )),
+        None => lines.push("Last recovery: none".to_string()),
+    }
+    format!("{}\n", lines.join("\n"))
+}
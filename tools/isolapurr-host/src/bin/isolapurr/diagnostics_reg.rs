@@ -0,0 +1,276 @@
+#[derive(Debug, clap::Args)]
+struct RegTargetArgs {
+    #[command(flatten)]
+    selector: UsbSelectorArgs,
+    #[arg(long, value_parser = ["sw2303", "tps55288"])]
+    target: String,
+}
+
+#[derive(Debug, Subcommand)]
+enum RegCommand {
+    #[command(about = "Show whether register debug is unlocked and the recent write log")]
+    Status(UsbSelectorArgs),
+    #[command(about = "Read one SW2303 or TPS55288 register")]
+    Read {
+        #[command(flatten)]
+        args: RegTargetArgs,
+        #[arg(long, value_parser = parse_register_byte)]
+        reg: u8,
+        #[arg(long)]
+        yes: bool,
+    },
+    #[command(
+        about = "Write one SW2303 or TPS55288 register and read it back",
+        after_help = "Raw writes bypass the PD coordinator. The saved power config is re-applied when\nthe session ends with `isolapurr diagnostics reg exit` or after 2 minutes idle."
+    )]
+    Write {
+        #[command(flatten)]
+        args: RegTargetArgs,
+        #[arg(long, value_parser = parse_register_byte)]
+        reg: u8,
+        #[arg(long, value_parser = parse_register_byte)]
+        value: u8,
+        #[arg(long)]
+        yes: bool,
+    },
+    #[command(about = "Read a range of registers (the whole map by default)")]
+    Dump {
+        #[command(flatten)]
+        args: RegTargetArgs,
+        #[arg(long, value_parser = parse_register_byte)]
+        start: Option<u8>,
+        #[arg(long)]
+        count: Option<u16>,
+        #[arg(long)]
+        yes: bool,
+    },
+    #[command(about = "Lock register debug and re-apply the saved power config")]
+    Exit(UsbSelectorArgs),
+}
+
+/// Accepts `0x1F` or `31`.
+fn parse_register_byte(raw: &str) -> Result<u8, String> {
+    let raw = raw.trim();
+    let parsed = match raw.strip_prefix("0x").or_else(|| raw.strip_prefix("0X")) {
+        Some(hex) => u8::from_str_radix(hex, 16),
+        None => raw.parse::<u8>(),
+    };
+    parsed.map_err(|_| format!("expected 0..=255 or 0x00..=0xFF, got {raw}"))
+}
+
+async fn handle_diagnostics_reg(
+    client: &Client,
+    devd: &DevdClient,
+    command: RegCommand,
+    allow_interactive: bool,
+) -> anyhow::Result<Value> {
+    let (selector, suffix, body, unlock) = match command {
+        RegCommand::Status(selector) => (selector, "debug", None, None),
+        RegCommand::Exit(selector) => (selector, "debug/exit", Some(json!({})), None),
+        RegCommand::Read { args, reg, yes } => (
+            args.selector,
+            "debug/registers/read",
+            Some(json!({"target": args.target, "reg": reg})),
+            Some(yes),
+        ),
+        RegCommand::Write {
+            args,
+            reg,
+            value,
+            yes,
+        } => (
+            args.selector,
+            "debug/registers/write",
+            Some(json!({"target": args.target, "reg": reg, "value": value})),
+            Some(yes),
+        ),
+        RegCommand::Dump {
+            args,
+            start,
+            count,
+            yes,
+        } => {
+            let mut body = json!({"target": args.target});
+            if let Some(start) = start {
+                body["start"] = json!(start);
+            }
+            if let Some(count) = count {
+                body["count"] = json!(count);
+            }
+            (args.selector, "debug/registers/dump", Some(body), Some(yes))
+        }
+    };
+    if unlock == Some(false) && !allow_interactive {
+        return Err(anyhow!("register debug requires --yes when --json is set"));
+    }
+
+    // Register debug is USB only: the device refuses it over LAN, so only a
+    // Local USB selector is accepted here.
+    let device =
+        materialize_live_usb_device(client, devd, resolve_usb_device(&selector, &devd.endpoint)?)
+            .await?;
+    let devd = devd.with_endpoint(device.devd.clone());
+    let path = |suffix: &str| format!("/api/v1/devices/{}/{suffix}", device.device);
+
+    if let Some(yes) = unlock {
+        let status = unwrap_device_success_result(
+            devd_request(client, &devd, Method::GET, &path("debug"), None).await?,
+        )?;
+        if status.get("unlocked").and_then(Value::as_bool) != Some(true) {
+            let challenge = unwrap_device_success_result(
+                devd_request(
+                    client,
+                    &devd,
+                    Method::POST,
+                    &path("debug/unlock"),
+                    Some(json!({})),
+                )
+                .await?,
+            )?;
+            let code = challenge
+                .get("challenge")
+                .and_then(Value::as_str)
+                .ok_or_else(|| anyhow!("device did not return an unlock challenge"))?
+                .to_string();
+            if !yes {
+                confirm_register_debug_unlock(&code)?;
+            }
+            unwrap_device_success_result(
+                devd_request(
+                    client,
+                    &devd,
+                    Method::POST,
+                    &path("debug/unlock"),
+                    Some(json!({"code": code})),
+                )
+                .await?,
+            )?;
+        }
+    }
+
+    let method = if suffix == "debug" {
+        Method::GET
+    } else {
+        Method::POST
+    };
+    let value = devd_request(client, &devd, method, &path(suffix), body).await?;
+    unwrap_device_success_result(value)
+}
+
+fn confirm_register_debug_unlock(code: &str) -> anyhow::Result<()> {
+    use std::io::IsTerminal;
+
+    if !std::io::stdin().is_terminal() {
+        return Err(anyhow!(
+            "register debug unlock requires an interactive terminal or --yes"
+        ));
+    }
+    eprintln!("Unlock raw SW2303/TPS55288 register access on the selected IsolaPurr hub.");
+    eprintln!(
+        "Writes bypass the PD coordinator and can change USB-C output; every write is logged."
+    );
+    eprintln!(
+        "The saved power config is re-applied on `diagnostics reg exit` or after 2 minutes idle."
+    );
+    eprintln!("Type the unlock code {code} to continue:");
+    let mut line = String::new();
+    std::io::stdin().read_line(&mut line)?;
+    if !line.trim().eq_ignore_ascii_case(code) {
+        return Err(UserCancelled.into());
+    }
+    Ok(())
+}
+
+fn format_register_byte(value: Option<&Value>) -> String {
+    match value.and_then(Value::as_u64) {
+        Some(value) => format!("0x{value:02X}"),
+        None => "--".to_string(),
+    }
+}
+
+fn format_register_debug_output(output: &Value) -> String {
+    let mut lines = Vec::new();
+    let target = output.get("target").and_then(Value::as_str).unwrap_or("?");
+    if let Some(restored) = output.get("restored").and_then(Value::as_bool) {
+        lines.push(if restored {
+            "Register debug locked; saved power config re-applied".to_string()
+        } else {
+            "Register debug locked".to_string()
+        });
+    } else if let Some(values) = output.get("values").and_then(Value::as_array) {
+        let start = output.get("start").and_then(Value::as_u64).unwrap_or(0);
+        lines.push(format!(
+            "{target} (0x{:02X}) registers 0x{start:02X}..0x{:02X}:",
+            output.get("address").and_then(Value::as_u64).unwrap_or(0),
+            start + values.len().saturating_sub(1) as u64,
+        ));
+        for (row, chunk) in values.chunks(16).enumerate() {
+            let bytes: Vec<String> = chunk
+                .iter()
+                .map(|value| format!("{:02X}", value.as_u64().unwrap_or(0)))
+                .collect();
+            lines.push(format!(
+                "  {:02X}: {}",
+                start + row as u64 * 16,
+                bytes.join(" ")
+            ));
+        }
+    } else if output.get("readback").is_some() {
+        lines.push(format!(
+            "{target} reg {}: {} -> {}, reads back {}",
+            format_register_byte(output.get("reg")),
+            format_register_byte(output.get("previous")),
+            format_register_byte(output.get("value")),
+            format_register_byte(output.get("readback")),
+        ));
+    } else if output.get("reg").is_some() {
+        lines.push(format!(
+            "{target} (0x{:02X}) reg {} = {}",
+            output.get("address").and_then(Value::as_u64).unwrap_or(0),
+            format_register_byte(output.get("reg")),
+            format_register_byte(output.get("value")),
+        ));
+    } else {
+        let unlocked = output.get("unlocked").and_then(Value::as_bool) == Some(true);
+        lines.push(
+            match output.get("idle_remaining_ms").and_then(Value::as_u64) {
+                Some(remaining) if unlocked => {
+                    format!(
+                        "Register debug: unlocked, locks after {} s idle",
+                        remaining / 1000
+                    )
+                }
+                _ => "Register debug: locked".to_string(),
+            },
+        );
+        lines.push(format!(
+            "Writes since boot: {}",
+            output
+                .get("total_writes")
+                .and_then(Value::as_u64)
+                .unwrap_or(0)
+        ));
+        for write in output
+            .get("writes")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            lines.push(format!(
+                "  {:>10} ms {:<8} reg {}: {} -> {}, reads back {}{}",
+                write.get("uptime_ms").and_then(Value::as_u64).unwrap_or(0),
+                write.get("target").and_then(Value::as_str).unwrap_or("?"),
+                format_register_byte(write.get("reg")),
+                format_register_byte(write.get("previous")),
+                format_register_byte(write.get("value")),
+                format_register_byte(write.get("readback")),
+                if write.get("ok").and_then(Value::as_bool) == Some(true) {
+                    ""
+                } else {
+                    " (FAILED)"
+                },
+            ));
+        }
+    }
+    format!("{}\n", lines.join("\n"))
+}
@@ -0,0 +1,139 @@
+#[derive(Debug, Subcommand)]
+enum DisplayCommand {
+    #[command(about = "Show brightness, idle sleep, rotation, and theme settings")]
+    Show(ApiSelectorArgs),
+    #[command(
+        about = "Update display settings; omitted options keep their current value",
+        after_help = "Timeouts are seconds of no button or port activity: 0 disables the stage,\notherwise 10-7200. --off-after must be longer than --dim-after when both are set."
+    )]
+    Set(DisplaySetArgs),
+    #[command(about = "Restore default display settings")]
+    Defaults(ApiSelectorArgs),
+    #[command(
+        about = "Save the frame currently on the device screen",
+        after_help = "The output format follows the file extension: .png or .bmp."
+    )]
+    Screenshot {
+        #[command(flatten)]
+        selector: ApiSelectorArgs,
+        output: PathBuf,
+    },
+}
+
+#[derive(Debug, Default, clap::Args)]
+struct DisplaySetArgs {
+    #[command(flatten)]
+    selector: ApiSelectorArgs,
+    #[arg(long, value_parser = clap::value_parser!(u8).range(5..=100))]
+    brightness: Option<u8>,
+    #[arg(long = "dim-after", value_name = "SECONDS", value_parser = clap::value_parser!(u16).range(0..=7200))]
+    dim_after: Option<u16>,
+    #[arg(long = "off-after", value_name = "SECONDS", value_parser = clap::value_parser!(u16).range(0..=7200))]
+    off_after: Option<u16>,
+    #[arg(long, value_parser = ["0", "180"])]
+    rotation: Option<String>,
+    #[arg(long, value_parser = ["light", "dark"])]
+    theme: Option<String>,
+}
+
+async fn handle_display(
+    client: &Client,
+    devd: &DevdClient,
+    command: DisplayCommand,
+) -> anyhow::Result<Value> {
+    let (selector, method, suffix, body) = match command {
+        DisplayCommand::Show(selector) => (selector, Method::GET, "/display", None),
+        DisplayCommand::Set(args) => {
+            let body = display_set_body(&args)?;
+            (args.selector, Method::PUT, "/display", Some(body))
+        }
+        DisplayCommand::Defaults(selector) => (selector, Method::POST, "/display/defaults", None),
+        DisplayCommand::Screenshot { selector, output } => {
+            return save_display_screenshot(client, devd, selector, output).await;
+        }
+    };
+    let value = request_selected(client, devd, selector, method, suffix, body).await?;
+    unwrap_device_success_result(value)
+}
+
+fn display_set_body(args: &DisplaySetArgs) -> anyhow::Result<Value> {
+    let mut body = serde_json::Map::new();
+    if let Some(brightness) = args.brightness {
+        body.insert("brightness_pct".to_string(), json!(brightness));
+    }
+    for (key, value) in [
+        ("dim_after_s", args.dim_after),
+        ("off_after_s", args.off_after),
+    ] {
+        if let Some(seconds) = value {
+            if seconds != 0 && seconds < 10 {
+                return Err(anyhow!("{key} must be 0 or 10-7200 seconds, got {seconds}"));
+            }
+            body.insert(key.to_string(), json!(seconds));
+        }
+    }
+    if let (Some(dim), Some(off)) = (args.dim_after, args.off_after)
+        && dim != 0
+        && off != 0
+        && off <= dim
+    {
+        return Err(anyhow!(
+            "--off-after ({off}s) must be longer than --dim-after ({dim}s)"
+        ));
+    }
+    if let Some(rotation) = args.rotation.as_deref() {
+        body.insert("rotation".to_string(), json!(rotation.parse::<u16>()?));
+    }
+    if let Some(theme) = args.theme.as_deref() {
+        body.insert("theme".to_string(), json!(theme));
+    }
+    if body.is_empty() {
+        return Err(anyhow!("display set needs at least one option to change"));
+    }
+    Ok(Value::Object(body))
+}
+
+fn format_display_output(output: &Value) -> String {
+    let seconds = |key: &str| output.get(key).and_then(Value::as_u64).unwrap_or(0);
+    let stage = |value: u64| {
+        if value == 0 {
+            "never".to_string()
+        } else if value.is_multiple_of(60) {
+            format!("{} min", value / 60)
+        } else {
+            format!("{value}s")
+        }
+    };
+    let mut lines = vec![
+        format!(
+            "Brightness: {}% (backlight {})",
+            seconds("brightness_pct"),
+            output
+                .get("backlight")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown"),
+        ),
+        format!(
+            "Idle: dim after {}, off after {}",
+            stage(seconds("dim_after_s")),
+            stage(seconds("off_after_s")),
+        ),
+        format!(
+            "Rotation: {}°, theme {}",
+            seconds("rotation"),
+            output
+                .get("theme")
+                .and_then(Value::as_str)
+                .unwrap_or("light"),
+        ),
+    ];
+    lines.push(format!(
+        "Saved: {}",
+        if output.get("persisted").and_then(Value::as_bool) == Some(true) {
+            "yes"
+        } else {
+            "no (defaults)"
+        }
+    ));
+    format!("{}\n", lines.join("\n"))
+}
@@ -0,0 +1,321 @@
+/// One captured display frame as native RGB565 pixels, top row first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DisplayScreenshot {
+    width: u32,
+    height: u32,
+    rgb565: Vec<u16>,
+}
+
+impl DisplayScreenshot {
+    /// Decodes the USB JSONL `display.screenshot` result.
+    fn from_jsonl_result(value: &Value) -> anyhow::Result<Self> {
+        use base64::Engine as _;
+
+        let dimension = |key: &str| {
+            value
+                .get(key)
+                .and_then(Value::as_u64)
+                .and_then(|v| u32::try_from(v).ok())
+                .ok_or_else(|| anyhow!("screenshot result is missing {key}"))
+        };
+        let (width, height) = (dimension("width")?, dimension("height")?);
+        let format = value.get("format").and_then(Value::as_str).unwrap_or("");
+        if format != "rgb565le" {
+            return Err(anyhow!("unsupported screenshot format {format:?}"));
+        }
+        let data = value
+            .get("data")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("screenshot result is missing data"))?;
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(data)
+            .context("decode screenshot data")?;
+        Self::from_le_bytes(width, height, &bytes)
+    }
+
+    /// Decodes the device HTTP BMP: 16-bit BI_BITFIELDS RGB565, either row order.
+    fn from_bmp(bytes: &[u8]) -> anyhow::Result<Self> {
+        let u16_at = |at: usize| {
+            bytes
+                .get(at..at + 2)
+                .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        };
+        let u32_at = |at: usize| {
+            bytes
+                .get(at..at + 4)
+                .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        };
+        if bytes.get(0..2) != Some(b"BM".as_slice()) {
+            return Err(anyhow!("screenshot is not a BMP image"));
+        }
+        let (Some(pixel_offset), Some(width), Some(raw_height), Some(bpp), Some(compression)) = (
+            u32_at(10),
+            u32_at(18),
+            u32_at(22).map(|h| h as i32),
+            u16_at(28),
+            u32_at(30),
+        ) else {
+            return Err(anyhow!("screenshot BMP header is truncated"));
+        };
+        if bpp != 16 || compression != 3 || u32_at(54) != Some(0xF800) {
+            return Err(anyhow!("screenshot BMP is not RGB565"));
+        }
+        let height = raw_height.unsigned_abs();
+        let stride = (width as usize * 2).div_ceil(4) * 4;
+        let mut rgb565 = Vec::with_capacity(width as usize * height as usize);
+        for row in 0..height as usize {
+            // Positive heights are stored bottom-up.
+            let stored_row = if raw_height < 0 {
+                row
+            } else {
+                height as usize - 1 - row
+            };
+            let start = pixel_offset as usize + stored_row * stride;
+            let pixels = bytes
+                .get(start..start + width as usize * 2)
+                .ok_or_else(|| anyhow!("screenshot BMP pixel data is truncated"))?;
+            rgb565.extend(
+                pixels
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_le_bytes([pair[0], pair[1]])),
+            );
+        }
+        Ok(Self {
+            width,
+            height,
+            rgb565,
+        })
+    }
+
+    fn from_le_bytes(width: u32, height: u32, bytes: &[u8]) -> anyhow::Result<Self> {
+        let expected = width as usize * height as usize * 2;
+        if bytes.len() != expected {
+            return Err(anyhow!(
+                "screenshot has {} bytes, expected {expected} for {width}x{height}",
+                bytes.len()
+            ));
+        }
+        Ok(Self {
+            width,
+            height,
+            rgb565: bytes
+                .chunks_exact(2)
+                .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                .collect(),
+        })
+    }
+
+    fn rgb888_rows(&self) -> impl Iterator<Item = Vec<u8>> + '_ {
+        self.rgb565.chunks_exact(self.width as usize).map(|row| {
+            row.iter()
+                .flat_map(|&pixel| {
+                    let r = ((pixel >> 11) & 0x1F) as u8;
+                    let g = ((pixel >> 5) & 0x3F) as u8;
+                    let b = (pixel & 0x1F) as u8;
+                    [
+                        (r << 3) | (r >> 2),
+                        (g << 2) | (g >> 4),
+                        (b << 3) | (b >> 2),
+                    ]
+                })
+                .collect()
+        })
+    }
+
+    /// Truecolor PNG using stored (uncompressed) deflate blocks, which keeps the
+    /// CLI free of an image codec dependency for a ~165 KB file.
+    fn encode_png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity((self.width as usize * 3 + 1) * self.height as usize);
+        for row in self.rgb888_rows() {
+            raw.push(0);
+            raw.extend_from_slice(&row);
+        }
+
+        let mut zlib = vec![0x78, 0x01];
+        let mut blocks = raw.chunks(0xFFFF).peekable();
+        if blocks.peek().is_none() {
+            zlib.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+        }
+        while let Some(block) = blocks.next() {
+            let len = block.len() as u16;
+            zlib.push(u8::from(blocks.peek().is_none()));
+            zlib.extend_from_slice(&len.to_le_bytes());
+            zlib.extend_from_slice(&(!len).to_le_bytes());
+            zlib.extend_from_slice(block);
+        }
+        zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+        let mut ihdr = Vec::with_capacity(13);
+        ihdr.extend_from_slice(&self.width.to_be_bytes());
+        ihdr.extend_from_slice(&self.height.to_be_bytes());
+        ihdr.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        write_png_chunk(&mut png, b"IHDR", &ihdr);
+        write_png_chunk(&mut png, b"IDAT", &zlib);
+        write_png_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+
+    /// Top-down RGB565 BMP, byte-for-byte what the device HTTP endpoint serves.
+    fn encode_bmp(&self) -> Vec<u8> {
+        let stride = (self.width as usize * 2).div_ceil(4) * 4;
+        let image_len = (stride * self.height as usize) as u32;
+        let mut bmp = Vec::with_capacity(66 + image_len as usize);
+        bmp.extend_from_slice(b"BM");
+        bmp.extend_from_slice(&(66 + image_len).to_le_bytes());
+        bmp.extend_from_slice(&[0; 4]);
+        bmp.extend_from_slice(&66u32.to_le_bytes());
+        bmp.extend_from_slice(&40u32.to_le_bytes());
+        bmp.extend_from_slice(&(self.width as i32).to_le_bytes());
+        bmp.extend_from_slice(&(-(self.height as i32)).to_le_bytes());
+        bmp.extend_from_slice(&1u16.to_le_bytes());
+        bmp.extend_from_slice(&16u16.to_le_bytes());
+        bmp.extend_from_slice(&3u32.to_le_bytes());
+        bmp.extend_from_slice(&image_len.to_le_bytes());
+        bmp.extend_from_slice(&2835u32.to_le_bytes());
+        bmp.extend_from_slice(&2835u32.to_le_bytes());
+        bmp.extend_from_slice(&[0; 8]);
+        for mask in [0xF800u32, 0x07E0, 0x001F] {
+            bmp.extend_from_slice(&mask.to_le_bytes());
+        }
+        for row in self.rgb565.chunks_exact(self.width as usize) {
+            let start = bmp.len();
+            for pixel in row {
+                bmp.extend_from_slice(&pixel.to_le_bytes());
+            }
+            bmp.resize(start + stride, 0);
+        }
+        bmp
+    }
+}
+
+fn write_png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in bytes {
+        a = (a + u32::from(byte)) % 65_521;
+        b = (b + a) % 65_521;
+    }
+    (b << 16) | a
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScreenshotFileFormat {
+    Png,
+    Bmp,
+}
+
+impl ScreenshotFileFormat {
+    fn from_path(path: &std::path::Path) -> anyhow::Result<Self> {
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase)
+            .as_deref()
+        {
+            Some("png") => Ok(Self::Png),
+            Some("bmp") => Ok(Self::Bmp),
+            _ => Err(anyhow!(
+                "screenshot output must end in .png or .bmp, got {}",
+                path.display()
+            )),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Bmp => "bmp",
+        }
+    }
+}
+
+async fn fetch_display_screenshot(
+    client: &Client,
+    devd: &DevdClient,
+    selector: ApiSelectorArgs,
+) -> anyhow::Result<DisplayScreenshot> {
+    match resolve_api_selector(selector, &devd.endpoint)? {
+        ResolvedTarget::Usb(usb) => {
+            let usb_devd = devd.with_endpoint(usb.devd.clone());
+            let usb = materialize_live_usb_device(client, &usb_devd, usb).await?;
+            let value = devd_request(
+                client,
+                &usb_devd,
+                Method::GET,
+                &format!("/api/v1/devices/{}/display/screenshot", usb.device),
+                None,
+            )
+            .await?;
+            DisplayScreenshot::from_jsonl_result(&unwrap_device_success_result(value)?)
+        }
+        ResolvedTarget::Http(url) => {
+            let bytes = client
+                .get(api_url(&url, "/api/v1/display/screenshot")?)
+                .send()
+                .await?
+                .error_for_status()?
+                .bytes()
+                .await?;
+            DisplayScreenshot::from_bmp(&bytes)
+        }
+    }
+}
+
+async fn save_display_screenshot(
+    client: &Client,
+    devd: &DevdClient,
+    selector: ApiSelectorArgs,
+    output: PathBuf,
+) -> anyhow::Result<Value> {
+    let format = ScreenshotFileFormat::from_path(&output)?;
+    let screenshot = fetch_display_screenshot(client, devd, selector).await?;
+    let encoded = match format {
+        ScreenshotFileFormat::Png => screenshot.encode_png(),
+        ScreenshotFileFormat::Bmp => screenshot.encode_bmp(),
+    };
+    fs::write(&output, &encoded).with_context(|| format!("write {}", output.display()))?;
+    Ok(json!({
+        "screenshot": {
+            "path": output.display().to_string(),
+            "format": format.as_str(),
+            "width": screenshot.width,
+            "height": screenshot.height,
+            "bytes": encoded.len(),
+        }
+    }))
+}
+
+fn format_display_screenshot_output(output: &Value) -> String {
+    let screenshot = &output["screenshot"];
+    format!(
+        "Saved {}x{} screenshot to {}\n",
+        screenshot["width"].as_u64().unwrap_or(0),
+        screenshot["height"].as_u64().unwrap_or(0),
+        screenshot["path"].as_str().unwrap_or("?"),
+    )
+}
@@ -0,0 +1,204 @@
+#[derive(Debug, clap::Args)]
+struct FastChargeProbeArgs {
+    #[command(flatten)]
+    selector: PowerSelectorArgs,
+    /// Show the last report without starting a probe.
+    #[arg(long, conflicts_with_all = ["cancel", "no_wait", "yes"])]
+    status: bool,
+    /// Stop a running probe; the saved source profile is restored right away.
+    #[arg(long, conflicts_with_all = ["no_wait", "yes"])]
+    cancel: bool,
+    /// Return once the probe is queued instead of waiting for the report.
+    #[arg(long)]
+    no_wait: bool,
+    #[arg(long)]
+    yes: bool,
+}
+
+/// The probe as returned by `power.fast_charge_probe_*`.
+#[derive(Debug, Deserialize)]
+struct CliFastChargeProbe {
+    state: String,
+    family: Option<ActiveProtocol>,
+    error: Option<FastChargeProbeError>,
+    step_count: usize,
+    results: Vec<FastChargeProbeResult>,
+}
+
+async fn handle_fast_charge_probe(
+    client: &Client,
+    devd: &DevdClient,
+    args: FastChargeProbeArgs,
+    allow_interactive: bool,
+) -> anyhow::Result<Value> {
+    let run = !args.status && !args.cancel;
+    if run && !allow_interactive && !args.yes {
+        return Err(anyhow!(
+            "fast-charge probe requires --yes when --json is set"
+        ));
+    }
+    if run && allow_interactive && !args.yes {
+        confirm_fast_charge_probe()?;
+    }
+    let selector =
+        maybe_select_power_target(client, devd, args.selector, allow_interactive).await?;
+    if args.status {
+        return fetch_fast_charge_probe(client, devd, &selector).await;
+    }
+
+    let owner = next_power_owner();
+    let action = if args.cancel { "cancel" } else { "run" };
+    let started = unwrap_device_success_result(
+        request_selected(
+            client,
+            devd,
+            selector.clone(),
+            Method::POST,
+            &format!("/power/fast-charge-probe/{action}?owner={owner}"),
+            None,
+        )
+        .await?,
+    )?;
+    if args.cancel || args.no_wait {
+        return Ok(started);
+    }
+    wait_for_fast_charge_probe(client, devd, &selector).await
+}
+
+async fn fetch_fast_charge_probe(
+    client: &Client,
+    devd: &DevdClient,
+    selector: &ApiSelectorArgs,
+) -> anyhow::Result<Value> {
+    unwrap_device_success_result(
+        request_selected(
+            client,
+            devd,
+            selector.clone(),
+            Method::GET,
+            "/power/fast-charge-probe",
+            None,
+        )
+        .await?,
+    )
+}
+
+async fn wait_for_fast_charge_probe(
+    client: &Client,
+    devd: &DevdClient,
+    selector: &ApiSelectorArgs,
+) -> anyhow::Result<Value> {
+    let started_at = Instant::now();
+    loop {
+        let snapshot = fetch_fast_charge_probe(client, devd, selector).await?;
+        if snapshot.get("state").and_then(Value::as_str) != Some("running") {
+            return Ok(snapshot);
+        }
+        let step_count = snapshot
+            .get("step_count")
+            .and_then(Value::as_u64)
+            .unwrap_or(10);
+        if started_at.elapsed() >= fast_charge_probe_total_timeout(step_count) {
+            return Err(anyhow!(
+                "fast-charge probe timed out; `isolapurr power fast-charge-probe --cancel` restores the profile"
+            ));
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+}
+
+fn fast_charge_probe_total_timeout(step_count: u64) -> Duration {
+    // Matches FAST_CHARGE_PROBE_STEP_MS in the firmware core.
+    const FAST_CHARGE_PROBE_STEP_BUDGET: Duration = Duration::from_secs(10);
+    const FAST_CHARGE_PROBE_TIMEOUT_PADDING: Duration = Duration::from_secs(30);
+
+    FAST_CHARGE_PROBE_STEP_BUDGET
+        .saturating_mul(u32::try_from(step_count.max(1)).unwrap_or(u32::MAX))
+        .saturating_add(FAST_CHARGE_PROBE_TIMEOUT_PADDING)
+}
+
+fn confirm_fast_charge_probe() -> anyhow::Result<()> {
+    use std::io::IsTerminal;
+
+    if !std::io::stdin().is_terminal() {
+        return Err(anyhow!(
+            "fast-charge probe requires an interactive terminal or --yes"
+        ));
+    }
+    eprintln!("Offer one fast-charge protocol at a time on USB-C, about 10 s each.");
+    eprintln!("The attached sink renegotiates for every family; keep it plugged in.");
+    eprintln!("The saved source profile is restored when the probe ends.");
+    eprintln!("Type 'probe' to continue:");
+    let mut line = String::new();
+    std::io::stdin().read_line(&mut line)?;
+    if line.trim() != "probe" {
+        return Err(UserCancelled.into());
+    }
+    Ok(())
+}
+
+fn format_fast_charge_probe_result(result: &FastChargeProbeResult) -> String {
+    let negotiated = result
+        .negotiated
+        .map_or("none", |protocol| format_active_protocol(protocol.as_str()));
+    let voltage = result.voltage_mv.map_or_else(
+        || "-".to_string(),
+        |mv| format!("{:.2} V", f64::from(mv) / 1000.0),
+    );
+    let current = result.current_ma.map_or_else(
+        || "-".to_string(),
+        |ma| format!("{:.2} A", f64::from(ma) / 1000.0),
+    );
+    format!(
+        "{:<7} {:<4} {:<11} {:>8} {:>7}",
+        format_active_protocol(result.family.as_str()),
+        if result.supported { "yes" } else { "no" },
+        negotiated,
+        voltage,
+        current,
+    )
+}
+
+fn format_fast_charge_probe_output(output: &Value) -> String {
+    let Ok(probe) = serde_json::from_value::<CliFastChargeProbe>(output.clone()) else {
+        return format!(
+            "{}\n",
+            serde_json::to_string_pretty(output).unwrap_or_default()
+        );
+    };
+    let finished = probe.results.len();
+    let supported = probe
+        .results
+        .iter()
+        .filter(|result| result.supported)
+        .count();
+    let mut lines = vec![match (probe.state.as_str(), probe.family) {
+        ("idle", _) => "No fast-charge probe has run since boot".to_string(),
+        ("running", Some(family)) => format!(
+            "Fast-charge probe: running, offering {} ({}/{})",
+            format_active_protocol(family.as_str()),
+            finished + 1,
+            probe.step_count
+        ),
+        ("running", None) => "Fast-charge probe: queued".to_string(),
+        ("aborted", _) => format!(
+            "Fast-charge probe: aborted after {finished}/{} families: {}",
+            probe.step_count,
+            probe
+                .error
+                .map_or("unknown error", FastChargeProbeError::message)
+        ),
+        (state, _) => format!(
+            "Fast-charge probe: {state}, {supported}/{} families supported",
+            probe.step_count
+        ),
+    }];
+    if !probe.results.is_empty() {
+        lines.push(format!(
+            "{:<7} {:<4} {:<11} {:>8} {:>7}",
+            "Family", "OK", "Negotiated", "Voltage", "Current"
+        ));
+        lines.extend(probe.results.iter().map(format_fast_charge_probe_result));
+    }
+    format!("{}\n", lines.join("\n"))
+}
@@ -0,0 +1,626 @@
+// `isolapurr firmware check|update`: compare devices with the published
+// release manifest and flash a release through the devd lease/espflash path.
+
+const DEFAULT_FIRMWARE_CATALOG: &str =
+    "https://isolapurr.ivanli.cc/firmware/releases-manifest.json";
+const RELEASE_CATALOG_FILE_NAME: &str = "isolapurr-firmware-catalog.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum FirmwareChannel {
+    Stable,
+    Prerelease,
+}
+
+impl FirmwareChannel {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Stable => "stable",
+            Self::Prerelease => "prerelease",
+        }
+    }
+}
+
+/// How the target release relates to the firmware the device runs now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FirmwareUpdateAction {
+    Upgrade,
+    Reinstall,
+    Downgrade,
+    /// The running version is unknown or not comparable.
+    Install,
+}
+
+impl FirmwareUpdateAction {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Upgrade => "upgrade",
+            Self::Reinstall => "reinstall",
+            Self::Downgrade => "downgrade",
+            Self::Install => "install",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReleaseManifest {
+    #[serde(default)]
+    releases: Vec<ReleaseEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReleaseEntry {
+    tag_name: String,
+    version: String,
+    #[serde(default)]
+    published_at: Option<String>,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(default)]
+    notes: Option<String>,
+    catalog_path: String,
+    app: ReleaseAsset,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReleaseAsset {
+    artifact_id: String,
+    asset_path: String,
+    #[serde(default)]
+    sha256: Option<String>,
+}
+
+/// Where the release manifest lives: an HTTP(S) URL or a local web bundle.
+#[derive(Debug, Clone, PartialEq)]
+enum FirmwareSource {
+    Url(reqwest::Url),
+    Path(PathBuf),
+}
+
+impl FirmwareSource {
+    fn parse(value: &str) -> anyhow::Result<Self> {
+        if value.starts_with("http://") || value.starts_with("https://") {
+            let url = reqwest::Url::parse(value)
+                .with_context(|| format!("invalid firmware catalog URL `{value}`"))?;
+            return Ok(Self::Url(url));
+        }
+        Ok(Self::Path(PathBuf::from(value)))
+    }
+
+    /// Manifest asset paths (`firmware/releases/...`) are relative to the web
+    /// root, one directory above the manifest itself.
+    fn asset(&self, relative: &str) -> anyhow::Result<Self> {
+        if relative.split('/').any(|part| part == "..") {
+            return Err(anyhow!(
+                "release asset path `{relative}` leaves the web root"
+            ));
+        }
+        match self {
+            Self::Url(url) => Ok(Self::Url(url.join("../")?.join(relative)?)),
+            Self::Path(path) => {
+                let root = path
+                    .parent()
+                    .and_then(std::path::Path::parent)
+                    .unwrap_or_else(|| std::path::Path::new(".."));
+                Ok(Self::Path(root.join(relative)))
+            }
+        }
+    }
+
+    async fn read(&self, client: &Client) -> anyhow::Result<Vec<u8>> {
+        match self {
+            Self::Url(url) => {
+                let response = client
+                    .get(url.clone())
+                    .send()
+                    .await
+                    .with_context(|| format!("fetch {url}"))?
+                    .error_for_status()
+                    .with_context(|| format!("fetch {url}"))?;
+                Ok(response.bytes().await?.to_vec())
+            }
+            Self::Path(path) => fs::read(path).with_context(|| format!("read {}", path.display())),
+        }
+    }
+}
+
+impl std::fmt::Display for FirmwareSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Url(url) => write!(f, "{url}"),
+            Self::Path(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Release downloads run far longer than the 5 s device requests allow.
+fn firmware_download_client() -> anyhow::Result<Client> {
+    Client::builder()
+        .connect_timeout(Duration::from_secs(5))
+        .timeout(Duration::from_secs(120))
+        .build()
+        .context("build firmware download client")
+}
+
+async fn read_release_manifest(
+    client: &Client,
+    source: &FirmwareSource,
+) -> anyhow::Result<ReleaseManifest> {
+    let raw = source.read(client).await?;
+    serde_json::from_slice(&raw).with_context(|| format!("parse release manifest {source}"))
+}
+
+/// Releases on `channel`, newest first. The stable channel skips prereleases;
+/// the prerelease channel offers everything. Unparseable versions are dropped.
+fn channel_releases(manifest: &ReleaseManifest, channel: FirmwareChannel) -> Vec<&ReleaseEntry> {
+    let mut releases = manifest
+        .releases
+        .iter()
+        .filter(|release| channel == FirmwareChannel::Prerelease || !release.prerelease)
+        .filter(|release| compare_firmware_versions(&release.version, &release.version).is_some())
+        .collect::<Vec<_>>();
+    releases.sort_by(|left, right| {
+        compare_firmware_versions(&right.version, &left.version)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    releases
+}
+
+fn find_release<'a>(
+    releases: &[&'a ReleaseEntry],
+    requested: &str,
+) -> anyhow::Result<&'a ReleaseEntry> {
+    releases
+        .iter()
+        .find(|release| {
+            release.tag_name == requested
+                || compare_firmware_versions(&release.version, requested)
+                    == Some(std::cmp::Ordering::Equal)
+        })
+        .copied()
+        .ok_or_else(|| anyhow!("release `{requested}` is not in the catalog on this channel"))
+}
+
+/// Changelog entries for releases newer than `current` up to `target`, newest
+/// first. An unknown current version yields only the target's entry.
+fn changelog_between(
+    releases: &[&ReleaseEntry],
+    current: Option<&str>,
+    target: &ReleaseEntry,
+) -> Vec<Value> {
+    use std::cmp::Ordering;
+
+    releases
+        .iter()
+        .filter(|release| {
+            compare_firmware_versions(&release.version, &target.version)
+                .is_some_and(Ordering::is_le)
+        })
+        .filter(|release| match current {
+            Some(current) => {
+                compare_firmware_versions(&release.version, current).is_some_and(Ordering::is_gt)
+            }
+            None => release.tag_name == target.tag_name,
+        })
+        .map(|release| {
+            json!({
+                "version": release.version,
+                "tag": release.tag_name,
+                "published_at": release.published_at,
+                "prerelease": release.prerelease,
+                "notes": release.notes,
+            })
+        })
+        .collect()
+}
+
+fn firmware_check_status(current: Option<&str>, latest: Option<&ReleaseEntry>) -> &'static str {
+    use std::cmp::Ordering;
+
+    let Some(latest) = latest else {
+        return "no_release";
+    };
+    match current.and_then(|current| compare_firmware_versions(current, &latest.version)) {
+        Some(Ordering::Less) => "update_available",
+        Some(Ordering::Equal) => "up_to_date",
+        Some(Ordering::Greater) => "ahead",
+        None => "unknown_version",
+    }
+}
+
+/// Refuses anything but an upgrade unless `force` is set.
+fn firmware_update_action(
+    current: Option<&str>,
+    target: &str,
+    force: bool,
+) -> anyhow::Result<FirmwareUpdateAction> {
+    use std::cmp::Ordering;
+
+    let action = match current.and_then(|current| compare_firmware_versions(target, current)) {
+        Some(Ordering::Greater) => FirmwareUpdateAction::Upgrade,
+        Some(Ordering::Equal) => FirmwareUpdateAction::Reinstall,
+        Some(Ordering::Less) => FirmwareUpdateAction::Downgrade,
+        None => FirmwareUpdateAction::Install,
+    };
+    if force || action == FirmwareUpdateAction::Upgrade {
+        return Ok(action);
+    }
+    let current = current.unwrap_or("unknown");
+    Err(match action {
+        FirmwareUpdateAction::Reinstall => {
+            anyhow!("device already runs {target}; pass --force to flash it again")
+        }
+        FirmwareUpdateAction::Downgrade => {
+            anyhow!("refusing to downgrade {current} to {target}; pass --force")
+        }
+        FirmwareUpdateAction::Upgrade | FirmwareUpdateAction::Install => {
+            anyhow!("cannot compare device firmware `{current}` with {target}; pass --force")
+        }
+    })
+}
+
+fn status_firmware_version(status: &Value) -> Option<&str> {
+    status
+        .pointer("/device/firmware/version")
+        .and_then(Value::as_str)
+}
+
+fn status_device_id(status: &Value) -> Option<&str> {
+    status.pointer("/device/device_id").and_then(Value::as_str)
+}
+
+async fn handle_firmware(
+    client: &Client,
+    devd: &DevdClient,
+    command: FirmwareCommand,
+) -> anyhow::Result<Value> {
+    match command {
+        FirmwareCommand::Check(args) => firmware_check(client, devd, args).await,
+        FirmwareCommand::Update(args) => firmware_update(client, devd, args).await,
+    }
+}
+
+enum FirmwareCheckTarget {
+    Selected(ApiSelectorArgs),
+    /// A hub devd sees on USB that is not in the saved registry.
+    LiveUsb(String),
+}
+
+/// The explicit selector, or every saved device plus unsaved live USB hubs.
+async fn firmware_check_targets(
+    client: &Client,
+    devd: &DevdClient,
+    selector: ApiSelectorArgs,
+) -> anyhow::Result<Vec<(String, FirmwareCheckTarget)>> {
+    if !selector.is_empty() {
+        let label = selector.device_id.clone().or(selector.url.clone());
+        return Ok(vec![(
+            label.unwrap_or_default(),
+            FirmwareCheckTarget::Selected(selector),
+        )]);
+    }
+    let saved = read_hardware_registry()?.devices;
+    let saved_ports = saved
+        .iter()
+        .filter_map(|device| device.local_usb_port_path().map(str::to_string))
+        .collect::<Vec<_>>();
+    let mut targets = saved
+        .iter()
+        .map(|device| {
+            (
+                device.name.clone(),
+                FirmwareCheckTarget::Selected(ApiSelectorArgs {
+                    device_id: Some(device.id.clone()),
+                    url: None,
+                }),
+            )
+        })
+        .collect::<Vec<_>>();
+    match devd_request(client, devd, Method::POST, "/api/v1/devices/scan", None).await {
+        Ok(scan) => {
+            let live = serde_json::from_value::<Vec<DeviceRecord>>(
+                scan.get("devices").cloned().unwrap_or_else(|| json!([])),
+            )?;
+            for device in live {
+                let Some(usb) = device.usb else {
+                    continue;
+                };
+                if !saved_ports.contains(&usb.port_path) {
+                    targets.push((device.display_name, FirmwareCheckTarget::LiveUsb(device.id)));
+                }
+            }
+        }
+        Err(err) if !targets.is_empty() => {
+            eprintln!("warning: skipping live USB devices: {err:#}");
+        }
+        Err(err) => return Err(err),
+    }
+    if targets.is_empty() {
+        return Err(anyhow!(
+            "no known devices; save one with `isolapurr hardware save` or connect a hub over USB"
+        ));
+    }
+    Ok(targets)
+}
+
+async fn firmware_target_status(
+    client: &Client,
+    devd: &DevdClient,
+    target: &FirmwareCheckTarget,
+) -> anyhow::Result<Value> {
+    let status = match target {
+        FirmwareCheckTarget::Selected(selector) => {
+            request_selected(client, devd, selector.clone(), Method::GET, "/status", None).await?
+        }
+        FirmwareCheckTarget::LiveUsb(device) => {
+            devd_request(
+                client,
+                devd,
+                Method::GET,
+                &format!("/api/v1/devices/{device}/status"),
+                None,
+            )
+            .await?
+        }
+    };
+    unwrap_device_success_result(status)
+}
+
+async fn firmware_check(
+    client: &Client,
+    devd: &DevdClient,
+    args: FirmwareCheckArgs,
+) -> anyhow::Result<Value> {
+    let source = FirmwareSource::parse(&args.source.catalog)?;
+    let manifest = read_release_manifest(&firmware_download_client()?, &source).await?;
+    let releases = channel_releases(&manifest, args.source.channel);
+    let latest = releases.first().copied();
+
+    let mut devices = Vec::new();
+    for (name, target) in firmware_check_targets(client, devd, args.selector).await? {
+        let row = match firmware_target_status(client, devd, &target).await {
+            Ok(status) => {
+                let current = status_firmware_version(&status);
+                json!({
+                    "name": name,
+                    "device_id": status_device_id(&status),
+                    "current_version": current,
+                    "status": firmware_check_status(current, latest),
+                    "changelog": latest
+                        .map(|latest| changelog_between(&releases, current, latest))
+                        .unwrap_or_default(),
+                })
+            }
+            Err(err) => json!({
+                "name": name,
+                "status": "unreachable",
+                "error": format!("{err:#}"),
+            }),
+        };
+        devices.push(row);
+    }
+    Ok(json!({
+        "firmware_channel": args.source.channel.as_str(),
+        "catalog": source.to_string(),
+        "latest": latest.map(|latest| json!({"version": latest.version, "tag": latest.tag_name})),
+        "devices": devices,
+    }))
+}
+
+fn firmware_cache_root(override_dir: Option<&PathBuf>) -> anyhow::Result<PathBuf> {
+    if let Some(dir) = override_dir {
+        return Ok(dir.clone());
+    }
+    let dirs = directories::ProjectDirs::from("cc", "isolapurr", "isolapurr")
+        .ok_or_else(|| anyhow!("cannot resolve user cache directory"))?;
+    Ok(dirs.cache_dir().join("firmware"))
+}
+
+/// Downloads a release's catalog and app image into `cache_root/<tag>/` so the
+/// existing local-catalog flash path can verify and flash them. Returns the
+/// cached catalog path.
+async fn cache_release(
+    client: &Client,
+    source: &FirmwareSource,
+    release: &ReleaseEntry,
+    cache_root: &std::path::Path,
+) -> anyhow::Result<PathBuf> {
+    use sha2::Digest as _;
+
+    if release.tag_name.is_empty()
+        || release.tag_name.contains(['/', '\\'])
+        || release.tag_name.starts_with('.')
+    {
+        return Err(anyhow!(
+            "release tag `{}` is not a safe directory name",
+            release.tag_name
+        ));
+    }
+    let catalog_raw = source.asset(&release.catalog_path)?.read(client).await?;
+    let catalog: FirmwareCatalog =
+        serde_json::from_slice(&catalog_raw).context("parse release firmware catalog")?;
+    let file = catalog
+        .artifacts
+        .iter()
+        .find(|artifact| artifact.artifact_id == release.app.artifact_id)
+        .and_then(|artifact| artifact.files.iter().find(|file| file.kind == "app_bin"))
+        .ok_or_else(|| {
+            anyhow!(
+                "release catalog has no app_bin for artifact {}",
+                release.app.artifact_id
+            )
+        })?;
+    if file.path.contains(['/', '\\']) || file.path.starts_with('.') {
+        return Err(anyhow!(
+            "release catalog app path `{}` is not a file name",
+            file.path
+        ));
+    }
+
+    let image = source.asset(&release.app.asset_path)?.read(client).await?;
+    let actual = format!("{:x}", sha2::Sha256::digest(&image));
+    for expected in [release.app.sha256.as_deref(), Some(file.sha256.as_str())]
+        .into_iter()
+        .flatten()
+    {
+        if !actual.eq_ignore_ascii_case(expected) {
+            return Err(anyhow!(
+                "downloaded {} hash mismatch: expected {expected}, got {actual}",
+                release.app.asset_path
+            ));
+        }
+    }
+
+    let dir = cache_root.join(&release.tag_name);
+    fs::create_dir_all(&dir).with_context(|| format!("create {}", dir.display()))?;
+    let image_path = dir.join(&file.path);
+    fs::write(&image_path, image).with_context(|| format!("write {}", image_path.display()))?;
+    let catalog_path = dir.join(RELEASE_CATALOG_FILE_NAME);
+    fs::write(&catalog_path, catalog_raw)
+        .with_context(|| format!("write {}", catalog_path.display()))?;
+    Ok(catalog_path)
+}
+
+async fn firmware_update(
+    client: &Client,
+    devd: &DevdClient,
+    args: FirmwareUpdateArgs,
+) -> anyhow::Result<Value> {
+    let source = FirmwareSource::parse(&args.source.catalog)?;
+    let download_client = firmware_download_client()?;
+    let manifest = read_release_manifest(&download_client, &source).await?;
+    let releases = channel_releases(&manifest, args.source.channel);
+    let release = match args.version.as_deref() {
+        Some(requested) => find_release(&releases, requested)?,
+        None => releases
+            .first()
+            .copied()
+            .ok_or_else(|| anyhow!("catalog has no {} releases", args.source.channel.as_str()))?,
+    };
+
+    let device = materialize_live_usb_device(
+        client,
+        devd,
+        resolve_usb_device(&args.selector, &devd.endpoint)?,
+    )
+    .await?;
+    let status = unwrap_device_success_result(
+        devd_request(
+            client,
+            &devd.with_endpoint(device.devd.clone()),
+            Method::GET,
+            &format!("/api/v1/devices/{}/status", device.device),
+            None,
+        )
+        .await?,
+    )?;
+    let current = status_firmware_version(&status);
+    let action = firmware_update_action(current, &release.version, args.force)?;
+
+    let cache_root = firmware_cache_root(args.cache_dir.as_ref())?;
+    let catalog_path = cache_release(&download_client, &source, release, &cache_root).await?;
+    let flash = handle_flash(
+        client,
+        devd,
+        FlashArgs {
+            selector: args.selector.clone(),
+            catalog: catalog_path.clone(),
+            artifact: release.app.artifact_id.clone(),
+            real: args.real,
+            first_time: false,
+            confirm_non_project_firmware: false,
+            expected_device_id: status_device_id(&status).map(str::to_string),
+            expected_mac: None,
+        },
+    )
+    .await?;
+    Ok(json!({
+        "firmware_update": release.version,
+        "tag": release.tag_name,
+        "from_version": current,
+        "action": action.as_str(),
+        "real": args.real,
+        "catalog_path": catalog_path,
+        "changelog": changelog_between(&releases, current, release),
+        "flash": flash,
+    }))
+}
+
+fn format_firmware_changelog(out: &mut String, changelog: Option<&Value>) {
+    for entry in changelog.and_then(Value::as_array).into_iter().flatten() {
+        let version = entry.get("version").and_then(Value::as_str).unwrap_or("?");
+        let published = entry
+            .get("published_at")
+            .and_then(Value::as_str)
+            .and_then(|published| published.get(..10))
+            .unwrap_or("unpublished");
+        out.push_str(&format!("  {version} ({published})\n"));
+        for line in entry
+            .get("notes")
+            .and_then(Value::as_str)
+            .unwrap_or("No release notes.")
+            .lines()
+        {
+            out.push_str(&format!("    {line}\n"));
+        }
+    }
+}
+
+fn format_firmware_check_output(output: &Value) -> String {
+    let channel = output
+        .get("firmware_channel")
+        .and_then(Value::as_str)
+        .unwrap_or("?");
+    let mut out = match output.get("latest").filter(|latest| !latest.is_null()) {
+        Some(latest) => format!(
+            "Latest {channel} firmware: {}\n",
+            latest.get("version").and_then(Value::as_str).unwrap_or("?")
+        ),
+        None => format!("No {channel} firmware releases in the catalog\n"),
+    };
+    for device in output
+        .get("devices")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let name = device.get("name").and_then(Value::as_str).unwrap_or("?");
+        let current = device
+            .get("current_version")
+            .and_then(Value::as_str)
+            .unwrap_or("?");
+        let status = device.get("status").and_then(Value::as_str).unwrap_or("?");
+        match status {
+            "unreachable" => out.push_str(&format!(
+                "{name}: unreachable ({})\n",
+                device.get("error").and_then(Value::as_str).unwrap_or("")
+            )),
+            _ => out.push_str(&format!("{name}: {current} {}\n", status.replace('_', " "))),
+        }
+        if status == "update_available" {
+            format_firmware_changelog(&mut out, device.get("changelog"));
+        }
+    }
+    out
+}
+
+fn format_firmware_update_output(output: &Value) -> String {
+    let version = output
+        .get("firmware_update")
+        .and_then(Value::as_str)
+        .unwrap_or("?");
+    let from = output
+        .get("from_version")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown");
+    let action = output.get("action").and_then(Value::as_str).unwrap_or("?");
+    let mut out = if output.get("real").and_then(Value::as_bool) == Some(true) {
+        format!("Flashed {version} over {from} ({action})\n")
+    } else {
+        format!("Dry run: would {action} {from} -> {version}; rerun with --real to flash\n")
+    };
+    format_firmware_changelog(&mut out, output.get("changelog"));
+    out
+}
@@ -25,6 +25,85 @@ fn format_human_output(output: &Value) -> String {
         return format_power_show_output(output);
     }
 
+    if output.get("quiet_hours").is_some() && output.get("patterns").is_some() {
+        return format_sound_output(output);
+    }
+
+    if output.get("screenshot").is_some() {
+        return format_display_screenshot_output(output);
+    }
+
+    if output.get("brightness_pct").is_some() && output.get("backlight").is_some() {
+        return format_display_output(output);
+    }
+
+    if output.get("actions").is_some() && output.get("locked").is_some() {
+        return format_buttons_output(output);
+    }
+
+    if output.get("buses").is_some() && output.get("sample_uptime_ms").is_some() {
+        return format_i2c_diagnostics_output(output);
+    }
+
+    if output.get("unacknowledged").is_some() && output.get("crash").is_some() {
+        return format_crash_output(output);
+    }
+
+    if output.get("total_writes").is_some()
+        || output.get("restored").is_some()
+        || (output.get("target").is_some() && output.get("address").is_some())
+    {
+        return format_register_debug_output(output);
+    }
+
+    if output.get("records").is_some() && output.get("oldest").is_some() {
+        return format_logs_output(output);
+    }
+
+    if output.get("sessions").is_some() && output.get("capacity").is_some() {
+        return format_sessions_output(output);
+    }
+
+    if output.get("ring_len").is_some() && output.get("syslog").is_some() {
+        return format_logs_config_output(output);
+    }
+
+    if output.get("boot_state").is_some() && output.get("last_state").is_some() {
+        return format_port_power_output(output);
+    }
+
+    if output.pointer("/port_a/threshold_ma").is_some() && output.get("persisted").is_some() {
+        return format_charge_termination_output(output);
+    }
+
+    if output.get("follow").is_some() && output.get("records_read").is_some() {
+        return format_logs_follow_output(output);
+    }
+
+    if output.get("samples").is_some() && output.get("stop_reason").is_some() {
+        return format_log_output(output);
+    }
+
+    if output.get("trace_export").is_some() {
+        return format_trace_export_output(output);
+    }
+
+    if output.get("firmware_channel").is_some() {
+        return format_firmware_check_output(output);
+    }
+
+    if output.get("firmware_update").is_some() {
+        return format_firmware_update_output(output);
+    }
+
+    if output.get("plan").is_some() && output.get("cases").is_some() {
+        return format_test_run_output(output);
+    }
+
+    if output.get("step_count").is_some() && output.get("results").is_some() {
+        return format_fast_charge_probe_output(output);
+    }
+
     if output.get("dataset").is_some() && output.get("run").is_some() {
         return format_idle_bias_output(output);
     }
@@ -240,9 +319,9 @@ fn format_power_show_output(output: &Value) -> String {
     let mut rendered = String::new();
     let mut manual_high_voltage_warning = false;
     if let Some(config) = output.get("config") {
-        if let Ok(config) = serde_json::from_value::<CliPowerConfig>(config.clone()) {
+        if let Ok(config) = serde_json::from_value::<PowerConfig>(config.clone()) {
             manual_high_voltage_warning =
-                config.tps_mode == "manual" && config.manual.voltage_mv > 5000;
+                config.tps_mode == TpsMode::Manual.as_str() && config.manual.voltage_mv > 5000;
         }
         rendered.push_str("Power config\n");
         rendered.push_str(&format_power_config_output(config));
@@ -254,7 +333,11 @@ fn format_power_show_output(output: &Value) -> String {
                 serde_json::to_string_pretty(output).unwrap_or_else(|_| output.to_string())
             );
         };
-        if let Some(port_c) = ports.ports.iter().find(|port| port.port_id == "port_c") {
+        if let Some(port_c) = ports
+            .ports
+            .iter()
+            .find(|port| port.port_id == PortId::PortC)
+        {
             rendered.push('\n');
             rendered.push_str("USB-C output\n");
             rendered.push_str(&format!(
@@ -278,7 +361,7 @@ fn format_power_show_output(output: &Value) -> String {
 }
 
 fn format_power_config_output(output: &Value) -> String {
-    let Ok(config) = serde_json::from_value::<CliPowerConfig>(output.clone()) else {
+    let Ok(config) = serde_json::from_value::<PowerConfig>(output.clone()) else {
         return format!(
             "{}\n",
             serde_json::to_string_pretty(output).unwrap_or_else(|_| output.to_string())
@@ -528,9 +611,9 @@ fn format_idle_bias_output(output: &Value) -> String {
     format!("{}\n", lines.join("\n"))
 }
 
-fn format_port_telemetry(telemetry: &CliPortTelemetry) -> String {
-    match telemetry.status.as_str() {
-        "ok" => match (
+fn format_port_telemetry(telemetry: &PortTelemetry) -> String {
+    match telemetry.status {
+        TelemetryStatus::Ok => match (
             telemetry.voltage_mv,
             telemetry.current_ma,
             telemetry.power_mw,
@@ -543,9 +626,9 @@ fn format_port_telemetry(telemetry: &CliPortTelemetry) -> String {
             }
             _ => "telemetry unavailable".to_string(),
         },
-        "not_inserted" => "not inserted".to_string(),
-        "overrange" => "overrange".to_string(),
-        _ => "telemetry error".to_string(),
+        TelemetryStatus::NotInserted => "not inserted".to_string(),
+        TelemetryStatus::Overrange => "overrange".to_string(),
+        TelemetryStatus::Error => "telemetry error".to_string(),
     }
 }
 
@@ -590,31 +673,31 @@ fn format_idle_bias_run(run: &CliIdleBiasRun) -> String {
 }
 
 fn format_power_mode(mode: &str) -> &'static str {
-    match mode {
-        "auto_follow" => "Auto follow USB-C request",
-        "manual" => "Manual bench output",
-        _ => "Unknown",
+    match TpsMode::parse(mode) {
+        Some(TpsMode::AutoFollow) => "Auto follow USB-C request",
+        Some(TpsMode::Manual) => "Manual bench output",
+        None => "Unknown",
     }
 }
 
 fn format_usb_c_path_mode(mode: &str) -> &'static str {
-    match mode {
-        "default" => "automatic",
-        "disconnect" => "disconnected",
-        "force" => "forced on",
-        _ => "unknown",
+    match ManualUsbCPathMode::parse(mode) {
+        Some(ManualUsbCPathMode::Default) => "automatic",
+        Some(ManualUsbCPathMode::Disconnect) => "disconnected",
+        Some(ManualUsbCPathMode::Force) => "forced on",
+        None => "unknown",
     }
 }
 
 fn format_light_load_mode(mode: &str) -> &'static str {
-    match mode {
-        "pfm" => "PFM",
-        "fpwm" => "FPWM",
-        _ => "unknown",
+    match LightLoadMode::parse(mode) {
+        Some(LightLoadMode::Pfm) => "PFM",
+        Some(LightLoadMode::Fpwm) => "FPWM",
+        None => "unknown",
     }
 }
 
-fn format_config_protocols(capability: &CliPowerCapability) -> String {
+fn format_config_protocols(capability: &PowerCapability) -> String {
     let mut labels = Vec::new();
     append_protocol(
         &mut labels,
@@ -0,0 +1,222 @@
+struct LogWriter {
+    path: PathBuf,
+    format: LogFormatArg,
+    rotate_bytes: Option<u64>,
+    rotate_every: Option<Duration>,
+    part: u32,
+    file: io::BufWriter<fs::File>,
+    written: u64,
+    opened_at: Instant,
+    files: Vec<PathBuf>,
+}
+
+impl LogWriter {
+    fn create(args: &LogArgs) -> anyhow::Result<Self> {
+        let path = args.out.clone();
+        let file = Self::open(&path, 1)?;
+        Ok(Self {
+            format: args
+                .format
+                .unwrap_or_else(|| LogFormatArg::from_path(&path)),
+            rotate_bytes: args.rotate_mb.map(|mb| mb * 1024 * 1024),
+            rotate_every: args.rotate_every,
+            part: 1,
+            file,
+            written: 0,
+            opened_at: Instant::now(),
+            files: vec![log_part_path(&path, 1)],
+            path,
+        })
+    }
+
+    fn open(path: &std::path::Path, part: u32) -> anyhow::Result<io::BufWriter<fs::File>> {
+        let path = log_part_path(path, part);
+        let file = fs::File::create(&path)
+            .with_context(|| format!("create log file {}", path.display()))?;
+        Ok(io::BufWriter::new(file))
+    }
+
+    fn write(&mut self, sample: &LogSample) -> anyhow::Result<()> {
+        use std::io::Write as _;
+
+        let due_by_size = self.rotate_bytes.is_some_and(|limit| self.written >= limit);
+        let due_by_age = self
+            .rotate_every
+            .is_some_and(|every| self.opened_at.elapsed() >= every);
+        if self.written > 0 && (due_by_size || due_by_age) {
+            self.file.flush()?;
+            self.part += 1;
+            self.file = Self::open(&self.path, self.part)?;
+            self.files.push(log_part_path(&self.path, self.part));
+            self.written = 0;
+            self.opened_at = Instant::now();
+        }
+        let mut text = String::new();
+        match self.format {
+            LogFormatArg::Csv => {
+                if self.written == 0 {
+                    text.push_str(&log_csv_header());
+                    text.push('\n');
+                }
+                text.push_str(&log_csv_row(sample));
+            }
+            LogFormatArg::Jsonl => text.push_str(&serde_json::to_string(sample)?),
+        }
+        text.push('\n');
+        self.file.write_all(text.as_bytes())?;
+        // Flush every row so a killed run still leaves a readable file.
+        self.file.flush()?;
+        self.written += text.len() as u64;
+        Ok(())
+    }
+}
+
+fn host_unix_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+async fn fetch_log_sample(
+    client: &Client,
+    devd: &DevdClient,
+    selector: &ApiSelectorArgs,
+    started: Instant,
+) -> anyhow::Result<LogSample> {
+    let ports = fetch_ports_snapshot(client, devd, selector).await?;
+    let diagnostics = fetch_power_diagnostics(client, devd, selector).await?;
+    Ok(LogSample::new(
+        host_unix_ms(),
+        started.elapsed().as_millis() as u64,
+        &ports.ports,
+        &diagnostics,
+    ))
+}
+
+async fn handle_log(client: &Client, devd: &DevdClient, args: LogArgs) -> anyhow::Result<Value> {
+    if args.interval < LOG_MIN_INTERVAL {
+        return Err(anyhow!(
+            "--interval must be at least {} ms",
+            LOG_MIN_INTERVAL.as_millis()
+        ));
+    }
+    let selector = select_api_target_interactively(client, devd, args.selector.clone()).await?;
+    let mut writer = LogWriter::create(&args)?;
+    eprintln!(
+        "logging to {} every {} ms; Ctrl-C stops",
+        writer.files[0].display(),
+        args.interval.as_millis()
+    );
+
+    let started = Instant::now();
+    let mut next_sample = started;
+    let mut samples = 0_u64;
+    let mut outages = 0_u64;
+    let mut outage_since: Option<Instant> = None;
+    let mut energy_mwh = PortId::ALL
+        .iter()
+        .map(|port| (*port, 0.0))
+        .collect::<Vec<(PortId, f64)>>();
+    // Cleared after an outage so the gap is not integrated.
+    let mut previous: Option<LogSample> = None;
+    let mut trigger_since: Option<u64> = None;
+    let stop_reason = loop {
+        if args
+            .duration
+            .is_some_and(|limit| started.elapsed() >= limit)
+        {
+            break "duration reached".to_string();
+        }
+        match fetch_log_sample(client, devd, &selector, started).await {
+            Ok(mut sample) => {
+                if let Some(since) = outage_since.take() {
+                    eprintln!(
+                        "transport back after {:.1} s",
+                        since.elapsed().as_secs_f64()
+                    );
+                }
+                accumulate_log_energy(&mut energy_mwh, previous.as_ref(), &mut sample);
+                writer.write(&sample)?;
+                samples += 1;
+
+                let stop = log_stop_reason(&args, &sample, &energy_mwh, &mut trigger_since);
+                previous = Some(sample);
+                if let Some(reason) = stop {
+                    break reason;
+                }
+            }
+            Err(err) => {
+                if outage_since.is_none() {
+                    eprintln!("transport lost: {err:#}; retrying");
+                    outage_since = Some(Instant::now());
+                    outages += 1;
+                    previous = None;
+                    trigger_since = None;
+                }
+            }
+        }
+
+        // While the hub is away, retry at most once a second so devd rescans
+        // stay cheap; otherwise skip missed ticks instead of bursting.
+        let step = if outage_since.is_some() {
+            args.interval.max(Duration::from_secs(1))
+        } else {
+            args.interval
+        };
+        next_sample = (next_sample + step).max(Instant::now());
+        tokio::select! {
+            _ = tokio::time::sleep_until(next_sample.into()) => {}
+            _ = tokio::signal::ctrl_c() => break "interrupted".to_string(),
+        }
+    };
+    eprintln!("stopped: {stop_reason}");
+
+    Ok(json!({
+        "files": writer
+            .files
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>(),
+        "samples": samples,
+        "duration_ms": started.elapsed().as_millis() as u64,
+        "stop_reason": stop_reason,
+        "outages": outages,
+        "energy_mwh": energy_mwh
+            .iter()
+            .map(|(port, mwh)| (port.as_str().to_string(), json!(mwh)))
+            .collect::<serde_json::Map<_, _>>(),
+    }))
+}
+
+fn log_stop_reason(
+    args: &LogArgs,
+    sample: &LogSample,
+    energy_mwh: &[(PortId, f64)],
+    trigger_since: &mut Option<u64>,
+) -> Option<String> {
+    if let Some(target) = args.energy_mwh
+        && energy_mwh
+            .iter()
+            .any(|(port, mwh)| *port == args.energy_port && *mwh >= target)
+    {
+        return Some(format!(
+            "{} reached {target} mWh",
+            args.energy_port.as_str()
+        ));
+    }
+    let trigger = args.stop_when?;
+    if !trigger.matches(sample) {
+        *trigger_since = None;
+        return None;
+    }
+    let since = *trigger_since.get_or_insert(sample.elapsed_ms);
+    let held_ms = sample.elapsed_ms - since;
+    (held_ms >= args.stop_hold.as_millis() as u64).then(|| {
+        format!(
+            "{} held for {:.1} s",
+            trigger.label(),
+            held_ms as f64 / 1000.0
+        )
+    })
+}
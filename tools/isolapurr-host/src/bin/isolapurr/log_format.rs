@@ -0,0 +1,380 @@
+// `isolapurr log`: sample rows, CSV/JSON Lines encoding, stop triggers, and
+// rotated file names.
+
+const LOG_MIN_INTERVAL: Duration = Duration::from_millis(20);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum LogFormatArg {
+    Csv,
+    Jsonl,
+}
+
+impl LogFormatArg {
+    fn from_path(path: &std::path::Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("jsonl" | "ndjson") => Self::Jsonl,
+            _ => Self::Csv,
+        }
+    }
+}
+
+/// Parses `250ms`, `1.5s`, `10m`, or `2h`.
+fn parse_log_duration(raw: &str) -> Result<Duration, String> {
+    let raw = raw.trim();
+    let split = raw
+        .find(|ch: char| !(ch.is_ascii_digit() || ch == '.'))
+        .ok_or_else(|| format!("`{raw}` needs a unit: ms, s, m, or h"))?;
+    let (number, unit) = raw.split_at(split);
+    let value: f64 = number
+        .parse()
+        .map_err(|_| format!("`{raw}` is not a duration"))?;
+    let seconds = match unit {
+        "ms" => value / 1000.0,
+        "s" => value,
+        "m" => value * 60.0,
+        "h" => value * 3600.0,
+        _ => {
+            return Err(format!(
+                "unknown duration unit `{unit}`; use ms, s, m, or h"
+            ));
+        }
+    };
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogField {
+    VoltageMv,
+    CurrentMa,
+    PowerMw,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogComparison {
+    Below,
+    Above,
+}
+
+/// `<port>.<field><op><value>`, e.g. `port_c.current_ma<50`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LogTrigger {
+    port: PortId,
+    field: LogField,
+    comparison: LogComparison,
+    value: u32,
+}
+
+impl LogTrigger {
+    fn label(&self) -> String {
+        let field = match self.field {
+            LogField::VoltageMv => "voltage_mv",
+            LogField::CurrentMa => "current_ma",
+            LogField::PowerMw => "power_mw",
+        };
+        let comparison = match self.comparison {
+            LogComparison::Below => '<',
+            LogComparison::Above => '>',
+        };
+        format!("{}.{field}{comparison}{}", self.port.as_str(), self.value)
+    }
+
+    /// A port without an `ok` reading never matches.
+    fn matches(&self, sample: &LogSample) -> bool {
+        let Some(port) = sample.ports.iter().find(|port| port.port == self.port) else {
+            return false;
+        };
+        let reading = match self.field {
+            LogField::VoltageMv => port.voltage_mv,
+            LogField::CurrentMa => port.current_ma,
+            LogField::PowerMw => port.power_mw,
+        };
+        match (port.status == TelemetryStatus::Ok, reading) {
+            (true, Some(reading)) => match self.comparison {
+                LogComparison::Below => reading < self.value,
+                LogComparison::Above => reading > self.value,
+            },
+            _ => false,
+        }
+    }
+}
+
+fn parse_log_trigger(raw: &str) -> Result<LogTrigger, String> {
+    let (lhs, comparison, value) = if let Some((lhs, value)) = raw.split_once('<') {
+        (lhs, LogComparison::Below, value)
+    } else if let Some((lhs, value)) = raw.split_once('>') {
+        (lhs, LogComparison::Above, value)
+    } else {
+        return Err(format!(
+            "`{raw}` needs `<` or `>`, e.g. port_c.current_ma<50"
+        ));
+    };
+    let (port, field) = lhs
+        .trim()
+        .split_once('.')
+        .ok_or_else(|| format!("`{lhs}` should be <port>.<field>"))?;
+    let port = PortId::parse(port).ok_or_else(|| format!("unknown port `{port}`"))?;
+    let field = match field {
+        "voltage_mv" => LogField::VoltageMv,
+        "current_ma" => LogField::CurrentMa,
+        "power_mw" => LogField::PowerMw,
+        _ => {
+            return Err(format!(
+                "unknown field `{field}`; use voltage_mv, current_ma, or power_mw"
+            ));
+        }
+    };
+    let value = value
+        .trim()
+        .parse()
+        .map_err(|_| format!("`{value}` is not a whole number"))?;
+    Ok(LogTrigger {
+        port,
+        field,
+        comparison,
+        value,
+    })
+}
+
+fn parse_log_port(raw: &str) -> Result<PortId, String> {
+    PortId::parse(raw).ok_or_else(|| format!("unknown port `{raw}`; use port_a or port_c"))
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct LogPortSample {
+    port: PortId,
+    power_enabled: bool,
+    status: TelemetryStatus,
+    voltage_mv: Option<u32>,
+    current_ma: Option<u32>,
+    power_mw: Option<u32>,
+    /// Since logging started, excluding transport outages.
+    energy_mwh: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct LogSample {
+    host_unix_ms: u64,
+    elapsed_ms: u64,
+    ports: Vec<LogPortSample>,
+    pd_protocol: Option<String>,
+    pd_request_mv: Option<u32>,
+    pd_request_ma: Option<u32>,
+    tps_output_enabled: Option<bool>,
+    tps_setpoint_mv: Option<u32>,
+    tps_iout_limit_ma: Option<u32>,
+    thermal_state: String,
+    thermal_reason: String,
+    mcu_temp_deci_c: Option<i32>,
+    tmp112_temp_deci_c: Option<i32>,
+    thermal_power_watts: u8,
+}
+
+impl LogSample {
+    fn new(
+        host_unix_ms: u64,
+        elapsed_ms: u64,
+        ports: &[Port],
+        diagnostics: &CliPowerDiagnostics,
+    ) -> Self {
+        let thermal = &diagnostics.thermal;
+        Self {
+            host_unix_ms,
+            elapsed_ms,
+            ports: ports
+                .iter()
+                .map(|port| LogPortSample {
+                    port: port.port_id,
+                    power_enabled: port.state.power_enabled,
+                    status: port.telemetry.status,
+                    voltage_mv: port.telemetry.voltage_mv,
+                    current_ma: port.telemetry.current_ma,
+                    power_mw: port.telemetry.power_mw,
+                    energy_mwh: 0.0,
+                })
+                .collect(),
+            pd_protocol: diagnostics.active_protocol.clone(),
+            pd_request_mv: diagnostics.sw2303_request.mv,
+            pd_request_ma: diagnostics.sw2303_request.ma,
+            tps_output_enabled: diagnostics.tps_setpoint.output_enabled,
+            tps_setpoint_mv: diagnostics.tps_setpoint.mv,
+            tps_iout_limit_ma: diagnostics.tps_setpoint.iout_limit_ma,
+            thermal_state: thermal.state.clone(),
+            thermal_reason: thermal.reason.clone(),
+            mcu_temp_deci_c: thermal.sensors.mcu.temperature_deci_c,
+            tmp112_temp_deci_c: thermal.sensors.tmp112.temperature_deci_c,
+            thermal_power_watts: thermal.effective_power_watts,
+        }
+    }
+
+    /// Power for the energy integral; anything but an `ok` reading counts as 0 mW.
+    fn power_mw(&self, port_id: PortId) -> u32 {
+        self.ports
+            .iter()
+            .find(|port| port.port == port_id)
+            .filter(|port| port.status == TelemetryStatus::Ok)
+            .and_then(|port| port.power_mw)
+            .unwrap_or(0)
+    }
+}
+
+/// Adds the energy since `previous` to `totals` and stamps the running totals
+/// on `sample`. Without a previous sample (start, or after an outage) nothing
+/// is added.
+fn accumulate_log_energy(
+    totals: &mut [(PortId, f64)],
+    previous: Option<&LogSample>,
+    sample: &mut LogSample,
+) {
+    for (port_id, total) in totals.iter_mut() {
+        if let Some(previous) = previous {
+            *total += energy_step_mwh(
+                (previous.elapsed_ms, previous.power_mw(*port_id)),
+                (sample.elapsed_ms, sample.power_mw(*port_id)),
+            );
+        }
+        if let Some(port) = sample.ports.iter_mut().find(|port| port.port == *port_id) {
+            port.energy_mwh = *total;
+        }
+    }
+}
+
+const LOG_CSV_PORT_COLUMNS: [&str; 6] = [
+    "power_enabled",
+    "status",
+    "voltage_mv",
+    "current_ma",
+    "power_mw",
+    "energy_mwh",
+];
+
+fn log_csv_header() -> String {
+    let mut columns = vec!["host_unix_ms".to_string(), "elapsed_ms".to_string()];
+    for port in PortId::ALL {
+        columns.extend(
+            LOG_CSV_PORT_COLUMNS
+                .iter()
+                .map(|column| format!("{}_{column}", port.as_str())),
+        );
+    }
+    columns.extend(
+        [
+            "pd_protocol",
+            "pd_request_mv",
+            "pd_request_ma",
+            "tps_output_enabled",
+            "tps_setpoint_mv",
+            "tps_iout_limit_ma",
+            "thermal_state",
+            "thermal_reason",
+            "mcu_temp_deci_c",
+            "tmp112_temp_deci_c",
+            "thermal_power_watts",
+        ]
+        .map(str::to_string),
+    );
+    columns.join(",")
+}
+
+fn log_csv_row(sample: &LogSample) -> String {
+    fn cell<T: ToString>(value: Option<T>) -> String {
+        value.map(|value| value.to_string()).unwrap_or_default()
+    }
+
+    let mut cells = vec![
+        sample.host_unix_ms.to_string(),
+        sample.elapsed_ms.to_string(),
+    ];
+    for port_id in PortId::ALL {
+        match sample.ports.iter().find(|port| port.port == *port_id) {
+            Some(port) => cells.extend([
+                port.power_enabled.to_string(),
+                port.status.as_str().to_string(),
+                cell(port.voltage_mv),
+                cell(port.current_ma),
+                cell(port.power_mw),
+                format!("{:.3}", port.energy_mwh),
+            ]),
+            None => cells.extend(std::iter::repeat_n(
+                String::new(),
+                LOG_CSV_PORT_COLUMNS.len(),
+            )),
+        }
+    }
+    cells.extend([
+        csv_field(sample.pd_protocol.as_deref().unwrap_or("")),
+        cell(sample.pd_request_mv),
+        cell(sample.pd_request_ma),
+        cell(sample.tps_output_enabled),
+        cell(sample.tps_setpoint_mv),
+        cell(sample.tps_iout_limit_ma),
+        csv_field(&sample.thermal_state),
+        csv_field(&sample.thermal_reason),
+        cell(sample.mcu_temp_deci_c),
+        cell(sample.tmp112_temp_deci_c),
+        sample.thermal_power_watts.to_string(),
+    ]);
+    cells.join(",")
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Part 1 is `path` itself; later parts insert the part number before the
+/// extension: `run.csv`, `run.2.csv`, `run.3.csv`.
+fn log_part_path(path: &std::path::Path, part: u32) -> PathBuf {
+    if part <= 1 {
+        return path.to_path_buf();
+    }
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let name = match path.extension() {
+        Some(ext) => format!("{stem}.{part}.{}", ext.to_string_lossy()),
+        None => format!("{stem}.{part}"),
+    };
+    path.with_file_name(name)
+}
+
+fn format_log_output(output: &Value) -> String {
+    let files = output
+        .get("files")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect::<Vec<_>>();
+    let mut lines = vec![
+        format!(
+            "Logged {} samples over {:.1} s ({})",
+            output.get("samples").and_then(Value::as_u64).unwrap_or(0),
+            output
+                .get("duration_ms")
+                .and_then(Value::as_u64)
+                .unwrap_or(0) as f64
+                / 1000.0,
+            output
+                .get("stop_reason")
+                .and_then(Value::as_str)
+                .unwrap_or("stopped")
+        ),
+        format!("Files: {}", files.join(", ")),
+    ];
+    if let Some(energy) = output.get("energy_mwh").and_then(Value::as_object) {
+        let parts = energy
+            .iter()
+            .map(|(port, mwh)| format!("{port} {:.3} mWh", mwh.as_f64().unwrap_or(0.0)))
+            .collect::<Vec<_>>();
+        lines.push(format!("Energy: {}", parts.join(", ")));
+    }
+    let outages = output.get("outages").and_then(Value::as_u64).unwrap_or(0);
+    if outages > 0 {
+        lines.push(format!("Transport outages: {outages}"));
+    }
+    lines.join("\n") + "\n"
+}
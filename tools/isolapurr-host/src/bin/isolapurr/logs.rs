@@ -0,0 +1,308 @@
+#[derive(Debug, clap::Args)]
+struct LogsArgs {
+    #[command(flatten)]
+    selector: ApiSelectorArgs,
+    /// First sequence number to read; 0 starts at the oldest record the hub still holds.
+    #[arg(long, default_value_t = 0)]
+    since: u32,
+    /// Keep polling for new records until Ctrl-C.
+    #[arg(long)]
+    follow: bool,
+    #[arg(long, value_parser = parse_log_duration, default_value = "1s")]
+    interval: Duration,
+    #[command(subcommand)]
+    command: Option<LogsCommand>,
+}
+
+#[derive(Debug, Subcommand)]
+enum LogsCommand {
+    #[command(
+        about = "Show or change the ring level and the syslog target",
+        after_help = "Without flags the current settings are shown. Settings persist across reboots."
+    )]
+    Config {
+        #[arg(long, value_enum)]
+        level: Option<LogLevelArg>,
+        /// Forward records as RFC 5424 over UDP to host[:port]; the port defaults to 514.
+        #[arg(long, value_parser = parse_syslog_target, conflicts_with = "no_syslog")]
+        syslog: Option<SyslogTargetArg>,
+        #[arg(long)]
+        no_syslog: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum LogLevelArg {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl LogLevelArg {
+    const fn as_config_value(self) -> &'static str {
+        match self {
+            Self::Error => LogLevel::Error.as_str(),
+            Self::Warn => LogLevel::Warn.as_str(),
+            Self::Info => LogLevel::Info.as_str(),
+            Self::Debug => LogLevel::Debug.as_str(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SyslogTargetArg {
+    host: std::net::Ipv4Addr,
+    port: u16,
+}
+
+const SYSLOG_DEFAULT_PORT: u16 = 514;
+
+fn parse_syslog_target(raw: &str) -> Result<SyslogTargetArg, String> {
+    let (host, port) = match raw.split_once(':') {
+        Some((host, port)) => {
+            let port = port
+                .parse::<u16>()
+                .ok()
+                .filter(|port| *port != 0)
+                .ok_or_else(|| format!("invalid syslog port `{port}`"))?;
+            (host, port)
+        }
+        None => (raw, SYSLOG_DEFAULT_PORT),
+    };
+    let host = host
+        .parse()
+        .map_err(|_| format!("syslog host must be an IPv4 address, got `{host}`"))?;
+    Ok(SyslogTargetArg { host, port })
+}
+
+fn logs_config_body(
+    level: Option<LogLevelArg>,
+    syslog: Option<SyslogTargetArg>,
+    no_syslog: bool,
+) -> Option<Value> {
+    let mut body = serde_json::Map::new();
+    if let Some(level) = level {
+        body.insert("level".to_string(), json!(level.as_config_value()));
+    }
+    if let Some(target) = syslog {
+        body.insert(
+            "syslog".to_string(),
+            json!({"host": target.host.to_string(), "port": target.port}),
+        );
+    } else if no_syslog {
+        body.insert("syslog".to_string(), Value::Null);
+    }
+    (!body.is_empty()).then_some(Value::Object(body))
+}
+
+async fn handle_logs(
+    client: &Client,
+    devd: &DevdClient,
+    args: LogsArgs,
+    human: bool,
+) -> anyhow::Result<Value> {
+    if let Some(LogsCommand::Config {
+        level,
+        syslog,
+        no_syslog,
+    }) = args.command
+    {
+        let value = match logs_config_body(level, syslog, no_syslog) {
+            Some(body) => {
+                request_selected(
+                    client,
+                    devd,
+                    args.selector,
+                    Method::PUT,
+                    "/logs/config",
+                    Some(body),
+                )
+                .await?
+            }
+            None => {
+                request_selected(
+                    client,
+                    devd,
+                    args.selector,
+                    Method::GET,
+                    "/logs/config",
+                    None,
+                )
+                .await?
+            }
+        };
+        return unwrap_device_success_result(value);
+    }
+
+    if !args.follow {
+        return fetch_logs_page(client, devd, args.selector, args.since).await;
+    }
+
+    let selector = select_api_target_interactively(client, devd, args.selector.clone()).await?;
+    eprintln!("following device log; Ctrl-C stops");
+    let mut since = args.since;
+    let mut records_read = 0_u64;
+    let mut dropped = 0_u64;
+    let mut transport_lost = false;
+    let stop_reason = loop {
+        let mut more = false;
+        match fetch_logs_page(client, devd, selector.clone(), since).await {
+            Ok(page) => {
+                if std::mem::take(&mut transport_lost) {
+                    eprintln!("transport back");
+                }
+                let next = page.get("next").and_then(Value::as_u64).unwrap_or(0) as u32;
+                if next < since {
+                    // Sequence numbers restart at 1 after a reboot.
+                    eprintln!("device log restarted; reading from its oldest record");
+                    since = 0;
+                    continue;
+                }
+                let page_dropped = page.get("dropped").and_then(Value::as_u64).unwrap_or(0);
+                if page_dropped > 0 {
+                    eprintln!("{page_dropped} record(s) overwritten before they were read");
+                    dropped += page_dropped;
+                }
+                for record in page
+                    .get("records")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                {
+                    if human {
+                        println!("{}", format_log_record(record));
+                    } else {
+                        println!("{}", serde_json::to_string(record)?);
+                    }
+                    records_read += 1;
+                }
+                since = next;
+                more = page.get("more").and_then(Value::as_bool).unwrap_or(false);
+            }
+            Err(err) => {
+                if !transport_lost {
+                    eprintln!("transport lost: {err:#}; retrying");
+                    transport_lost = true;
+                }
+            }
+        }
+        if more {
+            continue;
+        }
+        let wait = if transport_lost {
+            args.interval.max(Duration::from_secs(1))
+        } else {
+            args.interval
+        };
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = tokio::signal::ctrl_c() => break "interrupted",
+        }
+    };
+
+    Ok(json!({
+        "follow": true,
+        "records_read": records_read,
+        "dropped": dropped,
+        "next": since,
+        "stop_reason": stop_reason,
+    }))
+}
+
+async fn fetch_logs_page(
+    client: &Client,
+    devd: &DevdClient,
+    selector: ApiSelectorArgs,
+    since: u32,
+) -> anyhow::Result<Value> {
+    let value = request_selected(
+        client,
+        devd,
+        selector,
+        Method::GET,
+        &format!("/logs?since={since}"),
+        None,
+    )
+    .await?;
+    unwrap_device_success_result(value)
+}
+
+fn format_log_record(record: &Value) -> String {
+    format!(
+        "#{:<6} {:>10.3} s  {:<5}  {}",
+        record.get("seq").and_then(Value::as_u64).unwrap_or(0),
+        record.get("uptime_ms").and_then(Value::as_u64).unwrap_or(0) as f64 / 1000.0,
+        record
+            .get("level")
+            .and_then(Value::as_str)
+            .unwrap_or("?")
+            .to_ascii_uppercase(),
+        record.get("message").and_then(Value::as_str).unwrap_or(""),
+    )
+}
+
+fn format_logs_output(output: &Value) -> String {
+    let mut lines: Vec<String> = output
+        .get("records")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(format_log_record)
+        .collect();
+    if lines.is_empty() {
+        lines.push("No log records".to_string());
+    }
+    if let Some(dropped) = output
+        .get("dropped")
+        .and_then(Value::as_u64)
+        .filter(|dropped| *dropped > 0)
+    {
+        lines.push(format!(
+            "{dropped} record(s) overwritten before they were read"
+        ));
+    }
+    let next = output.get("next").and_then(Value::as_u64).unwrap_or(0);
+    if output.get("more").and_then(Value::as_bool) == Some(true) {
+        lines.push(format!("More records waiting: pass --since {next}"));
+    } else {
+        lines.push(format!("Next: --since {next}"));
+    }
+    format!("{}\n", lines.join("\n"))
+}
+
+fn format_logs_config_output(output: &Value) -> String {
+    let syslog = match output.get("syslog").filter(|syslog| !syslog.is_null()) {
+        Some(target) => format!(
+            "{}:{}",
+            target.get("host").and_then(Value::as_str).unwrap_or("?"),
+            target.get("port").and_then(Value::as_u64).unwrap_or(0)
+        ),
+        None => "off".to_string(),
+    };
+    format!(
+        "Level: {} ({} records kept)\nSyslog: {syslog}\nPersisted: {}\n",
+        output.get("level").and_then(Value::as_str).unwrap_or("?"),
+        output.get("ring_len").and_then(Value::as_u64).unwrap_or(0),
+        output
+            .get("persisted")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+    )
+}
+
+fn format_logs_follow_output(output: &Value) -> String {
+    format!(
+        "stopped: {}; {} record(s) read, {} dropped; resume with --since {}\n",
+        output
+            .get("stop_reason")
+            .and_then(Value::as_str)
+            .unwrap_or("?"),
+        output
+            .get("records_read")
+            .and_then(Value::as_u64)
+            .unwrap_or(0),
+        output.get("dropped").and_then(Value::as_u64).unwrap_or(0),
+        output.get("next").and_then(Value::as_u64).unwrap_or(0),
+    )
+}
@@ -229,6 +229,7 @@ fn map_devd_ipc_endpoint(
             "device.power.runtime_set"
         }
         ("GET", "power/idle-bias") => "device.power.idle_bias_get",
+        ("GET", "power/fast-charge-probe") => "device.power.fast_charge_probe_get",
         ("PUT", "power/config") => {
             let config = body.ok_or_else(|| anyhow!("power config body is required"))?;
             params_map.insert("config".to_string(), config);
@@ -287,6 +288,20 @@ fn map_devd_ipc_endpoint(
             }
             "device.power.idle_bias_clear"
         }
+        ("POST", "power/fast-charge-probe/run" | "power/fast-charge-probe/cancel") => {
+            if let Some(owner) = query
+                .split('&')
+                .find_map(|part| part.strip_prefix("owner="))
+                .and_then(|owner| owner.parse::<u32>().ok())
+            {
+                params_map.insert("owner".to_string(), json!(owner));
+            }
+            if suffix.ends_with("/run") {
+                "device.power.fast_charge_probe_run"
+            } else {
+                "device.power.fast_charge_probe_cancel"
+            }
+        }
         ("POST", "power/config/lock") => {
             let owner = query
                 .split('&')
@@ -332,6 +347,103 @@ fn map_devd_ipc_endpoint(
             "device.reset"
         }
         ("GET", "diagnostics") => "device.diagnostics",
+        ("GET", "display") => "device.display.get",
+        ("PUT", "display") => {
+            merge_body(params_map, body);
+            "device.display.set"
+        }
+        ("POST", "display/defaults") => "device.display.defaults",
+        ("GET", "buttons") => "device.buttons.get",
+        ("PUT", "buttons") => {
+            merge_body(params_map, body);
+            "device.buttons.set"
+        }
+        ("POST", "buttons/defaults") => "device.buttons.defaults",
+        ("GET", "diagnostics/i2c") => "device.i2c.diagnostics_get",
+        ("POST", "diagnostics/i2c/scan") => "device.i2c.scan",
+        ("POST", "diagnostics/i2c/recover") => {
+            let bus = query
+                .split('&')
+                .find_map(|part| part.strip_prefix("bus="))
+                .ok_or_else(|| anyhow!("bus query is required"))?;
+            params_map.insert("bus".to_string(), json!(bus));
+            if let Some(owner) = query
+                .split('&')
+                .find_map(|part| part.strip_prefix("owner="))
+                .and_then(|owner| owner.parse::<u32>().ok())
+            {
+                params_map.insert("owner".to_string(), json!(owner));
+            }
+            "device.i2c.recover"
+        }
+        ("GET", "diagnostics/crash") => "device.diagnostics.crash",
+        ("POST", "diagnostics/crash/ack") => "device.diagnostics.crash_ack",
+        ("GET", "logs") => {
+            if let Some(since) = query
+                .split('&')
+                .find_map(|part| part.strip_prefix("since="))
+            {
+                let since = since
+                    .parse::<u32>()
+                    .context("since must be a log sequence number")?;
+                params_map.insert("since".to_string(), json!(since));
+            }
+            "device.logs.tail"
+        }
+        ("GET", "sessions") => "device.sessions.list",
+        ("GET", "logs/config") => "device.logs.config_get",
+        ("PUT", "logs/config") => {
+            merge_body(params_map, body);
+            "device.logs.config_set"
+        }
+        ("GET", "ports/power-policy") => "device.ports.power_policy_get",
+        ("PUT", "ports/power-policy") => {
+            merge_body(params_map, body);
+            "device.ports.power_policy_set"
+        }
+        ("GET", "ports/charge-termination") => "device.ports.charge_termination_get",
+        ("PUT", "ports/charge-termination") => {
+            merge_body(params_map, body);
+            "device.ports.charge_termination_set"
+        }
+        ("GET", "debug") => "device.debug.status",
+        ("POST", "debug/unlock") => {
+            merge_body(params_map, body);
+            "device.debug.unlock"
+        }
+        ("POST", "debug/exit") => "device.debug.exit",
+        ("POST", "debug/registers/read") => {
+            merge_body(params_map, body);
+            "device.debug.reg_read"
+        }
+        ("POST", "debug/registers/write") => {
+            merge_body(params_map, body);
+            "device.debug.reg_write"
+        }
+        ("POST", "debug/registers/dump") => {
+            merge_body(params_map, body);
+            "device.debug.reg_dump"
+        }
+        ("GET", "display/screenshot") => "device.display.screenshot",
+        ("GET", "sound") => "device.sound.get",
+        ("PUT", "sound") => {
+            merge_body(params_map, body);
+            "device.sound.set"
+        }
+        ("POST", "sound/defaults") => "device.sound.defaults",
+        ("POST", _) if suffix.starts_with("sound/patterns/") && suffix.ends_with("/clear") => {
+            let slot = suffix
+                .trim_start_matches("sound/patterns/")
+                .trim_end_matches("/clear");
+            params_map.insert("slot".to_string(), json!(slot));
+            "device.sound.pattern_clear"
+        }
+        ("PUT", _) if suffix.starts_with("sound/patterns/") => {
+            let slot = suffix.trim_start_matches("sound/patterns/");
+            merge_body(params_map, body);
+            params_map.insert("slot".to_string(), json!(slot));
+            "device.sound.pattern_set"
+        }
         ("POST", _) if suffix.starts_with("ports/") && suffix.ends_with("/replug") => {
             let port = suffix
                 .trim_start_matches("ports/")
@@ -339,6 +451,18 @@ fn map_devd_ipc_endpoint(
             params_map.insert("port".to_string(), json!(port));
             "device.port.replug"
         }
+        ("POST", _) if suffix.starts_with("ports/") && suffix.contains("/data") => {
+            let port = suffix
+                .trim_start_matches("ports/")
+                .trim_end_matches("/data");
+            let mode = query
+                .split('&')
+                .find_map(|part| part.strip_prefix("mode="))
+                .ok_or_else(|| anyhow!("mode query is required"))?;
+            params_map.insert("port".to_string(), json!(port));
+            params_map.insert("mode".to_string(), json!(mode));
+            "device.port.data_set"
+        }
         ("POST", _) if suffix.starts_with("ports/") && suffix.contains("/power") => {
             let port = suffix
                 .trim_start_matches("ports/")
@@ -474,12 +598,52 @@ fn map_http_endpoint(
         ("POST", _) if suffix.starts_with("/power/idle-bias/clear?owner=") => {
             (Method::POST, format!("/api/v1{suffix}"), body)
         }
+        ("GET", "/power/fast-charge-probe") => {
+            (method, "/api/v1/power/fast-charge-probe".to_string(), body)
+        }
+        ("POST", _)
+            if suffix.starts_with("/power/fast-charge-probe/run?owner=")
+                || suffix.starts_with("/power/fast-charge-probe/cancel?owner=") =>
+        {
+            (Method::POST, format!("/api/v1{suffix}"), body)
+        }
         ("POST", _) if suffix.starts_with("/power/config/lock?owner=") => {
             (Method::POST, format!("/api/v1{suffix}"), body)
         }
         ("POST", _) if suffix.starts_with("/power/config/release?owner=") => {
             (Method::POST, format!("/api/v1{suffix}"), body)
         }
+        ("GET" | "PUT", "/display") => (method, "/api/v1/display".to_string(), body),
+        ("POST", "/display/defaults") => (method, "/api/v1/display/defaults".to_string(), body),
+        ("GET" | "PUT", "/buttons") => (method, "/api/v1/buttons".to_string(), body),
+        ("POST", "/buttons/defaults") => (method, "/api/v1/buttons/defaults".to_string(), body),
+        ("GET", "/diagnostics/i2c") => (method, "/api/v1/diagnostics/i2c".to_string(), body),
+        ("POST", "/diagnostics/i2c/scan") => {
+            (method, "/api/v1/diagnostics/i2c/scan".to_string(), body)
+        }
+        ("POST", _) if suffix.starts_with("/diagnostics/i2c/recover?") => {
+            (Method::POST, format!("/api/v1{suffix}"), body)
+        }
+        ("GET", "/diagnostics/crash") => (method, "/api/v1/diagnostics/crash".to_string(), body),
+        ("POST", "/diagnostics/crash/ack") => {
+            (method, "/api/v1/diagnostics/crash/ack".to_string(), body)
+        }
+        ("GET", _) if suffix == "/logs" || suffix.starts_with("/logs?") => {
+            (method, format!("/api/v1{suffix}"), body)
+        }
+        ("GET", "/sessions") => (method, "/api/v1/sessions".to_string(), body),
+        ("GET" | "PUT", "/logs/config") => (method, "/api/v1/logs/config".to_string(), body),
+        ("GET" | "PUT", "/ports/power-policy") => {
+            (method, "/api/v1/ports/power-policy".to_string(), body)
+        }
+        ("GET" | "PUT", "/ports/charge-termination") => {
+            (method, "/api/v1/ports/charge-termination".to_string(), body)
+        }
+        ("GET" | "PUT", "/sound") => (method, "/api/v1/sound".to_string(), body),
+        ("POST", "/sound/defaults") => (method, "/api/v1/sound/defaults".to_string(), body),
+        ("PUT" | "POST", _) if suffix.starts_with("/sound/patterns/") => {
+            (method, format!("/api/v1{suffix}"), body)
+        }
         ("POST", "/hub/route") => {
             let route = body
                 .as_ref()
@@ -513,6 +677,9 @@ fn map_http_endpoint(
                 None,
             )
         }
+        ("POST", _) if suffix.starts_with("/ports/") && suffix.contains("/data?mode=") => {
+            (Method::POST, format!("/api/v1{suffix}"), None)
+        }
         _ => (method, suffix.to_string(), body),
     };
     Ok(mapped)
@@ -548,6 +715,17 @@ async fn handle_ports(
             )
             .await
         }
+        Some(PortsCommand::Data { port, mode }) => {
+            request_selected(
+                client,
+                devd,
+                selector,
+                Method::POST,
+                &format!("/ports/{port}/data?mode={}", mode.as_wire()),
+                None,
+            )
+            .await
+        }
         Some(PortsCommand::Route { route }) => {
             request_selected(
                 client,
@@ -559,7 +737,87 @@ async fn handle_ports(
             )
             .await
         }
+        Some(PortsCommand::PowerPolicy { usb_a, usb_c }) => {
+            match port_power_policy_body(usb_a, usb_c) {
+                Some(body) => {
+                    request_selected(
+                        client,
+                        devd,
+                        selector,
+                        Method::PUT,
+                        "/ports/power-policy",
+                        Some(body),
+                    )
+                    .await
+                }
+                None => {
+                    request_selected(
+                        client,
+                        devd,
+                        selector,
+                        Method::GET,
+                        "/ports/power-policy",
+                        None,
+                    )
+                    .await
+                }
+            }
+        }
+        Some(PortsCommand::ChargeTermination(args)) => {
+            let body = charge_termination_body(&args)?;
+            let method = if body.is_some() {
+                Method::PUT
+            } else {
+                Method::GET
+            };
+            request_selected(
+                client,
+                devd,
+                selector,
+                method,
+                "/ports/charge-termination",
+                body,
+            )
+            .await
+        }
+    }
+}
+
+fn port_power_policy_body(
+    usb_a: Option<PortPowerPolicyArg>,
+    usb_c: Option<PortPowerPolicyArg>,
+) -> Option<Value> {
+    let mut body = serde_json::Map::new();
+    for (key, policy) in [("port_a", usb_a), ("port_c", usb_c)] {
+        if let Some(policy) = policy {
+            body.insert(key.to_string(), json!(policy.as_config_value()));
+        }
+    }
+    (!body.is_empty()).then_some(Value::Object(body))
+}
+
+fn format_port_power_output(output: &Value) -> String {
+    let mut text = String::new();
+    for (label, key) in [("USB-A", "port_a"), ("USB-C", "port_c")] {
+        let policy = output.get(key).and_then(Value::as_str).unwrap_or("?");
+        let boot_on = output
+            .get("boot_state")
+            .and_then(|state| state.get(key))
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        text.push_str(&format!(
+            "{label}: {policy} (next boot: {})\n",
+            if boot_on { "on" } else { "off" }
+        ));
     }
+    text.push_str(&format!(
+        "Persisted: {}\n",
+        output
+            .get("persisted")
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    ));
+    text
 }
 
 async fn handle_hardware(
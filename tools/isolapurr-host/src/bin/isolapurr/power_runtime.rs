@@ -2,7 +2,7 @@ async fn fetch_power_config(
     client: &Client,
     devd: &DevdClient,
     selector: &ApiSelectorArgs,
-) -> anyhow::Result<CliPowerConfig> {
+) -> anyhow::Result<PowerConfig> {
     let current = request_selected(
         client,
         devd,
@@ -510,6 +510,9 @@ async fn handle_power(
                 )
             }
         },
+        PowerCommand::FastChargeProbe(args) => {
+            handle_fast_charge_probe(client, devd, args, allow_interactive).await
+        }
         PowerCommand::IdleBias { command } => match command {
             IdleBiasCommand::Show { selector } => {
                 let selector =
@@ -629,7 +632,7 @@ async fn handle_power(
                     maybe_select_power_target(client, devd, selector, allow_interactive).await?;
                 let owner = next_power_owner();
                 let mut config = fetch_power_config(client, devd, &selector).await?;
-                config.tps_mode = "manual".to_string();
+                config.tps_mode = TpsMode::Manual.as_str().to_string();
                 apply_manual_output_args(&mut config, &args);
                 unwrap_device_success_result(
                     save_power_config_with_timeout_recovery(
@@ -643,7 +646,7 @@ async fn handle_power(
                     maybe_select_power_target(client, devd, selector, allow_interactive).await?;
                 let owner = next_power_owner();
                 let mut config = fetch_power_config(client, devd, &selector).await?;
-                config.tps_mode = "auto_follow".to_string();
+                config.tps_mode = TpsMode::AutoFollow.as_str().to_string();
                 unwrap_device_success_result(
                     save_power_config_with_timeout_recovery(
                         client, devd, &selector, owner, &config,
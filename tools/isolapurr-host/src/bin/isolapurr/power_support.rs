@@ -64,7 +64,7 @@ fn set_protocol_flag(protocols: &mut Value, key: &str, enabled: bool) -> anyhow:
 }
 
 fn apply_source_capability_args(
-    config: &mut CliPowerConfig,
+    config: &mut PowerConfig,
     args: &SourceCapabilitySetArgs,
 ) -> anyhow::Result<()> {
     if let Some(power_watts) = args.power_watts {
@@ -134,7 +134,7 @@ fn apply_source_capability_args(
     Ok(())
 }
 
-fn apply_manual_output_args(config: &mut CliPowerConfig, args: &ManualOutputArgs) {
+fn apply_manual_output_args(config: &mut PowerConfig, args: &ManualOutputArgs) {
     if let Some(voltage_mv) = args.voltage_mv {
         config.manual.voltage_mv = voltage_mv;
     }
@@ -153,7 +153,7 @@ fn apply_manual_output_args(config: &mut CliPowerConfig, args: &ManualOutputArgs
 }
 
 fn apply_power_config_set_args(
-    config: &mut CliPowerConfig,
+    config: &mut PowerConfig,
     args: &PowerConfigSetArgs,
 ) -> anyhow::Result<()> {
     if let Some(light_load_mode) = args.light_load_mode {
@@ -170,7 +170,7 @@ fn apply_power_config_set_args(
     Ok(())
 }
 
-fn power_config_update_payload(config: &CliPowerConfig) -> Value {
+fn power_config_update_payload(config: &PowerConfig) -> Value {
     json!({
         "hardware": config.hardware,
         "tps_mode": config.tps_mode,
@@ -204,7 +204,7 @@ fn power_config_update_payload(config: &CliPowerConfig) -> Value {
     })
 }
 
-fn same_power_config_contents(left: &CliPowerConfig, right: &CliPowerConfig) -> bool {
+fn same_power_config_contents(left: &PowerConfig, right: &PowerConfig) -> bool {
     left.hardware == right.hardware
         && left.tps_mode == right.tps_mode
         && left.light_load_mode == right.light_load_mode
@@ -213,8 +213,8 @@ fn same_power_config_contents(left: &CliPowerConfig, right: &CliPowerConfig) ->
         && left.manual == right.manual
 }
 
-fn full_power_capability_defaults() -> CliPowerCapability {
-    CliPowerCapability {
+fn full_power_capability_defaults() -> PowerCapability {
+    PowerCapability {
         profile: "full".to_string(),
         power_watts: 100,
         protocols: json!({
@@ -228,22 +228,22 @@ fn full_power_capability_defaults() -> CliPowerCapability {
             "bc12": true,
             "sfcp": true,
         }),
-        pd: CliPowerPd {
+        pd: PowerPd {
             pps: true,
             fixed_voltages_mv: vec![9000, 12000, 15000, 20000],
         },
-        current: CliPowerCurrentProfile::default(),
-        fast_charge: CliPowerFastChargeProfile::default(),
+        current: PowerCurrentProfile::default(),
+        fast_charge: PowerFastChargeProfile::default(),
     }
 }
 
-fn expected_default_power_config(current: &CliPowerConfig) -> CliPowerConfig {
+fn expected_default_power_config(current: &PowerConfig) -> PowerConfig {
     let mut expected = current.clone();
-    expected.tps_mode = "auto_follow".to_string();
-    expected.light_load_mode = "pfm".to_string();
-    expected.sw2303_line_compensation = default_sw2303_line_compensation();
+    expected.tps_mode = TpsMode::AutoFollow.as_str().to_string();
+    expected.light_load_mode = LightLoadMode::Pfm.as_str().to_string();
+    expected.sw2303_line_compensation = Sw2303LineCompensation::MilliOhm50.as_str().to_string();
     expected.capability = full_power_capability_defaults();
-    expected.manual = CliPowerManual {
+    expected.manual = PowerManual {
         voltage_mv: MANUAL_OUTPUT_DEFAULT_VOLTAGE_MV,
         current_limit_ma: MANUAL_OUTPUT_DEFAULT_CURRENT_MA,
         tps_cdc_rise_mv: 0,
@@ -262,7 +262,7 @@ async fn save_power_config_with_timeout_recovery(
     devd: &DevdClient,
     selector: &ApiSelectorArgs,
     owner: u32,
-    config: &CliPowerConfig,
+    config: &PowerConfig,
 ) -> anyhow::Result<Value> {
     let request = request_selected(
         client,
@@ -330,7 +330,7 @@ fn on_off(value: bool) -> &'static str {
     if value { "on" } else { "off" }
 }
 
-fn toggle_fixed_pd_voltage(config: &mut CliPowerConfig, mv: u16) {
+fn toggle_fixed_pd_voltage(config: &mut PowerConfig, mv: u16) {
     if let Some(index) = config
         .capability
         .pd
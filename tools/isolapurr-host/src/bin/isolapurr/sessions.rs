@@ -0,0 +1,100 @@
+#[derive(Debug, clap::Args)]
+struct SessionsArgs {
+    #[command(flatten)]
+    selector: ApiSelectorArgs,
+    /// Only list sessions of this port (`port_a` or `port_c`).
+    #[arg(long, value_parser = parse_session_port)]
+    port: Option<PortId>,
+}
+
+fn parse_session_port(raw: &str) -> Result<PortId, String> {
+    PortId::parse(raw).ok_or_else(|| format!("port must be port_a or port_c, got `{raw}`"))
+}
+
+async fn handle_sessions(
+    client: &Client,
+    devd: &DevdClient,
+    args: SessionsArgs,
+) -> anyhow::Result<Value> {
+    let value =
+        request_selected(client, devd, args.selector, Method::GET, "/sessions", None).await?;
+    let mut output = unwrap_device_success_result(value)?;
+    if let (Some(port), Some(sessions)) = (
+        args.port,
+        output.get_mut("sessions").and_then(Value::as_array_mut),
+    ) {
+        sessions
+            .retain(|session| session.get("port").and_then(Value::as_str) == Some(port.as_str()));
+    }
+    Ok(output)
+}
+
+fn format_session_duration(ms: u64) -> String {
+    let seconds = ms / 1_000;
+    match seconds {
+        0..60 => format!("{seconds}s"),
+        60..3_600 => format!("{}m{:02}s", seconds / 60, seconds % 60),
+        _ => format!("{}h{:02}m", seconds / 3_600, seconds / 60 % 60),
+    }
+}
+
+fn format_session(session: &ChargeSession) -> String {
+    let label = match session.port {
+        PortId::PortA => "USB-A",
+        PortId::PortC => "USB-C",
+    };
+    let (end, duration_ms) = match session.end_uptime_ms {
+        Some(end_ms) => (
+            format!("{:.0} s", end_ms as f64 / 1000.0),
+            end_ms.saturating_sub(session.start_uptime_ms),
+        ),
+        None => ("open".to_string(), 0),
+    };
+    let protocols: Vec<&str> = session
+        .protocols
+        .iter()
+        .map(|protocol| format_active_protocol(protocol.as_str()))
+        .collect();
+    let mut line = format!(
+        "#{:<4} {label}  {:.0} s -> {end}",
+        session.id,
+        session.start_uptime_ms as f64 / 1000.0,
+    );
+    if session.end_uptime_ms.is_some() {
+        line.push_str(&format!(" ({})", format_session_duration(duration_ms)));
+    }
+    line.push_str(&format!(
+        "  {:.2} Wh  avg {:.2} W  peak {:.2} W  max {:.2} V",
+        f64::from(session.energy_mwh) / 1000.0,
+        f64::from(session.avg_power_mw) / 1000.0,
+        f64::from(session.peak_power_mw) / 1000.0,
+        f64::from(session.max_voltage_mv) / 1000.0,
+    ));
+    if !protocols.is_empty() {
+        line.push_str(&format!("  {}", protocols.join(", ")));
+    }
+    line
+}
+
+fn format_sessions_output(output: &Value) -> String {
+    let mut lines: Vec<String> = output
+        .get("sessions")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(
+            |session| match serde_json::from_value::<ChargeSession>(session.clone()) {
+                Ok(session) => format_session(&session),
+                Err(_) => session.to_string(),
+            },
+        )
+        .collect();
+    if lines.is_empty() {
+        lines.push("No sessions recorded since boot".to_string());
+    }
+    lines.push(format!(
+        "Times are device uptime; the hub keeps the last {} finished sessions in RAM.",
+        output.get("capacity").and_then(Value::as_u64).unwrap_or(0)
+    ));
+    format!("{}\n", lines.join("\n"))
+}
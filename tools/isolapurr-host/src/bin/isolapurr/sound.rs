@@ -0,0 +1,254 @@
+const SOUND_SLOTS: [&str; 10] = [
+    "boot_ok",
+    "boot_warn",
+    "boot_fail",
+    "warning",
+    "error",
+    "action_ok",
+    "action_fail",
+    "menu_navigate",
+    "menu_confirm",
+    "identify",
+];
+
+#[derive(Debug, Subcommand)]
+enum SoundCommand {
+    #[command(about = "Show mute, volume, quiet hours, and custom tone settings")]
+    Show(ApiSelectorArgs),
+    #[command(
+        about = "Update sound settings and sync the device clock used for quiet hours",
+        after_help = "Safety alerts always sound, even when muted or inside quiet hours.\nThe host local time is sent with every update unless --local-time overrides it."
+    )]
+    Set(SoundSetArgs),
+    #[command(about = "Restore default sound settings and remove every custom tone")]
+    Defaults(ApiSelectorArgs),
+    #[command(about = "Replace or restore the tone played for one sound slot")]
+    Pattern {
+        #[command(subcommand)]
+        command: SoundPatternCommand,
+    },
+}
+
+#[derive(Debug, Default, clap::Args)]
+struct SoundSetArgs {
+    #[command(flatten)]
+    selector: ApiSelectorArgs,
+    #[arg(long, value_parser = clap::value_parser!(bool), action = ArgAction::Set)]
+    muted: Option<bool>,
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..=100))]
+    volume: Option<u8>,
+    #[arg(long, value_parser = clap::value_parser!(bool), action = ArgAction::Set)]
+    boot: Option<bool>,
+    #[arg(long, value_parser = clap::value_parser!(bool), action = ArgAction::Set)]
+    menu: Option<bool>,
+    #[arg(long, value_parser = clap::value_parser!(bool), action = ArgAction::Set)]
+    action: Option<bool>,
+    #[arg(long, value_parser = clap::value_parser!(bool), action = ArgAction::Set)]
+    warning: Option<bool>,
+    #[arg(long = "quiet-hours", value_parser = clap::value_parser!(bool), action = ArgAction::Set)]
+    quiet_hours: Option<bool>,
+    #[arg(long = "quiet-start", value_name = "HH:MM")]
+    quiet_start: Option<String>,
+    #[arg(long = "quiet-end", value_name = "HH:MM")]
+    quiet_end: Option<String>,
+    #[arg(long = "local-time", value_name = "HH:MM")]
+    local_time: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+enum SoundPatternCommand {
+    #[command(
+        about = "Set a custom tone as freq_hz:duration_ms steps",
+        after_help = "Example: --steps 2700:40,0:60,2700:40\nUse 0 Hz for silence. Up to 8 steps, 200-8000 Hz, 10-2000 ms each."
+    )]
+    Set {
+        #[command(flatten)]
+        selector: ApiSelectorArgs,
+        #[arg(long, value_parser = clap::builder::PossibleValuesParser::new(SOUND_SLOTS))]
+        slot: String,
+        #[arg(long)]
+        steps: String,
+    },
+    #[command(about = "Restore the built-in tone for a sound slot")]
+    Clear {
+        #[command(flatten)]
+        selector: ApiSelectorArgs,
+        #[arg(long, value_parser = clap::builder::PossibleValuesParser::new(SOUND_SLOTS))]
+        slot: String,
+    },
+}
+
+async fn handle_sound(
+    client: &Client,
+    devd: &DevdClient,
+    command: SoundCommand,
+) -> anyhow::Result<Value> {
+    let (selector, method, suffix, body) = match command {
+        SoundCommand::Show(selector) => (selector, Method::GET, "/sound".to_string(), None),
+        SoundCommand::Set(args) => {
+            let local_minute = match args.local_time.as_deref() {
+                Some(local_time) => Some(parse_clock_minute(local_time)?),
+                None => system_local_minute(),
+            };
+            let body = sound_set_body(&args, local_minute)?;
+            (args.selector, Method::PUT, "/sound".to_string(), Some(body))
+        }
+        SoundCommand::Defaults(selector) => {
+            (selector, Method::POST, "/sound/defaults".to_string(), None)
+        }
+        SoundCommand::Pattern { command } => match command {
+            SoundPatternCommand::Set {
+                selector,
+                slot,
+                steps,
+            } => (
+                selector,
+                Method::PUT,
+                format!("/sound/patterns/{slot}"),
+                Some(json!({"steps": steps})),
+            ),
+            SoundPatternCommand::Clear { selector, slot } => (
+                selector,
+                Method::POST,
+                format!("/sound/patterns/{slot}/clear"),
+                None,
+            ),
+        },
+    };
+    let value = request_selected(client, devd, selector, method, &suffix, body).await?;
+    unwrap_device_success_result(value)
+}
+
+fn sound_set_body(args: &SoundSetArgs, local_minute: Option<u16>) -> anyhow::Result<Value> {
+    let mut body = serde_json::Map::new();
+    let flags = [
+        ("muted", args.muted),
+        ("boot", args.boot),
+        ("menu", args.menu),
+        ("action", args.action),
+        ("warning", args.warning),
+        ("enabled", args.quiet_hours),
+    ];
+    for (key, value) in flags {
+        if let Some(value) = value {
+            body.insert(key.to_string(), json!(value));
+        }
+    }
+    if let Some(volume) = args.volume {
+        body.insert("volume_pct".to_string(), json!(volume));
+    }
+    for (key, value) in [("start", &args.quiet_start), ("end", &args.quiet_end)] {
+        if let Some(value) = value {
+            parse_clock_minute(value)?;
+            body.insert(key.to_string(), json!(value));
+        }
+    }
+    if let Some(local_minute) = local_minute {
+        body.insert("local_minute".to_string(), json!(local_minute));
+    }
+    Ok(Value::Object(body))
+}
+
+fn parse_clock_minute(value: &str) -> anyhow::Result<u16> {
+    let (hour, minute) = value
+        .split_once(':')
+        .ok_or_else(|| anyhow!("time must be HH:MM, got {value:?}"))?;
+    let hour = hour
+        .parse::<u16>()
+        .ok()
+        .filter(|hour| *hour < 24)
+        .ok_or_else(|| anyhow!("hour must be 00-23, got {value:?}"))?;
+    let minute = minute
+        .parse::<u16>()
+        .ok()
+        .filter(|minute| *minute < 60)
+        .ok_or_else(|| anyhow!("minute must be 00-59, got {value:?}"))?;
+    Ok(hour * 60 + minute)
+}
+
+/// Reads the host wall clock through `date` so quiet hours follow the local
+/// timezone without pulling a timezone database into the CLI.
+fn system_local_minute() -> Option<u16> {
+    let output = ProcessCommand::new("date")
+        .arg("+%H:%M")
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_clock_minute(String::from_utf8_lossy(&output.stdout).trim()).ok()
+}
+
+fn format_sound_output(output: &Value) -> String {
+    let flag = |value: &Value, key: &str| value.get(key).and_then(Value::as_bool).unwrap_or(false);
+    let mut lines = Vec::new();
+    let volume = output
+        .get("volume_pct")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    if flag(output, "muted") {
+        lines.push("Sound: muted (safety alerts still sound)".to_string());
+    } else {
+        lines.push(format!("Sound: on, volume {volume}%"));
+    }
+
+    let classes = output.get("classes").cloned().unwrap_or_else(|| json!({}));
+    lines.push(format!(
+        "Classes: boot {}, menu {}, action {}, warning {}, safety always",
+        on_off(flag(&classes, "boot")),
+        on_off(flag(&classes, "menu")),
+        on_off(flag(&classes, "action")),
+        on_off(flag(&classes, "warning")),
+    ));
+
+    let quiet = output
+        .get("quiet_hours")
+        .cloned()
+        .unwrap_or_else(|| json!({}));
+    if flag(&quiet, "enabled") {
+        let state = if !flag(output, "clock_synced") {
+            "clock not synced"
+        } else if flag(&quiet, "active") {
+            "active now"
+        } else {
+            "inactive"
+        };
+        lines.push(format!(
+            "Quiet hours: {}-{} ({state})",
+            quiet
+                .get("start")
+                .and_then(Value::as_str)
+                .unwrap_or("--:--"),
+            quiet.get("end").and_then(Value::as_str).unwrap_or("--:--"),
+        ));
+    } else {
+        lines.push("Quiet hours: off".to_string());
+    }
+    lines.push(format!(
+        "Saved: {}",
+        if flag(output, "persisted") {
+            "yes"
+        } else {
+            "no (defaults)"
+        }
+    ));
+
+    let custom = output
+        .get("patterns")
+        .and_then(Value::as_object)
+        .map(|patterns| {
+            patterns
+                .iter()
+                .filter_map(|(slot, steps)| Some(format!("- {slot}: {}", steps.as_str()?)))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    if custom.is_empty() {
+        lines.push("Custom tones: none".to_string());
+    } else {
+        lines.push("Custom tones:".to_string());
+        lines.extend(custom);
+    }
+    format!("{}\n", lines.join("\n"))
+}
@@ -170,7 +170,7 @@ fn make_choice_row(
 }
 
 fn render_source_capability_row(
-    config: &CliPowerConfig,
+    config: &PowerConfig,
     state: &SourceCapabilityEditorState,
     row_index: usize,
 ) -> ListItem<'static> {
@@ -368,7 +368,7 @@ fn render_source_capability_row(
 fn draw_source_capability_editor(
     frame: &mut Frame<'_>,
     diagnostics: &str,
-    config: &CliPowerConfig,
+    config: &PowerConfig,
     state: &SourceCapabilityEditorState,
 ) {
     let diagnostics = truncate_lines(diagnostics, 6);
@@ -424,7 +424,7 @@ fn draw_source_capability_editor(
 }
 
 fn apply_row_direction(
-    config: &mut CliPowerConfig,
+    config: &mut PowerConfig,
     state: &mut SourceCapabilityEditorState,
     direction: i8,
 ) -> anyhow::Result<()> {
@@ -610,7 +610,7 @@ fn select_choice<T: Copy + PartialEq>(
         .map(|index| choices[index]))
 }
 
-fn source_capability_row_label(config: &CliPowerConfig, row: SourceCapabilityEditorRow) -> String {
+fn source_capability_row_label(config: &PowerConfig, row: SourceCapabilityEditorRow) -> String {
     match row {
         SourceCapabilityEditorRow::PowerWatts => {
             format!("Power cap: {} W", config.capability.power_watts)
@@ -706,7 +706,7 @@ fn source_capability_row_label(config: &CliPowerConfig, row: SourceCapabilityEdi
 }
 
 fn edit_source_capability_row(
-    config: &mut CliPowerConfig,
+    config: &mut PowerConfig,
     row: SourceCapabilityEditorRow,
 ) -> anyhow::Result<Option<EditorSubmit>> {
     match row {
@@ -971,7 +971,7 @@ fn edit_source_capability_row(
 }
 
 fn submit_editor_row(
-    config: &mut CliPowerConfig,
+    config: &mut PowerConfig,
     state: &mut SourceCapabilityEditorState,
 ) -> anyhow::Result<EditorSubmit> {
     Ok(match SOURCE_CAPABILITY_EDITOR_ROWS[state.selected_row] {
@@ -992,7 +992,7 @@ fn submit_editor_row(
 }
 
 fn run_source_capability_editor_tui(
-    config: &mut CliPowerConfig,
+    config: &mut PowerConfig,
     diagnostics: &str,
 ) -> anyhow::Result<EditorSubmit> {
     let mut selected_row = 0usize;
@@ -0,0 +1,537 @@
+// `isolapurr test run`: declarative DUT test plans (TOML) and their reports.
+
+const TEST_DEFAULT_CASE_TIMEOUT_MS: u64 = 300_000;
+const TEST_DEFAULT_WAIT_TIMEOUT_MS: u64 = 10_000;
+const TEST_DEFAULT_POLL_MS: u64 = 250;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TestPlan {
+    name: String,
+    #[serde(default)]
+    defaults: TestPlanDefaults,
+    #[serde(rename = "case", default)]
+    cases: Vec<TestCase>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+struct TestPlanDefaults {
+    case_timeout_ms: u64,
+    wait_timeout_ms: u64,
+    poll_ms: u64,
+}
+
+impl Default for TestPlanDefaults {
+    fn default() -> Self {
+        Self {
+            case_timeout_ms: TEST_DEFAULT_CASE_TIMEOUT_MS,
+            wait_timeout_ms: TEST_DEFAULT_WAIT_TIMEOUT_MS,
+            poll_ms: TEST_DEFAULT_POLL_MS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TestCase {
+    name: String,
+    #[serde(default = "default_test_repeat")]
+    repeat: u32,
+    #[serde(default)]
+    timeout_ms: Option<u64>,
+    steps: Vec<TestStep>,
+}
+
+fn default_test_repeat() -> u32 {
+    1
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case", deny_unknown_fields)]
+enum TestStep {
+    /// Saved USB-C source capability in watts (`power config set`).
+    Preset {
+        watts: u8,
+    },
+    /// Manual TPS output (`power output manual`); omitted fields keep the saved target.
+    Voltage {
+        mv: u16,
+        #[serde(default)]
+        current_limit_ma: Option<u16>,
+    },
+    /// Back to automatic USB-C request tracking (`power output auto`).
+    AutoOutput,
+    PortPower {
+        port: PortId,
+        enabled: bool,
+    },
+    Replug {
+        port: PortId,
+    },
+    Sleep {
+        ms: u64,
+    },
+    /// Polls until the condition holds, failing after `timeout_ms`.
+    Wait {
+        expect: TestCondition,
+        #[serde(default)]
+        timeout_ms: Option<u64>,
+    },
+    /// Checks the condition once.
+    Assert {
+        expect: TestCondition,
+    },
+    /// Integrates port power over `duration_ms` and optionally bounds the result.
+    Energy {
+        port: PortId,
+        duration_ms: u64,
+        #[serde(default)]
+        min_mwh: Option<f64>,
+        #[serde(default)]
+        max_mwh: Option<f64>,
+    },
+    Loop {
+        count: u32,
+        steps: Vec<TestStep>,
+    },
+}
+
+/// Inclusive `[min, max]`.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+struct TestRange(u32, u32);
+
+impl TestRange {
+    fn contains(self, value: u32) -> bool {
+        (self.0..=self.1).contains(&value)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TestCondition {
+    #[serde(default)]
+    port: Option<PortId>,
+    #[serde(default)]
+    power_enabled: Option<bool>,
+    #[serde(default)]
+    voltage_mv: Option<TestRange>,
+    #[serde(default)]
+    current_ma: Option<TestRange>,
+    #[serde(default)]
+    power_mw: Option<TestRange>,
+    /// USB-C active fast-charge protocol, e.g. `pd`, `pps`, `qc30`; `none` for no contract.
+    #[serde(default)]
+    protocol: Option<String>,
+}
+
+impl TestCondition {
+    fn needs_port(&self) -> bool {
+        self.power_enabled.is_some()
+            || self.voltage_mv.is_some()
+            || self.current_ma.is_some()
+            || self.power_mw.is_some()
+    }
+}
+
+fn parse_test_plan(text: &str) -> anyhow::Result<TestPlan> {
+    let plan: TestPlan = toml::from_str(text).context("invalid test plan")?;
+    if plan.cases.is_empty() {
+        return Err(anyhow!("test plan has no [[case]] entries"));
+    }
+    if plan.defaults.poll_ms == 0 {
+        return Err(anyhow!("defaults.poll_ms must be greater than zero"));
+    }
+    for case in &plan.cases {
+        if case.repeat == 0 {
+            return Err(anyhow!("case `{}`: repeat must be at least 1", case.name));
+        }
+        validate_test_steps(&case.steps).with_context(|| format!("case `{}`", case.name))?;
+    }
+    Ok(plan)
+}
+
+fn validate_test_steps(steps: &[TestStep]) -> anyhow::Result<()> {
+    for step in steps {
+        match step {
+            TestStep::Voltage {
+                mv,
+                current_limit_ma,
+            } => {
+                if !(3000..=21000).contains(mv) {
+                    return Err(anyhow!("voltage mv must be 3000..=21000, got {mv}"));
+                }
+                if current_limit_ma.is_some_and(|ma| !(1..=6350).contains(&ma)) {
+                    return Err(anyhow!("voltage current_limit_ma must be 1..=6350"));
+                }
+            }
+            TestStep::Wait {
+                expect: condition, ..
+            }
+            | TestStep::Assert { expect: condition } => {
+                if condition.needs_port() && condition.port.is_none() {
+                    return Err(anyhow!("{} needs a port", test_step_label(step)));
+                }
+                if !condition.needs_port() && condition.protocol.is_none() {
+                    return Err(anyhow!("{} has nothing to check", test_step_label(step)));
+                }
+            }
+            TestStep::Energy {
+                min_mwh, max_mwh, ..
+            } => {
+                if let (Some(min), Some(max)) = (min_mwh, max_mwh)
+                    && min > max
+                {
+                    return Err(anyhow!("energy min_mwh is above max_mwh"));
+                }
+            }
+            TestStep::Loop { count, steps } => {
+                if *count == 0 {
+                    return Err(anyhow!("loop count must be at least 1"));
+                }
+                validate_test_steps(steps)?;
+            }
+            TestStep::Preset { .. }
+            | TestStep::AutoOutput
+            | TestStep::PortPower { .. }
+            | TestStep::Replug { .. }
+            | TestStep::Sleep { .. } => {}
+        }
+    }
+    Ok(())
+}
+
+fn test_step_label(step: &TestStep) -> String {
+    match step {
+        TestStep::Preset { watts } => format!("preset {watts} W"),
+        TestStep::Voltage {
+            mv,
+            current_limit_ma: Some(ma),
+        } => format!("voltage {mv} mV @ {ma} mA"),
+        TestStep::Voltage { mv, .. } => format!("voltage {mv} mV"),
+        TestStep::AutoOutput => "auto output".to_string(),
+        TestStep::PortPower { port, enabled } => format!(
+            "{} power {}",
+            port.as_str(),
+            if *enabled { "on" } else { "off" }
+        ),
+        TestStep::Replug { port } => format!("replug {}", port.as_str()),
+        TestStep::Sleep { ms } => format!("sleep {ms} ms"),
+        TestStep::Wait { expect, .. } => format!("wait {}", test_condition_label(expect)),
+        TestStep::Assert { expect } => format!("assert {}", test_condition_label(expect)),
+        TestStep::Energy {
+            port, duration_ms, ..
+        } => format!("energy {} for {duration_ms} ms", port.as_str()),
+        TestStep::Loop { count, steps } => format!("loop {count}x ({} steps)", steps.len()),
+    }
+}
+
+fn test_condition_label(condition: &TestCondition) -> String {
+    let mut parts = Vec::new();
+    if let Some(port) = condition.port {
+        parts.push(port.as_str().to_string());
+    }
+    if let Some(enabled) = condition.power_enabled {
+        parts.push(format!("power={}", if enabled { "on" } else { "off" }));
+    }
+    for (name, range) in [
+        ("voltage_mv", condition.voltage_mv),
+        ("current_ma", condition.current_ma),
+        ("power_mw", condition.power_mw),
+    ] {
+        if let Some(TestRange(min, max)) = range {
+            parts.push(format!("{name}={min}..{max}"));
+        }
+    }
+    if let Some(protocol) = &condition.protocol {
+        parts.push(format!("protocol={protocol}"));
+    }
+    parts.join(" ")
+}
+
+/// Returns every unmet part of `condition`, or an empty list when it holds.
+fn test_condition_mismatches(
+    condition: &TestCondition,
+    ports: &[Port],
+    active_protocol: Option<&str>,
+) -> Vec<String> {
+    let mut mismatches = Vec::new();
+    if let Some(port_id) = condition.port.filter(|_| condition.needs_port()) {
+        let Some(port) = ports.iter().find(|port| port.port_id == port_id) else {
+            return vec![format!("{} missing from ports", port_id.as_str())];
+        };
+        if let Some(expected) = condition.power_enabled
+            && port.state.power_enabled != expected
+        {
+            mismatches.push(format!(
+                "power_enabled is {}, expected {expected}",
+                port.state.power_enabled
+            ));
+        }
+        let telemetry = &port.telemetry;
+        for (name, range, value) in [
+            ("voltage_mv", condition.voltage_mv, telemetry.voltage_mv),
+            ("current_ma", condition.current_ma, telemetry.current_ma),
+            ("power_mw", condition.power_mw, telemetry.power_mw),
+        ] {
+            let Some(range) = range else {
+                continue;
+            };
+            match value.filter(|_| telemetry.status == TelemetryStatus::Ok) {
+                Some(value) if range.contains(value) => {}
+                Some(value) => mismatches.push(format!(
+                    "{name} is {value}, expected {}..{}",
+                    range.0, range.1
+                )),
+                None => mismatches.push(format!(
+                    "{name} unavailable (telemetry {})",
+                    telemetry.status.as_str()
+                )),
+            }
+        }
+    }
+    if let Some(expected) = &condition.protocol {
+        let actual = active_protocol.unwrap_or("none");
+        if actual != expected {
+            mismatches.push(format!("protocol is {actual}, expected {expected}"));
+        }
+    }
+    mismatches
+}
+
+/// Trapezoidal integral of `(elapsed_ms, power_mw)` samples, in mWh.
+fn integrate_energy_mwh(samples: &[(u64, u32)]) -> f64 {
+    samples
+        .windows(2)
+        .map(|pair| energy_step_mwh(pair[0], pair[1]))
+        .sum()
+}
+
+/// Energy between two `(elapsed_ms, power_mw)` samples, in mWh.
+fn energy_step_mwh((t0, p0): (u64, u32), (t1, p1): (u64, u32)) -> f64 {
+    (p0 as f64 + p1 as f64) / 2.0 * t1.saturating_sub(t0) as f64 / 3_600_000.0
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum TestOutcome {
+    Passed,
+    /// An assertion, wait, or energy bound did not hold.
+    Failed,
+    /// The device could not be driven (request error, case timeout).
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct TestStepReport {
+    step: String,
+    outcome: TestOutcome,
+    duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    energy_mwh: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct TestCaseReport {
+    name: String,
+    iteration: u32,
+    outcome: TestOutcome,
+    duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+    steps: Vec<TestStepReport>,
+}
+
+impl TestCaseReport {
+    fn junit_name(&self, repeat: u32) -> String {
+        if repeat > 1 {
+            format!("{} #{}", self.name, self.iteration)
+        } else {
+            self.name.clone()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct TestRunReport {
+    plan: String,
+    device: String,
+    duration_ms: u64,
+    passed: usize,
+    failed: usize,
+    errors: usize,
+    cases: Vec<TestCaseReport>,
+    #[serde(skip)]
+    repeats: HashMap<String, u32>,
+}
+
+impl TestRunReport {
+    fn new(plan: &TestPlan, device: String) -> Self {
+        Self {
+            plan: plan.name.clone(),
+            device,
+            duration_ms: 0,
+            passed: 0,
+            failed: 0,
+            errors: 0,
+            cases: Vec::new(),
+            repeats: plan
+                .cases
+                .iter()
+                .map(|case| (case.name.clone(), case.repeat))
+                .collect(),
+        }
+    }
+
+    fn push(&mut self, case: TestCaseReport) {
+        match case.outcome {
+            TestOutcome::Passed => self.passed += 1,
+            TestOutcome::Failed => self.failed += 1,
+            TestOutcome::Error => self.errors += 1,
+        }
+        self.cases.push(case);
+    }
+}
+
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // XML 1.0 cannot carry other control characters at all.
+            '\t' | '\n' | '\r' => escaped.push(ch),
+            ch if ch.is_control() => {}
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn junit_seconds(duration_ms: u64) -> String {
+    format!("{:.3}", duration_ms as f64 / 1000.0)
+}
+
+/// One `<testsuite>` per plan, one `<testcase>` per case iteration. Step
+/// timing goes to `<system-out>`, captured energy to `<properties>`.
+fn render_junit_report(report: &TestRunReport) -> String {
+    let suite = xml_escape(&report.plan);
+    let tests = report.cases.len();
+    let time = junit_seconds(report.duration_ms);
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"{suite}\" tests=\"{tests}\" failures=\"{}\" errors=\"{}\" time=\"{time}\">\n",
+        report.failed, report.errors
+    ));
+    xml.push_str(&format!(
+        "  <testsuite name=\"{suite}\" hostname=\"{}\" tests=\"{tests}\" failures=\"{}\" errors=\"{}\" time=\"{time}\">\n",
+        xml_escape(&report.device),
+        report.failed,
+        report.errors
+    ));
+    for case in &report.cases {
+        let repeat = report.repeats.get(&case.name).copied().unwrap_or(1);
+        xml.push_str(&format!(
+            "    <testcase classname=\"{suite}\" name=\"{}\" time=\"{}\">\n",
+            xml_escape(&case.junit_name(repeat)),
+            junit_seconds(case.duration_ms)
+        ));
+        let energy = case
+            .steps
+            .iter()
+            .filter_map(|step| step.energy_mwh.map(|mwh| (&step.step, mwh)))
+            .collect::<Vec<_>>();
+        if !energy.is_empty() {
+            xml.push_str("      <properties>\n");
+            for (step, mwh) in energy {
+                xml.push_str(&format!(
+                    "        <property name=\"{}\" value=\"{mwh:.3}\"/>\n",
+                    xml_escape(&format!("{step} mWh"))
+                ));
+            }
+            xml.push_str("      </properties>\n");
+        }
+        let tag = match case.outcome {
+            TestOutcome::Passed => None,
+            TestOutcome::Failed => Some("failure"),
+            TestOutcome::Error => Some("error"),
+        };
+        if let Some(tag) = tag {
+            let message = xml_escape(case.message.as_deref().unwrap_or(""));
+            xml.push_str(&format!(
+                "      <{tag} message=\"{message}\">{message}</{tag}>\n"
+            ));
+        }
+        let log = case
+            .steps
+            .iter()
+            .map(format_test_step_report)
+            .collect::<Vec<_>>()
+            .join("\n");
+        xml.push_str(&format!(
+            "      <system-out>{}</system-out>\n",
+            xml_escape(&log)
+        ));
+        xml.push_str("    </testcase>\n");
+    }
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+fn format_test_step_report(step: &TestStepReport) -> String {
+    let outcome = match step.outcome {
+        TestOutcome::Passed => "ok",
+        TestOutcome::Failed => "FAILED",
+        TestOutcome::Error => "ERROR",
+    };
+    let mut line = format!(
+        "{} ... {outcome} ({})",
+        step.step,
+        junit_seconds(step.duration_ms)
+    );
+    if let Some(mwh) = step.energy_mwh {
+        line.push_str(&format!(" {mwh:.3} mWh"));
+    }
+    if let Some(message) = &step.message {
+        line.push_str(&format!(": {message}"));
+    }
+    line
+}
+
+fn format_test_run_output(output: &Value) -> String {
+    let mut lines = vec![format!(
+        "Plan: {} on {}",
+        output.get("plan").and_then(Value::as_str).unwrap_or("?"),
+        output.get("device").and_then(Value::as_str).unwrap_or("?")
+    )];
+    for case in output
+        .get("cases")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let outcome = case.get("outcome").and_then(Value::as_str).unwrap_or("?");
+        let mut line = format!(
+            "  {} #{}: {outcome}",
+            case.get("name").and_then(Value::as_str).unwrap_or("?"),
+            case.get("iteration").and_then(Value::as_u64).unwrap_or(1)
+        );
+        if let Some(message) = case.get("message").and_then(Value::as_str) {
+            line.push_str(&format!(" - {message}"));
+        }
+        lines.push(line);
+    }
+    let count = |key: &str| output.get(key).and_then(Value::as_u64).unwrap_or(0);
+    lines.push(format!(
+        "Passed: {}  Failed: {}  Errors: {}",
+        count("passed"),
+        count("failed"),
+        count("errors")
+    ));
+    lines.join("\n") + "\n"
+}
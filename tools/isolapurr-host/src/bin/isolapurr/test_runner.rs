@@ -0,0 +1,326 @@
+struct TestStepFailure {
+    outcome: TestOutcome,
+    message: String,
+}
+
+impl TestStepFailure {
+    fn failed(message: impl Into<String>) -> Self {
+        Self {
+            outcome: TestOutcome::Failed,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<anyhow::Error> for TestStepFailure {
+    fn from(err: anyhow::Error) -> Self {
+        Self {
+            outcome: TestOutcome::Error,
+            message: format!("{err:#}"),
+        }
+    }
+}
+
+struct TestRunner<'a> {
+    client: &'a Client,
+    devd: &'a DevdClient,
+    selector: ApiSelectorArgs,
+    defaults: TestPlanDefaults,
+}
+
+impl TestRunner<'_> {
+    async fn run_case(&self, case: &TestCase, iteration: u32) -> TestCaseReport {
+        eprintln!("case {} #{iteration}/{}", case.name, case.repeat);
+        let started = Instant::now();
+        let timeout_ms = case.timeout_ms.unwrap_or(self.defaults.case_timeout_ms);
+        let mut steps = Vec::new();
+        let result = tokio::time::timeout(
+            Duration::from_millis(timeout_ms),
+            self.run_steps(&case.steps, "", &mut steps),
+        )
+        .await
+        .unwrap_or_else(|_| {
+            Err(TestStepFailure {
+                outcome: TestOutcome::Error,
+                message: format!("case timed out after {timeout_ms} ms"),
+            })
+        });
+        let (outcome, message) = match result {
+            Ok(()) => (TestOutcome::Passed, None),
+            Err(failure) => (failure.outcome, Some(failure.message)),
+        };
+        let report = TestCaseReport {
+            name: case.name.clone(),
+            iteration,
+            outcome,
+            duration_ms: started.elapsed().as_millis() as u64,
+            message,
+            steps,
+        };
+        eprintln!(
+            "case {} #{iteration}/{}: {} ({} s)",
+            case.name,
+            case.repeat,
+            match report.outcome {
+                TestOutcome::Passed => "passed",
+                TestOutcome::Failed => "FAILED",
+                TestOutcome::Error => "ERROR",
+            },
+            junit_seconds(report.duration_ms)
+        );
+        report
+    }
+
+    /// Runs `steps` in order and stops at the first one that does not pass.
+    async fn run_steps(
+        &self,
+        steps: &[TestStep],
+        prefix: &str,
+        reports: &mut Vec<TestStepReport>,
+    ) -> Result<(), TestStepFailure> {
+        for step in steps {
+            if let TestStep::Loop { count, steps } = step {
+                for round in 1..=*count {
+                    let prefix = format!("{prefix}[{round}/{count}] ");
+                    Box::pin(self.run_steps(steps, &prefix, reports)).await?;
+                }
+                continue;
+            }
+            let started = Instant::now();
+            let result = self.run_step(step).await;
+            let report = TestStepReport {
+                step: format!("{prefix}{}", test_step_label(step)),
+                outcome: match &result {
+                    Ok(_) => TestOutcome::Passed,
+                    Err(failure) => failure.outcome,
+                },
+                duration_ms: started.elapsed().as_millis() as u64,
+                message: result.as_ref().err().map(|failure| failure.message.clone()),
+                energy_mwh: result.as_ref().ok().copied().flatten(),
+            };
+            eprintln!("  {}", format_test_step_report(&report));
+            reports.push(report);
+            result?;
+        }
+        Ok(())
+    }
+
+    /// Returns the captured energy for `energy` steps.
+    async fn run_step(&self, step: &TestStep) -> Result<Option<f64>, TestStepFailure> {
+        match step {
+            TestStep::Preset { watts } => {
+                self.update_power_config(|config| config.capability.power_watts = *watts)
+                    .await?;
+            }
+            TestStep::Voltage {
+                mv,
+                current_limit_ma,
+            } => {
+                let args = ManualOutputArgs {
+                    voltage_mv: Some(*mv),
+                    current_limit_ma: *current_limit_ma,
+                    ..Default::default()
+                };
+                self.update_power_config(|config| {
+                    config.tps_mode = TpsMode::Manual.as_str().to_string();
+                    apply_manual_output_args(config, &args);
+                })
+                .await?;
+            }
+            TestStep::AutoOutput => {
+                self.update_power_config(|config| {
+                    config.tps_mode = TpsMode::AutoFollow.as_str().to_string();
+                })
+                .await?;
+            }
+            TestStep::PortPower { port, enabled } => {
+                self.post(&format!("/ports/{}/power?enabled={enabled}", port.as_str()))
+                    .await?;
+            }
+            TestStep::Replug { port } => {
+                self.post(&format!("/ports/{}/replug", port.as_str()))
+                    .await?;
+            }
+            TestStep::Sleep { ms } => tokio::time::sleep(Duration::from_millis(*ms)).await,
+            TestStep::Wait { expect, timeout_ms } => {
+                let timeout_ms = timeout_ms.unwrap_or(self.defaults.wait_timeout_ms);
+                let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+                loop {
+                    let mismatches = self.condition_mismatches(expect).await?;
+                    if mismatches.is_empty() {
+                        break;
+                    }
+                    if Instant::now() >= deadline {
+                        return Err(TestStepFailure::failed(format!(
+                            "not met after {timeout_ms} ms: {}",
+                            mismatches.join("; ")
+                        )));
+                    }
+                    tokio::time::sleep(Duration::from_millis(self.defaults.poll_ms)).await;
+                }
+            }
+            TestStep::Assert { expect } => {
+                let mismatches = self.condition_mismatches(expect).await?;
+                if !mismatches.is_empty() {
+                    return Err(TestStepFailure::failed(mismatches.join("; ")));
+                }
+            }
+            TestStep::Energy {
+                port,
+                duration_ms,
+                min_mwh,
+                max_mwh,
+            } => {
+                let mwh = self.capture_energy(*port, *duration_ms).await?;
+                if let Some(min) = min_mwh
+                    && mwh < *min
+                {
+                    return Err(TestStepFailure::failed(format!(
+                        "{mwh:.3} mWh is below {min} mWh"
+                    )));
+                }
+                if let Some(max) = max_mwh
+                    && mwh > *max
+                {
+                    return Err(TestStepFailure::failed(format!(
+                        "{mwh:.3} mWh is above {max} mWh"
+                    )));
+                }
+                return Ok(Some(mwh));
+            }
+            TestStep::Loop { .. } => unreachable!("loops are expanded by run_steps"),
+        }
+        Ok(None)
+    }
+
+    async fn post(&self, suffix: &str) -> anyhow::Result<()> {
+        let response = request_selected(
+            self.client,
+            self.devd,
+            self.selector.clone(),
+            Method::POST,
+            suffix,
+            None,
+        )
+        .await?;
+        unwrap_device_success_result(response).map(drop)
+    }
+
+    async fn update_power_config(&self, edit: impl FnOnce(&mut PowerConfig)) -> anyhow::Result<()> {
+        let mut config = fetch_power_config(self.client, self.devd, &self.selector).await?;
+        edit(&mut config);
+        let response = save_power_config_with_timeout_recovery(
+            self.client,
+            self.devd,
+            &self.selector,
+            next_power_owner(),
+            &config,
+        )
+        .await?;
+        unwrap_device_success_result(response).map(drop)
+    }
+
+    async fn condition_mismatches(&self, condition: &TestCondition) -> anyhow::Result<Vec<String>> {
+        let ports = if condition.needs_port() {
+            fetch_ports_snapshot(self.client, self.devd, &self.selector)
+                .await?
+                .ports
+        } else {
+            Vec::new()
+        };
+        let active_protocol = if condition.protocol.is_some() {
+            fetch_power_diagnostics(self.client, self.devd, &self.selector)
+                .await?
+                .active_protocol
+        } else {
+            None
+        };
+        Ok(test_condition_mismatches(
+            condition,
+            &ports,
+            active_protocol.as_deref(),
+        ))
+    }
+
+    async fn capture_energy(&self, port_id: PortId, duration_ms: u64) -> anyhow::Result<f64> {
+        let started = Instant::now();
+        let mut samples = Vec::new();
+        loop {
+            let ports = fetch_ports_snapshot(self.client, self.devd, &self.selector).await?;
+            let elapsed_ms = started.elapsed().as_millis() as u64;
+            let power_mw = ports
+                .ports
+                .iter()
+                .find(|port| port.port_id == port_id)
+                .filter(|port| port.telemetry.status == TelemetryStatus::Ok)
+                .and_then(|port| port.telemetry.power_mw)
+                .unwrap_or(0);
+            samples.push((elapsed_ms, power_mw));
+            if elapsed_ms >= duration_ms {
+                return Ok(integrate_energy_mwh(&samples));
+            }
+            let remaining = Duration::from_millis(duration_ms - elapsed_ms);
+            tokio::time::sleep(remaining.min(Duration::from_millis(self.defaults.poll_ms))).await;
+        }
+    }
+}
+
+async fn handle_test(
+    client: &Client,
+    devd: &DevdClient,
+    command: TestCommand,
+) -> anyhow::Result<Value> {
+    match command {
+        TestCommand::Run(args) => run_test_plan(client, devd, args).await,
+    }
+}
+
+async fn run_test_plan(
+    client: &Client,
+    devd: &DevdClient,
+    args: TestRunArgs,
+) -> anyhow::Result<Value> {
+    let text = fs::read_to_string(&args.plan)
+        .with_context(|| format!("read test plan {}", args.plan.display()))?;
+    let plan = parse_test_plan(&text)?;
+    let selector = select_api_target_interactively(client, devd, args.selector).await?;
+    let device = selector
+        .device_id
+        .clone()
+        .or_else(|| selector.url.clone())
+        .unwrap_or_default();
+    let runner = TestRunner {
+        client,
+        devd,
+        selector,
+        defaults: plan.defaults.clone(),
+    };
+
+    let started = Instant::now();
+    let mut report = TestRunReport::new(&plan, device);
+    for case in &plan.cases {
+        for iteration in 1..=case.repeat {
+            report.push(runner.run_case(case, iteration).await);
+        }
+    }
+    report.duration_ms = started.elapsed().as_millis() as u64;
+
+    if let Some(path) = &args.report {
+        fs::write(path, serde_json::to_string_pretty(&report)?)
+            .with_context(|| format!("write JSON report {}", path.display()))?;
+    }
+    if let Some(path) = &args.junit {
+        fs::write(path, render_junit_report(&report))
+            .with_context(|| format!("write JUnit report {}", path.display()))?;
+    }
+    let unsuccessful = report.failed + report.errors;
+    if unsuccessful > 0 {
+        return Err(anyhow!(
+            "{unsuccessful} of {} test case runs did not pass ({} failed, {} errors)",
+            report.cases.len(),
+            report.failed,
+            report.errors
+        ));
+    }
+    Ok(serde_json::to_value(&report)?)
+}
@@ -919,3 +919,54 @@ mod power_output_tests {
 
 #[cfg(test)]
 mod tests_cli;
+
+#[cfg(test)]
+mod tests_sound;
+
+#[cfg(test)]
+mod tests_display;
+
+#[cfg(test)]
+mod tests_buttons;
+
+#[cfg(test)]
+mod tests_i2c;
+
+#[cfg(test)]
+mod tests_reg;
+
+#[cfg(test)]
+mod tests_crash;
+
+#[cfg(test)]
+mod tests_logs;
+
+#[cfg(test)]
+mod tests_sessions;
+
+#[cfg(test)]
+mod tests_fast_charge_probe;
+
+#[cfg(test)]
+mod tests_port_data;
+
+#[cfg(test)]
+mod tests_port_power;
+
+#[cfg(test)]
+mod tests_charge_termination;
+
+#[cfg(test)]
+mod tests_watch;
+
+#[cfg(test)]
+mod tests_test_plan;
+
+#[cfg(test)]
+mod tests_log;
+
+#[cfg(test)]
+mod tests_trace;
+
+#[cfg(test)]
+mod tests_firmware;
@@ -0,0 +1,97 @@
+use super::{
+    ButtonsCommand, ButtonsSetArgs, Cli, Command, buttons_set_body, format_human_output,
+    map_devd_ipc_endpoint, map_http_endpoint,
+};
+use clap::Parser as _;
+use reqwest::Method;
+use serde_json::json;
+
+#[test]
+fn buttons_set_body_only_sends_requested_gestures() {
+    let args = ButtonsSetArgs {
+        right_double: Some("cycle_preset".to_string()),
+        combo_short: Some("lock".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        buttons_set_body(&args).expect("body should build"),
+        json!({"actions": {"right_double": "cycle_preset", "combo_short": "lock"}})
+    );
+
+    let combo_port = ButtonsSetArgs {
+        combo_long: Some("power_toggle".to_string()),
+        ..Default::default()
+    };
+    assert!(buttons_set_body(&combo_port).is_err());
+    assert!(buttons_set_body(&ButtonsSetArgs::default()).is_err());
+}
+
+#[test]
+fn buttons_cli_parses_actions_and_lock() {
+    let cli = Cli::try_parse_from(["isolapurr", "buttons", "set", "--left-long", "identify"])
+        .expect("action should parse");
+    let Command::Buttons {
+        command: ButtonsCommand::Set(args),
+    } = cli.command
+    else {
+        panic!("expected buttons set");
+    };
+    assert_eq!(args.left_long.as_deref(), Some("identify"));
+
+    assert!(Cli::try_parse_from(["isolapurr", "buttons", "set", "--left-long", "reboot"]).is_err());
+    assert!(matches!(
+        Cli::try_parse_from(["isolapurr", "buttons", "lock"])
+            .expect("lock should parse")
+            .command,
+        Command::Buttons {
+            command: ButtonsCommand::Lock(_)
+        }
+    ));
+}
+
+#[test]
+fn maps_buttons_endpoints_for_http_and_devd() {
+    let (method, path, body) =
+        map_http_endpoint(Method::PUT, "/buttons", Some(json!({"locked": true})))
+            .expect("buttons set should map");
+    assert_eq!(method, Method::PUT);
+    assert_eq!(path, "/api/v1/buttons");
+    assert_eq!(body, Some(json!({"locked": true})));
+
+    let (_, path, _) = map_http_endpoint(Method::POST, "/buttons/defaults", None)
+        .expect("buttons defaults should map");
+    assert_eq!(path, "/api/v1/buttons/defaults");
+
+    let (method, params) = map_devd_ipc_endpoint(
+        Method::PUT,
+        "/api/v1/devices/usb--dev-cu-usbmodem101/buttons",
+        Some(json!({"locked": false})),
+    )
+    .expect("devd buttons set should map");
+    assert_eq!(method, "device.buttons.set");
+    assert_eq!(params["device_id"], "usb--dev-cu-usbmodem101");
+    assert_eq!(params["locked"], false);
+}
+
+#[test]
+fn buttons_human_output_lists_every_gesture() {
+    let rendered = format_human_output(&json!({
+        "actions": {
+            "left_short": "data_replug",
+            "left_long": "power_toggle",
+            "left_double": "none",
+            "right_short": "data_replug",
+            "right_long": "power_toggle",
+            "right_double": "cycle_preset",
+            "combo_short": "lock",
+            "combo_long": "settings_menu"
+        },
+        "locked": true,
+        "persisted": true
+    }));
+
+    assert!(rendered.contains("Buttons: locked (hold both buttons to unlock)"));
+    assert!(rendered.contains("  right_double  cycle_preset"));
+    assert!(rendered.contains("  combo_long    settings_menu"));
+    assert!(rendered.contains("Saved: yes"));
+}
@@ -0,0 +1,103 @@
+use super::{
+    Cli, Command, PortsCommand, charge_termination_body, format_human_output,
+    map_devd_ipc_endpoint, map_http_endpoint,
+};
+use clap::Parser as _;
+use reqwest::Method;
+use serde_json::json;
+
+fn parse_args(args: &[&str]) -> super::ChargeTerminationArgs {
+    let cli = Cli::try_parse_from(
+        [
+            "isolapurr",
+            "ports",
+            "--device-id",
+            "hub",
+            "charge-termination",
+        ]
+        .into_iter()
+        .chain(args.iter().copied()),
+    )
+    .expect("charge-termination should parse");
+    let Command::Ports {
+        command: Some(PortsCommand::ChargeTermination(args)),
+        ..
+    } = cli.command
+    else {
+        panic!("expected ports charge-termination");
+    };
+    args
+}
+
+#[test]
+fn charge_termination_cli_builds_partial_body() {
+    let args = parse_args(&[
+        "--port",
+        "port_c",
+        "--action",
+        "drop-5v",
+        "--threshold-ma",
+        "80",
+    ]);
+    assert_eq!(
+        charge_termination_body(&args).expect("body should build"),
+        Some(json!({"port": "port_c", "action": "drop_5v", "threshold_ma": 80}))
+    );
+
+    let args = parse_args(&["--enabled", "true", "--action", "power_off"]);
+    assert!(charge_termination_body(&args).is_err());
+
+    let args = parse_args(&[]);
+    assert_eq!(charge_termination_body(&args).expect("get"), None);
+    assert!(
+        Cli::try_parse_from([
+            "isolapurr",
+            "ports",
+            "charge-termination",
+            "--action",
+            "explode"
+        ])
+        .is_err()
+    );
+}
+
+#[test]
+fn maps_charge_termination_endpoints_for_devd_and_http() {
+    let (method, params) = map_devd_ipc_endpoint(
+        Method::PUT,
+        "/api/v1/devices/usb--dev-cu-usbmodem101/ports/charge-termination",
+        Some(json!({"port": "port_a", "enabled": true})),
+    )
+    .expect("charge termination should map");
+    assert_eq!(method, "device.ports.charge_termination_set");
+    assert_eq!(
+        params,
+        json!({"device_id": "usb--dev-cu-usbmodem101", "port": "port_a", "enabled": true})
+    );
+
+    let (method, _) = map_devd_ipc_endpoint(
+        Method::GET,
+        "/api/v1/devices/usb--dev-cu-usbmodem101/ports/charge-termination",
+        None,
+    )
+    .expect("charge termination should map");
+    assert_eq!(method, "device.ports.charge_termination_get");
+
+    let (method, path, _) = map_http_endpoint(Method::GET, "/ports/charge-termination", None)
+        .expect("charge termination should map over HTTP");
+    assert_eq!(method, Method::GET);
+    assert_eq!(path, "/api/v1/ports/charge-termination");
+}
+
+#[test]
+fn formats_charge_termination() {
+    let text = format_human_output(&json!({
+        "port_a": {"enabled": false, "action": "notify", "threshold_ma": 100, "hold_s": 300, "min_charge_s": 1800},
+        "port_c": {"enabled": true, "action": "power_off", "threshold_ma": 80, "hold_s": 600, "min_charge_s": 3600},
+        "persisted": true,
+    }));
+    assert_eq!(
+        text,
+        "USB-A: disabled\nUSB-C: power_off below 80 mA for 600 s, after 3600 s of charging\nPersisted: true\n"
+    );
+}
@@ -1,9 +1,9 @@
 use super::{
-    CliPowerConfig, CliPowerDiagnostics, CliPowerSetpoint, DeviceProfile, DiscoverFirmware,
-    LightLoadModeArg, ManualOutputArgs, OutputUsbCPathArg, PowerConfigSetArgs,
-    SourceCapabilitySetArgs, Sw2303LineCompArg, TpsModeArg, apply_manual_output_args,
-    apply_power_config_set_args, discover_usb_match_keys, format_power_config_output,
-    format_power_show_output, parse_device_identity_from_info, parse_discovered_http_info,
+    CliPowerDiagnostics, CliPowerSetpoint, DeviceProfile, DiscoverFirmware, LightLoadModeArg,
+    ManualOutputArgs, OutputUsbCPathArg, PowerConfig, PowerConfigSetArgs, SourceCapabilitySetArgs,
+    Sw2303LineCompArg, TpsModeArg, apply_manual_output_args, apply_power_config_set_args,
+    discover_usb_match_keys, format_power_config_output, format_power_show_output,
+    parse_device_identity_from_info, parse_discovered_http_info,
     saved_hardware_match_for_transport,
 };
 use serde_json::json;
@@ -641,7 +641,7 @@ fn power_show_human_output_reports_thermal_shutdown_and_sensor_fault() {
 
 #[test]
 fn power_config_deserializes_when_current_profile_is_missing() {
-    let parsed: CliPowerConfig = serde_json::from_value(json!({
+    let parsed: PowerConfig = serde_json::from_value(json!({
         "hardware": "legacy-hardware",
         "persisted": true,
         "tps_mode": "auto_follow",
@@ -989,7 +989,7 @@ fn power_setpoint_serialization_keeps_legacy_ilim_field() {
 
 #[test]
 fn power_config_runtime_deserializes() {
-    let parsed: CliPowerConfig = serde_json::from_value(json!({
+    let parsed: PowerConfig = serde_json::from_value(json!({
         "hardware": "sw2303",
         "persisted": true,
         "tps_mode": "manual",
@@ -1033,7 +1033,7 @@ fn power_config_runtime_deserializes() {
 
 #[test]
 fn power_config_runtime_defaults_enabled_when_missing() {
-    let parsed: CliPowerConfig = serde_json::from_value(json!({
+    let parsed: PowerConfig = serde_json::from_value(json!({
         "hardware": "legacy-hardware",
         "persisted": true,
         "tps_mode": "auto_follow",
@@ -1081,7 +1081,7 @@ fn power_config_runtime_defaults_enabled_when_missing() {
 
 #[test]
 fn manual_output_updates_only_manual_section() {
-    let original: CliPowerConfig = serde_json::from_value(json!({
+    let original: PowerConfig = serde_json::from_value(json!({
         "hardware": "legacy-hardware",
         "persisted": true,
         "tps_mode": "auto_follow",
@@ -0,0 +1,112 @@
+use super::{
+    Cli, Command, DiagnosticsCommand, format_human_output, map_devd_ipc_endpoint,
+    map_http_endpoint, parse_addr2line_output,
+};
+use clap::Parser as _;
+use reqwest::Method;
+use serde_json::json;
+
+#[test]
+fn crash_cli_parses_elf_and_ack() {
+    let cli = Cli::try_parse_from([
+        "isolapurr",
+        "diagnostics",
+        "crash",
+        "--device-id",
+        "hub",
+        "--elf",
+        "fw.elf",
+        "--ack",
+    ])
+    .expect("crash should parse");
+    let Command::Diagnostics {
+        command: DiagnosticsCommand::Crash(args),
+    } = cli.command
+    else {
+        panic!("expected diagnostics crash");
+    };
+    assert_eq!(args.selector.device_id.as_deref(), Some("hub"));
+    assert_eq!(args.elf.as_deref(), Some(std::path::Path::new("fw.elf")));
+    assert_eq!(args.addr2line, "xtensa-esp32s3-elf-addr2line");
+    assert!(args.ack);
+}
+
+#[test]
+fn maps_crash_endpoints_for_devd_and_http() {
+    let (method, params) = map_devd_ipc_endpoint(
+        Method::GET,
+        "/api/v1/devices/usb--dev-cu-usbmodem101/diagnostics/crash",
+        None,
+    )
+    .expect("crash should map");
+    assert_eq!(method, "device.diagnostics.crash");
+    assert_eq!(params, json!({"device_id": "usb--dev-cu-usbmodem101"}));
+
+    let (method, _) = map_devd_ipc_endpoint(
+        Method::POST,
+        "/api/v1/devices/usb--dev-cu-usbmodem101/diagnostics/crash/ack",
+        None,
+    )
+    .expect("crash ack should map");
+    assert_eq!(method, "device.diagnostics.crash_ack");
+
+    let (method, path, _) = map_http_endpoint(Method::POST, "/diagnostics/crash/ack", None)
+        .expect("crash ack should map over HTTP");
+    assert_eq!(method, Method::POST);
+    assert_eq!(path, "/api/v1/diagnostics/crash/ack");
+}
+
+#[test]
+fn parses_addr2line_output_with_inlined_frames() {
+    let symbols = parse_addr2line_output(
+        "0x42001234: core::panicking::panic at /rustc/abc/library/core/src/panicking.rs:75:14\n\
+         0x42005678: isolapurr_usb_hub::pd_i2c::apply at src/pd_i2c.rs:120:9\n \
+         (inlined by) main_loop at src/bin/main.rs:250:5\n\
+         0x40379999: ?? ??:0\n",
+    );
+    assert_eq!(symbols.len(), 3);
+    assert_eq!(symbols[0]["function"], "core::panicking::panic");
+    assert_eq!(symbols[1]["location"], "src/pd_i2c.rs:120:9");
+    assert_eq!(
+        symbols[1]["inlined_by"],
+        json!(["main_loop at src/bin/main.rs:250:5"])
+    );
+    assert_eq!(symbols[2]["function"], "??");
+    assert!(symbols[2]["location"].is_null());
+}
+
+#[test]
+fn crash_human_output_shows_message_location_and_symbols() {
+    let rendered = format_human_output(&json!({
+        "reason": "panic",
+        "hw_code": 12,
+        "unacknowledged": 1,
+        "crash": {
+            "reason": "panic",
+            "hw_code": 12,
+            "uptime_ms": 12345,
+            "message": "index out of bounds",
+            "location": {"file": "src/bin/main.rs", "line": 42, "column": 7},
+            "backtrace": ["0x42001234", "0x42005678"]
+        },
+        "symbols": [{
+            "address": "0x42001234",
+            "function": "isolapurr_usb_hub::apply",
+            "location": "src/pd_i2c.rs:120:9",
+            "inlined_by": ["main_loop at src/bin/main.rs:250:5"]
+        }],
+        "acknowledged": 1
+    }));
+    assert!(rendered.contains("Last reset: panic (hw code 0x0C), 1 unacknowledged crash(es)"));
+    assert!(rendered.contains("Crash: panic after 12.3 s uptime"));
+    assert!(rendered.contains("  at src/bin/main.rs:42:7"));
+    assert!(rendered.contains("  0x42001234 isolapurr_usb_hub::apply at src/pd_i2c.rs:120:9"));
+    assert!(rendered.contains("      inlined by main_loop at src/bin/main.rs:250:5"));
+    assert!(rendered.contains("\n  0x42005678\n"));
+    assert!(rendered.contains("Acknowledged 1 crash report(s)"));
+
+    let rendered = format_human_output(&json!({
+        "reason": "power_on", "hw_code": 1, "unacknowledged": 0, "crash": null
+    }));
+    assert!(rendered.contains("No crash report stored"));
+}
@@ -0,0 +1,196 @@
+use super::{
+    Cli, Command, DisplayCommand, DisplayScreenshot, DisplaySetArgs, ScreenshotFileFormat, adler32,
+    crc32, display_set_body, format_human_output, map_devd_ipc_endpoint, map_http_endpoint,
+};
+use clap::Parser as _;
+use reqwest::Method;
+use serde_json::json;
+
+#[test]
+fn display_set_body_only_sends_requested_fields() {
+    let args = DisplaySetArgs {
+        brightness: Some(40),
+        off_after: Some(600),
+        rotation: Some("180".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        display_set_body(&args).expect("body should build"),
+        json!({"brightness_pct": 40, "off_after_s": 600, "rotation": 180})
+    );
+
+    let inverted = DisplaySetArgs {
+        dim_after: Some(300),
+        off_after: Some(60),
+        ..Default::default()
+    };
+    assert!(display_set_body(&inverted).is_err());
+
+    let too_short = DisplaySetArgs {
+        dim_after: Some(5),
+        ..Default::default()
+    };
+    assert!(display_set_body(&too_short).is_err());
+    assert!(display_set_body(&DisplaySetArgs::default()).is_err());
+}
+
+#[test]
+fn display_cli_validates_ranges_and_choices() {
+    let cli = Cli::try_parse_from(["isolapurr", "display", "set", "--theme", "dark"])
+        .expect("theme should parse");
+    let Command::Display {
+        command: DisplayCommand::Set(args),
+    } = cli.command
+    else {
+        panic!("expected display set");
+    };
+    assert_eq!(args.theme.as_deref(), Some("dark"));
+
+    assert!(Cli::try_parse_from(["isolapurr", "display", "set", "--brightness", "4"]).is_err());
+    assert!(Cli::try_parse_from(["isolapurr", "display", "set", "--rotation", "90"]).is_err());
+    assert!(Cli::try_parse_from(["isolapurr", "display", "set", "--off-after", "7201"]).is_err());
+}
+
+#[test]
+fn maps_display_endpoints_for_http_and_devd() {
+    let (method, path, body) =
+        map_http_endpoint(Method::PUT, "/display", Some(json!({"theme": "dark"})))
+            .expect("display set should map");
+    assert_eq!(method, Method::PUT);
+    assert_eq!(path, "/api/v1/display");
+    assert_eq!(body, Some(json!({"theme": "dark"})));
+
+    let (_, path, _) = map_http_endpoint(Method::POST, "/display/defaults", None)
+        .expect("display defaults should map");
+    assert_eq!(path, "/api/v1/display/defaults");
+
+    let (method, params) = map_devd_ipc_endpoint(
+        Method::PUT,
+        "/api/v1/devices/usb--dev-cu-usbmodem101/display",
+        Some(json!({"brightness_pct": 25})),
+    )
+    .expect("devd display set should map");
+    assert_eq!(method, "device.display.set");
+    assert_eq!(params["device_id"], "usb--dev-cu-usbmodem101");
+    assert_eq!(params["brightness_pct"], 25);
+}
+
+#[test]
+fn display_human_output_summarizes_settings() {
+    let rendered = format_human_output(&json!({
+        "brightness_pct": 75,
+        "dim_after_s": 300,
+        "off_after_s": 0,
+        "rotation": 180,
+        "theme": "dark",
+        "backlight": "dim",
+        "persisted": true
+    }));
+
+    assert!(rendered.contains("Brightness: 75% (backlight dim)"));
+    assert!(rendered.contains("Idle: dim after 5 min, off after never"));
+    assert!(rendered.contains("Rotation: 180°, theme dark"));
+    assert!(rendered.contains("Saved: yes"));
+}
+
+fn sample_screenshot() -> DisplayScreenshot {
+    DisplayScreenshot {
+        width: 3,
+        height: 2,
+        rgb565: vec![0xF800, 0x07E0, 0x001F, 0xFFFF, 0x0000, 0x8410],
+    }
+}
+
+#[test]
+fn screenshot_decodes_jsonl_base64_and_round_trips_through_bmp() {
+    use base64::Engine as _;
+
+    let screenshot = sample_screenshot();
+    let bytes = screenshot
+        .rgb565
+        .iter()
+        .flat_map(|pixel| pixel.to_le_bytes())
+        .collect::<Vec<_>>();
+    let decoded = DisplayScreenshot::from_jsonl_result(&json!({
+        "width": 3,
+        "height": 2,
+        "format": "rgb565le",
+        "data": base64::engine::general_purpose::STANDARD.encode(&bytes),
+    }))
+    .expect("jsonl screenshot should decode");
+    assert_eq!(decoded, screenshot);
+
+    // Three 16-bit pixels pad each BMP row from 6 to 8 bytes.
+    let bmp = screenshot.encode_bmp();
+    assert_eq!(bmp.len(), 66 + 8 * 2);
+    assert_eq!(
+        DisplayScreenshot::from_bmp(&bmp).expect("bmp should decode"),
+        screenshot
+    );
+
+    assert!(
+        DisplayScreenshot::from_jsonl_result(&json!({
+            "width": 3,
+            "height": 2,
+            "format": "rgb565le",
+            "data": "AAAA",
+        }))
+        .is_err()
+    );
+}
+
+#[test]
+fn screenshot_png_is_well_formed() {
+    let png = sample_screenshot().encode_png();
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    assert_eq!(&png[12..16], b"IHDR");
+    assert_eq!(u32::from_be_bytes(png[16..20].try_into().unwrap()), 3);
+    assert_eq!(u32::from_be_bytes(png[20..24].try_into().unwrap()), 2);
+    assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+
+    // Stored deflate carries the filtered rows verbatim: red then green.
+    let idat = png
+        .windows(4)
+        .position(|window| window == b"IDAT")
+        .expect("png should have IDAT");
+    assert_eq!(
+        &png[idat + 4 + 7..idat + 4 + 14],
+        &[0, 255, 0, 0, 0, 255, 0]
+    );
+
+    assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+    assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+}
+
+#[test]
+fn screenshot_cli_picks_format_from_extension() {
+    let cli = Cli::try_parse_from([
+        "isolapurr",
+        "display",
+        "screenshot",
+        "--url",
+        "192.168.1.20",
+        "out.png",
+    ])
+    .expect("screenshot should parse");
+    let Command::Display {
+        command: DisplayCommand::Screenshot { output, .. },
+    } = cli.command
+    else {
+        panic!("expected display screenshot");
+    };
+    assert_eq!(
+        ScreenshotFileFormat::from_path(&output).expect("png"),
+        ScreenshotFileFormat::Png
+    );
+    assert!(ScreenshotFileFormat::from_path(std::path::Path::new("shot.BMP")).is_ok());
+    assert!(ScreenshotFileFormat::from_path(std::path::Path::new("shot.jpg")).is_err());
+
+    let (method, _) = map_devd_ipc_endpoint(
+        Method::GET,
+        "/api/v1/devices/usb--dev-cu-usbmodem101/display/screenshot",
+        None,
+    )
+    .expect("devd screenshot should map");
+    assert_eq!(method, "device.display.screenshot");
+}
@@ -0,0 +1,144 @@
+use super::{
+    Cli, Command, PowerCommand, format_human_output, map_devd_ipc_endpoint, map_http_endpoint,
+};
+use clap::Parser as _;
+use reqwest::Method;
+use serde_json::json;
+
+#[test]
+fn fast_charge_probe_cli_parses_modes() {
+    let cli = Cli::try_parse_from([
+        "isolapurr",
+        "power",
+        "fast-charge-probe",
+        "--device-id",
+        "hub",
+        "--yes",
+        "--no-wait",
+    ])
+    .expect("fast-charge-probe should parse");
+    let Command::Power {
+        command: PowerCommand::FastChargeProbe(args),
+    } = cli.command
+    else {
+        panic!("expected power fast-charge-probe");
+    };
+    assert_eq!(args.selector.device_id.as_deref(), Some("hub"));
+    assert!(args.yes && args.no_wait);
+    assert!(!args.status && !args.cancel);
+
+    assert!(
+        Cli::try_parse_from([
+            "isolapurr",
+            "power",
+            "fast-charge-probe",
+            "--status",
+            "--cancel"
+        ])
+        .is_err()
+    );
+    assert!(
+        Cli::try_parse_from([
+            "isolapurr",
+            "power",
+            "fast-charge-probe",
+            "--cancel",
+            "--yes"
+        ])
+        .is_err()
+    );
+}
+
+#[test]
+fn maps_fast_charge_probe_endpoints_for_devd_and_http() {
+    let (method, params) = map_devd_ipc_endpoint(
+        Method::GET,
+        "/api/v1/devices/usb--dev-cu-usbmodem101/power/fast-charge-probe",
+        None,
+    )
+    .expect("fast-charge probe get should map");
+    assert_eq!(method, "device.power.fast_charge_probe_get");
+    assert_eq!(params, json!({"device_id": "usb--dev-cu-usbmodem101"}));
+
+    for (action, expected) in [
+        ("run", "device.power.fast_charge_probe_run"),
+        ("cancel", "device.power.fast_charge_probe_cancel"),
+    ] {
+        let (method, params) = map_devd_ipc_endpoint(
+            Method::POST,
+            &format!("/api/v1/devices/hub/power/fast-charge-probe/{action}?owner=7"),
+            None,
+        )
+        .expect("fast-charge probe action should map");
+        assert_eq!(method, expected);
+        assert_eq!(params, json!({"device_id": "hub", "owner": 7}));
+
+        let (method, path, _) = map_http_endpoint(
+            Method::POST,
+            &format!("/power/fast-charge-probe/{action}?owner=7"),
+            None,
+        )
+        .expect("fast-charge probe action should map over HTTP");
+        assert_eq!(method, Method::POST);
+        assert_eq!(
+            path,
+            format!("/api/v1/power/fast-charge-probe/{action}?owner=7")
+        );
+    }
+
+    let (method, path, _) = map_http_endpoint(Method::GET, "/power/fast-charge-probe", None)
+        .expect("fast-charge probe get should map over HTTP");
+    assert_eq!(method, Method::GET);
+    assert_eq!(path, "/api/v1/power/fast-charge-probe");
+}
+
+#[test]
+fn formats_complete_and_aborted_probe_reports() {
+    let text = format_human_output(&json!({
+        "state": "complete",
+        "family": null,
+        "error": null,
+        "started_uptime_ms": 12_000,
+        "step_count": 10,
+        "results": [
+            {"family": "pd", "supported": true, "negotiated": "pd", "voltage_mv": 9_020, "current_ma": 2_010},
+            {"family": "pps", "supported": false, "negotiated": "pd", "voltage_mv": 9_010, "current_ma": 1_990},
+            {"family": "scp", "supported": false, "negotiated": null, "voltage_mv": null, "current_ma": null}
+        ]
+    }));
+    assert_eq!(
+        text,
+        "Fast-charge probe: complete, 1/10 families supported\n\
+         Family  OK   Negotiated   Voltage Current\n\
+         PD      yes  PD            9.02 V  2.01 A\n\
+         PPS     no   PD            9.01 V  1.99 A\n\
+         SCP     no   none               -       -\n"
+    );
+
+    let text = format_human_output(&json!({
+        "state": "aborted",
+        "family": null,
+        "error": "detached",
+        "started_uptime_ms": 12_000,
+        "step_count": 10,
+        "results": []
+    }));
+    assert_eq!(
+        text,
+        "Fast-charge probe: aborted after 0/10 families: The USB-C sink detached during the fast-charge probe\n"
+    );
+
+    let text = format_human_output(&json!({
+        "state": "running",
+        "family": "qc30",
+        "error": null,
+        "started_uptime_ms": 12_000,
+        "step_count": 10,
+        "results": [
+            {"family": "pd", "supported": true, "negotiated": "pd", "voltage_mv": 9_020, "current_ma": 2_010},
+            {"family": "pps", "supported": true, "negotiated": "pps", "voltage_mv": 8_800, "current_ma": 2_200},
+            {"family": "qc20", "supported": false, "negotiated": "bc12", "voltage_mv": 5_050, "current_ma": 1_000}
+        ]
+    }));
+    assert!(text.starts_with("Fast-charge probe: running, offering QC3.0 (4/10)\n"));
+}
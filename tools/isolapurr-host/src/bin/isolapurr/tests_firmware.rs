@@ -0,0 +1,304 @@
+use super::{
+    Cli, Command, FirmwareChannel, FirmwareCommand, FirmwareSource, FirmwareUpdateAction,
+    ReleaseManifest, cache_release, changelog_between, channel_releases, find_release,
+    firmware_check_status, firmware_update_action, format_firmware_check_output,
+    format_firmware_update_output, read_release_manifest,
+};
+use clap::Parser as _;
+use serde_json::{Value, json};
+use sha2::Digest as _;
+use std::path::Path;
+
+const APP_IMAGE: &[u8] = b"isolapurr app image v0.6.0";
+
+fn manifest_json(app_sha256: &str) -> Value {
+    let release = |tag: &str, version: &str, prerelease: bool, notes: Option<&str>| {
+        json!({
+            "tagName": tag,
+            "version": version,
+            "publishedAt": "2026-10-01T12:00:00Z",
+            "prerelease": prerelease,
+            "notes": notes,
+            "catalogPath": format!("firmware/releases/{tag}/isolapurr-firmware-catalog.json"),
+            "app": {
+                "artifactId": format!("esp32s3-app-{tag}"),
+                "assetPath": format!("firmware/releases/{tag}/isolapurr-usb-hub.app.bin"),
+                "fileName": "isolapurr-usb-hub.app.bin",
+                "fileKind": "app_bin",
+                "flashAddress": 65536,
+                "sha256": app_sha256,
+                "size": APP_IMAGE.len(),
+            },
+            "recovery": null,
+        })
+    };
+    json!({
+        "schemaVersion": "1",
+        "releases": [
+            release("v0.7.0-dev.1", "0.7.0-dev.1", true, Some("- Try new PD retry")),
+            release("v0.6.0", "0.6.0", false, Some("- Charge-only ports\n- Faster boot")),
+            release("v0.5.1", "0.5.1", false, None),
+            release("v0.5.0", "0.5.0", false, Some("- First release")),
+        ],
+    })
+}
+
+fn manifest() -> ReleaseManifest {
+    serde_json::from_value(manifest_json("00")).expect("manifest fixture")
+}
+
+fn write_web_bundle(root: &Path, app_sha256: &str) {
+    let release_dir = root.join("firmware/releases/v0.6.0");
+    std::fs::create_dir_all(&release_dir).expect("release dir");
+    std::fs::write(
+        root.join("firmware/releases-manifest.json"),
+        serde_json::to_vec(&manifest_json(app_sha256)).expect("encode manifest"),
+    )
+    .expect("write manifest");
+    std::fs::write(release_dir.join("isolapurr-usb-hub.app.bin"), APP_IMAGE).expect("write app");
+    let catalog = json!({
+        "schemaVersion": "1",
+        "artifacts": [{
+            "artifactId": "esp32s3-app-v0.6.0",
+            "target": "esp32s3_app",
+            "version": "0.6.0",
+            "files": [{
+                "kind": "app_bin",
+                "path": "isolapurr-usb-hub.app.bin",
+                "sha256": app_sha256,
+                "size": APP_IMAGE.len(),
+                "flashAddress": 65536,
+            }],
+        }],
+    });
+    std::fs::write(
+        release_dir.join("isolapurr-firmware-catalog.json"),
+        serde_json::to_vec(&catalog).expect("encode catalog"),
+    )
+    .expect("write catalog");
+}
+
+/// Serves `root` over HTTP the way the published web bundle is served.
+async fn serve_web_bundle(root: &Path) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind stand-in");
+    let addr = listener.local_addr().expect("stand-in addr");
+    let router = axum::Router::new().fallback_service(tower_http::services::ServeDir::new(root));
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.expect("stand-in serve");
+    });
+    format!("http://{addr}/firmware/releases-manifest.json")
+}
+
+#[test]
+fn firmware_commands_parse_channel_catalog_and_force() {
+    let cli = Cli::try_parse_from([
+        "isolapurr",
+        "firmware",
+        "check",
+        "--channel",
+        "prerelease",
+        "--catalog",
+        "web/public/firmware/releases-manifest.json",
+    ])
+    .expect("firmware check should parse");
+    let Command::Firmware {
+        command: FirmwareCommand::Check(args),
+    } = cli.command
+    else {
+        panic!("expected firmware check");
+    };
+    assert_eq!(args.source.channel, FirmwareChannel::Prerelease);
+    assert!(args.selector.is_empty());
+
+    let cli = Cli::try_parse_from([
+        "isolapurr",
+        "firmware",
+        "update",
+        "--port-path",
+        "/dev/ttyACM0",
+        "--version",
+        "0.5.0",
+        "--force",
+    ])
+    .expect("firmware update should parse");
+    let Command::Firmware {
+        command: FirmwareCommand::Update(args),
+    } = cli.command
+    else {
+        panic!("expected firmware update");
+    };
+    assert_eq!(args.source.channel, FirmwareChannel::Stable);
+    assert!(args.source.catalog.starts_with("https://"));
+    assert!(args.force && !args.real);
+}
+
+#[test]
+fn firmware_channels_order_releases_and_collect_changelog() {
+    let manifest = manifest();
+    let stable = channel_releases(&manifest, FirmwareChannel::Stable);
+    assert_eq!(
+        stable
+            .iter()
+            .map(|release| release.version.as_str())
+            .collect::<Vec<_>>(),
+        ["0.6.0", "0.5.1", "0.5.0"]
+    );
+    let prerelease = channel_releases(&manifest, FirmwareChannel::Prerelease);
+    assert_eq!(prerelease[0].version, "0.7.0-dev.1");
+
+    let latest = stable[0];
+    let changelog = changelog_between(&stable, Some("0.5.0"), latest);
+    assert_eq!(
+        changelog
+            .iter()
+            .map(|entry| entry["version"].clone())
+            .collect::<Vec<_>>(),
+        [json!("0.6.0"), json!("0.5.1")]
+    );
+    assert_eq!(changelog_between(&stable, None, latest).len(), 1);
+    assert!(changelog_between(&stable, Some("0.6.0"), latest).is_empty());
+
+    assert_eq!(
+        firmware_check_status(Some("0.5.0"), Some(latest)),
+        "update_available"
+    );
+    assert_eq!(
+        firmware_check_status(Some("v0.6.0"), Some(latest)),
+        "up_to_date"
+    );
+    assert_eq!(
+        firmware_check_status(Some("0.7.0-dev.1"), Some(latest)),
+        "ahead"
+    );
+    assert_eq!(firmware_check_status(None, Some(latest)), "unknown_version");
+    assert_eq!(firmware_check_status(Some("0.5.0"), None), "no_release");
+
+    assert_eq!(
+        find_release(&stable, "v0.5.1").expect("by tag").version,
+        "0.5.1"
+    );
+    assert_eq!(
+        find_release(&stable, "0.5.0").expect("by version").tag_name,
+        "v0.5.0"
+    );
+    assert!(find_release(&stable, "0.7.0-dev.1").is_err());
+}
+
+#[test]
+fn firmware_update_refuses_downgrades_unless_forced() {
+    assert_eq!(
+        firmware_update_action(Some("0.5.0"), "0.6.0", false).expect("upgrade"),
+        FirmwareUpdateAction::Upgrade
+    );
+    assert_eq!(
+        firmware_update_action(Some("0.6.0-dev.3"), "0.6.0", false).expect("release"),
+        FirmwareUpdateAction::Upgrade
+    );
+    let err = firmware_update_action(Some("0.6.0"), "0.5.0", false).expect_err("downgrade");
+    assert!(err.to_string().contains("--force"));
+    assert!(firmware_update_action(Some("0.6.0"), "0.6.0", false).is_err());
+    assert!(firmware_update_action(None, "0.6.0", false).is_err());
+    assert_eq!(
+        firmware_update_action(Some("0.6.0"), "0.5.0", true).expect("forced"),
+        FirmwareUpdateAction::Downgrade
+    );
+    assert_eq!(
+        firmware_update_action(None, "0.6.0", true).expect("forced"),
+        FirmwareUpdateAction::Install
+    );
+}
+
+#[test]
+fn firmware_source_resolves_assets_from_the_web_root() {
+    let url = FirmwareSource::parse("https://example.test/site/firmware/releases-manifest.json")
+        .expect("url source");
+    assert_eq!(
+        url.asset("firmware/releases/v0.6.0/app.bin")
+            .expect("asset url")
+            .to_string(),
+        "https://example.test/site/firmware/releases/v0.6.0/app.bin"
+    );
+    let path =
+        FirmwareSource::parse("web/public/firmware/releases-manifest.json").expect("path source");
+    assert_eq!(
+        path.asset("firmware/releases/v0.6.0/app.bin")
+            .expect("asset path"),
+        FirmwareSource::Path("web/public/firmware/releases/v0.6.0/app.bin".into())
+    );
+    assert!(url.asset("firmware/../../secret").is_err());
+}
+
+#[tokio::test]
+async fn firmware_release_downloads_from_an_http_catalog_and_verifies_hashes() {
+    let web = tempfile::tempdir().expect("web root");
+    let sha256 = format!("{:x}", sha2::Sha256::digest(APP_IMAGE));
+    write_web_bundle(web.path(), &sha256);
+    let source = FirmwareSource::parse(&serve_web_bundle(web.path()).await).expect("source");
+    let client = reqwest::Client::new();
+
+    let manifest = read_release_manifest(&client, &source)
+        .await
+        .expect("manifest should download");
+    let stable = channel_releases(&manifest, FirmwareChannel::Stable);
+    let cache = tempfile::tempdir().expect("cache");
+    let catalog_path = cache_release(&client, &source, stable[0], cache.path())
+        .await
+        .expect("release should cache");
+    assert_eq!(
+        catalog_path,
+        cache.path().join("v0.6.0/isolapurr-firmware-catalog.json")
+    );
+    assert_eq!(
+        std::fs::read(cache.path().join("v0.6.0/isolapurr-usb-hub.app.bin")).expect("cached app"),
+        APP_IMAGE
+    );
+
+    write_web_bundle(web.path(), &"0".repeat(64));
+    let tampered = read_release_manifest(&client, &source)
+        .await
+        .expect("manifest");
+    let stable = channel_releases(&tampered, FirmwareChannel::Stable);
+    let err = cache_release(&client, &source, stable[0], cache.path())
+        .await
+        .expect_err("hash mismatch should fail");
+    assert!(err.to_string().contains("hash mismatch"));
+}
+
+#[test]
+fn firmware_output_lists_devices_and_changelog() {
+    let check = json!({
+        "firmware_channel": "stable",
+        "latest": {"version": "0.6.0", "tag": "v0.6.0"},
+        "devices": [
+            {
+                "name": "Bench hub",
+                "current_version": "0.5.0",
+                "status": "update_available",
+                "changelog": [{
+                    "version": "0.6.0",
+                    "published_at": "2026-10-01T12:00:00Z",
+                    "notes": "- Charge-only ports",
+                }],
+            },
+            {"name": "Desk hub", "status": "unreachable", "error": "timed out"},
+        ],
+    });
+    let text = format_firmware_check_output(&check);
+    assert!(text.contains("Latest stable firmware: 0.6.0"));
+    assert!(text.contains("Bench hub: 0.5.0 update available"));
+    assert!(text.contains("  0.6.0 (2026-10-01)\n    - Charge-only ports"));
+    assert!(text.contains("Desk hub: unreachable (timed out)"));
+
+    let update = json!({
+        "firmware_update": "0.6.0",
+        "from_version": "0.5.0",
+        "action": "upgrade",
+        "real": false,
+        "changelog": [],
+    });
+    assert!(
+        format_firmware_update_output(&update).contains("Dry run: would upgrade 0.5.0 -> 0.6.0")
+    );
+}
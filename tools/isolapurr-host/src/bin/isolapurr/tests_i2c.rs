@@ -0,0 +1,115 @@
+use super::{
+    Cli, Command, DiagnosticsCommand, I2cCommand, format_human_output, map_devd_ipc_endpoint,
+    map_http_endpoint,
+};
+use clap::Parser as _;
+use reqwest::Method;
+use serde_json::json;
+
+#[test]
+fn i2c_recover_cli_requires_a_known_bus() {
+    let cli = Cli::try_parse_from([
+        "isolapurr",
+        "diagnostics",
+        "i2c",
+        "recover",
+        "--bus",
+        "pd",
+        "--yes",
+    ])
+    .expect("recover should parse");
+    let Command::Diagnostics {
+        command:
+            DiagnosticsCommand::I2c {
+                command: I2cCommand::Recover { bus, yes, .. },
+            },
+    } = cli.command
+    else {
+        panic!("expected diagnostics i2c recover");
+    };
+    assert_eq!(bus, "pd");
+    assert!(yes);
+
+    assert!(
+        Cli::try_parse_from(["isolapurr", "diagnostics", "i2c", "recover", "--bus", "spi"])
+            .is_err()
+    );
+    assert!(Cli::try_parse_from(["isolapurr", "diagnostics", "i2c", "recover"]).is_err());
+}
+
+#[test]
+fn maps_i2c_endpoints_for_http_and_devd() {
+    let (method, path, _) = map_http_endpoint(Method::GET, "/diagnostics/i2c", None)
+        .expect("i2c diagnostics should map");
+    assert_eq!(method, Method::GET);
+    assert_eq!(path, "/api/v1/diagnostics/i2c");
+
+    let (method, path, _) = map_http_endpoint(
+        Method::POST,
+        "/diagnostics/i2c/recover?bus=system&owner=7",
+        None,
+    )
+    .expect("i2c recover should map");
+    assert_eq!(method, Method::POST);
+    assert_eq!(path, "/api/v1/diagnostics/i2c/recover?bus=system&owner=7");
+
+    let (method, _) = map_devd_ipc_endpoint(
+        Method::POST,
+        "/api/v1/devices/usb--dev-cu-usbmodem101/diagnostics/i2c/scan",
+        None,
+    )
+    .expect("devd i2c scan should map");
+    assert_eq!(method, "device.i2c.scan");
+
+    let (method, params) = map_devd_ipc_endpoint(
+        Method::POST,
+        "/api/v1/devices/usb--dev-cu-usbmodem101/diagnostics/i2c/recover?bus=pd&owner=42",
+        None,
+    )
+    .expect("devd i2c recover should map");
+    assert_eq!(method, "device.i2c.recover");
+    assert_eq!(params["bus"], "pd");
+    assert_eq!(params["owner"], 42);
+}
+
+#[test]
+fn i2c_human_output_lists_counters_scan_and_recovery() {
+    let rendered = format_human_output(&json!({
+        "buses": [{
+            "bus": "system",
+            "sda_gpio": 8,
+            "scl_gpio": 9,
+            "available": true,
+            "lines": {"sda_high": false, "scl_high": true, "idle": false},
+            "recoveries": 1,
+            "rejected": 0,
+            "addresses": [{
+                "address": 116,
+                "device": "tps55288",
+                "transactions": 1200,
+                "errors": 3,
+                "nack": 1,
+                "timeout": 2,
+                "arbitration": 0,
+                "other": 0
+            }]
+        }],
+        "last_scan": {
+            "uptime_ms": 5000,
+            "results": [{"bus": "pd", "address": 60, "device": "sw2303", "result": "skipped"}]
+        },
+        "last_recovery": {
+            "bus": "system",
+            "uptime_ms": 6000,
+            "recovered": false,
+            "lines": {"sda_high": false, "scl_high": true, "idle": false}
+        },
+        "sample_uptime_ms": 7000
+    }));
+
+    assert!(rendered.contains("I2C system (GPIO8 SDA / GPIO9 SCL): SDA LOW SCL high"));
+    assert!(rendered.contains("0x74 tps55288"));
+    assert!(rendered.contains("(nack 1, timeout 2, arb 0, other 0)"));
+    assert!(rendered.contains("pd     0x3C sw2303       skipped"));
+    assert!(rendered.contains("Last recovery: system bus at 6000 ms, FAILED (SDA LOW SCL high)"));
+}
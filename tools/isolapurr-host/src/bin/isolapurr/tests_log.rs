@@ -0,0 +1,187 @@
+use super::{
+    Cli, Command, LogArgs, LogFormatArg, LogPortSample, LogSample, LogWriter,
+    accumulate_log_energy, log_csv_header, log_csv_row, log_part_path, log_stop_reason,
+    parse_log_duration, parse_log_trigger,
+};
+use clap::Parser as _;
+use isolapurr_api::ports::{PortId, TelemetryStatus};
+use std::path::Path;
+use std::time::Duration;
+
+fn sample(elapsed_ms: u64, port_c_ma: u32) -> LogSample {
+    LogSample {
+        host_unix_ms: 1_760_000_000_000 + elapsed_ms,
+        elapsed_ms,
+        ports: vec![
+            LogPortSample {
+                port: PortId::PortA,
+                power_enabled: true,
+                status: TelemetryStatus::NotInserted,
+                voltage_mv: None,
+                current_ma: None,
+                power_mw: None,
+                energy_mwh: 0.0,
+            },
+            LogPortSample {
+                port: PortId::PortC,
+                power_enabled: true,
+                status: TelemetryStatus::Ok,
+                voltage_mv: Some(9000),
+                current_ma: Some(port_c_ma),
+                power_mw: Some(9 * port_c_ma),
+                energy_mwh: 0.0,
+            },
+        ],
+        pd_protocol: Some("pd".to_string()),
+        pd_request_mv: Some(9000),
+        pd_request_ma: Some(3000),
+        tps_output_enabled: Some(true),
+        tps_setpoint_mv: Some(9000),
+        tps_iout_limit_ma: Some(3100),
+        thermal_state: "normal".to_string(),
+        thermal_reason: "none".to_string(),
+        mcu_temp_deci_c: Some(412),
+        tmp112_temp_deci_c: None,
+        thermal_power_watts: 100,
+    }
+}
+
+fn log_args(extra: &[&str]) -> LogArgs {
+    let mut argv = vec!["isolapurr", "log", "--url", "10.0.0.7", "--out", "run.csv"];
+    argv.extend_from_slice(extra);
+    match Cli::try_parse_from(argv)
+        .expect("log args should parse")
+        .command
+    {
+        Command::Log(args) => args,
+        _ => panic!("expected log command"),
+    }
+}
+
+#[test]
+fn log_durations_need_a_unit() {
+    assert_eq!(parse_log_duration("100ms"), Ok(Duration::from_millis(100)));
+    assert_eq!(parse_log_duration("1.5s"), Ok(Duration::from_millis(1500)));
+    assert_eq!(parse_log_duration("2m"), Ok(Duration::from_secs(120)));
+    assert_eq!(parse_log_duration("1h"), Ok(Duration::from_secs(3600)));
+    assert!(parse_log_duration("100").is_err());
+    assert!(parse_log_duration("5d").is_err());
+
+    let args = log_args(&["--interval", "100ms", "--stop-when", "port_c.current_ma<50"]);
+    assert_eq!(args.interval, Duration::from_millis(100));
+    assert_eq!(args.energy_port, PortId::PortC);
+    assert!(
+        Cli::try_parse_from(["isolapurr", "log", "--out", "x.csv", "--stop-hold", "5s"]).is_err()
+    );
+}
+
+#[test]
+fn log_trigger_parses_and_needs_an_ok_reading() {
+    let trigger = parse_log_trigger("port_c.current_ma<50").expect("trigger should parse");
+    assert_eq!(trigger.label(), "port_c.current_ma<50");
+    assert!(trigger.matches(&sample(0, 20)));
+    assert!(!trigger.matches(&sample(0, 80)));
+
+    let above = parse_log_trigger("port_a.voltage_mv>4000").expect("trigger should parse");
+    assert!(!above.matches(&sample(0, 0)), "not_inserted never matches");
+
+    assert!(parse_log_trigger("port_c.current<50").is_err());
+    assert!(parse_log_trigger("port_b.current_ma<50").is_err());
+    assert!(parse_log_trigger("port_c.current_ma=50").is_err());
+}
+
+#[test]
+fn log_stop_trigger_must_hold() {
+    let args = log_args(&["--stop-when", "port_c.current_ma<50", "--stop-hold", "2s"]);
+    let totals = [(PortId::PortA, 0.0), (PortId::PortC, 0.0)];
+    let mut since = None;
+    assert_eq!(
+        log_stop_reason(&args, &sample(0, 20), &totals, &mut since),
+        None
+    );
+    assert_eq!(
+        log_stop_reason(&args, &sample(1000, 80), &totals, &mut since),
+        None
+    );
+    assert_eq!(since, None, "a miss restarts the hold");
+    assert_eq!(
+        log_stop_reason(&args, &sample(2000, 20), &totals, &mut since),
+        None
+    );
+    assert_eq!(
+        log_stop_reason(&args, &sample(4000, 20), &totals, &mut since).as_deref(),
+        Some("port_c.current_ma<50 held for 2.0 s")
+    );
+
+    let energy = log_args(&["--energy-mwh", "10"]);
+    let done = [(PortId::PortA, 0.0), (PortId::PortC, 10.5)];
+    assert_eq!(
+        log_stop_reason(&energy, &sample(0, 0), &done, &mut None).as_deref(),
+        Some("port_c reached 10 mWh")
+    );
+}
+
+#[test]
+fn log_energy_skips_gaps() {
+    let mut totals = vec![(PortId::PortA, 0.0), (PortId::PortC, 0.0)];
+    let mut first = sample(0, 1000);
+    accumulate_log_energy(&mut totals, None, &mut first);
+    // 9 W for 400 ms = 1 mWh.
+    let mut second = sample(400, 1000);
+    accumulate_log_energy(&mut totals, Some(&first), &mut second);
+    assert!((second.ports[1].energy_mwh - 1.0).abs() < 1e-9);
+    assert_eq!(second.ports[0].energy_mwh, 0.0);
+
+    // After an outage there is no previous sample, so the gap adds nothing.
+    let mut resumed = sample(60_000, 1000);
+    accumulate_log_energy(&mut totals, None, &mut resumed);
+    assert!((resumed.ports[1].energy_mwh - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn log_csv_rows_line_up_with_the_header() {
+    let header = log_csv_header();
+    let row = log_csv_row(&sample(100, 500));
+    assert_eq!(header.split(',').count(), row.split(',').count());
+    assert!(header.starts_with("host_unix_ms,elapsed_ms,port_a_power_enabled,port_a_status"));
+    assert!(row.contains(",true,not_inserted,,,,0.000,true,ok,9000,500,4500,0.000,pd,9000,"));
+    assert!(row.ends_with(",normal,none,412,,100"));
+}
+
+#[test]
+fn log_files_rotate_with_numbered_parts() {
+    assert_eq!(
+        log_part_path(Path::new("out/run.csv"), 1),
+        Path::new("out/run.csv")
+    );
+    assert_eq!(
+        log_part_path(Path::new("out/run.csv"), 3),
+        Path::new("out/run.3.csv")
+    );
+    assert_eq!(log_part_path(Path::new("run"), 2), Path::new("run.2"));
+
+    let temp = tempfile::tempdir().expect("temp dir");
+    let out = temp.path().join("run.jsonl");
+    let mut args = log_args(&["--rotate-mb", "1"]);
+    args.out = out.clone();
+    let mut writer = LogWriter::create(&args).expect("writer should open");
+    assert_eq!(writer.format, LogFormatArg::Jsonl);
+    writer.rotate_bytes = Some(1);
+    for elapsed_ms in [0, 100, 200] {
+        writer
+            .write(&sample(elapsed_ms, 500))
+            .expect("write should pass");
+    }
+    assert_eq!(
+        writer.files,
+        [
+            out.clone(),
+            temp.path().join("run.2.jsonl"),
+            temp.path().join("run.3.jsonl")
+        ]
+    );
+    let first = std::fs::read_to_string(&out).expect("first part");
+    let row: serde_json::Value = serde_json::from_str(first.trim()).expect("one JSON row");
+    assert_eq!(row["ports"][1]["port"], "port_c");
+    assert_eq!(row["thermal_state"], "normal");
+}
@@ -0,0 +1,154 @@
+use super::{
+    Cli, Command, LogLevelArg, LogsCommand, SyslogTargetArg, format_human_output, logs_config_body,
+    map_devd_ipc_endpoint, map_http_endpoint, parse_syslog_target,
+};
+use clap::Parser as _;
+use reqwest::Method;
+use serde_json::json;
+
+#[test]
+fn logs_cli_parses_follow_and_config() {
+    let cli = Cli::try_parse_from([
+        "isolapurr",
+        "logs",
+        "--device-id",
+        "hub",
+        "--since",
+        "42",
+        "--follow",
+        "--interval",
+        "500ms",
+    ])
+    .expect("logs should parse");
+    let Command::Logs(args) = cli.command else {
+        panic!("expected logs");
+    };
+    assert_eq!(args.selector.device_id.as_deref(), Some("hub"));
+    assert_eq!(args.since, 42);
+    assert!(args.follow);
+    assert_eq!(args.interval, std::time::Duration::from_millis(500));
+    assert!(args.command.is_none());
+
+    let cli = Cli::try_parse_from([
+        "isolapurr",
+        "logs",
+        "config",
+        "--level",
+        "debug",
+        "--syslog",
+        "192.168.1.20",
+    ])
+    .expect("logs config should parse");
+    let Command::Logs(args) = cli.command else {
+        panic!("expected logs");
+    };
+    let Some(LogsCommand::Config {
+        level,
+        syslog,
+        no_syslog,
+    }) = args.command
+    else {
+        panic!("expected logs config");
+    };
+    assert_eq!(
+        logs_config_body(level, syslog, no_syslog),
+        Some(json!({"level": "debug", "syslog": {"host": "192.168.1.20", "port": 514}}))
+    );
+
+    assert!(
+        Cli::try_parse_from([
+            "isolapurr",
+            "logs",
+            "config",
+            "--syslog",
+            "10.0.0.1",
+            "--no-syslog",
+        ])
+        .is_err()
+    );
+}
+
+#[test]
+fn syslog_target_and_config_body() {
+    assert_eq!(
+        parse_syslog_target("10.0.0.5:1514"),
+        Ok(SyslogTargetArg {
+            host: std::net::Ipv4Addr::new(10, 0, 0, 5),
+            port: 1514,
+        })
+    );
+    assert!(parse_syslog_target("10.0.0.5:0").is_err());
+    assert!(parse_syslog_target("syslog.lan").is_err());
+
+    assert_eq!(logs_config_body(None, None, false), None);
+    assert_eq!(
+        logs_config_body(Some(LogLevelArg::Warn), None, true),
+        Some(json!({"level": "warn", "syslog": null}))
+    );
+}
+
+#[test]
+fn maps_logs_endpoints_for_devd_and_http() {
+    let (method, params) = map_devd_ipc_endpoint(
+        Method::GET,
+        "/api/v1/devices/usb--dev-cu-usbmodem101/logs?since=17",
+        None,
+    )
+    .expect("logs should map");
+    assert_eq!(method, "device.logs.tail");
+    assert_eq!(
+        params,
+        json!({"device_id": "usb--dev-cu-usbmodem101", "since": 17})
+    );
+
+    let (method, params) = map_devd_ipc_endpoint(
+        Method::PUT,
+        "/api/v1/devices/usb--dev-cu-usbmodem101/logs/config",
+        Some(json!({"level": "debug"})),
+    )
+    .expect("logs config should map");
+    assert_eq!(method, "device.logs.config_set");
+    assert_eq!(
+        params,
+        json!({"device_id": "usb--dev-cu-usbmodem101", "level": "debug"})
+    );
+
+    let (method, path, _) =
+        map_http_endpoint(Method::GET, "/logs?since=17", None).expect("logs should map over HTTP");
+    assert_eq!(method, Method::GET);
+    assert_eq!(path, "/api/v1/logs?since=17");
+
+    let (_, path, _) = map_http_endpoint(Method::PUT, "/logs/config", None)
+        .expect("logs config should map over HTTP");
+    assert_eq!(path, "/api/v1/logs/config");
+}
+
+#[test]
+fn formats_log_page_and_config() {
+    let text = format_human_output(&json!({
+        "level": "info",
+        "oldest": 5,
+        "next": 7,
+        "dropped": 2,
+        "more": false,
+        "records": [
+            {"seq": 5, "uptime_ms": 1234, "level": "info", "message": "Wi-Fi link up: ip=192.168.1.30 gw=192.168.1.1"},
+            {"seq": 6, "uptime_ms": 65000, "level": "warn", "message": "Wi-Fi STA disconnected; will retry"},
+        ],
+    }));
+    assert!(text.contains("#5           1.234 s  INFO   Wi-Fi link up"));
+    assert!(text.contains("#6          65.000 s  WARN   Wi-Fi STA disconnected"));
+    assert!(text.contains("2 record(s) overwritten"));
+    assert!(text.ends_with("Next: --since 7\n"));
+
+    let text = format_human_output(&json!({
+        "level": "debug",
+        "ring_len": 64,
+        "persisted": true,
+        "syslog": {"host": "192.168.1.20", "port": 514},
+    }));
+    assert_eq!(
+        text,
+        "Level: debug (64 records kept)\nSyslog: 192.168.1.20:514\nPersisted: true\n"
+    );
+}
@@ -0,0 +1,79 @@
+use super::{
+    Cli, Command, PortDataModeArg, PortsCommand, map_devd_ipc_endpoint, map_http_endpoint,
+};
+use clap::Parser as _;
+use reqwest::Method;
+use serde_json::json;
+
+#[test]
+fn data_cli_parses_modes() {
+    let cli = Cli::try_parse_from([
+        "isolapurr",
+        "ports",
+        "--device-id",
+        "hub",
+        "data",
+        "--port",
+        "port_a",
+        "--mode",
+        "charge-only",
+    ])
+    .expect("ports data should parse");
+    let Command::Ports {
+        command: Some(PortsCommand::Data { port, mode }),
+        ..
+    } = cli.command
+    else {
+        panic!("expected ports data");
+    };
+    assert_eq!(port, "port_a");
+    assert_eq!(mode, PortDataModeArg::ChargeOnly);
+    assert_eq!(mode.as_wire(), "charge_only");
+
+    assert!(
+        Cli::try_parse_from([
+            "isolapurr",
+            "ports",
+            "data",
+            "--port",
+            "port_c",
+            "--mode",
+            "charge_only",
+        ])
+        .is_ok()
+    );
+    assert!(
+        Cli::try_parse_from([
+            "isolapurr",
+            "ports",
+            "data",
+            "--port",
+            "port_c",
+            "--mode",
+            "off"
+        ])
+        .is_err()
+    );
+}
+
+#[test]
+fn maps_data_mode_endpoints_for_devd_and_http() {
+    let (method, params) = map_devd_ipc_endpoint(
+        Method::POST,
+        "/api/v1/devices/usb--dev-cu-usbmodem101/ports/port_c/data?mode=charge_only",
+        None,
+    )
+    .expect("data mode should map");
+    assert_eq!(method, "device.port.data_set");
+    assert_eq!(
+        params,
+        json!({"device_id": "usb--dev-cu-usbmodem101", "port": "port_c", "mode": "charge_only"})
+    );
+
+    let (method, path, body) =
+        map_http_endpoint(Method::POST, "/ports/port_a/data?mode=connected", None)
+            .expect("data mode should map over HTTP");
+    assert_eq!(method, Method::POST);
+    assert_eq!(path, "/api/v1/ports/port_a/data?mode=connected");
+    assert_eq!(body, None);
+}
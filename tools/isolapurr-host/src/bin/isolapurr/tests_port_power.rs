@@ -0,0 +1,91 @@
+use super::{
+    Cli, Command, PortPowerPolicyArg, PortsCommand, format_human_output, map_devd_ipc_endpoint,
+    map_http_endpoint, port_power_policy_body,
+};
+use clap::Parser as _;
+use reqwest::Method;
+use serde_json::json;
+
+#[test]
+fn power_policy_cli_builds_partial_body() {
+    let cli = Cli::try_parse_from([
+        "isolapurr",
+        "ports",
+        "--device-id",
+        "hub",
+        "power-policy",
+        "--usb-c",
+        "last",
+    ])
+    .expect("power-policy should parse");
+    let Command::Ports {
+        command: Some(PortsCommand::PowerPolicy { usb_a, usb_c }),
+        ..
+    } = cli.command
+    else {
+        panic!("expected ports power-policy");
+    };
+    assert_eq!(
+        port_power_policy_body(usb_a, usb_c),
+        Some(json!({"port_c": "last"}))
+    );
+
+    assert_eq!(port_power_policy_body(None, None), None);
+    assert_eq!(
+        port_power_policy_body(Some(PortPowerPolicyArg::Off), Some(PortPowerPolicyArg::On)),
+        Some(json!({"port_a": "off", "port_c": "on"}))
+    );
+    assert!(
+        Cli::try_parse_from([
+            "isolapurr",
+            "ports",
+            "power-policy",
+            "--port-a",
+            "sometimes"
+        ])
+        .is_err()
+    );
+}
+
+#[test]
+fn maps_power_policy_endpoints_for_devd_and_http() {
+    let (method, params) = map_devd_ipc_endpoint(
+        Method::PUT,
+        "/api/v1/devices/usb--dev-cu-usbmodem101/ports/power-policy",
+        Some(json!({"port_a": "off"})),
+    )
+    .expect("power policy should map");
+    assert_eq!(method, "device.ports.power_policy_set");
+    assert_eq!(
+        params,
+        json!({"device_id": "usb--dev-cu-usbmodem101", "port_a": "off"})
+    );
+
+    let (method, _) = map_devd_ipc_endpoint(
+        Method::GET,
+        "/api/v1/devices/usb--dev-cu-usbmodem101/ports/power-policy",
+        None,
+    )
+    .expect("power policy should map");
+    assert_eq!(method, "device.ports.power_policy_get");
+
+    let (method, path, _) = map_http_endpoint(Method::PUT, "/ports/power-policy", None)
+        .expect("power policy should map over HTTP");
+    assert_eq!(method, Method::PUT);
+    assert_eq!(path, "/api/v1/ports/power-policy");
+}
+
+#[test]
+fn formats_power_policy() {
+    let text = format_human_output(&json!({
+        "port_a": "last",
+        "port_c": "off",
+        "last_state": {"port_a": true, "port_c": true},
+        "boot_state": {"port_a": true, "port_c": false},
+        "persisted": true,
+    }));
+    assert_eq!(
+        text,
+        "USB-A: last (next boot: on)\nUSB-C: off (next boot: off)\nPersisted: true\n"
+    );
+}
@@ -1,6 +1,6 @@
 #[test]
 fn power_config_set_updates_only_requested_fields() {
-    let original: CliPowerConfig = serde_json::from_value(json!({
+    let original: PowerConfig = serde_json::from_value(json!({
         "hardware": "sw2303",
         "persisted": true,
         "tps_mode": "auto_follow",
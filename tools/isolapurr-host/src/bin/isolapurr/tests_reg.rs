@@ -0,0 +1,135 @@
+use super::{
+    Cli, Command, DiagnosticsCommand, RegCommand, format_human_output, map_devd_ipc_endpoint,
+};
+use clap::Parser as _;
+use reqwest::Method;
+use serde_json::json;
+
+#[test]
+fn reg_cli_takes_hex_bytes_and_local_usb_only() {
+    let cli = Cli::try_parse_from([
+        "isolapurr",
+        "diagnostics",
+        "reg",
+        "write",
+        "--device-id",
+        "hub",
+        "--target",
+        "tps55288",
+        "--reg",
+        "0x06",
+        "--value",
+        "160",
+        "--yes",
+    ])
+    .expect("reg write should parse");
+    let Command::Diagnostics {
+        command:
+            DiagnosticsCommand::Reg {
+                command:
+                    RegCommand::Write {
+                        args,
+                        reg,
+                        value,
+                        yes,
+                    },
+            },
+    } = cli.command
+    else {
+        panic!("expected diagnostics reg write");
+    };
+    assert_eq!(args.target, "tps55288");
+    assert_eq!((reg, value), (0x06, 0xA0));
+    assert!(yes);
+
+    for args in [
+        &["--target", "ina226", "--reg", "0"][..],
+        &["--target", "sw2303", "--reg", "0x100"],
+        &[
+            "--target",
+            "sw2303",
+            "--reg",
+            "0",
+            "--url",
+            "http://hub.local",
+        ],
+    ] {
+        let mut argv = vec!["isolapurr", "diagnostics", "reg", "read"];
+        argv.extend_from_slice(args);
+        assert!(Cli::try_parse_from(argv).is_err(), "{args:?}");
+    }
+}
+
+#[test]
+fn maps_register_debug_endpoints_for_devd() {
+    let (method, params) = map_devd_ipc_endpoint(
+        Method::GET,
+        "/api/v1/devices/usb--dev-cu-usbmodem101/debug",
+        None,
+    )
+    .expect("debug status should map");
+    assert_eq!(method, "device.debug.status");
+    assert_eq!(params, json!({"device_id": "usb--dev-cu-usbmodem101"}));
+
+    let (method, params) = map_devd_ipc_endpoint(
+        Method::POST,
+        "/api/v1/devices/usb--dev-cu-usbmodem101/debug/unlock",
+        Some(json!({"code": "1234CD"})),
+    )
+    .expect("debug unlock should map");
+    assert_eq!(method, "device.debug.unlock");
+    assert_eq!(params["code"], "1234CD");
+
+    let (method, params) = map_devd_ipc_endpoint(
+        Method::POST,
+        "/api/v1/devices/usb--dev-cu-usbmodem101/debug/registers/dump",
+        Some(json!({"target": "sw2303", "start": 16, "count": 32})),
+    )
+    .expect("register dump should map");
+    assert_eq!(method, "device.debug.reg_dump");
+    assert_eq!(params["target"], "sw2303");
+    assert_eq!(params["count"], 32);
+}
+
+#[test]
+fn register_debug_human_output_covers_dump_write_and_status() {
+    let rendered = format_human_output(&json!({
+        "target": "tps55288",
+        "address": 116,
+        "start": 0,
+        "values": [0, 1, 2, 3, 4, 5, 160, 7]
+    }));
+    assert!(rendered.contains("tps55288 (0x74) registers 0x00..0x07:"));
+    assert!(rendered.contains("  00: 00 01 02 03 04 05 A0 07"));
+
+    let rendered = format_human_output(&json!({
+        "target": "sw2303",
+        "address": 60,
+        "reg": 18,
+        "value": 90,
+        "previous": 0,
+        "readback": 90
+    }));
+    assert!(rendered.contains("sw2303 reg 0x12: 0x00 -> 0x5A, reads back 0x5A"));
+
+    let rendered = format_human_output(&json!({
+        "unlocked": true,
+        "idle_timeout_ms": 120000,
+        "idle_remaining_ms": 95000,
+        "total_writes": 1,
+        "writes": [{
+            "uptime_ms": 5000,
+            "target": "sw2303",
+            "reg": 18,
+            "value": 90,
+            "previous": null,
+            "readback": null,
+            "ok": false
+        }]
+    }));
+    assert!(rendered.contains("Register debug: unlocked, locks after 95 s idle"));
+    assert!(rendered.contains("reg 0x12: -- -> 0x5A, reads back -- (FAILED)"));
+
+    let rendered = format_human_output(&json!({"unlocked": false, "restored": true}));
+    assert!(rendered.contains("saved power config re-applied"));
+}
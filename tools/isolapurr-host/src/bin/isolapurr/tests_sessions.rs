@@ -0,0 +1,84 @@
+use super::{Cli, Command, format_human_output, map_devd_ipc_endpoint, map_http_endpoint};
+use clap::Parser as _;
+use isolapurr_api::ports::PortId;
+use reqwest::Method;
+use serde_json::json;
+
+#[test]
+fn sessions_cli_parses_port_filter() {
+    let cli = Cli::try_parse_from([
+        "isolapurr",
+        "sessions",
+        "--device-id",
+        "hub",
+        "--port",
+        "port_c",
+    ])
+    .expect("sessions should parse");
+    let Command::Sessions(args) = cli.command else {
+        panic!("expected sessions");
+    };
+    assert_eq!(args.selector.device_id.as_deref(), Some("hub"));
+    assert_eq!(args.port, Some(PortId::PortC));
+
+    assert!(Cli::try_parse_from(["isolapurr", "sessions", "--port", "usb-b"]).is_err());
+}
+
+#[test]
+fn maps_sessions_endpoint_for_devd_and_http() {
+    let (method, params) = map_devd_ipc_endpoint(
+        Method::GET,
+        "/api/v1/devices/usb--dev-cu-usbmodem101/sessions",
+        None,
+    )
+    .expect("sessions should map");
+    assert_eq!(method, "device.sessions.list");
+    assert_eq!(params, json!({"device_id": "usb--dev-cu-usbmodem101"}));
+
+    let (method, path, _) =
+        map_http_endpoint(Method::GET, "/sessions", None).expect("sessions should map over HTTP");
+    assert_eq!(method, Method::GET);
+    assert_eq!(path, "/api/v1/sessions");
+}
+
+#[test]
+fn formats_finished_and_open_sessions() {
+    let text = format_human_output(&json!({
+        "capacity": 16,
+        "sessions": [
+            {
+                "id": 1,
+                "port": "port_c",
+                "start_uptime_ms": 60_000,
+                "end_uptime_ms": 3_720_000,
+                "energy_mwh": 10_002,
+                "avg_power_mw": 9_838,
+                "peak_power_mw": 20_310,
+                "max_voltage_mv": 9_050,
+                "protocols": ["pd", "pps"]
+            },
+            {
+                "id": 2,
+                "port": "port_a",
+                "start_uptime_ms": 3_800_000,
+                "end_uptime_ms": null,
+                "energy_mwh": 12,
+                "avg_power_mw": 2_500,
+                "peak_power_mw": 2_600,
+                "max_voltage_mv": 5_110,
+                "protocols": []
+            }
+        ]
+    }));
+    assert_eq!(
+        text,
+        "#1    USB-C  60 s -> 3720 s (1h01m)  10.00 Wh  avg 9.84 W  peak 20.31 W  max 9.05 V  PD, PPS\n\
+         #2    USB-A  3800 s -> open  0.01 Wh  avg 2.50 W  peak 2.60 W  max 5.11 V\n\
+         Times are device uptime; the hub keeps the last 16 finished sessions in RAM.\n"
+    );
+    assert_eq!(
+        format_human_output(&json!({"capacity": 16, "sessions": []})),
+        "No sessions recorded since boot\n\
+         Times are device uptime; the hub keeps the last 16 finished sessions in RAM.\n"
+    );
+}
@@ -0,0 +1,142 @@
+use super::{
+    Cli, Command, SoundCommand, SoundPatternCommand, SoundSetArgs, format_human_output,
+    map_devd_ipc_endpoint, map_http_endpoint, parse_clock_minute, sound_set_body,
+};
+use clap::Parser as _;
+use reqwest::Method;
+use serde_json::json;
+
+#[test]
+fn sound_set_body_only_sends_requested_fields() {
+    let args = SoundSetArgs {
+        muted: Some(false),
+        volume: Some(40),
+        warning: Some(true),
+        quiet_hours: Some(true),
+        quiet_start: Some("22:30".to_string()),
+        ..Default::default()
+    };
+
+    let body = sound_set_body(&args, Some(7 * 60 + 5)).expect("body should build");
+    assert_eq!(
+        body,
+        json!({
+            "muted": false,
+            "volume_pct": 40,
+            "warning": true,
+            "enabled": true,
+            "start": "22:30",
+            "local_minute": 425
+        })
+    );
+
+    let bad = SoundSetArgs {
+        quiet_end: Some("24:00".to_string()),
+        ..Default::default()
+    };
+    assert!(sound_set_body(&bad, None).is_err());
+}
+
+#[test]
+fn parse_clock_minute_accepts_hh_mm_only() {
+    assert_eq!(parse_clock_minute("00:00").expect("midnight"), 0);
+    assert_eq!(parse_clock_minute("23:59").expect("last minute"), 1439);
+    assert!(parse_clock_minute("7").is_err());
+    assert!(parse_clock_minute("12:60").is_err());
+}
+
+#[test]
+fn sound_cli_parses_pattern_and_rejects_unknown_slot() {
+    let cli = Cli::try_parse_from([
+        "isolapurr",
+        "sound",
+        "pattern",
+        "set",
+        "--device-id",
+        "aabbcc001122",
+        "--slot",
+        "warning",
+        "--steps",
+        "2700:40,0:60",
+    ])
+    .expect("pattern set should parse");
+    let Command::Sound {
+        command:
+            SoundCommand::Pattern {
+                command: SoundPatternCommand::Set { slot, steps, .. },
+            },
+    } = cli.command
+    else {
+        panic!("expected sound pattern set");
+    };
+    assert_eq!(slot, "warning");
+    assert_eq!(steps, "2700:40,0:60");
+
+    assert!(
+        Cli::try_parse_from(["isolapurr", "sound", "pattern", "clear", "--slot", "siren"]).is_err()
+    );
+    assert!(Cli::try_parse_from(["isolapurr", "sound", "set", "--volume", "101"]).is_err());
+}
+
+#[test]
+fn maps_sound_endpoints_for_http_and_devd() {
+    let (method, path, body) =
+        map_http_endpoint(Method::PUT, "/sound", Some(json!({"muted": true})))
+            .expect("sound set should map");
+    assert_eq!(method, Method::PUT);
+    assert_eq!(path, "/api/v1/sound");
+    assert_eq!(body, Some(json!({"muted": true})));
+
+    let (_, path, _) = map_http_endpoint(Method::POST, "/sound/patterns/boot_ok/clear", None)
+        .expect("pattern clear should map");
+    assert_eq!(path, "/api/v1/sound/patterns/boot_ok/clear");
+
+    let (method, params) = map_devd_ipc_endpoint(
+        Method::PUT,
+        "/api/v1/devices/usb--dev-cu-usbmodem101/sound",
+        Some(json!({"volume_pct": 30, "local_minute": 600})),
+    )
+    .expect("devd sound set should map");
+    assert_eq!(method, "device.sound.set");
+    assert_eq!(params["device_id"], "usb--dev-cu-usbmodem101");
+    assert_eq!(params["volume_pct"], 30);
+    assert_eq!(params["local_minute"], 600);
+
+    let (method, params) = map_devd_ipc_endpoint(
+        Method::PUT,
+        "/api/v1/devices/usb--dev-cu-usbmodem101/sound/patterns/menu_confirm",
+        Some(json!({"steps": "3000:30"})),
+    )
+    .expect("devd pattern set should map");
+    assert_eq!(method, "device.sound.pattern_set");
+    assert_eq!(params["slot"], "menu_confirm");
+    assert_eq!(params["steps"], "3000:30");
+
+    let (method, params) = map_devd_ipc_endpoint(
+        Method::POST,
+        "/api/v1/devices/usb--dev-cu-usbmodem101/sound/patterns/identify/clear",
+        None,
+    )
+    .expect("devd pattern clear should map");
+    assert_eq!(method, "device.sound.pattern_clear");
+    assert_eq!(params["slot"], "identify");
+}
+
+#[test]
+fn sound_human_output_summarizes_settings() {
+    let rendered = format_human_output(&json!({
+        "muted": false,
+        "volume_pct": 60,
+        "classes": {"boot": true, "menu": false, "action": true, "warning": true, "safety": true},
+        "quiet_hours": {"enabled": true, "start": "22:00", "end": "07:00", "active": false},
+        "clock_synced": false,
+        "persisted": true,
+        "patterns": {"boot_ok": null, "warning": "2700:40,0:60"}
+    }));
+
+    assert!(rendered.contains("Sound: on, volume 60%"));
+    assert!(rendered.contains("menu off"));
+    assert!(rendered.contains("Quiet hours: 22:00-07:00 (clock not synced)"));
+    assert!(rendered.contains("- warning: 2700:40,0:60"));
+    assert!(!rendered.contains("boot_ok"));
+}
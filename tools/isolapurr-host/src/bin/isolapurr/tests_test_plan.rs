@@ -0,0 +1,223 @@
+use super::{
+    TestCaseReport, TestCondition, TestOutcome, TestRange, TestRunReport, TestStep, TestStepReport,
+    integrate_energy_mwh, parse_test_plan, render_junit_report, test_condition_mismatches,
+    test_step_label,
+};
+use isolapurr_api::ports::{
+    Port, PortCapabilities, PortId, PortSnapshot, PortState, PortTelemetry, TelemetryStatus,
+};
+
+const SAMPLE_PLAN: &str = r#"
+name = "charger compat"
+
+[defaults]
+wait_timeout_ms = 5000
+
+[[case]]
+name = "9 V PD contract"
+repeat = 2
+steps = [
+  { action = "preset", watts = 45 },
+  { action = "port_power", port = "port_c", enabled = true },
+  { action = "wait", expect = { port = "port_c", voltage_mv = [8500, 9500] }, timeout_ms = 8000 },
+  { action = "assert", expect = { protocol = "pd" } },
+  { action = "energy", port = "port_c", duration_ms = 10000, min_mwh = 5 },
+]
+
+[[case]]
+name = "power cycling"
+steps = [
+  { action = "voltage", mv = 12000, current_limit_ma = 2000 },
+  { action = "loop", count = 3, steps = [
+    { action = "port_power", port = "port_a", enabled = false },
+    { action = "sleep", ms = 500 },
+    { action = "replug", port = "port_a" },
+  ] },
+  { action = "auto_output" },
+]
+"#;
+
+fn port_c(status: TelemetryStatus, voltage_mv: u32, power_enabled: bool) -> Port {
+    let mut port = Port::new(
+        PortId::PortC,
+        &PortSnapshot::unknown(),
+        PortCapabilities::default(),
+    );
+    port.telemetry = PortTelemetry {
+        status,
+        voltage_mv: Some(voltage_mv),
+        current_ma: Some(1000),
+        power_mw: Some(voltage_mv),
+        sample_uptime_ms: 0,
+    };
+    port.state = PortState {
+        power_enabled,
+        ..PortState::default()
+    };
+    port
+}
+
+#[test]
+fn test_plan_parses_steps_loops_and_defaults() {
+    let plan = parse_test_plan(SAMPLE_PLAN).expect("sample plan should parse");
+    assert_eq!(plan.name, "charger compat");
+    assert_eq!(plan.defaults.wait_timeout_ms, 5000);
+    assert_eq!(plan.defaults.poll_ms, 250);
+    assert_eq!(plan.cases[0].repeat, 2);
+    assert_eq!(plan.cases[1].repeat, 1);
+
+    let labels = plan.cases[0]
+        .steps
+        .iter()
+        .map(test_step_label)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        labels,
+        [
+            "preset 45 W",
+            "port_c power on",
+            "wait port_c voltage_mv=8500..9500",
+            "assert protocol=pd",
+            "energy port_c for 10000 ms",
+        ]
+    );
+    let TestStep::Loop { count, steps } = &plan.cases[1].steps[1] else {
+        panic!("expected loop step");
+    };
+    assert_eq!((*count, steps.len()), (3, 3));
+}
+
+#[test]
+fn test_plan_rejects_typos_and_empty_checks() {
+    let typo = r#"
+name = "x"
+[[case]]
+name = "c"
+steps = [{ action = "wait", expect = { port = "port_c", voltage = [1, 2] } }]
+"#;
+    assert!(parse_test_plan(typo).is_err());
+    let step_typo = typo.replace(
+        r#"{ action = "wait", expect = { port = "port_c", voltage = [1, 2] } }"#,
+        r#"{ action = "sleep", ms = 100, timeout = 5 }"#,
+    );
+    assert!(parse_test_plan(&step_typo).is_err());
+
+    let no_port = r#"
+name = "x"
+[[case]]
+name = "c"
+steps = [{ action = "assert", expect = { current_ma = [0, 100] } }]
+"#;
+    let err = parse_test_plan(no_port).expect_err("telemetry check needs a port");
+    assert!(format!("{err:#}").contains("needs a port"));
+
+    let nothing = r#"
+name = "x"
+[[case]]
+name = "c"
+steps = [{ action = "assert", expect = { port = "port_a" } }]
+"#;
+    assert!(parse_test_plan(nothing).is_err());
+    assert!(parse_test_plan("name = \"x\"").is_err());
+}
+
+#[test]
+fn test_condition_reports_every_mismatch() {
+    let condition = TestCondition {
+        port: Some(PortId::PortC),
+        power_enabled: Some(true),
+        voltage_mv: Some(TestRange(8500, 9500)),
+        protocol: Some("pd".to_string()),
+        ..TestCondition::default()
+    };
+    let ok = [port_c(TelemetryStatus::Ok, 9000, true)];
+    assert!(test_condition_mismatches(&condition, &ok, Some("pd")).is_empty());
+
+    let off = [port_c(TelemetryStatus::Ok, 5000, false)];
+    assert_eq!(
+        test_condition_mismatches(&condition, &off, None),
+        [
+            "power_enabled is false, expected true",
+            "voltage_mv is 5000, expected 8500..9500",
+            "protocol is none, expected pd",
+        ]
+    );
+
+    let unplugged = [port_c(TelemetryStatus::NotInserted, 9000, true)];
+    assert_eq!(
+        test_condition_mismatches(&condition, &unplugged, Some("pd")),
+        ["voltage_mv unavailable (telemetry not_inserted)"]
+    );
+}
+
+#[test]
+fn energy_integrates_trapezoids_in_mwh() {
+    assert_eq!(integrate_energy_mwh(&[(0, 1000)]), 0.0);
+    // 1 W for one hour, sampled at the ends.
+    let mwh = integrate_energy_mwh(&[(0, 1000), (1_800_000, 1000), (3_600_000, 1000)]);
+    assert!((mwh - 1000.0).abs() < 1e-9);
+    let ramp = integrate_energy_mwh(&[(0, 0), (3_600_000, 2000)]);
+    assert!((ramp - 1000.0).abs() < 1e-9);
+}
+
+#[test]
+fn junit_report_counts_outcomes_and_escapes_text() {
+    let plan = parse_test_plan(SAMPLE_PLAN).expect("sample plan should parse");
+    let mut report = TestRunReport::new(&plan, "hub <1>".to_string());
+    let step = |outcome, message: Option<&str>, energy_mwh| TestStepReport {
+        step: "energy port_c for 10000 ms".to_string(),
+        outcome,
+        duration_ms: 10_020,
+        message: message.map(str::to_string),
+        energy_mwh,
+    };
+    report.push(TestCaseReport {
+        name: "9 V PD contract".to_string(),
+        iteration: 1,
+        outcome: TestOutcome::Passed,
+        duration_ms: 12_000,
+        message: None,
+        steps: vec![step(TestOutcome::Passed, None, Some(12.5))],
+    });
+    report.push(TestCaseReport {
+        name: "9 V PD contract".to_string(),
+        iteration: 2,
+        outcome: TestOutcome::Failed,
+        duration_ms: 11_000,
+        message: Some("voltage_mv is 5000, expected 8500..9500 & \"pd\"".to_string()),
+        steps: vec![step(TestOutcome::Failed, Some("low"), None)],
+    });
+    report.push(TestCaseReport {
+        name: "power cycling".to_string(),
+        iteration: 1,
+        outcome: TestOutcome::Error,
+        duration_ms: 300,
+        message: Some("request failed".to_string()),
+        steps: Vec::new(),
+    });
+    report.duration_ms = 23_300;
+
+    let xml = render_junit_report(&report);
+    assert!(xml.contains(
+        "<testsuites name=\"charger compat\" tests=\"3\" failures=\"1\" errors=\"1\" time=\"23.300\">"
+    ));
+    assert!(xml.contains("hostname=\"hub &lt;1&gt;\""));
+    assert!(xml.contains("<testcase classname=\"charger compat\" name=\"9 V PD contract #2\""));
+    assert!(xml.contains("<testcase classname=\"charger compat\" name=\"power cycling\""));
+    assert!(xml.contains("<property name=\"energy port_c for 10000 ms mWh\" value=\"12.500\"/>"));
+    assert!(xml.contains(
+        "<failure message=\"voltage_mv is 5000, expected 8500..9500 &amp; &quot;pd&quot;\">"
+    ));
+    assert!(xml.contains("<error message=\"request failed\">"));
+    assert!(
+        xml.contains(
+            "<system-out>energy port_c for 10000 ms ... FAILED (10.020): low</system-out>"
+        )
+    );
+
+    let json = serde_json::to_value(&report).expect("report should serialize");
+    assert_eq!(json["passed"], 1);
+    assert_eq!(json["cases"][1]["outcome"], "failed");
+    assert_eq!(json["cases"][0]["steps"][0]["energy_mwh"], 12.5);
+    assert!(json.get("repeats").is_none());
+}
@@ -0,0 +1,114 @@
+use super::{
+    Cli, Command, TraceCommand, TraceExportArgs, export_trace, filter_trace_records,
+    format_trace_export_output,
+};
+use clap::Parser as _;
+use isolapurr_host::TraceRecord;
+use serde_json::json;
+
+fn record(device_id: &str, timestamp_unix_ms: u128) -> TraceRecord {
+    TraceRecord {
+        timestamp_unix_ms,
+        device_id: device_id.to_string(),
+        transport: "usb_serial".to_string(),
+        target: format!("/dev/{device_id}"),
+        method: "ports.get".to_string(),
+        request: json!({"id": "1", "method": "ports.get", "params": {}}),
+        response: Some(json!({"id": "1", "ok": true, "result": {"ports": []}})),
+        error: None,
+        duration_ms: 8,
+    }
+}
+
+#[test]
+fn trace_export_parses_filters() {
+    let cli = Cli::try_parse_from([
+        "isolapurr",
+        "trace",
+        "export",
+        "--out",
+        "bug.jsonl",
+        "--device-id",
+        "hub-a",
+        "--since",
+        "30m",
+    ])
+    .expect("trace export should parse");
+    let Command::Trace {
+        command: TraceCommand::Export(args),
+    } = cli.command
+    else {
+        panic!("expected trace export");
+    };
+    assert_eq!(args.device_id.as_deref(), Some("hub-a"));
+    assert_eq!(args.since, Some(std::time::Duration::from_secs(1800)));
+    assert!(Cli::try_parse_from(["isolapurr", "trace", "export"]).is_err());
+}
+
+#[test]
+fn trace_filter_matches_device_id_or_port_and_time() {
+    let records = vec![
+        record("hub-a", 1_000),
+        record("hub-b", 2_000),
+        record("hub-a", 3_000),
+    ];
+    assert_eq!(filter_trace_records(records.clone(), None, None).len(), 3);
+    assert_eq!(
+        filter_trace_records(records.clone(), Some("hub-a"), None).len(),
+        2
+    );
+    assert_eq!(
+        filter_trace_records(records.clone(), Some("/dev/hub-b"), None).len(),
+        1
+    );
+    let recent = filter_trace_records(records, Some("hub-a"), Some(2_000));
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent[0].timestamp_unix_ms, 3_000);
+}
+
+#[test]
+fn trace_export_merges_rotated_files_oldest_first() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let line = |record: &TraceRecord| serde_json::to_string(record).expect("encode") + "\n";
+    std::fs::write(
+        temp.path().join("devd-trace.1.jsonl"),
+        line(&record("hub-a", 1_000)),
+    )
+    .expect("write rotated");
+    std::fs::write(
+        temp.path().join("devd-trace.jsonl"),
+        line(&record("hub-b", 2_000)) + &line(&record("hub-a", 3_000)),
+    )
+    .expect("write live");
+
+    let out = temp.path().join("bug.jsonl");
+    let output = export_trace(&TraceExportArgs {
+        out: out.clone(),
+        device_id: None,
+        since: None,
+        trace_dir: Some(temp.path().to_path_buf()),
+    })
+    .expect("export should pass");
+    assert_eq!(output["records"], 3);
+    assert_eq!(output["devices"], json!(["hub-a", "hub-b"]));
+    let exported = isolapurr_host::read_trace_records(&out).expect("exported trace");
+    assert_eq!(
+        exported
+            .iter()
+            .map(|record| record.timestamp_unix_ms)
+            .collect::<Vec<_>>(),
+        [1_000, 2_000, 3_000]
+    );
+    assert!(format_trace_export_output(&output).contains("isolapurr-devd replay"));
+
+    let empty = tempfile::tempdir().expect("temp dir");
+    assert!(
+        export_trace(&TraceExportArgs {
+            out,
+            device_id: None,
+            since: None,
+            trace_dir: Some(empty.path().to_path_buf()),
+        })
+        .is_err()
+    );
+}
@@ -0,0 +1,167 @@
+use super::{
+    Cli, CliPowerDiagnostics, Command, WATCH_HISTORY_LEN, WatchAction, WatchPortHistory,
+    sparkline_window, watch_action_for_key, watch_power_lines, watch_tile_areas,
+};
+use clap::Parser as _;
+use crossterm::event::KeyCode;
+use isolapurr_api::ports::{PortTelemetry, TelemetryStatus};
+use ratatui::layout::Rect;
+use serde_json::json;
+
+fn telemetry(voltage_mv: u32, current_ma: u32) -> PortTelemetry {
+    PortTelemetry {
+        status: TelemetryStatus::Ok,
+        voltage_mv: Some(voltage_mv),
+        current_ma: Some(current_ma),
+        power_mw: Some(voltage_mv * current_ma / 1000),
+        sample_uptime_ms: 0,
+    }
+}
+
+#[test]
+fn watch_accepts_several_hubs() {
+    let cli = Cli::try_parse_from([
+        "isolapurr",
+        "watch",
+        "--device-id",
+        "hub-a",
+        "--device-id",
+        "hub-b",
+        "--url",
+        "http://10.0.0.7",
+        "--interval-ms",
+        "500",
+    ])
+    .expect("watch args should parse");
+    let Command::Watch(args) = cli.command else {
+        panic!("expected watch command");
+    };
+    let selectors = args.selectors();
+    assert_eq!(selectors.len(), 3);
+    assert_eq!(selectors[1].device_id.as_deref(), Some("hub-b"));
+    assert_eq!(selectors[2].url.as_deref(), Some("http://10.0.0.7"));
+    assert_eq!(args.interval_ms, 500);
+
+    assert!(Cli::try_parse_from(["isolapurr", "watch", "--interval-ms", "50"]).is_err());
+}
+
+#[test]
+fn watch_keys_map_to_actions() {
+    assert_eq!(
+        watch_action_for_key(KeyCode::Char('q'), false),
+        Some(WatchAction::Quit)
+    );
+    assert_eq!(
+        watch_action_for_key(KeyCode::Char('c'), true),
+        Some(WatchAction::Quit)
+    );
+    assert_eq!(watch_action_for_key(KeyCode::Char('c'), false), None);
+    assert_eq!(
+        watch_action_for_key(KeyCode::BackTab, false),
+        Some(WatchAction::FocusDevice(-1))
+    );
+    assert_eq!(
+        watch_action_for_key(KeyCode::Char('p'), false),
+        Some(WatchAction::TogglePower)
+    );
+    assert_eq!(
+        watch_action_for_key(KeyCode::Char('+'), false),
+        Some(WatchAction::StepPreset(1))
+    );
+}
+
+#[test]
+fn watch_history_is_bounded_and_windowed_to_width() {
+    let mut history = WatchPortHistory::default();
+    for index in 0..(WATCH_HISTORY_LEN as u32 + 10) {
+        history.push(&telemetry(5000 + index, 1000));
+    }
+    history.push(&PortTelemetry::unknown());
+
+    assert_eq!(history.voltage_mv.len(), WATCH_HISTORY_LEN);
+    assert_eq!(
+        sparkline_window(&history.voltage_mv, 3),
+        vec![
+            5000 + WATCH_HISTORY_LEN as u64 + 8,
+            5000 + WATCH_HISTORY_LEN as u64 + 9,
+            0
+        ]
+    );
+    assert_eq!(
+        sparkline_window(&history.power_mw, 1000).len(),
+        WATCH_HISTORY_LEN
+    );
+}
+
+#[test]
+fn watch_tiles_fill_columns_before_rows() {
+    let wide = watch_tile_areas(Rect::new(0, 0, 200, 40), 3);
+    assert_eq!(wide.len(), 3);
+    assert!(wide.iter().all(|tile| tile.y == 0 && tile.height == 40));
+
+    let narrow = watch_tile_areas(Rect::new(0, 0, 130, 40), 3);
+    assert_eq!(narrow.len(), 3);
+    assert_eq!((narrow[0].y, narrow[1].y, narrow[2].y), (0, 0, 20));
+    assert_eq!(narrow[2].width, 130);
+
+    assert!(watch_tile_areas(Rect::new(0, 0, 80, 24), 0).is_empty());
+}
+
+#[test]
+fn watch_power_lines_show_request_setpoint_and_thermal() {
+    let diagnostics: CliPowerDiagnostics = serde_json::from_value(json!({
+        "usb_c_power_enabled": true,
+        "sw2303_i2c_allowed": true,
+        "sw2303_profile_applied": true,
+        "sw2303_stable_reads": 3,
+        "sw2303_error_latched": false,
+        "tps_error_latched": false,
+        "sw2303_readback_config": {
+            "available": true,
+            "matches_config": true,
+            "power_watts": 100,
+            "protocols": {},
+            "pd": {"pps": true, "fixed_voltages_mv": [9000]}
+        },
+        "sw2303_request": {"mv": 9000, "ma": 3000},
+        "sw2303_last_valid_request": {"mv": 9000, "ma": 3000},
+        "usb_c_actual": {
+            "status": "ok",
+            "voltage_mv": 8980,
+            "current_ma": 1200,
+            "power_mw": 10776,
+            "sample_uptime_ms": 1500
+        },
+        "active_protocol": "pd",
+        "tps_setpoint": {
+            "output_enabled": true,
+            "discharge_enabled": false,
+            "mv": 9000,
+            "iout_limit_ma": 3100
+        },
+        "tps_iout_limit_readback": {"enabled": true, "ma": 3100},
+        "thermal": {
+            "sensors": {
+                "mcu": {"temperature_deci_c": 412, "status": "ok"},
+                "tmp112": {"temperature_deci_c": 385, "status": "ok"}
+            },
+            "hottest_temperature_deci_c": 412,
+            "state": "normal",
+            "reason": "none",
+            "effective_power_watts": 100,
+            "sample_uptime_ms": 1500
+        },
+        "runtime_recovery_count": 0,
+        "sample_uptime_ms": 1500
+    }))
+    .expect("diagnostics should deserialize");
+
+    let lines = watch_power_lines(&diagnostics, None);
+    assert_eq!(lines[0], "PD PD  request 9000 mV @ 3000 mA");
+    assert_eq!(
+        lines[1],
+        "TPS set 9000 mV @ 3100 mA  read 8980 mV @ 1200 mA / 10776 mW  limit 3100 mA"
+    );
+    assert_eq!(lines[2], "Thermal Normal  MCU 41.2°C  TMP112 38.5°C");
+    assert_eq!(lines[3], "preset ?  thermal cap 100 W");
+}
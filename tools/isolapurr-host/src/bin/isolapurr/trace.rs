@@ -0,0 +1,89 @@
+// `isolapurr trace export`: bundle the devd trace files for a bug report.
+
+fn handle_trace(command: TraceCommand) -> anyhow::Result<Value> {
+    match command {
+        TraceCommand::Export(args) => export_trace(&args),
+    }
+}
+
+fn export_trace(args: &TraceExportArgs) -> anyhow::Result<Value> {
+    let dir = match &args.trace_dir {
+        Some(dir) => dir.clone(),
+        None => default_trace_dir()?,
+    };
+    let sources = trace_files(&dir);
+    if sources.is_empty() {
+        return Err(anyhow!(
+            "no devd traces in {}; isolapurr-devd records them unless started with --no-trace",
+            dir.display()
+        ));
+    }
+    let mut records = Vec::new();
+    for path in &sources {
+        records.extend(read_trace_records(path)?);
+    }
+    let since_unix_ms = args
+        .since
+        .map(|since| (host_unix_ms() as u128).saturating_sub(since.as_millis()));
+    let records = filter_trace_records(records, args.device_id.as_deref(), since_unix_ms);
+    if records.is_empty() {
+        return Err(anyhow!("no trace records match the filters"));
+    }
+
+    let mut text = String::new();
+    for record in &records {
+        text.push_str(&serde_json::to_string(record)?);
+        text.push('\n');
+    }
+    fs::write(&args.out, text).with_context(|| format!("write {}", args.out.display()))?;
+
+    let mut devices = Vec::<&str>::new();
+    for record in &records {
+        if !devices.contains(&record.device_id.as_str()) {
+            devices.push(&record.device_id);
+        }
+    }
+    Ok(json!({
+        "trace_export": args.out.display().to_string(),
+        "records": records.len(),
+        "devices": devices,
+        "sources": sources
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>(),
+    }))
+}
+
+/// `device` matches the devd device id or the serial port path.
+fn filter_trace_records(
+    records: Vec<TraceRecord>,
+    device: Option<&str>,
+    since_unix_ms: Option<u128>,
+) -> Vec<TraceRecord> {
+    records
+        .into_iter()
+        .filter(|record| {
+            device.is_none_or(|device| record.device_id == device || record.target == device)
+        })
+        .filter(|record| since_unix_ms.is_none_or(|since| record.timestamp_unix_ms >= since))
+        .collect()
+}
+
+fn format_trace_export_output(output: &Value) -> String {
+    let out = output
+        .get("trace_export")
+        .and_then(Value::as_str)
+        .unwrap_or("");
+    let devices = output
+        .get("devices")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect::<Vec<_>>();
+    format!(
+        "Exported {} trace records for {} to {out}\nReplay with: isolapurr-devd replay {out}\n",
+        output.get("records").and_then(Value::as_u64).unwrap_or(0),
+        devices.join(", ")
+    )
+}
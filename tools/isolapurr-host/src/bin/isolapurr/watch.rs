@@ -0,0 +1,562 @@
+// `isolapurr watch`: full-screen live dashboard. Each hub gets a tile with
+// per-port V/I/P sparklines, the USB-C power path, and its link state.
+
+const WATCH_HISTORY_LEN: usize = 240;
+const WATCH_MIN_TILE_WIDTH: u16 = 64;
+// /status and /power/config change rarely; poll them every Nth refresh.
+const WATCH_SLOW_POLL_EVERY: u32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WatchAction {
+    Quit,
+    FocusDevice(i8),
+    FocusPort(i8),
+    TogglePower,
+    Replug,
+    Identify,
+    StepPreset(i8),
+}
+
+fn watch_action_for_key(code: crossterm::event::KeyCode, ctrl: bool) -> Option<WatchAction> {
+    use crossterm::event::KeyCode;
+
+    Some(match code {
+        KeyCode::Char('c') if ctrl => WatchAction::Quit,
+        KeyCode::Char('q') | KeyCode::Esc => WatchAction::Quit,
+        KeyCode::Tab | KeyCode::Right => WatchAction::FocusDevice(1),
+        KeyCode::BackTab | KeyCode::Left => WatchAction::FocusDevice(-1),
+        KeyCode::Down | KeyCode::Char('j') => WatchAction::FocusPort(1),
+        KeyCode::Up | KeyCode::Char('k') => WatchAction::FocusPort(-1),
+        KeyCode::Char('p') => WatchAction::TogglePower,
+        KeyCode::Char('r') => WatchAction::Replug,
+        KeyCode::Char('i') => WatchAction::Identify,
+        KeyCode::Char('+') | KeyCode::Char('=') => WatchAction::StepPreset(1),
+        KeyCode::Char('-') => WatchAction::StepPreset(-1),
+        _ => return None,
+    })
+}
+
+#[derive(Debug, Default, Clone)]
+struct WatchPortHistory {
+    voltage_mv: std::collections::VecDeque<u64>,
+    current_ma: std::collections::VecDeque<u64>,
+    power_mw: std::collections::VecDeque<u64>,
+}
+
+impl WatchPortHistory {
+    fn push(&mut self, telemetry: &PortTelemetry) {
+        // Gaps plot as zero so a dropped sample stays visible in the trace.
+        let ok = telemetry.status == TelemetryStatus::Ok;
+        let sample = |value: Option<u32>| if ok { value.unwrap_or(0) as u64 } else { 0 };
+        for (series, value) in [
+            (&mut self.voltage_mv, sample(telemetry.voltage_mv)),
+            (&mut self.current_ma, sample(telemetry.current_ma)),
+            (&mut self.power_mw, sample(telemetry.power_mw)),
+        ] {
+            if series.len() == WATCH_HISTORY_LEN {
+                series.pop_front();
+            }
+            series.push_back(value);
+        }
+    }
+}
+
+/// Newest samples that fit in `width` columns, oldest first.
+fn sparkline_window(series: &std::collections::VecDeque<u64>, width: u16) -> Vec<u64> {
+    let skip = series.len().saturating_sub(width as usize);
+    series.iter().skip(skip).copied().collect()
+}
+
+struct WatchDevice {
+    selector: ApiSelectorArgs,
+    label: String,
+    transport: String,
+    ports: Vec<Port>,
+    history: Vec<WatchPortHistory>,
+    diagnostics: Option<CliPowerDiagnostics>,
+    config: Option<PowerConfig>,
+    status: Option<Value>,
+    error: Option<String>,
+    focused_port: usize,
+}
+
+impl WatchDevice {
+    fn new(selector: ApiSelectorArgs, default_devd: &str) -> Self {
+        let label = selector
+            .device_id
+            .clone()
+            .or_else(|| selector.url.clone())
+            .unwrap_or_default();
+        let transport = match resolve_api_selector(selector.clone(), default_devd) {
+            Ok(ResolvedTarget::Http(base_url)) => format!("http {base_url}"),
+            Ok(ResolvedTarget::Usb(usb)) => format!("usb {} via devd", usb.device),
+            Err(err) => format!("unresolved: {err}"),
+        };
+        Self {
+            selector,
+            label,
+            transport,
+            ports: Vec::new(),
+            history: Vec::new(),
+            diagnostics: None,
+            config: None,
+            status: None,
+            error: None,
+            focused_port: 0,
+        }
+    }
+
+    fn record_ports(&mut self, ports: Vec<Port>) {
+        if self.history.len() != ports.len() {
+            self.history = vec![WatchPortHistory::default(); ports.len()];
+        }
+        for (history, port) in self.history.iter_mut().zip(&ports) {
+            history.push(&port.telemetry);
+        }
+        self.focused_port = self.focused_port.min(ports.len().saturating_sub(1));
+        self.ports = ports;
+    }
+
+    async fn refresh(&mut self, client: &Client, devd: &DevdClient, slow: bool) {
+        let result: anyhow::Result<()> = async {
+            let ports = fetch_ports_snapshot(client, devd, &self.selector).await?;
+            self.record_ports(ports.ports);
+            self.diagnostics = Some(fetch_power_diagnostics(client, devd, &self.selector).await?);
+            if slow || self.status.is_none() {
+                let status = request_selected(
+                    client,
+                    devd,
+                    self.selector.clone(),
+                    Method::GET,
+                    "/status",
+                    None,
+                )
+                .await?;
+                self.status = Some(unwrap_device_success_result(status)?);
+            }
+            if slow || self.config.is_none() {
+                self.config = Some(fetch_power_config(client, devd, &self.selector).await?);
+            }
+            Ok(())
+        }
+        .await;
+        self.error = result.err().map(|err| format!("{err:#}"));
+    }
+
+    fn focused_port_id(&self) -> anyhow::Result<PortId> {
+        self.ports
+            .get(self.focused_port)
+            .map(|port| port.port_id)
+            .ok_or_else(|| anyhow!("no port data yet"))
+    }
+}
+
+struct WatchState {
+    devices: Vec<WatchDevice>,
+    focused: usize,
+    message: Option<String>,
+}
+
+async fn handle_watch(
+    client: &Client,
+    devd: &DevdClient,
+    args: WatchArgs,
+) -> anyhow::Result<Value> {
+    if !io::stdout().is_terminal() {
+        return Err(anyhow!("isolapurr watch needs an interactive terminal"));
+    }
+    let mut selectors = args.selectors();
+    if selectors.is_empty() {
+        selectors
+            .push(select_api_target_interactively(client, devd, ApiSelectorArgs::default()).await?);
+    }
+    let mut state = WatchState {
+        devices: selectors
+            .into_iter()
+            .map(|selector| WatchDevice::new(selector, &devd.endpoint))
+            .collect(),
+        focused: 0,
+        message: None,
+    };
+    let interval = Duration::from_millis(args.interval_ms);
+    let mut terminal = ratatui::try_init().context("failed to initialize watch TUI")?;
+    let result = run_watch(&mut terminal, client, devd, &mut state, interval).await;
+    ratatui::restore();
+    result?;
+    Err(UserCancelled.into())
+}
+
+async fn run_watch(
+    terminal: &mut DefaultTerminal,
+    client: &Client,
+    devd: &DevdClient,
+    state: &mut WatchState,
+    interval: Duration,
+) -> anyhow::Result<()> {
+    use crossterm::event::{self, Event, KeyEventKind, KeyModifiers};
+
+    let mut next_refresh = Instant::now();
+    let mut refreshes = 0_u32;
+    loop {
+        if Instant::now() >= next_refresh {
+            let slow = refreshes.is_multiple_of(WATCH_SLOW_POLL_EVERY);
+            for device in &mut state.devices {
+                device.refresh(client, devd, slow).await;
+            }
+            refreshes = refreshes.wrapping_add(1);
+            next_refresh = Instant::now() + interval;
+        }
+        terminal.draw(|frame| render_watch(frame, state))?;
+
+        // Short polls keep the runtime responsive between refreshes.
+        let wait = next_refresh
+            .saturating_duration_since(Instant::now())
+            .min(Duration::from_millis(100));
+        if !event::poll(wait)? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match watch_action_for_key(key.code, ctrl) {
+            None => {}
+            Some(WatchAction::Quit) => return Ok(()),
+            Some(WatchAction::FocusDevice(direction)) => {
+                let focus: Vec<usize> = (0..state.devices.len()).collect();
+                state.focused = cycle_choice(state.focused, &focus, direction);
+            }
+            Some(WatchAction::FocusPort(direction)) => {
+                let device = &mut state.devices[state.focused];
+                let ports: Vec<usize> = (0..device.ports.len()).collect();
+                device.focused_port = cycle_choice(device.focused_port, &ports, direction);
+            }
+            Some(action) => {
+                let device = &mut state.devices[state.focused];
+                state.message = Some(
+                    match apply_watch_action(client, devd, device, action).await {
+                        Ok(done) => format!("{}: {done}", device.label),
+                        Err(err) => format!("{}: {err:#}", device.label),
+                    },
+                );
+                next_refresh = Instant::now();
+            }
+        }
+    }
+}
+
+async fn apply_watch_action(
+    client: &Client,
+    devd: &DevdClient,
+    device: &mut WatchDevice,
+    action: WatchAction,
+) -> anyhow::Result<String> {
+    let selector = device.selector.clone();
+    match action {
+        WatchAction::TogglePower => {
+            let port_id = device.focused_port_id()?;
+            let enabled = !device.ports[device.focused_port].state.power_enabled;
+            let response = request_selected(
+                client,
+                devd,
+                selector,
+                Method::POST,
+                &format!("/ports/{}/power?enabled={enabled}", port_id.as_str()),
+                None,
+            )
+            .await?;
+            unwrap_device_success_result(response)?;
+            let state = if enabled { "on" } else { "off" };
+            Ok(format!("{} power {state}", port_id.label()))
+        }
+        WatchAction::Replug => {
+            let port_id = device.focused_port_id()?;
+            let response = request_selected(
+                client,
+                devd,
+                selector,
+                Method::POST,
+                &format!("/ports/{}/replug", port_id.as_str()),
+                None,
+            )
+            .await?;
+            unwrap_device_success_result(response)?;
+            Ok(format!("{} replug started", port_id.label()))
+        }
+        WatchAction::Identify => {
+            let response =
+                request_selected(client, devd, selector, Method::POST, "/identify", None).await?;
+            unwrap_device_success_result(response)?;
+            Ok("identifying".to_string())
+        }
+        WatchAction::StepPreset(direction) => {
+            let mut config = fetch_power_config(client, devd, &selector).await?;
+            let current = config.capability.power_watts;
+            config.capability.power_watts =
+                cycle_choice(current, &power_watt_choices(current), direction);
+            let watts = config.capability.power_watts;
+            save_power_config_with_timeout_recovery(
+                client,
+                devd,
+                &selector,
+                next_power_owner(),
+                &config,
+            )
+            .await?;
+            device.config = Some(config);
+            Ok(format!("power preset {current} W -> {watts} W"))
+        }
+        WatchAction::Quit | WatchAction::FocusDevice(_) | WatchAction::FocusPort(_) => {
+            Ok(String::new())
+        }
+    }
+}
+
+/// Splits `area` into one tile per hub: as many columns as fit at
+/// [`WATCH_MIN_TILE_WIDTH`], then rows for the rest.
+fn watch_tile_areas(area: Rect, count: usize) -> Vec<Rect> {
+    if count == 0 {
+        return Vec::new();
+    }
+    let max_columns = (area.width / WATCH_MIN_TILE_WIDTH).max(1) as usize;
+    let columns = count.min(max_columns);
+    let rows = count.div_ceil(columns);
+    let row_areas = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Ratio(1, rows as u32); rows])
+        .split(area);
+    let mut tiles = Vec::with_capacity(count);
+    for (row, row_area) in row_areas.iter().enumerate() {
+        let in_row = (count - row * columns).min(columns);
+        let cells = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Ratio(1, in_row as u32); in_row])
+            .split(*row_area);
+        tiles.extend(cells.iter().copied());
+    }
+    tiles
+}
+
+fn render_watch(frame: &mut Frame, state: &WatchState) {
+    let [body, footer] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .areas(frame.area());
+    for (index, (device, area)) in state
+        .devices
+        .iter()
+        .zip(watch_tile_areas(body, state.devices.len()))
+        .enumerate()
+    {
+        render_watch_tile(frame, area, device, index == state.focused);
+    }
+    let help = "Tab hub  ↑/↓ port  p power  r replug  i identify  -/+ preset  q quit";
+    let text = match &state.message {
+        Some(message) => format!("{message}  |  {help}"),
+        None => help.to_string(),
+    };
+    frame.render_widget(
+        Paragraph::new(truncate_to_width(&text, footer.width))
+            .style(Style::default().fg(Color::Gray)),
+        footer,
+    );
+}
+
+fn render_watch_tile(frame: &mut Frame, area: Rect, device: &WatchDevice, focused: bool) {
+    let border = if focused { Color::Yellow } else { Color::Gray };
+    let block = panel_block(&device.label).border_style(Style::default().fg(border));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let header = watch_header_lines(device);
+    let power = device
+        .diagnostics
+        .as_ref()
+        .map(|diagnostics| watch_power_lines(diagnostics, device.config.as_ref()))
+        .unwrap_or_default();
+    let mut constraints = vec![Constraint::Length(header.len() as u16)];
+    constraints.extend(device.ports.iter().map(|_| Constraint::Length(4)));
+    constraints.push(Constraint::Length(power.len() as u16));
+    constraints.push(Constraint::Min(0));
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(inner);
+
+    frame.render_widget(watch_lines(header, inner.width), rows[0]);
+    for (index, (port, history)) in device.ports.iter().zip(&device.history).enumerate() {
+        let selected = focused && index == device.focused_port;
+        render_watch_port(frame, rows[index + 1], port, history, selected);
+    }
+    let power_row = device.ports.len() + 1;
+    frame.render_widget(watch_lines(power, inner.width), rows[power_row]);
+    if let Some(error) = &device.error {
+        frame.render_widget(
+            Paragraph::new(error.as_str())
+                .style(Style::default().fg(Color::Red))
+                .wrap(Wrap { trim: true }),
+            rows[power_row + 1],
+        );
+    }
+}
+
+fn render_watch_port(
+    frame: &mut Frame,
+    area: Rect,
+    port: &Port,
+    history: &WatchPortHistory,
+    selected: bool,
+) {
+    use ratatui::widgets::Sparkline;
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1); 4])
+        .split(area);
+    let title_style = if selected {
+        Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD)
+    } else {
+        Style::default().add_modifier(Modifier::BOLD)
+    };
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            truncate_to_width(&watch_port_title(port), area.width),
+            title_style,
+        )),
+        rows[0],
+    );
+    for (row, (label, series, color)) in rows[1..].iter().zip([
+        ("V", &history.voltage_mv, Color::Cyan),
+        ("I", &history.current_ma, Color::Magenta),
+        ("P", &history.power_mw, Color::Green),
+    ]) {
+        let [label_area, spark_area] = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(3), Constraint::Min(1)])
+            .areas(*row);
+        frame.render_widget(
+            Paragraph::new(label).style(Style::default().fg(Color::Gray)),
+            label_area,
+        );
+        frame.render_widget(
+            Sparkline::default()
+                .data(sparkline_window(series, spark_area.width))
+                .style(Style::default().fg(color)),
+            spark_area,
+        );
+    }
+}
+
+fn watch_lines(lines: Vec<String>, width: u16) -> Paragraph<'static> {
+    Paragraph::new(Text::from(
+        lines
+            .into_iter()
+            .map(|line| Line::from(truncate_to_width(&line, width)))
+            .collect::<Vec<_>>(),
+    ))
+}
+
+fn watch_port_title(port: &Port) -> String {
+    let power = if port.state.power_enabled {
+        "on"
+    } else {
+        "off"
+    };
+    let mut flags = String::new();
+    if port.state.data_mode == PortDataMode::ChargeOnly {
+        flags.push_str("  charge-only");
+    }
+    if port.charge_complete.is_some() {
+        flags.push_str("  charged");
+    }
+    if port.state.replugging {
+        flags.push_str("  replugging");
+    }
+    if port.state.busy {
+        flags.push_str("  busy");
+    }
+    format!(
+        "{} [{power}]  {}{flags}",
+        port.port_id.label(),
+        format_port_telemetry(&port.telemetry)
+    )
+}
+
+fn watch_header_lines(device: &WatchDevice) -> Vec<String> {
+    let mut link = device.transport.clone();
+    if let Some(wifi) = device
+        .status
+        .as_ref()
+        .and_then(|status| status.pointer("/device/wifi"))
+    {
+        let state = wifi
+            .get("state")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        link.push_str(&format!("  Wi-Fi {state}"));
+        if let Some(ipv4) = wifi.get("ipv4").and_then(Value::as_str) {
+            link.push_str(&format!(" {ipv4}"));
+        }
+    }
+    let firmware = device
+        .status
+        .as_ref()
+        .and_then(|status| status.pointer("/device/firmware/version"))
+        .and_then(Value::as_str)
+        .unwrap_or("?");
+    let uptime_s = device
+        .status
+        .as_ref()
+        .and_then(|status| status.pointer("/device/uptime_ms"))
+        .and_then(Value::as_u64)
+        .map(|uptime_ms| uptime_ms / 1000);
+    let uptime = uptime_s.map_or("?".to_string(), |seconds| format!("{seconds} s"));
+    vec![link, format!("firmware {firmware}  uptime {uptime}")]
+}
+
+fn watch_power_lines(
+    diagnostics: &CliPowerDiagnostics,
+    config: Option<&PowerConfig>,
+) -> Vec<String> {
+    let protocol = diagnostics
+        .active_protocol
+        .as_deref()
+        .map_or("none", format_active_protocol);
+    let readback = diagnostics
+        .usb_c_actual
+        .as_ref()
+        .map_or_else(|| "unavailable".to_string(), format_port_telemetry);
+    let mut tps = format!(
+        "TPS set {}  read {readback}",
+        format_output_target(&diagnostics.tps_setpoint)
+    );
+    if let Some(ma) = diagnostics
+        .tps_iout_limit_readback
+        .as_ref()
+        .and_then(|readback| readback.ma)
+    {
+        tps.push_str(&format!("  limit {ma} mA"));
+    }
+    let thermal = &diagnostics.thermal;
+    let preset = config.map_or_else(
+        || "preset ?".to_string(),
+        |config| format!("preset {} W", config.capability.power_watts),
+    );
+    vec![
+        format!(
+            "PD {protocol}  request {}",
+            format_power_request(&diagnostics.sw2303_request)
+        ),
+        tps,
+        format!(
+            "Thermal {}  MCU {}  TMP112 {}",
+            format_thermal_state(thermal),
+            format_temperature_deci_c(thermal.sensors.mcu.temperature_deci_c),
+            format_temperature_deci_c(thermal.sensors.tmp112.temperature_deci_c),
+        ),
+        format!("{preset}  thermal cap {} W", thermal.effective_power_watts),
+    ]
+}
@@ -0,0 +1,70 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use isolapurr_api::methods;
+use serde_json::Value;
+
+use super::{
+    AppState, error_from_anyhow, redact_sensitive, require_auth,
+    require_compatible_project_firmware, usb_jsonl_request,
+};
+
+pub(super) fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/devices/{id}/buttons",
+            get(buttons_get).put(buttons_set),
+        )
+        .route(
+            "/api/v1/devices/{id}/buttons/defaults",
+            post(buttons_defaults),
+        )
+}
+
+async fn buttons_request(
+    state: &AppState,
+    headers: &HeaderMap,
+    id: &str,
+    method: &str,
+    params: Option<Value>,
+) -> Response {
+    if let Err(response) = require_auth(headers, state) {
+        return *response;
+    }
+    if let Err(err) = require_compatible_project_firmware(state, id).await {
+        return error_from_anyhow(err);
+    }
+    match usb_jsonl_request(state, id, method, params).await {
+        Ok(value) => Json(redact_sensitive(&value)).into_response(),
+        Err(err) => error_from_anyhow(err),
+    }
+}
+
+async fn buttons_get(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Response {
+    buttons_request(&state, &headers, &id, methods::BUTTONS_GET, None).await
+}
+
+async fn buttons_set(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Json(body): Json<Value>,
+) -> Response {
+    buttons_request(&state, &headers, &id, methods::BUTTONS_SET, Some(body)).await
+}
+
+async fn buttons_defaults(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Response {
+    buttons_request(&state, &headers, &id, methods::BUTTONS_DEFAULTS, None).await
+}
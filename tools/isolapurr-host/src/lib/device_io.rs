@@ -236,20 +236,16 @@ async fn usb_jsonl_request_with_exclusive(
     params: Option<Value>,
     allowed_exclusive_reason: Option<&str>,
 ) -> anyhow::Result<Value> {
-    let (port_path, request_id) = {
+    let (route, request_id) = {
         let inner = state.inner.lock().await;
         let device = inner
             .devices
             .get(device_id)
             .ok_or_else(|| anyhow!("device not found"))?;
-        let usb = device
-            .usb
-            .as_ref()
-            .ok_or_else(|| anyhow!("device has no Local USB target"))?;
-        (usb.port_path.clone(), next_id())
+        (JsonlRoute::for_device(device)?, next_id())
     };
     let _serial_guard =
-        acquire_serial_port_guard(state, &port_path, allowed_exclusive_reason).await?;
+        acquire_serial_port_guard(state, route.target(), allowed_exclusive_reason).await?;
 
     let request = json!({
         "id": request_id,
@@ -257,9 +253,37 @@ async fn usb_jsonl_request_with_exclusive(
         "params": params.unwrap_or_else(|| json!({})),
     });
     push_trace(state, device_id, "tx", method, &request).await;
-    let response = tokio::task::spawn_blocking(move || serial_jsonl_roundtrip(&port_path, request))
-        .await
-        .context("serial worker join")??;
+    if let Some(replayed) = replay_trace_response(state, device_id, &request).await {
+        let response = replayed?;
+        push_trace(state, device_id, "rx", method, &response).await;
+        return Ok(response);
+    }
+    let started = Instant::now();
+    let result = match &route {
+        JsonlRoute::Serial(port_path) => {
+            let port_path = port_path.clone();
+            let request = request.clone();
+            tokio::task::spawn_blocking(move || serial_jsonl_roundtrip(&port_path, request))
+                .await
+                .context("serial worker join")
+                .and_then(|result| result)
+        }
+        JsonlRoute::Tcp(tcp) => {
+            let timeout = Duration::from_millis(serial_timeout_ms_for_method(method));
+            tcp::jsonl_roundtrip(&tcp.addr, tcp.token.as_deref(), &request, timeout).await
+        }
+    };
+    record_trace(
+        state,
+        device_id,
+        route.trace_transport(),
+        route.target(),
+        &request,
+        &result,
+        started,
+    )
+    .await;
+    let response = result?;
     push_trace(state, device_id, "rx", method, &response).await;
     Ok(response)
 }
@@ -273,7 +297,8 @@ async fn usb_wifi_clear_request(state: &AppState, device_id: &str) -> anyhow::Re
             "verified_after_serial_timeout": true,
         }
     });
-    usb_wifi_credentials_clear_like_request(state, device_id, "wifi.clear", None, success).await
+    usb_wifi_credentials_clear_like_request(state, device_id, methods::WIFI_CLEAR, None, success)
+        .await
 }
 
 async fn usb_settings_reset_request(
@@ -296,7 +321,7 @@ async fn usb_settings_reset_request(
         return usb_wifi_credentials_clear_like_request(
             state,
             device_id,
-            "settings.reset",
+            methods::SETTINGS_RESET,
             params,
             success,
         )
@@ -311,7 +336,7 @@ async fn usb_settings_reset_request(
             "verified_after_serial_reconnect": true,
         }
     });
-    match usb_jsonl_request(state, device_id, "settings.reset", params).await {
+    match usb_jsonl_request(state, device_id, methods::SETTINGS_RESET, params).await {
         Ok(value) => Ok(value),
         Err(err) if should_verify_other_settings_reset_after_serial_error(&err) => {
             match verify_other_settings_reset_after_serial_reconnect(state, device_id).await {
@@ -349,7 +374,7 @@ async fn verify_wifi_cleared_after_serial_timeout(
     let mut last_error = None;
     for _ in 0..10 {
         tokio::time::sleep(Duration::from_millis(500)).await;
-        match usb_jsonl_request(state, device_id, "wifi.get", None).await {
+        match usb_jsonl_request(state, device_id, methods::WIFI_GET, None).await {
             Ok(value) => {
                 if wifi_config_is_cleared(&value) {
                     return Ok(());
@@ -382,21 +407,14 @@ async fn verify_other_settings_reset_after_serial_reconnect(
     let mut last_error = None;
     for _ in 0..12 {
         tokio::time::sleep(Duration::from_millis(500)).await;
-        let ports = match usb_jsonl_request(state, device_id, "ports.get", None).await {
-            Ok(value) => value,
-            Err(err) => {
-                last_error = Some(err);
-                continue;
-            }
-        };
-        let power = match usb_jsonl_request(state, device_id, "power.config_get", None).await {
+        let ports = match usb_jsonl_request(state, device_id, methods::PORTS_GET, None).await {
             Ok(value) => value,
             Err(err) => {
                 last_error = Some(err);
                 continue;
             }
         };
-        let idle_bias = match usb_jsonl_request(state, device_id, "power.idle_bias_get", None).await
+        let power = match usb_jsonl_request(state, device_id, methods::POWER_CONFIG_GET, None).await
         {
             Ok(value) => value,
             Err(err) => {
@@ -404,6 +422,14 @@ async fn verify_other_settings_reset_after_serial_reconnect(
                 continue;
             }
         };
+        let idle_bias =
+            match usb_jsonl_request(state, device_id, methods::POWER_IDLE_BIAS_GET, None).await {
+                Ok(value) => value,
+                Err(err) => {
+                    last_error = Some(err);
+                    continue;
+                }
+            };
         if other_settings_reset_is_verified(&ports, &power, &idle_bias) {
             return Ok(());
         }
@@ -458,12 +484,19 @@ async fn device_usb_port_path(state: &AppState, device_id: &str) -> anyhow::Resu
 
 fn serial_timeout_ms_for_method(method: &str) -> u64 {
     match method {
-        "power.config_set"
-        | "power.config_defaults"
-        | "power.idle_bias_set"
-        | "power.idle_bias_clear" => SERIAL_POWER_CONFIG_EARLY_VERIFY_TIMEOUT_MS,
-        "power.idle_bias_run" => 178_000,
-        "settings.reset" => SERIAL_SETTINGS_RESET_TIMEOUT_MS,
+        methods::POWER_CONFIG_SET
+        | methods::POWER_CONFIG_DEFAULTS
+        | methods::POWER_IDLE_BIAS_SET
+        | methods::POWER_IDLE_BIAS_CLEAR => SERIAL_POWER_CONFIG_EARLY_VERIFY_TIMEOUT_MS,
+        methods::POWER_IDLE_BIAS_RUN => 178_000,
+        methods::SETTINGS_RESET => SERIAL_SETTINGS_RESET_TIMEOUT_MS,
+        // ~150 KB of base64 pixels on a single line.
+        methods::DISPLAY_SCREENSHOT => SERIAL_DISPLAY_SCREENSHOT_TIMEOUT_MS,
+        // A PD recovery waits for the next coordinator tick and a CE cycle; a
+        // register dump is up to 256 single-register transfers.
+        methods::I2C_SCAN | methods::I2C_RECOVER | methods::DEBUG_REG_DUMP => {
+            SERIAL_I2C_ACTION_TIMEOUT_MS
+        }
         _ => SERIAL_TIMEOUT_MS,
     }
 }
@@ -478,6 +511,10 @@ mod device_io_tests {
             serial_timeout_ms_for_method("settings.reset"),
             SERIAL_SETTINGS_RESET_TIMEOUT_MS
         );
+        assert_eq!(
+            serial_timeout_ms_for_method("display.screenshot"),
+            SERIAL_DISPLAY_SCREENSHOT_TIMEOUT_MS
+        );
         assert_eq!(
             serial_timeout_ms_for_method("power.idle_bias_set"),
             SERIAL_POWER_CONFIG_EARLY_VERIFY_TIMEOUT_MS
@@ -487,6 +524,14 @@ mod device_io_tests {
             SERIAL_POWER_CONFIG_EARLY_VERIFY_TIMEOUT_MS
         );
         assert_eq!(serial_timeout_ms_for_method("power.idle_bias_run"), 178_000);
+        assert_eq!(
+            serial_timeout_ms_for_method("i2c.recover"),
+            SERIAL_I2C_ACTION_TIMEOUT_MS
+        );
+        assert_eq!(
+            serial_timeout_ms_for_method("debug.reg_dump"),
+            SERIAL_I2C_ACTION_TIMEOUT_MS
+        );
     }
 
     #[test]
@@ -624,52 +669,12 @@ fn serial_jsonl_roundtrip_with_timeout(
     request: Value,
     timeout_ms_override: Option<u64>,
 ) -> anyhow::Result<Value> {
-    let mut port = serialport::new(port_path, SERIAL_BAUD)
-        .timeout(Duration::from_millis(50))
-        .open()
-        .with_context(|| format!("open serial port {port_path}"))?;
-    let mut line = serde_json::to_string(&request)?;
-    line.push('\n');
-    use std::io::{Read as _, Write as _};
-    port.write_all(line.as_bytes()).context("serial write")?;
-    port.flush().context("serial flush")?;
-
-    let expected_id = request.get("id").cloned();
     let method = request
         .get("method")
         .and_then(Value::as_str)
         .unwrap_or_default();
-    let deadline = Instant::now()
-        + Duration::from_millis(
-            timeout_ms_override.unwrap_or_else(|| serial_timeout_ms_for_method(method)),
-        );
-    let mut raw = Vec::<u8>::new();
-    let mut buf = [0_u8; 256];
-    while Instant::now() < deadline {
-        match port.read(&mut buf) {
-            Ok(0) => {}
-            Ok(n) => {
-                raw.extend_from_slice(&buf[..n]);
-                while let Some(pos) = raw.iter().position(|byte| *byte == b'\n') {
-                    let frame = raw.drain(..=pos).collect::<Vec<_>>();
-                    let text = String::from_utf8_lossy(&frame);
-                    let trimmed = text.trim();
-                    if trimmed.is_empty() {
-                        continue;
-                    }
-                    let Ok(value) = serde_json::from_str::<Value>(trimmed) else {
-                        continue;
-                    };
-                    if expected_id.is_none() || value.get("id") == expected_id.as_ref() {
-                        return Ok(value);
-                    }
-                }
-            }
-            Err(err) if err.kind() == std::io::ErrorKind::TimedOut => {}
-            Err(err) => return Err(err).context("serial read"),
-        }
-    }
-    Err(anyhow!("serial response timed out"))
+    let timeout_ms = timeout_ms_override.unwrap_or_else(|| serial_timeout_ms_for_method(method));
+    serial::jsonl_roundtrip(port_path, &request, Duration::from_millis(timeout_ms))
 }
 
 async fn run_flash_request(
@@ -0,0 +1,82 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use isolapurr_api::methods;
+use serde_json::Value;
+
+use super::{
+    AppState, error_from_anyhow, redact_sensitive, require_auth,
+    require_compatible_project_firmware, usb_jsonl_request,
+};
+
+pub(super) fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/devices/{id}/display",
+            get(display_get).put(display_set),
+        )
+        .route(
+            "/api/v1/devices/{id}/display/defaults",
+            post(display_defaults),
+        )
+        .route(
+            "/api/v1/devices/{id}/display/screenshot",
+            get(display_screenshot),
+        )
+}
+
+async fn display_request(
+    state: &AppState,
+    headers: &HeaderMap,
+    id: &str,
+    method: &str,
+    params: Option<Value>,
+) -> Response {
+    if let Err(response) = require_auth(headers, state) {
+        return *response;
+    }
+    if let Err(err) = require_compatible_project_firmware(state, id).await {
+        return error_from_anyhow(err);
+    }
+    match usb_jsonl_request(state, id, method, params).await {
+        Ok(value) => Json(redact_sensitive(&value)).into_response(),
+        Err(err) => error_from_anyhow(err),
+    }
+}
+
+async fn display_get(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Response {
+    display_request(&state, &headers, &id, methods::DISPLAY_GET, None).await
+}
+
+async fn display_set(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Json(body): Json<Value>,
+) -> Response {
+    display_request(&state, &headers, &id, methods::DISPLAY_SET, Some(body)).await
+}
+
+async fn display_defaults(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Response {
+    display_request(&state, &headers, &id, methods::DISPLAY_DEFAULTS, None).await
+}
+
+async fn display_screenshot(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Response {
+    display_request(&state, &headers, &id, methods::DISPLAY_SCREENSHOT, None).await
+}
@@ -1,10 +1,22 @@
+#[path = "buttons_bridge.rs"]
+mod buttons_bridge;
+#[path = "display_bridge.rs"]
+mod display_bridge;
 #[path = "http_bridge_storage.rs"]
 mod http_bridge_storage;
 #[cfg(test)]
 #[path = "http_bridge_tests.rs"]
 mod http_bridge_tests;
+#[path = "i2c_bridge.rs"]
+mod i2c_bridge;
+#[path = "jsonl_tcp_bridge.rs"]
+mod jsonl_tcp_bridge;
+#[path = "serial_bridge.rs"]
+mod serial_bridge;
 #[path = "settings_reset_bridge.rs"]
 mod settings_reset_bridge;
+#[path = "sound_bridge.rs"]
+mod sound_bridge;
 
 #[cfg(test)]
 use http_bridge_storage::{parse_import_profiles, web_storage_devices};
@@ -20,6 +32,7 @@ pub async fn serve_http_bridge(config: DevdConfig) -> anyhow::Result<()> {
         .with_context(|| format!("bind {}", config.bind))?;
     let port = listener.local_addr()?.port();
     let state = AppState::new(format!("http://127.0.0.1:{port}"));
+    enable_trace_recording(&state, config.trace_dir.as_deref()).await;
 
     let router = router(state, config.web_root, config.allow_dev_cors);
     tracing::info!("isolapurr-devd HTTP bridge listening on http://127.0.0.1:{port}");
@@ -86,6 +99,26 @@ fn router(state: AppState, web_root: Option<PathBuf>, allow_dev_cors: bool) -> R
             "/api/v1/devices/{id}/power/config/release",
             post(device_power_config_release),
         )
+        .merge(display_bridge::routes())
+        .merge(buttons_bridge::routes())
+        .merge(i2c_bridge::routes())
+        .merge(jsonl_tcp_bridge::routes())
+        .route(
+            "/api/v1/devices/{id}/sound",
+            get(sound_bridge::sound_get).put(sound_bridge::sound_set),
+        )
+        .route(
+            "/api/v1/devices/{id}/sound/defaults",
+            post(sound_bridge::sound_defaults),
+        )
+        .route(
+            "/api/v1/devices/{id}/sound/patterns/{slot}",
+            put(sound_bridge::sound_pattern_set),
+        )
+        .route(
+            "/api/v1/devices/{id}/sound/patterns/{slot}/clear",
+            post(sound_bridge::sound_pattern_clear),
+        )
         .route("/api/v1/devices/{id}/flash", post(device_flash))
         .route(
             "/api/v1/devices/{id}/flash-upload",
@@ -107,8 +140,14 @@ fn router(state: AppState, web_root: Option<PathBuf>, allow_dev_cors: bool) -> R
         )
         .route("/api/v1/serial/ports", get(serial_ports))
         .route("/api/v1/serial/register", post(serial_register))
-        .route("/api/v1/serial/request", post(serial_request))
-        .route("/api/v1/serial/board-info", post(serial_board_info))
+        .route(
+            "/api/v1/serial/request",
+            post(serial_bridge::serial_request),
+        )
+        .route(
+            "/api/v1/serial/board-info",
+            post(serial_bridge::serial_board_info),
+        )
         .route(
             "/api/v1/serial/lease/{lease_id}",
             post(http_bridge_storage::heartbeat_lease).delete(http_bridge_storage::release_lease),
@@ -248,83 +287,12 @@ async fn serial_register(
     Json(json!({ "ok": true, "device": device })).into_response()
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct SerialBoardInfoRequest {
-    port_path: String,
-}
-
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct SerialRegisterRequest {
     port_path: String,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct SerialRequestBody {
-    port_path: String,
-    request: Value,
-    timeout_ms: Option<u64>,
-}
-
-async fn serial_board_info(
-    State(state): State<AppState>,
-    headers: HeaderMap,
-    Json(req): Json<SerialBoardInfoRequest>,
-) -> Response {
-    if let Err(response) = require_auth(&headers, &state) {
-        return *response;
-    }
-    if let Err(err) = register_requested_usb_device(&state, &req.port_path).await {
-        return error_from_anyhow(err);
-    }
-    match local_usb_board_info(&state, &req.port_path).await {
-        Ok(result) => Json(json!({ "ok": true, "result": result })).into_response(),
-        Err(err) => error_from_anyhow(err),
-    }
-}
-
-async fn serial_request(
-    State(state): State<AppState>,
-    headers: HeaderMap,
-    Json(req): Json<SerialRequestBody>,
-) -> Response {
-    if let Err(response) = require_auth(&headers, &state) {
-        return *response;
-    }
-    if let Err(err) = register_requested_usb_device(&state, &req.port_path).await {
-        return error_from_anyhow(err);
-    }
-    let device_id = stable_usb_device_id(&req.port_path);
-    let request = req.request.clone();
-    let request_method = request
-        .get("method")
-        .and_then(Value::as_str)
-        .unwrap_or("request")
-        .to_string();
-    push_trace(&state, &device_id, "tx", &request_method, &request).await;
-    let port_path = req.port_path.clone();
-    let timeout_ms = req.timeout_ms;
-    let response_method = request_method.clone();
-    let result = async {
-        let _guard = acquire_serial_port_guard(&state, &port_path, None).await?;
-        tokio::task::spawn_blocking(move || {
-            serial_jsonl_roundtrip_with_timeout(&port_path, request, timeout_ms)
-        })
-        .await
-        .context("serial worker join")?
-    }
-    .await;
-    match result {
-        Ok(response) => {
-            push_trace(&state, &device_id, "rx", &response_method, &response).await;
-            Json(json!({ "response": response })).into_response()
-        }
-        Err(err) => error_from_anyhow(err),
-    }
-}
-
 fn reconcile_scanned_usb_devices(inner: &mut DevdState, ports: Vec<UsbTarget>) {
     let mut scanned_ids = HashSet::new();
     for port in ports {
@@ -368,6 +336,7 @@ fn upsert_usb_device(inner: &mut DevdState, port: UsbTarget) -> DeviceRecord {
             connection: "available".to_string(),
             usb: Some(port),
             http: None,
+            tcp: None,
             identity: None,
             session: DeviceSession::default(),
         })
@@ -433,7 +402,7 @@ async fn wifi_get(
     if let Err(err) = require_compatible_project_firmware(&state, &id).await {
         return error_from_anyhow(err);
     }
-    match usb_jsonl_request(&state, &id, "wifi.get", None).await {
+    match usb_jsonl_request(&state, &id, methods::WIFI_GET, None).await {
         Ok(value) => Json(redact_sensitive(&value)).into_response(),
         Err(err) => error_from_anyhow(err),
     }
@@ -454,7 +423,7 @@ async fn wifi_set(
     match usb_jsonl_request(
         &state,
         &id,
-        "wifi.set",
+        methods::WIFI_SET,
         Some(json!({"ssid": req.ssid, "psk": req.psk})),
     )
     .await
@@ -492,7 +461,7 @@ async fn device_ports(
     if let Err(err) = require_compatible_project_firmware(&state, &id).await {
         return error_from_anyhow(err);
     }
-    match usb_jsonl_request(&state, &id, "ports.get", None).await {
+    match usb_jsonl_request(&state, &id, methods::PORTS_GET, None).await {
         Ok(value) => Json(redact_sensitive(&value)).into_response(),
         Err(err) => error_from_anyhow(err),
     }
@@ -513,7 +482,7 @@ async fn device_identify(
     if let Err(err) = validate_identify_capability(&info) {
         return error_from_anyhow(err);
     }
-    match usb_jsonl_request(&state, &id, "identify", None).await {
+    match usb_jsonl_request(&state, &id, methods::IDENTIFY, None).await {
         Ok(value) => Json(redact_sensitive(&value)).into_response(),
         Err(err) => error_from_anyhow(err),
     }
@@ -535,7 +504,7 @@ async fn port_power(
     match usb_jsonl_request(
         &state,
         &id,
-        "port.power_set",
+        methods::PORT_POWER_SET,
         Some(json!({"port": port_id, "enabled": enabled})),
     )
     .await
@@ -556,7 +525,14 @@ async fn port_replug(
     if let Err(err) = require_compatible_project_firmware(&state, &id).await {
         return error_from_anyhow(err);
     }
-    match usb_jsonl_request(&state, &id, "port.replug", Some(json!({"port": port_id}))).await {
+    match usb_jsonl_request(
+        &state,
+        &id,
+        methods::PORT_REPLUG,
+        Some(json!({"port": port_id})),
+    )
+    .await
+    {
         Ok(value) => Json(redact_sensitive(&value)).into_response(),
         Err(err) => error_from_anyhow(err),
     }
@@ -575,7 +551,14 @@ async fn hub_route_set(
         return error_from_anyhow(err);
     }
     let route = req.route;
-    match usb_jsonl_request(&state, &id, "hub.route_set", Some(json!({"route": route}))).await {
+    match usb_jsonl_request(
+        &state,
+        &id,
+        methods::HUB_ROUTE_SET,
+        Some(json!({"route": route})),
+    )
+    .await
+    {
         Ok(value) => Json(redact_sensitive(&value)).into_response(),
         Err(err) => match verify_hub_route_after_disconnect(&state, &id, &route).await {
             Ok(value) => Json(redact_sensitive(&value)).into_response(),
@@ -592,17 +575,17 @@ async fn verify_hub_route_after_disconnect(
     let mut last_error = None;
     for _ in 0..5 {
         tokio::time::sleep(Duration::from_millis(300)).await;
-        match usb_jsonl_request(state, id, "ports.get", None).await {
+        match usb_jsonl_request(state, id, methods::PORTS_GET, None).await {
             Ok(value) => {
-                if let Some((actual_route, persisted)) = extract_hub_route(&value)
-                    && actual_route == route
+                if let Some(hub) = extract_hub_status(&value)
+                    && hub.usb_c_downstream_route.as_str() == route
                 {
                     return Ok(json!({
                         "ok": true,
                         "result": {
                             "accepted": true,
-                            "usb_c_downstream_route": actual_route,
-                            "persisted": persisted.unwrap_or(false),
+                            "usb_c_downstream_route": hub.usb_c_downstream_route,
+                            "persisted": hub.usb_c_downstream_persisted,
                             "verified_after_serial_reconnect": true
                         }
                     }));
@@ -614,19 +597,12 @@ async fn verify_hub_route_after_disconnect(
     Err(last_error.unwrap_or_else(|| anyhow!("USB-C route did not verify after reconnect")))
 }
 
-fn extract_hub_route(value: &Value) -> Option<(String, Option<bool>)> {
+fn extract_hub_status(value: &Value) -> Option<HubStatus> {
     let hub = value
         .get("result")
         .and_then(|result| result.get("hub"))
         .or_else(|| value.get("hub"))?;
-    let route = hub
-        .get("usb_c_downstream_route")
-        .and_then(Value::as_str)?
-        .to_string();
-    let persisted = hub
-        .get("usb_c_downstream_persisted")
-        .and_then(Value::as_bool);
-    Some((route, persisted))
+    HubStatus::deserialize(hub).ok()
 }
 
 async fn verify_wifi_after_set_timeout(
@@ -637,7 +613,7 @@ async fn verify_wifi_after_set_timeout(
     let mut last_error = None;
     for _ in 0..10 {
         tokio::time::sleep(Duration::from_millis(500)).await;
-        match usb_jsonl_request(state, id, "wifi.get", None).await {
+        match usb_jsonl_request(state, id, methods::WIFI_GET, None).await {
             Ok(mut value) => {
                 if wifi_matches_expected_ssid(&value, expected_ssid) {
                     if let Some(result) = value.get_mut("result").and_then(Value::as_object_mut) {
@@ -672,7 +648,7 @@ async fn power_config_saved_after_serial_timeout(
     let mut last_error = None;
     for _ in 0..6 {
         tokio::time::sleep(Duration::from_millis(250)).await;
-        match usb_jsonl_request(state, id, "power.config_get", None).await {
+        match usb_jsonl_request(state, id, methods::POWER_CONFIG_GET, None).await {
             Ok(mut value) => {
                 if power_config_matches_expected(&value, expected) {
                     if let Some(result) = value.get_mut("result").and_then(Value::as_object_mut) {
@@ -697,7 +673,7 @@ async fn power_defaults_saved_after_serial_timeout(
     let mut last_error = None;
     for _ in 0..6 {
         tokio::time::sleep(Duration::from_millis(250)).await;
-        match usb_jsonl_request(state, id, "power.config_get", None).await {
+        match usb_jsonl_request(state, id, methods::POWER_CONFIG_GET, None).await {
             Ok(mut value) => {
                 if power_config_matches_defaults(&value) {
                     if let Some(result) = value.get_mut("result").and_then(Value::as_object_mut) {
@@ -717,24 +693,26 @@ async fn power_defaults_saved_after_serial_timeout(
 
 fn power_config_matches_expected(value: &Value, expected: &Value) -> bool {
     let observed = value.get("result").unwrap_or(value);
-    observed.get("persisted").and_then(Value::as_bool) == Some(true)
-        && observed.get("hardware") == expected.get("hardware")
-        && observed.get("tps_mode") == expected.get("tps_mode")
-        && observed.get("light_load_mode") == expected.get("light_load_mode")
-        && observed.get("capability") == expected.get("capability")
-        && observed.pointer("/manual/voltage_mv") == expected.pointer("/manual/voltage_mv")
-        && observed.pointer("/manual/current_limit_ma")
-            == expected.pointer("/manual/current_limit_ma")
-        && observed.pointer("/manual/usb_c_path_mode")
-            == expected.pointer("/manual/usb_c_path_mode")
+    if observed.get("persisted").and_then(Value::as_bool) != Some(true) {
+        return false;
+    }
+    match (
+        PowerSettings::deserialize(observed),
+        PowerSettings::deserialize(expected),
+    ) {
+        (Ok(observed), Ok(expected)) => observed == expected,
+        _ => false,
+    }
 }
 
 fn power_config_matches_defaults(value: &Value) -> bool {
     let observed = value.get("result").unwrap_or(value);
     observed.get("persisted").and_then(Value::as_bool) == Some(true)
-        && observed.get("hardware").and_then(Value::as_str) == Some("sw2303")
-        && observed.get("tps_mode").and_then(Value::as_str) == Some("auto_follow")
-        && observed.get("light_load_mode").and_then(Value::as_str) == Some("pfm")
+        && observed.get("hardware").and_then(Value::as_str)
+            == Some(PowerHardwareKind::Sw2303.as_str())
+        && observed.get("tps_mode").and_then(Value::as_str) == Some(TpsMode::AutoFollow.as_str())
+        && observed.get("light_load_mode").and_then(Value::as_str)
+            == Some(LightLoadMode::Pfm.as_str())
         && observed
             .pointer("/capability/profile")
             .and_then(Value::as_str)
@@ -902,7 +880,8 @@ async fn device_reset(
         port_path,
         serial_guard: Some(serial_guard),
     };
-    let result = usb_jsonl_request_with_exclusive(&state, &id, "reboot", None, Some("reset")).await;
+    let result =
+        usb_jsonl_request_with_exclusive(&state, &id, methods::REBOOT, None, Some("reset")).await;
     drop(guard);
     match result {
         Ok(value) => Json(redact_sensitive(&value)).into_response(),
@@ -921,7 +900,7 @@ async fn device_diagnostics(
     if let Err(err) = require_compatible_project_firmware(&state, &id).await {
         return error_from_anyhow(err);
     }
-    match usb_jsonl_request(&state, &id, "pd.diagnostics", None).await {
+    match usb_jsonl_request(&state, &id, methods::PD_DIAGNOSTICS, None).await {
         Ok(value) => Json(redact_sensitive(&value)).into_response(),
         Err(err) => error_from_anyhow(err),
     }
@@ -938,7 +917,7 @@ async fn device_power_config_get(
     if let Err(err) = require_compatible_project_firmware(&state, &id).await {
         return error_from_anyhow(err);
     }
-    match usb_jsonl_request(&state, &id, "power.config_get", None).await {
+    match usb_jsonl_request(&state, &id, methods::POWER_CONFIG_GET, None).await {
         Ok(value) => Json(redact_sensitive(&value)).into_response(),
         Err(err) => error_from_anyhow(err),
     }
@@ -955,7 +934,7 @@ async fn device_power_idle_bias_get(
     if let Err(err) = require_compatible_project_firmware(&state, &id).await {
         return error_from_anyhow(err);
     }
-    match usb_jsonl_request(&state, &id, "power.idle_bias_get", None).await {
+    match usb_jsonl_request(&state, &id, methods::POWER_IDLE_BIAS_GET, None).await {
         Ok(value) => Json(redact_sensitive(&value)).into_response(),
         Err(err) => error_from_anyhow(err),
     }
@@ -975,7 +954,7 @@ async fn device_power_config_set(
         return error_from_anyhow(err);
     }
     let params = json!({"config": config, "owner": query.owner});
-    match usb_jsonl_request(&state, &id, "power.config_set", Some(params)).await {
+    match usb_jsonl_request(&state, &id, methods::POWER_CONFIG_SET, Some(params)).await {
         Ok(value) => Json(redact_sensitive(&value)).into_response(),
         Err(err) if should_verify_power_config_after_serial_error(&err) => {
             match power_config_saved_after_serial_timeout(&state, &id, &config).await {
@@ -1005,7 +984,7 @@ async fn device_power_runtime_set(
         "enabled": body.get("enabled").cloned().unwrap_or(Value::Null),
         "owner": query.owner,
     });
-    match usb_jsonl_request(&state, &id, "power.runtime_set", Some(params)).await {
+    match usb_jsonl_request(&state, &id, methods::POWER_RUNTIME_SET, Some(params)).await {
         Ok(value) => Json(redact_sensitive(&value)).into_response(),
         Err(err) => error_from_anyhow(err),
     }
@@ -1028,7 +1007,7 @@ async fn device_power_idle_bias_set(
         "correction_enabled": body.get("correction_enabled").cloned().unwrap_or(Value::Null),
         "owner": query.owner,
     });
-    match usb_jsonl_request(&state, &id, "power.idle_bias_set", Some(params)).await {
+    match usb_jsonl_request(&state, &id, methods::POWER_IDLE_BIAS_SET, Some(params)).await {
         Ok(value) => Json(redact_sensitive(&value)).into_response(),
         Err(err) => error_from_anyhow(err),
     }
@@ -1049,7 +1028,7 @@ async fn device_power_config_defaults(
     match usb_jsonl_request(
         &state,
         &id,
-        "power.config_defaults",
+        methods::POWER_CONFIG_DEFAULTS,
         Some(json!({"owner": query.owner})),
     )
     .await
@@ -1080,7 +1059,7 @@ async fn device_power_idle_bias_run(
     match usb_jsonl_request(
         &state,
         &id,
-        "power.idle_bias_run",
+        methods::POWER_IDLE_BIAS_RUN,
         Some(json!({"owner": query.owner})),
     )
     .await
@@ -1105,7 +1084,7 @@ async fn device_power_idle_bias_clear(
     match usb_jsonl_request(
         &state,
         &id,
-        "power.idle_bias_clear",
+        methods::POWER_IDLE_BIAS_CLEAR,
         Some(json!({"owner": query.owner})),
     )
     .await
@@ -1130,7 +1109,7 @@ async fn device_power_config_lock(
     match usb_jsonl_request(
         &state,
         &id,
-        "power.lock",
+        methods::POWER_LOCK,
         Some(json!({"owner": query.owner, "acquire": true})),
     )
     .await
@@ -1155,7 +1134,7 @@ async fn device_power_config_release(
     match usb_jsonl_request(
         &state,
         &id,
-        "power.lock",
+        methods::POWER_LOCK,
         Some(json!({"owner": query.owner, "acquire": false})),
     )
     .await
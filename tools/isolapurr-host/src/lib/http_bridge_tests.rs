@@ -206,6 +206,50 @@ fn power_config_verify_rejects_mismatched_requested_payload() {
     assert!(!power_config_matches_expected(&observed, &expected));
 }
 
+#[test]
+fn power_config_verify_compares_typed_settings() {
+    let mut expected = json!({
+        "hardware": "sw2303",
+        "tps_mode": "auto_follow",
+        "light_load_mode": "pfm",
+        "capability": {
+            "profile": "full",
+            "power_watts": 65,
+            "protocols": {
+                "pd": true,
+                "qc20": true,
+                "qc30": true,
+                "fcp": false,
+                "afc": false,
+                "scp": false,
+                "pe20": false,
+                "bc12": true,
+                "sfcp": false
+            },
+            "pd": {
+                "pps": false,
+                "fixed_voltages_mv": [20000, 9000]
+            }
+        },
+        "manual": {
+            "voltage_mv": 5000,
+            "current_limit_ma": 1000,
+            "usb_c_path_mode": "default"
+        }
+    });
+    let mut observed = json!({"ok": true, "result": expected.clone()});
+    observed["result"]["persisted"] = json!(true);
+    observed["result"]["capability"]["pd"]["fixed_voltages_mv"] = json!([9000, 20000]);
+    assert!(power_config_matches_expected(&observed, &expected));
+
+    observed["result"]["persisted"] = json!(false);
+    assert!(!power_config_matches_expected(&observed, &expected));
+
+    observed["result"]["persisted"] = json!(true);
+    expected["tps_mode"] = json!("turbo");
+    assert!(!power_config_matches_expected(&observed, &expected));
+}
+
 #[test]
 fn power_config_defaults_match_full_profile() {
     let observed = json!({
@@ -0,0 +1,79 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use isolapurr_api::methods;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use super::{
+    AppState, error_from_anyhow, redact_sensitive, require_auth,
+    require_compatible_project_firmware, usb_jsonl_request,
+};
+
+#[derive(Debug, Deserialize)]
+struct I2cRecoverQuery {
+    bus: String,
+    owner: Option<u32>,
+}
+
+pub(super) fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/devices/{id}/diagnostics/i2c",
+            get(i2c_diagnostics_get),
+        )
+        .route("/api/v1/devices/{id}/diagnostics/i2c/scan", post(i2c_scan))
+        .route(
+            "/api/v1/devices/{id}/diagnostics/i2c/recover",
+            post(i2c_recover),
+        )
+}
+
+async fn i2c_request(
+    state: &AppState,
+    headers: &HeaderMap,
+    id: &str,
+    method: &str,
+    params: Option<Value>,
+) -> Response {
+    if let Err(response) = require_auth(headers, state) {
+        return *response;
+    }
+    if let Err(err) = require_compatible_project_firmware(state, id).await {
+        return error_from_anyhow(err);
+    }
+    match usb_jsonl_request(state, id, method, params).await {
+        Ok(value) => Json(redact_sensitive(&value)).into_response(),
+        Err(err) => error_from_anyhow(err),
+    }
+}
+
+async fn i2c_diagnostics_get(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Response {
+    i2c_request(&state, &headers, &id, methods::I2C_DIAGNOSTICS_GET, None).await
+}
+
+async fn i2c_scan(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Response {
+    i2c_request(&state, &headers, &id, methods::I2C_SCAN, None).await
+}
+
+async fn i2c_recover(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Query(query): Query<I2cRecoverQuery>,
+) -> Response {
+    let params = json!({"bus": query.bus, "owner": query.owner});
+    i2c_request(&state, &headers, &id, methods::I2C_RECOVER, Some(params)).await
+}
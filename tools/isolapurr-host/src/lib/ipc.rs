@@ -1,5 +1,6 @@
 pub async fn serve_ipc(config: IpcConfig) -> anyhow::Result<()> {
     let state = AppState::new("ipc://isolapurr-devd");
+    enable_trace_recording(&state, config.trace_dir.as_deref()).await;
     serve_ipc_with_state(config, state).await
 }
 
@@ -189,6 +190,11 @@ async fn dispatch_ipc_request(
         "devd.health" => Ok(json!({"ok": true})),
         "devices.list" => ipc_list_devices(state).await,
         "devices.scan" => ipc_scan_devices(state).await,
+        "tcp.register" => {
+            let req: TcpRegisterRequest = serde_json::from_value(params)?;
+            let device = register_tcp_device(state, &req.addr, req.token).await?;
+            Ok(json!({"ok": true, "device": device}))
+        }
         "device.status" => {
             let req: DeviceIdRequest = serde_json::from_value(params)?;
             Ok(redact_sensitive(
@@ -200,7 +206,7 @@ async fn dispatch_ipc_request(
             let info = require_compatible_project_firmware(state, &req.device_id).await?;
             validate_identify_capability(&info)?;
             Ok(redact_sensitive(
-                &usb_jsonl_request(state, &req.device_id, "identify", None).await?,
+                &usb_jsonl_request(state, &req.device_id, methods::IDENTIFY, None).await?,
             ))
         }
         "device.session" => {
@@ -211,7 +217,7 @@ async fn dispatch_ipc_request(
             let req: DeviceIdRequest = serde_json::from_value(params)?;
             require_compatible_project_firmware(state, &req.device_id).await?;
             Ok(redact_sensitive(
-                &usb_jsonl_request(state, &req.device_id, "wifi.get", None).await?,
+                &usb_jsonl_request(state, &req.device_id, methods::WIFI_GET, None).await?,
             ))
         }
         "device.wifi.set" => {
@@ -221,7 +227,7 @@ async fn dispatch_ipc_request(
             match usb_jsonl_request(
                 state,
                 &req.device_id,
-                "wifi.set",
+                methods::WIFI_SET,
                 Some(json!({"ssid": req.ssid, "psk": req.psk})),
             )
             .await
@@ -243,6 +249,21 @@ async fn dispatch_ipc_request(
                 &usb_wifi_clear_request(state, &req.device_id).await?,
             ))
         }
+        "device.jsonl_tcp.get" => {
+            let req: DeviceIdRequest = serde_json::from_value(params)?;
+            require_compatible_project_firmware(state, &req.device_id).await?;
+            Ok(redact_sensitive(
+                &usb_jsonl_request(state, &req.device_id, methods::JSONL_TCP_GET, None).await?,
+            ))
+        }
+        "device.jsonl_tcp.set" => {
+            let req: DeviceJsonlTcpSetRequest = serde_json::from_value(params)?;
+            usb_jsonl_tcp_set(state, &req.device_id, req.token).await
+        }
+        "device.jsonl_tcp.clear" => {
+            let req: DeviceIdRequest = serde_json::from_value(params)?;
+            usb_jsonl_tcp_clear(state, &req.device_id).await
+        }
         "device.settings.reset" => {
             let req: DeviceSettingsResetRequest = serde_json::from_value(params)?;
             require_compatible_project_firmware(state, &req.device_id).await?;
@@ -254,7 +275,7 @@ async fn dispatch_ipc_request(
             let req: DeviceIdRequest = serde_json::from_value(params)?;
             require_compatible_project_firmware(state, &req.device_id).await?;
             Ok(redact_sensitive(
-                &usb_jsonl_request(state, &req.device_id, "ports.get", None).await?,
+                &usb_jsonl_request(state, &req.device_id, methods::PORTS_GET, None).await?,
             ))
         }
         "device.port.power" => {
@@ -264,12 +285,25 @@ async fn dispatch_ipc_request(
                 &usb_jsonl_request(
                     state,
                     &req.device_id,
-                    "port.power_set",
+                    methods::PORT_POWER_SET,
                     Some(json!({"port": req.port, "enabled": req.enabled})),
                 )
                 .await?,
             ))
         }
+        "device.port.data_set" => {
+            let req: DevicePortDataRequest = serde_json::from_value(params)?;
+            require_compatible_project_firmware(state, &req.device_id).await?;
+            Ok(redact_sensitive(
+                &usb_jsonl_request(
+                    state,
+                    &req.device_id,
+                    methods::PORT_DATA_SET,
+                    Some(json!({"port": req.port, "mode": req.mode})),
+                )
+                .await?,
+            ))
+        }
         "device.port.replug" => {
             let req: DevicePortRequest = serde_json::from_value(params)?;
             require_compatible_project_firmware(state, &req.device_id).await?;
@@ -277,7 +311,7 @@ async fn dispatch_ipc_request(
                 &usb_jsonl_request(
                     state,
                     &req.device_id,
-                    "port.replug",
+                    methods::PORT_REPLUG,
                     Some(json!({"port": req.port})),
                 )
                 .await?,
@@ -289,7 +323,7 @@ async fn dispatch_ipc_request(
             match usb_jsonl_request(
                 state,
                 &req.device_id,
-                "hub.route_set",
+                methods::HUB_ROUTE_SET,
                 Some(json!({"route": req.route})),
             )
             .await
@@ -308,7 +342,7 @@ async fn dispatch_ipc_request(
             let req: DeviceIdRequest = serde_json::from_value(params)?;
             require_compatible_project_firmware(state, &req.device_id).await?;
             Ok(redact_sensitive(
-                &usb_jsonl_request(state, &req.device_id, "power.config_get", None).await?,
+                &usb_jsonl_request(state, &req.device_id, methods::POWER_CONFIG_GET, None).await?,
             ))
         }
         "device.power.config_set" => {
@@ -318,7 +352,7 @@ async fn dispatch_ipc_request(
                 &usb_jsonl_request(
                     state,
                     &req.device_id,
-                    "power.config_set",
+                    methods::POWER_CONFIG_SET,
                     Some(json!({"config": req.config, "owner": req.owner})),
                 )
                 .await?,
@@ -331,7 +365,7 @@ async fn dispatch_ipc_request(
                 &usb_jsonl_request(
                     state,
                     &req.device_id,
-                    "power.runtime_set",
+                    methods::POWER_RUNTIME_SET,
                     Some(json!({
                         "action": req.action,
                         "enabled": req.enabled,
@@ -348,7 +382,7 @@ async fn dispatch_ipc_request(
                 &usb_jsonl_request(
                     state,
                     &req.device_id,
-                    "power.config_defaults",
+                    methods::POWER_CONFIG_DEFAULTS,
                     Some(json!({"owner": req.owner})),
                 )
                 .await?,
@@ -358,7 +392,8 @@ async fn dispatch_ipc_request(
             let req: DeviceIdRequest = serde_json::from_value(params)?;
             require_compatible_project_firmware(state, &req.device_id).await?;
             Ok(redact_sensitive(
-                &usb_jsonl_request(state, &req.device_id, "power.idle_bias_get", None).await?,
+                &usb_jsonl_request(state, &req.device_id, methods::POWER_IDLE_BIAS_GET, None)
+                    .await?,
             ))
         }
         "device.power.idle_bias_set" => {
@@ -368,7 +403,7 @@ async fn dispatch_ipc_request(
                 &usb_jsonl_request(
                     state,
                     &req.device_id,
-                    "power.idle_bias_set",
+                    methods::POWER_IDLE_BIAS_SET,
                     Some(json!({
                         "correction_enabled": req.correction_enabled,
                         "owner": req.owner,
@@ -384,7 +419,7 @@ async fn dispatch_ipc_request(
                 &usb_jsonl_request(
                     state,
                     &req.device_id,
-                    "power.idle_bias_run",
+                    methods::POWER_IDLE_BIAS_RUN,
                     Some(json!({"owner": req.owner})),
                 )
                 .await?,
@@ -397,12 +432,197 @@ async fn dispatch_ipc_request(
                 &usb_jsonl_request(
                     state,
                     &req.device_id,
-                    "power.idle_bias_clear",
+                    methods::POWER_IDLE_BIAS_CLEAR,
                     Some(json!({"owner": req.owner})),
                 )
                 .await?,
             ))
         }
+        "device.power.fast_charge_probe_get" => {
+            let req: DeviceIdRequest = serde_json::from_value(params)?;
+            require_compatible_project_firmware(state, &req.device_id).await?;
+            Ok(redact_sensitive(
+                &usb_jsonl_request(
+                    state,
+                    &req.device_id,
+                    methods::POWER_FAST_CHARGE_PROBE_GET,
+                    None,
+                )
+                .await?,
+            ))
+        }
+        "device.power.fast_charge_probe_run" | "device.power.fast_charge_probe_cancel" => {
+            let req: DevicePowerOwnerRequest = serde_json::from_value(params)?;
+            require_compatible_project_firmware(state, &req.device_id).await?;
+            let serial_method = method.trim_start_matches("device.");
+            Ok(redact_sensitive(
+                &usb_jsonl_request(
+                    state,
+                    &req.device_id,
+                    serial_method,
+                    Some(json!({"owner": req.owner})),
+                )
+                .await?,
+            ))
+        }
+        "device.display.get" | "device.display.defaults" | "device.display.screenshot" => {
+            let req: DeviceIdRequest = serde_json::from_value(params)?;
+            require_compatible_project_firmware(state, &req.device_id).await?;
+            let serial_method = method.trim_start_matches("device.");
+            Ok(redact_sensitive(
+                &usb_jsonl_request(state, &req.device_id, serial_method, None).await?,
+            ))
+        }
+        "device.display.set" => {
+            let req: DeviceDisplaySetRequest = serde_json::from_value(params)?;
+            require_compatible_project_firmware(state, &req.device_id).await?;
+            Ok(redact_sensitive(
+                &usb_jsonl_request(
+                    state,
+                    &req.device_id,
+                    methods::DISPLAY_SET,
+                    Some(Value::Object(req.settings)),
+                )
+                .await?,
+            ))
+        }
+        "device.buttons.get" | "device.buttons.defaults" => {
+            let req: DeviceIdRequest = serde_json::from_value(params)?;
+            require_compatible_project_firmware(state, &req.device_id).await?;
+            let serial_method = method.trim_start_matches("device.");
+            Ok(redact_sensitive(
+                &usb_jsonl_request(state, &req.device_id, serial_method, None).await?,
+            ))
+        }
+        "device.buttons.set" => {
+            let req: DeviceButtonsSetRequest = serde_json::from_value(params)?;
+            require_compatible_project_firmware(state, &req.device_id).await?;
+            Ok(redact_sensitive(
+                &usb_jsonl_request(
+                    state,
+                    &req.device_id,
+                    methods::BUTTONS_SET,
+                    Some(Value::Object(req.settings)),
+                )
+                .await?,
+            ))
+        }
+        "device.i2c.diagnostics_get" | "device.i2c.scan" => {
+            let req: DeviceIdRequest = serde_json::from_value(params)?;
+            require_compatible_project_firmware(state, &req.device_id).await?;
+            let serial_method = method.trim_start_matches("device.");
+            Ok(redact_sensitive(
+                &usb_jsonl_request(state, &req.device_id, serial_method, None).await?,
+            ))
+        }
+        "device.diagnostics.crash" | "device.diagnostics.crash_ack" | "device.sessions.list" => {
+            let req: DeviceIdRequest = serde_json::from_value(params)?;
+            require_compatible_project_firmware(state, &req.device_id).await?;
+            let serial_method = method.trim_start_matches("device.");
+            Ok(redact_sensitive(
+                &usb_jsonl_request(state, &req.device_id, serial_method, None).await?,
+            ))
+        }
+        "device.ports.power_policy_get"
+        | "device.ports.power_policy_set"
+        | "device.ports.charge_termination_get"
+        | "device.ports.charge_termination_set" => {
+            let req: DevicePortSettingsRequest = serde_json::from_value(params)?;
+            require_compatible_project_firmware(state, &req.device_id).await?;
+            let serial_method = method.trim_start_matches("device.");
+            let params = (!req.params.is_empty()).then_some(Value::Object(req.params));
+            Ok(redact_sensitive(
+                &usb_jsonl_request(state, &req.device_id, serial_method, params).await?,
+            ))
+        }
+        "device.logs.tail" | "device.logs.config_get" | "device.logs.config_set" => {
+            let req: DeviceLogsRequest = serde_json::from_value(params)?;
+            require_compatible_project_firmware(state, &req.device_id).await?;
+            let serial_method = method.trim_start_matches("device.");
+            let params = (!req.params.is_empty()).then_some(Value::Object(req.params));
+            Ok(redact_sensitive(
+                &usb_jsonl_request(state, &req.device_id, serial_method, params).await?,
+            ))
+        }
+        "device.i2c.recover" => {
+            let req: DeviceI2cRecoverRequest = serde_json::from_value(params)?;
+            require_compatible_project_firmware(state, &req.device_id).await?;
+            Ok(redact_sensitive(
+                &usb_jsonl_request(
+                    state,
+                    &req.device_id,
+                    methods::I2C_RECOVER,
+                    Some(json!({"bus": req.bus, "owner": req.owner})),
+                )
+                .await?,
+            ))
+        }
+        "device.debug.status"
+        | "device.debug.unlock"
+        | "device.debug.exit"
+        | "device.debug.reg_read"
+        | "device.debug.reg_write"
+        | "device.debug.reg_dump" => {
+            // Register debug is USB only; devd forwards it over the serial
+            // console and has no HTTP bridge route for it.
+            let req: DeviceDebugRequest = serde_json::from_value(params)?;
+            require_compatible_project_firmware(state, &req.device_id).await?;
+            let serial_method = method.trim_start_matches("device.");
+            let params = (!req.params.is_empty()).then_some(Value::Object(req.params));
+            Ok(redact_sensitive(
+                &usb_jsonl_request(state, &req.device_id, serial_method, params).await?,
+            ))
+        }
+        "device.sound.get" | "device.sound.defaults" => {
+            let req: DeviceIdRequest = serde_json::from_value(params)?;
+            require_compatible_project_firmware(state, &req.device_id).await?;
+            let serial_method = method.trim_start_matches("device.");
+            Ok(redact_sensitive(
+                &usb_jsonl_request(state, &req.device_id, serial_method, None).await?,
+            ))
+        }
+        "device.sound.set" => {
+            let req: DeviceSoundSetRequest = serde_json::from_value(params)?;
+            require_compatible_project_firmware(state, &req.device_id).await?;
+            Ok(redact_sensitive(
+                &usb_jsonl_request(
+                    state,
+                    &req.device_id,
+                    methods::SOUND_SET,
+                    Some(Value::Object(req.settings)),
+                )
+                .await?,
+            ))
+        }
+        "device.sound.pattern_set" => {
+            let req: DeviceSoundPatternRequest = serde_json::from_value(params)?;
+            require_compatible_project_firmware(state, &req.device_id).await?;
+            let steps = req
+                .steps
+                .ok_or_else(|| anyhow!("steps is required for device.sound.pattern_set"))?;
+            Ok(redact_sensitive(
+                &usb_jsonl_request(
+                    state,
+                    &req.device_id,
+                    methods::SOUND_PATTERN_SET,
+                    Some(json!({"slot": req.slot, "steps": steps})),
+                )
+                .await?,
+            ))
+        }
+        "device.sound.pattern_clear" => {
+            let req: DeviceSoundPatternRequest = serde_json::from_value(params)?;
+            require_compatible_project_firmware(state, &req.device_id).await?;
+            Ok(redact_sensitive(
+                &usb_jsonl_request(
+                    state,
+                    &req.device_id,
+                    methods::SOUND_PATTERN_CLEAR,
+                    Some(json!({"slot": req.slot})),
+                )
+                .await?,
+            ))
+        }
         "device.power.lock" => {
             let req: DevicePowerLockRequest = serde_json::from_value(params)?;
             require_compatible_project_firmware(state, &req.device_id).await?;
@@ -410,7 +630,7 @@ async fn dispatch_ipc_request(
                 &usb_jsonl_request(
                     state,
                     &req.device_id,
-                    "power.lock",
+                    methods::POWER_LOCK,
                     Some(json!({"owner": req.owner, "acquire": req.acquire})),
                 )
                 .await?,
@@ -420,6 +640,10 @@ async fn dispatch_ipc_request(
             let req: LeaseRequest = serde_json::from_value(params)?;
             ipc_create_lease(state, req).await
         }
+        "serial.lease.heartbeat" => {
+            let req: LeaseIdRequest = serde_json::from_value(params)?;
+            ipc_heartbeat_lease(state, &req.lease_id).await
+        }
         "serial.lease.release" => {
             let req: LeaseIdRequest = serde_json::from_value(params)?;
             ipc_release_lease(state, &req.lease_id).await
@@ -439,7 +663,7 @@ async fn dispatch_ipc_request(
             let req: DeviceIdRequest = serde_json::from_value(params)?;
             require_compatible_project_firmware(state, &req.device_id).await?;
             Ok(redact_sensitive(
-                &usb_jsonl_request(state, &req.device_id, "pd.diagnostics", None).await?,
+                &usb_jsonl_request(state, &req.device_id, methods::PD_DIAGNOSTICS, None).await?,
             ))
         }
         "firmware.catalog.validate" => {
@@ -470,6 +694,18 @@ struct DeviceWifiSetRequest {
     psk: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct TcpRegisterRequest {
+    addr: String,
+    token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeviceJsonlTcpSetRequest {
+    device_id: String,
+    token: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct DeviceSettingsResetRequest {
     device_id: String,
@@ -490,12 +726,47 @@ struct DevicePortPowerRequest {
     enabled: bool,
 }
 
+#[derive(Debug, Deserialize)]
+struct DevicePortDataRequest {
+    device_id: String,
+    port: String,
+    mode: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct DeviceHubRouteRequest {
     device_id: String,
     route: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct DeviceDisplaySetRequest {
+    device_id: String,
+    #[serde(flatten)]
+    settings: serde_json::Map<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeviceButtonsSetRequest {
+    device_id: String,
+    #[serde(flatten)]
+    settings: serde_json::Map<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeviceSoundSetRequest {
+    device_id: String,
+    #[serde(flatten)]
+    settings: serde_json::Map<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeviceSoundPatternRequest {
+    device_id: String,
+    slot: String,
+    steps: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct DevicePowerConfigSetRequest {
     device_id: String,
@@ -517,6 +788,34 @@ struct DevicePowerOwnerRequest {
     owner: Option<u32>,
 }
 
+#[derive(Debug, Deserialize)]
+struct DeviceI2cRecoverRequest {
+    device_id: String,
+    bus: String,
+    owner: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeviceLogsRequest {
+    device_id: String,
+    #[serde(flatten)]
+    params: serde_json::Map<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DevicePortSettingsRequest {
+    device_id: String,
+    #[serde(flatten)]
+    params: serde_json::Map<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeviceDebugRequest {
+    device_id: String,
+    #[serde(flatten)]
+    params: serde_json::Map<String, Value>,
+}
+
 #[derive(Debug, Deserialize)]
 struct DeviceIdleBiasSetRequest {
     device_id: String,
@@ -563,6 +862,9 @@ async fn ipc_list_devices(state: &AppState) -> anyhow::Result<Value> {
 }
 
 async fn ipc_scan_devices(state: &AppState) -> anyhow::Result<Value> {
+    if trace_replay_active(state).await {
+        return ipc_list_devices(state).await;
+    }
     let ports = list_serial_ports().context("serial enumeration failed")?;
     let mut inner = state.inner.lock().await;
     reconcile_scanned_usb_devices(&mut inner, ports);
@@ -625,6 +927,21 @@ async fn ipc_create_lease(state: &AppState, req: LeaseRequest) -> anyhow::Result
     }))
 }
 
+async fn ipc_heartbeat_lease(state: &AppState, lease_id: &str) -> anyhow::Result<Value> {
+    let mut inner = state.inner.lock().await;
+    let lease = inner
+        .leases
+        .get_mut(lease_id)
+        .ok_or_else(|| anyhow!("lease not found or expired"))?;
+    lease.expires_at = Instant::now() + Duration::from_millis(LEASE_TTL_MS);
+    Ok(json!({
+        "lease_id": lease.lease_id,
+        "device_id": lease.device_id,
+        "port_path": lease.port_path,
+        "lease_ttl_ms": LEASE_TTL_MS,
+    }))
+}
+
 async fn ipc_release_lease(state: &AppState, lease_id: &str) -> anyhow::Result<Value> {
     let removed = state.inner.lock().await.leases.remove(lease_id).is_some();
     Ok(json!({"ok": true, "released": removed}))
@@ -672,58 +989,8 @@ async fn ipc_device_reset(
         serial_guard: Some(serial_guard),
     };
     let result =
-        usb_jsonl_request_with_exclusive(state, device_id, "reboot", None, Some("reset")).await;
+        usb_jsonl_request_with_exclusive(state, device_id, methods::REBOOT, None, Some("reset"))
+            .await;
     drop(guard);
     Ok(redact_sensitive(&result?))
 }
-
-pub async fn ipc_call(endpoint: &str, method: &str, params: Value) -> anyhow::Result<Value> {
-    let request = IpcRequest {
-        id: next_id(),
-        method: method.to_string(),
-        params,
-    };
-    #[cfg(unix)]
-    {
-        let stream = tokio::net::UnixStream::connect(endpoint)
-            .await
-            .with_context(|| format!("connect IPC socket {endpoint}"))?;
-        send_ipc_request(stream, request).await
-    }
-    #[cfg(windows)]
-    {
-        let stream = tokio::net::windows::named_pipe::ClientOptions::new()
-            .open(endpoint)
-            .with_context(|| format!("connect IPC pipe {endpoint}"))?;
-        send_ipc_request(stream, request).await
-    }
-    #[cfg(not(any(unix, windows)))]
-    {
-        let _ = (endpoint, request);
-        Err(anyhow!("isolapurr IPC is unsupported on this platform"))
-    }
-}
-
-async fn send_ipc_request<S>(mut stream: S, request: IpcRequest) -> anyhow::Result<Value>
-where
-    S: AsyncRead + AsyncWrite + Unpin,
-{
-    let mut encoded = serde_json::to_vec(&request)?;
-    encoded.push(b'\n');
-    stream.write_all(&encoded).await?;
-    stream.flush().await?;
-    let mut reader = BufReader::new(stream);
-    let mut line = String::new();
-    reader.read_line(&mut line).await?;
-    let response: IpcResponse = serde_json::from_str(line.trim()).context("decode IPC response")?;
-    if response.ok {
-        Ok(response.result.unwrap_or_else(|| json!({})))
-    } else {
-        Err(anyhow!(
-            "{}",
-            response
-                .error
-                .unwrap_or_else(|| "IPC request failed".to_string())
-        ))
-    }
-}
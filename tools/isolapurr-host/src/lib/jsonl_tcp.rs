@@ -0,0 +1,170 @@
+#[cfg(test)]
+#[path = "jsonl_tcp_tests.rs"]
+mod jsonl_tcp_tests;
+
+/// Where a device's JSONL requests go: its Local USB port, or the TCP console
+/// of a hub registered by address. Local USB wins when a device has both.
+enum JsonlRoute {
+    Serial(String),
+    Tcp(TcpTarget),
+}
+
+impl JsonlRoute {
+    fn for_device(device: &DeviceRecord) -> anyhow::Result<Self> {
+        match (&device.usb, &device.tcp) {
+            (Some(usb), _) => Ok(Self::Serial(usb.port_path.clone())),
+            (None, Some(tcp)) => Ok(Self::Tcp(tcp.clone())),
+            (None, None) => Err(anyhow!("device has no Local USB or JSONL TCP target")),
+        }
+    }
+
+    /// Serial port path or `host:port`; also the key of the per-target lock.
+    fn target(&self) -> &str {
+        match self {
+            Self::Serial(port_path) => port_path,
+            Self::Tcp(tcp) => &tcp.addr,
+        }
+    }
+
+    fn trace_transport(&self) -> &'static str {
+        match self {
+            Self::Serial(_) => TRACE_TRANSPORT_USB_SERIAL,
+            Self::Tcp(_) => TRACE_TRANSPORT_JSONL_TCP,
+        }
+    }
+}
+
+fn stable_tcp_device_id(addr: &str) -> String {
+    let sanitized = addr
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '-' })
+        .collect::<String>();
+    format!("tcp-{sanitized}")
+}
+
+fn info_device_id(info: &Value) -> Option<&str> {
+    let device = info
+        .get("result")
+        .and_then(|value| value.get("device"))
+        .or_else(|| info.get("device"))?;
+    device
+        .get("device_id")
+        .or_else(|| device.get("deviceId"))
+        .and_then(Value::as_str)
+}
+
+/// Probes `info` on the hub's JSONL TCP console and adds it as a device that
+/// requests reach over TCP. Re-registering an address replaces its token.
+async fn register_tcp_device(
+    state: &AppState,
+    addr: &str,
+    token: Option<String>,
+) -> anyhow::Result<DeviceRecord> {
+    let addr = tcp::socket_addr(addr);
+    let request = json!({"id": next_id(), "method": methods::INFO, "params": {}});
+    let timeout = Duration::from_millis(serial_timeout_ms_for_method(methods::INFO));
+    let info = tcp::jsonl_roundtrip(&addr, token.as_deref(), &request, timeout)
+        .await
+        .with_context(|| format!("{addr} did not answer IsolaPurr `info` over JSONL TCP"))?;
+    validate_project_firmware(&info)?;
+    let device = info.get("result").and_then(|result| result.get("device"));
+    let identity = DeviceIdentity {
+        device_id: info_device_id(&info).map(str::to_string),
+        mac: device
+            .and_then(|device| device.get("mac"))
+            .and_then(Value::as_str)
+            .map(str::to_string),
+    };
+    let display_name = device
+        .and_then(|device| device.get("hostname"))
+        .and_then(Value::as_str)
+        .unwrap_or(&addr)
+        .to_string();
+    let target = TcpTarget {
+        addr: addr.clone(),
+        token,
+    };
+    let id = stable_tcp_device_id(&addr);
+    let mut inner = state.inner.lock().await;
+    let record = inner
+        .devices
+        .entry(id.clone())
+        .and_modify(|device| {
+            device.display_name = display_name.clone();
+            device.connection = "available".to_string();
+            device.tcp = Some(target.clone());
+        })
+        .or_insert(DeviceRecord {
+            id,
+            display_name: display_name.clone(),
+            connection: "available".to_string(),
+            usb: None,
+            http: None,
+            tcp: Some(target),
+            identity: None,
+            session: DeviceSession::default(),
+        });
+    record.identity = Some(serde_json::to_value(identity)?);
+    Ok(record.clone())
+}
+
+/// Sets the JSONL TCP token over Local USB (`jsonl_tcp.*` is USB-only) and
+/// hands it to every TCP target registered for the same hub. Without a
+/// `token`, devd generates one and returns it once as `generated_token`.
+async fn usb_jsonl_tcp_set(
+    state: &AppState,
+    device_id: &str,
+    token: Option<String>,
+) -> anyhow::Result<Value> {
+    device_usb_port_path(state, device_id).await?;
+    let info = require_compatible_project_firmware(state, device_id).await?;
+    let generated = token.is_none();
+    let token = token.unwrap_or_else(generate_token);
+    let response = usb_jsonl_request(
+        state,
+        device_id,
+        methods::JSONL_TCP_SET,
+        Some(json!({"token": token})),
+    )
+    .await?;
+    if response.get("ok").and_then(Value::as_bool) != Some(true) {
+        return Ok(redact_sensitive(&response));
+    }
+    update_hub_tcp_tokens(state, &info, Some(&token)).await;
+    let mut response = redact_sensitive(&response);
+    if generated && let Some(object) = response.as_object_mut() {
+        object.insert("generated_token".to_string(), Value::String(token));
+    }
+    Ok(response)
+}
+
+async fn usb_jsonl_tcp_clear(state: &AppState, device_id: &str) -> anyhow::Result<Value> {
+    device_usb_port_path(state, device_id).await?;
+    let info = require_compatible_project_firmware(state, device_id).await?;
+    let response = usb_jsonl_request(state, device_id, methods::JSONL_TCP_CLEAR, None).await?;
+    if response.get("ok").and_then(Value::as_bool) == Some(true) {
+        update_hub_tcp_tokens(state, &info, None).await;
+    }
+    Ok(redact_sensitive(&response))
+}
+
+async fn update_hub_tcp_tokens(state: &AppState, info: &Value, token: Option<&str>) {
+    let Some(hub_id) = info_device_id(info) else {
+        return;
+    };
+    let mut inner = state.inner.lock().await;
+    for device in inner.devices.values_mut() {
+        let same_hub = device
+            .identity
+            .as_ref()
+            .and_then(|identity| identity.get("deviceId"))
+            .and_then(Value::as_str)
+            == Some(hub_id);
+        if !same_hub {
+            continue;
+        }
+        if let Some(tcp) = device.tcp.as_mut() {
+            tcp.token = token.map(str::to_string);
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use isolapurr_api::methods;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use super::{
+    AppState, error_from_anyhow, redact_sensitive, register_tcp_device, require_auth,
+    require_compatible_project_firmware, usb_jsonl_request, usb_jsonl_tcp_clear, usb_jsonl_tcp_set,
+};
+
+pub(super) fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/tcp/register", post(tcp_register))
+        .route(
+            "/api/v1/devices/{id}/jsonl-tcp",
+            get(jsonl_tcp_get).put(jsonl_tcp_set),
+        )
+        .route(
+            "/api/v1/devices/{id}/jsonl-tcp/clear",
+            post(jsonl_tcp_clear),
+        )
+}
+
+#[derive(Debug, Deserialize)]
+struct TcpRegisterRequest {
+    addr: String,
+    token: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct JsonlTcpSetRequest {
+    token: Option<String>,
+}
+
+async fn tcp_register(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<TcpRegisterRequest>,
+) -> Response {
+    if let Err(response) = require_auth(&headers, &state) {
+        return *response;
+    }
+    match register_tcp_device(&state, &req.addr, req.token).await {
+        Ok(device) => Json(json!({ "ok": true, "device": device })).into_response(),
+        Err(err) => error_from_anyhow(err),
+    }
+}
+
+async fn jsonl_tcp_get(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Response {
+    if let Err(response) = require_auth(&headers, &state) {
+        return *response;
+    }
+    if let Err(err) = require_compatible_project_firmware(&state, &id).await {
+        return error_from_anyhow(err);
+    }
+    match usb_jsonl_request(&state, &id, methods::JSONL_TCP_GET, None).await {
+        Ok(value) => Json(redact_sensitive(&value)).into_response(),
+        Err(err) => error_from_anyhow(err),
+    }
+}
+
+async fn jsonl_tcp_set(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    body: Option<Json<JsonlTcpSetRequest>>,
+) -> Response {
+    if let Err(response) = require_auth(&headers, &state) {
+        return *response;
+    }
+    let req = body.map(|Json(req)| req).unwrap_or_default();
+    match usb_jsonl_tcp_set(&state, &id, req.token).await {
+        Ok(value) => Json::<Value>(value).into_response(),
+        Err(err) => error_from_anyhow(err),
+    }
+}
+
+async fn jsonl_tcp_clear(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Response {
+    if let Err(response) = require_auth(&headers, &state) {
+        return *response;
+    }
+    match usb_jsonl_tcp_clear(&state, &id).await {
+        Ok(value) => Json::<Value>(value).into_response(),
+        Err(err) => error_from_anyhow(err),
+    }
+}
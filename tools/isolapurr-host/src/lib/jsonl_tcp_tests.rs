@@ -0,0 +1,111 @@
+use super::*;
+
+const TOKEN: &str = "0123456789abcdef";
+
+/// Token-gated console: answers `auth`, `info`, and `ports.get` on each
+/// connection, refusing anything before a good `auth`.
+async fn fake_jsonl_tcp_hub() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind");
+    let addr = listener.local_addr().expect("addr").to_string();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let (read, mut write) = stream.into_split();
+                let mut lines = BufReader::new(read).lines();
+                let mut authed = false;
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let request: Value = serde_json::from_str(&line).expect("json");
+                    let id = request["id"].clone();
+                    let method = request["method"].as_str().unwrap_or_default();
+                    let response = match method {
+                        methods::AUTH => {
+                            authed = request["token"] == TOKEN;
+                            json!({"id": id, "ok": authed})
+                        }
+                        _ if !authed => {
+                            json!({"id": id, "ok": false, "error": {"code": "unauthorized"}})
+                        }
+                        methods::INFO => json!({"id": id, "ok": true, "result": {"device": {
+                            "device_id": "aabbcc001122",
+                            "hostname": "isolapurr-hub",
+                            "firmware": {"name": "isolapurr-usb-hub", "version": "0.1.0"}
+                        }}}),
+                        _ => json!({"id": id, "ok": true, "result": {"method": method}}),
+                    };
+                    let mut encoded = serde_json::to_vec(&response).expect("encode");
+                    encoded.push(b'\n');
+                    if write.write_all(&encoded).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+    addr
+}
+
+#[tokio::test]
+async fn registers_a_tcp_hub_and_routes_requests_over_it() {
+    let addr = fake_jsonl_tcp_hub().await;
+    let state = AppState::new("ipc://test");
+
+    let device = register_tcp_device(&state, &addr, Some(TOKEN.to_string()))
+        .await
+        .expect("register");
+    assert_eq!(device.id, stable_tcp_device_id(&addr));
+    assert_eq!(device.display_name, "isolapurr-hub");
+    assert_eq!(
+        device
+            .identity
+            .as_ref()
+            .map(|identity| &identity["deviceId"]),
+        Some(&json!("aabbcc001122"))
+    );
+    let listed = serde_json::to_value(&device).expect("serialize");
+    assert_eq!(listed["tcp"], json!({"addr": addr}));
+
+    let response = usb_jsonl_request(&state, &device.id, methods::PORTS_GET, None)
+        .await
+        .expect("ports.get over TCP");
+    assert_eq!(response["result"]["method"], methods::PORTS_GET);
+}
+
+#[tokio::test]
+async fn registration_fails_without_the_console_token() {
+    let addr = fake_jsonl_tcp_hub().await;
+    let state = AppState::new("ipc://test");
+
+    let err = register_tcp_device(&state, &addr, Some("wrong-token-value".to_string()))
+        .await
+        .expect_err("bad token");
+    assert!(format!("{err:#}").contains("refused auth"), "{err:#}");
+    assert!(state.inner.lock().await.devices.is_empty());
+}
+
+#[tokio::test]
+async fn token_changes_reach_tcp_targets_of_the_same_hub() {
+    let addr = fake_jsonl_tcp_hub().await;
+    let state = AppState::new("ipc://test");
+    let device = register_tcp_device(&state, &addr, Some(TOKEN.to_string()))
+        .await
+        .expect("register");
+    let info = json!({"ok": true, "result": {"device": {"device_id": "aabbcc001122"}}});
+    let other = json!({"ok": true, "result": {"device": {"device_id": "ffeeddccbbaa"}}});
+
+    update_hub_tcp_tokens(&state, &other, None).await;
+    assert_eq!(tcp_token(&state, &device.id).await.as_deref(), Some(TOKEN));
+
+    update_hub_tcp_tokens(&state, &info, None).await;
+    assert_eq!(tcp_token(&state, &device.id).await, None);
+    let response = usb_jsonl_request(&state, &device.id, methods::PORTS_GET, None)
+        .await
+        .expect("refused response");
+    assert_eq!(response["error"]["code"], "unauthorized");
+}
+
+async fn tcp_token(state: &AppState, device_id: &str) -> Option<String> {
+    let inner = state.inner.lock().await;
+    inner.devices[device_id].tcp.as_ref()?.token.clone()
+}
@@ -0,0 +1,98 @@
+use anyhow::Context as _;
+use axum::{
+    Json,
+    extract::State,
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::time::Instant;
+
+use super::{
+    AppState, TRACE_TRANSPORT_USB_SERIAL, acquire_serial_port_guard, error_from_anyhow,
+    local_usb_board_info, push_trace, record_trace, register_requested_usb_device, require_auth,
+    serial_jsonl_roundtrip_with_timeout, stable_usb_device_id,
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct SerialBoardInfoRequest {
+    port_path: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct SerialRequestBody {
+    port_path: String,
+    request: Value,
+    timeout_ms: Option<u64>,
+}
+
+pub(super) async fn serial_board_info(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<SerialBoardInfoRequest>,
+) -> Response {
+    if let Err(response) = require_auth(&headers, &state) {
+        return *response;
+    }
+    if let Err(err) = register_requested_usb_device(&state, &req.port_path).await {
+        return error_from_anyhow(err);
+    }
+    match local_usb_board_info(&state, &req.port_path).await {
+        Ok(result) => Json(json!({ "ok": true, "result": result })).into_response(),
+        Err(err) => error_from_anyhow(err),
+    }
+}
+
+pub(super) async fn serial_request(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<SerialRequestBody>,
+) -> Response {
+    if let Err(response) = require_auth(&headers, &state) {
+        return *response;
+    }
+    if let Err(err) = register_requested_usb_device(&state, &req.port_path).await {
+        return error_from_anyhow(err);
+    }
+    let device_id = stable_usb_device_id(&req.port_path);
+    let request = req.request.clone();
+    let request_method = request
+        .get("method")
+        .and_then(Value::as_str)
+        .unwrap_or("request")
+        .to_string();
+    push_trace(&state, &device_id, "tx", &request_method, &request).await;
+    let port_path = req.port_path.clone();
+    let timeout_ms = req.timeout_ms;
+    let response_method = request_method.clone();
+    let started = Instant::now();
+    let result = async {
+        let _guard = acquire_serial_port_guard(&state, &port_path, None).await?;
+        tokio::task::spawn_blocking(move || {
+            serial_jsonl_roundtrip_with_timeout(&port_path, request, timeout_ms)
+        })
+        .await
+        .context("serial worker join")?
+    }
+    .await;
+    record_trace(
+        &state,
+        &device_id,
+        TRACE_TRANSPORT_USB_SERIAL,
+        &req.port_path,
+        &req.request,
+        &result,
+        started,
+    )
+    .await;
+    match result {
+        Ok(response) => {
+            push_trace(&state, &device_id, "rx", &response_method, &response).await;
+            Json(json!({ "response": response })).into_response()
+        }
+        Err(err) => error_from_anyhow(err),
+    }
+}
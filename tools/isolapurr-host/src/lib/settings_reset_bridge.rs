@@ -22,7 +22,8 @@ pub(super) struct SettingsResetRequest {
 }
 
 enum SettingsResetTarget {
-    Usb,
+    /// Local USB or JSONL TCP, both through `usb_jsonl_request`.
+    Jsonl,
     Http(String),
 }
 
@@ -32,13 +33,13 @@ async fn settings_reset_target(state: &AppState, id: &str) -> anyhow::Result<Set
         .devices
         .get(id)
         .ok_or_else(|| anyhow!("device not found"))?;
-    if device.usb.is_some() {
-        return Ok(SettingsResetTarget::Usb);
+    if device.usb.is_some() || device.tcp.is_some() {
+        return Ok(SettingsResetTarget::Jsonl);
     }
     if let Some(http) = device.http.as_ref() {
         return Ok(SettingsResetTarget::Http(http.base_url.clone()));
     }
-    Err(anyhow!("device has no Local USB target"))
+    Err(anyhow!("device has no USB, TCP, or HTTP target"))
 }
 
 async fn http_settings_reset_request(
@@ -99,13 +100,13 @@ pub(super) async fn settings_reset(
         Ok(target) => target,
         Err(err) => return error_from_anyhow(err),
     };
-    if matches!(target, SettingsResetTarget::Usb)
+    if matches!(target, SettingsResetTarget::Jsonl)
         && let Err(err) = require_compatible_project_firmware(&state, &id).await
     {
         return error_from_anyhow(err);
     }
     match target {
-        SettingsResetTarget::Usb => {
+        SettingsResetTarget::Jsonl => {
             match usb_settings_reset_request(&state, &id, &req.scope, req.owner).await {
                 Ok(value) => Json(redact_sensitive(&value)).into_response(),
                 Err(err) => error_from_anyhow(err),
@@ -0,0 +1,93 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+};
+use isolapurr_api::methods;
+use serde_json::{Value, json};
+
+use super::{
+    AppState, error_from_anyhow, redact_sensitive, require_auth,
+    require_compatible_project_firmware, usb_jsonl_request,
+};
+
+async fn sound_request(
+    state: &AppState,
+    headers: &HeaderMap,
+    id: &str,
+    method: &str,
+    params: Option<Value>,
+) -> Response {
+    if let Err(response) = require_auth(headers, state) {
+        return *response;
+    }
+    if let Err(err) = require_compatible_project_firmware(state, id).await {
+        return error_from_anyhow(err);
+    }
+    match usb_jsonl_request(state, id, method, params).await {
+        Ok(value) => Json(redact_sensitive(&value)).into_response(),
+        Err(err) => error_from_anyhow(err),
+    }
+}
+
+pub(super) async fn sound_get(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Response {
+    sound_request(&state, &headers, &id, methods::SOUND_GET, None).await
+}
+
+pub(super) async fn sound_set(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Json(body): Json<Value>,
+) -> Response {
+    sound_request(&state, &headers, &id, methods::SOUND_SET, Some(body)).await
+}
+
+pub(super) async fn sound_defaults(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Response {
+    sound_request(&state, &headers, &id, methods::SOUND_DEFAULTS, None).await
+}
+
+pub(super) async fn sound_pattern_set(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((id, slot)): Path<(String, String)>,
+    Json(body): Json<Value>,
+) -> Response {
+    let params = json!({
+        "slot": slot,
+        "steps": body.get("steps").cloned().unwrap_or(Value::Null),
+    });
+    sound_request(
+        &state,
+        &headers,
+        &id,
+        methods::SOUND_PATTERN_SET,
+        Some(params),
+    )
+    .await
+}
+
+pub(super) async fn sound_pattern_clear(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((id, slot)): Path<(String, String)>,
+) -> Response {
+    let params = json!({"slot": slot});
+    sound_request(
+        &state,
+        &headers,
+        &id,
+        methods::SOUND_PATTERN_CLEAR,
+        Some(params),
+    )
+    .await
+}
@@ -242,7 +242,7 @@ async fn require_compatible_project_firmware(
     state: &AppState,
     device_id: &str,
 ) -> anyhow::Result<Value> {
-    let info = usb_jsonl_request(state, device_id, "info", None).await.with_context(|| {
+    let info = usb_jsonl_request(state, device_id, methods::INFO, None).await.with_context(|| {
         "device did not respond to IsolaPurr `info`; it may be in download mode or running non-IsolaPurr firmware"
     })?;
     validate_project_firmware(&info)?;
@@ -256,9 +256,8 @@ pub fn validate_identify_capability(info: &Value) -> anyhow::Result<()> {
             info.get("result")
                 .and_then(|result| result.get("capabilities"))
         })
-        .and_then(|capabilities| capabilities.get("identify"))
-        .and_then(Value::as_bool)
-        .unwrap_or(false);
+        .and_then(|capabilities| Capabilities::deserialize(capabilities).ok())
+        .is_some_and(|capabilities| capabilities.identify);
     if !supported {
         return Err(anyhow!(
             "device does not advertise capabilities.identify=true; refusing identify"
@@ -286,7 +285,7 @@ async fn require_project_firmware_for_upgrade(
     state: &AppState,
     device_id: &str,
 ) -> anyhow::Result<Value> {
-    let info = usb_jsonl_request(state, device_id, "info", None).await.with_context(|| {
+    let info = usb_jsonl_request(state, device_id, methods::INFO, None).await.with_context(|| {
         "device did not respond to IsolaPurr `info`; it may be in download mode or running non-IsolaPurr firmware"
     })?;
     let firmware = project_firmware_metadata(&info)?;
@@ -319,13 +318,69 @@ fn validate_project_firmware_name(firmware: &Value) -> anyhow::Result<()> {
 }
 
 fn version_at_least(actual: &str, minimum: &str) -> bool {
-    let actual = parse_version_triplet(actual);
-    let minimum = parse_version_triplet(minimum);
-    matches!((actual, minimum), (Some(actual), Some(minimum)) if actual >= minimum)
+    compare_firmware_versions(actual, minimum).is_some_and(|order| order.is_ge())
+}
+
+/// Orders firmware versions the semver way: `v` and `+build` are ignored, a
+/// prerelease (`0.5.0-dev.1`) sorts before its release, and numeric prerelease
+/// parts compare as numbers. `None` when either side is not a version.
+pub fn compare_firmware_versions(left: &str, right: &str) -> Option<std::cmp::Ordering> {
+    let (left_core, left_pre) = split_firmware_version(left)?;
+    let (right_core, right_pre) = split_firmware_version(right)?;
+    Some(
+        left_core
+            .cmp(&right_core)
+            .then_with(|| compare_prerelease(left_pre, right_pre)),
+    )
+}
+
+/// `(major, minor, patch)`.
+type VersionCore = (u64, u64, u64);
+
+fn split_firmware_version(value: &str) -> Option<(VersionCore, Option<&str>)> {
+    let value = value.trim().trim_start_matches('v');
+    let value = value.split_once('+').map_or(value, |(version, _)| version);
+    match value.split_once('-') {
+        Some((core, pre)) if !pre.is_empty() => Some((parse_version_triplet(core)?, Some(pre))),
+        Some(_) => None,
+        None => Some((parse_version_triplet(value)?, None)),
+    }
+}
+
+fn compare_prerelease(left: Option<&str>, right: Option<&str>) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+
+    let (left, right) = match (left, right) {
+        (None, None) => return Ordering::Equal,
+        (None, Some(_)) => return Ordering::Greater,
+        (Some(_), None) => return Ordering::Less,
+        (Some(left), Some(right)) => (left, right),
+    };
+    let mut left = left.split('.');
+    let mut right = right.split('.');
+    loop {
+        let (left, right) = match (left.next(), right.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(left), Some(right)) => (left, right),
+        };
+        let order = match (left.parse::<u64>(), right.parse::<u64>()) {
+            (Ok(left), Ok(right)) => left.cmp(&right),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => left.cmp(right),
+        };
+        if order.is_ne() {
+            return order;
+        }
+    }
 }
 
-fn parse_version_triplet(value: &str) -> Option<(u64, u64, u64)> {
-    let mut parts = value.trim_start_matches('v').split('.');
+/// Parts after the patch number (`0.5.0.1`) are ignored, as the compatibility
+/// check always has.
+fn parse_version_triplet(value: &str) -> Option<VersionCore> {
+    let mut parts = value.split('.');
     let major = parts.next()?.parse().ok()?;
     let minor = parts.next().unwrap_or("0").parse().ok()?;
     let patch = parts.next().unwrap_or("0").parse().ok()?;
@@ -377,7 +432,7 @@ async fn capture_first_time_identity_after_flash(
         match usb_jsonl_request_with_exclusive(
             state,
             device_id,
-            "info",
+            methods::INFO,
             None,
             Some("firmware flash"),
         )
@@ -779,25 +834,30 @@ fn error_from_anyhow(err: anyhow::Error) -> Response {
 }
 
 fn unauthorized(message: &str) -> Response {
-    error_response(StatusCode::UNAUTHORIZED, "unauthorized", message, false)
+    error_response(
+        StatusCode::UNAUTHORIZED,
+        errors::UNAUTHORIZED,
+        message,
+        false,
+    )
 }
 
 fn bad_request(message: &str) -> Response {
-    error_response(StatusCode::BAD_REQUEST, "bad_request", message, false)
+    error_response(StatusCode::BAD_REQUEST, errors::BAD_REQUEST, message, false)
 }
 
 fn not_found(message: &str) -> Response {
-    error_response(StatusCode::NOT_FOUND, "not_found", message, false)
+    error_response(StatusCode::NOT_FOUND, errors::NOT_FOUND, message, false)
 }
 
 fn conflict(message: &str) -> Response {
-    error_response(StatusCode::CONFLICT, "busy", message, true)
+    error_response(StatusCode::CONFLICT, errors::BUSY, message, true)
 }
 
 fn internal_error(message: &str) -> Response {
     error_response(
         StatusCode::INTERNAL_SERVER_ERROR,
-        "internal_error",
+        errors::INTERNAL_ERROR,
         message,
         false,
     )
@@ -814,7 +874,7 @@ fn error_response(
         Json(ErrorEnvelope {
             error: ErrorInfo {
                 code,
-                message: message.to_string(),
+                message,
                 retryable,
             },
         }),
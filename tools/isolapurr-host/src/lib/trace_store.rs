@@ -0,0 +1,338 @@
+#[cfg(test)]
+#[path = "trace_store_tests.rs"]
+mod trace_store_tests;
+
+const TRACE_FILE_STEM: &str = "devd-trace";
+const TRACE_FILE_MAX_BYTES: u64 = 4 * 1024 * 1024;
+const TRACE_MAX_FILES: usize = 5;
+const TRACE_TRANSPORT_USB_SERIAL: &str = "usb_serial";
+const TRACE_TRANSPORT_JSONL_TCP: &str = "jsonl_tcp";
+const REPLAY_IPC_FILE_NAME: &str = "devd-replay.sock";
+
+/// One device exchange as written to the persistent trace files: the request
+/// devd sent, what came back (or why nothing did), and how long it took.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceRecord {
+    pub timestamp_unix_ms: u128,
+    pub device_id: String,
+    pub transport: String,
+    pub target: String,
+    pub method: String,
+    pub request: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+#[derive(Default)]
+struct TraceState {
+    recorder: Option<TraceRecorder>,
+    replay: Option<TraceReplay>,
+}
+
+pub fn default_trace_dir() -> anyhow::Result<PathBuf> {
+    let dirs = ProjectDirs::from("cc", "isolapurr", "isolapurr")
+        .ok_or_else(|| anyhow!("cannot resolve user data directory"))?;
+    Ok(dirs.data_local_dir().join("traces"))
+}
+
+pub fn default_replay_ipc_endpoint() -> String {
+    #[cfg(windows)]
+    {
+        format!("{DEFAULT_WINDOWS_PIPE_NAME}-replay")
+    }
+    #[cfg(not(windows))]
+    {
+        PathBuf::from(default_ipc_endpoint())
+            .with_file_name(REPLAY_IPC_FILE_NAME)
+            .to_string_lossy()
+            .to_string()
+    }
+}
+
+/// `devd-trace.jsonl` is the live file; rotation shifts it to
+/// `devd-trace.1.jsonl` and so on, dropping the oldest.
+fn trace_file_path(dir: &FsPath, index: usize) -> PathBuf {
+    if index == 0 {
+        dir.join(format!("{TRACE_FILE_STEM}.jsonl"))
+    } else {
+        dir.join(format!("{TRACE_FILE_STEM}.{index}.jsonl"))
+    }
+}
+
+/// Existing trace files in `dir`, oldest first.
+pub fn trace_files(dir: &FsPath) -> Vec<PathBuf> {
+    (0..TRACE_MAX_FILES)
+        .rev()
+        .map(|index| trace_file_path(dir, index))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// A torn last line, left by a daemon killed mid-write, is skipped.
+pub fn read_trace_records(path: &FsPath) -> anyhow::Result<Vec<TraceRecord>> {
+    let raw = fs::read_to_string(path).with_context(|| format!("read trace {}", path.display()))?;
+    let torn_tail = !raw.is_empty() && !raw.ends_with('\n');
+    let line_count = raw.lines().count();
+    let mut records = Vec::new();
+    for (index, line) in raw.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(line) {
+            Ok(record) => records.push(record),
+            Err(_) if torn_tail && index + 1 == line_count => {}
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("parse trace {}:{}", path.display(), index + 1));
+            }
+        }
+    }
+    Ok(records)
+}
+
+#[derive(Debug)]
+struct TraceRecorder {
+    dir: PathBuf,
+    file: fs::File,
+    written: u64,
+}
+
+impl TraceRecorder {
+    fn open(dir: &FsPath) -> anyhow::Result<Self> {
+        fs::create_dir_all(dir).with_context(|| format!("create {}", dir.display()))?;
+        let file = open_trace_file(dir)?;
+        let written = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            file,
+            written,
+        })
+    }
+
+    fn append(&mut self, record: &TraceRecord) -> anyhow::Result<()> {
+        use std::io::Write as _;
+
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        if self.written > 0 && self.written + line.len() as u64 > TRACE_FILE_MAX_BYTES {
+            self.rotate()?;
+        }
+        // One write per record so a crash leaves at most a torn last line.
+        self.file.write_all(&line)?;
+        self.written += line.len() as u64;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> anyhow::Result<()> {
+        for index in (1..TRACE_MAX_FILES).rev() {
+            let from = trace_file_path(&self.dir, index - 1);
+            if from.is_file() {
+                fs::rename(&from, trace_file_path(&self.dir, index))
+                    .with_context(|| format!("rotate {}", from.display()))?;
+            }
+        }
+        self.file = open_trace_file(&self.dir)?;
+        self.written = 0;
+        Ok(())
+    }
+}
+
+fn open_trace_file(dir: &FsPath) -> anyhow::Result<fs::File> {
+    let path = trace_file_path(dir, 0);
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("open trace {}", path.display()))
+}
+
+/// A trace directory that cannot be opened only disables recording; devd
+/// still serves devices.
+async fn enable_trace_recording(state: &AppState, trace_dir: Option<&FsPath>) {
+    let Some(dir) = trace_dir else {
+        return;
+    };
+    match TraceRecorder::open(dir) {
+        Ok(recorder) => {
+            tracing::info!("isolapurr-devd recording traces to {}", dir.display());
+            state.inner.lock().await.trace.recorder = Some(recorder);
+        }
+        Err(err) => tracing::warn!("trace recording disabled: {err:#}"),
+    }
+}
+
+async fn record_trace(
+    state: &AppState,
+    device_id: &str,
+    transport: &str,
+    target: &str,
+    request: &Value,
+    result: &anyhow::Result<Value>,
+    started: Instant,
+) {
+    let mut inner = state.inner.lock().await;
+    let Some(recorder) = inner.trace.recorder.as_mut() else {
+        return;
+    };
+    let record = TraceRecord {
+        timestamp_unix_ms: now_unix_millis(),
+        device_id: device_id.to_string(),
+        transport: transport.to_string(),
+        target: target.to_string(),
+        method: request
+            .get("method")
+            .and_then(Value::as_str)
+            .unwrap_or("request")
+            .to_string(),
+        request: redact_sensitive(request),
+        response: result.as_ref().ok().map(redact_sensitive),
+        error: result.as_ref().err().map(|err| format!("{err:#}")),
+        duration_ms: started.elapsed().as_millis() as u64,
+    };
+    if let Err(err) = recorder.append(&record) {
+        tracing::warn!("trace recording disabled: {err:#}");
+        inner.trace.recorder = None;
+    }
+}
+
+/// Recorded responses keyed by device and method. Each request takes the next
+/// response recorded with the same params, falling back to the next one for
+/// the method; the last response of a queue repeats once it is reached.
+#[derive(Debug, Default)]
+struct TraceReplay {
+    exact: HashMap<(String, String, String), VecDeque<TraceRecord>>,
+    by_method: HashMap<(String, String), VecDeque<TraceRecord>>,
+}
+
+impl TraceReplay {
+    fn new(records: &[TraceRecord]) -> Self {
+        let mut replay = Self::default();
+        for record in records {
+            let params = replay_params_key(&record.request);
+            replay
+                .exact
+                .entry((record.device_id.clone(), record.method.clone(), params))
+                .or_default()
+                .push_back(record.clone());
+            replay
+                .by_method
+                .entry((record.device_id.clone(), record.method.clone()))
+                .or_default()
+                .push_back(record.clone());
+        }
+        replay
+    }
+
+    fn next(&mut self, device_id: &str, request: &Value) -> anyhow::Result<Value> {
+        let method = request
+            .get("method")
+            .and_then(Value::as_str)
+            .unwrap_or("request")
+            .to_string();
+        let exact_key = (
+            device_id.to_string(),
+            method.clone(),
+            replay_params_key(request),
+        );
+        let record = match self.exact.get_mut(&exact_key) {
+            Some(queue) => pop_or_repeat(queue),
+            None => self
+                .by_method
+                .get_mut(&(device_id.to_string(), method.clone()))
+                .map(pop_or_repeat)
+                .ok_or_else(|| anyhow!("trace has no recorded `{method}` for {device_id}"))?,
+        };
+        match (record.response, record.error) {
+            (Some(mut response), _) => {
+                if let (Some(object), Some(id)) = (response.as_object_mut(), request.get("id")) {
+                    object.insert("id".to_string(), id.clone());
+                }
+                Ok(response)
+            }
+            (None, error) => Err(anyhow!(
+                "{}",
+                error.unwrap_or_else(|| "recorded request had no response".to_string())
+            )),
+        }
+    }
+}
+
+fn pop_or_repeat(queue: &mut VecDeque<TraceRecord>) -> TraceRecord {
+    if queue.len() > 1 {
+        queue.pop_front().expect("queue has records")
+    } else {
+        queue[0].clone()
+    }
+}
+
+fn replay_params_key(request: &Value) -> String {
+    request
+        .get("params")
+        .map(Value::to_string)
+        .unwrap_or_default()
+}
+
+/// `None` outside replay mode; otherwise the recorded answer for `request`.
+async fn replay_trace_response(
+    state: &AppState,
+    device_id: &str,
+    request: &Value,
+) -> Option<anyhow::Result<Value>> {
+    let mut inner = state.inner.lock().await;
+    let replay = inner.trace.replay.as_mut()?;
+    Some(replay.next(device_id, request))
+}
+
+async fn trace_replay_active(state: &AppState) -> bool {
+    state.inner.lock().await.trace.replay.is_some()
+}
+
+fn replay_device_records(records: &[TraceRecord]) -> Vec<DeviceRecord> {
+    let mut seen = HashSet::new();
+    records
+        .iter()
+        .filter(|record| seen.insert(record.device_id.clone()))
+        .map(|record| DeviceRecord {
+            id: record.device_id.clone(),
+            display_name: format!("Replay {}", record.device_id),
+            connection: "available".to_string(),
+            usb: Some(UsbTarget {
+                port_path: record.target.clone(),
+                label: "trace replay".to_string(),
+                vendor_id: None,
+                product_id: None,
+                serial_number: None,
+            }),
+            http: None,
+            tcp: None,
+            identity: None,
+            session: DeviceSession::default(),
+        })
+        .collect()
+}
+
+/// Serves IPC from recorded traces instead of hardware: the traced devices are
+/// listed, and each device request gets its recorded response.
+pub async fn serve_replay_ipc(config: IpcConfig, traces: &[PathBuf]) -> anyhow::Result<()> {
+    let mut records = Vec::new();
+    for path in traces {
+        records.extend(read_trace_records(path)?);
+    }
+    if records.is_empty() {
+        return Err(anyhow!("trace has no records to replay"));
+    }
+    let state = AppState::new("ipc://isolapurr-devd-replay");
+    {
+        let mut inner = state.inner.lock().await;
+        for device in replay_device_records(&records) {
+            tracing::info!("replaying {} from trace", device.id);
+            inner.devices.insert(device.id.clone(), device);
+        }
+        inner.trace.replay = Some(TraceReplay::new(&records));
+    }
+    serve_ipc_with_state(config, state).await
+}
@@ -0,0 +1,170 @@
+use super::*;
+
+fn record(method: &str, params: Value, response: Option<Value>) -> TraceRecord {
+    TraceRecord {
+        timestamp_unix_ms: 1_760_000_000_000,
+        device_id: "usb--dev-cu-usbmodem101".to_string(),
+        transport: TRACE_TRANSPORT_USB_SERIAL.to_string(),
+        target: "/dev/cu.usbmodem101".to_string(),
+        method: method.to_string(),
+        request: json!({"id": "rec", "method": method, "params": params}),
+        error: response
+            .is_none()
+            .then(|| "serial read timed out".to_string()),
+        response,
+        duration_ms: 12,
+    }
+}
+
+fn info_record() -> TraceRecord {
+    record(
+        methods::INFO,
+        json!({}),
+        Some(json!({
+            "id": "rec",
+            "ok": true,
+            "result": {
+                "device": {
+                    "device_id": "aabbcc001122",
+                    "firmware": {"name": "isolapurr-usb-hub", "version": "0.1.0"}
+                }
+            }
+        })),
+    )
+}
+
+#[test]
+fn trace_recorder_rotates_and_keeps_the_newest_files() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let mut recorder = TraceRecorder::open(temp.path()).expect("recorder should open");
+    for round in 0..TRACE_MAX_FILES + 2 {
+        recorder
+            .append(&record(
+                methods::PORTS_GET,
+                json!({"round": round}),
+                Some(json!({"ok": true})),
+            ))
+            .expect("append should pass");
+        recorder.written = TRACE_FILE_MAX_BYTES;
+    }
+
+    let files = trace_files(temp.path());
+    assert_eq!(files.len(), TRACE_MAX_FILES);
+    assert_eq!(files.last(), Some(&trace_file_path(temp.path(), 0)));
+    let newest = read_trace_records(&trace_file_path(temp.path(), 0)).expect("newest file");
+    assert_eq!(newest[0].request["params"]["round"], TRACE_MAX_FILES + 1);
+    let oldest = read_trace_records(&files[0]).expect("oldest file");
+    assert_eq!(oldest[0].request["params"]["round"], 2);
+}
+
+#[tokio::test]
+async fn trace_records_redact_and_capture_errors() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let state = AppState::new("ipc://test");
+    enable_trace_recording(&state, Some(temp.path())).await;
+
+    let request =
+        json!({"id": "1", "method": "wifi.set", "params": {"ssid": "bench", "psk": "secret"}});
+    record_trace(
+        &state,
+        "hub-1",
+        TRACE_TRANSPORT_USB_SERIAL,
+        "/dev/ttyACM0",
+        &request,
+        &Err(anyhow!("serial read timed out")),
+        Instant::now(),
+    )
+    .await;
+
+    let records = read_trace_records(&trace_file_path(temp.path(), 0)).expect("trace file");
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].method, "wifi.set");
+    assert_eq!(records[0].transport, "usb_serial");
+    assert_eq!(records[0].target, "/dev/ttyACM0");
+    assert_eq!(records[0].request["params"]["psk"], "<redacted>");
+    assert_eq!(records[0].response, None);
+    assert_eq!(records[0].error.as_deref(), Some("serial read timed out"));
+}
+
+#[test]
+fn trace_replay_matches_params_then_method_and_repeats_the_last() {
+    let mut replay = TraceReplay::new(&[
+        record(
+            methods::PORTS_GET,
+            json!({}),
+            Some(json!({"id": "rec", "result": 1})),
+        ),
+        record(
+            methods::PORTS_GET,
+            json!({}),
+            Some(json!({"id": "rec", "result": 2})),
+        ),
+        record("port.power", json!({"port": "port_a"}), None),
+    ]);
+    let device = "usb--dev-cu-usbmodem101";
+    let ports = json!({"id": "new", "method": methods::PORTS_GET, "params": {}});
+
+    let first = replay.next(device, &ports).expect("first response");
+    assert_eq!(first, json!({"id": "new", "result": 1}));
+    assert_eq!(replay.next(device, &ports).expect("second")["result"], 2);
+    assert_eq!(replay.next(device, &ports).expect("repeat")["result"], 2);
+
+    let other_params = json!({"id": "x", "method": methods::PORTS_GET, "params": {"tail": 1}});
+    assert!(replay.next(device, &other_params).is_ok());
+
+    let power = json!({"id": "p", "method": "port.power", "params": {"port": "port_a"}});
+    let err = replay
+        .next(device, &power)
+        .expect_err("recorded error replays");
+    assert!(err.to_string().contains("timed out"));
+
+    let unknown = json!({"id": "u", "method": "wifi.get", "params": {}});
+    assert!(replay.next(device, &unknown).is_err());
+    assert!(replay.next("other", &ports).is_err());
+}
+
+#[tokio::test]
+async fn trace_replay_serves_ipc_without_hardware() {
+    let records = vec![
+        info_record(),
+        record(
+            methods::PORTS_GET,
+            json!({}),
+            Some(json!({"id": "rec", "ok": true, "result": {"ports": []}})),
+        ),
+    ];
+    let state = AppState::new("ipc://test");
+    {
+        let mut inner = state.inner.lock().await;
+        for device in replay_device_records(&records) {
+            inner.devices.insert(device.id.clone(), device);
+        }
+        inner.trace.replay = Some(TraceReplay::new(&records));
+    }
+
+    let scanned = dispatch_ipc_request(&state, "devices.scan", json!({}))
+        .await
+        .expect("scan should list replay devices");
+    assert_eq!(scanned["devices"][0]["id"], "usb--dev-cu-usbmodem101");
+
+    let ports = dispatch_ipc_request(
+        &state,
+        "device.ports.get",
+        json!({"device_id": "usb--dev-cu-usbmodem101"}),
+    )
+    .await
+    .expect("ports should replay");
+    assert_eq!(ports["result"]["ports"], json!([]));
+}
+
+#[test]
+fn trace_reader_skips_a_torn_last_line_only() {
+    let temp = tempfile::tempdir().expect("temp dir");
+    let path = temp.path().join("trace.jsonl");
+    let line = serde_json::to_string(&info_record()).expect("encode");
+    fs::write(&path, format!("{line}\n{{\"timestampUnixMs\":1")).expect("write");
+    assert_eq!(read_trace_records(&path).expect("torn tail").len(), 1);
+
+    fs::write(&path, format!("{{\"broken\":true}}\n{line}\n")).expect("write");
+    assert!(read_trace_records(&path).is_err());
+}
@@ -22,11 +22,15 @@ export type PortTelemetry = {
   sample_uptime_ms: number;
 };
 
+export type PortDataMode = "connected" | "charge_only";
+
 export type PortState = {
   power_enabled: boolean;
   data_connected: boolean;
   replugging: boolean;
   busy: boolean;
+  // Backward-compat: older firmware omits the persisted data mode.
+  data_mode?: PortDataMode;
 };
 
 export type PortCapabilities = {
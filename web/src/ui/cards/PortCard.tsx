@@ -26,10 +26,12 @@ function PortStateSummary({
   powerEnabled,
   dataConnected,
   replugging,
+  chargeOnly,
 }: {
   powerEnabled: boolean;
   dataConnected: boolean;
   replugging: boolean;
+  chargeOnly: boolean;
 }) {
   const items = [
     {
@@ -41,7 +43,9 @@ function PortStateSummary({
         ? "Replugging"
         : dataConnected
           ? "Data linked"
-          : "Data off",
+          : chargeOnly
+            ? "Charge only"
+            : "Data off",
       active: dataConnected && !replugging,
     },
   ];
@@ -199,6 +203,7 @@ export function PortCard({
           powerEnabled={state.power_enabled}
           dataConnected={state.data_connected}
           replugging={state.replugging}
+          chargeOnly={state.data_mode === "charge_only"}
         />
       </div>
 